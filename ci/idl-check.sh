@@ -0,0 +1,66 @@
+#!/usr/bin/env bash
+
+# Checks that the IDL generated by `anchor build` covers every instruction,
+# event, error and PDA seed declared in the program source, so that clients
+# generated from the IDL can't silently drift from the program.
+
+set -e
+cd "$(dirname "$0")/.."
+
+program_src=programs/hyperplane/src
+idl=${1:-target/idl/hyperplane.json}
+
+if [[ ! -r $idl ]]; then
+  echo "Error: IDL not found at $idl, run 'anchor build' first"
+  exit 1
+fi
+
+snake_to_camel() {
+  sed -E 's/_([a-z0-9])/\U\1/g'
+}
+
+failed=
+
+check() {
+  local kind=$1
+  local expected=$2
+  local actual=$3
+  local missing
+  missing=$(comm -23 <(echo "$expected" | sort -u) <(echo "$actual" | sort -u))
+  if [[ -n $missing ]]; then
+    failed=1
+    echo "Missing $kind in $idl:"
+    echo "$missing" | sed 's/^/  - /'
+  fi
+}
+
+# instructions - every handler in the #[program] module
+expected_instructions=$(
+  sed -n '/^pub mod hyperplane {/,/^}/p' $program_src/lib.rs |
+    grep -oE '^    pub fn [a-z0-9_]+' |
+    awk '{print $3}' |
+    snake_to_camel
+)
+check instructions "$expected_instructions" "$(jq -r '.instructions[].name' $idl)"
+
+# events
+expected_events=$(grep -A2 '^#\[event\]' $program_src/event.rs | grep -oE '^pub struct [A-Za-z0-9]+' | awk '{print $3}')
+check events "$expected_events" "$(jq -r '(.events // [])[].name' $idl)"
+
+# errors
+expected_errors=$(
+  sed -n '/^pub enum SwapError {/,/^}/p' $program_src/error.rs |
+    grep -oE '^    [A-Z][A-Za-z0-9]+,' |
+    tr -d ' ,'
+)
+check errors "$expected_errors" "$(jq -r '(.errors // [])[].name' $idl)"
+
+# pda seeds - exported as #[constant]s
+expected_seeds=$(grep -oE '^pub const [A-Z_]+: &\[u8\]' $program_src/utils/seeds.rs | awk '{print $3}' | tr -d ':')
+check seeds "$expected_seeds" "$(jq -r '(.constants // [])[].name' $idl)"
+
+if [[ -n $failed ]]; then
+  exit 1
+fi
+
+echo "IDL $idl is consistent with the program source"
@@ -0,0 +1,74 @@
+//! Throughput of batch quoting, e.g. for a routing backend quoting every pool of a route graph
+//!
+//! Run with `cargo bench -p hyperplane-client --bench quote`, criterion reports the quotes per
+//! second of each batch.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hyperplane::{
+    curve::{base::SwapCurve, calculator::TradeDirection, fees::Fees},
+    model::CurveParameters,
+};
+use hyperplane_client::quote::{quote_many, QuoteRequest};
+
+const BATCH_SIZES: [usize; 2] = [1_000, 10_000];
+
+/// Pools of the same curve with spread out reserves, quoted for 0.1% of their source reserve
+fn requests<'a>(swap_curve: &'a SwapCurve, fees: &'a Fees, count: usize) -> Vec<QuoteRequest<'a>> {
+    (0..count as u64)
+        .map(|i| {
+            let source_vault_amount = 1_000_000_000 + i * 7_919_000;
+            let destination_vault_amount = 1_000_000_000 + i * 6_133_000;
+            QuoteRequest {
+                swap_curve,
+                fees,
+                source_vault_amount,
+                destination_vault_amount,
+                trade_direction: if i % 2 == 0 {
+                    TradeDirection::AtoB
+                } else {
+                    TradeDirection::BtoA
+                },
+                amount_in: source_vault_amount / 1_000,
+            }
+        })
+        .collect()
+}
+
+fn bench_quote_many(c: &mut Criterion) {
+    let fees = Fees {
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10_000,
+        owner_trade_fee_numerator: 5,
+        owner_trade_fee_denominator: 10_000,
+        ..Fees::default()
+    };
+    let curves = [
+        ("constant_product", CurveParameters::ConstantProduct),
+        (
+            "stable",
+            CurveParameters::Stable {
+                amp: 100,
+                token_a_decimals: 6,
+                token_b_decimals: 6,
+            },
+        ),
+    ];
+
+    let mut group = c.benchmark_group("quote_many");
+    for (name, curve_params) in curves {
+        let swap_curve = SwapCurve::new_from_params(curve_params).unwrap();
+        for batch_size in BATCH_SIZES {
+            let batch = requests(&swap_curve, &fees, batch_size);
+            assert!(quote_many(&batch).iter().all(Option::is_some));
+
+            group.throughput(Throughput::Elements(batch_size as u64));
+            group.bench_with_input(BenchmarkId::new(name, batch_size), &batch, |b, batch| {
+                b.iter(|| quote_many(black_box(batch)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_quote_many);
+criterion_main!(benches);
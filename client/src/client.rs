@@ -1,20 +1,38 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
 use anchor_client::{
     anchor_lang::{prelude::Pubkey, system_program::System, AccountDeserialize, Id},
     solana_sdk::{
+        commitment_config::CommitmentConfig,
+        program_pack::Pack,
         rent::Rent,
         signature::{Keypair, Signer},
         sysvar::SysvarId,
     },
 };
 use anchor_spl::token::TokenAccount;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use hyperplane::{
-    ix::{Initialize, UpdatePoolConfig},
-    state::SwapPool,
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator::{CurveCalculator, TradeDirection},
+        oracle_pegged::{OraclePeggedCalculator, OraclePrice},
+    },
+    ix,
+    ix::{amounts, Initialize, Swap, UpdatePoolConfig},
+    state::{
+        ConfigChange, ConstantPriceCurve, ConstantProductCurve, ConstantProductWithBoundsCurve,
+        EventMode, LockedLpPosition, OffsetCurve, OraclePeggedCurve, PeggedCurve,
+        PoolConfigHistory, StableCurve, SwapPool, SwapState, UpdatePoolConfigMode, WeightedCurve,
+    },
     utils::seeds::{pda, pda::InitPoolPdas},
-    InitialSupply,
+    CurveUserParameters, InitialSupply,
 };
 use orbit_link::{async_client::AsyncClient, OrbitLink};
+use spl_token::state::Mint;
 use tracing::info;
 
 use crate::send_tx;
@@ -22,6 +40,8 @@ use crate::send_tx;
 pub struct HyperplaneClient<T: AsyncClient, S: Signer> {
     pub client: OrbitLink<T, S>,
     pub config: Config,
+    /// Decimals of the mints fetched so far
+    mint_decimals: Mutex<HashMap<Pubkey, u8>>,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -52,7 +72,11 @@ where
 {
     #[tracing::instrument(skip(client))] //Skip client that does not impl Debug
     pub async fn new(client: OrbitLink<T, S>, config: Config) -> Result<Self> {
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            mint_decimals: Mutex::new(HashMap::new()),
+        })
     }
 
     pub async fn initialize_pool(
@@ -68,6 +92,8 @@ where
                     initial_supply_a,
                     initial_supply_b,
                 },
+            burn_owner_fees,
+            strict_token_program,
         }: Initialize,
     ) -> Result<Pubkey> {
         let pool_kp = Keypair::new();
@@ -164,6 +190,8 @@ where
                 initial_supply_b,
                 fees,
                 curve_parameters,
+                burn_owner_fees,
+                strict_token_program,
             },
         );
 
@@ -182,17 +210,322 @@ where
         pool: Pubkey,
         update: UpdatePoolConfig,
     ) -> Result<()> {
-        // let swap_pool: SwapPool = self.client.get_anchor_account(&pool).await?;
+        let swap_pool: SwapPool = self.client.get_anchor_account(&pool).await?;
+        let (config_history, _bump) =
+            pda::config_history_pda_program_id(&self.config.program_id, &pool);
+        let feature_set = update
+            .mode
+            .feature()
+            .map(|_| pda::feature_set_pda_program_id(&self.config.program_id).0);
+        let emits_status_event = update.mode == UpdatePoolConfigMode::Status
+            && swap_pool.event_mode() == EventMode::Cpi;
+        let mut update_ix = ix::update_pool_config(
+            &self.config.program_id,
+            &admin,
+            &pool,
+            &config_history,
+            &swap_pool.token_a_vault,
+            &swap_pool.token_b_vault,
+            &swap_pool.pool_token_mint,
+            feature_set.as_ref(),
+            update,
+        )?;
+        if emits_status_event {
+            // the `PoolStatusChanged` of the pool is emitted through its event mode
+            update_ix = ix::with_event_cpi_accounts(update_ix, &self.config.program_id);
+        }
+        let tx = self.client.tx_builder().add_ix(update_ix);
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    pub async fn initialize_config_history(&self, admin: Pubkey, pool: Pubkey) -> Result<()> {
+        let (config_history, _bump) =
+            pda::config_history_pda_program_id(&self.config.program_id, &pool);
         let tx = self.client.tx_builder().add_anchor_ix(
             &self.config.program_id,
-            hyperplane::accounts::UpdatePoolConfig { admin, pool },
-            hyperplane::instruction::UpdatePoolConfig::from(update),
+            hyperplane::accounts::InitializeConfigHistory {
+                admin,
+                pool,
+                config_history,
+                system_program: System::id(),
+            },
+            hyperplane::instruction::InitializeConfigHistory {},
         );
         send_tx!(self, tx, []);
 
         Ok(())
     }
 
+    /// Config changes of the pool still in its history, oldest first
+    pub async fn get_config_history(&self, pool: &Pubkey) -> Result<Vec<ConfigChange>> {
+        let (config_history, _bump) =
+            pda::config_history_pda_program_id(&self.config.program_id, pool);
+        let config_history: PoolConfigHistory =
+            self.client.get_anchor_account(&config_history).await?;
+        Ok(config_history.recent_changes())
+    }
+
+    /// Pool tokens locked by the owner, fails if the owner has no locked position in the pool
+    pub async fn get_locked_lp_position(
+        &self,
+        pool: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<LockedLpPosition> {
+        let locked = pda::locked_lp_position_pdas_program_id(&self.config.program_id, pool, owner);
+        self.client.get_anchor_account(&locked.position).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn swap(
+        &self,
+        user: Pubkey,
+        pool_pubkey: Pubkey,
+        pool: &SwapPool,
+        trade_direction: TradeDirection,
+        source_user_ata: Pubkey,
+        destination_user_ata: Pubkey,
+        Swap {
+            amount_in,
+            minimum_amount_out,
+            deadline,
+        }: Swap,
+    ) -> Result<()> {
+        let (source_mint, destination_mint, source_vault, destination_vault, source_fees_vault) =
+            match trade_direction {
+                TradeDirection::AtoB => (
+                    pool.token_a_mint,
+                    pool.token_b_mint,
+                    pool.token_a_vault,
+                    pool.token_b_vault,
+                    pool.token_a_fees_vault,
+                ),
+                TradeDirection::BtoA => (
+                    pool.token_b_mint,
+                    pool.token_a_mint,
+                    pool.token_b_vault,
+                    pool.token_a_vault,
+                    pool.token_b_fees_vault,
+                ),
+            };
+        let source_token_program = self.client.client.get_account(&source_mint).await?.owner;
+        let destination_token_program = self
+            .client
+            .client
+            .get_account(&destination_mint)
+            .await?
+            .owner;
+        let allowed_swap_taker = if pool.permissioned_swaps() {
+            let (allowed_swap_taker, _bump) = pda::allowed_swap_taker_pda_program_id(
+                &self.config.program_id,
+                &pool_pubkey,
+                &user,
+            );
+            Some(allowed_swap_taker)
+        } else {
+            None
+        };
+
+        let oracle = if pool.curve_type() == CurveType::OraclePegged {
+            let curve: OraclePeggedCurve = self.client.get_anchor_account(&pool.swap_curve).await?;
+            Some(curve.oracle)
+        } else {
+            None
+        };
+
+        let tx = self.client.tx_builder().add_anchor_ix(
+            &self.config.program_id,
+            hyperplane::accounts::Swap {
+                signer: user,
+                pool: pool_pubkey,
+                swap_curve: pool.swap_curve,
+                pool_authority: pool.pool_authority,
+                source_mint,
+                destination_mint,
+                source_vault,
+                destination_vault,
+                source_token_fees_vault: source_fees_vault,
+                source_user_ata,
+                destination_user_ata,
+                source_token_host_fees_account: None,
+                source_token_program,
+                destination_token_program,
+                allowed_swap_taker,
+                oracle,
+                destination_token_host_fees_account: None,
+            },
+            hyperplane::instruction::Swap {
+                amount_in,
+                minimum_amount_out,
+                deadline,
+            },
+        );
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    pub async fn get_swap_curve(&self, pool: &SwapPool) -> Result<SwapCurve> {
+        let curve_type = pool.curve_type();
+        let calculator: Arc<dyn CurveCalculator + Sync + Send> = match curve_type {
+            CurveType::ConstantProduct => Arc::new(
+                self.client
+                    .get_anchor_account::<ConstantProductCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::ConstantPrice => Arc::new(
+                self.client
+                    .get_anchor_account::<ConstantPriceCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Stable => Arc::new(
+                self.client
+                    .get_anchor_account::<StableCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Offset => Arc::new(
+                self.client
+                    .get_anchor_account::<OffsetCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Pegged => Arc::new(
+                self.client
+                    .get_anchor_account::<PeggedCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::ConstantProductWithBounds => Arc::new(
+                self.client
+                    .get_anchor_account::<ConstantProductWithBoundsCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Weighted => Arc::new(
+                self.client
+                    .get_anchor_account::<WeightedCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::OraclePegged => {
+                let curve: OraclePeggedCurve =
+                    self.client.get_anchor_account(&pool.swap_curve).await?;
+                let oracle = self.get_rpc().get_account(&curve.oracle).await?;
+                let slot = self
+                    .get_rpc()
+                    .get_slot_with_commitment(CommitmentConfig::confirmed())
+                    .await?;
+                let price = OraclePrice::from_account_data(&curve, &oracle.data, slot)?;
+                Arc::new(OraclePeggedCalculator::new(curve, Some(price)))
+            }
+        };
+        Ok(SwapCurve {
+            curve_type,
+            calculator,
+        })
+    }
+
+    /// Parameters of the curve of a pool, as given at initialization
+    pub async fn get_curve_user_parameters(&self, pool: &SwapPool) -> Result<CurveUserParameters> {
+        Ok(match pool.curve_type() {
+            CurveType::ConstantProduct => CurveUserParameters::ConstantProduct,
+            CurveType::ConstantPrice => {
+                let curve: ConstantPriceCurve =
+                    self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::ConstantPrice {
+                    token_b_price: curve.token_b_price,
+                    spread_bps: curve.spread_bps,
+                }
+            }
+            CurveType::Stable => {
+                let curve: StableCurve = self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::Stable { amp: curve.amp }
+            }
+            CurveType::Offset => {
+                let curve: OffsetCurve = self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::Offset {
+                    token_b_offset: curve.token_b_offset,
+                }
+            }
+            CurveType::Pegged => {
+                let curve: PeggedCurve = self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::Pegged {
+                    amp: curve.amp,
+                    token_b_peg: curve.token_b_peg,
+                }
+            }
+            CurveType::ConstantProductWithBounds => {
+                let curve: ConstantProductWithBoundsCurve =
+                    self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::ConstantProductWithBounds {
+                    min_price: curve.min_price,
+                    max_price: curve.max_price,
+                }
+            }
+            CurveType::Weighted => {
+                let curve: WeightedCurve = self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::Weighted {
+                    weight_a: curve.weight_a,
+                    weight_b: curve.weight_b,
+                }
+            }
+            CurveType::OraclePegged => {
+                let curve: OraclePeggedCurve =
+                    self.client.get_anchor_account(&pool.swap_curve).await?;
+                CurveUserParameters::OraclePegged {
+                    oracle: curve.oracle,
+                    oracle_type: curve.oracle_type,
+                    scope_price_index: curve.scope_price_index,
+                    max_age_slots: curve.max_age_slots,
+                    spread_bps: curve.spread_bps,
+                }
+            }
+        })
+    }
+
+    pub async fn get_token_account(&self, address: &Pubkey) -> Result<TokenAccount> {
+        let account = self.client.client.get_account(address).await?;
+        let mut data: &[u8] = &account.data;
+        Ok(TokenAccount::try_deserialize(&mut data)?)
+    }
+
+    /// Decimals of a spl-token or token-2022 mint, fetched once and cached
+    pub async fn get_mint_decimals(&self, mint: &Pubkey) -> Result<u8> {
+        if let Some(decimals) = self.mint_decimals.lock().unwrap().get(mint) {
+            return Ok(*decimals);
+        }
+        let account = self.client.client.get_account(mint).await?;
+        if account.data.len() < Mint::LEN {
+            bail!("Account {} is not a mint", mint);
+        }
+        let decimals = Mint::unpack_from_slice(&account.data[..Mint::LEN])?.decimals;
+        self.mint_decimals.lock().unwrap().insert(*mint, decimals);
+        Ok(decimals)
+    }
+
+    /// Supply of a spl-token or token-2022 mint
+    pub async fn get_mint_supply(&self, mint: &Pubkey) -> Result<u64> {
+        let account = self.client.client.get_account(mint).await?;
+        if account.data.len() < Mint::LEN {
+            bail!("Account {} is not a mint", mint);
+        }
+        Ok(Mint::unpack_from_slice(&account.data[..Mint::LEN])?.supply)
+    }
+
+    pub async fn ui_amount_to_native(&self, mint: &Pubkey, ui_amount: f64) -> Result<u64> {
+        let decimals = self.get_mint_decimals(mint).await?;
+        amounts::ui_amount_to_native(ui_amount, decimals).ok_or_else(|| {
+            anyhow!(
+                "Invalid amount {} for mint {} with {} decimals",
+                ui_amount,
+                mint,
+                decimals
+            )
+        })
+    }
+
+    pub async fn native_to_ui_amount(&self, mint: &Pubkey, amount: u64) -> Result<f64> {
+        let decimals = self.get_mint_decimals(mint).await?;
+        Ok(amounts::native_to_ui_amount(amount, decimals))
+    }
+
     /// Get an the rpc instance used by the KLendClient
     pub fn get_rpc(&self) -> &T {
         &self.client.client
@@ -1,29 +1,35 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 
 use anchor_client::{
     anchor_lang::prelude::Pubkey,
+    solana_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::{
+        commitment_config::CommitmentConfig,
         program_pack::Pack,
         signature::{Keypair, Signer},
     },
 };
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use hyperplane::{
-    curve::{base::CurveType, calculator::CurveCalculator},
-    ix::Initialize,
-    state::{
-        ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve, SwapPool,
-        UpdatePoolConfigMode,
-    },
+    curve::calculator::TradeDirection,
+    ix::{amounts, Initialize, Swap},
+    state::{PoolOperation, SwapPool, UpdatePoolConfigMode},
+    InitialSupply,
 };
 use orbit_link::async_client::AsyncClient;
 use spl_associated_token_account as ata;
 use spl_token::state::Mint;
-use tokio::{fs::File, io::AsyncWriteExt};
-use tracing::info;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+use tracing::{info, warn};
 
 use crate::{
-    client::HyperplaneClient, configs::PoolConfigValue, model::InitializePoolConfig, send_tx,
+    client::HyperplaneClient, configs::PoolConfigValue, fork, fork::AccountOverride, history,
+    localnet, localnet::LocalnetScenario, metrics, model::InitializePoolConfig,
+    model::VerifyPoolConfig, quote, recommend, recommend::PairType, send_tx, verify,
 };
 
 pub async fn create_ata<T: AsyncClient, S: Signer>(
@@ -60,10 +66,16 @@ pub async fn create_mint<T: AsyncClient, S: Signer>(
     hyperplane: &HyperplaneClient<T, S>,
     out: PathBuf,
     mint_authority: Pubkey,
-    initial_supply: Option<u64>,
+    initial_supply: Option<f64>,
 ) -> Result<()> {
     let mint = Keypair::new();
     let decimals = 6;
+    let initial_supply = initial_supply
+        .map(|supply| {
+            amounts::ui_amount_to_native(supply, decimals)
+                .ok_or_else(|| anyhow!("Invalid initial supply {}", supply))
+        })
+        .transpose()?;
 
     let mut builder = hyperplane
         .client
@@ -106,10 +118,11 @@ pub async fn create_mint<T: AsyncClient, S: Signer>(
                 ).unwrap()
             );
             info!(
-                "Minting {} tokens to ATA {} for owner {}.",
-                initial_supply.unwrap(),
-                mint_authority,
-                ata
+                "Minting {} tokens ({} native) to ATA {} for owner {}.",
+                amounts::native_to_ui_amount(n, decimals),
+                n,
+                ata,
+                mint_authority
             );
         }
     }
@@ -155,6 +168,8 @@ pub async fn initialize_pool<T: AsyncClient, S: Signer>(
                 fees: config.fees,
                 curve_parameters: config.curve,
                 initial_supply: config.initial_supply,
+                burn_owner_fees: config.burn_owner_fees,
+                strict_token_program: config.strict_token_program,
             },
         )
         .await?;
@@ -175,38 +190,485 @@ pub async fn update_pool<T: AsyncClient, S: Signer>(
     Ok(())
 }
 
-pub async fn print_pool<T: AsyncClient, S: Signer>(
+pub async fn initialize_config_history<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    admin: Pubkey,
+    pool: Pubkey,
+) -> Result<()> {
+    hyperplane.initialize_config_history(admin, pool).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn swap<T: AsyncClient, S: Signer>(
     hyperplane: &HyperplaneClient<T, S>,
+    user: Pubkey,
     pool_pubkey: Pubkey,
+    trade_direction: TradeDirection,
+    ui_amount_in: f64,
+    slippage_bps: u16,
+    max_slot_lag: u64,
+    source_user_ata: Option<Pubkey>,
+    destination_user_ata: Option<Pubkey>,
 ) -> Result<()> {
+    let quote_slot = hyperplane
+        .get_rpc()
+        .get_slot_with_commitment(CommitmentConfig::confirmed())
+        .await?;
     let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
-    let curve: Box<dyn CurveCalculator> = match CurveType::try_from(pool.curve_type).unwrap() {
-        CurveType::ConstantProduct => Box::new(
-            hyperplane
-                .client
-                .get_anchor_account::<ConstantProductCurve>(&pool.swap_curve)
-                .await?,
+    let swap_curve = hyperplane.get_swap_curve(&pool).await?;
+
+    let (source_mint, destination_mint, source_vault, destination_vault) = match trade_direction {
+        TradeDirection::AtoB => (
+            pool.token_a_mint,
+            pool.token_b_mint,
+            pool.token_a_vault,
+            pool.token_b_vault,
         ),
-        CurveType::ConstantPrice => Box::new(
-            hyperplane
-                .client
-                .get_anchor_account::<ConstantPriceCurve>(&pool.swap_curve)
-                .await?,
+        TradeDirection::BtoA => (
+            pool.token_b_mint,
+            pool.token_a_mint,
+            pool.token_b_vault,
+            pool.token_a_vault,
         ),
-        CurveType::Stable => Box::new(
-            hyperplane
-                .client
-                .get_anchor_account::<StableCurve>(&pool.swap_curve)
-                .await?,
+    };
+    let amount_in = hyperplane
+        .ui_amount_to_native(&source_mint, ui_amount_in)
+        .await?;
+    let source_user_ata =
+        source_user_ata.unwrap_or_else(|| ata::get_associated_token_address(&user, &source_mint));
+    let destination_user_ata = destination_user_ata
+        .unwrap_or_else(|| ata::get_associated_token_address(&user, &destination_mint));
+
+    let source_vault_amount = hyperplane.get_token_account(&source_vault).await?.amount;
+    let destination_vault_amount = hyperplane
+        .get_token_account(&destination_vault)
+        .await?
+        .amount;
+    let quoted_amount_out = quote::quote(&quote::QuoteRequest {
+        swap_curve: &swap_curve,
+        fees: &pool.swap_fees(quote_slot)?,
+        source_vault_amount,
+        destination_vault_amount,
+        trade_direction,
+        amount_in,
+    })?;
+    let minimum_amount_out = quote::minimum_amount_out(quoted_amount_out, slippage_bps);
+    info!(
+        "Quote at slot {}: amount_in={} ({}), quoted_amount_out={} ({}), minimum_amount_out={}, slippage_bps={}",
+        quote_slot,
+        ui_amount_in,
+        amount_in,
+        hyperplane
+            .native_to_ui_amount(&destination_mint, quoted_amount_out)
+            .await?,
+        quoted_amount_out,
+        minimum_amount_out,
+        slippage_bps
+    );
+
+    let destination_amount_before = hyperplane
+        .get_token_account(&destination_user_ata)
+        .await?
+        .amount;
+
+    // Don't send against a pool state that is too old, the recent blockhash of the transaction
+    // further bounds how long after the quote the swap can land
+    let current_slot = hyperplane
+        .get_rpc()
+        .get_slot_with_commitment(CommitmentConfig::confirmed())
+        .await?;
+    if current_slot > quote_slot.saturating_add(max_slot_lag) {
+        bail!(
+            "Quote from slot {} is stale at slot {}, max_slot_lag={}",
+            quote_slot,
+            current_slot,
+            max_slot_lag
+        );
+    }
+
+    hyperplane
+        .swap(
+            user,
+            pool_pubkey,
+            &pool,
+            trade_direction,
+            source_user_ata,
+            destination_user_ata,
+            Swap::new(amount_in, minimum_amount_out, None),
+        )
+        .await?;
+
+    if hyperplane.config.dry_run || hyperplane.config.multisig {
+        return Ok(());
+    }
+
+    let destination_amount_after = hyperplane
+        .get_token_account(&destination_user_ata)
+        .await?
+        .amount;
+    let realized_amount_out = destination_amount_after.saturating_sub(destination_amount_before);
+    info!(
+        "Swap realized: quoted_amount_out={}, realized_amount_out={}, realized_slippage_bps={}",
+        quoted_amount_out,
+        realized_amount_out,
+        quote::realized_slippage_bps(quoted_amount_out, realized_amount_out)
+    );
+
+    Ok(())
+}
+
+/// Quote a swap against the reserves of the pool at the end of `slot`
+///
+/// The reserves are read from the transaction history served by `archive_rpc`. The pool account
+/// itself has no history, so the fees and curve are the current ones.
+pub async fn simulate_at<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    archive_rpc: &RpcClient,
+    pool_pubkey: Pubkey,
+    slot: u64,
+    trade_direction: TradeDirection,
+    ui_amount_in: f64,
+) -> Result<()> {
+    let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
+    let swap_curve = hyperplane.get_swap_curve(&pool).await?;
+
+    let (source_mint, destination_mint, source_vault, destination_vault) = match trade_direction {
+        TradeDirection::AtoB => (
+            pool.token_a_mint,
+            pool.token_b_mint,
+            pool.token_a_vault,
+            pool.token_b_vault,
         ),
-        CurveType::Offset => Box::new(
-            hyperplane
-                .client
-                .get_anchor_account::<OffsetCurve>(&pool.swap_curve)
-                .await?,
+        TradeDirection::BtoA => (
+            pool.token_b_mint,
+            pool.token_a_mint,
+            pool.token_b_vault,
+            pool.token_a_vault,
+        ),
+    };
+    let amount_in = hyperplane
+        .ui_amount_to_native(&source_mint, ui_amount_in)
+        .await?;
+
+    match hyperplane.get_config_history(&pool_pubkey).await {
+        Ok(changes) => {
+            for change in changes.iter().filter(|change| change.slot > slot) {
+                warn!(
+                    "Config changed after slot {}: slot={} mode={} {} -> {}",
+                    slot, change.slot, change.mode, change.old_value, change.new_value
+                );
+            }
+        }
+        Err(e) => warn!(
+            "No config history for pool {}, the fees may have changed since slot {}: {}",
+            pool_pubkey, slot, e
         ),
+    }
+    if pool.dynamic_fees_enabled() {
+        warn!(
+            "Dynamic fees are enabled, the trade fee follows the volatility last recorded at slot {}",
+            pool.volatility_updated_slot
+        );
+    }
+
+    let source_vault_amount = history::token_balance_at(archive_rpc, &source_vault, slot).await?;
+    let destination_vault_amount =
+        history::token_balance_at(archive_rpc, &destination_vault, slot).await?;
+    let swap_result = swap_curve.swap(
+        u128::from(amount_in),
+        u128::from(source_vault_amount),
+        u128::from(destination_vault_amount),
+        trade_direction,
+        &pool.swap_fees(slot)?,
+    )?;
+    let amount_out = u64::try_from(swap_result.destination_amount_swapped)?;
+    info!(
+        "Quote at slot {}: source_vault_amount={}, destination_vault_amount={}, amount_in={} ({}), amount_out={} ({}), trade_fee={}, owner_fee={}",
+        slot,
+        source_vault_amount,
+        destination_vault_amount,
+        ui_amount_in,
+        amount_in,
+        hyperplane
+            .native_to_ui_amount(&destination_mint, amount_out)
+            .await?,
+        amount_out,
+        swap_result.trade_fee,
+        swap_result.owner_fee
+    );
+
+    Ok(())
+}
+
+pub async fn fork<T: AsyncClient>(
+    fork_rpc: &T,
+    program_id: Pubkey,
+    pool_pubkey: Pubkey,
+    overrides: Vec<AccountOverride>,
+    include_program: bool,
+    out_dir: PathBuf,
+) -> Result<()> {
+    let mut fixtures = fork::pool_fixtures(fork_rpc, pool_pubkey).await?;
+    if include_program {
+        fixtures.extend(fork::program_fixtures(fork_rpc, program_id).await?);
+    }
+    let validator_args = fork::write_fixtures(&fixtures, &overrides, &out_dir).await?;
+    let program_arg = if include_program {
+        String::new()
+    } else {
+        format!("--bpf-program {} target/deploy/hyperplane.so ", program_id)
+    };
+    info!(
+        "Start a local validator with the forked pool:\n\nsolana-test-validator --reset {}{}\n",
+        program_arg,
+        validator_args.join(" ")
+    );
+    Ok(())
+}
+
+pub async fn localnet<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    config: PathBuf,
+) -> Result<()> {
+    let scenario: LocalnetScenario =
+        serde_json::from_reader(File::open(config).await?.into_std().await)?;
+    let mut validator = localnet::start_validator(
+        &hyperplane.config.program_id,
+        &hyperplane.client.payer(),
+        &scenario,
+    )?;
+
+    let summary = match async {
+        localnet::wait_for_validator(hyperplane.get_rpc(), Duration::from_secs(60)).await?;
+        localnet::bootstrap(hyperplane, &scenario).await
+    }
+    .await
+    {
+        Ok(summary) => summary,
+        Err(e) => {
+            validator.kill().await?;
+            return Err(e);
+        }
     };
+
+    info!("\x1b[32mLocalnet ready\x1b[0m");
+    info!("Program: {}", hyperplane.config.program_id);
+    info!("Admin: {}", hyperplane.client.payer());
+    for (name, mint) in &summary.mints {
+        info!("Mint {}: {}", name, mint);
+    }
+    for (name, pool) in &summary.pools {
+        info!("Pool {}: {}", name, pool);
+    }
+    for (name, wallet, path) in &summary.wallets {
+        info!("Wallet {}: {} ({})", name, wallet, path.display());
+    }
+    info!("Validator running, Ctrl-C to stop");
+
+    validator.wait().await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn recommend<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pair_type: PairType,
+    target_depth: f64,
+    liquidity: f64,
+    max_slippage_bps: u16,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    price: f64,
+    out: PathBuf,
+) -> Result<()> {
+    if pair_type != PairType::Volatile && price != 1.0 {
+        bail!("Stable and correlated pairs are recommended at a 1:1 price");
+    }
+    let recommendation =
+        recommend::recommend(pair_type, target_depth, liquidity, max_slippage_bps)?;
+    if recommendation.meets_target {
+        info!(
+            "Trading {} tokens moves the price by {} bps with {:?}",
+            target_depth, recommendation.price_impact_bps, recommendation.curve
+        );
+    } else {
+        info!(
+            "\x1b[33mTarget not met\x1b[0m: trading {} tokens moves the price by {} bps with {:?}, max {} bps",
+            target_depth, recommendation.price_impact_bps, recommendation.curve, max_slippage_bps
+        );
+        if let Some(min_liquidity) = recommendation.min_liquidity {
+            info!("At least {} tokens per side are needed", min_liquidity);
+        }
+    }
+
+    let config = InitializePoolConfig {
+        token_a_mint: token_a_mint.to_string(),
+        token_b_mint: token_b_mint.to_string(),
+        curve: recommendation.curve,
+        fees: recommendation.fees,
+        initial_supply: InitialSupply {
+            initial_supply_a: hyperplane
+                .ui_amount_to_native(&token_a_mint, liquidity)
+                .await?,
+            initial_supply_b: hyperplane
+                .ui_amount_to_native(&token_b_mint, liquidity * price)
+                .await?,
+        },
+        burn_owner_fees: false,
+        strict_token_program: false,
+    };
+    let mut file = File::create(&out).await?;
+    file.write_all(serde_json::to_string_pretty(&config)?.as_bytes())
+        .await?;
+    info!("Wrote pool config to {}", out.to_string_lossy());
+
+    Ok(())
+}
+
+pub async fn print_pool<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool_pubkey: Pubkey,
+    lock_owners: Vec<Pubkey>,
+) -> Result<()> {
+    let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
+    let curve = hyperplane.get_swap_curve(&pool).await?;
     info!("\x1b[32mPool {}:\x1b\n\n{:#?}\n\n", pool_pubkey, pool);
-    info!("\x1b[32mCurve {}:\x1b\n\n{:#?}\n\n", pool.swap_curve, curve);
+    if pool.pending_admin != Pubkey::default() {
+        info!(
+            "Pending admin {} proposed by admin {}, not accepted yet",
+            pool.pending_admin, pool.admin
+        );
+    }
+    let paused: Vec<PoolOperation> = [
+        PoolOperation::Swaps,
+        PoolOperation::Deposits,
+        PoolOperation::SingleSided,
+        PoolOperation::FeeWithdrawals,
+    ]
+    .into_iter()
+    .filter(|operation| pool.is_paused(*operation))
+    .collect();
+    if !paused.is_empty() {
+        info!("Paused operations: {:?}", paused);
+    }
+    info!(
+        "\x1b[32mCurve {}:\x1b\n\n{:#?}\n\n",
+        pool.swap_curve, curve.calculator
+    );
+    if !lock_owners.is_empty() {
+        let current_slot = hyperplane
+            .get_rpc()
+            .get_slot_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        for owner in lock_owners {
+            match hyperplane
+                .get_locked_lp_position(&pool_pubkey, &owner)
+                .await
+            {
+                Ok(position) => info!(
+                    "Locked by {}: pool_token_amount={} unlock_slot={} remaining_slots={}",
+                    owner,
+                    position.pool_token_amount,
+                    position.unlock_slot,
+                    position.remaining_slots(current_slot)
+                ),
+                Err(e) => info!("No pool tokens locked by {}: {}", owner, e),
+            }
+        }
+    }
     Ok(())
 }
+
+/// Compare a pool with a desired state config file, failing if any field differs
+pub async fn verify_pool<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool_pubkey: Pubkey,
+    config: PathBuf,
+) -> Result<()> {
+    let config: VerifyPoolConfig =
+        serde_json::from_reader(File::open(config).await?.into_std().await)?;
+    let deployed = verify::get_deployed_pool(hyperplane, pool_pubkey).await?;
+    let checks = verify::check_pool(&hyperplane.config.program_id, &config, &deployed)?;
+
+    for check in &checks {
+        if check.passed() {
+            info!("\x1b[32mPASS\x1b[0m {}: {}", check.field, check.actual);
+        } else {
+            info!(
+                "\x1b[31mFAIL\x1b[0m {}: expected {}, found {}",
+                check.field, check.expected, check.actual
+            );
+        }
+    }
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    if failed > 0 {
+        bail!(
+            "Pool {} differs from its config in {} of {} checks",
+            pool_pubkey,
+            failed,
+            checks.len()
+        );
+    }
+    info!("Pool {} matches its config", pool_pubkey);
+    Ok(())
+}
+
+pub async fn print_config_history<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool_pubkey: Pubkey,
+) -> Result<()> {
+    let changes = hyperplane.get_config_history(&pool_pubkey).await?;
+    info!("\x1b[32mConfig history of pool {}:\x1b\n", pool_pubkey);
+    for change in changes {
+        let mode = UpdatePoolConfigMode::try_from(u16::try_from(change.mode)?)
+            .map_err(|_| anyhow!("Unknown config mode {}", change.mode))?;
+        info!(
+            "slot={} authority={} mode={:?} {} -> {}",
+            change.slot, change.authority, mode, change.old_value, change.new_value
+        );
+    }
+    Ok(())
+}
+
+/// Serve the metrics of the pools to Prometheus scrapes, fetched fresh on every request
+pub async fn metrics<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pools: Vec<Pubkey>,
+    listen: SocketAddr,
+) -> Result<()> {
+    let listener = TcpListener::bind(listen).await?;
+    info!(
+        "Serving metrics of {} pools on http://{}/metrics",
+        pools.len(),
+        listen
+    );
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        // the request is not parsed, read it so the client does not see a reset connection
+        let mut request = [0u8; 1024];
+        let _ = stream.read(&mut request).await;
+
+        let mut pools_metrics = Vec::with_capacity(pools.len());
+        let mut error = None;
+        for pool in &pools {
+            match metrics::get_pool_metrics(hyperplane, *pool).await {
+                Ok(pool_metrics) => pools_metrics.push(pool_metrics),
+                Err(e) => {
+                    error = Some(format!("Failed to fetch metrics of pool {}: {}", pool, e));
+                    break;
+                }
+            }
+        }
+        let response = match error {
+            None => metrics::http_response("200 OK", &metrics::render(&pools_metrics)),
+            Some(error) => {
+                warn!("{}", error);
+                metrics::http_response("500 Internal Server Error", &error)
+            }
+        };
+        if let Err(e) = stream.write_all(response.as_bytes()).await {
+            warn!("Failed to send metrics to {}: {}", peer, e);
+        }
+    }
+}
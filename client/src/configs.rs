@@ -1,20 +1,117 @@
-use hyperplane::state::{UpdatePoolConfigMode, UpdatePoolConfigValue};
+use std::str::FromStr;
+
+use hyperplane::{
+    ix::config_value,
+    state::{EventMode, PoolOperation, UpdatePoolConfigMode, UpdatePoolConfigValue},
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PoolConfigValue {
     WithdrawalsOnly(bool),
+    PermissionedSwaps(bool),
+    MinPrice(u64),
+    MaxPrice(u64),
+    PreventSelfTrades(bool),
+    QuietLogs(bool),
+    EventMode(EventMode),
+    PermissionedDeposits(bool),
+    DynamicFeeNumerator(u64),
+    MaxDynamicTradeFeeNumerator(u64),
+    /// Bitmask of the paused operations, indexed by `PoolOperation`
+    Status(u64),
+    BurnOwnerFees(bool),
+    MaxTradeSizeBps(u64),
+    MaxPriceImpactBps(u64),
+    MaxSlotOutflowBps(u64),
+    FlashFeeBps(u64),
+    ProtocolFeeBps(u64),
+    HostFeeInDestinationToken(bool),
+    MaxSingleSidedWithdrawImbalanceBps(u64),
 }
 
 impl PoolConfigValue {
     pub fn new(mode: UpdatePoolConfigMode, value: UpdatePoolConfigValue) -> Self {
-        #[allow(unreachable_patterns)] // remove when more modes + values are added
         match (mode, value) {
             (UpdatePoolConfigMode::WithdrawalsOnly, UpdatePoolConfigValue::Bool(val)) => {
                 PoolConfigValue::WithdrawalsOnly(val)
             }
+            (UpdatePoolConfigMode::PermissionedSwaps, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::PermissionedSwaps(val)
+            }
+            (UpdatePoolConfigMode::MinPrice, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::MinPrice(val)
+            }
+            (UpdatePoolConfigMode::MaxPrice, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::MaxPrice(val)
+            }
+            (UpdatePoolConfigMode::PreventSelfTrades, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::PreventSelfTrades(val)
+            }
+            (UpdatePoolConfigMode::QuietLogs, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::QuietLogs(val)
+            }
+            (UpdatePoolConfigMode::EventMode, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::EventMode(EventMode::try_from(val).unwrap())
+            }
+            (UpdatePoolConfigMode::PermissionedDeposits, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::PermissionedDeposits(val)
+            }
+            (UpdatePoolConfigMode::DynamicFeeNumerator, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::DynamicFeeNumerator(val)
+            }
+            (
+                UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator,
+                UpdatePoolConfigValue::U64(val),
+            ) => PoolConfigValue::MaxDynamicTradeFeeNumerator(val),
+            (UpdatePoolConfigMode::Status, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::Status(val)
+            }
+            (UpdatePoolConfigMode::BurnOwnerFees, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::BurnOwnerFees(val)
+            }
+            (UpdatePoolConfigMode::MaxTradeSizeBps, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::MaxTradeSizeBps(val)
+            }
+            (UpdatePoolConfigMode::MaxPriceImpactBps, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::MaxPriceImpactBps(val)
+            }
+            (UpdatePoolConfigMode::MaxSlotOutflowBps, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::MaxSlotOutflowBps(val)
+            }
+            (UpdatePoolConfigMode::FlashFeeBps, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::FlashFeeBps(val)
+            }
+            (UpdatePoolConfigMode::ProtocolFeeBps, UpdatePoolConfigValue::U64(val)) => {
+                PoolConfigValue::ProtocolFeeBps(val)
+            }
+            (UpdatePoolConfigMode::HostFeeInDestinationToken, UpdatePoolConfigValue::Bool(val)) => {
+                PoolConfigValue::HostFeeInDestinationToken(val)
+            }
+            (
+                UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
+                UpdatePoolConfigValue::U64(val),
+            ) => PoolConfigValue::MaxSingleSidedWithdrawImbalanceBps(val),
             (
                 // explicitly match all other cases to catch new modes at compile time
-                UpdatePoolConfigMode::WithdrawalsOnly,
+                UpdatePoolConfigMode::WithdrawalsOnly
+                | UpdatePoolConfigMode::PermissionedSwaps
+                | UpdatePoolConfigMode::MinPrice
+                | UpdatePoolConfigMode::MaxPrice
+                | UpdatePoolConfigMode::PreventSelfTrades
+                | UpdatePoolConfigMode::QuietLogs
+                | UpdatePoolConfigMode::EventMode
+                | UpdatePoolConfigMode::PermissionedDeposits
+                | UpdatePoolConfigMode::DynamicFeeNumerator
+                | UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator
+                | UpdatePoolConfigMode::Status
+                | UpdatePoolConfigMode::BurnOwnerFees
+                | UpdatePoolConfigMode::MaxTradeSizeBps
+                | UpdatePoolConfigMode::MaxPriceImpactBps
+                | UpdatePoolConfigMode::MaxSlotOutflowBps
+                | UpdatePoolConfigMode::FlashFeeBps
+                | UpdatePoolConfigMode::ProtocolFeeBps
+                | UpdatePoolConfigMode::HostFeeInDestinationToken
+                | UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
                 _,
             ) => {
                 panic!("Invalid value for update lending market mode: {mode:?}");
@@ -24,9 +121,41 @@ impl PoolConfigValue {
 
     pub fn new_from_str(mode: UpdatePoolConfigMode, value: String) -> PoolConfigValue {
         let parsed_value = match (mode, value) {
-            (UpdatePoolConfigMode::WithdrawalsOnly, val) => {
-                UpdatePoolConfigValue::Bool(val.parse::<bool>().unwrap())
+            (
+                UpdatePoolConfigMode::WithdrawalsOnly
+                | UpdatePoolConfigMode::PermissionedSwaps
+                | UpdatePoolConfigMode::PreventSelfTrades
+                | UpdatePoolConfigMode::QuietLogs
+                | UpdatePoolConfigMode::PermissionedDeposits
+                | UpdatePoolConfigMode::BurnOwnerFees
+                | UpdatePoolConfigMode::HostFeeInDestinationToken,
+                val,
+            ) => UpdatePoolConfigValue::Bool(val.parse::<bool>().unwrap()),
+            (
+                UpdatePoolConfigMode::MinPrice
+                | UpdatePoolConfigMode::MaxPrice
+                | UpdatePoolConfigMode::DynamicFeeNumerator
+                | UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator
+                | UpdatePoolConfigMode::MaxTradeSizeBps
+                | UpdatePoolConfigMode::MaxPriceImpactBps
+                | UpdatePoolConfigMode::MaxSlotOutflowBps
+                | UpdatePoolConfigMode::FlashFeeBps
+                | UpdatePoolConfigMode::ProtocolFeeBps
+                | UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
+                val,
+            ) => UpdatePoolConfigValue::U64(val.parse::<u64>().unwrap()),
+            (UpdatePoolConfigMode::EventMode, val) => {
+                UpdatePoolConfigValue::U64(EventMode::from_str(&val).unwrap() as u64)
             }
+            // comma separated paused operations, e.g. "Swaps,Deposits", empty to resume all
+            (UpdatePoolConfigMode::Status, val) => UpdatePoolConfigValue::U64(
+                val.split(',')
+                    .map(str::trim)
+                    .filter(|operation| !operation.is_empty())
+                    .fold(0, |status, operation| {
+                        status | PoolOperation::from_str(operation).unwrap().flag()
+                    }),
+            ),
         };
         PoolConfigValue::new(mode, parsed_value)
     }
@@ -37,8 +166,90 @@ impl From<PoolConfigValue> for hyperplane::instruction::UpdatePoolConfig {
         match value {
             PoolConfigValue::WithdrawalsOnly(val) => hyperplane::instruction::UpdatePoolConfig {
                 mode: UpdatePoolConfigMode::WithdrawalsOnly as u16,
-                value: UpdatePoolConfigValue::Bool(val).to_bytes(),
+                value: config_value::withdrawals_only(val),
+            },
+            PoolConfigValue::PermissionedSwaps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::PermissionedSwaps as u16,
+                value: config_value::permissioned_swaps(val),
+            },
+            PoolConfigValue::MinPrice(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::MinPrice as u16,
+                value: config_value::min_price(val),
+            },
+            PoolConfigValue::MaxPrice(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::MaxPrice as u16,
+                value: config_value::max_price(val),
+            },
+            PoolConfigValue::PreventSelfTrades(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::PreventSelfTrades as u16,
+                value: config_value::prevent_self_trades(val),
+            },
+            PoolConfigValue::QuietLogs(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::QuietLogs as u16,
+                value: config_value::quiet_logs(val),
+            },
+            PoolConfigValue::EventMode(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::EventMode as u16,
+                value: config_value::event_mode(val),
+            },
+            PoolConfigValue::PermissionedDeposits(val) => {
+                hyperplane::instruction::UpdatePoolConfig {
+                    mode: UpdatePoolConfigMode::PermissionedDeposits as u16,
+                    value: config_value::permissioned_deposits(val),
+                }
+            }
+            PoolConfigValue::DynamicFeeNumerator(val) => {
+                hyperplane::instruction::UpdatePoolConfig {
+                    mode: UpdatePoolConfigMode::DynamicFeeNumerator as u16,
+                    value: config_value::dynamic_fee_numerator(val),
+                }
+            }
+            PoolConfigValue::MaxDynamicTradeFeeNumerator(val) => {
+                hyperplane::instruction::UpdatePoolConfig {
+                    mode: UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator as u16,
+                    value: config_value::max_dynamic_trade_fee_numerator(val),
+                }
+            }
+            PoolConfigValue::Status(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::Status as u16,
+                value: config_value::status(val),
+            },
+            PoolConfigValue::BurnOwnerFees(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::BurnOwnerFees as u16,
+                value: config_value::burn_owner_fees(val),
+            },
+            PoolConfigValue::MaxTradeSizeBps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::MaxTradeSizeBps as u16,
+                value: config_value::max_trade_size_bps(val),
+            },
+            PoolConfigValue::MaxPriceImpactBps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::MaxPriceImpactBps as u16,
+                value: config_value::max_price_impact_bps(val),
+            },
+            PoolConfigValue::MaxSlotOutflowBps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::MaxSlotOutflowBps as u16,
+                value: config_value::max_slot_outflow_bps(val),
+            },
+            PoolConfigValue::FlashFeeBps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::FlashFeeBps as u16,
+                value: config_value::flash_fee_bps(val),
             },
+            PoolConfigValue::ProtocolFeeBps(val) => hyperplane::instruction::UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::ProtocolFeeBps as u16,
+                value: config_value::protocol_fee_bps(val),
+            },
+            PoolConfigValue::HostFeeInDestinationToken(val) => {
+                hyperplane::instruction::UpdatePoolConfig {
+                    mode: UpdatePoolConfigMode::HostFeeInDestinationToken as u16,
+                    value: config_value::host_fee_in_destination_token(val),
+                }
+            }
+            PoolConfigValue::MaxSingleSidedWithdrawImbalanceBps(val) => {
+                hyperplane::instruction::UpdatePoolConfig {
+                    mode: UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps as u16,
+                    value: config_value::max_single_sided_withdraw_imbalance_bps(val),
+                }
+            }
         }
     }
 }
@@ -50,6 +261,84 @@ impl From<PoolConfigValue> for hyperplane::ix::UpdatePoolConfig {
                 UpdatePoolConfigMode::WithdrawalsOnly,
                 UpdatePoolConfigValue::Bool(val),
             ),
+            PoolConfigValue::PermissionedSwaps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::PermissionedSwaps,
+                UpdatePoolConfigValue::Bool(val),
+            ),
+            PoolConfigValue::MinPrice(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MinPrice,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::MaxPrice(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MaxPrice,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::PreventSelfTrades(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::PreventSelfTrades,
+                UpdatePoolConfigValue::Bool(val),
+            ),
+            PoolConfigValue::QuietLogs(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::QuietLogs,
+                UpdatePoolConfigValue::Bool(val),
+            ),
+            PoolConfigValue::EventMode(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::EventMode,
+                UpdatePoolConfigValue::U64(val as u64),
+            ),
+            PoolConfigValue::PermissionedDeposits(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::PermissionedDeposits,
+                UpdatePoolConfigValue::Bool(val),
+            ),
+            PoolConfigValue::DynamicFeeNumerator(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::DynamicFeeNumerator,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::MaxDynamicTradeFeeNumerator(val) => {
+                hyperplane::ix::UpdatePoolConfig::new(
+                    UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator,
+                    UpdatePoolConfigValue::U64(val),
+                )
+            }
+            PoolConfigValue::Status(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::Status,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::BurnOwnerFees(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::BurnOwnerFees,
+                UpdatePoolConfigValue::Bool(val),
+            ),
+            PoolConfigValue::MaxTradeSizeBps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MaxTradeSizeBps,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::MaxPriceImpactBps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MaxPriceImpactBps,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::MaxSlotOutflowBps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MaxSlotOutflowBps,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::FlashFeeBps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::FlashFeeBps,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::ProtocolFeeBps(val) => hyperplane::ix::UpdatePoolConfig::new(
+                UpdatePoolConfigMode::ProtocolFeeBps,
+                UpdatePoolConfigValue::U64(val),
+            ),
+            PoolConfigValue::HostFeeInDestinationToken(val) => {
+                hyperplane::ix::UpdatePoolConfig::new(
+                    UpdatePoolConfigMode::HostFeeInDestinationToken,
+                    UpdatePoolConfigValue::Bool(val),
+                )
+            }
+            PoolConfigValue::MaxSingleSidedWithdrawImbalanceBps(val) => {
+                hyperplane::ix::UpdatePoolConfig::new(
+                    UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
+                    UpdatePoolConfigValue::U64(val),
+                )
+            }
         }
     }
 }
@@ -69,6 +358,29 @@ mod test {
         assert_eq!(config_val, PoolConfigValue::WithdrawalsOnly(true));
     }
 
+    #[test]
+    pub fn test_new_market_config_permissioned_swaps() {
+        let config_val = PoolConfigValue::new_from_str(
+            UpdatePoolConfigMode::PermissionedSwaps,
+            "false".to_string(),
+        );
+        assert_eq!(config_val, PoolConfigValue::PermissionedSwaps(false));
+    }
+
+    #[test]
+    pub fn test_new_market_config_max_price() {
+        let config_val =
+            PoolConfigValue::new_from_str(UpdatePoolConfigMode::MaxPrice, "1050000000".to_string());
+        assert_eq!(config_val, PoolConfigValue::MaxPrice(1_050_000_000));
+    }
+
+    #[test]
+    pub fn test_new_market_config_event_mode() {
+        let config_val =
+            PoolConfigValue::new_from_str(UpdatePoolConfigMode::EventMode, "Cpi".to_string());
+        assert_eq!(config_val, PoolConfigValue::EventMode(EventMode::Cpi));
+    }
+
     #[test]
     #[should_panic]
     pub fn test_new_market_config_unparseable_bool() {
@@ -0,0 +1,93 @@
+//! Decoding of the pool events from each channel of `EventMode`
+
+use anchor_client::anchor_lang::{AnchorDeserialize, Discriminator, Event};
+use hyperplane::instruction::EmitEvent;
+
+const PROGRAM_DATA_LOG: &str = "Program data: ";
+
+/// Decode an event from a "Program data" log line, emitted by pools in `EventMode::Log`
+pub fn from_log<E: Event + AnchorDeserialize>(log: &str) -> Option<E> {
+    let encoded = log.strip_prefix(PROGRAM_DATA_LOG)?;
+    from_event_data(&base64::decode(encoded).ok()?)
+}
+
+/// Decode an event from the return data of the transaction, set by every pool instruction
+/// whatever the event mode of the pool
+pub fn from_return_data<E: Event + AnchorDeserialize>(return_data: &[u8]) -> Option<E> {
+    E::deserialize(&mut &return_data[..]).ok()
+}
+
+/// Decode an event from the data of an inner `emit_event` instruction, invoked by pools in
+/// `EventMode::Cpi`
+pub fn from_cpi_data<E: Event + AnchorDeserialize>(ix_data: &[u8]) -> Option<E> {
+    let data = ix_data.strip_prefix(&EmitEvent::discriminator())?;
+    let emit_event = EmitEvent::deserialize(&mut &data[..]).ok()?;
+    from_event_data(&emit_event.event)
+}
+
+/// Decode the discriminator prefixed event data of the log and CPI channels
+fn from_event_data<E: Event + AnchorDeserialize>(data: &[u8]) -> Option<E> {
+    let data = data.strip_prefix(&E::discriminator())?;
+    E::deserialize(&mut &data[..]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::anchor_lang::{AnchorSerialize, InstructionData};
+    use hyperplane::event;
+
+    use super::*;
+
+    fn swap_event() -> event::Swap {
+        event::Swap {
+            token_in_amount: 1_000,
+            token_out_amount: 990,
+            total_fees: 3,
+            owner_fees_burned: 0,
+            self_trade: false,
+            host_fee: 1,
+            host_fee_recipient: None,
+            trade_fee: 2,
+            trade_fee_numerator: 25,
+            protocol_fee: 0,
+            host_fee_in_destination_token: false,
+            token_a_reserve_after: 101_000,
+            token_b_reserve_after: 99_010,
+        }
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let log = format!(
+            "{}{}",
+            PROGRAM_DATA_LOG,
+            base64::encode(swap_event().data())
+        );
+        assert_eq!(from_log::<event::Swap>(&log), Some(swap_event()));
+        assert_eq!(from_log::<event::Deposit>(&log), None);
+        assert_eq!(from_log::<event::Swap>("Program log: Swap"), None);
+    }
+
+    #[test]
+    fn test_decode_return_data() {
+        let return_data = swap_event().try_to_vec().unwrap();
+        assert_eq!(
+            from_return_data::<event::Swap>(&return_data),
+            Some(swap_event())
+        );
+    }
+
+    #[test]
+    fn test_decode_cpi_data() {
+        let ix_data = EmitEvent {
+            event: swap_event().data(),
+        }
+        .data();
+        assert_eq!(from_cpi_data::<event::Swap>(&ix_data), Some(swap_event()));
+        assert_eq!(from_cpi_data::<event::Withdraw>(&ix_data), None);
+        assert_eq!(
+            from_cpi_data::<event::Swap>(&swap_event().try_to_vec().unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Dump the accounts of a pool from a remote cluster so it can be reproduced on a local validator
+//!
+//! Accounts are written in the `solana account --output json` format consumed by
+//! `solana-test-validator --account <pubkey> <file>`. Overrides replace the dumped state of an
+//! account, or add an account, e.g. a pool with the admin swapped for a local keypair.
+//!
+//! [`pool_fixtures`] and [`program_fixtures`] snapshot the accounts in memory for integrators
+//! building their own test setups, [`write_fixtures`] writes them out.
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anchor_client::{
+    anchor_lang::{prelude::Pubkey, AccountDeserialize},
+    solana_sdk::{account::Account, bpf_loader_upgradeable},
+};
+use anyhow::{anyhow, Result};
+use hyperplane::state::SwapPool;
+use orbit_link::async_client::AsyncClient;
+use serde_json::json;
+use tokio::fs;
+use tracing::info;
+
+/// `<pubkey>=<file.json>` replacement for an account of the fork
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountOverride {
+    pub pubkey: Pubkey,
+    pub path: PathBuf,
+}
+
+impl FromStr for AccountOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (pubkey, path) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected <pubkey>=<file.json>, got {}", s))?;
+        Ok(Self {
+            pubkey: Pubkey::from_str(pubkey)?,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+/// Snapshot of an account, as loaded by `solana-test-validator --account`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountFixture {
+    pub pubkey: Pubkey,
+    pub account: Account,
+}
+
+impl AccountFixture {
+    /// Same layout as `solana account --output json`
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&json!({
+            "pubkey": self.pubkey.to_string(),
+            "account": {
+                "lamports": self.account.lamports,
+                "data": [base64::encode(&self.account.data), "base64"],
+                "owner": self.account.owner.to_string(),
+                "executable": self.account.executable,
+                "rentEpoch": self.account.rent_epoch,
+            },
+        }))?)
+    }
+}
+
+/// Fetch the pool and the accounts it references: curve, mints, vaults and fees vaults
+pub async fn pool_fixtures<T: AsyncClient>(
+    rpc: &T,
+    pool_pubkey: Pubkey,
+) -> Result<Vec<AccountFixture>> {
+    let pool_account = rpc.get_account(&pool_pubkey).await?;
+    let pool = SwapPool::try_deserialize(&mut pool_account.data.as_slice())?;
+
+    let mut fixtures = vec![AccountFixture {
+        pubkey: pool_pubkey,
+        account: pool_account,
+    }];
+    for pubkey in pool_addresses(pool_pubkey, &pool).into_iter().skip(1) {
+        fixtures.push(AccountFixture {
+            pubkey,
+            account: rpc.get_account(&pubkey).await?,
+        });
+    }
+    Ok(fixtures)
+}
+
+/// Fetch the program and its program data account, to run the deployed version of the program
+/// instead of a local build
+pub async fn program_fixtures<T: AsyncClient>(
+    rpc: &T,
+    program_id: Pubkey,
+) -> Result<Vec<AccountFixture>> {
+    let program_data = bpf_loader_upgradeable::get_program_data_address(&program_id);
+    Ok(vec![
+        AccountFixture {
+            pubkey: program_id,
+            account: rpc.get_account(&program_id).await?,
+        },
+        AccountFixture {
+            pubkey: program_data,
+            account: rpc.get_account(&program_data).await?,
+        },
+    ])
+}
+
+/// Write one `<pubkey>.json` file per fixture to `out_dir`, applying the overrides
+///
+/// Returns the `solana-test-validator` arguments loading the written accounts.
+pub async fn write_fixtures(
+    fixtures: &[AccountFixture],
+    overrides: &[AccountOverride],
+    out_dir: &Path,
+) -> Result<Vec<String>> {
+    fs::create_dir_all(out_dir).await?;
+    let mut validator_args = vec![];
+    for fixture in fixtures {
+        let path = out_dir.join(format!("{}.json", fixture.pubkey));
+        if let Some(account_override) = overrides.iter().find(|o| o.pubkey == fixture.pubkey) {
+            info!(
+                "Overriding {} with {:?}",
+                fixture.pubkey, account_override.path
+            );
+            fs::copy(&account_override.path, &path).await?;
+        } else {
+            fs::write(&path, fixture.to_json()?).await?;
+        }
+        validator_args.push(format!("--account {} {}", fixture.pubkey, path.display()));
+    }
+    for account_override in overrides
+        .iter()
+        .filter(|o| !fixtures.iter().any(|f| f.pubkey == o.pubkey))
+    {
+        info!(
+            "Adding {} from {:?}",
+            account_override.pubkey, account_override.path
+        );
+        validator_args.push(format!(
+            "--account {} {}",
+            account_override.pubkey,
+            account_override.path.display()
+        ));
+    }
+    Ok(validator_args)
+}
+
+fn pool_addresses(pool_pubkey: Pubkey, pool: &SwapPool) -> [Pubkey; 9] {
+    [
+        pool_pubkey,
+        pool.swap_curve,
+        pool.token_a_mint,
+        pool.token_b_mint,
+        pool.token_a_vault,
+        pool.token_b_vault,
+        pool.pool_token_mint,
+        pool.token_a_fees_vault,
+        pool.token_b_fees_vault,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_account_override() {
+        let pubkey = Pubkey::new_unique();
+
+        assert_eq!(
+            AccountOverride::from_str(&format!("{}=pool.json", pubkey)).unwrap(),
+            AccountOverride {
+                pubkey,
+                path: PathBuf::from("pool.json"),
+            }
+        );
+        assert!(AccountOverride::from_str("pool.json").is_err());
+        assert!(AccountOverride::from_str("notapubkey=pool.json").is_err());
+    }
+
+    #[test]
+    fn test_fixture_json() {
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = Account {
+            lamports: 1_000,
+            data: vec![1, 2, 3],
+            owner,
+            executable: false,
+            rent_epoch: 5,
+        };
+
+        let fixture = AccountFixture { pubkey, account };
+
+        let value: serde_json::Value = serde_json::from_str(&fixture.to_json().unwrap()).unwrap();
+
+        assert_eq!(value["pubkey"], pubkey.to_string());
+        assert_eq!(value["account"]["lamports"], 1_000);
+        assert_eq!(value["account"]["data"], json!(["AQID", "base64"]));
+        assert_eq!(value["account"]["owner"], owner.to_string());
+        assert_eq!(value["account"]["rentEpoch"], 5);
+    }
+}
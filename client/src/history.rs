@@ -0,0 +1,157 @@
+//! Token account balances at a past slot, read from the transaction metadata of an archival node
+//!
+//! RPC nodes only serve the latest state of an account. The balance of a token account at the end
+//! of a past slot is the post-transaction balance recorded by the last successful transaction
+//! which referenced it at or before that slot.
+
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_client::{
+        nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+        rpc_config::RpcTransactionConfig, rpc_response::RpcConfirmedTransactionStatusWithSignature,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+};
+use anyhow::{anyhow, bail, Result};
+use solana_transaction_status::{
+    UiLoadedAddresses, UiTransactionEncoding, UiTransactionTokenBalance,
+};
+
+/// Balance of `token_account` at the end of `slot`
+///
+/// Pages back through the signatures of the account, newest first, so the cost grows with the
+/// number of transactions landed since `slot`.
+pub async fn token_balance_at(rpc: &RpcClient, token_account: &Pubkey, slot: u64) -> Result<u64> {
+    let mut before = None;
+    loop {
+        let signatures = rpc
+            .get_signatures_for_address_with_config(
+                token_account,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: None,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+        if let Some(status) = latest_at_or_before(&signatures, slot) {
+            let signature = Signature::from_str(&status.signature)?;
+            return post_token_balance(rpc, &signature, token_account).await;
+        }
+        match signatures.last() {
+            Some(oldest) => before = Some(Signature::from_str(&oldest.signature)?),
+            None => bail!(
+                "No transaction referenced {} at or before slot {}",
+                token_account,
+                slot
+            ),
+        }
+    }
+}
+
+/// Balance of `token_account` recorded by the metadata of a transaction once it executed
+async fn post_token_balance(
+    rpc: &RpcClient,
+    signature: &Signature,
+    token_account: &Pubkey,
+) -> Result<u64> {
+    let confirmed = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow!("Cannot decode transaction {}", signature))?;
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow!("Transaction {} has no status metadata", signature))?;
+
+    // token balances index the static keys followed by the writable then readonly keys loaded
+    // from lookup tables
+    let mut account_keys = transaction.message.static_account_keys().to_vec();
+    if let Some(loaded) = Option::<UiLoadedAddresses>::from(meta.loaded_addresses) {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(Pubkey::from_str(key)?);
+        }
+    }
+    let balances = Option::<Vec<UiTransactionTokenBalance>>::from(meta.post_token_balances)
+        .unwrap_or_default();
+    let balance = balances
+        .iter()
+        .find(|balance| account_keys.get(usize::from(balance.account_index)) == Some(token_account))
+        .ok_or_else(|| {
+            anyhow!(
+                "Transaction {} has no token balance for {}",
+                signature,
+                token_account
+            )
+        })?;
+    Ok(u64::from_str(&balance.ui_token_amount.amount)?)
+}
+
+/// Latest successful transaction landed at or before `slot`, in signatures ordered newest first
+fn latest_at_or_before(
+    signatures: &[RpcConfirmedTransactionStatusWithSignature],
+    slot: u64,
+) -> Option<&RpcConfirmedTransactionStatusWithSignature> {
+    signatures
+        .iter()
+        .find(|status| status.slot <= slot && status.err.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_sdk::transaction::TransactionError;
+
+    use super::*;
+
+    fn status(
+        signature: &str,
+        slot: u64,
+        err: Option<TransactionError>,
+    ) -> RpcConfirmedTransactionStatusWithSignature {
+        RpcConfirmedTransactionStatusWithSignature {
+            signature: signature.to_string(),
+            slot,
+            err,
+            memo: None,
+            block_time: None,
+            confirmation_status: None,
+        }
+    }
+
+    #[test]
+    fn test_latest_at_or_before() {
+        let signatures = vec![
+            status("newest", 120, None),
+            status("failed", 100, Some(TransactionError::AccountInUse)),
+            status("at_slot", 100, None),
+            status("oldest", 90, None),
+        ];
+
+        assert_eq!(
+            latest_at_or_before(&signatures, 100).unwrap().signature,
+            "at_slot"
+        );
+        assert_eq!(
+            latest_at_or_before(&signatures, 99).unwrap().signature,
+            "oldest"
+        );
+        assert_eq!(
+            latest_at_or_before(&signatures, 200).unwrap().signature,
+            "newest"
+        );
+        assert!(latest_at_or_before(&signatures, 89).is_none());
+    }
+}
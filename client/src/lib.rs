@@ -1,5 +1,14 @@
 pub mod client;
 pub mod command;
 pub mod configs;
+pub mod events;
+pub mod fork;
+pub mod history;
+pub mod localnet;
+pub mod metrics;
 pub mod model;
+pub mod quote;
+pub mod recommend;
+pub mod replay;
 pub mod utils;
+pub mod verify;
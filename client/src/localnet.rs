@@ -0,0 +1,389 @@
+//! Start a local validator with the program deployed and bootstrap it from a scenario file
+//!
+//! The scenario lists the mints to create, the pools to initialize between them and the wallets
+//! to fund. Pools and wallets refer to the mints by name. The payer of the CLI is the genesis
+//! mint of the validator, it pays for everything and is the admin of the mints and pools.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use anchor_client::{
+    anchor_lang::prelude::Pubkey,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        native_token::sol_to_lamports,
+        program_pack::Pack,
+        signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+        system_instruction,
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use hyperplane::{curve::fees::Fees, ix::Initialize, CurveUserParameters, InitialSupply};
+use orbit_link::async_client::AsyncClient;
+use spl_associated_token_account as ata;
+use spl_token::state::Mint;
+use tokio::process::{Child, Command};
+use tracing::info;
+
+use crate::{client::HyperplaneClient, send_tx};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LocalnetScenario {
+    /// Program binary to preload at the program id
+    #[serde(default = "default_program_so")]
+    pub program_so: PathBuf,
+    /// Ledger of the validator, reset on every start
+    #[serde(default = "default_ledger")]
+    pub ledger: PathBuf,
+    /// Directory of the wallet keypairs, existing keypairs are reused across runs
+    #[serde(default = "default_wallets_dir")]
+    pub wallets_dir: PathBuf,
+    #[serde(default)]
+    pub mints: Vec<MintSpec>,
+    #[serde(default)]
+    pub pools: Vec<PoolSpec>,
+    #[serde(default)]
+    pub wallets: Vec<WalletSpec>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MintSpec {
+    pub name: String,
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+    /// Amount minted to the admin ata, in tokens, must cover the initial supply of the pools
+    pub supply: f64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PoolSpec {
+    /// Mint name
+    pub token_a_mint: String,
+    /// Mint name
+    pub token_b_mint: String,
+    pub curve: CurveUserParameters,
+    pub fees: Fees,
+    pub initial_supply: InitialSupply,
+    #[serde(default)]
+    pub burn_owner_fees: bool,
+    #[serde(default)]
+    pub strict_token_program: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct WalletSpec {
+    pub name: String,
+    #[serde(default)]
+    pub sol: f64,
+    /// Mint name to amount, in tokens
+    #[serde(default)]
+    pub tokens: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Default)]
+pub struct LocalnetSummary {
+    pub mints: Vec<(String, Pubkey)>,
+    pub pools: Vec<(String, Pubkey)>,
+    pub wallets: Vec<(String, Pubkey, PathBuf)>,
+}
+
+fn default_program_so() -> PathBuf {
+    PathBuf::from("target/deploy/hyperplane.so")
+}
+
+fn default_ledger() -> PathBuf {
+    PathBuf::from("test-ledger")
+}
+
+fn default_wallets_dir() -> PathBuf {
+    PathBuf::from("localnet-wallets")
+}
+
+fn default_decimals() -> u8 {
+    6
+}
+
+/// `solana-test-validator` arguments preloading the program and funding the payer at genesis
+pub fn validator_args(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    scenario: &LocalnetScenario,
+) -> Vec<String> {
+    vec![
+        "--reset".to_string(),
+        "--quiet".to_string(),
+        "--ledger".to_string(),
+        scenario.ledger.display().to_string(),
+        "--mint".to_string(),
+        payer.to_string(),
+        "--bpf-program".to_string(),
+        program_id.to_string(),
+        scenario.program_so.display().to_string(),
+    ]
+}
+
+pub fn start_validator(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    scenario: &LocalnetScenario,
+) -> Result<Child> {
+    if !scenario.program_so.exists() {
+        bail!(
+            "Program binary {} not found, build it with `anchor build`",
+            scenario.program_so.display()
+        );
+    }
+    let args = validator_args(program_id, payer, scenario);
+    info!("Starting solana-test-validator {}", args.join(" "));
+    Command::new("solana-test-validator")
+        .args(args)
+        .stdout(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start solana-test-validator: {}", e))
+}
+
+/// Poll the validator until it produces slots
+pub async fn wait_for_validator<T: AsyncClient>(rpc: &T, timeout: Duration) -> Result<()> {
+    let poll_interval = Duration::from_millis(500);
+    let mut waited = Duration::ZERO;
+    loop {
+        if let Ok(slot) = rpc
+            .get_slot_with_commitment(CommitmentConfig::confirmed())
+            .await
+        {
+            if slot > 0 {
+                info!("Validator ready at slot {}", slot);
+                return Ok(());
+            }
+        }
+        if waited >= timeout {
+            bail!("Validator not ready after {:?}", timeout);
+        }
+        tokio::time::sleep(poll_interval).await;
+        waited += poll_interval;
+    }
+}
+
+/// Create the mints, pools and wallets of the scenario
+pub async fn bootstrap<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    scenario: &LocalnetScenario,
+) -> Result<LocalnetSummary> {
+    if hyperplane.config.dry_run || hyperplane.config.multisig {
+        bail!("Localnet cannot run in dry-run or multisig mode");
+    }
+    let admin = hyperplane.client.payer();
+    let mut summary = LocalnetSummary::default();
+
+    let mut mints = HashMap::new();
+    for spec in &scenario.mints {
+        let mint = create_mint(hyperplane, admin, spec).await?;
+        info!("Created mint {} {}", spec.name, mint);
+        mints.insert(spec.name.as_str(), mint);
+        summary.mints.push((spec.name.clone(), mint));
+    }
+    let mint = |name: &str| {
+        mints
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow!("Unknown mint {} in scenario", name))
+    };
+
+    for spec in &scenario.pools {
+        let token_a_mint = mint(&spec.token_a_mint)?;
+        let token_b_mint = mint(&spec.token_b_mint)?;
+        let pool = hyperplane
+            .initialize_pool(
+                admin,
+                ata::get_associated_token_address(&admin, &token_a_mint),
+                ata::get_associated_token_address(&admin, &token_b_mint),
+                Initialize {
+                    fees: spec.fees,
+                    curve_parameters: spec.curve.clone(),
+                    initial_supply: spec.initial_supply.clone(),
+                    burn_owner_fees: spec.burn_owner_fees,
+                    strict_token_program: spec.strict_token_program,
+                },
+            )
+            .await?;
+        summary
+            .pools
+            .push((format!("{}/{}", spec.token_a_mint, spec.token_b_mint), pool));
+    }
+
+    tokio::fs::create_dir_all(&scenario.wallets_dir).await?;
+    for spec in &scenario.wallets {
+        let path = scenario.wallets_dir.join(format!("{}.json", spec.name));
+        let wallet = wallet_keypair(&path)?;
+        let mut builder = hyperplane.client.tx_builder();
+        if spec.sol > 0.0 {
+            builder = builder.add_ix(system_instruction::transfer(
+                &admin,
+                &wallet.pubkey(),
+                sol_to_lamports(spec.sol),
+            ));
+        }
+        for (name, ui_amount) in &spec.tokens {
+            let mint = mint(name)?;
+            let amount = hyperplane.ui_amount_to_native(&mint, *ui_amount).await?;
+            builder = builder
+                .add_ix(
+                    ata::instruction::create_associated_token_account_idempotent(
+                        &admin,
+                        &wallet.pubkey(),
+                        &mint,
+                        &spl_token::id(),
+                    ),
+                )
+                .add_ix(spl_token::instruction::mint_to(
+                    &spl_token::id(),
+                    &mint,
+                    &ata::get_associated_token_address(&wallet.pubkey(), &mint),
+                    &admin,
+                    &[],
+                    amount,
+                )?);
+        }
+        send_tx!(hyperplane, builder, []);
+        summary
+            .wallets
+            .push((spec.name.clone(), wallet.pubkey(), path));
+    }
+
+    Ok(summary)
+}
+
+async fn create_mint<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    admin: Pubkey,
+    spec: &MintSpec,
+) -> Result<Pubkey> {
+    let mint = Keypair::new();
+    let supply = hyperplane::ix::amounts::ui_amount_to_native(spec.supply, spec.decimals)
+        .ok_or_else(|| anyhow!("Invalid supply {} for mint {}", spec.supply, spec.name))?;
+    let builder = hyperplane
+        .client
+        .tx_builder()
+        .add_ix(
+            hyperplane
+                .client
+                .create_account_ix(&mint.pubkey(), Mint::LEN, &spl_token::id())
+                .await?,
+        )
+        .add_ix(spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &admin,
+            None,
+            spec.decimals,
+        )?)
+        .add_ix(
+            ata::instruction::create_associated_token_account_idempotent(
+                &admin,
+                &admin,
+                &mint.pubkey(),
+                &spl_token::id(),
+            ),
+        )
+        .add_ix(spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &ata::get_associated_token_address(&admin, &mint.pubkey()),
+            &admin,
+            &[],
+            supply,
+        )?);
+    send_tx!(hyperplane, builder, [&mint]);
+    Ok(mint.pubkey())
+}
+
+fn wallet_keypair(path: &Path) -> Result<Keypair> {
+    if path.exists() {
+        return read_keypair_file(path)
+            .map_err(|e| anyhow!("Invalid wallet keypair {}: {}", path.display(), e));
+    }
+    let wallet = Keypair::new();
+    write_keypair_file(&wallet, path)
+        .map_err(|e| anyhow!("Failed to write wallet keypair {}: {}", path.display(), e))?;
+    Ok(wallet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario: LocalnetScenario = serde_json::from_str(
+            r#"
+        {
+            "mints": [
+                { "name": "USDC", "supply": 1000000 },
+                { "name": "USDH", "decimals": 9, "supply": 1000000 }
+            ],
+            "pools": [
+                {
+                    "token_a_mint": "USDC",
+                    "token_b_mint": "USDH",
+                    "curve": { "Stable": { "amp": 100 } },
+                    "fees": {
+                        "trade_fee_numerator": 25,
+                        "trade_fee_denominator": 10000,
+                        "owner_trade_fee_numerator": 5,
+                        "owner_trade_fee_denominator": 10000,
+                        "owner_withdraw_fee_numerator": 0,
+                        "owner_withdraw_fee_denominator": 10000,
+                        "host_fee_numerator": 5,
+                        "host_fee_denominator": 10000
+                    },
+                    "initial_supply": {
+                        "initial_supply_a": 100000000000,
+                        "initial_supply_b": 100000000000000
+                    }
+                }
+            ],
+            "wallets": [
+                { "name": "alice", "sol": 10, "tokens": { "USDC": 1000 } }
+            ]
+        }
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(scenario.program_so, default_program_so());
+        assert_eq!(scenario.ledger, default_ledger());
+        assert_eq!(scenario.mints[0].decimals, 6);
+        assert_eq!(scenario.mints[1].decimals, 9);
+        assert_eq!(scenario.pools[0].token_b_mint, "USDH");
+        assert_eq!(scenario.wallets[0].tokens["USDC"], 1000.0);
+    }
+
+    #[test]
+    fn test_validator_args() {
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let scenario: LocalnetScenario = serde_json::from_str("{}").unwrap();
+
+        let args = validator_args(&program_id, &payer, &scenario);
+
+        assert_eq!(
+            args,
+            vec![
+                "--reset".to_string(),
+                "--quiet".to_string(),
+                "--ledger".to_string(),
+                "test-ledger".to_string(),
+                "--mint".to_string(),
+                payer.to_string(),
+                "--bpf-program".to_string(),
+                program_id.to_string(),
+                "target/deploy/hyperplane.so".to_string(),
+            ]
+        );
+    }
+}
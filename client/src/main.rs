@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf};
 
 use anchor_client::{
     solana_client::nonblocking::rpc_client::RpcClient,
@@ -10,10 +10,12 @@ use anchor_client::{
 };
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use hyperplane::state::UpdatePoolConfigMode;
+use hyperplane::{curve::calculator::TradeDirection, state::UpdatePoolConfigMode};
 use hyperplane_client::{
     client::{Config, HyperplaneClient},
     command,
+    fork::AccountOverride,
+    recommend::PairType,
 };
 use orbit_link::OrbitLink;
 use tracing::info;
@@ -81,9 +83,9 @@ pub enum Actions {
     },
     #[clap(arg_required_else_help = true)]
     CreateMint {
-        /// Amount to mint to the admin's ata
+        /// Amount to mint to the admin's ata, in tokens
         #[clap(short, long)]
-        supply: Option<u64>,
+        supply: Option<f64>,
         /// File to output the mint secret key
         #[clap(short, long, parse(from_os_str))]
         out: PathBuf,
@@ -109,11 +111,145 @@ pub enum Actions {
         #[clap(short, long)]
         value: String,
     },
+    /// Swap against a pool with a minimum amount out derived from a local quote
+    #[clap(arg_required_else_help = true)]
+    Swap {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Trade direction, AtoB or BtoA
+        #[clap(short, long)]
+        direction: TradeDirection,
+        /// Amount of source token to swap, in tokens, e.g. 1.5
+        #[clap(short, long)]
+        amount_in: f64,
+        /// Maximum slippage from the quoted amount out, in basis points
+        #[clap(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// Maximum number of slots between the quote and sending the swap
+        #[clap(long, default_value_t = 10)]
+        max_slot_lag: u64,
+        /// Source token account, else signer ata
+        #[clap(long, parse(try_from_str))]
+        source_ata: Option<Pubkey>,
+        /// Destination token account, else signer ata
+        #[clap(long, parse(try_from_str))]
+        destination_ata: Option<Pubkey>,
+    },
+    /// Quote a swap against the pool reserves at the end of a past slot, e.g. to settle what a
+    /// swap landed in the next slot should have returned
+    #[clap(arg_required_else_help = true)]
+    SimulateAt {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Slot whose final reserves are quoted against
+        #[clap(long)]
+        slot: u64,
+        /// AtoB or BtoA
+        #[clap(short, long)]
+        direction: TradeDirection,
+        /// Amount of source token to swap, in tokens, e.g. 1.5
+        #[clap(short, long)]
+        amount_in: f64,
+        /// Archival node serving the transaction history of the pool vaults, else --url
+        #[clap(long)]
+        archive_url: Option<String>,
+    },
+    /// Dump a pool and its accounts from a remote cluster to reproduce it on a local validator
+    #[clap(arg_required_else_help = true)]
+    Fork {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Cluster to copy the pool accounts from
+        #[clap(long)]
+        fork_url: String,
+        /// Replace or add an account, as <pubkey>=<file.json> in the `solana account --output json` format
+        #[clap(long, parse(try_from_str), multiple_occurrences = true)]
+        override_account: Vec<AccountOverride>,
+        /// Also dump the program and its program data, to run the deployed program instead of a local build
+        #[clap(long, takes_value = false)]
+        include_program: bool,
+        /// Directory to write the account files to
+        #[clap(short, long, parse(from_os_str), default_value = "fork")]
+        out_dir: PathBuf,
+    },
+    /// Start a local validator with the program, then create the mints, pools and wallets of a scenario
+    #[clap(arg_required_else_help = true)]
+    Localnet {
+        /// Scenario file
+        #[clap(long, parse(from_os_str))]
+        config: PathBuf,
+    },
+    /// Suggest a curve and fees for a target depth and write them to a pool config file
+    #[clap(arg_required_else_help = true)]
+    Recommend {
+        /// Stable, Correlated or Volatile
+        #[clap(long)]
+        pair_type: PairType,
+        /// Trade size, in tokens, to execute within the max slippage
+        #[clap(long)]
+        target_depth: f64,
+        /// Liquidity on each side of the pool, in tokens
+        #[clap(long)]
+        liquidity: f64,
+        /// Maximum price impact of a target depth trade, in basis points
+        #[clap(long, default_value_t = 10)]
+        max_slippage_bps: u16,
+        #[clap(long, parse(try_from_str))]
+        token_a_mint: Pubkey,
+        #[clap(long, parse(try_from_str))]
+        token_b_mint: Pubkey,
+        /// Token B per token A, only for volatile pairs
+        #[clap(long, default_value_t = 1.0)]
+        price: f64,
+        /// File to output the pool config
+        #[clap(short, long, parse(from_os_str))]
+        out: PathBuf,
+    },
     #[clap(arg_required_else_help = true)]
     PrintPool {
         /// Reserve pubkey
         #[clap(short, long, parse(try_from_str))]
         pool: Pubkey,
+        /// Owners to print the locked pool tokens and remaining lock slots of, can be repeated
+        #[clap(long = "lock-owner", parse(try_from_str))]
+        lock_owners: Vec<Pubkey>,
+    },
+    /// Start recording the config changes of a pool created before config histories existed
+    #[clap(arg_required_else_help = true)]
+    InitConfigHistory {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+    },
+    /// Print the recorded config changes of a pool, oldest first
+    #[clap(arg_required_else_help = true)]
+    PrintConfigHistory {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+    },
+    /// Compare fees, curve, mints, authorities and token programs of a pool with a config file
+    #[clap(arg_required_else_help = true)]
+    VerifyPool {
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Desired state config file, a pool config file with optional admin and token programs
+        #[clap(long, parse(from_os_str))]
+        config: PathBuf,
+    },
+    /// Serve reserves, fee vault balances, virtual price and imbalance of pools as Prometheus metrics
+    #[clap(arg_required_else_help = true)]
+    Metrics {
+        /// Pools to export, repeat the flag for several pools
+        #[clap(
+            short,
+            long,
+            parse(try_from_str),
+            multiple_occurrences = true,
+            required = true
+        )]
+        pools: Vec<Pubkey>,
+        /// Address to serve the metrics on
+        #[clap(long, default_value = "0.0.0.0:9090")]
+        listen: SocketAddr,
     },
 }
 
@@ -160,7 +296,106 @@ async fn main() -> Result<()> {
         Actions::UpdatePool { pool, mode, value } => {
             command::update_pool(&hyperplane_client, admin, pool, mode, value).await
         }
-        Actions::PrintPool { pool } => command::print_pool(&hyperplane_client, pool).await,
+        Actions::Swap {
+            pool,
+            direction,
+            amount_in,
+            slippage_bps,
+            max_slot_lag,
+            source_ata,
+            destination_ata,
+        } => {
+            command::swap(
+                &hyperplane_client,
+                admin,
+                pool,
+                direction,
+                amount_in,
+                slippage_bps,
+                max_slot_lag,
+                source_ata,
+                destination_ata,
+            )
+            .await
+        }
+        Actions::SimulateAt {
+            pool,
+            slot,
+            direction,
+            amount_in,
+            archive_url,
+        } => {
+            let archive_rpc = RpcClient::new_with_commitment(
+                archive_url.unwrap_or_else(|| args.url.url().to_string()),
+                commitment,
+            );
+            command::simulate_at(
+                &hyperplane_client,
+                &archive_rpc,
+                pool,
+                slot,
+                direction,
+                amount_in,
+            )
+            .await
+        }
+        Actions::Fork {
+            pool,
+            fork_url,
+            override_account,
+            include_program,
+            out_dir,
+        } => {
+            let fork_rpc = RpcClient::new_with_commitment(fork_url, commitment);
+            command::fork(
+                &fork_rpc,
+                args.program,
+                pool,
+                override_account,
+                include_program,
+                out_dir,
+            )
+            .await
+        }
+        Actions::Localnet { config } => command::localnet(&hyperplane_client, config).await,
+        Actions::Recommend {
+            pair_type,
+            target_depth,
+            liquidity,
+            max_slippage_bps,
+            token_a_mint,
+            token_b_mint,
+            price,
+            out,
+        } => {
+            command::recommend(
+                &hyperplane_client,
+                pair_type,
+                target_depth,
+                liquidity,
+                max_slippage_bps,
+                token_a_mint,
+                token_b_mint,
+                price,
+                out,
+            )
+            .await
+        }
+        Actions::PrintPool { pool, lock_owners } => {
+            command::print_pool(&hyperplane_client, pool, lock_owners).await
+        }
+        Actions::InitConfigHistory { pool } => {
+            command::initialize_config_history(&hyperplane_client, admin, pool).await
+        }
+        Actions::PrintConfigHistory { pool } => {
+            command::print_config_history(&hyperplane_client, pool).await
+        }
+        Actions::VerifyPool { pool, config } => {
+            command::verify_pool(&hyperplane_client, pool, config).await
+        }
+        Actions::Metrics { pools, listen } => {
+            command::metrics(&hyperplane_client, pools, listen).await
+        }
     }
 }
 
@@ -288,4 +523,220 @@ mod test {
             }
         );
     }
+
+    #[test]
+    pub fn test_parsing_swap() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "swap",
+            "-p",
+            &pool.to_string(),
+            "-d",
+            "BtoA",
+            "-a",
+            "1.5",
+            "--slippage-bps",
+            "25",
+        ]);
+
+        assert_eq!(
+            x,
+            Args {
+                keypair: PathBuf::from("../../test/test/admin.json"),
+                url: Cluster::from_str("localnet").unwrap(),
+                program: hyperplane::ID,
+                dry_run: false,
+                multisig: false,
+                signer: None,
+                action: Actions::Swap {
+                    pool,
+                    direction: TradeDirection::BtoA,
+                    amount_in: 1.5,
+                    slippage_bps: 25,
+                    max_slot_lag: 10,
+                    source_ata: None,
+                    destination_ata: None,
+                },
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_simulate_at() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "simulate-at",
+            "-p",
+            &pool.to_string(),
+            "--slot",
+            "123456",
+            "-d",
+            "AtoB",
+            "-a",
+            "2.5",
+            "--archive-url",
+            "https://api.mainnet-beta.solana.com",
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::SimulateAt {
+                pool,
+                slot: 123456,
+                direction: TradeDirection::AtoB,
+                amount_in: 2.5,
+                archive_url: Some("https://api.mainnet-beta.solana.com".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_fork() {
+        let pool = Pubkey::new_unique();
+        let admin_override = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "fork",
+            "-p",
+            &pool.to_string(),
+            "--fork-url",
+            "https://api.mainnet-beta.solana.com",
+            "--override-account",
+            &format!("{}=pool.json", pool),
+            "--override-account",
+            &format!("{}=admin.json", admin_override),
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::Fork {
+                pool,
+                fork_url: "https://api.mainnet-beta.solana.com".to_string(),
+                override_account: vec![
+                    AccountOverride {
+                        pubkey: pool,
+                        path: PathBuf::from("pool.json"),
+                    },
+                    AccountOverride {
+                        pubkey: admin_override,
+                        path: PathBuf::from("admin.json"),
+                    },
+                ],
+                include_program: false,
+                out_dir: PathBuf::from("fork"),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_localnet() {
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "localnet",
+            "--config",
+            "localnet.json",
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::Localnet {
+                config: PathBuf::from("localnet.json"),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_recommend() {
+        let token_a_mint = Pubkey::new_unique();
+        let token_b_mint = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "recommend",
+            "--pair-type",
+            "Stable",
+            "--target-depth",
+            "10000",
+            "--liquidity",
+            "1000000",
+            "--token-a-mint",
+            &token_a_mint.to_string(),
+            "--token-b-mint",
+            &token_b_mint.to_string(),
+            "-o",
+            "pool.json",
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::Recommend {
+                pair_type: PairType::Stable,
+                target_depth: 10_000.0,
+                liquidity: 1_000_000.0,
+                max_slippage_bps: 10,
+                token_a_mint,
+                token_b_mint,
+                price: 1.0,
+                out: PathBuf::from("pool.json"),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_metrics() {
+        let pool_1 = Pubkey::new_unique();
+        let pool_2 = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "metrics",
+            "-p",
+            &pool_1.to_string(),
+            "--pools",
+            &pool_2.to_string(),
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::Metrics {
+                pools: vec![pool_1, pool_2],
+                listen: SocketAddr::from(([0, 0, 0, 0], 9090)),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_verify_pool() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "verify-pool",
+            "-p",
+            &pool.to_string(),
+            "--config",
+            "pool.json",
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::VerifyPool {
+                pool,
+                config: PathBuf::from("pool.json"),
+            }
+        );
+    }
 }
@@ -0,0 +1,215 @@
+//! Pool health metrics in the Prometheus text exposition format
+
+use std::fmt::Write;
+
+use anchor_client::{anchor_lang::prelude::Pubkey, solana_sdk::signature::Signer};
+use anyhow::{anyhow, Result};
+use hyperplane::state::SwapPool;
+use orbit_link::async_client::AsyncClient;
+
+use crate::client::HyperplaneClient;
+
+/// Name and help text of the exported gauges, in the order of [PoolMetrics::values]
+const GAUGES: [(&str, &str); 7] = [
+    (
+        "hyperplane_token_a_reserve",
+        "Token A vault balance, in tokens",
+    ),
+    (
+        "hyperplane_token_b_reserve",
+        "Token B vault balance, in tokens",
+    ),
+    (
+        "hyperplane_token_a_fees",
+        "Token A fees vault balance, in tokens",
+    ),
+    (
+        "hyperplane_token_b_fees",
+        "Token B fees vault balance, in tokens",
+    ),
+    (
+        "hyperplane_pool_token_supply",
+        "Pool token supply, in tokens",
+    ),
+    (
+        "hyperplane_virtual_price",
+        "Curve value of the reserves per pool token, in native units",
+    ),
+    (
+        "hyperplane_imbalance_ratio",
+        "Token A reserve over token B reserve, in tokens",
+    ),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolMetrics {
+    pub pool: Pubkey,
+    pub token_a_reserve: f64,
+    pub token_b_reserve: f64,
+    pub token_a_fees: f64,
+    pub token_b_fees: f64,
+    pub pool_token_supply: f64,
+    pub virtual_price: f64,
+}
+
+impl PoolMetrics {
+    /// Token A reserve over token B reserve, 1.0 for a balanced stable pool
+    pub fn imbalance_ratio(&self) -> f64 {
+        self.token_a_reserve / self.token_b_reserve
+    }
+
+    fn values(&self) -> [f64; 7] {
+        [
+            self.token_a_reserve,
+            self.token_b_reserve,
+            self.token_a_fees,
+            self.token_b_fees,
+            self.pool_token_supply,
+            self.virtual_price,
+            self.imbalance_ratio(),
+        ]
+    }
+}
+
+pub async fn get_pool_metrics<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool_pubkey: Pubkey,
+) -> Result<PoolMetrics> {
+    let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
+    let swap_curve = hyperplane.get_swap_curve(&pool).await?;
+
+    let token_a_reserve = hyperplane
+        .get_token_account(&pool.token_a_vault)
+        .await?
+        .amount;
+    let token_b_reserve = hyperplane
+        .get_token_account(&pool.token_b_vault)
+        .await?
+        .amount;
+    let token_a_fees = hyperplane
+        .get_token_account(&pool.token_a_fees_vault)
+        .await?
+        .amount;
+    let token_b_fees = hyperplane
+        .get_token_account(&pool.token_b_fees_vault)
+        .await?
+        .amount;
+    let pool_token_supply = hyperplane.get_mint_supply(&pool.pool_token_mint).await?;
+
+    let pool_value = swap_curve
+        .calculator
+        .normalized_value(u128::from(token_a_reserve), u128::from(token_b_reserve))?
+        .to_imprecise()
+        .ok_or_else(|| anyhow!("Pool {} value does not fit in a u128", pool_pubkey))?;
+    let virtual_price = if pool_token_supply == 0 {
+        0.0
+    } else {
+        pool_value as f64 / pool_token_supply as f64
+    };
+
+    Ok(PoolMetrics {
+        pool: pool_pubkey,
+        token_a_reserve: hyperplane
+            .native_to_ui_amount(&pool.token_a_mint, token_a_reserve)
+            .await?,
+        token_b_reserve: hyperplane
+            .native_to_ui_amount(&pool.token_b_mint, token_b_reserve)
+            .await?,
+        token_a_fees: hyperplane
+            .native_to_ui_amount(&pool.token_a_mint, token_a_fees)
+            .await?,
+        token_b_fees: hyperplane
+            .native_to_ui_amount(&pool.token_b_mint, token_b_fees)
+            .await?,
+        pool_token_supply: hyperplane
+            .native_to_ui_amount(&pool.pool_token_mint, pool_token_supply)
+            .await?,
+        virtual_price,
+    })
+}
+
+/// Render the metrics of the pools, one gauge per metric labelled by pool
+pub fn render(pools: &[PoolMetrics]) -> String {
+    let mut out = String::new();
+    for (i, (name, help)) in GAUGES.iter().enumerate() {
+        writeln!(out, "# HELP {} {}", name, help).unwrap();
+        writeln!(out, "# TYPE {} gauge", name).unwrap();
+        for pool in pools {
+            writeln!(
+                out,
+                "{}{{pool=\"{}\"}} {}",
+                name,
+                pool.pool,
+                format_value(pool.values()[i])
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+/// Minimal HTTP/1.1 response, the exporter serves the same body on every path
+pub fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Prometheus spells the special float values differently from rust
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_pool_metrics() {
+        let pool = Pubkey::new_unique();
+        let rendered = render(&[PoolMetrics {
+            pool,
+            token_a_reserve: 150.5,
+            token_b_reserve: 0.0,
+            token_a_fees: 1.25,
+            token_b_fees: 0.0,
+            pool_token_supply: 100.0,
+            virtual_price: 1.01,
+        }]);
+
+        assert!(rendered.contains("# TYPE hyperplane_token_a_reserve gauge\n"));
+        assert!(rendered.contains(&format!(
+            "hyperplane_token_a_reserve{{pool=\"{}\"}} 150.5\n",
+            pool
+        )));
+        assert!(rendered.contains(&format!(
+            "hyperplane_virtual_price{{pool=\"{}\"}} 1.01\n",
+            pool
+        )));
+        // a drained token B vault
+        assert!(rendered.contains(&format!(
+            "hyperplane_imbalance_ratio{{pool=\"{}\"}} +Inf\n",
+            pool
+        )));
+        assert_eq!(rendered.matches("# HELP").count(), GAUGES.len());
+    }
+
+    #[test]
+    fn test_http_response_content_length() {
+        let response = http_response("200 OK", "abc\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Length: 4\r\n"));
+        assert!(response.ends_with("\r\n\r\nabc\n"));
+    }
+}
@@ -7,4 +7,41 @@ pub struct InitializePoolConfig {
     pub curve: CurveUserParameters,
     pub fees: Fees,
     pub initial_supply: InitialSupply,
+    /// Leave owner trade fees in the pool vault instead of the fees vault
+    #[serde(default)]
+    pub burn_owner_fees: bool,
+    /// Require both trading tokens to be owned by the same token program
+    #[serde(default)]
+    pub strict_token_program: bool,
+}
+
+/// Desired state of a deployed pool, a pool config file can be used as is
+///
+/// The optional fields are only checked when set.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct VerifyPoolConfig {
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub curve: CurveUserParameters,
+    pub fees: Fees,
+    #[serde(default)]
+    pub burn_owner_fees: Option<bool>,
+    #[serde(default)]
+    pub flash_fee_bps: Option<u64>,
+    #[serde(default)]
+    pub dynamic_fee_numerator: Option<u64>,
+    #[serde(default)]
+    pub max_dynamic_trade_fee_numerator: Option<u64>,
+    #[serde(default)]
+    pub protocol_fee_bps: Option<u64>,
+    #[serde(default)]
+    pub host_fee_in_destination_token: Option<bool>,
+    #[serde(default)]
+    pub admin: Option<String>,
+    #[serde(default)]
+    pub token_a_program: Option<String>,
+    #[serde(default)]
+    pub token_b_program: Option<String>,
+    #[serde(default)]
+    pub pool_token_program: Option<String>,
 }
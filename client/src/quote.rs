@@ -0,0 +1,119 @@
+//! Locally computed swap quotes, and the slippage bounds derived from them
+
+use anyhow::Result;
+use hyperplane::{
+    curve::{base::SwapCurve, calculator::TradeDirection, fees::Fees},
+    utils::math::BPS_DENOMINATOR,
+};
+
+/// Swap to quote against reserves read beforehand, e.g. from an account subscription
+#[derive(Clone, Debug)]
+pub struct QuoteRequest<'a> {
+    pub swap_curve: &'a SwapCurve,
+    /// Fees of the pool at the quoted slot, see `SwapPool::swap_fees`
+    pub fees: &'a Fees,
+    pub source_vault_amount: u64,
+    pub destination_vault_amount: u64,
+    pub trade_direction: TradeDirection,
+    pub amount_in: u64,
+}
+
+/// Amount out of a swap, computed with the program's curve math
+pub fn quote(request: &QuoteRequest) -> Result<u64> {
+    let swap_result = request.swap_curve.swap(
+        u128::from(request.amount_in),
+        u128::from(request.source_vault_amount),
+        u128::from(request.destination_vault_amount),
+        request.trade_direction,
+        request.fees,
+    )?;
+    Ok(u64::try_from(swap_result.destination_amount_swapped)?)
+}
+
+/// Quote a batch of swaps, typically one per pool of a routing graph
+///
+/// Quotes the program would reject, e.g. a trade draining the reserves, are `None` rather than
+/// failing the whole batch. The curve math logs the reason of a rejection to stdout.
+pub fn quote_many(requests: &[QuoteRequest]) -> Vec<Option<u64>> {
+    requests.iter().map(|request| quote(request).ok()).collect()
+}
+
+/// Minimum amount out to accept for a quoted amount out and a slippage tolerance in basis points
+pub fn minimum_amount_out(quoted_amount_out: u64, slippage_bps: u16) -> u64 {
+    let tolerance = BPS_DENOMINATOR.saturating_sub(u64::from(slippage_bps));
+    (u128::from(quoted_amount_out) * u128::from(tolerance) / u128::from(BPS_DENOMINATOR)) as u64
+}
+
+/// Slippage of the realized amount out relative to the quoted amount out, in basis points
+///
+/// Negative if the swap returned more than quoted.
+pub fn realized_slippage_bps(quoted_amount_out: u64, realized_amount_out: u64) -> i64 {
+    if quoted_amount_out == 0 {
+        return 0;
+    }
+    let diff = i128::from(quoted_amount_out) - i128::from(realized_amount_out);
+    (diff * i128::from(BPS_DENOMINATOR) / i128::from(quoted_amount_out)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::model::CurveParameters;
+
+    use super::*;
+
+    fn constant_product() -> SwapCurve {
+        SwapCurve::new_from_params(CurveParameters::ConstantProduct).unwrap()
+    }
+
+    #[test]
+    fn test_quote_many() {
+        let swap_curve = constant_product();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            ..Fees::default()
+        };
+        let request = QuoteRequest {
+            swap_curve: &swap_curve,
+            fees: &fees,
+            source_vault_amount: 1_000_000,
+            destination_vault_amount: 1_000_000,
+            trade_direction: TradeDirection::AtoB,
+            amount_in: 10_000,
+        };
+        let quotes = quote_many(&[
+            request.clone(),
+            QuoteRequest {
+                destination_vault_amount: 0,
+                ..request.clone()
+            },
+            QuoteRequest {
+                trade_direction: TradeDirection::BtoA,
+                amount_in: 1_000,
+                ..request.clone()
+            },
+        ]);
+
+        // 9_900 in after the trade fee: 1_000_000 - ceil(10^12 / 1_009_900)
+        assert_eq!(quotes, vec![Some(9_802), None, Some(989)]);
+        assert_eq!(quote(&request).unwrap(), 9_802);
+    }
+
+    #[test]
+    fn test_minimum_amount_out() {
+        assert_eq!(minimum_amount_out(10_000, 0), 10_000);
+        assert_eq!(minimum_amount_out(10_000, 50), 9_950);
+        assert_eq!(minimum_amount_out(999, 50), 994);
+        assert_eq!(minimum_amount_out(10_000, 10_000), 0);
+        assert_eq!(minimum_amount_out(10_000, u16::MAX), 0);
+        assert_eq!(minimum_amount_out(u64::MAX, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_realized_slippage_bps() {
+        assert_eq!(realized_slippage_bps(10_000, 10_000), 0);
+        assert_eq!(realized_slippage_bps(10_000, 9_950), 50);
+        assert_eq!(realized_slippage_bps(10_000, 10_100), -100);
+        assert_eq!(realized_slippage_bps(0, 0), 0);
+    }
+}
@@ -0,0 +1,184 @@
+//! Pool parameter recommendation for a target depth
+//!
+//! The depth is the trade size, in tokens, that must execute within a maximum price impact
+//! against a balanced pool. Stable swap quotes come from the off-chain model of the sim crate,
+//! the smallest amp meeting the target is preferred since a lower amp is more forgiving of a depeg.
+
+use anyhow::{bail, Result};
+use hyperplane::{curve::fees::Fees, utils::math::BPS_DENOMINATOR, CurveUserParameters};
+use hyperplane_sim::StableSwapModel;
+use strum::EnumString;
+
+/// Native units per token used to run the simulation, price impact is insensitive to it
+const SIM_SCALE: f64 = 1_000_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+pub enum PairType {
+    /// Assets pegged to the same value, e.g. USDC/USDT
+    Stable,
+    /// Assets trading close to each other with some drift, e.g. SOL/mSOL
+    Correlated,
+    /// Uncorrelated assets, e.g. SOL/USDC
+    Volatile,
+}
+
+impl PairType {
+    /// Amps to try, in increasing order
+    fn amp_candidates(&self) -> &'static [u64] {
+        match self {
+            PairType::Stable => &[10, 20, 50, 100, 200, 500, 1000, 2000],
+            PairType::Correlated => &[2, 5, 10, 20, 50, 100, 200],
+            PairType::Volatile => &[],
+        }
+    }
+
+    /// Trade and owner trade fees, in basis points
+    fn fees(&self) -> Fees {
+        let (trade_fee_bps, owner_trade_fee_bps) = match self {
+            PairType::Stable => (1, 1),
+            PairType::Correlated => (4, 1),
+            PairType::Volatile => (25, 5),
+        };
+        Fees {
+            trade_fee_numerator: trade_fee_bps,
+            trade_fee_denominator: BPS_DENOMINATOR,
+            owner_trade_fee_numerator: owner_trade_fee_bps,
+            owner_trade_fee_denominator: BPS_DENOMINATOR,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: BPS_DENOMINATOR,
+            host_fee_numerator: 2_000,
+            host_fee_denominator: BPS_DENOMINATOR,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Recommendation {
+    pub curve: CurveUserParameters,
+    pub fees: Fees,
+    /// Price impact of a `target_depth` trade with the recommended curve, before fees
+    pub price_impact_bps: u64,
+    pub meets_target: bool,
+    /// Liquidity per side, in tokens, for a constant product pool to meet the target
+    pub min_liquidity: Option<f64>,
+}
+
+/// Recommend a curve and fees for a pool with `liquidity` tokens on each side so that trading
+/// `target_depth` tokens moves the price by at most `max_slippage_bps`
+///
+/// If no curve meets the target, the closest one is returned with `meets_target` unset.
+pub fn recommend(
+    pair_type: PairType,
+    target_depth: f64,
+    liquidity: f64,
+    max_slippage_bps: u16,
+) -> Result<Recommendation> {
+    if !(target_depth > 0.0 && liquidity > 0.0) {
+        bail!(
+            "Target depth {} and liquidity {} must be positive",
+            target_depth,
+            liquidity
+        );
+    }
+    if max_slippage_bps == 0 || u64::from(max_slippage_bps) >= BPS_DENOMINATOR {
+        bail!("Invalid max slippage {} bps", max_slippage_bps);
+    }
+    let max_slippage_bps = u64::from(max_slippage_bps);
+    let dx = (target_depth * SIM_SCALE) as u128;
+    let reserve = (liquidity * SIM_SCALE) as u128;
+
+    if pair_type == PairType::Volatile {
+        let price_impact_bps = constant_product_price_impact_bps(reserve, dx);
+        let meets_target = price_impact_bps <= max_slippage_bps;
+        // dx / (x + dx) <= s  <=>  x >= dx * (1 - s) / s
+        let min_liquidity = (!meets_target).then(|| {
+            target_depth * (BPS_DENOMINATOR - max_slippage_bps) as f64 / max_slippage_bps as f64
+        });
+        return Ok(Recommendation {
+            curve: CurveUserParameters::ConstantProduct,
+            fees: pair_type.fees(),
+            price_impact_bps,
+            meets_target,
+            min_liquidity,
+        });
+    }
+
+    let mut recommendation = None;
+    for amp in pair_type.amp_candidates() {
+        let price_impact_bps = stable_price_impact_bps(*amp, reserve, dx);
+        let meets_target = price_impact_bps <= max_slippage_bps;
+        recommendation = Some(Recommendation {
+            curve: CurveUserParameters::Stable { amp: *amp },
+            fees: pair_type.fees(),
+            price_impact_bps,
+            meets_target,
+            min_liquidity: None,
+        });
+        if meets_target {
+            break;
+        }
+    }
+    Ok(recommendation.expect("amp candidates are not empty"))
+}
+
+fn constant_product_price_impact_bps(reserve: u128, dx: u128) -> u64 {
+    (dx * u128::from(BPS_DENOMINATOR) / (reserve + dx)) as u64
+}
+
+fn stable_price_impact_bps(amp: u64, reserve: u128, dx: u128) -> u64 {
+    let model = StableSwapModel::new(u128::from(amp), vec![reserve, reserve], vec![1, 1], 2);
+    let dy = model.sim_dy(0, 1, dx);
+    (dx.saturating_sub(dy) * u128::from(BPS_DENOMINATOR) / dx) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend_smallest_amp_meeting_target() {
+        let recommendation = recommend(PairType::Stable, 10_000.0, 1_000_000.0, 5).unwrap();
+
+        assert_eq!(
+            recommendation.curve,
+            CurveUserParameters::Stable { amp: 20 }
+        );
+        assert!(recommendation.meets_target);
+        assert!(recommendation.price_impact_bps <= 5);
+        assert_eq!(recommendation.fees.trade_fee_numerator, 1);
+        recommendation.fees.validate().unwrap();
+    }
+
+    #[test]
+    fn test_recommend_largest_amp_when_target_unreachable() {
+        let recommendation = recommend(PairType::Correlated, 100_000.0, 1_000_000.0, 1).unwrap();
+
+        assert_eq!(
+            recommendation.curve,
+            CurveUserParameters::Stable { amp: 200 }
+        );
+        assert!(!recommendation.meets_target);
+        assert_eq!(recommendation.min_liquidity, None);
+    }
+
+    #[test]
+    fn test_recommend_volatile_min_liquidity() {
+        let recommendation = recommend(PairType::Volatile, 10_000.0, 1_000_000.0, 50).unwrap();
+
+        assert_eq!(recommendation.curve, CurveUserParameters::ConstantProduct);
+        assert_eq!(recommendation.price_impact_bps, 99);
+        assert!(!recommendation.meets_target);
+        assert_eq!(recommendation.min_liquidity, Some(1_990_000.0));
+
+        let recommendation = recommend(PairType::Volatile, 10_000.0, 1_990_000.0, 50).unwrap();
+        assert!(recommendation.meets_target);
+        assert_eq!(recommendation.min_liquidity, None);
+    }
+
+    #[test]
+    fn test_recommend_invalid_inputs() {
+        assert!(recommend(PairType::Stable, 0.0, 1_000_000.0, 5).is_err());
+        assert!(recommend(PairType::Stable, 10_000.0, 1_000_000.0, 0).is_err());
+        assert!(recommend(PairType::Stable, 10_000.0, 1_000_000.0, 10_000).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+//! Reconstruct pool reserves and LP supply from an ordered stream of decoded events
+//!
+//! Events carrying post-instruction balances (deposits, withdrawals and swaps) are checked against
+//! the replayed state. In strict mode a divergence is an error, otherwise the replay resyncs to the
+//! reported balances and keeps going.
+
+use anyhow::{anyhow, bail, Result};
+use hyperplane::{curve::calculator::TradeDirection, event};
+
+/// A decoded event of a single pool, with the context needed to apply it
+#[derive(Clone, Debug, PartialEq)]
+pub enum PoolEvent {
+    PoolCreated(event::PoolCreated),
+    Deposit(event::Deposit),
+    /// Emitted by both `withdraw` and `claim_withdraw`
+    Withdraw(event::Withdraw),
+    Swap {
+        trade_direction: TradeDirection,
+        event: event::Swap,
+    },
+    /// Rebalance as seen from the pool liquidity was taken from
+    RebalanceSource(event::RebalanceAcrossPools),
+    /// Rebalance as seen from the pool liquidity was added to
+    RebalanceDestination(event::RebalanceAcrossPools),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolReserves {
+    pub token_a: u64,
+    pub token_b: u64,
+    pub pool_token_supply: u64,
+}
+
+#[derive(Debug)]
+pub struct Replay {
+    reserves: PoolReserves,
+    strict: bool,
+    events_applied: usize,
+    divergences: usize,
+}
+
+impl Replay {
+    /// Replay from an empty pool, the first event is expected to be `PoolCreated`
+    pub fn new(strict: bool) -> Self {
+        Self::from_reserves(PoolReserves::default(), strict)
+    }
+
+    /// Replay from a known state, e.g. a snapshot of the pool accounts
+    pub fn from_reserves(reserves: PoolReserves, strict: bool) -> Self {
+        Self {
+            reserves,
+            strict,
+            events_applied: 0,
+            divergences: 0,
+        }
+    }
+
+    pub fn reserves(&self) -> PoolReserves {
+        self.reserves
+    }
+
+    pub fn events_applied(&self) -> usize {
+        self.events_applied
+    }
+
+    /// Number of times the replay resynced to the reported balances, always 0 in strict mode
+    pub fn divergences(&self) -> usize {
+        self.divergences
+    }
+
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a PoolEvent>) -> Result<()> {
+        events.into_iter().try_for_each(|event| self.apply(event))
+    }
+
+    pub fn apply(&mut self, event: &PoolEvent) -> Result<()> {
+        let r = &mut self.reserves;
+        match event {
+            PoolEvent::PoolCreated(e) => {
+                *r = PoolReserves {
+                    token_a: e.initial_supply_a,
+                    token_b: e.initial_supply_b,
+                    pool_token_supply: e.initial_pool_token_amount,
+                };
+            }
+            PoolEvent::Deposit(e) => {
+                r.token_a = add(r.token_a, e.token_a_amount)?;
+                r.token_b = add(r.token_b, e.token_b_amount)?;
+                r.pool_token_supply = add(r.pool_token_supply, e.pool_token_amount)?;
+                self.check(PoolReserves {
+                    token_a: e.token_a_reserve_after,
+                    token_b: e.token_b_reserve_after,
+                    pool_token_supply: e.pool_token_supply_after,
+                })?;
+            }
+            PoolEvent::Withdraw(e) => {
+                // withdrawal fees leave the vaults for the fees vaults
+                r.token_a = sub(r.token_a, add(e.token_a_amount, e.token_a_fees)?)?;
+                r.token_b = sub(r.token_b, add(e.token_b_amount, e.token_b_fees)?)?;
+                r.pool_token_supply = sub(r.pool_token_supply, e.pool_token_amount)?;
+                self.check(PoolReserves {
+                    token_a: e.token_a_reserve_after,
+                    token_b: e.token_b_reserve_after,
+                    pool_token_supply: e.pool_token_supply_after,
+                })?;
+            }
+            PoolEvent::Swap {
+                trade_direction,
+                event: e,
+            } => {
+                match trade_direction {
+                    TradeDirection::AtoB => {
+                        r.token_a = add(r.token_a, e.token_in_amount)?;
+                        r.token_b = sub(r.token_b, e.token_out_amount)?;
+                    }
+                    TradeDirection::BtoA => {
+                        r.token_b = add(r.token_b, e.token_in_amount)?;
+                        r.token_a = sub(r.token_a, e.token_out_amount)?;
+                    }
+                }
+                // transfer fees and destination host fees also leave the vault
+                let pool_token_supply = r.pool_token_supply;
+                self.check(PoolReserves {
+                    token_a: e.token_a_reserve_after,
+                    token_b: e.token_b_reserve_after,
+                    pool_token_supply,
+                })?;
+            }
+            PoolEvent::RebalanceSource(e) => {
+                r.token_a = sub(r.token_a, add(e.token_a_amount, e.token_a_remaining)?)?;
+                r.token_b = sub(r.token_b, add(e.token_b_amount, e.token_b_remaining)?)?;
+                r.pool_token_supply = sub(r.pool_token_supply, e.source_pool_token_amount)?;
+            }
+            PoolEvent::RebalanceDestination(e) => {
+                r.token_a = add(r.token_a, e.token_a_amount)?;
+                r.token_b = add(r.token_b, e.token_b_amount)?;
+                r.pool_token_supply = add(r.pool_token_supply, e.destination_pool_token_amount)?;
+            }
+        }
+        self.events_applied += 1;
+        Ok(())
+    }
+
+    fn check(&mut self, reported: PoolReserves) -> Result<()> {
+        if self.reserves == reported {
+            return Ok(());
+        }
+        if self.strict {
+            bail!(
+                "Replay diverged at event {}: replayed={:?}, reported={:?}",
+                self.events_applied,
+                self.reserves,
+                reported
+            );
+        }
+        self.divergences += 1;
+        self.reserves = reported;
+        Ok(())
+    }
+}
+
+fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .ok_or_else(|| anyhow!("Replay overflow: {} + {}", a, b))
+}
+
+fn sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b)
+        .ok_or_else(|| anyhow!("Replay underflow: {} - {}", a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use hyperplane::curve::fees::Fees;
+
+    use super::*;
+
+    fn pool_created() -> PoolEvent {
+        PoolEvent::PoolCreated(event::PoolCreated {
+            pool: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            swap_curve: Pubkey::new_unique(),
+            pool_authority: Pubkey::new_unique(),
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            token_a_vault: Pubkey::new_unique(),
+            token_b_vault: Pubkey::new_unique(),
+            pool_token_mint: Pubkey::new_unique(),
+            token_a_fees_vault: Pubkey::new_unique(),
+            token_b_fees_vault: Pubkey::new_unique(),
+            curve_type: 1,
+            fees: Fees::default(),
+            initial_supply_a: 1_000,
+            initial_supply_b: 1_000,
+            initial_pool_token_amount: 1_000_000,
+            burn_owner_fees: false,
+            strict_token_program: false,
+        })
+    }
+
+    fn events(withdraw_reserve_b_after: u64) -> Vec<PoolEvent> {
+        vec![
+            pool_created(),
+            PoolEvent::Deposit(event::Deposit {
+                token_a_amount: 100,
+                token_b_amount: 100,
+                pool_token_amount: 100_000,
+                pool_token_supply_after: 1_100_000,
+                user_share_bps_after: 909,
+                token_a_reserve_after: 1_100,
+                token_b_reserve_after: 1_100,
+                referrer: None,
+            }),
+            PoolEvent::Swap {
+                trade_direction: TradeDirection::AtoB,
+                event: event::Swap {
+                    token_in_amount: 110,
+                    token_out_amount: 99,
+                    total_fees: 1,
+                    owner_fees_burned: 0,
+                    self_trade: false,
+                    host_fee: 0,
+                    host_fee_recipient: None,
+                    trade_fee: 1,
+                    trade_fee_numerator: 25,
+                    protocol_fee: 0,
+                    host_fee_in_destination_token: false,
+                    token_a_reserve_after: 1_210,
+                    token_b_reserve_after: 1_001,
+                },
+            },
+            PoolEvent::Withdraw(event::Withdraw {
+                token_a_amount: 120,
+                token_b_amount: 99,
+                pool_token_amount: 110_000,
+                token_a_fees: 1,
+                token_b_fees: 1,
+                pool_token_supply_after: 990_000,
+                user_share_bps_after: 0,
+                token_a_reserve_after: 1_089,
+                token_b_reserve_after: withdraw_reserve_b_after,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_replay_reconstructs_reserves() {
+        let mut replay = Replay::new(true);
+        replay.apply_all(&events(901)).unwrap();
+
+        assert_eq!(
+            replay.reserves(),
+            PoolReserves {
+                token_a: 1_089,
+                token_b: 901,
+                pool_token_supply: 990_000,
+            }
+        );
+        assert_eq!(replay.events_applied(), 4);
+        assert_eq!(replay.divergences(), 0);
+    }
+
+    #[test]
+    fn test_strict_replay_errors_on_divergence() {
+        let mut replay = Replay::new(true);
+        let err = replay.apply_all(&events(900)).unwrap_err();
+
+        assert!(err.to_string().starts_with("Replay diverged at event 3"));
+        assert_eq!(replay.events_applied(), 3);
+    }
+
+    #[test]
+    fn test_lenient_replay_resyncs_on_divergence() {
+        let mut replay = Replay::new(false);
+        replay.apply_all(&events(900)).unwrap();
+
+        assert_eq!(replay.reserves().token_b, 900);
+        assert_eq!(replay.divergences(), 1);
+    }
+
+    #[test]
+    fn test_rebalance_moves_liquidity_between_pools() {
+        let rebalance = event::RebalanceAcrossPools {
+            source_pool_token_amount: 500_000,
+            destination_pool_token_amount: 400_000,
+            token_a_amount: 400,
+            token_b_amount: 450,
+            token_a_remaining: 100,
+            token_b_remaining: 50,
+        };
+        let mut source = Replay::new(true);
+        let mut destination = Replay::new(true);
+        source
+            .apply_all(&[
+                pool_created(),
+                PoolEvent::RebalanceSource(rebalance.clone()),
+            ])
+            .unwrap();
+        destination
+            .apply_all(&[pool_created(), PoolEvent::RebalanceDestination(rebalance)])
+            .unwrap();
+
+        assert_eq!(
+            source.reserves(),
+            PoolReserves {
+                token_a: 500,
+                token_b: 500,
+                pool_token_supply: 500_000,
+            }
+        );
+        assert_eq!(
+            destination.reserves(),
+            PoolReserves {
+                token_a: 1_400,
+                token_b: 1_450,
+                pool_token_supply: 1_400_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_replay_errors_on_underflow() {
+        let mut replay = Replay::new(false);
+        let err = replay
+            .apply(&PoolEvent::Swap {
+                trade_direction: TradeDirection::BtoA,
+                event: event::Swap {
+                    token_in_amount: 1,
+                    token_out_amount: 1,
+                    total_fees: 0,
+                    owner_fees_burned: 0,
+                    self_trade: false,
+                    host_fee: 0,
+                    host_fee_recipient: None,
+                    trade_fee: 0,
+                    trade_fee_numerator: 0,
+                    protocol_fee: 0,
+                    host_fee_in_destination_token: false,
+                    token_a_reserve_after: 0,
+                    token_b_reserve_after: 0,
+                },
+            })
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "Replay underflow: 0 - 1");
+    }
+}
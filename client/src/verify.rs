@@ -0,0 +1,303 @@
+//! Comparison of a deployed pool with the desired state of a config file
+
+use std::{fmt::Display, str::FromStr};
+
+use anchor_client::{anchor_lang::prelude::Pubkey, solana_sdk::signature::Signer};
+use anyhow::Result;
+use hyperplane::{
+    state::{SwapPool, SwapState},
+    utils::seeds::pda,
+    CurveUserParameters,
+};
+use orbit_link::async_client::AsyncClient;
+
+use crate::{client::HyperplaneClient, model::VerifyPoolConfig};
+
+/// A field of the pool compared with the config
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Check {
+    fn new(field: &'static str, expected: impl Display, actual: impl Display) -> Self {
+        Self {
+            field,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// On-chain state of a pool, with the owners of its mints
+#[derive(Debug, PartialEq)]
+pub struct DeployedPool {
+    pub pubkey: Pubkey,
+    pub pool: SwapPool,
+    pub curve: CurveUserParameters,
+    pub token_a_program: Pubkey,
+    pub token_b_program: Pubkey,
+    pub pool_token_program: Pubkey,
+}
+
+pub async fn get_deployed_pool<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool_pubkey: Pubkey,
+) -> Result<DeployedPool> {
+    let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
+    let curve = hyperplane.get_curve_user_parameters(&pool).await?;
+    let rpc = hyperplane.get_rpc();
+    Ok(DeployedPool {
+        pubkey: pool_pubkey,
+        curve,
+        token_a_program: rpc.get_account(&pool.token_a_mint).await?.owner,
+        token_b_program: rpc.get_account(&pool.token_b_mint).await?.owner,
+        pool_token_program: rpc.get_account(&pool.pool_token_mint).await?.owner,
+        pool,
+    })
+}
+
+/// Compare the pool with the config, the optional fields of the config are only checked when set
+pub fn check_pool(
+    program_id: &Pubkey,
+    config: &VerifyPoolConfig,
+    deployed: &DeployedPool,
+) -> Result<Vec<Check>> {
+    let pool = &deployed.pool;
+    let fees = pool.fees();
+    let (pool_authority, _) = pda::pool_authority_pda_program_id(program_id, &deployed.pubkey);
+
+    let mut checks = vec![
+        Check::new(
+            "token_a_mint",
+            Pubkey::from_str(&config.token_a_mint)?,
+            pool.token_a_mint,
+        ),
+        Check::new(
+            "token_b_mint",
+            Pubkey::from_str(&config.token_b_mint)?,
+            pool.token_b_mint,
+        ),
+        Check::new(
+            "curve",
+            format!("{:?}", config.curve),
+            format!("{:?}", deployed.curve),
+        ),
+        Check::new(
+            "trade_fee",
+            fraction(
+                config.fees.trade_fee_numerator,
+                config.fees.trade_fee_denominator,
+            ),
+            fraction(fees.trade_fee_numerator, fees.trade_fee_denominator),
+        ),
+        Check::new(
+            "owner_trade_fee",
+            fraction(
+                config.fees.owner_trade_fee_numerator,
+                config.fees.owner_trade_fee_denominator,
+            ),
+            fraction(
+                fees.owner_trade_fee_numerator,
+                fees.owner_trade_fee_denominator,
+            ),
+        ),
+        Check::new(
+            "owner_withdraw_fee",
+            fraction(
+                config.fees.owner_withdraw_fee_numerator,
+                config.fees.owner_withdraw_fee_denominator,
+            ),
+            fraction(
+                fees.owner_withdraw_fee_numerator,
+                fees.owner_withdraw_fee_denominator,
+            ),
+        ),
+        Check::new(
+            "host_fee",
+            fraction(
+                config.fees.host_fee_numerator,
+                config.fees.host_fee_denominator,
+            ),
+            fraction(fees.host_fee_numerator, fees.host_fee_denominator),
+        ),
+        Check::new("pool_authority", pool_authority, pool.pool_authority),
+    ];
+    if let Some(burn_owner_fees) = config.burn_owner_fees {
+        checks.push(Check::new(
+            "burn_owner_fees",
+            burn_owner_fees,
+            pool.burn_owner_fees(),
+        ));
+    }
+    if let Some(flash_fee_bps) = config.flash_fee_bps {
+        checks.push(Check::new(
+            "flash_fee_bps",
+            flash_fee_bps,
+            pool.flash_fee_bps,
+        ));
+    }
+    if let Some(host_fee_in_destination_token) = config.host_fee_in_destination_token {
+        checks.push(Check::new(
+            "host_fee_in_destination_token",
+            host_fee_in_destination_token,
+            pool.host_fee_in_destination_token(),
+        ));
+    }
+    if let Some(protocol_fee_bps) = config.protocol_fee_bps {
+        checks.push(Check::new(
+            "protocol_fee_bps",
+            protocol_fee_bps,
+            pool.protocol_fee_bps,
+        ));
+    }
+    if let Some(dynamic_fee_numerator) = config.dynamic_fee_numerator {
+        checks.push(Check::new(
+            "dynamic_fee_numerator",
+            dynamic_fee_numerator,
+            pool.dynamic_fee_numerator,
+        ));
+    }
+    if let Some(max_dynamic_trade_fee_numerator) = config.max_dynamic_trade_fee_numerator {
+        checks.push(Check::new(
+            "max_dynamic_trade_fee_numerator",
+            max_dynamic_trade_fee_numerator,
+            pool.max_dynamic_trade_fee_numerator,
+        ));
+    }
+    let optional_pubkeys = [
+        ("admin", &config.admin, pool.admin),
+        (
+            "token_a_program",
+            &config.token_a_program,
+            deployed.token_a_program,
+        ),
+        (
+            "token_b_program",
+            &config.token_b_program,
+            deployed.token_b_program,
+        ),
+        (
+            "pool_token_program",
+            &config.pool_token_program,
+            deployed.pool_token_program,
+        ),
+    ];
+    for (field, expected, actual) in optional_pubkeys {
+        if let Some(expected) = expected {
+            checks.push(Check::new(field, Pubkey::from_str(expected)?, actual));
+        }
+    }
+    Ok(checks)
+}
+
+fn fraction(numerator: u64, denominator: u64) -> String {
+    format!("{}/{}", numerator, denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::curve::fees::Fees;
+
+    use super::*;
+
+    fn fees() -> Fees {
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+        }
+    }
+
+    fn deployed() -> DeployedPool {
+        let pubkey = Pubkey::new_unique();
+        DeployedPool {
+            pubkey,
+            pool: SwapPool {
+                admin: Pubkey::new_unique(),
+                pool_authority: pda::pool_authority_pda(&pubkey).0,
+                token_a_mint: Pubkey::new_unique(),
+                token_b_mint: Pubkey::new_unique(),
+                fees: fees(),
+                ..SwapPool::default()
+            },
+            curve: CurveUserParameters::Stable { amp: 100 },
+            token_a_program: spl_token::id(),
+            token_b_program: spl_token::id(),
+            pool_token_program: spl_token::id(),
+        }
+    }
+
+    fn config(deployed: &DeployedPool) -> VerifyPoolConfig {
+        VerifyPoolConfig {
+            token_a_mint: deployed.pool.token_a_mint.to_string(),
+            token_b_mint: deployed.pool.token_b_mint.to_string(),
+            curve: CurveUserParameters::Stable { amp: 100 },
+            fees: fees(),
+            burn_owner_fees: None,
+            flash_fee_bps: None,
+            dynamic_fee_numerator: None,
+            max_dynamic_trade_fee_numerator: None,
+            protocol_fee_bps: None,
+            host_fee_in_destination_token: None,
+            admin: Some(deployed.pool.admin.to_string()),
+            token_a_program: Some(spl_token::id().to_string()),
+            token_b_program: None,
+            pool_token_program: None,
+        }
+    }
+
+    #[test]
+    fn test_check_pool_matching_config() {
+        let deployed = deployed();
+        let checks = check_pool(&hyperplane::ID, &config(&deployed), &deployed).unwrap();
+
+        assert!(checks.iter().all(Check::passed));
+        assert!(checks.iter().any(|check| check.field == "admin"));
+        assert!(!checks.iter().any(|check| check.field == "token_b_program"));
+    }
+
+    #[test]
+    fn test_check_pool_reports_differences() {
+        let deployed = deployed();
+        let mut config = config(&deployed);
+        config.curve = CurveUserParameters::Stable { amp: 200 };
+        config.fees.host_fee_numerator = 10;
+        config.burn_owner_fees = Some(true);
+
+        let failed: Vec<Check> = check_pool(&hyperplane::ID, &config, &deployed)
+            .unwrap()
+            .into_iter()
+            .filter(|check| !check.passed())
+            .collect();
+
+        assert_eq!(
+            failed,
+            vec![
+                Check::new("curve", "Stable { amp: 200 }", "Stable { amp: 100 }"),
+                Check::new("host_fee", "10/100", "20/100"),
+                Check::new("burn_owner_fees", true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_pool_rejects_invalid_pubkey() {
+        let deployed = deployed();
+        let mut config = config(&deployed);
+        config.admin = Some("not a pubkey".to_string());
+
+        assert!(check_pool(&hyperplane::ID, &config, &deployed).is_err());
+    }
+}
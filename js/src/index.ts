@@ -246,6 +246,8 @@ export class SwapPool {
     initialSupplyA: number,
     initialSupplyB: number,
     curveParameters?: Numberu64,
+    burnOwnerFees = false,
+    strictTokenProgram = false,
     confirmOptions?: ConfirmOptions,
   ): Promise<[SwapPool, PublicKey]> {
     const pool = new Keypair();
@@ -315,6 +317,8 @@ export class SwapPool {
         }),
         initialSupplyA: new Numberu64(initialSupplyA),
         initialSupplyB: new Numberu64(initialSupplyB),
+        burnOwnerFees,
+        strictTokenProgram,
       },
       {
         pool: pool.publicKey,
@@ -371,6 +375,7 @@ export class SwapPool {
    * @param userTransferAuthority Account delegated to transfer user's tokens
    * @param amountIn Amount to transfer from source account
    * @param minimumAmountOut Minimum amount of tokens the user will receive
+   * @param deadline Unix timestamp after which the swap is rejected
    */
   async swap(
     userSource: PublicKey,
@@ -386,6 +391,7 @@ export class SwapPool {
     userTransferAuthority: Keypair,
     amountIn: number | Numberu64,
     minimumAmountOut: number | Numberu64,
+    deadline: number | null = null,
     confirmOptions?: ConfirmOptions,
   ): Promise<TransactionSignature> {
     return await sendAndConfirmTransaction(
@@ -410,6 +416,7 @@ export class SwapPool {
           destinationTokenProgramId,
           amountIn,
           minimumAmountOut,
+          deadline,
         ),
       ),
       [this.admin, userTransferAuthority],
@@ -436,11 +443,13 @@ export class SwapPool {
     destinationTokenProgramId: PublicKey,
     amountIn: number | Numberu64,
     minimumAmountOut: number | Numberu64,
+    deadline: number | null = null,
   ): TransactionInstruction {
     return Instructions.swap(
       {
         amountIn: new Numberu64(amountIn),
         minimumAmountOut: new Numberu64(minimumAmountOut),
+        deadline: deadline === null ? null : new BN(deadline),
       },
       {
         signer: userTransferAuthority,
@@ -472,6 +481,7 @@ export class SwapPool {
    * @param poolTokenAmount Amount of pool tokens to mint
    * @param maximumTokenA The maximum amount of token A to deposit
    * @param maximumTokenB The maximum amount of token B to deposit
+   * @param deadline Unix timestamp after which the deposit is rejected
    */
   async deposit(
     userAccountA: PublicKey,
@@ -483,6 +493,7 @@ export class SwapPool {
     poolTokenAmount: number | Numberu64,
     maximumTokenA: number | Numberu64,
     maximumTokenB: number | Numberu64,
+    deadline: number | null = null,
     confirmOptions?: ConfirmOptions,
   ): Promise<TransactionSignature> {
     return await sendAndConfirmTransaction(
@@ -508,6 +519,9 @@ export class SwapPool {
           poolTokenAmount,
           maximumTokenA,
           maximumTokenB,
+          undefined,
+          undefined,
+          deadline,
         ),
       ),
       [this.admin, userTransferAuthority],
@@ -535,12 +549,16 @@ export class SwapPool {
     poolTokenAmount: number | Numberu64,
     maximumTokenA: number | Numberu64,
     maximumTokenB: number | Numberu64,
+    referrer?: PublicKey,
+    allowedLiquidityProvider?: PublicKey,
+    deadline: number | null = null,
   ): TransactionInstruction {
     return Instructions.deposit(
       {
         poolTokenAmount: new Numberu64(poolTokenAmount),
         maximumTokenAAmount: new Numberu64(maximumTokenA),
         maximumTokenBAmount: new Numberu64(maximumTokenB),
+        deadline: deadline === null ? null : new BN(deadline),
       },
       {
         signer: userTransferAuthority,
@@ -558,6 +576,8 @@ export class SwapPool {
         poolTokenProgram: poolTokenProgramId,
         tokenATokenProgram: tokenProgramIdA,
         tokenBTokenProgram: tokenProgramIdB,
+        referrer: referrer || swapProgramId,
+        allowedLiquidityProvider: allowedLiquidityProvider || swapProgramId,
       },
     );
   }
@@ -574,6 +594,8 @@ export class SwapPool {
    * @param poolTokenAmount Amount of pool tokens to burn
    * @param minimumTokenA The minimum amount of token A to withdraw
    * @param minimumTokenB The minimum amount of token B to withdraw
+   * @param burnDust Also burn the dust pool tokens left in the user account
+   * @param deadline Unix timestamp after which the withdrawal is rejected
    */
   async withdraw(
     userAccountA: PublicKey,
@@ -585,6 +607,8 @@ export class SwapPool {
     poolTokenAmount: number | Numberu64,
     minimumTokenA: number | Numberu64,
     minimumTokenB: number | Numberu64,
+    burnDust = false,
+    deadline: number | null = null,
     confirmOptions?: ConfirmOptions,
   ): Promise<TransactionSignature> {
     return await sendAndConfirmTransaction(
@@ -612,6 +636,8 @@ export class SwapPool {
           poolTokenAmount,
           minimumTokenA,
           minimumTokenB,
+          burnDust,
+          deadline,
         ),
       ),
       [this.admin, userTransferAuthority],
@@ -641,12 +667,16 @@ export class SwapPool {
     poolTokenAmount: number | Numberu64,
     minimumTokenA: number | Numberu64,
     minimumTokenB: number | Numberu64,
+    burnDust = false,
+    deadline: number | null = null,
   ): TransactionInstruction {
     return Instructions.withdraw(
       {
         poolTokenAmount: new Numberu64(poolTokenAmount),
         minimumTokenAAmount: new Numberu64(minimumTokenA),
         minimumTokenBAmount: new Numberu64(minimumTokenB),
+        burnDust,
+        deadline: deadline === null ? null : new BN(deadline),
       },
       {
         signer: userTransferAuthority,
@@ -691,6 +721,9 @@ export class SwapPool {
           this.admin.publicKey,
           this.pool,
           this.authority,
+          this.tokenAVault,
+          this.tokenBVault,
+          this.poolTokenMint,
           feesMint,
           feesVault,
           adminFeesAta,
@@ -707,12 +740,20 @@ export class SwapPool {
     admin: PublicKey,
     pool: PublicKey,
     authority: PublicKey,
+    tokenAVault: PublicKey,
+    tokenBVault: PublicKey,
+    poolTokenMint: PublicKey,
     feesMint: PublicKey,
     feesVault: PublicKey,
     adminFeesAta: PublicKey,
     feesTokenProgram: PublicKey,
     requestedPoolTokenAmount: number | Numberu64,
   ): TransactionInstruction {
+    const [defaultHostFees, _defaultHostFeesBump] =
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('default_host_fees'), pool.toBuffer()],
+        TOKEN_SWAP_PROGRAM_ID,
+      );
     return Instructions.withdrawFees(
       {
         requestedPoolTokenAmount: new Numberu64(requestedPoolTokenAmount),
@@ -721,10 +762,14 @@ export class SwapPool {
         admin,
         pool,
         poolAuthority: authority,
+        tokenAVault,
+        tokenBVault,
+        poolTokenMint,
         feesMint,
         feesVault,
         adminFeesAta,
         feesTokenProgram,
+        defaultHostFees,
       },
     );
   }
@@ -746,6 +791,9 @@ export class SwapPool {
         SwapPool.updatePoolConfigInstruction(
           this.admin.publicKey,
           this.pool,
+          this.tokenAVault,
+          this.tokenBVault,
+          this.poolTokenMint,
           mode,
           value,
         ),
@@ -758,9 +806,17 @@ export class SwapPool {
   static updatePoolConfigInstruction(
     admin: PublicKey,
     pool: PublicKey,
+    tokenAVault: PublicKey,
+    tokenBVault: PublicKey,
+    poolTokenMint: PublicKey,
     mode: UpdatePoolConfigModeKind,
     value: UpdatePoolConfigValueKind,
   ): TransactionInstruction {
+    const [configHistory, _configHistoryBump] =
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('config_history'), pool.toBuffer()],
+        TOKEN_SWAP_PROGRAM_ID,
+      );
     return Instructions.updatePoolConfig(
       {
         mode: mode.discriminator,
@@ -769,6 +825,10 @@ export class SwapPool {
       {
         admin,
         pool,
+        configHistory,
+        tokenAVault,
+        tokenBVault,
+        poolTokenMint,
       },
     );
   }
@@ -6,6 +6,12 @@ export function serializeConfigValue(val: UpdatePoolConfigValueKind): number[] {
     case 'Bool': {
       buffer = Buffer.alloc(32);
       val.value[0] ? buffer.writeUInt8(1, 0) : buffer.writeUInt8(0, 0);
+      break;
+    }
+    case 'U64': {
+      buffer = Buffer.alloc(32);
+      buffer.writeBigUInt64LE(BigInt(val.value[0].toString()), 0);
+      break;
     }
   }
   return [...buffer];
@@ -174,6 +174,8 @@ impl NativeTokenSwap {
                 fees,
                 curve_parameters: curve_params.clone().into(),
                 initial_supply: InitialSupply::new(token_a_amount, token_b_amount),
+                burn_owner_fees: false,
+                strict_token_program: false,
             },
         )
         .unwrap();
@@ -279,6 +281,7 @@ impl NativeTokenSwap {
             ],
         )
         .unwrap();
+        let mut hyperplane_program_account = create_program_account(hyperplane::id());
         let swap_instruction = ix::swap(
             &hyperplane::id(),
             &user_transfer_authority_account.key,
@@ -295,6 +298,9 @@ impl NativeTokenSwap {
             Some(&self.admin_token_a_ata.key),
             &spl_token::id(),
             &spl_token::id(),
+            None,
+            None,
+            None,
             instruction,
         )
         .unwrap();
@@ -316,6 +322,7 @@ impl NativeTokenSwap {
                 self.admin_token_a_ata.as_account_info(),
                 self.token_a_program_account.as_account_info(),
                 self.token_b_program_account.as_account_info(),
+                hyperplane_program_account.as_account_info(),
             ],
         )
     }
@@ -346,6 +353,7 @@ impl NativeTokenSwap {
         )
         .unwrap();
 
+        let mut hyperplane_program_account = create_program_account(hyperplane::id());
         let swap_instruction = ix::swap(
             &hyperplane::id(),
             &user_transfer_authority_account.key,
@@ -362,6 +370,9 @@ impl NativeTokenSwap {
             Some(&self.admin_token_b_ata.key),
             &spl_token::id(),
             &spl_token::id(),
+            None,
+            None,
+            None,
             instruction,
         )
         .unwrap();
@@ -383,6 +394,7 @@ impl NativeTokenSwap {
                 self.admin_token_b_ata.as_account_info(),
                 self.token_b_program_account.as_account_info(),
                 self.token_a_program_account.as_account_info(),
+                hyperplane_program_account.as_account_info(),
             ],
         )
     }
@@ -455,10 +467,13 @@ impl NativeTokenSwap {
             &self.pool_token_program_account.key,
             &spl_token::id(),
             &spl_token::id(),
+            None,
+            None,
             instruction,
         )
         .unwrap();
 
+        let mut hyperplane_program_account = create_program_account(hyperplane::id());
         do_process_instruction(
             deposit_instruction,
             &[
@@ -477,6 +492,8 @@ impl NativeTokenSwap {
                 self.pool_token_program_account.as_account_info(),
                 self.token_a_program_account.as_account_info(),
                 self.token_b_program_account.as_account_info(),
+                hyperplane_program_account.as_account_info(),
+                hyperplane_program_account.as_account_info(),
             ],
         )
     }
@@ -574,6 +591,8 @@ impl NativeTokenSwap {
                 pool_token_amount,
                 minimum_token_a_amount: 0,
                 minimum_token_b_amount: 0,
+                burn_dust: false,
+                deadline: None,
             };
             self.withdraw(pool_account, token_a_account, token_b_account, instruction)
         } else {
@@ -586,15 +605,22 @@ impl NativeTokenSwap {
         admin_a_fees_ata: &mut NativeAccountData,
         instruction: WithdrawFees,
     ) -> ProgramResult {
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&self.pool_account.key);
+        let mut default_host_fees_account =
+            NativeAccountData::new_with_key(default_host_fees, 0, system_program::id());
         let withdraw_instruction = ix::withdraw_fees(
             &hyperplane::id(),
             &self.admin.key,
             &self.pool_account.key,
             &self.pool_authority_account.key,
+            &self.token_a_vault_account.key,
+            &self.token_b_vault_account.key,
+            &self.pool_token_mint_account.key,
             &self.token_a_mint_account.key,
             &self.token_a_fees_vault_account.key,
             &admin_a_fees_ata.key,
             &self.token_a_program_account.key,
+            &default_host_fees,
             instruction,
         )
         .unwrap();
@@ -605,10 +631,14 @@ impl NativeTokenSwap {
                 self.admin.as_account_info(),
                 self.pool_account.as_account_info(),
                 self.pool_authority_account.as_account_info(),
+                self.token_a_vault_account.as_account_info(),
+                self.token_b_vault_account.as_account_info(),
+                self.pool_token_mint_account.as_account_info(),
                 self.token_a_mint_account.as_account_info(),
                 self.token_a_fees_vault_account.as_account_info(),
                 admin_a_fees_ata.as_account_info(),
                 self.token_a_program_account.as_account_info(),
+                default_host_fees_account.as_account_info(),
             ],
         )
     }
@@ -618,15 +648,22 @@ impl NativeTokenSwap {
         admin_b_fees_ata: &mut NativeAccountData,
         instruction: WithdrawFees,
     ) -> ProgramResult {
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&self.pool_account.key);
+        let mut default_host_fees_account =
+            NativeAccountData::new_with_key(default_host_fees, 0, system_program::id());
         let withdraw_instruction = ix::withdraw_fees(
             &hyperplane::id(),
             &self.admin.key,
             &self.pool_account.key,
             &self.pool_authority_account.key,
+            &self.token_a_vault_account.key,
+            &self.token_b_vault_account.key,
+            &self.pool_token_mint_account.key,
             &self.token_b_mint_account.key,
             &self.token_b_fees_vault_account.key,
             &admin_b_fees_ata.key,
             &self.token_b_program_account.key,
+            &default_host_fees,
             instruction,
         )
         .unwrap();
@@ -637,10 +674,14 @@ impl NativeTokenSwap {
                 self.admin.as_account_info(),
                 self.pool_account.as_account_info(),
                 self.pool_authority_account.as_account_info(),
+                self.token_a_vault_account.as_account_info(),
+                self.token_b_vault_account.as_account_info(),
+                self.pool_token_mint_account.as_account_info(),
                 self.token_b_mint_account.as_account_info(),
                 self.token_b_fees_vault_account.as_account_info(),
                 admin_b_fees_ata.as_account_info(),
                 self.token_b_program_account.as_account_info(),
+                default_host_fees_account.as_account_info(),
             ],
         )
     }
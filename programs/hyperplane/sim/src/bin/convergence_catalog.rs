@@ -0,0 +1,23 @@
+//! Print the stable swap inputs which the on-chain solver cannot converge on as Rust test vectors
+//!
+//! `cargo run -p hyperplane-sim --bin convergence_catalog > convergence_cases.rs`
+
+use hyperplane_sim::catalog::{Sweep, ONCHAIN_ITERATIONS};
+
+fn main() {
+    let sweep = Sweep::default();
+    let cases = sweep.run();
+
+    println!(
+        "// {} of {} swept cases need more than {} iterations",
+        cases.len(),
+        sweep.len(),
+        ONCHAIN_ITERATIONS
+    );
+    println!("// (amp, balance_in, balance_out, dx)");
+    println!("pub const CONVERGENCE_CASES: &[(u128, u128, u128, u128)] = &[");
+    for case in cases {
+        println!("    {}", case.to_test_vector());
+    }
+    println!("];");
+}
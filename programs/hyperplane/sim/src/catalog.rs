@@ -0,0 +1,111 @@
+//! Catalog of stable swap inputs the on-chain solver cannot converge on
+//!
+//! The smart contract gives up on D and y after `ONCHAIN_ITERATIONS`, the model after
+//! `MAX_ITERATIONS`. Sweeping amps, balances and trade sizes for cases which only the model solves
+//! gives a fixed set of test vectors to measure on-chain solver improvements against.
+
+use crate::StableSwapModel;
+
+/// Iteration budget of the on-chain D and y solvers
+pub const ONCHAIN_ITERATIONS: u64 = 256;
+
+/// Swap of `dx` into a two coin pool which needs more than `ONCHAIN_ITERATIONS` to solve
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConvergenceCase {
+    pub amp: u128,
+    pub balance_in: u128,
+    pub balance_out: u128,
+    pub dx: u128,
+    pub d_iterations: u64,
+    pub y_iterations: u64,
+}
+
+impl ConvergenceCase {
+    /// `(amp, balance_in, balance_out, dx)` tuple, one entry of the generated test vector array
+    pub fn to_test_vector(&self) -> String {
+        format!(
+            "({}, {}, {}, {}), // d_iterations={}, y_iterations={}",
+            self.amp,
+            self.balance_in,
+            self.balance_out,
+            self.dx,
+            self.d_iterations,
+            self.y_iterations
+        )
+    }
+}
+
+/// Input space to sweep, every combination is solved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sweep {
+    pub amps: Vec<u128>,
+    /// Balance of the destination coin
+    pub balances: Vec<u128>,
+    /// Source balance as a multiple of the destination balance
+    pub imbalance_ratios: Vec<u128>,
+    /// Trade size in basis points of the source balance
+    pub trade_sizes_bps: Vec<u128>,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            amps: vec![1, 10, 100, 1_000, 10_000, 100_000, 1_000_000],
+            balances: vec![1_000, 1_000_000, 1_000_000_000, 1_000_000_000_000_000],
+            imbalance_ratios: vec![1, 10, 1_000, 100_000],
+            trade_sizes_bps: vec![1, 100, 10_000, 1_000_000],
+        }
+    }
+}
+
+impl Sweep {
+    pub fn len(&self) -> usize {
+        self.amps.len()
+            * self.balances.len()
+            * self.imbalance_ratios.len()
+            * self.trade_sizes_bps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cases which the model solves but which exceed the on-chain iteration budget
+    pub fn run(&self) -> Vec<ConvergenceCase> {
+        let mut cases = vec![];
+        for &amp in &self.amps {
+            for &balance_out in &self.balances {
+                for &ratio in &self.imbalance_ratios {
+                    let balance_in = balance_out * ratio;
+                    for &trade_size_bps in &self.trade_sizes_bps {
+                        let dx = balance_in * trade_size_bps / 10_000;
+                        if dx == 0 {
+                            continue;
+                        }
+                        if let Some(case) = Self::solve(amp, balance_in, balance_out, dx) {
+                            cases.push(case);
+                        }
+                    }
+                }
+            }
+        }
+        cases
+    }
+
+    fn solve(amp: u128, balance_in: u128, balance_out: u128, dx: u128) -> Option<ConvergenceCase> {
+        let model = StableSwapModel::new(amp, vec![balance_in, balance_out], vec![1, 1], 2);
+        let d = model.sim_d_convergence();
+        let y = model.sim_y_convergence(0, 1, balance_in + dx);
+        let model_converged = d.converged && y.converged;
+        let onchain_exhausted =
+            d.iterations > ONCHAIN_ITERATIONS || y.iterations > ONCHAIN_ITERATIONS;
+        (model_converged && onchain_exhausted).then_some(ConvergenceCase {
+            amp,
+            balance_in,
+            balance_out,
+            dx,
+            d_iterations: d.iterations,
+            y_iterations: y.iterations,
+        })
+    }
+}
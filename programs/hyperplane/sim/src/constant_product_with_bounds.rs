@@ -0,0 +1,86 @@
+/// An off-chain implementation of the constant product invariant bounded to a price range
+///
+/// Differences from smart contract impl:
+///
+/// - Use unlimited size numbers (BigInt), amounts and square root prices scaled to 18 dp
+/// - Solves the liquidity `L` of `(x + L / sqrt(max_price)) * (y + L * sqrt(min_price)) = L^2`
+///   directly, instead of the virtual token A reserve `L / sqrt(max_price)`
+///
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+
+/// Scale of the `min_price` and `max_price` given to the model, the same as the smart contract
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+
+#[derive(Clone, Debug)]
+pub struct ConstantProductWithBoundsModel {
+    pub sqrt_min_price: BigInt,
+    pub sqrt_max_price: BigInt,
+    pub balance_a: BigInt,
+    pub balance_b: BigInt,
+}
+
+fn scale() -> BigInt {
+    BigInt::from(10).pow(18)
+}
+
+impl ConstantProductWithBoundsModel {
+    /// Prices of token A in token B native units, scaled by `PRICE_SCALE`
+    pub fn new(
+        min_price: u128,
+        max_price: u128,
+        balance_a: u128,
+        balance_b: u128,
+    ) -> ConstantProductWithBoundsModel {
+        // sqrt(price / PRICE_SCALE) * 10^18
+        let sqrt_price = |price: u128| (BigInt::from(price) * BigInt::from(10).pow(27)).sqrt();
+        Self {
+            sqrt_min_price: sqrt_price(min_price),
+            sqrt_max_price: sqrt_price(max_price),
+            balance_a: BigInt::from(balance_a) * scale(),
+            balance_b: BigInt::from(balance_b) * scale(),
+        }
+    }
+
+    /// Liquidity of the pool, scaled to 18 dp
+    pub fn sim_liquidity(&self) -> BigInt {
+        let s = scale();
+        // (1 - sqrt(min_price / max_price)) * L^2
+        //   - (x * sqrt(min_price) + y / sqrt(max_price)) * L - x * y = 0
+        let a = &s - &self.sqrt_min_price * &s / &self.sqrt_max_price;
+        let b = &self.balance_a * &self.sqrt_min_price / &s
+            + &self.balance_b * &s / &self.sqrt_max_price;
+        let c = &self.balance_a * &self.balance_b / &s;
+        let discriminant = &b * &b + BigInt::from(4) * &a * &c;
+        (b + discriminant.sqrt()) * &s / (BigInt::from(2) * a)
+    }
+
+    /// Token A and token B reserves of the constant product curve the range is a section of,
+    /// scaled to 18 dp
+    pub fn sim_virtual_reserves(&self) -> (BigInt, BigInt) {
+        let s = scale();
+        let liquidity = self.sim_liquidity();
+        (
+            &self.balance_a + &liquidity * &s / &self.sqrt_max_price,
+            &self.balance_b + &liquidity * &self.sqrt_min_price / &s,
+        )
+    }
+
+    /// Amount of token B received for `dx` of token A, or token A for token B when `a_to_b` is
+    /// unset, `None` if the pool does not hold the amount
+    pub fn sim_swap(&self, a_to_b: bool, dx: u128) -> Option<u128> {
+        let (virtual_a, virtual_b) = self.sim_virtual_reserves();
+        let (source, destination, balance) = if a_to_b {
+            (virtual_a, virtual_b, &self.balance_b)
+        } else {
+            (virtual_b, virtual_a, &self.balance_a)
+        };
+        let new_source = &source + BigInt::from(dx) * scale();
+        let new_destination = &source * &destination / new_source;
+        let dy = destination - new_destination;
+        if dy > *balance || dy <= BigInt::zero() {
+            return None;
+        }
+        (dy / scale()).to_u128()
+    }
+}
@@ -11,6 +11,10 @@
 ///
 extern crate core;
 
+pub mod catalog;
+pub mod constant_product_with_bounds;
+pub mod weighted;
+
 use num_bigint::BigInt;
 use num_traits::{One, ToPrimitive, Zero};
 
@@ -20,9 +24,51 @@ const DEFAULT_TARGET_PRICE: u128 = 1_000_000_000_000_000_000;
 pub const MODEL_FEE_NUMERATOR: u128 = 1;
 pub const MODEL_FEE_DENOMINATOR: u128 = 1000;
 
+/// Linear ramp of the amplification coefficient, `time` is in the unit of the caller (e.g. slots)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmpSchedule {
+    pub start_amp: u128,
+    pub target_amp: u128,
+    pub duration: u64,
+}
+
+impl AmpSchedule {
+    /// Amp at `time` after the start of the ramp, `target_amp` once the ramp is over
+    pub fn amp_at(&self, time: u64) -> u128 {
+        if time >= self.duration {
+            return self.target_amp;
+        }
+        let elapsed = time as u128;
+        let duration = self.duration as u128;
+        if self.target_amp >= self.start_amp {
+            self.start_amp + (self.target_amp - self.start_amp) * elapsed / duration
+        } else {
+            self.start_amp - (self.start_amp - self.target_amp) * elapsed / duration
+        }
+    }
+}
+
+/// Result of an iterative solve, with the number of iterations it took
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Convergence {
+    pub value: u128,
+    pub iterations: u64,
+    /// The last two estimates are within 1 of each other, unset if `MAX_ITERATIONS` ran out first
+    pub converged: bool,
+}
+
+/// Quote of a swap at a given time of an amp ramp
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduledQuote {
+    pub time: u64,
+    pub amp: u128,
+    pub dy: u128,
+}
+
 #[derive(Clone, Debug)]
 pub struct StableSwapModel {
     pub amp_factor: BigInt,
+    pub amp_schedule: Option<AmpSchedule>,
     pub balances: Vec<BigInt>,
     pub rates: Vec<BigInt>,
     pub n_coins: BigInt,
@@ -50,6 +96,7 @@ impl StableSwapModel {
     ) -> StableSwapModel {
         Self {
             amp_factor: BigInt::from(amp_factor),
+            amp_schedule: None,
             rates: rates.iter().map(|x| BigInt::from(*x)).collect(),
             balances: balances.iter().map(|x| BigInt::from(*x)).collect(),
             n_coins: BigInt::from(n_coins),
@@ -62,6 +109,61 @@ impl StableSwapModel {
         }
     }
 
+    /// Ramp the amp from `start_amp` to `target_amp` over `duration`, the model starts at `start_amp`
+    pub fn with_amp_schedule(mut self, start_amp: u128, target_amp: u128, duration: u64) -> Self {
+        self.amp_factor = BigInt::from(start_amp);
+        self.amp_schedule = Some(AmpSchedule {
+            start_amp,
+            target_amp,
+            duration,
+        });
+        self
+    }
+
+    /// Move the model to `time` of the amp schedule, a no-op without a schedule
+    pub fn set_time(&mut self, time: u64) {
+        if let Some(schedule) = &self.amp_schedule {
+            self.amp_factor = BigInt::from(schedule.amp_at(time));
+        }
+    }
+
+    /// Quote swapping `dx` of coin `i` for coin `j` every `step` of the amp schedule, balances are left untouched
+    pub fn sim_dy_schedule(&self, i: u128, j: u128, dx: u128, step: u64) -> Vec<ScheduledQuote> {
+        assert!(step > 0, "step must be greater than 0");
+        let duration = self
+            .amp_schedule
+            .as_ref()
+            .map(|schedule| schedule.duration)
+            .unwrap_or_default();
+        let mut model = self.clone();
+        let mut quotes = vec![];
+        let mut time = 0;
+        loop {
+            model.set_time(time);
+            quotes.push(ScheduledQuote {
+                time,
+                amp: model.amp_factor.to_u128().unwrap(),
+                dy: model.sim_dy(i, j, dx),
+            });
+            if time >= duration {
+                break;
+            }
+            time = (time + step).min(duration);
+        }
+        quotes
+    }
+
+    /// Largest change of the quote between two consecutive steps of the amp schedule
+    ///
+    /// Bounds the value an arbitrageur can extract from the ramp per step, with unchanged balances
+    pub fn sim_max_dy_move(&self, i: u128, j: u128, dx: u128, step: u64) -> u128 {
+        self.sim_dy_schedule(i, j, dx, step)
+            .windows(2)
+            .map(|w| w[0].dy.abs_diff(w[1].dy))
+            .max()
+            .unwrap_or_default()
+    }
+
     pub fn sim_xp(&self) -> Vec<BigInt> {
         self.balances
             .iter()
@@ -73,6 +175,10 @@ impl StableSwapModel {
     }
 
     pub fn sim_d(&self) -> u128 {
+        self.sim_d_convergence().value
+    }
+
+    pub fn sim_d_convergence(&self) -> Convergence {
         let mut d_prev = BigInt::zero();
         let xp = self.sim_xp();
         let s = xp.iter().fold(BigInt::zero(), |acc, x| acc + x);
@@ -95,7 +201,11 @@ impl StableSwapModel {
             d = numerator / denominator;
             iterations += 1;
         }
-        d.to_u128().unwrap()
+        Convergence {
+            converged: d.abs_diff(&d_prev) <= BigInt::one(),
+            value: d.to_u128().unwrap(),
+            iterations,
+        }
     }
 
     pub fn sim_dy(&self, i: u128, j: u128, dx: u128) -> u128 {
@@ -118,6 +228,10 @@ impl StableSwapModel {
     }
 
     pub fn sim_y(&self, i: u128, j: u128, x: u128) -> u128 {
+        self.sim_y_convergence(i, j, x).value
+    }
+
+    pub fn sim_y_convergence(&self, i: u128, j: u128, x: u128) -> Convergence {
         let d = BigInt::from(self.sim_d());
         let mut xx = self.sim_xp();
         xx[i as usize] = BigInt::from(x);
@@ -157,7 +271,11 @@ impl StableSwapModel {
             y = (y.pow(2) + &c) / (2 * &y + &b);
             iterations += 1;
         }
-        y.to_u128().unwrap()
+        Convergence {
+            converged: y.abs_diff(&y_prev) <= BigInt::one(),
+            value: y.to_u128().unwrap(),
+            iterations,
+        }
     }
 
     pub fn sim_y_d(&mut self, i: u128, d: u128) -> u128 {
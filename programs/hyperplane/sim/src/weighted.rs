@@ -0,0 +1,122 @@
+/// An off-chain implementation of the two token weighted constant product invariant
+///
+/// Differences from smart contract impl:
+///
+/// - Use unlimited size numbers (BigInt) and the integer weights as exponents, so the invariant
+///   `x^weight_a * y^weight_b` is computed exactly instead of with fixed point logs and exps
+/// - Solves swaps and single sided deposits by bisection on the exact invariant
+///
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+
+#[derive(Clone, Debug)]
+pub struct WeightedModel {
+    pub weight_a: u32,
+    pub weight_b: u32,
+    pub balance_a: BigInt,
+    pub balance_b: BigInt,
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Largest value in `[0, max]` for which `holds` is true, `holds` being true up to some value
+fn bisect(max: u128, holds: impl Fn(u128) -> bool) -> u128 {
+    let (mut low, mut high) = (0, max);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if holds(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+impl WeightedModel {
+    /// The weights are reduced by their greatest common divisor to keep the invariant small
+    pub fn new(weight_a: u64, weight_b: u64, balance_a: u128, balance_b: u128) -> WeightedModel {
+        let divisor = gcd(weight_a, weight_b);
+        Self {
+            weight_a: (weight_a / divisor) as u32,
+            weight_b: (weight_b / divisor) as u32,
+            balance_a: BigInt::from(balance_a),
+            balance_b: BigInt::from(balance_b),
+        }
+    }
+
+    /// `balance_a^weight_a * balance_b^weight_b`
+    pub fn sim_invariant(&self, balance_a: &BigInt, balance_b: &BigInt) -> BigInt {
+        balance_a.pow(self.weight_a) * balance_b.pow(self.weight_b)
+    }
+
+    /// Largest amount of token B received for `dx` of token A, or token A for token B when
+    /// `a_to_b` is unset, without decreasing the invariant
+    pub fn sim_swap(&self, a_to_b: bool, dx: u128) -> u128 {
+        let invariant = self.sim_invariant(&self.balance_a, &self.balance_b);
+        let dx = BigInt::from(dx);
+        let max = if a_to_b {
+            &self.balance_b
+        } else {
+            &self.balance_a
+        };
+        bisect(max.to_u128().unwrap(), |dy| {
+            let dy = BigInt::from(dy);
+            let (balance_a, balance_b) = if a_to_b {
+                (&self.balance_a + &dx, &self.balance_b - &dy)
+            } else {
+                (&self.balance_a - &dy, &self.balance_b + &dx)
+            };
+            self.sim_invariant(&balance_a, &balance_b) >= invariant
+        })
+    }
+
+    /// Largest amount of pool tokens minted for a deposit of `dx` of token A only, or token B
+    /// when `a_side` is unset, without decreasing the invariant per pool token
+    ///
+    /// The normalized invariant `invariant^(1 / (weight_a + weight_b))` must grow at least as
+    /// much as the supply
+    pub fn sim_deposit_single(&self, a_side: bool, dx: u128, pool_token_supply: u128) -> u128 {
+        let dx = BigInt::from(dx);
+        let (balance_a, balance_b, balance) = if a_side {
+            (
+                &self.balance_a + &dx,
+                self.balance_b.clone(),
+                &self.balance_a,
+            )
+        } else {
+            (
+                self.balance_a.clone(),
+                &self.balance_b + &dx,
+                &self.balance_b,
+            )
+        };
+        let invariant = self.sim_invariant(&self.balance_a, &self.balance_b);
+        let new_invariant = self.sim_invariant(&balance_a, &balance_b);
+        let weights = self.weight_a + self.weight_b;
+        let supply = BigInt::from(pool_token_supply);
+        let supply_power = supply.pow(weights);
+        // the invariant grows less than the deposited balance, a weight being at most 1
+        let max: BigInt = &supply * &dx / balance + 1;
+        bisect(max.to_u128().unwrap(), |pool_tokens| {
+            let new_supply = &supply + BigInt::from(pool_tokens);
+            // (new_supply / supply)^weights <= new_invariant / invariant
+            new_supply.pow(weights) * &invariant <= &new_invariant * &supply_power
+        })
+    }
+
+    /// Whether moving the balances to `balance_a` and `balance_b` keeps the invariant
+    pub fn sim_invariant_holds(&self, balance_a: u128, balance_b: u128) -> bool {
+        let (balance_a, balance_b) = (BigInt::from(balance_a), BigInt::from(balance_b));
+        !balance_a.is_zero()
+            && !balance_b.is_zero()
+            && self.sim_invariant(&balance_a, &balance_b)
+                >= self.sim_invariant(&self.balance_a, &self.balance_b)
+    }
+}
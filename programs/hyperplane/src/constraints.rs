@@ -33,8 +33,12 @@ pub struct SwapConstraints<'a> {
     pub valid_curve_types: &'a [CurveType],
     /// Valid fees
     pub fees: &'a Fees,
+    /// Protocol share of the owner trade fees of every pool, in basis points
+    pub protocol_fee_bps: u64,
     /// token_2022 trading token blocked extensions
     pub blocked_trading_token_extensions: &'a [ExtensionType],
+    /// Both trading tokens must be owned by the same token program, whatever the pool creator asks
+    pub strict_token_program: bool,
 }
 
 impl<'a> SwapConstraints<'a> {
@@ -81,6 +85,15 @@ impl<'a> SwapConstraints<'a> {
         }
     }
 
+    /// Checks that the provided protocol fee is the one fixed by the constraints
+    pub fn validate_protocol_fee_bps(&self, protocol_fee_bps: u64) -> Result<()> {
+        if protocol_fee_bps == self.protocol_fee_bps {
+            Ok(())
+        } else {
+            err!(SwapError::InvalidFee)
+        }
+    }
+
     /// Checks that the provided admin is valid for the given constraints
     pub fn validate_token_2022_trading_token_extensions(
         &self,
@@ -114,16 +127,21 @@ const FEES: &Fees = &Fees {
     host_fee_denominator: 100,
 };
 #[cfg(feature = "production")]
+const PROTOCOL_FEE_BPS: u64 = 0;
+#[cfg(feature = "production")]
 const VALID_CURVE_TYPES: &[CurveType] = &[CurveType::ConstantPrice, CurveType::ConstantProduct];
 #[cfg(feature = "production")]
 const INVALID_TOKEN_2022_EXTENSIONS: &[ExtensionType] = &[ExtensionType::TransferFeeConfig];
+#[cfg(feature = "production")]
+const STRICT_TOKEN_PROGRAM: bool = false;
 
 /// Fee structure defined by program creator in order to enforce certain
 /// fees when others use the program.  Adds checks on pool creation and
 /// swapping to ensure the correct fees and account owners are passed.
 /// Fees provided during production build currently are considered min
 /// fees that creator of the pool can specify. Host fee is a fixed
-/// percentage that host receives as a portion of owner fees
+/// percentage that host receives as a portion of owner fees, and so is the
+/// protocol fee
 pub const SWAP_CONSTRAINTS: Option<SwapConstraints> = {
     #[cfg(feature = "production")]
     {
@@ -131,7 +149,9 @@ pub const SWAP_CONSTRAINTS: Option<SwapConstraints> = {
             owner_key: OWNER_KEY,
             valid_curve_types: VALID_CURVE_TYPES,
             fees: FEES,
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
             blocked_trading_token_extensions: INVALID_TOKEN_2022_EXTENSIONS,
+            strict_token_program: STRICT_TOKEN_PROGRAM,
         })
     }
     #[cfg(not(feature = "production"))]
@@ -198,7 +218,9 @@ mod tests {
             owner_key,
             valid_curve_types: &[curve_type],
             fees: &valid_fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         };
 
         constraints.validate_curve(&swap_curve).unwrap();
@@ -250,6 +272,12 @@ mod tests {
         );
         fees.owner_trade_fee_denominator = owner_trade_fee_denominator;
 
+        constraints.validate_protocol_fee_bps(0).unwrap();
+        assert_eq!(
+            Err(SwapError::InvalidFee.into()),
+            constraints.validate_protocol_fee_bps(1_000),
+        );
+
         let swap_curve = SwapCurve {
             curve_type: CurveType::ConstantPrice,
             calculator: Arc::new(calculator),
@@ -269,7 +297,9 @@ mod tests {
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         };
 
         constraints.validate_admin(&key).unwrap();
@@ -284,7 +314,9 @@ mod tests {
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         };
 
         let res = constraints.validate_admin(&Pubkey::new_unique());
@@ -318,7 +350,9 @@ mod tests {
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         };
 
         constraints
@@ -353,7 +387,9 @@ mod tests {
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[ExtensionType::TransferFeeConfig],
+            strict_token_program: false,
         };
 
         let res = constraints.validate_token_2022_trading_token_extensions(&mint_info);
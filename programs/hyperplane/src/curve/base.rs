@@ -2,7 +2,7 @@
 
 use std::{fmt::Debug, sync::Arc};
 
-use anchor_lang::Result;
+use anchor_lang::{error, Result};
 #[cfg(feature = "fuzz")]
 use arbitrary::Arbitrary;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -11,9 +11,15 @@ use crate::{
     curve::{
         calculator::{CurveCalculator, SwapWithoutFeesResult, TradeDirection},
         fees::Fees,
+        oracle_pegged::OraclePeggedCalculator,
     },
+    error::SwapError,
     model::CurveParameters,
-    state::{ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve},
+    require_msg,
+    state::{
+        ConstantPriceCurve, ConstantProductCurve, ConstantProductWithBoundsCurve, OffsetCurve,
+        OraclePeggedCurve, PeggedCurve, StableCurve, WeightedCurve,
+    },
     try_math,
     utils::math::TryMath,
 };
@@ -31,6 +37,25 @@ pub enum CurveType {
     Offset = 3,
     /// Stable curve, like constant product with less slippage around a fixed price
     Stable = 4,
+    /// Stable curve around a configurable peg, like constant product far from the peg
+    Pegged = 5,
+    /// Constant product curve providing liquidity only within a price range, with virtual reserves
+    ConstantProductWithBounds = 6,
+    /// Balancer-style constant product of the reserves raised to their weights, e.g. 80/20
+    Weighted = 7,
+    /// Constant price read from a Pyth or Scope price account on every swap, with a spread
+    OraclePegged = 8,
+}
+
+impl CurveType {
+    /// Whether the curve has balanced reserves to compare with, see
+    /// [crate::curve::calculator::CurveCalculator::reserves_imbalance_bps]
+    pub fn measures_reserves_imbalance(self) -> bool {
+        matches!(
+            self,
+            CurveType::Stable | CurveType::Pegged | CurveType::OraclePegged
+        )
+    }
 }
 
 /// Encodes all results of swapping from a source token to a destination token
@@ -56,6 +81,36 @@ pub struct SwapResult {
     pub owner_fee: u128,
 }
 
+/// Encodes all results of a deposit of a single trading token
+#[derive(Debug, PartialEq)]
+pub struct DepositSingleResult {
+    /// Amount of pool tokens minted to the user
+    pub pool_token_amount: u128,
+    /// Amount of source token deposited, net of fees
+    pub source_amount_deposited: u128,
+    /// Amount of source token to transfer to the vault (trade_fee + source_amount_deposited)
+    pub source_amount_to_vault: u128,
+    /// Amount of source tokens going to pool holders
+    pub trade_fee: u128,
+    /// Amount of source tokens going to owner
+    pub owner_fee: u128,
+}
+
+/// Encodes the result of a single token withdrawal
+#[derive(Debug, PartialEq)]
+pub struct WithdrawSingleResult {
+    /// Amount of pool tokens burned from the user
+    pub pool_token_amount: u128,
+    /// Amount of destination token the pool tokens are worth, the user amount and fees included
+    pub destination_amount_withdrawn: u128,
+    /// Amount of destination tokens left in the pool for pool holders
+    pub trade_fee: u128,
+    /// Amount of destination tokens going to owner
+    pub owner_fee: u128,
+    /// Amount of destination tokens going to owner as withdrawal fee
+    pub withdraw_fee: u128,
+}
+
 /// Concrete struct to wrap around the trait object which performs calculation.
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -77,10 +132,14 @@ impl SwapCurve {
                     ..Default::default()
                 }),
             },
-            CurveParameters::ConstantPrice { token_b_price } => SwapCurve {
+            CurveParameters::ConstantPrice {
+                token_b_price,
+                spread_bps,
+            } => SwapCurve {
                 curve_type: CurveType::ConstantPrice,
                 calculator: Arc::new(ConstantPriceCurve {
                     token_b_price,
+                    spread_bps,
                     ..Default::default()
                 }),
             },
@@ -99,6 +158,54 @@ impl SwapCurve {
                 curve_type: CurveType::Stable,
                 calculator: Arc::new(StableCurve::new(amp, token_a_decimals, token_b_decimals)?),
             },
+            CurveParameters::Pegged {
+                amp,
+                token_b_peg,
+                token_a_decimals,
+                token_b_decimals,
+            } => SwapCurve {
+                curve_type: CurveType::Pegged,
+                calculator: Arc::new(PeggedCurve::new(
+                    amp,
+                    token_b_peg,
+                    token_a_decimals,
+                    token_b_decimals,
+                )?),
+            },
+            CurveParameters::ConstantProductWithBounds {
+                min_price,
+                max_price,
+            } => SwapCurve {
+                curve_type: CurveType::ConstantProductWithBounds,
+                calculator: Arc::new(ConstantProductWithBoundsCurve::new(min_price, max_price)),
+            },
+            CurveParameters::Weighted { weight_a, weight_b } => SwapCurve {
+                curve_type: CurveType::Weighted,
+                calculator: Arc::new(WeightedCurve::new(weight_a, weight_b)),
+            },
+            CurveParameters::OraclePegged {
+                oracle,
+                oracle_type,
+                scope_price_index,
+                max_age_slots,
+                spread_bps,
+                token_a_decimals,
+                token_b_decimals,
+            } => SwapCurve {
+                curve_type: CurveType::OraclePegged,
+                calculator: Arc::new(OraclePeggedCalculator::new(
+                    OraclePeggedCurve::new(
+                        oracle,
+                        oracle_type,
+                        scope_price_index,
+                        max_age_slots,
+                        spread_bps,
+                        token_a_decimals,
+                        token_b_decimals,
+                    ),
+                    None,
+                )),
+            },
         };
         Ok(curve)
     }
@@ -120,15 +227,53 @@ impl SwapCurve {
         let total_fees = try_math!(trade_fee.try_add(owner_fee))?;
         let source_amount_less_fees = try_math!(source_amount.try_sub(total_fees))?;
 
-        let SwapWithoutFeesResult {
-            source_amount_swapped,
-            destination_amount_swapped,
-        } = self.calculator.swap_without_fees(
-            source_amount_less_fees,
+        require_msg!(
+            pool_destination_amount > 0,
+            SwapError::EmptyReserves,
+            "EmptyReserves: pool_destination_amount=0"
+        );
+
+        if let Some(max_source_amount) = self.calculator.max_source_amount(
             pool_source_amount,
             pool_destination_amount,
             trade_direction,
-        )?;
+        )? {
+            require_msg!(
+                source_amount_less_fees <= max_source_amount,
+                SwapError::TradeTooLargeForConvergence,
+                &format!(
+                    "TradeTooLargeForConvergence: source_amount_less_fees={} > max_source_amount={}",
+                    source_amount_less_fees, max_source_amount
+                )
+            );
+        }
+
+        let SwapWithoutFeesResult {
+            source_amount_swapped,
+            destination_amount_swapped,
+        } = self
+            .calculator
+            .swap_without_fees(
+                source_amount_less_fees,
+                pool_source_amount,
+                pool_destination_amount,
+                trade_direction,
+            )
+            .map_err(|e| {
+                if e == SwapError::CalculationFailure.into() {
+                    error!(SwapError::CurveOverflow)
+                } else {
+                    e
+                }
+            })?;
+        require_msg!(
+            destination_amount_swapped <= pool_destination_amount,
+            SwapError::EmptyReserves,
+            &format!(
+                "EmptyReserves: destination_amount_swapped={} > pool_destination_amount={}",
+                destination_amount_swapped, pool_destination_amount
+            )
+        );
 
         let source_amount_to_vault = try_math!(source_amount_swapped.try_add(trade_fee))?;
         let total_source_amount_swapped = try_math!(source_amount_swapped.try_add(total_fees))?;
@@ -146,6 +291,92 @@ impl SwapCurve {
             owner_fee,
         })
     }
+
+    /// Subtract fees and calculate the pool tokens minted for a deposit of a single token.
+    ///
+    /// Half of the deposit is implicitly swapped for the other token, so the trading and owner
+    /// fees are charged on that half.
+    pub fn deposit_single_token_type(
+        &self,
+        source_amount: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        pool_token_supply: u128,
+        trade_direction: TradeDirection,
+        fees: &Fees,
+    ) -> Result<DepositSingleResult> {
+        let half_source_amount = std::cmp::max(1, try_math!(source_amount.try_div(2))?);
+        let trade_fee = try_math!(fees.trading_fee(half_source_amount))?;
+        let owner_fee = try_math!(fees.owner_trading_fee(half_source_amount))?;
+        let total_fees = try_math!(trade_fee.try_add(owner_fee))?;
+        let source_amount_deposited = try_math!(source_amount.try_sub(total_fees))?;
+
+        let pool_token_amount = self.calculator.deposit_single_token_type(
+            source_amount_deposited,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            pool_token_supply,
+            trade_direction,
+        )?;
+        Ok(DepositSingleResult {
+            pool_token_amount,
+            source_amount_deposited,
+            source_amount_to_vault: try_math!(source_amount_deposited.try_add(trade_fee))?,
+            trade_fee,
+            owner_fee,
+        })
+    }
+
+    /// Get the amount of pool tokens to burn for a withdrawal of exactly `destination_amount` of
+    /// one of the trading tokens
+    ///
+    /// As for a single token deposit, half of the withdrawal is implicitly swapped, so the trade and
+    /// owner fees are charged on that half. The owner withdrawal fee is charged on the whole amount.
+    pub fn withdraw_single_token_type_exact_out(
+        &self,
+        destination_amount: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        pool_token_supply: u128,
+        trade_direction: TradeDirection,
+        fees: &Fees,
+    ) -> Result<WithdrawSingleResult> {
+        let half_destination_amount = std::cmp::max(1, try_math!(destination_amount.try_div(2))?);
+        let trade_fee = try_math!(fees.trading_fee(half_destination_amount))?;
+        let owner_fee = try_math!(fees.owner_trading_fee(half_destination_amount))?;
+        let withdraw_fee = try_math!(fees.owner_withdraw_fee(destination_amount))?;
+        let destination_amount_withdrawn = try_math!(destination_amount
+            .try_add(trade_fee)?
+            .try_add(owner_fee)?
+            .try_add(withdraw_fee))?;
+        let pool_destination_amount = match trade_direction {
+            TradeDirection::AtoB => pool_token_a_amount,
+            TradeDirection::BtoA => pool_token_b_amount,
+        };
+        require_msg!(
+            destination_amount_withdrawn < pool_destination_amount,
+            SwapError::EmptyReserves,
+            &format!(
+                "EmptyReserves: destination_amount_withdrawn={} >= pool_destination_amount={}",
+                destination_amount_withdrawn, pool_destination_amount
+            )
+        );
+
+        let pool_token_amount = self.calculator.withdraw_single_token_type_exact_out(
+            destination_amount_withdrawn,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            pool_token_supply,
+            trade_direction,
+        )?;
+        Ok(WithdrawSingleResult {
+            pool_token_amount,
+            destination_amount_withdrawn,
+            trade_fee,
+            owner_fee,
+            withdraw_fee,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -273,4 +504,147 @@ mod test {
         assert_eq!(result.destination_amount_swapped, 4545);
         assert_eq!(result.new_pool_destination_amount, 45455);
     }
+
+    #[test]
+    fn constant_product_deposit_single_token_type() {
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve::default()),
+        };
+        // supply * (sqrt(1_100 * 1_000) / 1_000 - 1) = 48.8
+        let result = swap_curve
+            .deposit_single_token_type(
+                100,
+                1_000,
+                1_000,
+                1_000,
+                TradeDirection::AtoB,
+                &Fees::default(),
+            )
+            .unwrap();
+        assert_eq!(result.pool_token_amount, 48);
+        assert_eq!(result.source_amount_to_vault, 100);
+
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        };
+        // fees on the half swapped, supply * (sqrt(1_098 * 1_000) / 1_000 - 1) = 47.9
+        let result = swap_curve
+            .deposit_single_token_type(100, 1_000, 1_000, 1_000, TradeDirection::BtoA, &fees)
+            .unwrap();
+        assert_eq!(
+            result,
+            DepositSingleResult {
+                pool_token_amount: 47,
+                source_amount_deposited: 98,
+                source_amount_to_vault: 99,
+                trade_fee: 1,
+                owner_fee: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn constant_product_withdraw_single_token_type_exact_out() {
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve::default()),
+        };
+        // supply * (1 - sqrt(900 * 1_000) / 1_000) = 51.3, rounded up
+        let result = swap_curve
+            .withdraw_single_token_type_exact_out(
+                100,
+                1_000,
+                1_000,
+                1_000,
+                TradeDirection::AtoB,
+                &Fees::default(),
+            )
+            .unwrap();
+        assert_eq!(result.pool_token_amount, 52);
+        assert_eq!(result.destination_amount_withdrawn, 100);
+
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+            ..Fees::default()
+        };
+        // supply * (1 - sqrt(897 * 1_000) / 1_000) = 52.9, rounded up
+        let result = swap_curve
+            .withdraw_single_token_type_exact_out(
+                100,
+                1_000,
+                1_000,
+                1_000,
+                TradeDirection::BtoA,
+                &fees,
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            WithdrawSingleResult {
+                pool_token_amount: 53,
+                destination_amount_withdrawn: 103,
+                trade_fee: 1,
+                owner_fee: 1,
+                withdraw_fee: 1,
+            }
+        );
+
+        assert_eq!(
+            swap_curve.withdraw_single_token_type_exact_out(
+                1_000,
+                1_000,
+                1_000,
+                1_000,
+                TradeDirection::AtoB,
+                &Fees::default(),
+            ),
+            Err(SwapError::EmptyReserves.into())
+        );
+    }
+
+    #[test]
+    fn typed_curve_errors() {
+        let fees = Fees::default();
+        let constant_product = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve::default()),
+        };
+        assert_eq!(
+            constant_product.swap(100, 1_000, 0, TradeDirection::AtoB, &fees),
+            Err(SwapError::EmptyReserves.into())
+        );
+        assert_eq!(
+            constant_product.swap(
+                100,
+                u128::MAX / 2,
+                u128::MAX / 2,
+                TradeDirection::AtoB,
+                &fees
+            ),
+            Err(SwapError::CurveOverflow.into())
+        );
+
+        // the offset pays out of the faked token B liquidity, beyond the real reserve
+        let offset = SwapCurve {
+            curve_type: CurveType::Offset,
+            calculator: Arc::new(OffsetCurve {
+                token_b_offset: 1_000_000,
+                ..Default::default()
+            }),
+        };
+        assert_eq!(
+            offset.swap(1_000, 1_000, 10, TradeDirection::AtoB, &fees),
+            Err(SwapError::EmptyReserves.into())
+        );
+    }
 }
@@ -2,12 +2,17 @@
 
 use std::fmt::Debug;
 
-use anchor_lang::Result;
+use anchor_lang::{error, error::Error, AnchorDeserialize, AnchorSerialize, Result};
 #[cfg(feature = "fuzz")]
 use arbitrary::Arbitrary;
 use spl_math::precise_number::PreciseNumber;
+use strum::EnumString;
 
-use crate::{error::SwapError, require_msg};
+use crate::{
+    error::SwapError,
+    require_msg, try_math,
+    utils::math::{TryMath, TryMathRef, TryNew},
+};
 
 /// Initial amount of pool tokens for swap contract, hard-coded to something
 /// "sensible" given a maximum of u128.
@@ -23,7 +28,7 @@ pub const TOKENS_IN_POOL: u128 = 2;
 /// token differently (by adding offsets or weights)
 #[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, EnumString)]
 pub enum TradeDirection {
     /// Input token A, output token B
     AtoB,
@@ -32,7 +37,7 @@ pub enum TradeDirection {
 }
 
 /// Utility to represent either token A or token B
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub enum AorB {
     A,
     B,
@@ -78,6 +83,26 @@ pub struct TradingTokenResult {
     pub token_b_amount: u128,
 }
 
+/// Curve failures specific enough to be surfaced to callers as is
+pub const TYPED_CURVE_ERRORS: [SwapError; 4] = [
+    SwapError::CurveOverflow,
+    SwapError::CurveNonConvergence,
+    SwapError::EmptyReserves,
+    SwapError::TradeTooLargeForConvergence,
+];
+
+/// Keep a typed curve error, replace any other error of a curve calculation with `fallback`
+pub fn map_curve_error(e: Error, fallback: SwapError) -> Error {
+    if TYPED_CURVE_ERRORS
+        .into_iter()
+        .any(|typed| e == typed.into())
+    {
+        e
+    } else {
+        error!(fallback)
+    }
+}
+
 /// Trait for anchor serializing trait objects, required because structs that implement
 /// `AccountSerialize` cannot be used as trait objects (as `dyn AccountSerialize`).
 pub trait DynAccountSerialize {
@@ -120,6 +145,76 @@ pub trait CurveCalculator: Debug + DynAccountSerialize {
         round_direction: RoundDirection,
     ) -> Result<TradingTokenResult>;
 
+    /// Get the amount of pool tokens for a deposit of only one of the trading tokens, in
+    /// proportion to the growth of the normalized value of the pool, rounded down
+    /// * `source_amount` - the amount of the deposited token, less fees
+    /// * `pool_token_a_amount` - the amount of token A in the pool before the deposit
+    /// * `pool_token_b_amount` - the amount of token B in the pool before the deposit
+    /// * `pool_token_supply` - the total supply of pool tokens
+    /// * `trade_direction` - `AtoB` for a deposit of token A, `BtoA` for token B
+    fn deposit_single_token_type(
+        &self,
+        source_amount: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        pool_token_supply: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<u128> {
+        let (new_pool_token_a_amount, new_pool_token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (
+                try_math!(pool_token_a_amount.try_add(source_amount))?,
+                pool_token_b_amount,
+            ),
+            TradeDirection::BtoA => (
+                pool_token_a_amount,
+                try_math!(pool_token_b_amount.try_add(source_amount))?,
+            ),
+        };
+        let value = self.normalized_value(pool_token_a_amount, pool_token_b_amount)?;
+        let new_value = self.normalized_value(new_pool_token_a_amount, new_pool_token_b_amount)?;
+        new_value
+            .try_sub(&value)?
+            .try_mul(&PreciseNumber::try_new(pool_token_supply)?)?
+            .try_div(&value)?
+            .try_floor()?
+            .try_to_imprecise()
+    }
+
+    /// Get the amount of pool tokens to burn for a withdrawal of only one of the trading tokens,
+    /// in proportion to the decrease of the normalized value of the pool, rounded up
+    /// * `destination_amount` - the amount of the withdrawn token leaving the pool, fees included
+    /// * `pool_token_a_amount` - the amount of token A in the pool before the withdrawal
+    /// * `pool_token_b_amount` - the amount of token B in the pool before the withdrawal
+    /// * `pool_token_supply` - the total supply of pool tokens
+    /// * `trade_direction` - `AtoB` for a withdrawal of token A, `BtoA` for token B
+    fn withdraw_single_token_type_exact_out(
+        &self,
+        destination_amount: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        pool_token_supply: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<u128> {
+        let (new_pool_token_a_amount, new_pool_token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (
+                try_math!(pool_token_a_amount.try_sub(destination_amount))?,
+                pool_token_b_amount,
+            ),
+            TradeDirection::BtoA => (
+                pool_token_a_amount,
+                try_math!(pool_token_b_amount.try_sub(destination_amount))?,
+            ),
+        };
+        let value = self.normalized_value(pool_token_a_amount, pool_token_b_amount)?;
+        let new_value = self.normalized_value(new_pool_token_a_amount, new_pool_token_b_amount)?;
+        value
+            .try_sub(&new_value)?
+            .try_mul(&PreciseNumber::try_new(pool_token_supply)?)?
+            .try_div(&value)?
+            .try_ceil()?
+            .try_to_imprecise()
+    }
+
     /// Validate that the given curve has no invalid parameters
     fn validate(&self) -> Result<()>;
 
@@ -148,6 +243,34 @@ pub trait CurveCalculator: Debug + DynAccountSerialize {
         true
     }
 
+    /// Whether a deposit can be made in only one of the tokens, valued by
+    /// `deposit_single_token_type`
+    fn allows_single_sided_deposits(&self) -> bool {
+        self.allows_deposits()
+    }
+
+    /// Largest source amount, less fees, that can be swapped against the given reserves, `None`
+    /// when the curve does not limit the trade size
+    fn max_source_amount(
+        &self,
+        _pool_source_amount: u128,
+        _pool_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> Result<Option<u128>> {
+        Ok(None)
+    }
+
+    /// Imbalance of the reserves in basis points of their total, 0 when balanced and 10_000 when
+    /// one side is empty, `None` when the curve has no balanced reserves to compare with, e.g. a
+    /// constant product pool is balanced at any price
+    fn reserves_imbalance_bps(
+        &self,
+        _pool_token_a_amount: u128,
+        _pool_token_b_amount: u128,
+    ) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
     /// Calculates the total normalized value of the curve given the liquidity
     /// parameters.
     ///
@@ -50,9 +50,34 @@ pub fn trading_tokens_to_pool_tokens(
     }
 }
 
+/// Denominator of the constant price bid/ask spread
+pub const SPREAD_BPS_DENOMINATOR: u64 = 10_000;
+
+impl ConstantPriceCurve {
+    /// Amount of token A paid by the pool for 1 token B, ie. `token_b_price * (1 - spread)`,
+    /// scaled by `SPREAD_BPS_DENOMINATOR`
+    pub fn bid_price(&self) -> Result<u128> {
+        let spread_factor = try_math!(SPREAD_BPS_DENOMINATOR.try_sub(self.spread_bps))?;
+        try_math!(u128::from(self.token_b_price).try_mul(u128::from(spread_factor)))
+    }
+
+    /// Amount of token A charged by the pool for 1 token B, ie. `token_b_price * (1 + spread)`,
+    /// scaled by `SPREAD_BPS_DENOMINATOR`
+    pub fn ask_price(&self) -> Result<u128> {
+        let spread_factor = try_math!(SPREAD_BPS_DENOMINATOR.try_add(self.spread_bps))?;
+        try_math!(u128::from(self.token_b_price).try_mul(u128::from(spread_factor)))
+    }
+}
+
+fn to_u128(value: U256) -> Result<u128> {
+    require!(value <= U256::from(u128::MAX), SwapError::ConversionFailure);
+    Ok(value.as_u128())
+}
+
 /// ConstantPriceCurve struct implementing CurveCalculator
 impl CurveCalculator for ConstantPriceCurve {
-    /// Constant price curve always returns 1:1
+    /// Constant price curve always returns `token_b_price` A tokens per B token,
+    /// adjusted by the spread in the pool's favour
     fn swap_without_fees(
         &self,
         source_amount: u128,
@@ -60,26 +85,35 @@ impl CurveCalculator for ConstantPriceCurve {
         _swap_destination_amount: u128,
         trade_direction: TradeDirection,
     ) -> Result<SwapWithoutFeesResult> {
-        let token_b_price = self.token_b_price as u128;
+        let denominator = U256::from(SPREAD_BPS_DENOMINATOR);
 
         let (source_amount_swapped, destination_amount_swapped) = match trade_direction {
-            TradeDirection::BtoA => (
-                source_amount,
-                try_math!(source_amount.try_mul(token_b_price))?,
-            ),
+            TradeDirection::BtoA => {
+                let bid_price = U256::from(self.bid_price()?);
+                let destination_amount_swapped = try_math!(U256::from(source_amount)
+                    .try_mul(bid_price)?
+                    .try_div(denominator))?;
+                (source_amount, to_u128(destination_amount_swapped)?)
+            }
             TradeDirection::AtoB => {
-                let destination_amount_swapped = try_math!(source_amount.try_div(token_b_price))?;
-                let mut source_amount_swapped = source_amount;
-
-                // if there is a remainder from buying token B, floor
-                // token_a_amount to avoid taking too many tokens, but
-                // don't recalculate the fees
-                let remainder = try_math!(source_amount_swapped.try_rem(token_b_price))?;
-                if remainder > 0 {
-                    source_amount_swapped = try_math!(source_amount.try_sub(remainder))?;
-                }
-
-                (source_amount_swapped, destination_amount_swapped)
+                let ask_price = U256::from(self.ask_price()?);
+                let destination_amount_swapped = try_math!(U256::from(source_amount)
+                    .try_mul(denominator)?
+                    .try_div(ask_price))?;
+
+                // if there is a remainder from buying token B, only take the
+                // token A needed for the floored token B amount to avoid taking
+                // too many tokens, but don't recalculate the fees
+                let source_amount_swapped = try_math!(destination_amount_swapped
+                    .try_mul(ask_price)?
+                    .try_add(denominator)?
+                    .try_sub(U256::one())?
+                    .try_div(denominator))?;
+
+                (
+                    to_u128(source_amount_swapped)?,
+                    to_u128(destination_amount_swapped)?,
+                )
             }
         };
         require!(
@@ -143,6 +177,14 @@ impl CurveCalculator for ConstantPriceCurve {
             SwapError::InvalidCurve,
             "Token B price must be greater than 0 for constant price curve"
         );
+        require_msg!(
+            self.spread_bps < SPREAD_BPS_DENOMINATOR,
+            SwapError::InvalidCurve,
+            &format!(
+                "Spread must be less than {} bps for constant price curve",
+                SPREAD_BPS_DENOMINATOR
+            )
+        );
         Ok(())
     }
 
@@ -245,6 +287,64 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn swap_calculation_with_spread() {
+        let curve = ConstantPriceCurve {
+            token_b_price: 100,
+            spread_bps: 100,
+            ..Default::default()
+        };
+
+        // selling token B gets the bid price: 10 * 100 * 0.99
+        let result = curve
+            .swap_without_fees(10, 0, 0, TradeDirection::BtoA)
+            .unwrap();
+        assert_eq!(
+            result,
+            SwapWithoutFeesResult {
+                source_amount_swapped: 10,
+                destination_amount_swapped: 990,
+            }
+        );
+
+        // buying token B pays the ask price: 10 * 100 * 1.01
+        let result = curve
+            .swap_without_fees(1_010, 0, 0, TradeDirection::AtoB)
+            .unwrap();
+        assert_eq!(
+            result,
+            SwapWithoutFeesResult {
+                source_amount_swapped: 1_010,
+                destination_amount_swapped: 10,
+            }
+        );
+
+        // any remainder is not taken from the user
+        let result = curve
+            .swap_without_fees(1_019, 0, 0, TradeDirection::AtoB)
+            .unwrap();
+        assert_eq!(
+            result,
+            SwapWithoutFeesResult {
+                source_amount_swapped: 1_010,
+                destination_amount_swapped: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn validate_spread() {
+        let mut curve = ConstantPriceCurve {
+            token_b_price: 100,
+            spread_bps: SPREAD_BPS_DENOMINATOR - 1,
+            ..Default::default()
+        };
+        curve.validate().unwrap();
+
+        curve.spread_bps = SPREAD_BPS_DENOMINATOR;
+        assert_eq!(curve.validate().err(), Some(SwapError::InvalidCurve.into()));
+    }
+
     #[test]
     fn serialize_constant_price_curve() {
         let token_b_price = 1_251_258;
@@ -0,0 +1,483 @@
+//! The constant product invariant providing liquidity only between two prices.
+//!
+//! The real reserves are topped up with virtual reserves, sized so that the virtual pool prices
+//! token A at `min_price` once all of token B is swapped out, and at `max_price` once all of
+//! token A is. Within the range, swaps are priced by the constant product of the virtual
+//! reserves, so the liquidity is as deep as that of a constant product pool holding the virtual
+//! reserves.
+//!
+//! The virtual token A reserve `u` solves `k * u^2 - (x + y / q) * u - x * y / q = 0`, where
+//! `q = sqrt(min_price * max_price)` and `k = sqrt(max_price / min_price) - 1`, and the virtual
+//! token B reserve is `u * q`.
+
+use anchor_lang::{require, Result};
+use spl_math::{precise_number::PreciseNumber, uint::U256};
+
+use crate::{
+    curve::{
+        calculator::{
+            CurveCalculator, DynAccountSerialize, RoundDirection, SwapWithoutFeesResult,
+            TradeDirection, TradingTokenResult,
+        },
+        constant_product, math,
+    },
+    error::SwapError,
+    require_msg,
+    state::ConstantProductWithBoundsCurve,
+    try_math,
+    utils::math::{TryMath, TryMathRef, TryNew},
+};
+
+/// Scale of the square roots of the prices
+const SQRT_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Scale of the virtual reserves while they are solved, so that small pools keep their precision
+const AMOUNT_SCALE: u128 = 1_000_000_000;
+
+/// Smallest `max_price / min_price`, in bps, below which the virtual reserves become too large
+pub const MIN_PRICE_RANGE_BPS: u64 = 10_001;
+
+fn to_u128(value: U256) -> Result<u128> {
+    require!(value <= U256::from(u128::MAX), SwapError::ConversionFailure);
+    Ok(value.as_u128())
+}
+
+impl ConstantProductWithBoundsCurve {
+    /// Geometric mean of the prices `q` and `k = sqrt(max_price / min_price) - 1`, both scaled by
+    /// `SQRT_SCALE`
+    fn range(&self) -> Result<(U256, U256)> {
+        let scale = U256::from(SQRT_SCALE);
+        let min_price = U256::from(self.min_price);
+        let max_price = U256::from(self.max_price);
+        let mean_price = try_math!(min_price.try_mul(max_price)?.try_mul(scale))?.integer_sqrt();
+        let sqrt_ratio =
+            try_math!(max_price.try_mul(scale)?.try_mul(scale)?.try_div(min_price))?.integer_sqrt();
+        let k = try_math!(sqrt_ratio.try_sub(scale))?;
+        Ok((mean_price, k))
+    }
+
+    /// Virtual token A reserve scaled by `AMOUNT_SCALE`, with the geometric mean of the prices
+    fn virtual_reserve(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<(U256, U256)> {
+        let (mean_price, k) = self.range()?;
+        let scale = U256::from(SQRT_SCALE);
+        let amount_scale = U256::from(AMOUNT_SCALE);
+
+        let x = try_math!(U256::from(pool_token_a_amount).try_mul(amount_scale))?;
+        // y / q
+        let y = try_math!(U256::from(pool_token_b_amount)
+            .try_mul(amount_scale)?
+            .try_mul(scale)?
+            .try_div(mean_price))?;
+        let w = try_math!(x.try_add(y))?;
+        // w^2 + 4 * k * x * y / q
+        let discriminant = try_math!(w.try_mul(w)?.try_add(
+            k.try_mul(x)?
+                .try_div(scale)?
+                .try_mul(y)?
+                .try_mul(U256::from(4))?
+        ))?;
+        let u = try_math!(w
+            .try_add(discriminant.integer_sqrt())?
+            .try_mul(scale)?
+            .try_div(k.try_mul(U256::from(2))?))?;
+        Ok((u, mean_price))
+    }
+
+    /// Virtual token A and token B reserves added to the real ones, each rounded in `rounding`
+    pub fn virtual_reserves(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        rounding: (RoundDirection, RoundDirection),
+    ) -> Result<(u128, u128)> {
+        let (u, mean_price) = self.virtual_reserve(pool_token_a_amount, pool_token_b_amount)?;
+        let amount_scale = U256::from(AMOUNT_SCALE);
+        let b_numerator = try_math!(u.try_mul(mean_price))?;
+        let b_denominator = try_math!(amount_scale.try_mul(U256::from(SQRT_SCALE)))?;
+        let virtual_a = match rounding.0 {
+            RoundDirection::Floor => try_math!(u.try_div(amount_scale))?,
+            RoundDirection::Ceiling => try_math!(u
+                .try_add(amount_scale)?
+                .try_sub(U256::one())?
+                .try_div(amount_scale))?,
+        };
+        let virtual_b = match rounding.1 {
+            RoundDirection::Floor => try_math!(b_numerator.try_div(b_denominator))?,
+            RoundDirection::Ceiling => try_math!(b_numerator
+                .try_add(b_denominator)?
+                .try_sub(U256::one())?
+                .try_div(b_denominator))?,
+        };
+        Ok((to_u128(virtual_a)?, to_u128(virtual_b)?))
+    }
+}
+
+impl CurveCalculator for ConstantProductWithBoundsCurve {
+    /// Constant product of the real and virtual reserves, the virtual reserves of the source
+    /// rounded up and of the destination rounded down in favour of the pool
+    fn swap_without_fees(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<SwapWithoutFeesResult> {
+        let (virtual_source, virtual_destination) = match trade_direction {
+            TradeDirection::AtoB => self.virtual_reserves(
+                pool_source_amount,
+                pool_destination_amount,
+                (RoundDirection::Ceiling, RoundDirection::Floor),
+            )?,
+            TradeDirection::BtoA => {
+                let (virtual_a, virtual_b) = self.virtual_reserves(
+                    pool_destination_amount,
+                    pool_source_amount,
+                    (RoundDirection::Floor, RoundDirection::Ceiling),
+                )?;
+                (virtual_b, virtual_a)
+            }
+        };
+        constant_product::swap(
+            source_amount,
+            try_math!(pool_source_amount.try_add(virtual_source))?,
+            try_math!(pool_destination_amount.try_add(virtual_destination))?,
+        )
+    }
+
+    /// The virtual reserves scale with the real ones, so the tokens of a share of the pool are
+    /// the same share of the real reserves
+    fn pool_tokens_to_trading_tokens(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        round_direction: RoundDirection,
+    ) -> Result<TradingTokenResult> {
+        math::pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            round_direction,
+        )
+    }
+
+    fn validate(&self) -> Result<()> {
+        require_msg!(self.min_price > 0, SwapError::InvalidCurve, "min_price=0");
+        require_msg!(
+            u128::from(self.max_price) * 10_000
+                >= u128::from(self.min_price) * u128::from(MIN_PRICE_RANGE_BPS),
+            SwapError::InvalidCurve,
+            &format!(
+                "max_price={} / min_price={} < MIN_PRICE_RANGE_BPS={}",
+                self.max_price, self.min_price, MIN_PRICE_RANGE_BPS
+            )
+        );
+        Ok(())
+    }
+
+    /// The virtual token A reserve, which is proportional to the liquidity of the range
+    fn normalized_value(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<PreciseNumber> {
+        let (u, _) = self.virtual_reserve(pool_token_a_amount, pool_token_b_amount)?;
+        PreciseNumber::try_new(to_u128(u)?)?.try_div(&PreciseNumber::try_new(AMOUNT_SCALE)?)
+    }
+}
+
+impl DynAccountSerialize for ConstantProductWithBoundsCurve {
+    fn try_dyn_serialize(&self, mut dst: std::cell::RefMut<&mut [u8]>) -> Result<()> {
+        let dst: &mut [u8] = &mut dst;
+        let mut cursor = std::io::Cursor::new(dst);
+        anchor_lang::AccountSerialize::try_serialize(self, &mut cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::BorrowMut;
+
+    use anchor_lang::{error, AccountDeserialize};
+    use hyperplane_sim::constant_product_with_bounds::ConstantProductWithBoundsModel;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        curve::calculator::{
+            test::{check_pool_value_from_deposit, check_pool_value_from_withdraw},
+            INITIAL_SWAP_POOL_AMOUNT,
+        },
+        state::{ConstantProductCurve, Curve},
+    };
+
+    const MIN_PRICE: u64 = 900_000_000;
+    const MAX_PRICE: u64 = 1_100_000_000;
+
+    fn swapped(
+        curve: &dyn CurveCalculator,
+        source: u128,
+        pool_source: u128,
+        pool_destination: u128,
+        trade_direction: TradeDirection,
+    ) -> u128 {
+        curve
+            .swap_without_fees(source, pool_source, pool_destination, trade_direction)
+            .unwrap()
+            .destination_amount_swapped
+    }
+
+    #[test]
+    fn initial_pool_amount() {
+        let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+        assert_eq!(curve.new_pool_supply(), INITIAL_SWAP_POOL_AMOUNT);
+    }
+
+    #[test]
+    fn serialize_constant_product_with_bounds_curve() {
+        let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+
+        let mut arr = [0u8; Curve::LEN];
+        let packed = arr.borrow_mut();
+        let ref_mut = std::cell::RefCell::new(packed);
+
+        curve.try_dyn_serialize(ref_mut.borrow_mut()).unwrap();
+        let unpacked = ConstantProductWithBoundsCurve::try_deserialize(&mut arr.as_ref()).unwrap();
+        assert_eq!(curve, unpacked);
+    }
+
+    #[test]
+    fn validate_params() {
+        assert!(ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE)
+            .validate()
+            .is_ok());
+        assert!(ConstantProductWithBoundsCurve::new(10_000, 10_001)
+            .validate()
+            .is_ok());
+        for curve in [
+            ConstantProductWithBoundsCurve::new(0, MAX_PRICE),
+            ConstantProductWithBoundsCurve::new(MAX_PRICE, MAX_PRICE),
+            ConstantProductWithBoundsCurve::new(MAX_PRICE, MIN_PRICE),
+            ConstantProductWithBoundsCurve::new(100_000, 100_009),
+        ] {
+            assert_eq!(curve.validate(), Err(error!(SwapError::InvalidCurve)));
+        }
+    }
+
+    #[test]
+    fn virtual_reserves_price_the_bounds() {
+        let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+        let floor = (RoundDirection::Floor, RoundDirection::Floor);
+
+        // only token A left, the pool prices token A at the lower bound
+        let (virtual_a, virtual_b) = curve.virtual_reserves(1_000_000_000, 0, floor).unwrap();
+        let price = virtual_b * 1_000_000_000 / (1_000_000_000 + virtual_a);
+        assert!(price.abs_diff(u128::from(MIN_PRICE)) <= 1);
+
+        // only token B left, the pool prices token A at the upper bound
+        let (virtual_a, virtual_b) = curve.virtual_reserves(0, 1_000_000_000, floor).unwrap();
+        let price = (1_000_000_000 + virtual_b) * 1_000_000_000 / virtual_a;
+        assert!(price.abs_diff(u128::from(MAX_PRICE)) <= 1);
+    }
+
+    #[test]
+    fn deeper_than_constant_product_in_range() {
+        let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+        let pool_a = 1_000_000_000;
+        let pool_b = 1_000_000_000;
+
+        for trade_direction in [TradeDirection::AtoB, TradeDirection::BtoA] {
+            let bounded = swapped(&curve, 10_000_000, pool_a, pool_b, trade_direction);
+            let constant_product = swapped(
+                &ConstantProductCurve::default(),
+                10_000_000,
+                pool_a,
+                pool_b,
+                trade_direction,
+            );
+            assert!(bounded > constant_product);
+        }
+    }
+
+    #[test]
+    fn swaps_past_the_bound_take_more_than_the_reserves() {
+        let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+        let pool_a = 1_000_000_000;
+        let pool_b = 1_000_000_000;
+
+        // 1.2B token A buy more than the whole token B reserve
+        let result = swapped(&curve, 1_200_000_000, pool_a, pool_b, TradeDirection::AtoB);
+        assert!(result > pool_b);
+
+        // with all of token B swapped out, a swap to token B is only priced against the virtual
+        // reserve, for `SwapCurve::swap` to reject
+        let result = swapped(&curve, 1_000, pool_a, 0, TradeDirection::AtoB);
+        assert!(result > 0);
+
+        // token A can still be bought with token B, at the lower bound price
+        let result = swapped(&curve, 900_000, 0, pool_a, TradeDirection::BtoA);
+        assert!(result > 999_000 && result < 1_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn compare_sim_swap_no_fee(
+            min_price in 1_000..1_000_000_000_000_000_u64,
+            range_bps in MIN_PRICE_RANGE_BPS..1_000_000,
+            pool_token_a_amount in 1_000..1_000_000_000_000_000_u128,
+            pool_token_b_amount in 1_000..1_000_000_000_000_000_u128,
+            source_amount in 1_000..1_000_000_000_000_u128,
+            a_to_b: bool,
+        ) {
+            let max_price = u128::from(min_price) * u128::from(range_bps) / 10_000;
+            prop_assume!(max_price <= u128::from(u64::MAX));
+            let curve = ConstantProductWithBoundsCurve::new(min_price, max_price as u64);
+            let model = ConstantProductWithBoundsModel::new(
+                min_price.into(),
+                max_price,
+                pool_token_a_amount,
+                pool_token_b_amount,
+            );
+            let (trade_direction, pool_source_amount, pool_destination_amount) = if a_to_b {
+                (TradeDirection::AtoB, pool_token_a_amount, pool_token_b_amount)
+            } else {
+                (TradeDirection::BtoA, pool_token_b_amount, pool_token_a_amount)
+            };
+
+            let sim_result = model.sim_swap(a_to_b, source_amount);
+            prop_assume!(sim_result.map_or(false, |dy| dy > 0));
+            let sim_result = sim_result.unwrap();
+            let result = swapped(
+                &curve,
+                source_amount,
+                pool_source_amount,
+                pool_destination_amount,
+                trade_direction,
+            );
+
+            // the rounding favours the pool, within a tolerance of the exact model
+            let tolerance = std::cmp::max(2, sim_result / 1_000_000_000);
+            assert!(
+                result <= sim_result + 1 && sim_result.saturating_sub(result) <= tolerance,
+                "result={}, sim_result={}, min_price={}, max_price={}, a={}, b={}, source={}",
+                result,
+                sim_result,
+                min_price,
+                max_price,
+                pool_token_a_amount,
+                pool_token_b_amount,
+                source_amount,
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap(
+            range_bps in MIN_PRICE_RANGE_BPS..1_000_000,
+            pool_token_a_amount in 1..u64::MAX,
+            pool_token_b_amount in 1..u64::MAX,
+            source_amount in 1..u64::MAX,
+            a_to_b: bool,
+        ) {
+            let max_price = u128::from(MIN_PRICE) * u128::from(range_bps) / 10_000;
+            let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, max_price as u64);
+            let (pool_token_a_amount, pool_token_b_amount) =
+                (u128::from(pool_token_a_amount), u128::from(pool_token_b_amount));
+            let (trade_direction, pool_source_amount, pool_destination_amount) = if a_to_b {
+                (TradeDirection::AtoB, pool_token_a_amount, pool_token_b_amount)
+            } else {
+                (TradeDirection::BtoA, pool_token_b_amount, pool_token_a_amount)
+            };
+            let result = curve.swap_without_fees(
+                u128::from(source_amount),
+                pool_source_amount,
+                pool_destination_amount,
+                trade_direction,
+            );
+            prop_assume!(result.is_ok());
+            let result = result.unwrap();
+            prop_assume!(result.destination_amount_swapped <= pool_destination_amount);
+
+            let new_pool_source_amount = pool_source_amount + result.source_amount_swapped;
+            let new_pool_destination_amount =
+                pool_destination_amount - result.destination_amount_swapped;
+            let (new_pool_token_a_amount, new_pool_token_b_amount) = if a_to_b {
+                (new_pool_source_amount, new_pool_destination_amount)
+            } else {
+                (new_pool_destination_amount, new_pool_source_amount)
+            };
+            let (value, _) = curve
+                .virtual_reserve(pool_token_a_amount, pool_token_b_amount)
+                .unwrap();
+            let (new_value, _) = curve
+                .virtual_reserve(new_pool_token_a_amount, new_pool_token_b_amount)
+                .unwrap();
+
+            // the virtual reserve is solved with a rounding of a few units of `AMOUNT_SCALE`
+            let tolerance = value / U256::from(1_000_000_000_000_u64) + U256::from(10);
+            assert!(new_value + tolerance >= value, "value={}, new_value={}", value, new_value);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_deposit(
+            pool_token_amount in 1..u64::MAX,
+            pool_token_supply in 1..u64::MAX,
+            pool_token_a_amount in 1..u64::MAX,
+            pool_token_b_amount in 1..u64::MAX,
+        ) {
+            let pool_token_amount = pool_token_amount as u128;
+            let pool_token_supply = pool_token_supply as u128;
+            let pool_token_a_amount = pool_token_a_amount as u128;
+            let pool_token_b_amount = pool_token_b_amount as u128;
+            // Make sure we will get at least one trading token out for each
+            // side, otherwise the calculation fails
+            prop_assume!(pool_token_amount * pool_token_a_amount / pool_token_supply >= 1);
+            prop_assume!(pool_token_amount * pool_token_b_amount / pool_token_supply >= 1);
+            let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+            check_pool_value_from_deposit(
+                &curve,
+                pool_token_amount,
+                pool_token_supply,
+                pool_token_a_amount,
+                pool_token_b_amount,
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_withdraw(
+            pool_token_supply in 2..u64::MAX,
+            pool_token_amount in 1..u64::MAX,
+            pool_token_a_amount in 1..u64::MAX,
+            pool_token_b_amount in 1..u64::MAX,
+        ) {
+            prop_assume!(pool_token_amount < pool_token_supply);
+            let pool_token_amount = pool_token_amount as u128;
+            let pool_token_supply = pool_token_supply as u128;
+            let pool_token_a_amount = pool_token_a_amount as u128;
+            let pool_token_b_amount = pool_token_b_amount as u128;
+            // Make sure we will get at least one trading token out for each
+            // side, otherwise the calculation fails
+            prop_assume!(pool_token_amount * pool_token_a_amount / pool_token_supply >= 1);
+            prop_assume!(pool_token_amount * pool_token_b_amount / pool_token_supply >= 1);
+            let curve = ConstantProductWithBoundsCurve::new(MIN_PRICE, MAX_PRICE);
+            check_pool_value_from_withdraw(
+                &curve,
+                pool_token_amount,
+                pool_token_supply,
+                pool_token_a_amount,
+                pool_token_b_amount,
+            );
+        }
+    }
+}
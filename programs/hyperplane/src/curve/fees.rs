@@ -47,7 +47,12 @@ pub struct Fees {
     pub host_fee_denominator: u64,
 }
 
-/// Helper function for calculating swap fee
+/// Fee of `numerator / denominator` on `token_amount`
+///
+/// The fee is floored, unless `round_direction` is `Ceiling` and the floored fee is zero
+/// while both `token_amount` and `fee_numerator` are not, in which case the fee is one token so
+/// that small trades cannot avoid paying it. A zero `fee_numerator` or `token_amount` is
+/// always free, even with a zero `fee_denominator`.
 pub fn calculate_fee(
     token_amount: u128,
     fee_numerator: u128,
@@ -72,6 +77,98 @@ pub fn calculate_fee(
     }
 }
 
+/// Trade fee on the input of a swap, left in the pool vault for the LPs
+///
+/// Floored, and at least one token for a non-zero fee on a non-zero input.
+pub fn trading_fee(trading_tokens: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        trading_tokens,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Ceiling,
+    )
+}
+
+/// Owner trade fee on the input of a swap, sent to the fees vault, host fee included
+///
+/// Floored, and at least one token for a non-zero fee on a non-zero input.
+pub fn owner_fee(trading_tokens: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        trading_tokens,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Ceiling,
+    )
+}
+
+/// Host share of the owner trade fee
+///
+/// Floored, with no minimum: the host gets nothing when its share is a fraction of a token,
+/// so the host fee can never exceed the owner fee it is taken from.
+pub fn host_fee(owner_fee: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        owner_fee,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Floor,
+    )
+}
+
+/// Protocol share of the owner trade fee left after the host fee
+///
+/// Floored like the host fee, the protocol never takes more than the owner fee.
+pub fn protocol_fee(owner_fee: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        owner_fee,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Floor,
+    )
+}
+
+/// Owner fee on the trading tokens of a withdrawal
+///
+/// Floored, and at least one token for a non-zero fee on a non-zero withdrawal.
+pub fn owner_withdraw_fee(trading_tokens: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        trading_tokens,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Ceiling,
+    )
+}
+
+/// Flash fee on the amount of a flash loan, repaid on top of it
+///
+/// Floored, and at least one token for a non-zero fee on a non-zero loan.
+pub fn flash_fee(loan_amount: u128, numerator: u64, denominator: u64) -> Result<u128> {
+    calculate_fee(
+        loan_amount,
+        u128::from(numerator),
+        u128::from(denominator),
+        RoundDirection::Ceiling,
+    )
+}
+
+/// Trade fee numerator raised by `volatility_bps` basis points of recent volatility
+///
+/// Adds `dynamic_fee_numerator` per basis point to the base numerator, up to `max_numerator`. A
+/// zero `dynamic_fee_numerator` leaves the base numerator untouched.
+pub fn dynamic_trade_fee_numerator(
+    trade_fee_numerator: u64,
+    dynamic_fee_numerator: u64,
+    max_numerator: u64,
+    volatility_bps: u64,
+) -> Result<u64> {
+    if dynamic_fee_numerator == 0 {
+        return Ok(trade_fee_numerator);
+    }
+    let numerator = try_math!(u128::from(volatility_bps)
+        .try_mul(u128::from(dynamic_fee_numerator))?
+        .try_add(u128::from(trade_fee_numerator)))?;
+    Ok(numerator.min(u128::from(max_numerator)) as u64)
+}
+
 fn ceil_div(dividend: u128, divisor: u128) -> Result<u128> {
     try_math!(dividend.try_add(divisor)?.try_sub(1)?.try_div(divisor))
 }
@@ -103,33 +200,30 @@ fn validate_fraction(numerator: u64, denominator: u64) -> Result<()> {
 }
 
 impl Fees {
-    /// Calculate the withdraw fee in trading tokens
+    /// Calculate the withdraw fee in trading tokens, see [owner_withdraw_fee]
     pub fn owner_withdraw_fee(&self, trading_tokens: u128) -> Result<u128> {
-        calculate_fee(
+        owner_withdraw_fee(
             trading_tokens,
-            u128::from(self.owner_withdraw_fee_numerator),
-            u128::from(self.owner_withdraw_fee_denominator),
-            RoundDirection::Ceiling,
+            self.owner_withdraw_fee_numerator,
+            self.owner_withdraw_fee_denominator,
         )
     }
 
-    /// Calculate the trading fee in trading tokens
+    /// Calculate the trading fee in trading tokens, see [trading_fee]
     pub fn trading_fee(&self, trading_tokens: u128) -> Result<u128> {
-        calculate_fee(
+        trading_fee(
             trading_tokens,
-            u128::from(self.trade_fee_numerator),
-            u128::from(self.trade_fee_denominator),
-            RoundDirection::Ceiling,
+            self.trade_fee_numerator,
+            self.trade_fee_denominator,
         )
     }
 
-    /// Calculate the owner trading fee in trading tokens
+    /// Calculate the owner trading fee in trading tokens, see [owner_fee]
     pub fn owner_trading_fee(&self, trading_tokens: u128) -> Result<u128> {
-        calculate_fee(
+        owner_fee(
             trading_tokens,
-            u128::from(self.owner_trade_fee_numerator),
-            u128::from(self.owner_trade_fee_denominator),
-            RoundDirection::Ceiling,
+            self.owner_trade_fee_numerator,
+            self.owner_trade_fee_denominator,
         )
     }
 
@@ -164,16 +258,21 @@ impl Fees {
     }
 
     /// Calculate the host fee based on the owner fee, only used in production
-    /// situations where a program is hosted by multiple frontends
+    /// situations where a program is hosted by multiple frontends, see [host_fee]
     pub fn host_fee(&self, owner_fee: u128) -> Result<u128> {
-        calculate_fee(
+        host_fee(
             owner_fee,
-            u128::from(self.host_fee_numerator),
-            u128::from(self.host_fee_denominator),
-            RoundDirection::Floor,
+            self.host_fee_numerator,
+            self.host_fee_denominator,
         )
     }
 
+    /// Calculate the host fee paid in destination tokens, the host share of the owner trade fee
+    /// rate applied to the destination amount of the swap
+    pub fn destination_host_fee(&self, destination_amount: u128) -> Result<u128> {
+        self.host_fee(self.owner_trading_fee(destination_amount)?)
+    }
+
     /// Validate that the fees are reasonable
     pub fn validate(&self) -> Result<()> {
         validate_fraction(self.trade_fee_numerator, self.trade_fee_denominator)?;
@@ -189,3 +288,192 @@ impl Fees {
         Ok(())
     }
 }
+
+/// Validate the dynamic fee parameters against the base trade fee of `fees`
+///
+/// The cap must stay a valid fraction of the trade fee denominator and cannot be lower than the
+/// base trade fee. Nothing is checked when dynamic fees are disabled.
+pub fn validate_dynamic_fees(
+    fees: &Fees,
+    dynamic_fee_numerator: u64,
+    max_dynamic_trade_fee_numerator: u64,
+) -> Result<()> {
+    if dynamic_fee_numerator == 0 {
+        return Ok(());
+    }
+    validate_fraction(max_dynamic_trade_fee_numerator, fees.trade_fee_denominator)?;
+    if max_dynamic_trade_fee_numerator < fees.trade_fee_numerator {
+        return err!(SwapError::InvalidFee);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const MAX: u128 = u64::MAX as u128;
+
+    #[test_case(0, 0, 0, 0; "free with zero fraction")]
+    #[test_case(1_000, 0, 100, 0; "free with zero numerator")]
+    #[test_case(0, 1, 100, 0; "free on zero amount")]
+    #[test_case(1_000, 1, 100, 10; "exact")]
+    #[test_case(1_050, 1, 100, 10; "floored")]
+    #[test_case(99, 1, 100, 1; "at least one token")]
+    #[test_case(1, 1, 100, 1; "at least one token on one token")]
+    #[test_case(1_000, 100, 100, 1_000; "whole amount with equal numerator and denominator")]
+    #[test_case(MAX, 1, 1, MAX; "max amount with equal numerator and denominator")]
+    #[test_case(MAX, u64::MAX, u64::MAX, MAX; "max amount and fraction")]
+    #[test_case(MAX, 1, u64::MAX, 1; "max amount over max denominator")]
+    fn test_trading_owner_withdraw_and_flash_fees(
+        amount: u128,
+        numerator: u64,
+        denominator: u64,
+        expected: u128,
+    ) {
+        assert_eq!(
+            trading_fee(amount, numerator, denominator).unwrap(),
+            expected
+        );
+        assert_eq!(owner_fee(amount, numerator, denominator).unwrap(), expected);
+        assert_eq!(
+            owner_withdraw_fee(amount, numerator, denominator).unwrap(),
+            expected
+        );
+        assert_eq!(flash_fee(amount, numerator, denominator).unwrap(), expected);
+    }
+
+    #[test_case(0, 0, 0, 0; "free with zero fraction")]
+    #[test_case(1_000, 0, 100, 0; "free with zero numerator")]
+    #[test_case(0, 20, 100, 0; "free on zero owner fee")]
+    #[test_case(1_000, 20, 100, 200; "exact")]
+    #[test_case(1_004, 20, 100, 200; "floored")]
+    #[test_case(4, 20, 100, 0; "no minimum")]
+    #[test_case(1_000, 100, 100, 1_000; "whole owner fee with equal numerator and denominator")]
+    #[test_case(MAX, u64::MAX, u64::MAX, MAX; "max owner fee and fraction")]
+    #[test_case(MAX, 1, u64::MAX, 1; "max owner fee over max denominator")]
+    fn test_host_and_protocol_fees(
+        owner_fee: u128,
+        numerator: u64,
+        denominator: u64,
+        expected: u128,
+    ) {
+        assert_eq!(
+            host_fee(owner_fee, numerator, denominator).unwrap(),
+            expected
+        );
+        assert_eq!(
+            protocol_fee(owner_fee, numerator, denominator).unwrap(),
+            expected
+        );
+    }
+
+    #[test_case(25, 0, 100, 500, 25; "disabled")]
+    #[test_case(25, 1, 100, 0, 25; "no volatility")]
+    #[test_case(25, 1, 100, 50, 75; "raised by the volatility")]
+    #[test_case(25, 1, 100, 500, 100; "capped")]
+    #[test_case(25, u64::MAX, 100, u64::MAX, 100; "capped on max volatility and numerator")]
+    fn test_dynamic_trade_fee_numerator(
+        trade_fee_numerator: u64,
+        dynamic_fee_numerator: u64,
+        max_numerator: u64,
+        volatility_bps: u64,
+        expected: u64,
+    ) {
+        assert_eq!(
+            dynamic_trade_fee_numerator(
+                trade_fee_numerator,
+                dynamic_fee_numerator,
+                max_numerator,
+                volatility_bps
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test_case(0, 0, true; "disabled without cap")]
+    #[test_case(1, 100, true; "cap above the trade fee")]
+    #[test_case(1, 25, true; "cap equal to the trade fee")]
+    #[test_case(1, 24, false; "cap below the trade fee")]
+    #[test_case(1, 10_001, false; "cap above the denominator")]
+    fn test_validate_dynamic_fees(
+        dynamic_fee_numerator: u64,
+        max_dynamic_trade_fee_numerator: u64,
+        valid: bool,
+    ) {
+        let fees = Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_dynamic_fees(
+                &fees,
+                dynamic_fee_numerator,
+                max_dynamic_trade_fee_numerator
+            )
+            .is_ok(),
+            valid
+        );
+        if valid {
+            let raised = dynamic_trade_fee_numerator(
+                fees.trade_fee_numerator,
+                dynamic_fee_numerator,
+                max_dynamic_trade_fee_numerator,
+                1_000,
+            )
+            .unwrap();
+            assert!(raised <= max_dynamic_trade_fee_numerator.max(25));
+        }
+    }
+
+    #[test]
+    fn test_fee_with_zero_denominator_fails() {
+        assert!(trading_fee(1_000, 1, 0).is_err());
+        assert!(host_fee(1_000, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_fee_above_max_u128_fails() {
+        assert!(trading_fee(u128::MAX, 2, 2).is_err());
+    }
+
+    #[test]
+    fn test_fees_methods_match_pure_functions() {
+        let fees = Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 1_000,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+        };
+        for amount in [0, 1, 399, 400, 1_000_000, MAX] {
+            assert_eq!(
+                fees.trading_fee(amount).unwrap(),
+                trading_fee(amount, 25, 10_000).unwrap()
+            );
+            assert_eq!(
+                fees.owner_trading_fee(amount).unwrap(),
+                owner_fee(amount, 5, 10_000).unwrap()
+            );
+            assert_eq!(
+                fees.owner_withdraw_fee(amount).unwrap(),
+                owner_withdraw_fee(amount, 1, 1_000).unwrap()
+            );
+            assert_eq!(
+                fees.host_fee(amount).unwrap(),
+                host_fee(amount, 20, 100).unwrap()
+            );
+            assert_eq!(
+                fees.destination_host_fee(amount).unwrap(),
+                host_fee(owner_fee(amount, 5, 10_000).unwrap(), 20, 100).unwrap()
+            );
+        }
+    }
+}
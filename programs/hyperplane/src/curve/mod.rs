@@ -4,7 +4,11 @@ pub mod base;
 pub mod calculator;
 pub mod constant_price;
 pub mod constant_product;
+pub mod constant_product_with_bounds;
 pub mod fees;
 pub mod math;
 pub mod offset;
+pub mod oracle_pegged;
+pub mod pegged;
 pub mod stable;
+pub mod weighted;
@@ -0,0 +1,794 @@
+//! Constant price swap curve pegged to an external price feed.
+//!
+//! The pool quotes the price of one token B in tokens A read from a Pyth or Scope price account,
+//! adjusted by a bid/ask spread in its favour, like the constant price curve with a moving price.
+//! The price account is passed to `swap` and rejected if it is not the one of the curve or older
+//! than `max_age_slots`. Without it, the pool swaps at the price cached in the curve by the
+//! permissionless `refresh_price` crank, under the same age limit.
+//!
+//! Deposits and withdrawals of both tokens are pro-rata and do not read the price, single sided
+//! deposits are not allowed as they would be valued at the price.
+
+use std::sync::Arc;
+
+use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use spl_math::{precise_number::PreciseNumber, uint::U256};
+
+use crate::{
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator::{
+            CurveCalculator, DynAccountSerialize, RoundDirection, SwapWithoutFeesResult,
+            TradeDirection, TradingTokenResult,
+        },
+        constant_price::SPREAD_BPS_DENOMINATOR,
+        math,
+    },
+    error::SwapError,
+    require_msg,
+    state::OraclePeggedCurve,
+    to_u64, try_math,
+    utils::{
+        instructions::deserialize_curve,
+        math::{TryMath, TryNew},
+    },
+};
+
+/// Layout of the price account of an oracle pegged curve
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+pub enum OracleType {
+    /// Pyth v2 price account
+    Pyth = 0,
+    /// Scope prices account, read at `OraclePeggedCurve::scope_price_index`
+    Scope = 1,
+}
+
+/// Largest power of 10 between the native units of the tokens and the oracle price
+const MAX_PRICE_EXPONENT: i32 = 30;
+
+/// Pyth v2 price account layout, see <https://github.com/pyth-network/pyth-client>
+mod pyth {
+    pub const MAGIC: u32 = 0xa1b2c3d4;
+    pub const VERSION: u32 = 2;
+    pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+    pub const STATUS_TRADING: u32 = 1;
+
+    pub const MAGIC_OFFSET: usize = 0;
+    pub const VERSION_OFFSET: usize = 4;
+    pub const ACCOUNT_TYPE_OFFSET: usize = 8;
+    pub const EXPONENT_OFFSET: usize = 20;
+    pub const PRICE_OFFSET: usize = 208;
+    pub const STATUS_OFFSET: usize = 224;
+    pub const PUBLISH_SLOT_OFFSET: usize = 232;
+    pub const LEN: usize = 240;
+}
+
+/// Scope prices account layout, see <https://github.com/Kamino-Finance/scope>
+mod scope {
+    /// Discriminator and oracle mappings address
+    pub const PRICES_OFFSET: usize = 8 + 32;
+    /// `value: u64, exp: u64, last_updated_slot: u64, unix_timestamp: u64, _reserved: [u64; 2],
+    /// _reserved2: [u16; 3], index: u16`
+    pub const DATED_PRICE_LEN: usize = 56;
+    pub const MAX_ENTRIES: u64 = 512;
+
+    pub const VALUE_OFFSET: usize = 0;
+    pub const EXP_OFFSET: usize = 8;
+    pub const LAST_UPDATED_SLOT_OFFSET: usize = 16;
+}
+
+/// Price of one token B in tokens A, `value * 10^exponent`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub value: u64,
+    pub exponent: i32,
+    /// Slot the price was published at
+    pub slot: u64,
+}
+
+fn read<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| error!(SwapError::InvalidOracle))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read(data, offset)?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read(data, offset)?))
+}
+
+impl OraclePrice {
+    /// Read the price of the curve from the data of its price account and check its age
+    pub fn from_account_data(
+        curve: &OraclePeggedCurve,
+        data: &[u8],
+        current_slot: u64,
+    ) -> Result<Self> {
+        let oracle_type = OracleType::try_from(curve.oracle_type)
+            .map_err(|_| error!(SwapError::InvalidOracle))?;
+        let price = match oracle_type {
+            OracleType::Pyth => Self::from_pyth(data)?,
+            OracleType::Scope => Self::from_scope(data, curve.scope_price_index)?,
+        };
+        require_msg!(
+            price.value > 0,
+            SwapError::InvalidOracle,
+            "InvalidOracle: oracle price is 0"
+        );
+        price.require_fresh(curve, current_slot)?;
+        Ok(price)
+    }
+
+    /// Price cached in the curve by `refresh_price`, `None` if it was never refreshed
+    pub fn cached(curve: &OraclePeggedCurve) -> Option<Self> {
+        if curve.cached_price_value == 0 {
+            return None;
+        }
+        Some(Self {
+            value: curve.cached_price_value,
+            exponent: i32::try_from(curve.cached_price_exponent).ok()?,
+            slot: curve.cached_price_slot,
+        })
+    }
+
+    fn is_fresh(&self, curve: &OraclePeggedCurve, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.slot) <= curve.max_age_slots
+    }
+
+    /// Check the price is at most `max_age_slots` old
+    pub fn require_fresh(&self, curve: &OraclePeggedCurve, current_slot: u64) -> Result<()> {
+        require_msg!(
+            self.is_fresh(curve, current_slot),
+            SwapError::StaleOracle,
+            &format!(
+                "StaleOracle: price slot={} is {} slots old > max_age_slots={}",
+                self.slot,
+                current_slot.saturating_sub(self.slot),
+                curve.max_age_slots
+            )
+        );
+        Ok(())
+    }
+
+    fn from_pyth(data: &[u8]) -> Result<Self> {
+        require_msg!(
+            data.len() >= pyth::LEN
+                && read_u32(data, pyth::MAGIC_OFFSET)? == pyth::MAGIC
+                && read_u32(data, pyth::VERSION_OFFSET)? == pyth::VERSION
+                && read_u32(data, pyth::ACCOUNT_TYPE_OFFSET)? == pyth::ACCOUNT_TYPE_PRICE,
+            SwapError::InvalidOracle,
+            "InvalidOracle: not a Pyth v2 price account"
+        );
+        let status = read_u32(data, pyth::STATUS_OFFSET)?;
+        require_msg!(
+            status == pyth::STATUS_TRADING,
+            SwapError::StaleOracle,
+            &format!("StaleOracle: Pyth price status={} is not trading", status)
+        );
+        let value = i64::from_le_bytes(read(data, pyth::PRICE_OFFSET)?);
+        Ok(Self {
+            value: u64::try_from(value).map_err(|_| error!(SwapError::InvalidOracle))?,
+            exponent: i32::from_le_bytes(read(data, pyth::EXPONENT_OFFSET)?),
+            slot: read_u64(data, pyth::PUBLISH_SLOT_OFFSET)?,
+        })
+    }
+
+    fn from_scope(data: &[u8], index: u64) -> Result<Self> {
+        require_msg!(
+            index < scope::MAX_ENTRIES,
+            SwapError::InvalidOracle,
+            &format!("InvalidOracle: scope_price_index={} out of range", index)
+        );
+        let offset = scope::PRICES_OFFSET + index as usize * scope::DATED_PRICE_LEN;
+        let exp = read_u64(data, offset + scope::EXP_OFFSET)?;
+        Ok(Self {
+            value: read_u64(data, offset + scope::VALUE_OFFSET)?,
+            exponent: i32::try_from(exp)
+                .ok()
+                .and_then(i32::checked_neg)
+                .ok_or_else(|| error!(SwapError::InvalidOracle))?,
+            slot: read_u64(data, offset + scope::LAST_UPDATED_SLOT_OFFSET)?,
+        })
+    }
+}
+
+/// Oracle pegged curve swapping at the price read from the oracle by the instruction, or cached
+/// in the curve by `refresh_price`
+///
+/// Without a price, the curve only prices deposits and withdrawals of both tokens.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OraclePeggedCalculator {
+    pub curve: OraclePeggedCurve,
+    pub price: Option<OraclePrice>,
+}
+
+impl OraclePeggedCalculator {
+    pub fn new(curve: OraclePeggedCurve, price: Option<OraclePrice>) -> Self {
+        Self { curve, price }
+    }
+
+    /// Calculator with the price cached by `refresh_price`, if it is at most `max_age_slots` old
+    pub fn with_cached_price(curve: OraclePeggedCurve, current_slot: u64) -> Self {
+        let price =
+            OraclePrice::cached(&curve).filter(|price| price.is_fresh(&curve, current_slot));
+        Self::new(curve, price)
+    }
+
+    /// Price of one native token B in native tokens A, as `(numerator, denominator)`
+    fn native_price(&self) -> Result<(U256, U256)> {
+        let price = self.price.ok_or_else(|| {
+            msg!("StaleOracle: the oracle price was not read and no fresh price is cached");
+            error!(SwapError::StaleOracle)
+        })?;
+        let exponent = i64::from(price.exponent) + self.curve.token_a_decimals as i64
+            - self.curve.token_b_decimals as i64;
+        require_msg!(
+            exponent.abs() <= i64::from(MAX_PRICE_EXPONENT),
+            SwapError::InvalidOracle,
+            &format!("InvalidOracle: price exponent={} out of range", exponent)
+        );
+        let power = U256::from(10).pow(U256::from(exponent.unsigned_abs()));
+        let value = U256::from(price.value);
+        if exponent >= 0 {
+            Ok((try_math!(value.try_mul(power))?, U256::one()))
+        } else {
+            Ok((value, power))
+        }
+    }
+}
+
+/// Read the price of the curve from its oracle account
+pub fn read_oracle_price(
+    curve: &OraclePeggedCurve,
+    oracle: &AccountInfo,
+    current_slot: u64,
+) -> Result<OraclePrice> {
+    require_msg!(
+        oracle.key() == curve.oracle,
+        SwapError::InvalidOracle,
+        &format!(
+            "InvalidOracle: oracle={} != curve.oracle={}",
+            oracle.key(),
+            curve.oracle
+        )
+    );
+    OraclePrice::from_account_data(curve, &oracle.try_borrow_data()?, current_slot)
+}
+
+/// Build the swap curve of an oracle pegged pool with the price of its oracle account, or the
+/// price cached by `refresh_price` if the oracle account is not passed
+pub fn swap_curve_with_oracle(
+    swap_curve_info: &AccountInfo,
+    oracle: Option<&AccountInfo>,
+    current_slot: u64,
+    checksum_written: bool,
+) -> Result<SwapCurve> {
+    let curve = deserialize_curve::<OraclePeggedCurve>(swap_curve_info, checksum_written)?;
+    let price = match oracle {
+        Some(oracle) => read_oracle_price(&curve, oracle, current_slot)?,
+        None => {
+            let price = OraclePrice::cached(&curve).ok_or_else(|| {
+                msg!("InvalidOracle: the oracle account of the curve is required until refresh_price caches its price");
+                error!(SwapError::InvalidOracle)
+            })?;
+            price.require_fresh(&curve, current_slot)?;
+            price
+        }
+    };
+    Ok(SwapCurve {
+        curve_type: CurveType::OraclePegged,
+        calculator: Arc::new(OraclePeggedCalculator::new(curve, Some(price))),
+    })
+}
+
+fn to_u128(value: U256) -> Result<u128> {
+    require!(value <= U256::from(u128::MAX), SwapError::ConversionFailure);
+    Ok(value.as_u128())
+}
+
+impl CurveCalculator for OraclePeggedCalculator {
+    /// Swaps at the oracle price, adjusted by the spread in the pool's favour
+    fn swap_without_fees(
+        &self,
+        source_amount: u128,
+        _pool_source_amount: u128,
+        _pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<SwapWithoutFeesResult> {
+        let (numerator, denominator) = self.native_price()?;
+        let spread_denominator = U256::from(SPREAD_BPS_DENOMINATOR);
+
+        let (source_amount_swapped, destination_amount_swapped) = match trade_direction {
+            TradeDirection::BtoA => {
+                let bid_factor = try_math!(SPREAD_BPS_DENOMINATOR.try_sub(self.curve.spread_bps))?;
+                let destination_amount_swapped = try_math!(U256::from(source_amount)
+                    .try_mul(numerator)?
+                    .try_mul(U256::from(bid_factor))?
+                    .try_div(denominator.try_mul(spread_denominator)?))?;
+                (source_amount, to_u128(destination_amount_swapped)?)
+            }
+            TradeDirection::AtoB => {
+                let ask_factor = try_math!(SPREAD_BPS_DENOMINATOR.try_add(self.curve.spread_bps))?;
+                let ask_price = try_math!(numerator.try_mul(U256::from(ask_factor)))?;
+                let scale = try_math!(denominator.try_mul(spread_denominator))?;
+                let destination_amount_swapped =
+                    try_math!(U256::from(source_amount).try_mul(scale)?.try_div(ask_price))?;
+
+                // only take the token A needed for the floored token B amount, as the constant
+                // price curve does
+                let source_amount_swapped = try_math!(destination_amount_swapped
+                    .try_mul(ask_price)?
+                    .try_add(scale)?
+                    .try_sub(U256::one())?
+                    .try_div(scale))?;
+                (
+                    to_u128(source_amount_swapped)?,
+                    to_u128(destination_amount_swapped)?,
+                )
+            }
+        };
+        require!(
+            source_amount_swapped > 0 && destination_amount_swapped > 0,
+            SwapError::ZeroTradingTokens
+        );
+        Ok(SwapWithoutFeesResult {
+            source_amount_swapped,
+            destination_amount_swapped,
+        })
+    }
+
+    fn pool_tokens_to_trading_tokens(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        round_direction: RoundDirection,
+    ) -> Result<TradingTokenResult> {
+        math::pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            round_direction,
+        )
+    }
+
+    fn validate(&self) -> Result<()> {
+        require_msg!(
+            self.curve.oracle != Pubkey::default(),
+            SwapError::InvalidCurve,
+            "Oracle must be set for oracle pegged curve"
+        );
+        require_msg!(
+            OracleType::try_from(self.curve.oracle_type).is_ok(),
+            SwapError::InvalidCurve,
+            &format!("Unknown oracle_type={}", self.curve.oracle_type)
+        );
+        require_msg!(
+            self.curve.oracle_type != u64::from(OracleType::Scope)
+                || self.curve.scope_price_index < scope::MAX_ENTRIES,
+            SwapError::InvalidCurve,
+            &format!(
+                "scope_price_index={} must be less than {}",
+                self.curve.scope_price_index,
+                scope::MAX_ENTRIES
+            )
+        );
+        require_msg!(
+            self.curve.max_age_slots > 0,
+            SwapError::InvalidCurve,
+            "max_age_slots must be greater than 0 for oracle pegged curve"
+        );
+        require_msg!(
+            self.curve.spread_bps < SPREAD_BPS_DENOMINATOR,
+            SwapError::InvalidCurve,
+            &format!(
+                "Spread must be less than {} bps for oracle pegged curve",
+                SPREAD_BPS_DENOMINATOR
+            )
+        );
+        Ok(())
+    }
+
+    fn allows_single_sided_deposits(&self) -> bool {
+        false
+    }
+
+    /// The reserves are balanced when they are worth the same at the oracle price
+    fn reserves_imbalance_bps(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<Option<u64>> {
+        let (numerator, denominator) = self.native_price()?;
+        let token_a_value = U256::from(pool_token_a_amount);
+        let token_b_value = try_math!(U256::from(pool_token_b_amount)
+            .try_mul(numerator)?
+            .try_div(denominator))?;
+        let total = try_math!(token_a_value.try_add(token_b_value))?;
+        if total.is_zero() {
+            return Ok(Some(0));
+        }
+        let difference = if token_a_value > token_b_value {
+            try_math!(token_a_value.try_sub(token_b_value))?
+        } else {
+            try_math!(token_b_value.try_sub(token_a_value))?
+        };
+        let imbalance_bps = try_math!(difference.try_mul(U256::from(10_000))?.try_div(total))?;
+        Ok(Some(to_u64!(to_u128(imbalance_bps)?)?))
+    }
+
+    /// The value of the pool in token A at the oracle price, without the spread
+    fn normalized_value(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<PreciseNumber> {
+        let (numerator, denominator) = self.native_price()?;
+        let token_b_value = try_math!(U256::from(pool_token_b_amount)
+            .try_mul(numerator)?
+            .try_div(denominator))?;
+        let value = try_math!(token_b_value.try_add(U256::from(pool_token_a_amount)))?;
+        PreciseNumber::try_new(to_u128(value)?)
+    }
+}
+
+impl DynAccountSerialize for OraclePeggedCalculator {
+    fn try_dyn_serialize(&self, mut dst: std::cell::RefMut<&mut [u8]>) -> Result<()> {
+        let dst: &mut [u8] = &mut dst;
+        let mut cursor = std::io::Cursor::new(dst);
+        anchor_lang::AccountSerialize::try_serialize(&self.curve, &mut cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::BorrowMut;
+
+    use anchor_lang::AccountDeserialize;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        curve::calculator::{test::check_pool_value_from_deposit, INITIAL_SWAP_POOL_AMOUNT},
+        state::Curve,
+    };
+
+    const SLOT: u64 = 1_000;
+
+    /// SOL priced 150 USDC by Pyth, SOL being token B with 9 decimals and USDC token A with 6
+    const PRICE: OraclePrice = OraclePrice {
+        value: 15_000_000_000,
+        exponent: -8,
+        slot: SLOT,
+    };
+
+    fn curve(oracle_type: OracleType, spread_bps: u64) -> OraclePeggedCurve {
+        OraclePeggedCurve::new(
+            Pubkey::new_unique(),
+            oracle_type.into(),
+            3,
+            10,
+            spread_bps,
+            6,
+            9,
+        )
+    }
+
+    fn pyth_account(value: i64, exponent: i32, status: u32, slot: u64) -> Vec<u8> {
+        let mut data = vec![0; pyth::LEN];
+        let mut write = |offset: usize, bytes: &[u8]| {
+            data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        write(pyth::MAGIC_OFFSET, &pyth::MAGIC.to_le_bytes());
+        write(pyth::VERSION_OFFSET, &pyth::VERSION.to_le_bytes());
+        write(
+            pyth::ACCOUNT_TYPE_OFFSET,
+            &pyth::ACCOUNT_TYPE_PRICE.to_le_bytes(),
+        );
+        write(pyth::EXPONENT_OFFSET, &exponent.to_le_bytes());
+        write(pyth::PRICE_OFFSET, &value.to_le_bytes());
+        write(pyth::STATUS_OFFSET, &status.to_le_bytes());
+        write(pyth::PUBLISH_SLOT_OFFSET, &slot.to_le_bytes());
+        data
+    }
+
+    fn scope_account(index: u64, value: u64, exp: u64, slot: u64) -> Vec<u8> {
+        let mut data =
+            vec![0; scope::PRICES_OFFSET + scope::MAX_ENTRIES as usize * scope::DATED_PRICE_LEN];
+        let offset = scope::PRICES_OFFSET + index as usize * scope::DATED_PRICE_LEN;
+        let mut write = |field: usize, value: u64| {
+            data[offset + field..offset + field + 8].copy_from_slice(&value.to_le_bytes());
+        };
+        write(scope::VALUE_OFFSET, value);
+        write(scope::EXP_OFFSET, exp);
+        write(scope::LAST_UPDATED_SLOT_OFFSET, slot);
+        data
+    }
+
+    fn swapped(
+        calculator: &OraclePeggedCalculator,
+        source_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> SwapWithoutFeesResult {
+        calculator
+            .swap_without_fees(source_amount, 0, 0, trade_direction)
+            .unwrap()
+    }
+
+    #[test]
+    fn initial_pool_amount() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 0), None);
+        assert_eq!(calculator.new_pool_supply(), INITIAL_SWAP_POOL_AMOUNT);
+    }
+
+    #[test]
+    fn serialize_oracle_pegged_curve() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Scope, 10), Some(PRICE));
+
+        let mut arr = [0u8; Curve::LEN];
+        let packed = arr.borrow_mut();
+        let ref_mut = std::cell::RefCell::new(packed);
+
+        calculator.try_dyn_serialize(ref_mut.borrow_mut()).unwrap();
+        let unpacked = OraclePeggedCurve::try_deserialize(&mut arr.as_ref()).unwrap();
+        assert_eq!(calculator.curve, unpacked);
+    }
+
+    #[test]
+    fn validate_params() {
+        let valid = curve(OracleType::Scope, 9_999);
+        assert!(OraclePeggedCalculator::new(valid.clone(), None)
+            .validate()
+            .is_ok());
+
+        for invalid in [
+            OraclePeggedCurve {
+                oracle: Pubkey::default(),
+                ..valid.clone()
+            },
+            OraclePeggedCurve {
+                oracle_type: 2,
+                ..valid.clone()
+            },
+            OraclePeggedCurve {
+                scope_price_index: scope::MAX_ENTRIES,
+                ..valid.clone()
+            },
+            OraclePeggedCurve {
+                max_age_slots: 0,
+                ..valid.clone()
+            },
+            OraclePeggedCurve {
+                spread_bps: 10_000,
+                ..valid.clone()
+            },
+        ] {
+            assert_eq!(
+                OraclePeggedCalculator::new(invalid, None).validate(),
+                Err(error!(SwapError::InvalidCurve))
+            );
+        }
+    }
+
+    #[test]
+    fn read_pyth_price() {
+        let curve = curve(OracleType::Pyth, 0);
+        let data = pyth_account(15_000_000_000, -8, pyth::STATUS_TRADING, SLOT);
+
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &data, SLOT + 10),
+            Ok(PRICE)
+        );
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &data, SLOT + 11),
+            Err(error!(SwapError::StaleOracle))
+        );
+
+        let halted = pyth_account(15_000_000_000, -8, 2, SLOT);
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &halted, SLOT),
+            Err(error!(SwapError::StaleOracle))
+        );
+        let negative = pyth_account(-1, -8, pyth::STATUS_TRADING, SLOT);
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &negative, SLOT),
+            Err(error!(SwapError::InvalidOracle))
+        );
+        let mut not_pyth = data.clone();
+        not_pyth[pyth::MAGIC_OFFSET] = 0;
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &not_pyth, SLOT),
+            Err(error!(SwapError::InvalidOracle))
+        );
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &data[..pyth::LEN - 1], SLOT),
+            Err(error!(SwapError::InvalidOracle))
+        );
+    }
+
+    #[test]
+    fn read_scope_price() {
+        let curve = curve(OracleType::Scope, 0);
+        let data = scope_account(3, 150_000_000, 6, SLOT);
+
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &data, SLOT + 10),
+            Ok(OraclePrice {
+                value: 150_000_000,
+                exponent: -6,
+                slot: SLOT,
+            })
+        );
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &data, SLOT + 11),
+            Err(error!(SwapError::StaleOracle))
+        );
+        // another entry of the account, never published
+        let other_entry = scope_account(4, 150_000_000, 6, SLOT);
+        assert_eq!(
+            OraclePrice::from_account_data(&curve, &other_entry, SLOT),
+            Err(error!(SwapError::InvalidOracle))
+        );
+    }
+
+    #[test]
+    fn cached_price() {
+        let never_refreshed = curve(OracleType::Pyth, 0);
+        assert_eq!(OraclePrice::cached(&never_refreshed), None);
+        assert_eq!(
+            OraclePeggedCalculator::with_cached_price(never_refreshed, SLOT).price,
+            None
+        );
+
+        let refreshed = OraclePeggedCurve {
+            cached_price_value: PRICE.value,
+            cached_price_exponent: PRICE.exponent.into(),
+            cached_price_slot: PRICE.slot,
+            ..curve(OracleType::Pyth, 0)
+        };
+        assert_eq!(OraclePrice::cached(&refreshed), Some(PRICE));
+        assert_eq!(
+            OraclePeggedCalculator::with_cached_price(refreshed.clone(), SLOT + 10).price,
+            Some(PRICE)
+        );
+        assert_eq!(
+            OraclePeggedCalculator::with_cached_price(refreshed.clone(), SLOT + 11).price,
+            None
+        );
+        assert_eq!(
+            PRICE.require_fresh(&refreshed, SLOT + 11),
+            Err(error!(SwapError::StaleOracle))
+        );
+    }
+
+    #[test]
+    fn swap_at_the_oracle_price() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 0), Some(PRICE));
+
+        // 1 SOL for 150 USDC
+        let result = swapped(&calculator, 1_000_000_000, TradeDirection::BtoA);
+        assert_eq!(result.destination_amount_swapped, 150_000_000);
+        let result = swapped(&calculator, 150_000_000, TradeDirection::AtoB);
+        assert_eq!(result.destination_amount_swapped, 1_000_000_000);
+        assert_eq!(result.source_amount_swapped, 150_000_000);
+    }
+
+    #[test]
+    fn reserves_imbalance_bps_at_the_oracle_price() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 10), Some(PRICE));
+
+        // 150 USDC and 1 SOL are worth the same, the spread is ignored
+        assert_eq!(
+            calculator
+                .reserves_imbalance_bps(150_000_000, 1_000_000_000)
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            calculator
+                .reserves_imbalance_bps(450_000_000, 1_000_000_000)
+                .unwrap(),
+            Some(5_000)
+        );
+        assert_eq!(
+            calculator.reserves_imbalance_bps(0, 1_000_000_000).unwrap(),
+            Some(10_000)
+        );
+        assert_eq!(calculator.reserves_imbalance_bps(0, 0).unwrap(), Some(0));
+
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 10), None);
+        assert_eq!(
+            calculator.reserves_imbalance_bps(150_000_000, 1_000_000_000),
+            Err(error!(SwapError::StaleOracle))
+        );
+    }
+
+    #[test]
+    fn swap_with_spread() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 10), Some(PRICE));
+
+        let result = swapped(&calculator, 1_000_000_000, TradeDirection::BtoA);
+        assert_eq!(result.source_amount_swapped, 1_000_000_000);
+        assert_eq!(result.destination_amount_swapped, 149_850_000);
+        let result = swapped(&calculator, 150_000_000, TradeDirection::AtoB);
+        assert_eq!(result.destination_amount_swapped, 999_000_999);
+        assert_eq!(result.source_amount_swapped, 150_000_000);
+    }
+
+    #[test]
+    fn swap_without_price_fails() {
+        let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 0), None);
+        assert_eq!(
+            calculator.swap_without_fees(1_000_000_000, 0, 0, TradeDirection::BtoA),
+            Err(error!(SwapError::StaleOracle))
+        );
+        assert!(!calculator.allows_single_sided_deposits());
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_does_not_profit(
+            source_amount in 1..u64::MAX,
+            value in 1..u64::MAX,
+            exponent in -12..0_i32,
+            spread_bps in 0..1_000_u64,
+        ) {
+            let price = OraclePrice { value, exponent, slot: SLOT };
+            let calculator =
+                OraclePeggedCalculator::new(curve(OracleType::Pyth, spread_bps), Some(price));
+            let result = calculator.swap_without_fees(
+                u128::from(source_amount),
+                0,
+                0,
+                TradeDirection::AtoB,
+            );
+            prop_assume!(result.is_ok());
+            let result = result.unwrap();
+            prop_assert!(result.source_amount_swapped <= u128::from(source_amount));
+
+            let back = calculator.swap_without_fees(
+                result.destination_amount_swapped,
+                0,
+                0,
+                TradeDirection::BtoA,
+            );
+            prop_assume!(back.is_ok());
+            prop_assert!(back.unwrap().destination_amount_swapped <= result.source_amount_swapped);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_deposit(
+            pool_token_amount in 1..u64::MAX,
+            pool_token_supply in 1..u64::MAX,
+            pool_token_a_amount in 1..u64::MAX,
+            pool_token_b_amount in 1..u64::MAX,
+        ) {
+            let pool_token_amount = pool_token_amount as u128;
+            let pool_token_supply = pool_token_supply as u128;
+            let pool_token_a_amount = pool_token_a_amount as u128;
+            let pool_token_b_amount = pool_token_b_amount as u128;
+            // Make sure we will get at least one trading token out for each
+            // side, otherwise the calculation fails
+            prop_assume!(pool_token_amount * pool_token_a_amount / pool_token_supply >= 1);
+            prop_assume!(pool_token_amount * pool_token_b_amount / pool_token_supply >= 1);
+            // deposits do not read the price
+            let calculator = OraclePeggedCalculator::new(curve(OracleType::Pyth, 0), None);
+            check_pool_value_from_deposit(
+                &calculator,
+                pool_token_amount,
+                pool_token_supply,
+                pool_token_a_amount,
+                pool_token_b_amount,
+            );
+        }
+    }
+}
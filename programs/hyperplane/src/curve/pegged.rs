@@ -0,0 +1,289 @@
+//! The stableswap invariant around a configurable peg, for correlated pairs which are not hard
+//! pegged, such as a token and its liquid staking derivative.
+//!
+//! The reserves are valued in token A at the peg before being fed to the stableswap invariant.
+//! Near the peg the amplification concentrates the liquidity, while in an imbalanced pool the
+//! invariant degrades to constant product pricing, so a drifting pair is not drained at a stale
+//! price as it would be by a constant price pool.
+
+use anchor_lang::Result;
+use spl_math::precise_number::PreciseNumber;
+
+use crate::{
+    curve::{
+        calculator::{
+            CurveCalculator, DynAccountSerialize, RoundDirection, SwapWithoutFeesResult,
+            TradeDirection, TradingTokenResult,
+        },
+        math,
+        stable::{compute_ann, compute_d, MAX_AMP, MIN_AMP},
+    },
+    error::SwapError,
+    require_msg,
+    state::{PeggedCurve, StableCurve},
+    try_math,
+    utils::math::{TryMath, TryMathRef, TryNew},
+};
+
+impl PeggedCurve {
+    /// Stable curve pricing the peg-valued reserves
+    fn stable(&self) -> StableCurve {
+        StableCurve {
+            amp: self.amp,
+            token_a_factor: self.token_a_factor,
+            token_b_factor: self.token_b_factor,
+            ..Default::default()
+        }
+    }
+}
+
+impl CurveCalculator for PeggedCurve {
+    fn swap_without_fees(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<SwapWithoutFeesResult> {
+        self.stable().swap_without_fees(
+            source_amount,
+            pool_source_amount,
+            pool_destination_amount,
+            trade_direction,
+        )
+    }
+
+    fn pool_tokens_to_trading_tokens(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        round_direction: RoundDirection,
+    ) -> Result<TradingTokenResult> {
+        math::pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            round_direction,
+        )
+    }
+
+    fn max_source_amount(
+        &self,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<Option<u128>> {
+        self.stable().max_source_amount(
+            pool_source_amount,
+            pool_destination_amount,
+            trade_direction,
+        )
+    }
+
+    /// The reserves are balanced when they are worth the same at the peg
+    fn reserves_imbalance_bps(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<Option<u64>> {
+        self.stable()
+            .reserves_imbalance_bps(pool_token_a_amount, pool_token_b_amount)
+    }
+
+    fn validate(&self) -> Result<()> {
+        require_msg!(
+            self.amp > MIN_AMP,
+            SwapError::InvalidCurve,
+            &format!("amp={} <= MIN_AMP={}", self.amp, MIN_AMP)
+        );
+        require_msg!(
+            self.amp < MAX_AMP,
+            SwapError::InvalidCurve,
+            &format!("amp={} >= MAX_AMP={}", self.amp, MAX_AMP)
+        );
+        require_msg!(
+            self.token_b_peg > 0,
+            SwapError::InvalidCurve,
+            "token_b_peg=0"
+        );
+        Ok(())
+    }
+
+    /// The stableswap invariant of the peg-valued reserves, in native token A
+    fn normalized_value(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<PreciseNumber> {
+        let d = compute_d(
+            compute_ann(self.amp)?,
+            try_math!(pool_token_a_amount.try_mul(u128::from(self.token_a_factor)))?,
+            try_math!(pool_token_b_amount.try_mul(u128::from(self.token_b_factor)))?,
+        )?;
+        PreciseNumber::try_new(d)?
+            .try_div(&PreciseNumber::try_new(u128::from(self.token_a_factor))?)
+    }
+}
+
+impl DynAccountSerialize for PeggedCurve {
+    fn try_dyn_serialize(&self, mut dst: std::cell::RefMut<&mut [u8]>) -> Result<()> {
+        let dst: &mut [u8] = &mut dst;
+        let mut cursor = std::io::Cursor::new(dst);
+        anchor_lang::AccountSerialize::try_serialize(self, &mut cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::BorrowMut;
+
+    use anchor_lang::{error, AccountDeserialize};
+
+    use super::*;
+    use crate::{
+        curve::calculator::INITIAL_SWAP_POOL_AMOUNT,
+        state::{ConstantProductCurve, Curve},
+    };
+
+    /// Price of the derivative, token B, in the underlying, token A
+    const PEG: u64 = 1_050_000_000;
+
+    fn swapped(
+        curve: &dyn CurveCalculator,
+        source: u128,
+        pool_source: u128,
+        pool_dest: u128,
+    ) -> u128 {
+        curve
+            .swap_without_fees(source, pool_source, pool_dest, TradeDirection::BtoA)
+            .unwrap()
+            .destination_amount_swapped
+    }
+
+    #[test]
+    fn factors_apply_decimals_and_peg() {
+        let curve = PeggedCurve::new(10, PEG, 6, 9).unwrap();
+        assert_eq!(curve.token_a_factor, 1_000 * PeggedCurve::PEG_SCALE);
+        assert_eq!(curve.token_b_factor, PEG);
+
+        let curve = PeggedCurve::new(10, PEG, 9, 6).unwrap();
+        assert_eq!(curve.token_a_factor, PeggedCurve::PEG_SCALE);
+        assert_eq!(curve.token_b_factor, 1_000 * PEG);
+
+        assert!(PeggedCurve::new(10, PEG, 0, 18).is_err());
+    }
+
+    #[test]
+    fn validate_params() {
+        assert!(PeggedCurve::new(10, PEG, 6, 6).unwrap().validate().is_ok());
+        for curve in [
+            PeggedCurve::new(MIN_AMP, PEG, 6, 6).unwrap(),
+            PeggedCurve::new(MAX_AMP, PEG, 6, 6).unwrap(),
+            PeggedCurve::new(10, 0, 6, 6).unwrap(),
+        ] {
+            assert_eq!(curve.validate(), Err(error!(SwapError::InvalidCurve)));
+        }
+    }
+
+    #[test]
+    fn deeper_than_constant_product_at_the_peg() {
+        let curve = PeggedCurve::new(10, PEG, 6, 6).unwrap();
+        // reserves of equal value at the peg
+        let pool_a = 1_050_000_000;
+        let pool_b = 1_000_000_000;
+
+        let pegged = swapped(&curve, 10_000_000, pool_b, pool_a);
+        let constant_product =
+            swapped(&ConstantProductCurve::default(), 10_000_000, pool_b, pool_a);
+
+        assert!(pegged > constant_product);
+        assert!(pegged < 10_500_000);
+    }
+
+    #[test]
+    fn price_moves_away_from_the_peg_when_imbalanced() {
+        let curve = PeggedCurve::new(10, PEG, 6, 6).unwrap();
+        // most of token A already swapped out of the pool
+        let pool_a = 100_000_000;
+        let pool_b = 2_000_000_000;
+
+        let pegged = swapped(&curve, 10_000_000, pool_b, pool_a);
+
+        assert!(pegged < 10_500_000 / 2);
+    }
+
+    #[test]
+    fn reserves_imbalance_bps_at_the_peg() {
+        let curve = PeggedCurve::new(10, PEG, 6, 6).unwrap();
+        assert_eq!(
+            curve
+                .reserves_imbalance_bps(1_050_000_000, 1_000_000_000)
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            curve
+                .reserves_imbalance_bps(3_150_000_000, 1_000_000_000)
+                .unwrap(),
+            Some(5_000)
+        );
+        assert_eq!(
+            curve.reserves_imbalance_bps(0, 1_000_000_000).unwrap(),
+            Some(10_000)
+        );
+    }
+
+    #[test]
+    fn normalized_value_in_token_a() {
+        let curve = PeggedCurve::new(10, PEG, 6, 9).unwrap();
+        let value = curve
+            .normalized_value(1_050_000, 1_000_000_000)
+            .unwrap()
+            .to_imprecise()
+            .unwrap();
+        assert_eq!(value, 2_100_000);
+    }
+
+    #[test]
+    fn swap_does_not_decrease_value() {
+        let curve = PeggedCurve::new(10, PEG, 6, 6).unwrap();
+        let pool_a = 1_050_000_000u128;
+        let pool_b = 1_000_000_000u128;
+        let value_before = curve.normalized_value(pool_a, pool_b).unwrap();
+
+        for source in [1, 1_000, 10_000_000, 900_000_000] {
+            let result = curve
+                .swap_without_fees(source, pool_b, pool_a, TradeDirection::BtoA)
+                .unwrap();
+            let value_after = curve
+                .normalized_value(
+                    pool_a - result.destination_amount_swapped,
+                    pool_b + result.source_amount_swapped,
+                )
+                .unwrap();
+            assert!(value_after.greater_than_or_equal(&value_before));
+        }
+    }
+
+    #[test]
+    fn initial_pool_amount() {
+        let curve = PeggedCurve::new(10, PEG, 6, 6).unwrap();
+        assert_eq!(curve.new_pool_supply(), INITIAL_SWAP_POOL_AMOUNT);
+    }
+
+    #[test]
+    fn serialize_pegged_curve() {
+        let curve = PeggedCurve::new(10, PEG, 6, 9).unwrap();
+
+        let mut arr = [0u8; Curve::LEN];
+        let packed = arr.borrow_mut();
+        let ref_mut = std::cell::RefCell::new(packed);
+
+        curve.try_dyn_serialize(ref_mut.borrow_mut()).unwrap();
+        let unpacked = PeggedCurve::try_deserialize(&mut arr.as_ref()).unwrap();
+        assert_eq!(curve, unpacked);
+    }
+}
@@ -1,7 +1,7 @@
 //! The stableswap invariant calculator.
 use std::convert::TryFrom;
 
-use anchor_lang::{error, Result};
+use anchor_lang::{err, error, prelude::msg, Result};
 use spl_math::{checked_ceil_div::CheckedCeilDiv, precise_number::PreciseNumber, uint::U256};
 
 use crate::{
@@ -15,7 +15,7 @@ use crate::{
     error::SwapError,
     require_msg,
     state::StableCurve,
-    try_math,
+    to_u64, try_math,
     utils::math::{AbsDiff, TryMath, TryNew},
 };
 
@@ -32,7 +32,7 @@ pub const MAX_AMP: u64 = 1_000_000;
 /// Calculates An**n for deriving D
 ///
 /// We choose to use A * n rather than A * n**n because `D**n / prod(x)` loses precision with a huge A value.
-fn compute_ann(amp: u64) -> Result<u64> {
+pub(crate) fn compute_ann(amp: u64) -> Result<u64> {
     amp.try_mul(N_COINS as u64)
 }
 
@@ -114,7 +114,10 @@ fn compute_next_d(ann: u64, d_init: &U256, d_product: &U256, sum_x: u128) -> Res
 /// * `ann` - The invariant of A - the amplification coefficient times n**(n-1)
 /// * `amount_a` - The number of A tokens in the pool
 /// * `amount_b` - The number of B tokens in the pool
-fn compute_d(ann: u64, amount_a: u128, amount_b: u128) -> Result<u128> {
+///
+/// On heavily imbalanced pools the integer iteration can settle into a small oscillation instead
+/// of converging, the last estimate is returned in that case rather than `CurveNonConvergence`.
+pub(crate) fn compute_d(ann: u64, amount_a: u128, amount_b: u128) -> Result<u128> {
     let sum_x = try_math!(amount_a.try_add(amount_b))?; // sum(x_i), a.k.a S
     if sum_x == 0 {
         Ok(0)
@@ -211,12 +214,56 @@ fn compute_y(ann: u64, x: u128, d: u128) -> Result<u128> {
             }
         });
         if y_new == y {
-            break;
-        } else {
-            y = y_new;
+            return u128::try_from(y).map_err(|_| error!(SwapError::CalculationFailure));
         }
+        y = y_new;
+    }
+    msg!(
+        "CurveNonConvergence: y did not converge in {} iterations, x={}, d={}",
+        ITERATIONS,
+        x,
+        d
+    );
+    err!(SwapError::CurveNonConvergence)
+}
+
+/// Largest pool source amount `x` for which the destination amount `y` solved by `compute_y` is
+/// still at least one token, beyond it the destination reserve is only drained by rounding
+///
+/// `y >= 1` holds while f(1) <= 0 for the polynomial solved by `compute_y`, i.e.
+///
+/// ```md
+/// 1 + (b - D) - c <= 0
+///
+/// Multiplying by x and expanding b and c:
+///
+/// x**2 - (D - D / Ann - 1) * x - D**(n+1) / (n**n * Ann) <= 0
+///
+/// x_max = (m + sqrt(m**2 + D**3 / Ann)) / 2, where m = D - D / Ann - 1
+/// ```
+///
+/// * `ann` - A * n**n - Ann - The invariant of A - the amplification coefficient times n**(n-1)
+/// * `d` - D - The total amount of tokens when they have an equal price i.e. at equilibrium when all tokens have equal balance
+fn compute_max_x(ann: u64, d: u128) -> Result<u128> {
+    if d == 0 {
+        return Ok(0);
     }
-    u128::try_from(y).map_err(|_| error!(SwapError::CalculationFailure))
+    let ann: U256 = ann.into();
+    let d: U256 = d.into();
+    // m = D - D / Ann - 1, non-negative as Ann > 1
+    let m = try_math!(d.try_sub(d.try_div(ann)?)?.try_sub(U256::one()))?;
+    let max_x = match d
+        .checked_mul(d)
+        .and_then(|d_squared| d_squared.checked_mul(d))
+    {
+        Some(d_cubed) => {
+            let discriminant = try_math!(m.try_mul(m)?.try_add(d_cubed.try_div(ann)?))?;
+            try_math!(m.try_add(discriminant.integer_sqrt())?.try_div(2.into()))?
+        }
+        // beyond any amount a token account can hold
+        None => return Ok(u128::MAX),
+    };
+    Ok(u128::try_from(max_x).unwrap_or(u128::MAX))
 }
 
 fn scale_up(source_amount: u128, factor: u64) -> Result<u128> {
@@ -396,6 +443,58 @@ impl CurveCalculator for StableCurve {
         )
     }
 
+    /// The pool source amount after the swap is bounded by [`compute_max_x`]
+    fn max_source_amount(
+        &self,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Result<Option<u128>> {
+        let ann = compute_ann(self.amp)?;
+        let (_, pool_source_amt_scaled, pool_dest_amt_scaled) = try_math!(scale_swap_inputs(
+            self,
+            0,
+            pool_source_amount,
+            pool_destination_amount,
+            trade_direction,
+        ))?;
+        let max_new_source_amount = try_math!(compute_max_x(
+            ann,
+            try_math!(compute_d(ann, pool_source_amt_scaled, pool_dest_amt_scaled))?,
+        ))?;
+        let source_factor = match trade_direction {
+            TradeDirection::AtoB => self.token_a_factor,
+            TradeDirection::BtoA => self.token_b_factor,
+        };
+        let max_source_amount = try_math!(scale_down(
+            max_new_source_amount.saturating_sub(pool_source_amt_scaled),
+            source_factor,
+            false, // round down so the bound is never exceeded once scaled back up
+        ))?;
+        Ok(Some(max_source_amount))
+    }
+
+    /// The reserves are balanced when equal once scaled to the same decimals
+    fn reserves_imbalance_bps(
+        &self,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> Result<Option<u64>> {
+        let pool_token_a_amt_scaled =
+            try_math!(scale_up(pool_token_a_amount, self.token_a_factor))?;
+        let pool_token_b_amt_scaled =
+            try_math!(scale_up(pool_token_b_amount, self.token_b_factor))?;
+        let total = try_math!(pool_token_a_amt_scaled.try_add(pool_token_b_amt_scaled))?;
+        if total == 0 {
+            return Ok(Some(0));
+        }
+        let imbalance_bps = try_math!(pool_token_a_amt_scaled
+            .abs_diff(pool_token_b_amt_scaled)
+            .try_mul(10_000)?
+            .try_div(total))?;
+        Ok(Some(to_u64!(imbalance_bps)?))
+    }
+
     fn validate(&self) -> Result<()> {
         require_msg!(
             self.amp > MIN_AMP,
@@ -468,7 +567,7 @@ mod tests {
     // useful for d.p. clarity in tests
     #![allow(clippy::inconsistent_digit_grouping)]
 
-    use std::{borrow::BorrowMut, cmp::Ordering};
+    use std::{borrow::BorrowMut, cmp::Ordering, sync::Arc};
 
     use anchor_lang::AccountDeserialize;
     use hyperplane_sim::StableSwapModel;
@@ -476,12 +575,16 @@ mod tests {
 
     use super::*;
     use crate::{
-        curve::calculator::{
-            test::{
-                check_curve_value_from_swap, check_pool_value_from_deposit,
-                check_pool_value_from_withdraw, total_and_intermediate,
+        curve::{
+            base::{CurveType, SwapCurve},
+            calculator::{
+                test::{
+                    check_curve_value_from_swap, check_pool_value_from_deposit,
+                    check_pool_value_from_withdraw, total_and_intermediate,
+                },
+                RoundDirection, INITIAL_SWAP_POOL_AMOUNT,
             },
-            RoundDirection, INITIAL_SWAP_POOL_AMOUNT,
+            fees::Fees,
         },
         state::Curve,
         utils::math::decimals_to_factor,
@@ -550,6 +653,84 @@ mod tests {
         assert_eq!(result.destination_amount_swapped, 0);
     }
 
+    #[test]
+    fn reserves_imbalance_bps() {
+        // token A has 6 decimals and token B 9, so A is scaled up by 1_000
+        let curve = StableCurve::new(100, 6, 9).unwrap();
+        assert_eq!(
+            curve.reserves_imbalance_bps(1_000, 1_000_000).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            curve.reserves_imbalance_bps(3_000, 1_000_000).unwrap(),
+            Some(5_000)
+        );
+        assert_eq!(
+            curve.reserves_imbalance_bps(0, 1_000_000).unwrap(),
+            Some(10_000)
+        );
+        assert_eq!(curve.reserves_imbalance_bps(0, 0).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn max_source_amount_boundary() {
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::Stable,
+            calculator: Arc::new(StableCurve::new(100, 6, 6).unwrap()),
+        };
+        let pool_source_amount = 1_000_000;
+        let pool_destination_amount = 1_000_000;
+
+        let max_source_amount = swap_curve
+            .calculator
+            .max_source_amount(
+                pool_source_amount,
+                pool_destination_amount,
+                TradeDirection::AtoB,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(max_source_amount, 99_999_949);
+
+        let result = swap_curve
+            .swap(
+                max_source_amount,
+                pool_source_amount,
+                pool_destination_amount,
+                TradeDirection::AtoB,
+                &Fees::default(),
+            )
+            .unwrap();
+        assert!(result.new_pool_destination_amount >= 1);
+
+        assert_eq!(
+            swap_curve
+                .swap(
+                    max_source_amount + 1,
+                    pool_source_amount,
+                    pool_destination_amount,
+                    TradeDirection::AtoB,
+                    &Fees::default(),
+                )
+                .unwrap_err(),
+            error!(SwapError::TradeTooLargeForConvergence)
+        );
+    }
+
+    #[test]
+    fn max_source_amount_of_tiny_pool() {
+        let curve = StableCurve::new(100, 6, 6).unwrap();
+
+        assert_eq!(
+            curve.max_source_amount(5, 7, TradeDirection::AtoB).unwrap(),
+            Some(6)
+        );
+        assert_eq!(
+            curve.max_source_amount(1, 1, TradeDirection::BtoA).unwrap(),
+            Some(0)
+        );
+    }
+
     #[test]
     fn serialize_stable_curve() {
         let amp = u64::MAX;
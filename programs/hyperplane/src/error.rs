@@ -77,6 +77,99 @@ pub enum SwapError {
     InvaliPoolAdmin,
     #[msg("Token 2022 extension is not supported")]
     InvalidTokenExtension,
+    #[msg("Signer is not an allowed taker of this permissioned pool")]
+    SwapTakerNotAllowed,
+    #[msg("Instruction is disabled in the feature set")]
+    FeatureDisabled,
+    #[msg("No transfer amount nets exactly the required amount after the token transfer fee")]
+    TransferFeeGrossUpImpossible,
+    #[msg("The approval window of the proposed config change has not passed yet")]
+    ProposalNotExecutable,
+    #[msg("Pool token mint authority is not the pool authority")]
+    InvalidPoolTokenMintAuthority,
+    #[msg("Swap moves the pool price outside of its min and max price bounds")]
+    PriceOutOfBounds,
+    #[msg("Min price must not be greater than max price")]
+    InvalidPriceBounds,
+    #[msg("Swap amount is beyond what the curve can reliably solve for the pool reserves")]
+    TradeTooLargeForConvergence,
+    #[msg("Curve calculation overflowed for the swap amount and pool reserves")]
+    CurveOverflow,
+    #[msg("Curve calculation did not converge within the iteration limit")]
+    CurveNonConvergence,
+    #[msg("Pool reserves are empty or too low to pay out the swap")]
+    EmptyReserves,
+    #[msg("User stats account does not belong to the pool and the swap token accounts owner")]
+    InvalidUserStats,
+    #[msg("Swaps to or from token accounts owned by the pool admin are not allowed")]
+    SelfTradeNotAllowed,
+    #[msg("Pool vault has a delegate or close authority, revoke it with revoke_vault_delegates")]
+    VaultDelegateSet,
+    #[msg("Airdrop snapshot slot must be after the current slot")]
+    InvalidAirdropSnapshotSlot,
+    #[msg("Airdrop snapshot slot has passed, pool tokens can no longer be registered")]
+    AirdropRegistrationClosed,
+    #[msg("Airdrop snapshot slot has not passed yet or registered positions are still unclaimed")]
+    AirdropNotClaimable,
+    #[msg("Pool requires both trading tokens to be owned by the same token program")]
+    MixedTokenPrograms,
+    #[msg("Pool emits CPI events, the event authority and program accounts must be passed last")]
+    MissingEventAuthority,
+    #[msg("Pool vault balance did not move by the amount the instruction was computed on")]
+    VaultBalanceMismatch,
+    /// No longer returned, `initialize_test_pool` is left out of the builds without `dev-mode`
+    #[msg("Test pools can only be initialized by a program built with the dev-mode feature")]
+    TestPoolsDisabled,
+    #[msg("A flash loan of the pool is ongoing, it must be repaid first")]
+    FlashLoanActive,
+    #[msg("Flash borrows must be followed by a flash_repay of the same pool in the transaction")]
+    FlashRepayMissing,
+    #[msg("Flash borrows cannot be invoked through CPI")]
+    FlashBorrowCpi,
+    #[msg("No flash loan of this token is ongoing")]
+    FlashLoanNotActive,
+    #[msg("Signer is not an allow-listed liquidity provider of the pool")]
+    LiquidityProviderNotAllowed,
+    #[msg("Pool price is outside of the asserted bounds")]
+    PoolPriceOutOfRange,
+    #[msg("Pool can only be closed once all pool tokens are burned and no host fees are owed")]
+    PoolNotClosable,
+    #[msg("Curve account does not match its checksum")]
+    CurveCorrupted,
+    #[msg("Signer is not the pending admin of the pool")]
+    InvalidPendingAdmin,
+    #[msg("Operation is paused on this pool")]
+    OperationPaused,
+    #[msg("Successor mint or conversion result does not match the migrated mint 1:1")]
+    InvalidMintMigration,
+    #[msg("Liquidity lock duration must be at least one slot")]
+    InvalidLockDuration,
+    #[msg("Locked pool tokens cannot be unlocked before their unlock slot")]
+    LiquidityLocked,
+    #[msg("Transaction was processed after its deadline")]
+    DeadlineExceeded,
+    #[msg("Oracle price is older than the maximum age of the curve or not trading")]
+    StaleOracle,
+    #[msg("Oracle account is missing, does not match the curve or cannot be read")]
+    InvalidOracle,
+    #[msg("Swap batch has no fills or more than the maximum")]
+    InvalidSwapBatch,
+    #[msg("Token 2022 extension of the mint is not supported by the pool")]
+    UnsupportedTokenExtension,
+    #[msg("Fee tier is not set in the fee tier registry")]
+    InvalidFeeTier,
+    #[msg("The pool guardian can only pause the pool")]
+    GuardianCannotUnpause,
+    #[msg("Swap takes too much of the reserves or moves the price too far")]
+    TradeTooLarge,
+    #[msg("Net outflow of the pool in this slot exceeds its limit")]
+    SlotOutflowExceeded,
+    #[msg("Single sided withdrawals are off while the pool reserves are too imbalanced")]
+    PoolImbalanced,
+    #[msg("The checksum of the pool curve is already written")]
+    CurveChecksumWritten,
+    #[msg("Conversion program or accounts are not allowed for mint migrations")]
+    InvalidConversionProgram,
 }
 
 impl From<SwapError> for ProgramError {
@@ -1,6 +1,40 @@
-use anchor_lang::{event, prelude::borsh, AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{
+    event,
+    prelude::{borsh, Pubkey},
+    AnchorDeserialize, AnchorSerialize,
+};
 
-use crate::state::{UpdatePoolConfigMode, UpdatePoolConfigValue};
+use crate::{
+    curve::fees::Fees,
+    state::{Feature, UpdatePoolConfigMode, UpdatePoolConfigValue},
+};
+
+#[event]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolCreated {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub swap_curve: Pubkey,
+    pub pool_authority: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub pool_token_mint: Pubkey,
+    pub token_a_fees_vault: Pubkey,
+    pub token_b_fees_vault: Pubkey,
+    /// [crate::curve::base::CurveType] of the pool
+    pub curve_type: u64,
+    pub fees: Fees,
+    pub initial_supply_a: u64,
+    pub initial_supply_b: u64,
+    /// Pool tokens minted to the admin
+    pub initial_pool_token_amount: u64,
+    /// Owner trade fees are left in the pool vault instead of the fees vault
+    pub burn_owner_fees: bool,
+    /// Both trading tokens were required to be owned by the same token program
+    pub strict_token_program: bool,
+}
 
 #[event]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -8,6 +42,16 @@ pub struct Deposit {
     pub token_a_amount: u64,
     pub token_b_amount: u64,
     pub pool_token_amount: u64,
+    /// Pool token supply after the deposit
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the deposit, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the deposit
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the deposit
+    pub token_b_reserve_after: u64,
+    /// Referrer the deposit is attributed to, if any
+    pub referrer: Option<Pubkey>,
 }
 
 #[event]
@@ -18,21 +62,61 @@ pub struct Withdraw {
     pub pool_token_amount: u64,
     pub token_a_fees: u64,
     pub token_b_fees: u64,
+    /// Pool token supply after the withdrawal
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the withdrawal, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the withdrawal
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the withdrawal
+    pub token_b_reserve_after: u64,
 }
 
 #[event]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Swap {
     pub token_in_amount: u64,
+    /// Destination tokens sent to the user, net of a host fee paid in destination tokens
     pub token_out_amount: u64,
     /// The total fees collected (includes owner, trading, + host fees)
     pub total_fees: u64,
+    /// Owner fees left in the pool vault when the pool burns owner fees, included in `token_in_amount`
+    pub owner_fees_burned: u64,
+    /// The source or destination token account is owned by the pool admin, only possible
+    /// when the pool does not prevent self trades - excluded from volume by indexers
+    pub self_trade: bool,
+    /// Share of the owner fees paid to a host, included in `total_fees` unless it was paid in
+    /// destination tokens
+    pub host_fee: u64,
+    /// Host fees account the host fee was paid to, none when the swap passed no host fees account
+    /// and the host fee accrued to the default host fees recipient of the pool
+    pub host_fee_recipient: Option<Pubkey>,
+    /// Trade fee left in the pool vault for the LPs, included in `total_fees`
+    pub trade_fee: u64,
+    /// Trade fee numerator charged, over the trade fee denominator of the pool. Differs from the
+    /// pool fees when dynamic fees raise it with the volatility.
+    pub trade_fee_numerator: u64,
+    /// Share of the owner fees owed to the protocol fee authority, included in `total_fees`
+    pub protocol_fee: u64,
+    /// The host fee was carved out of the destination tokens of the swap
+    pub host_fee_in_destination_token: bool,
+    /// Token A vault balance after the swap
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the swap
+    pub token_b_reserve_after: u64,
 }
 
 #[event]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WithdrawFees {
     pub withdraw_amount: u64,
+    /// Token A vault balance when the fees were withdrawn
+    pub token_a_reserve: u64,
+    /// Token B vault balance when the fees were withdrawn
+    pub token_b_reserve: u64,
+    /// Pool token supply when the fees were withdrawn
+    pub pool_token_supply: u64,
+    pub slot: u64,
 }
 
 #[event]
@@ -40,4 +124,414 @@ pub struct WithdrawFees {
 pub struct UpdatePoolConfig {
     pub mode: UpdatePoolConfigMode,
     pub value: UpdatePoolConfigValue,
+    /// Token A vault balance when the config was changed
+    pub token_a_reserve: u64,
+    /// Token B vault balance when the config was changed
+    pub token_b_reserve: u64,
+    /// Pool token supply when the config was changed
+    pub pool_token_supply: u64,
+    pub slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposePoolConfig {
+    pub mode: UpdatePoolConfigMode,
+    pub value: UpdatePoolConfigValue,
+    /// First slot the change can be executed in
+    pub executable_slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddSwapTaker {
+    pub taker: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveSwapTaker {
+    pub taker: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpdateFeatureSet {
+    pub feature: Feature,
+    pub enabled: bool,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetConversionProgram {
+    pub index: u16,
+    /// Conversion program allowed in the slot, default pubkey if the slot was cleared
+    pub program: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetFeeTier {
+    pub tier: u16,
+    pub fees: Fees,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebalanceAcrossPools {
+    pub source_pool_token_amount: u64,
+    pub destination_pool_token_amount: u64,
+    /// Trading tokens moved from the source pool into the destination pool
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// Trading tokens withdrawn from the source pool that were returned to the admin
+    pub token_a_remaining: u64,
+    pub token_b_remaining: u64,
+    /// Owner withdrawal fees of the source pool sent to its fees vaults
+    pub token_a_fees: u64,
+    pub token_b_fees: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestWithdraw {
+    pub pool_token_amount: u64,
+    pub minimum_token_a_amount: u64,
+    pub minimum_token_b_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokeVaultDelegates {
+    /// Vaults which had a delegate or close authority cleared, empty if all vaults were clean
+    pub revoked_vaults: Vec<Pubkey>,
+    pub slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateLpAirdrop {
+    pub airdrop: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Reward tokens received by the airdrop vault
+    pub reward_amount: u64,
+    pub snapshot_slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisterLpAirdrop {
+    pub pool_token_amount: u64,
+    /// Pool tokens registered with the airdrop by all owners after this registration
+    pub total_registered_pool_tokens: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimLpAirdrop {
+    pub reward_amount: u64,
+    /// Registered pool tokens returned to the owner
+    pub pool_token_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloseLpAirdrop {
+    /// Unclaimed reward tokens returned to the sponsor
+    pub reward_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockLiquidity {
+    pub position: Pubkey,
+    pub pool_token_amount: u64,
+    /// First slot the pool tokens can be unlocked in
+    pub unlock_slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnlockLiquidity {
+    /// Pool tokens of the vault returned to the owner
+    pub pool_token_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Donation {
+    pub donor: Pubkey,
+    /// Tokens received by the vaults, the transfer fees were paid on top
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// Token A vault balance after the donation
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the donation
+    pub token_b_reserve_after: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkimLamports {
+    pub treasury: Pubkey,
+    /// Lamports above rent exemption moved out of each account
+    pub pool_lamports: u64,
+    pub swap_curve_lamports: u64,
+    pub pool_authority_lamports: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetDefaultHostFees {
+    pub recipient: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimDefaultHostFees {
+    pub recipient: Pubkey,
+    pub claim_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositSingle {
+    /// Mint of the deposited trading token
+    pub source_mint: Pubkey,
+    /// Source tokens debited from the user, fees included
+    pub source_token_amount: u64,
+    pub pool_token_amount: u64,
+    /// Fee left in the pool for the LPs
+    pub trade_fee: u64,
+    /// Fee sent to the fees vault, or burned in the pool vault
+    pub owner_fee: u64,
+    /// Pool token supply after the deposit
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the deposit, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the deposit
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the deposit
+    pub token_b_reserve_after: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendPool {
+    /// Size of the pool account before and after the extension, in bytes
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawSingle {
+    /// Mint of the withdrawn trading token
+    pub destination_mint: Pubkey,
+    /// Destination tokens credited to the user
+    pub destination_token_amount: u64,
+    pub pool_token_amount: u64,
+    /// Fee left in the pool for the LPs
+    pub trade_fee: u64,
+    /// Fee sent to the fees vault, or burned in the pool vault
+    pub owner_fee: u64,
+    /// Owner withdrawal fee sent to the fees vault
+    pub withdraw_fee: u64,
+    /// Pool token supply after the withdrawal
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the withdrawal, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the withdrawal
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the withdrawal
+    pub token_b_reserve_after: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapRouteTwoHop {
+    pub first_pool: Pubkey,
+    pub second_pool: Pubkey,
+    /// Source tokens taken from the user, including the owner fees of the first pool
+    pub token_in_amount: u64,
+    /// Intermediate tokens received from the first pool and swapped in the second pool
+    pub intermediate_amount: u64,
+    /// Destination tokens received by the user
+    pub token_out_amount: u64,
+    /// Trade and owner fees of each leg, in the source and intermediate token
+    pub first_total_fees: u64,
+    pub second_total_fees: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapBatch {
+    pub fills: u64,
+    /// Source tokens taken from the user over all the fills, including the owner fees
+    pub token_in_amount: u64,
+    /// Destination tokens taken from the pool over all the fills
+    pub token_out_amount: u64,
+    /// Trade and owner fees of all the fills, in the source token
+    pub total_fees: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlashLoan {
+    /// Mint of the borrowed trading token
+    pub mint: Pubkey,
+    /// Tokens lent out of the pool vault and repaid
+    pub amount: u64,
+    /// Flash fee repaid on top of the loan, left in the pool for the LPs
+    pub fee: u64,
+    /// Vault balance of the borrowed token after the repayment
+    pub reserve_after: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddLiquidityProvider {
+    pub provider: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveLiquidityProvider {
+    pub provider: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClosePool {
+    /// Pool tokens of the admin burned before closing
+    pub pool_tokens_burned: u64,
+    /// Token A left in the vault, returned to the admin
+    pub token_a_amount: u64,
+    /// Token B left in the vault, returned to the admin
+    pub token_b_amount: u64,
+    /// Token A left in the fees vault, returned to the admin
+    pub token_a_fees: u64,
+    /// Token B left in the fees vault, returned to the admin
+    pub token_b_fees: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawProtocolFees {
+    /// Mint of the fees vault the protocol fees were withdrawn from
+    pub mint: Pubkey,
+    pub withdraw_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposeAdmin {
+    /// Admin taking over once it accepts, default pubkey if the transfer was cancelled
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AcceptAdmin {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetGuardian {
+    /// Guardian allowed to pause the pool, default pubkey if the guardian was removed
+    pub guardian: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteCurveChecksum {
+    /// Checksum written at the end of the curve account
+    pub checksum: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefreshPrice {
+    /// Oracle price cached in the curve, `value * 10^exponent` tokens A per token B
+    pub value: u64,
+    pub exponent: i32,
+    /// Slot the price was published at
+    pub slot: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SweepProtocolFees {
+    /// Mint of the fees vault the protocol fees were swept from
+    pub mint: Pubkey,
+    pub sweep_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolStatusChanged {
+    /// Paused operations before the update, a bitmask indexed by `PoolOperation`
+    pub old_status: u64,
+    pub new_status: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrateMint {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    /// Balance of the vault converted to the successor mint
+    pub vault_amount: u64,
+    /// Balance of the fees vault converted to the successor mint
+    pub fees_vault_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositBothExactIn {
+    /// Token A debited from the user, owner fee included
+    pub token_a_amount: u64,
+    /// Token B debited from the user, owner fee included
+    pub token_b_amount: u64,
+    pub pool_token_amount: u64,
+    /// Mint of the remainder deposited as a single token, if any
+    pub rebalanced_mint: Option<Pubkey>,
+    /// Fee of the remainder left in the pool for the LPs
+    pub trade_fee: u64,
+    /// Fee of the remainder sent to the fees vault, or burned in the pool vault
+    pub owner_fee: u64,
+    /// Pool token supply after the deposit
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the deposit, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the deposit
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the deposit
+    pub token_b_reserve_after: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawExactAmountsOut {
+    /// Token A credited to the user
+    pub token_a_amount: u64,
+    /// Token B credited to the user
+    pub token_b_amount: u64,
+    pub pool_token_amount: u64,
+    /// Part of the pool tokens burned for the remainders withdrawn as a single token
+    pub single_sided_pool_token_amount: u64,
+    /// Owner withdrawal and trade fees in token A sent to the fees vault
+    pub token_a_fees: u64,
+    /// Owner withdrawal and trade fees in token B sent to the fees vault
+    pub token_b_fees: u64,
+    /// Pool token supply after the withdrawal
+    pub pool_token_supply_after: u64,
+    /// Share of the pool token supply held by the user after the withdrawal, in basis points
+    pub user_share_bps_after: u64,
+    /// Token A vault balance after the withdrawal
+    pub token_a_reserve_after: u64,
+    /// Token B vault balance after the withdrawal
+    pub token_b_reserve_after: u64,
 }
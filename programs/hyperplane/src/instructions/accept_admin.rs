@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{emitted, error::SwapError, event, require_msg, state::SwapPool};
+
+/// Take over the pool admin rights proposed by the current admin with `propose_admin`
+///
+/// Requiring the signature of the new admin makes sure the pool cannot be handed over to a key
+/// nobody controls.
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<event::AcceptAdmin> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let pending_admin = ctx.accounts.pending_admin.key();
+    require_msg!(
+        pool.pending_admin == pending_admin,
+        SwapError::InvalidPendingAdmin,
+        &format!(
+            "InvalidPendingAdmin: signer={}, pending_admin={}",
+            pending_admin, pool.pending_admin
+        )
+    );
+
+    let previous_admin = pool.admin;
+    pool.admin = pending_admin;
+    pool.pending_admin = Pubkey::default();
+
+    msg!("Accepted pool admin: {} -> {}", previous_admin, pool.admin);
+
+    emitted!(event::AcceptAdmin {
+        previous_admin,
+        admin: pending_admin,
+    });
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: AccountLoader<'info, SwapPool>,
+}
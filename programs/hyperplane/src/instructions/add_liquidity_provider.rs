@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{AllowedLiquidityProvider, Feature, FeatureSet, SwapPool},
+    utils::seeds,
+};
+
+pub fn handler(ctx: Context<AddLiquidityProvider>) -> Result<event::AddLiquidityProvider> {
+    ctx.accounts
+        .feature_set
+        .require_enabled(Feature::PermissionedDeposits)?;
+
+    let allowed_liquidity_provider = &mut ctx.accounts.allowed_liquidity_provider;
+    allowed_liquidity_provider.pool = ctx.accounts.pool.key();
+    allowed_liquidity_provider.provider = ctx.accounts.provider.key();
+
+    msg!(
+        "Added allowed liquidity provider: pool={}, provider={}",
+        allowed_liquidity_provider.pool,
+        allowed_liquidity_provider.provider,
+    );
+
+    emitted!(event::AddLiquidityProvider {
+        provider: allowed_liquidity_provider.provider,
+    });
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidityProvider<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: any signer can be allow-listed
+    pub provider: UncheckedAccount<'info>,
+
+    #[account(init,
+        seeds = [seeds::ALLOWED_LIQUIDITY_PROVIDER, pool.key().as_ref(), provider.key().as_ref()],
+        bump,
+        payer = admin,
+        space = AllowedLiquidityProvider::LEN,
+    )]
+    pub allowed_liquidity_provider: Box<Account<'info, AllowedLiquidityProvider>>,
+
+    #[account(
+        seeds = [seeds::FEATURE_SET],
+        bump,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{AllowedSwapTaker, Feature, FeatureSet, SwapPool},
+    utils::seeds,
+};
+
+pub fn handler(ctx: Context<AddSwapTaker>) -> Result<event::AddSwapTaker> {
+    ctx.accounts
+        .feature_set
+        .require_enabled(Feature::PermissionedSwaps)?;
+
+    let allowed_swap_taker = &mut ctx.accounts.allowed_swap_taker;
+    allowed_swap_taker.pool = ctx.accounts.pool.key();
+    allowed_swap_taker.taker = ctx.accounts.taker.key();
+
+    msg!(
+        "Added allowed swap taker: pool={}, taker={}",
+        allowed_swap_taker.pool,
+        allowed_swap_taker.taker,
+    );
+
+    emitted!(event::AddSwapTaker {
+        taker: allowed_swap_taker.taker,
+    });
+}
+
+#[derive(Accounts)]
+pub struct AddSwapTaker<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: any signer can be allow-listed
+    pub taker: UncheckedAccount<'info>,
+
+    #[account(init,
+        seeds = [seeds::ALLOWED_SWAP_TAKER, pool.key().as_ref(), taker.key().as_ref()],
+        bump,
+        payer = admin,
+        space = AllowedSwapTaker::LEN,
+    )]
+    pub allowed_swap_taker: Box<Account<'info, AllowedSwapTaker>>,
+
+    #[account(
+        seeds = [seeds::FEATURE_SET],
+        bump,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::{accounts::interface_account::InterfaceAccount, prelude::*};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    assert_pool_price::utils::validate_inputs, error::SwapError, require_msg, state::SwapPool,
+};
+
+/// Fail unless the pool price, token A in token B native units scaled by `PRICE_BOUNDS_SCALE`,
+/// is within `[min, max]`
+///
+/// Meant as the first instruction of a bundle, so that it does not land if the pool changed
+/// since it was simulated. The price is the ratio of the vault balances, which is the spot price
+/// of constant product pools and moves with every swap or unbalanced deposit of other curves.
+pub fn handler(ctx: Context<AssertPoolPrice>, min: u64, max: u64) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&pool, min, max)?;
+
+    let token_a_amount = ctx.accounts.token_a_vault.amount;
+    let token_b_amount = ctx.accounts.token_b_vault.amount;
+    require_msg!(
+        token_a_amount > 0,
+        SwapError::EmptyReserves,
+        "EmptyReserves: token_a_vault.amount=0"
+    );
+    let price = SwapPool::reserves_price(u128::from(token_a_amount), u128::from(token_b_amount))?;
+    require_msg!(
+        price >= u128::from(min) && price <= u128::from(max),
+        SwapError::PoolPriceOutOfRange,
+        &format!(
+            "PoolPriceOutOfRange: price={} not in [min={}, max={}]",
+            price, min, max
+        )
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AssertPoolPrice<'info> {
+    #[account(
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(pool: &Ref<SwapPool>, min: u64, max: u64) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        require_msg!(
+            min <= max,
+            SwapError::InvalidPriceBounds,
+            &format!("InvalidPriceBounds: min={} > max={}", min, max)
+        );
+        Ok(())
+    }
+}
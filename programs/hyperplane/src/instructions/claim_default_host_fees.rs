@@ -0,0 +1,137 @@
+use std::cmp;
+
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    claim_default_host_fees::utils::validate_inputs,
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{DefaultHostFees, PoolOperation, SwapPool, SwapState},
+    utils::{seeds, swap_token},
+};
+
+/// Pay the host fees accrued in the fees vault to the default host fees recipient
+///
+/// Anyone can crank the claim, the fees can only go to a token account of the recipient.
+pub fn handler(ctx: Context<ClaimDefaultHostFees>) -> Result<event::ClaimDefaultHostFees> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let a_or_b = validate_inputs(&ctx, &pool)?;
+
+    let accrued = pool.default_host_fees_mut(a_or_b);
+    let claim_amount = cmp::min(*accrued, ctx.accounts.fees_vault.amount);
+    require_msg!(
+        claim_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "No default host fees to claim"
+    );
+    *accrued -= claim_amount;
+
+    msg!(
+        "Claiming default host fees: claim_amount={}, a_or_b={:?}",
+        claim_amount,
+        a_or_b
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.fees_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.fees_vault.to_account_info(),
+        ctx.accounts.fees_mint.to_account_info(),
+        ctx.accounts.recipient_fees_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        claim_amount,
+        ctx.accounts.fees_mint.decimals,
+    )?;
+
+    emitted!(event::ClaimDefaultHostFees {
+        recipient: ctx.accounts.default_host_fees.recipient,
+        claim_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ClaimDefaultHostFees<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [seeds::DEFAULT_HOST_FEES, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub default_host_fees: Box<Account<'info, DefaultHostFees>>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        token::token_program = fees_token_program,
+    )]
+    pub fees_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Fee vault the host fees accrued in
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = fees_token_program,
+    )]
+    pub fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Recipient's token account to pay the host fees to
+    #[account(mut,
+        token::mint = fees_mint,
+        token::authority = default_host_fees.recipient,
+        token::token_program = fees_token_program,
+    )]
+    pub recipient_fees_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the fee token mint
+    pub fees_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::RefMut;
+
+    use super::*;
+    use crate::curve::calculator::AorB;
+
+    pub fn validate_inputs(
+        ctx: &Context<ClaimDefaultHostFees>,
+        pool: &RefMut<SwapPool>,
+    ) -> Result<AorB> {
+        pool.require_not_paused(PoolOperation::FeeWithdrawals)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        let (pool_fees_vault, a_or_b) = if ctx.accounts.fees_mint.key() == pool.token_a_mint {
+            (pool.token_a_fees_vault, AorB::A)
+        } else if ctx.accounts.fees_mint.key() == pool.token_b_mint {
+            (pool.token_b_fees_vault, AorB::B)
+        } else {
+            return err!(SwapError::IncorrectTradingMint);
+        };
+        require_msg!(
+            pool_fees_vault == ctx.accounts.fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: pool_fees_vault.key ({}) != fees_vault.key ({}), a_or_b={:?}",
+                pool_fees_vault,
+                ctx.accounts.fees_vault.key(),
+                a_or_b,
+            )
+        );
+        Ok(a_or_b)
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpAirdrop, LpAirdropPosition, SwapPool, SwapState},
+    try_math,
+    utils::{math::TryMath, swap_token},
+};
+
+/// Pay out the reward share of a registered position and return its pool tokens to the owner
+pub fn handler(ctx: Context<ClaimLpAirdrop>) -> Result<event::ClaimLpAirdrop> {
+    let pool = ctx.accounts.pool.load()?;
+    let current_slot = Clock::get()?.slot;
+    let snapshot_slot = ctx.accounts.airdrop.snapshot_slot;
+    require_msg!(
+        current_slot > snapshot_slot,
+        SwapError::AirdropNotClaimable,
+        &format!(
+            "AirdropNotClaimable: current_slot={} <= snapshot_slot={}",
+            current_slot, snapshot_slot
+        )
+    );
+
+    let pool_token_amount = ctx.accounts.position.pool_token_amount;
+    let reward_amount = ctx
+        .accounts
+        .airdrop
+        .reward_share(pool_token_amount)
+        .ok_or_else(|| error!(SwapError::CalculationFailure))?;
+    msg!(
+        "Claim LP airdrop outputs: reward_amount={}, pool_token_amount={}",
+        reward_amount,
+        pool_token_amount,
+    );
+
+    if reward_amount > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.reward_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.reward_user_ata.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            reward_amount,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+    }
+    swap_token::transfer_from_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        pool_token_amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    let airdrop = &mut ctx.accounts.airdrop;
+    airdrop.claimed_pool_tokens =
+        try_math!(airdrop.claimed_pool_tokens.try_add(pool_token_amount))?;
+
+    emitted!(event::ClaimLpAirdrop {
+        reward_amount,
+        pool_token_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ClaimLpAirdrop<'info> {
+    /// Owner of the position, receives the position rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the airdrop
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        has_one = pool,
+        has_one = reward_mint,
+        has_one = reward_vault,
+        has_one = pool_token_vault,
+    )]
+    pub airdrop: Box<Account<'info, LpAirdrop>>,
+
+    /// CHECK: has_one constraint on the airdrop
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the airdrop
+    #[account(mut)]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = airdrop,
+        has_one = owner,
+        close = owner,
+    )]
+    pub position: Box<Account<'info, LpAirdropPosition>>,
+
+    /// Owner's reward token account
+    #[account(mut,
+        token::mint = reward_mint,
+        token::authority = owner,
+        token::token_program = reward_token_program,
+    )]
+    pub reward_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's pool token account to return the registered pool tokens to
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
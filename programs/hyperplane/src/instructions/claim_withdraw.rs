@@ -0,0 +1,310 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    claim_withdraw::utils::validate_inputs,
+    curve,
+    curve::{
+        base::SwapCurve,
+        calculator::{AorB, RoundDirection},
+    },
+    error::SwapError,
+    event, pool_emitted, require_msg,
+    state::{SwapPool, SwapState, WithdrawalTicket},
+    try_math,
+    utils::{math, math::TryMath, pool_token, swap_token},
+    withdraw::utils::sub_withdraw_fee,
+};
+
+/// Pay out a queued withdrawal to the ticket owner
+///
+/// Anyone can claim once the ticket minimums are met, the owner can claim at any time. The
+/// escrowed pool tokens are burned and the ticket and its vault are closed to the owner.
+pub fn handler(ctx: Context<ClaimWithdraw>) -> Result<event::Withdraw> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&pool)?;
+
+    let ticket = &ctx.accounts.withdrawal_ticket;
+    // anything sent to the vault on top of the ticket is withdrawn with it, so it can be closed
+    let pool_token_amount = ctx.accounts.pool_token_vault.amount;
+    let (minimum_token_a_amount, minimum_token_b_amount) =
+        if ctx.accounts.signer.key() == ticket.owner {
+            (0, 0)
+        } else {
+            (ticket.minimum_token_a_amount, ticket.minimum_token_b_amount)
+        };
+    msg!(
+        "Claim withdraw inputs: minimum_token_a_amount={}, minimum_token_b_amount={}, pool_token_amount={}",
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+        pool_token_amount,
+    );
+
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+    let calculator = &swap_curve.calculator;
+
+    msg!(
+        "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+    );
+
+    let results = calculator
+        .pool_tokens_to_trading_tokens(
+            u128::from(pool_token_amount),
+            u128::from(ctx.accounts.pool_token_mint.supply),
+            u128::from(ctx.accounts.token_a_vault.amount),
+            u128::from(ctx.accounts.token_b_vault.amount),
+            RoundDirection::Floor,
+        )
+        .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+    let (token_a_after_fee, token_a_fees) = sub_withdraw_fee(
+        &pool,
+        ctx.accounts.token_a_vault.amount,
+        results.token_a_amount,
+        minimum_token_a_amount,
+        AorB::A,
+    )?;
+    let (token_b_after_fee, token_b_fees) = sub_withdraw_fee(
+        &pool,
+        ctx.accounts.token_b_vault.amount,
+        results.token_b_amount,
+        minimum_token_b_amount,
+        AorB::B,
+    )?;
+
+    msg!(
+        "Claim withdraw outputs: token_a_to_receive={}, token_b_to_receive={}, pool_tokens_to_burn={}",
+        token_a_after_fee,
+        token_b_after_fee,
+        pool_token_amount,
+    );
+
+    let withdrawal_ticket_bump = u8::try_from(ticket.bump).unwrap();
+    pool_token::burn_from_withdrawal_ticket(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ticket.to_account_info(),
+        &ticket.pool,
+        &ticket.owner,
+        withdrawal_ticket_bump,
+        pool_token_amount,
+    )?;
+    pool_token::close_withdrawal_ticket_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ticket.to_account_info(),
+        &ticket.pool,
+        &ticket.owner,
+        withdrawal_ticket_bump,
+    )?;
+
+    for (token_program, vault, mint, destination, amount) in [
+        (
+            &ctx.accounts.token_a_token_program,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.token_a_user_ata.to_account_info(),
+            token_a_after_fee,
+        ),
+        (
+            &ctx.accounts.token_b_token_program,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.token_b_user_ata.to_account_info(),
+            token_b_after_fee,
+        ),
+        (
+            &ctx.accounts.token_a_token_program,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.token_a_fees_vault.to_account_info(),
+            token_a_fees,
+        ),
+        (
+            &ctx.accounts.token_b_token_program,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.token_b_fees_vault.to_account_info(),
+            token_b_fees,
+        ),
+    ] {
+        if amount > 0 {
+            swap_token::transfer_from_vault(
+                token_program.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                vault.to_account_info(),
+                mint.to_account_info(),
+                destination,
+                ctx.accounts.pool_authority.to_account_info(),
+                pool.bump_seed(),
+                amount,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    let token_a_vault_amount_after =
+        try_math!(
+            try_math!(ctx.accounts.token_a_vault.amount.try_sub(token_a_after_fee))?
+                .try_sub(token_a_fees)
+        )?;
+    let token_b_vault_amount_after =
+        try_math!(
+            try_math!(ctx.accounts.token_b_vault.amount.try_sub(token_b_after_fee))?
+                .try_sub(token_b_fees)
+        )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_a_vault,
+        token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_b_vault,
+        token_b_vault_amount_after,
+    )?;
+    ctx.accounts.pool_token_mint.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::Withdraw {
+            token_a_amount: token_a_after_fee,
+            token_b_amount: token_b_after_fee,
+            pool_token_amount,
+            token_a_fees,
+            token_b_fees,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdraw<'info> {
+    /// Anyone can claim a ticket once the minimums are met
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: has_one constraint on the withdrawal ticket, receives the ticket rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    #[account(mut,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Account to collect fees into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Account to collect fees into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = pool,
+        has_one = owner,
+        has_one = pool_token_vault,
+        close = owner,
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+
+    /// CHECK: has_one constraint on the withdrawal ticket
+    #[account(mut)]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's token A token account
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::authority = owner,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's token B token account
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = owner,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's pool token account, for the share of the pool left to the owner
+    #[account(
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpAirdrop, SwapPool, SwapState},
+    utils::swap_token,
+};
+
+/// Return the unclaimed reward to the sponsor once every registered position has been claimed
+///
+/// What is left is the rounding remainder of the claims, or the whole reward if no pool tokens
+/// were registered.
+pub fn handler(ctx: Context<CloseLpAirdrop>) -> Result<event::CloseLpAirdrop> {
+    let pool = ctx.accounts.pool.load()?;
+    let airdrop = &ctx.accounts.airdrop;
+    let current_slot = Clock::get()?.slot;
+    require_msg!(
+        current_slot > airdrop.snapshot_slot
+            && airdrop.claimed_pool_tokens == airdrop.registered_pool_tokens,
+        SwapError::AirdropNotClaimable,
+        &format!(
+            "AirdropNotClaimable: current_slot={}, snapshot_slot={}, claimed_pool_tokens={}, registered_pool_tokens={}",
+            current_slot,
+            airdrop.snapshot_slot,
+            airdrop.claimed_pool_tokens,
+            airdrop.registered_pool_tokens
+        )
+    );
+
+    let reward_amount = ctx.accounts.reward_vault.amount;
+    msg!("Close LP airdrop outputs: reward_amount={}", reward_amount);
+
+    if reward_amount > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.reward_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.sponsor_reward_ata.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            reward_amount,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+    }
+
+    emitted!(event::CloseLpAirdrop { reward_amount });
+}
+
+#[derive(Accounts)]
+pub struct CloseLpAirdrop<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(has_one = pool_authority @ SwapError::InvalidProgramAddress)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the airdrop
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        has_one = pool,
+        has_one = sponsor,
+        has_one = reward_mint,
+        has_one = reward_vault,
+        close = sponsor,
+    )]
+    pub airdrop: Box<Account<'info, LpAirdrop>>,
+
+    /// CHECK: has_one constraint on the airdrop
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Sponsor's reward token account to return the unclaimed reward to
+    #[account(mut,
+        token::mint = reward_mint,
+        token::authority = sponsor,
+        token::token_program = reward_token_program,
+    )]
+    pub sponsor_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,266 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+    system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    close_pool::utils::{close_program_owned, drain_and_close_vault, validate_inputs},
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{SwapPool, SwapState},
+    utils::pool_token,
+};
+
+/// Decommission the pool, returning its remaining tokens and the rent of its accounts to the admin
+///
+/// The pool tokens held by the admin are burned first, the pool can only be closed if no other
+/// holder is left. The pool, swap curve, vaults and fees vaults are closed, the pool token mint
+/// cannot be closed and is left with a zero supply.
+pub fn handler(ctx: Context<ClosePool>) -> Result<event::ClosePool> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&pool)?;
+
+    let pool_tokens_burned = ctx.accounts.admin_pool_token_ata.amount;
+    if pool_tokens_burned > 0 {
+        pool_token::burn(
+            ctx.accounts.pool_token_mint.to_account_info(),
+            ctx.accounts.admin_pool_token_ata.to_account_info(),
+            ctx.accounts.admin.to_account_info(),
+            ctx.accounts.pool_token_program.to_account_info(),
+            pool_tokens_burned,
+        )?;
+        ctx.accounts.pool_token_mint.reload()?;
+    }
+    require_msg!(
+        ctx.accounts.pool_token_mint.supply == 0,
+        SwapError::PoolNotClosable,
+        &format!(
+            "PoolNotClosable: pool_token_mint.supply={} after burning the admin's {} pool tokens",
+            ctx.accounts.pool_token_mint.supply, pool_tokens_burned
+        )
+    );
+
+    let token_a_amount = drain_and_close_vault(
+        ctx.accounts,
+        &pool,
+        &ctx.accounts.token_a_vault,
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.admin_token_a_ata,
+        &ctx.accounts.token_a_token_program,
+    )?;
+    let token_a_fees = drain_and_close_vault(
+        ctx.accounts,
+        &pool,
+        &ctx.accounts.token_a_fees_vault,
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.admin_token_a_ata,
+        &ctx.accounts.token_a_token_program,
+    )?;
+    let token_b_amount = drain_and_close_vault(
+        ctx.accounts,
+        &pool,
+        &ctx.accounts.token_b_vault,
+        &ctx.accounts.token_b_mint,
+        &ctx.accounts.admin_token_b_ata,
+        &ctx.accounts.token_b_token_program,
+    )?;
+    let token_b_fees = drain_and_close_vault(
+        ctx.accounts,
+        &pool,
+        &ctx.accounts.token_b_fees_vault,
+        &ctx.accounts.token_b_mint,
+        &ctx.accounts.admin_token_b_ata,
+        &ctx.accounts.token_b_token_program,
+    )?;
+
+    close_program_owned(&ctx.accounts.swap_curve, &ctx.accounts.admin)?;
+
+    msg!(
+        "Close pool outputs: pool_tokens_burned={}, token_a_amount={}, token_b_amount={}, token_a_fees={}, token_b_fees={}",
+        pool_tokens_burned,
+        token_a_amount,
+        token_b_amount,
+        token_a_fees,
+        token_b_fees
+    );
+
+    // the pool itself is closed by its account constraint once the handler returns
+    emitted!(event::ClosePool {
+        pool_tokens_burned,
+        token_a_amount,
+        token_b_amount,
+        token_a_fees,
+        token_b_fees,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+        close = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Admin's token A account receiving the remaining reserve and fees
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::authority = admin,
+        token::token_program = token_a_token_program,
+    )]
+    pub admin_token_a_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's token B account receiving the remaining reserve and fees
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = admin,
+        token::token_program = token_b_token_program,
+    )]
+    pub admin_token_b_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's pool token account, its balance is burned
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+    use crate::{
+        try_math,
+        utils::{math::TryMath, swap_token},
+    };
+
+    pub fn validate_inputs(pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        require_msg!(
+            pool.default_host_fees_a == 0 && pool.default_host_fees_b == 0,
+            SwapError::PoolNotClosable,
+            &format!(
+                "PoolNotClosable: default host fees are still owed, default_host_fees_a={}, default_host_fees_b={}",
+                pool.default_host_fees_a, pool.default_host_fees_b
+            )
+        );
+        require_msg!(
+            pool.protocol_fees_a == 0 && pool.protocol_fees_b == 0,
+            SwapError::PoolNotClosable,
+            &format!(
+                "PoolNotClosable: protocol fees are still owed, protocol_fees_a={}, protocol_fees_b={}",
+                pool.protocol_fees_a, pool.protocol_fees_b
+            )
+        );
+        Ok(())
+    }
+
+    /// Transfer the whole balance of a pool vault to the admin and close it, returns the amount
+    /// transferred
+    pub fn drain_and_close_vault<'info>(
+        accounts: &ClosePool<'info>,
+        pool: &Ref<SwapPool>,
+        vault: &InterfaceAccount<'info, TokenAccount>,
+        mint: &InterfaceAccount<'info, Mint>,
+        admin_ata: &InterfaceAccount<'info, TokenAccount>,
+        token_program: &Interface<'info, TokenInterface>,
+    ) -> Result<u64> {
+        let amount = vault.amount;
+        if amount > 0 {
+            swap_token::transfer_from_vault(
+                token_program.to_account_info(),
+                accounts.pool.to_account_info(),
+                vault.to_account_info(),
+                mint.to_account_info(),
+                admin_ata.to_account_info(),
+                accounts.pool_authority.to_account_info(),
+                pool.bump_seed(),
+                amount,
+                mint.decimals,
+            )?;
+        }
+        swap_token::close_vault(
+            token_program.to_account_info(),
+            accounts.pool.to_account_info(),
+            vault.to_account_info(),
+            accounts.admin.to_account_info(),
+            accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+        )?;
+        Ok(amount)
+    }
+
+    /// Move all the lamports of an account owned by this program to `destination` and hand it
+    /// back to the system program
+    pub fn close_program_owned(account: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+        let lamports = account.lamports();
+        let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+        **destination_lamports = try_math!(destination_lamports.try_add(lamports))?;
+        **account.try_borrow_mut_lamports()? = 0;
+
+        account.assign(&system_program::ID);
+        account.realloc(0, false)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpAirdrop, SwapPool},
+    utils::{seeds, swap_token},
+};
+
+/// Fund a reward shared between the pool token holders which register before the snapshot slot
+pub fn handler(
+    ctx: Context<CreateLpAirdrop>,
+    reward_amount: u64,
+    snapshot_slot: u64,
+) -> Result<event::CreateLpAirdrop> {
+    msg!(
+        "Create LP airdrop inputs: reward_amount={}, snapshot_slot={}",
+        reward_amount,
+        snapshot_slot,
+    );
+    require_msg!(
+        reward_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: reward_amount=0"
+    );
+    let current_slot = Clock::get()?.slot;
+    require_msg!(
+        snapshot_slot > current_slot,
+        SwapError::InvalidAirdropSnapshotSlot,
+        &format!(
+            "InvalidAirdropSnapshotSlot: snapshot_slot={} <= current_slot={}",
+            snapshot_slot, current_slot
+        )
+    );
+
+    swap_token::transfer_from_user(
+        ctx.accounts.reward_token_program.to_account_info(),
+        ctx.accounts.sponsor_reward_ata.to_account_info(),
+        ctx.accounts.reward_mint.to_account_info(),
+        ctx.accounts.reward_vault.to_account_info(),
+        ctx.accounts.sponsor.to_account_info(),
+        reward_amount,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+    // the reward mint may charge a transfer fee, share what the vault actually received
+    ctx.accounts.reward_vault.reload()?;
+    let received_amount = ctx.accounts.reward_vault.amount;
+
+    let airdrop = &mut ctx.accounts.airdrop;
+    airdrop.pool = ctx.accounts.pool.key();
+    airdrop.sponsor = ctx.accounts.sponsor.key();
+    airdrop.reward_mint = ctx.accounts.reward_mint.key();
+    airdrop.reward_vault = ctx.accounts.reward_vault.key();
+    airdrop.pool_token_vault = ctx.accounts.pool_token_vault.key();
+    airdrop.reward_amount = received_amount;
+    airdrop.snapshot_slot = snapshot_slot;
+
+    emitted!(event::CreateLpAirdrop {
+        airdrop: ctx.accounts.airdrop.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        reward_amount: received_amount,
+        snapshot_slot,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(reward_amount: u64, snapshot_slot: u64)]
+pub struct CreateLpAirdrop<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = reward_token_program)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(init,
+        seeds = [
+            seeds::LP_AIRDROP,
+            pool.key().as_ref(),
+            sponsor.key().as_ref(),
+            reward_mint.key().as_ref(),
+            &snapshot_slot.to_le_bytes(),
+        ],
+        bump,
+        payer = sponsor,
+        space = LpAirdrop::LEN,
+    )]
+    pub airdrop: Box<Account<'info, LpAirdrop>>,
+
+    #[account(init,
+        seeds = [seeds::LP_AIRDROP_REWARD_VAULT, airdrop.key().as_ref()],
+        bump,
+        payer = sponsor,
+        token::mint = reward_mint,
+        token::authority = pool_authority,
+        token::token_program = reward_token_program,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Holds the registered pool tokens until they are claimed
+    #[account(init,
+        seeds = [seeds::LP_AIRDROP_POOL_TOKEN_VAULT, airdrop.key().as_ref()],
+        bump,
+        payer = sponsor,
+        token::mint = pool_token_mint,
+        token::authority = pool_authority,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Sponsor's reward token account to fund the airdrop from
+    #[account(mut,
+        token::mint = reward_mint,
+        token::authority = sponsor,
+        token::token_program = reward_token_program,
+    )]
+    pub sponsor_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
@@ -8,12 +8,11 @@ use crate::{
     curve,
     curve::{base::SwapCurve, calculator::RoundDirection},
     deposit::utils::validate_inputs,
-    emitted,
     error::SwapError,
-    event, require_msg,
-    state::{SwapPool, SwapState},
-    to_u64,
-    utils::{pool_token, swap_token},
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{AllowedLiquidityProvider, PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{instructions::require_before_deadline, math, math::TryMath, pool_token, swap_token},
 };
 
 pub fn handler(
@@ -21,10 +20,13 @@ pub fn handler(
     pool_token_amount: u64,
     maximum_token_a_amount: u64,
     maximum_token_b_amount: u64,
+    deadline: Option<i64>,
 ) -> Result<event::Deposit> {
+    require_before_deadline(deadline)?;
     let pool = ctx.accounts.pool.load()?;
     validate_inputs(&ctx, &pool)?;
-    msg!(
+    pool_msg!(
+        pool,
         "Deposit inputs: maximum_token_a_amount={}, maximum_token_b_amount={}, pool_token_amount={}",
         maximum_token_a_amount,
         maximum_token_b_amount,
@@ -38,7 +40,8 @@ pub fn handler(
         SwapError::UnsupportedCurveOperation
     );
 
-    msg!(
+    pool_msg!(
+        pool,
         "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
         swap_curve.curve_type,
         ctx.accounts.token_a_vault.amount,
@@ -67,14 +70,16 @@ pub fn handler(
     let token_b_amount = to_u64!(results.token_b_amount)?;
     let pool_token_amount = to_u64!(pool_token_amount)?;
 
-    msg!(
+    pool_msg!(
+        pool,
         "Deposit outputs: token_a_to_deposit={}, token_b_to_deposit={}, pool_tokens_to_mint={}",
         token_a_amount,
         token_b_amount,
         pool_token_amount,
     );
 
-    require_msg!(
+    require_pool_msg!(
+        pool,
         token_a_amount <= maximum_token_a_amount,
         SwapError::ExceededSlippage,
         &format!(
@@ -91,7 +96,8 @@ pub fn handler(
         )
     );
 
-    require_msg!(
+    require_pool_msg!(
+        pool,
         token_b_amount <= maximum_token_b_amount,
         SwapError::ExceededSlippage,
         &format!(
@@ -108,13 +114,17 @@ pub fn handler(
         )
     );
 
+    // The transfer fees of the trading token mints are paid on top of the deposit
     swap_token::transfer_from_user(
         ctx.accounts.token_a_token_program.to_account_info(),
         ctx.accounts.token_a_user_ata.to_account_info(),
         ctx.accounts.token_a_mint.to_account_info(),
         ctx.accounts.token_a_vault.to_account_info(),
         ctx.accounts.signer.to_account_info(),
-        token_a_amount,
+        swap_token::add_inverse_transfer_fee(
+            &ctx.accounts.token_a_mint.to_account_info(),
+            token_a_amount,
+        )?,
         ctx.accounts.token_a_mint.decimals,
     )?;
     swap_token::transfer_from_user(
@@ -123,9 +133,24 @@ pub fn handler(
         ctx.accounts.token_b_mint.to_account_info(),
         ctx.accounts.token_b_vault.to_account_info(),
         ctx.accounts.signer.to_account_info(),
-        token_b_amount,
+        swap_token::add_inverse_transfer_fee(
+            &ctx.accounts.token_b_mint.to_account_info(),
+            token_b_amount,
+        )?,
         ctx.accounts.token_b_mint.decimals,
     )?;
+    let token_a_vault_amount_after =
+        try_math!(ctx.accounts.token_a_vault.amount.try_add(token_a_amount))?;
+    let token_b_vault_amount_after =
+        try_math!(ctx.accounts.token_b_vault.amount.try_add(token_b_amount))?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_a_vault,
+        token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_b_vault,
+        token_b_vault_amount_after,
+    )?;
 
     pool_token::mint(
         ctx.accounts.pool_token_program.to_account_info(),
@@ -137,11 +162,31 @@ pub fn handler(
         pool_token_amount,
     )?;
 
-    emitted!(event::Deposit {
-        token_a_amount,
-        token_b_amount,
-        pool_token_amount,
-    });
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::Deposit {
+            token_a_amount,
+            token_b_amount,
+            pool_token_amount,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+            referrer: ctx
+                .accounts
+                .referrer
+                .as_ref()
+                .map(|referrer| referrer.key()),
+        }
+    );
 }
 
 #[derive(Accounts)]
@@ -213,6 +258,14 @@ pub struct Deposit<'info> {
     pub token_a_token_program: Interface<'info, TokenInterface>,
     /// Token program for the destination mint
     pub token_b_token_program: Interface<'info, TokenInterface>,
+
+    /// Referrer of the depositor, for LP acquisition campaigns
+    /// CHECK: only recorded in the deposit event
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// Allow-list entry of the signer - required if the pool only allows permissioned deposits
+    /// CHECK: checked in the handler
+    pub allowed_liquidity_provider: Option<Box<Account<'info, AllowedLiquidityProvider>>>,
 }
 
 mod utils {
@@ -226,6 +279,22 @@ mod utils {
             SwapError::WithdrawalsOnlyMode,
             "The pool is in withdrawals only mode"
         );
+        pool.require_not_paused(PoolOperation::Deposits)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        if pool.permissioned_deposits() {
+            AllowedLiquidityProvider::require_allowed(
+                ctx.accounts
+                    .allowed_liquidity_provider
+                    .as_deref()
+                    .map(|entry| &**entry),
+                &ctx.accounts.pool.key(),
+                &ctx.accounts.signer.key(),
+            )?;
+        }
         require_msg!(
             pool.token_a_vault != ctx.accounts.token_a_user_ata.key(),
             SwapError::IncorrectSwapAccount,
@@ -244,6 +313,10 @@ mod utils {
                 pool.token_b_vault.key()
             )
         );
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
         Ok(())
     }
 }
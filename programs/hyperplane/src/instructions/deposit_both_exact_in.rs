@@ -0,0 +1,472 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve,
+    curve::{
+        base::{DepositSingleResult, SwapCurve},
+        calculator::{RoundDirection, TradeDirection},
+        fees::Fees,
+    },
+    deposit_both_exact_in::utils::validate_inputs,
+    deposit_exact_amounts::utils::pool_tokens_for_amounts,
+    error::SwapError,
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{AllowedLiquidityProvider, PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math, math::TryMath, pool_token, swap_token},
+};
+
+/// Deposit exactly `token_a_amount` and `token_b_amount`, minting at least
+/// `minimum_pool_token_amount` pool tokens
+///
+/// The largest balanced part of the amounts is deposited like `deposit`, and the remainder of the
+/// token in excess is deposited like `deposit_single`, paying the trade and owner fees on the half
+/// implicitly swapped. Rounding dust of the other token, or a remainder too small to pay its fees
+/// and mint a pool token, stays in the user's account. The remainder is rejected like
+/// `deposit_single` while the reserves are more imbalanced than
+/// `max_single_sided_withdraw_imbalance_bps`.
+pub fn handler(
+    ctx: Context<DepositBothExactIn>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    minimum_pool_token_amount: u64,
+) -> Result<event::DepositBothExactIn> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&ctx, &pool)?;
+    pool_msg!(
+        pool,
+        "Deposit both exact in inputs: token_a_amount={}, token_b_amount={}, minimum_pool_token_amount={}",
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
+    );
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+
+    require!(
+        swap_curve.calculator.allows_deposits(),
+        SwapError::UnsupportedCurveOperation
+    );
+    require_msg!(
+        ctx.accounts.pool_token_mint.supply > 0,
+        SwapError::EmptySupply,
+        "EmptySupply: pool_token_supply=0, the pool must be funded with deposit"
+    );
+
+    pool_msg!(
+        pool,
+        "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+    );
+
+    let token_a_vault_amount = u128::from(ctx.accounts.token_a_vault.amount);
+    let token_b_vault_amount = u128::from(ctx.accounts.token_b_vault.amount);
+    let pool_token_supply = u128::from(ctx.accounts.pool_token_mint.supply);
+
+    // balanced part, proportional to the vaults
+    let balanced_pool_token_amount = u128::from(pool_tokens_for_amounts(
+        &swap_curve,
+        token_a_amount,
+        token_b_amount,
+        ctx.accounts.pool_token_mint.supply,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+    )?);
+    let balanced = swap_curve.calculator.pool_tokens_to_trading_tokens(
+        balanced_pool_token_amount,
+        pool_token_supply,
+        token_a_vault_amount,
+        token_b_vault_amount,
+        RoundDirection::Ceiling,
+    )?;
+    let token_a_remainder = try_math!(u128::from(token_a_amount).try_sub(balanced.token_a_amount))?;
+    let token_b_remainder = try_math!(u128::from(token_b_amount).try_sub(balanced.token_b_amount))?;
+
+    // imbalanced part, the remainder worth the most relative to its vault
+    let token_a_remainder_value = try_math!(token_a_remainder.try_mul(token_b_vault_amount))?;
+    let token_b_remainder_value = try_math!(token_b_remainder.try_mul(token_a_vault_amount))?;
+    let (trade_direction, remainder) = if token_a_remainder_value >= token_b_remainder_value {
+        (TradeDirection::AtoB, token_a_remainder)
+    } else {
+        (TradeDirection::BtoA, token_b_remainder)
+    };
+    let rebalance = if utils::covers_fees(remainder, pool.fees())? {
+        require!(
+            swap_curve.calculator.allows_single_sided_deposits(),
+            SwapError::UnsupportedCurveOperation
+        );
+        pool.require_not_paused(PoolOperation::SingleSided)?;
+        pool.require_single_sided_balanced(
+            swap_curve.calculator.as_ref(),
+            token_a_vault_amount,
+            token_b_vault_amount,
+        )?;
+        let result = swap_curve.deposit_single_token_type(
+            remainder,
+            try_math!(token_a_vault_amount.try_add(balanced.token_a_amount))?,
+            try_math!(token_b_vault_amount.try_add(balanced.token_b_amount))?,
+            try_math!(pool_token_supply.try_add(balanced_pool_token_amount))?,
+            trade_direction,
+            pool.fees(),
+        )?;
+        Some(result).filter(|result| result.pool_token_amount > 0)
+    } else {
+        None
+    };
+
+    let (source_amount_to_vault, trade_fee, owner_fee, rebalanced_pool_token_amount) =
+        match &rebalance {
+            Some(DepositSingleResult {
+                source_amount_to_vault,
+                trade_fee,
+                owner_fee,
+                pool_token_amount,
+                ..
+            }) => (
+                to_u64!(*source_amount_to_vault)?,
+                to_u64!(*trade_fee)?,
+                to_u64!(*owner_fee)?,
+                *pool_token_amount,
+            ),
+            None => (0, 0, 0, 0),
+        };
+    let pool_token_amount = to_u64!(try_math!(
+        balanced_pool_token_amount.try_add(rebalanced_pool_token_amount)
+    )?)?;
+    let ((token_a_to_vault, token_a_owner_fee), (token_b_to_vault, token_b_owner_fee)) = {
+        let balanced_a = to_u64!(balanced.token_a_amount)?;
+        let balanced_b = to_u64!(balanced.token_b_amount)?;
+        match trade_direction {
+            TradeDirection::AtoB => (
+                (
+                    try_math!(balanced_a.try_add(source_amount_to_vault))?,
+                    owner_fee,
+                ),
+                (balanced_b, 0),
+            ),
+            TradeDirection::BtoA => (
+                (balanced_a, 0),
+                (
+                    try_math!(balanced_b.try_add(source_amount_to_vault))?,
+                    owner_fee,
+                ),
+            ),
+        }
+    };
+    let rebalanced_mint = rebalance.as_ref().map(|_| match trade_direction {
+        TradeDirection::AtoB => ctx.accounts.token_a_mint.key(),
+        TradeDirection::BtoA => ctx.accounts.token_b_mint.key(),
+    });
+
+    pool_msg!(
+        pool,
+        "Deposit both exact in outputs: token_a_to_deposit={}, token_b_to_deposit={}, trade_fee={}, owner_fee={}, pool_tokens_to_mint={}",
+        token_a_to_vault,
+        token_b_to_vault,
+        trade_fee,
+        owner_fee,
+        pool_token_amount,
+    );
+
+    require_msg!(
+        pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        &format!(
+            "Deposit is worth less than 1 pool token: token_a_amount={}, token_b_amount={}",
+            token_a_amount, token_b_amount
+        )
+    );
+    require_pool_msg!(
+        pool,
+        pool_token_amount >= minimum_pool_token_amount,
+        SwapError::ExceededSlippage,
+        &format!(
+            "ExceededSlippage: pool_token_amount={} < minimum_pool_token_amount={}",
+            pool_token_amount, minimum_pool_token_amount
+        )
+    );
+
+    // The transfer fees of the trading token mints are paid on top of the deposit
+    if token_a_to_vault > 0 {
+        swap_token::transfer_from_user(
+            ctx.accounts.token_a_token_program.to_account_info(),
+            ctx.accounts.token_a_user_ata.to_account_info(),
+            ctx.accounts.token_a_mint.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(
+                &ctx.accounts.token_a_mint.to_account_info(),
+                token_a_to_vault,
+            )?,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if token_b_to_vault > 0 {
+        swap_token::transfer_from_user(
+            ctx.accounts.token_b_token_program.to_account_info(),
+            ctx.accounts.token_b_user_ata.to_account_info(),
+            ctx.accounts.token_b_mint.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(
+                &ctx.accounts.token_b_mint.to_account_info(),
+                token_b_to_vault,
+            )?,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+    let mut owner_fees_burned = 0;
+    if owner_fee > 0 {
+        let (token_program, user_ata, mint, vault, fees_vault) = match trade_direction {
+            TradeDirection::AtoB => (
+                &ctx.accounts.token_a_token_program,
+                &ctx.accounts.token_a_user_ata,
+                &ctx.accounts.token_a_mint,
+                &ctx.accounts.token_a_vault,
+                &ctx.accounts.token_a_fees_vault,
+            ),
+            TradeDirection::BtoA => (
+                &ctx.accounts.token_b_token_program,
+                &ctx.accounts.token_b_user_ata,
+                &ctx.accounts.token_b_mint,
+                &ctx.accounts.token_b_vault,
+                &ctx.accounts.token_b_fees_vault,
+            ),
+        };
+        let owner_fee_destination = if pool.burn_owner_fees() {
+            owner_fees_burned = owner_fee;
+            vault.to_account_info()
+        } else {
+            fees_vault.to_account_info()
+        };
+        swap_token::transfer_from_user(
+            token_program.to_account_info(),
+            user_ata.to_account_info(),
+            mint.to_account_info(),
+            owner_fee_destination,
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(&mint.to_account_info(), owner_fee)?,
+            mint.decimals,
+        )?;
+    }
+
+    let (token_a_burned, token_b_burned) = match trade_direction {
+        TradeDirection::AtoB => (owner_fees_burned, 0),
+        TradeDirection::BtoA => (0, owner_fees_burned),
+    };
+    let token_a_vault_amount_after = try_math!(ctx
+        .accounts
+        .token_a_vault
+        .amount
+        .try_add(token_a_to_vault)?
+        .try_add(token_a_burned))?;
+    let token_b_vault_amount_after = try_math!(ctx
+        .accounts
+        .token_b_vault
+        .amount
+        .try_add(token_b_to_vault)?
+        .try_add(token_b_burned))?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_a_vault,
+        token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_b_vault,
+        token_b_vault_amount_after,
+    )?;
+
+    pool_token::mint(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        pool_token_amount,
+    )?;
+
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::DepositBothExactIn {
+            token_a_amount: try_math!(token_a_to_vault.try_add(token_a_owner_fee))?,
+            token_b_amount: try_math!(token_b_to_vault.try_add(token_b_owner_fee))?,
+            pool_token_amount,
+            rebalanced_mint,
+            trade_fee,
+            owner_fee,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct DepositBothExactIn<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Account to collect the owner fee of a token A remainder into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Account to collect the owner fee of a token B remainder into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token A token account
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token B token account
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = token_a_user_ata.owner,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's pool token account
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = token_b_user_ata.owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+
+    /// Allow-list entry of the signer - required if the pool only allows permissioned deposits
+    /// CHECK: checked in the handler
+    pub allowed_liquidity_provider: Option<Box<Account<'info, AllowedLiquidityProvider>>>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(ctx: &Context<DepositBothExactIn>, pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        pool.require_not_paused(PoolOperation::Deposits)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        if pool.permissioned_deposits() {
+            AllowedLiquidityProvider::require_allowed(
+                ctx.accounts
+                    .allowed_liquidity_provider
+                    .as_deref()
+                    .map(|entry| &**entry),
+                &ctx.accounts.pool.key(),
+                &ctx.accounts.signer.key(),
+            )?;
+        }
+        require_msg!(
+            pool.token_a_vault != ctx.accounts.token_a_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_a_user_ata.key ({}) == token_a_vault.key ({})",
+                ctx.accounts.token_a_user_ata.key(),
+                pool.token_a_vault.key()
+            )
+        );
+        require_msg!(
+            pool.token_b_vault != ctx.accounts.token_b_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_b_user_ata.key ({}) == token_b_vault.key ({})",
+                ctx.accounts.token_b_user_ata.key(),
+                pool.token_b_vault.key()
+            )
+        );
+        swap_token::require_no_vault_delegates(&ctx.accounts.token_a_vault)?;
+        swap_token::require_no_vault_delegates(&ctx.accounts.token_b_vault)?;
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
+        Ok(())
+    }
+
+    /// Whether a single token deposit of `remainder` is larger than the fees charged on it, see
+    /// [SwapCurve::deposit_single_token_type]
+    pub fn covers_fees(remainder: u128, fees: &Fees) -> Result<bool> {
+        if remainder == 0 {
+            return Ok(false);
+        }
+        let half_remainder = std::cmp::max(1, try_math!(remainder.try_div(2))?);
+        let total_fees = try_math!(fees
+            .trading_fee(half_remainder)?
+            .try_add(fees.owner_trading_fee(half_remainder)?))?;
+        Ok(remainder > total_fees)
+    }
+}
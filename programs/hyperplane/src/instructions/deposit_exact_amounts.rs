@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    curve, curve::base::SwapCurve, error::SwapError, event, instructions, instructions::Deposit,
+    require_msg, state::SwapState, to_u64,
+};
+
+/// Deposit at most `token_a_amount` and `token_b_amount`, minting as many pool tokens as the
+/// scarcer side allows
+///
+/// The pool tokens are derived from the fixed amounts and the deposit is then processed exactly
+/// like `deposit`, with the fixed amounts as maximums, so the unused amount of the other token
+/// stays in the user's account.
+pub fn handler(
+    ctx: Context<Deposit>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    minimum_pool_token_amount: u64,
+) -> Result<event::Deposit> {
+    msg!(
+        "Deposit exact amounts inputs: token_a_amount={}, token_b_amount={}, minimum_pool_token_amount={}",
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
+    );
+    let pool_token_amount = {
+        let pool = ctx.accounts.pool.load()?;
+        let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+        utils::pool_tokens_for_amounts(
+            &swap_curve,
+            token_a_amount,
+            token_b_amount,
+            ctx.accounts.pool_token_mint.supply,
+            ctx.accounts.token_a_vault.amount,
+            ctx.accounts.token_b_vault.amount,
+        )?
+    };
+
+    require_msg!(
+        pool_token_amount >= minimum_pool_token_amount,
+        SwapError::ExceededSlippage,
+        &format!(
+            "ExceededSlippage: pool_token_amount={} < minimum_pool_token_amount={}",
+            pool_token_amount, minimum_pool_token_amount
+        )
+    );
+
+    instructions::deposit::handler(ctx, pool_token_amount, token_a_amount, token_b_amount, None)
+}
+
+pub(crate) mod utils {
+    use super::*;
+    use crate::{curve::calculator::RoundDirection, try_math, utils::math::TryMath};
+
+    /// Largest amount of pool tokens worth at most the given trading token amounts
+    pub fn pool_tokens_for_amounts(
+        swap_curve: &SwapCurve,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        pool_token_supply: u64,
+        token_a_vault_amount: u64,
+        token_b_vault_amount: u64,
+    ) -> Result<u64> {
+        if pool_token_supply == 0 {
+            // the deposit resets the pool supply
+            return to_u64!(swap_curve.calculator.new_pool_supply());
+        }
+        let supply = u128::from(pool_token_supply);
+
+        // trading tokens backing the whole supply, the pool tokens are proportional to them
+        let pool_value = swap_curve.calculator.pool_tokens_to_trading_tokens(
+            supply,
+            supply,
+            u128::from(token_a_vault_amount),
+            u128::from(token_b_vault_amount),
+            RoundDirection::Floor,
+        )?;
+        require_msg!(
+            pool_value.token_a_amount > 0 && pool_value.token_b_amount > 0,
+            SwapError::ZeroTradingTokens,
+            &format!(
+                "ZeroTradingTokens: pool value token_a_amount={}, token_b_amount={}",
+                pool_value.token_a_amount, pool_value.token_b_amount
+            )
+        );
+        let from_a = try_math!(u128::from(token_a_amount)
+            .try_mul(supply)?
+            .try_div(pool_value.token_a_amount))?;
+        let from_b = try_math!(u128::from(token_b_amount)
+            .try_mul(supply)?
+            .try_div(pool_value.token_b_amount))?;
+        let mut pool_tokens = from_a.min(from_b);
+
+        // the deposit rounds the trading tokens up, make sure they still fit the fixed amounts
+        let required = swap_curve.calculator.pool_tokens_to_trading_tokens(
+            pool_tokens,
+            supply,
+            u128::from(token_a_vault_amount),
+            u128::from(token_b_vault_amount),
+            RoundDirection::Ceiling,
+        )?;
+        if pool_tokens > 0
+            && (required.token_a_amount > u128::from(token_a_amount)
+                || required.token_b_amount > u128::from(token_b_amount))
+        {
+            pool_tokens -= 1;
+        }
+        to_u64!(pool_tokens)
+    }
+}
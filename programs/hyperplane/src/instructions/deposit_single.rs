@@ -0,0 +1,341 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve,
+    curve::{base::SwapCurve, calculator::TradeDirection},
+    deposit_single::utils::validate_inputs,
+    error::SwapError,
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{AllowedLiquidityProvider, PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math, math::TryMath, pool_token, swap_token},
+};
+
+/// Deposit `source_token_amount` of only one of the trading tokens, minting pool tokens worth
+/// the growth of the pool value
+///
+/// Half of the deposit is implicitly swapped for the other token, so the trading fee is left in
+/// the pool and the owner fee is sent to the fees vault, on that half only.
+///
+/// Rejected while the reserves are more imbalanced than `max_single_sided_withdraw_imbalance_bps`,
+/// so pool tokens are not minted against the depegged token at the curve price.
+pub fn handler(
+    ctx: Context<DepositSingle>,
+    source_token_amount: u64,
+    minimum_pool_token_amount: u64,
+) -> Result<event::DepositSingle> {
+    let pool = ctx.accounts.pool.load()?;
+    let trade_direction = validate_inputs(&ctx, &pool)?;
+    pool_msg!(
+        pool,
+        "Deposit single inputs: trade_direction={:?}, source_token_amount={}, minimum_pool_token_amount={}",
+        trade_direction,
+        source_token_amount,
+        minimum_pool_token_amount,
+    );
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+
+    require!(
+        swap_curve.calculator.allows_single_sided_deposits(),
+        SwapError::UnsupportedCurveOperation
+    );
+    pool.require_single_sided_balanced(
+        swap_curve.calculator.as_ref(),
+        u128::from(ctx.accounts.token_a_vault.amount),
+        u128::from(ctx.accounts.token_b_vault.amount),
+    )?;
+    require_msg!(
+        ctx.accounts.pool_token_mint.supply > 0,
+        SwapError::EmptySupply,
+        "EmptySupply: pool_token_supply=0, the pool must be funded with both tokens"
+    );
+
+    pool_msg!(
+        pool,
+        "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+    );
+
+    let result = swap_curve.deposit_single_token_type(
+        u128::from(source_token_amount),
+        u128::from(ctx.accounts.token_a_vault.amount),
+        u128::from(ctx.accounts.token_b_vault.amount),
+        u128::from(ctx.accounts.pool_token_mint.supply),
+        trade_direction,
+        pool.fees(),
+    )?;
+
+    let pool_token_amount = to_u64!(result.pool_token_amount)?;
+    let source_amount_to_vault = to_u64!(result.source_amount_to_vault)?;
+    let trade_fee = to_u64!(result.trade_fee)?;
+    let owner_fee = to_u64!(result.owner_fee)?;
+
+    pool_msg!(
+        pool,
+        "Deposit single outputs: source_amount_to_vault={}, trade_fee={}, owner_fee={}, pool_tokens_to_mint={}",
+        source_amount_to_vault,
+        trade_fee,
+        owner_fee,
+        pool_token_amount,
+    );
+
+    require_msg!(
+        pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        &format!(
+            "Deposit is worth less than 1 pool token: source_token_amount={}",
+            source_token_amount
+        )
+    );
+    require_pool_msg!(
+        pool,
+        pool_token_amount >= minimum_pool_token_amount,
+        SwapError::ExceededSlippage,
+        &format!(
+            "ExceededSlippage: pool_token_amount={} < minimum_pool_token_amount={}",
+            pool_token_amount, minimum_pool_token_amount
+        )
+    );
+
+    let (source_vault, source_vault_amount) = match trade_direction {
+        TradeDirection::AtoB => (
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.token_a_vault.amount,
+        ),
+        TradeDirection::BtoA => (
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.token_b_vault.amount,
+        ),
+    };
+    // The transfer fees of the source mint are paid on top of the deposit
+    swap_token::transfer_from_user(
+        ctx.accounts.source_token_program.to_account_info(),
+        ctx.accounts.source_user_ata.to_account_info(),
+        ctx.accounts.source_mint.to_account_info(),
+        source_vault.clone(),
+        ctx.accounts.signer.to_account_info(),
+        swap_token::add_inverse_transfer_fee(
+            &ctx.accounts.source_mint.to_account_info(),
+            source_amount_to_vault,
+        )?,
+        ctx.accounts.source_mint.decimals,
+    )?;
+    let mut owner_fees_burned = 0;
+    if owner_fee > 0 {
+        let owner_fee_destination = if pool.burn_owner_fees() {
+            owner_fees_burned = owner_fee;
+            source_vault
+        } else {
+            ctx.accounts.source_token_fees_vault.to_account_info()
+        };
+        swap_token::transfer_from_user(
+            ctx.accounts.source_token_program.to_account_info(),
+            ctx.accounts.source_user_ata.to_account_info(),
+            ctx.accounts.source_mint.to_account_info(),
+            owner_fee_destination,
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(
+                &ctx.accounts.source_mint.to_account_info(),
+                owner_fee,
+            )?,
+            ctx.accounts.source_mint.decimals,
+        )?;
+    }
+
+    let source_vault_amount_after = try_math!(try_math!(
+        source_vault_amount.try_add(source_amount_to_vault)
+    )?
+    .try_add(owner_fees_burned))?;
+    match trade_direction {
+        TradeDirection::AtoB => swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_a_vault,
+            source_vault_amount_after,
+        )?,
+        TradeDirection::BtoA => swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_b_vault,
+            source_vault_amount_after,
+        )?,
+    }
+
+    pool_token::mint(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        pool_token_amount,
+    )?;
+
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::DepositSingle {
+            source_mint: ctx.accounts.source_mint.key(),
+            source_token_amount,
+            pool_token_amount,
+            trade_fee,
+            owner_fee,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct DepositSingle<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// Mint of the deposited token, either of the trading tokens
+    /// CHECK: checked in the handler
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Account to collect the owner fee into
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_token_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's source token account
+    #[account(mut,
+        token::mint = source_mint,
+        token::token_program = source_token_program,
+    )]
+    pub source_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's pool token account
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = source_user_ata.owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the source mint
+    pub source_token_program: Interface<'info, TokenInterface>,
+
+    /// Allow-list entry of the signer - required if the pool only allows permissioned deposits
+    /// CHECK: checked in the handler
+    pub allowed_liquidity_provider: Option<Box<Account<'info, AllowedLiquidityProvider>>>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(
+        ctx: &Context<DepositSingle>,
+        pool: &Ref<SwapPool>,
+    ) -> Result<TradeDirection> {
+        require_msg!(
+            !pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        pool.require_not_paused(PoolOperation::Deposits)?;
+        pool.require_not_paused(PoolOperation::SingleSided)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        if pool.permissioned_deposits() {
+            AllowedLiquidityProvider::require_allowed(
+                ctx.accounts
+                    .allowed_liquidity_provider
+                    .as_deref()
+                    .map(|entry| &**entry),
+                &ctx.accounts.pool.key(),
+                &ctx.accounts.signer.key(),
+            )?;
+        }
+        let source_mint = ctx.accounts.source_mint.key();
+        let (trade_direction, source_vault, source_token_fees_vault) =
+            if source_mint == pool.token_a_mint {
+                (
+                    TradeDirection::AtoB,
+                    pool.token_a_vault,
+                    pool.token_a_fees_vault,
+                )
+            } else if source_mint == pool.token_b_mint {
+                (
+                    TradeDirection::BtoA,
+                    pool.token_b_vault,
+                    pool.token_b_fees_vault,
+                )
+            } else {
+                return err!(SwapError::IncorrectTradingMint);
+            };
+        require_msg!(
+            ctx.accounts.source_token_fees_vault.key() == source_token_fees_vault,
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: source_token_fees_vault.key ({}) != {}",
+                ctx.accounts.source_token_fees_vault.key(),
+                source_token_fees_vault
+            )
+        );
+        require_msg!(
+            ctx.accounts.source_user_ata.key() != source_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: source_user_ata.key ({}) == source_vault.key ({})",
+                ctx.accounts.source_user_ata.key(),
+                source_vault
+            )
+        );
+        swap_token::require_no_vault_delegates(&ctx.accounts.token_a_vault)?;
+        swap_token::require_no_vault_delegates(&ctx.accounts.token_b_vault)?;
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
+        Ok(trade_direction)
+    }
+}
@@ -0,0 +1,172 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    donate_to_pool::utils::validate_inputs,
+    error::SwapError,
+    event, pool_emitted, require_msg,
+    state::{SwapPool, SwapState},
+    try_math,
+    utils::{math::TryMath, swap_token},
+};
+
+/// Transfer tokens into the pool vaults without minting pool tokens, raising the value of every
+/// pool token
+///
+/// The vault balances are the pool reserves, so tokens transferred directly into the vaults are
+/// priced by the next swap just the same. Donating through the program records who donated and
+/// how much in a `Donation` event.
+pub fn handler(
+    ctx: Context<DonateToPool>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<event::Donation> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&ctx, &pool)?;
+    require_msg!(
+        token_a_amount > 0 || token_b_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "Donation of 0 token a and 0 token b"
+    );
+    msg!(
+        "Donation: token_a_amount={}, token_b_amount={}",
+        token_a_amount,
+        token_b_amount
+    );
+
+    // The transfer fees of the trading token mints are paid on top of the donation
+    if token_a_amount > 0 {
+        let vault_amount_after =
+            try_math!(ctx.accounts.token_a_vault.amount.try_add(token_a_amount))?;
+        swap_token::transfer_from_user(
+            ctx.accounts.token_a_token_program.to_account_info(),
+            ctx.accounts.token_a_user_ata.to_account_info(),
+            ctx.accounts.token_a_mint.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(
+                &ctx.accounts.token_a_mint.to_account_info(),
+                token_a_amount,
+            )?,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+        swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_a_vault,
+            vault_amount_after,
+        )?;
+    }
+    if token_b_amount > 0 {
+        let vault_amount_after =
+            try_math!(ctx.accounts.token_b_vault.amount.try_add(token_b_amount))?;
+        swap_token::transfer_from_user(
+            ctx.accounts.token_b_token_program.to_account_info(),
+            ctx.accounts.token_b_user_ata.to_account_info(),
+            ctx.accounts.token_b_mint.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            swap_token::add_inverse_transfer_fee(
+                &ctx.accounts.token_b_mint.to_account_info(),
+                token_b_amount,
+            )?,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+        swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_b_vault,
+            vault_amount_after,
+        )?;
+    }
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::Donation {
+            donor: ctx.accounts.signer.key(),
+            token_a_amount,
+            token_b_amount,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct DonateToPool<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token A token account
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token B token account
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(ctx: &Context<DonateToPool>, pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        require_msg!(
+            pool.token_a_vault != ctx.accounts.token_a_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_a_user_ata.key ({}) == token_a_vault.key ({})",
+                ctx.accounts.token_a_user_ata.key(),
+                pool.token_a_vault.key()
+            )
+        );
+        require_msg!(
+            pool.token_b_vault != ctx.accounts.token_b_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_b_user_ata.key ({}) == token_b_vault.key ({})",
+                ctx.accounts.token_b_user_ata.key(),
+                pool.token_b_vault.key()
+            )
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::seeds;
+
+/// Carry the event of a pool in `EventMode::Cpi` as instruction data, which indexers read from
+/// the inner instructions of the transaction
+///
+/// Only the program itself can sign for the event authority, so events cannot be forged.
+pub fn handler(_ctx: Context<EmitEvent>, _event: Vec<u8>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmitEvent<'info> {
+    #[account(seeds = [seeds::EVENT_AUTHORITY], bump)]
+    pub event_authority: Signer<'info>,
+}
@@ -0,0 +1,92 @@
+use anchor_lang::{accounts::interface_account::InterfaceAccount, prelude::*};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{FeatureSet, ProposedConfigChange, SwapPool},
+    update_pool_config::utils::{
+        parse_config, record_config_change, require_feature_enabled, set_pool_config,
+    },
+    utils::seeds,
+};
+
+/// Apply a proposed pool config change after its approval window, closing the proposal
+pub fn handler(ctx: Context<ExecutePoolConfig>) -> Result<event::UpdatePoolConfig> {
+    let proposed_config_change = &ctx.accounts.proposed_config_change;
+    let slot = Clock::get()?.slot;
+    require_msg!(
+        slot >= proposed_config_change.executable_slot,
+        SwapError::ProposalNotExecutable,
+        &format!(
+            "ProposalNotExecutable: slot={} < executable_slot={}",
+            slot, proposed_config_change.executable_slot
+        )
+    );
+
+    let (mode, value) = parse_config(proposed_config_change.mode, &proposed_config_change.value)?;
+    require_feature_enabled(ctx.accounts.feature_set.as_deref(), mode, &value)?;
+    let pool = &mut ctx.accounts.pool.load_mut()?;
+    let old_value = set_pool_config(pool, mode, &value, ctx.remaining_accounts)?;
+    record_config_change(
+        &ctx.accounts.config_history,
+        mode,
+        old_value,
+        &value,
+        ctx.accounts.admin.key(),
+    )?;
+
+    emitted!(event::UpdatePoolConfig {
+        mode,
+        value,
+        token_a_reserve: ctx.accounts.token_a_vault.amount,
+        token_b_reserve: ctx.accounts.token_b_vault.amount,
+        pool_token_supply: ctx.accounts.pool_token_mint.supply,
+        slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ExecutePoolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        has_one = pool,
+        close = admin,
+    )]
+    pub proposed_config_change: Box<Account<'info, ProposedConfigChange>>,
+
+    /// CHECK: Config history of the pool, changes are recorded once it is initialized
+    #[account(mut,
+        seeds = [seeds::CONFIG_HISTORY, pool.key().as_ref()],
+        bump,
+    )]
+    pub config_history: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Global feature flags - required to turn on a config gated by a feature, e.g.
+    /// `PermissionedSwaps`
+    #[account(
+        seeds = [seeds::FEATURE_SET],
+        bump,
+    )]
+    pub feature_set: Option<Box<Account<'info, FeatureSet>>>,
+}
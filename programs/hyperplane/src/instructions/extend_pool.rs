@@ -0,0 +1,94 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::{
+    curve,
+    curve::base::SwapCurve,
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{SwapPool, SwapState},
+};
+
+/// Grow a pool account created before `SwapPool::LEN` was increased to the current size
+///
+/// Anyone can pay for the extension. The new fields are zeroed, which is their default value, and
+/// pools which are already at the current size are not reallocated. The curve capabilities are
+/// the exception: they are copied from the curve calculator, as on initialization, since the
+/// pools created before they were added read them as zero.
+pub fn handler(ctx: Context<ExtendPool>) -> Result<event::ExtendPool> {
+    let pool = ctx.accounts.pool.to_account_info();
+    {
+        let data = pool.try_borrow_data()?;
+        require!(
+            data.len() >= SwapPool::LEGACY_LEN,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        require!(
+            data[..8] == SwapPool::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    let old_len = pool.data_len();
+    if old_len < SwapPool::LEN {
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(SwapPool::LEN);
+        let lamports = rent_exempt_lamports.saturating_sub(pool.lamports());
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: pool.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        pool.realloc(SwapPool::LEN, true)?;
+    }
+
+    let pool_loader = AccountLoader::<SwapPool>::try_from(&pool)?;
+    let mut pool = pool_loader.load_mut()?;
+    require_msg!(
+        ctx.accounts.swap_curve.key() == pool.swap_curve,
+        SwapError::IncorrectSwapAccount,
+        &format!(
+            "IncorrectSwapAccount: swap_curve.key ({}) != {}",
+            ctx.accounts.swap_curve.key(),
+            pool.swap_curve
+        )
+    );
+    let swap_curve: SwapCurve = curve!(ctx.accounts.swap_curve, pool);
+    pool.allows_deposits = u64::from(swap_curve.calculator.allows_deposits());
+    pool.allows_single_sided_deposits =
+        u64::from(swap_curve.calculator.allows_single_sided_deposits());
+
+    msg!(
+        "Extended pool: old_len={}, new_len={}, allows_deposits={}, allows_single_sided_deposits={}",
+        old_len,
+        SwapPool::LEN,
+        pool.allows_deposits(),
+        pool.allows_single_sided_deposits()
+    );
+
+    emitted!(event::ExtendPool {
+        old_len: old_len as u64,
+        new_len: SwapPool::LEN as u64,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ExtendPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner and discriminator checked, the pool cannot be loaded before it is extended
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the pool once extended, deserialized as the curve type of the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
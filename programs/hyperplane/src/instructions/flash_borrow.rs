@@ -0,0 +1,192 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+    solana_program::sysvar,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve::calculator::AorB,
+    error::SwapError,
+    flash_borrow::utils::{require_flash_repay, validate_inputs},
+    require_msg,
+    state::{SwapPool, SwapState},
+    try_math,
+    utils::{math::TryMath, swap_token},
+};
+
+/// Lend `amount` of one of the trading tokens out of the pool vault
+///
+/// The transaction must repay the loan and the flash fee with a later top-level `flash_repay` of
+/// the same pool, until then the pool rejects swaps, deposits and withdrawals.
+pub fn handler(ctx: Context<FlashBorrow>, amount: u64) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let a_or_b = validate_inputs(&ctx, &pool, amount)?;
+    require_flash_repay(&ctx.accounts.instructions, &ctx.accounts.pool.key())?;
+    msg!(
+        "Flash borrow inputs: mint={}, amount={}",
+        ctx.accounts.token_mint.key(),
+        amount
+    );
+
+    *pool.flash_loan_amount_mut(a_or_b) = amount;
+
+    let vault_amount = ctx.accounts.token_vault.amount;
+    swap_token::transfer_from_vault(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.destination_user_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_vault,
+        try_math!(vault_amount.try_sub(amount))?,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// Mint of the borrowed token, either of the trading tokens
+    /// CHECK: checked in the handler
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account receiving the loan
+    #[account(mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the borrowed mint
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: address constraint, the transaction instructions are read to find the repayment
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+mod utils {
+    use std::cell::RefMut;
+
+    use anchor_lang::{
+        solana_program::sysvar::instructions::{
+            load_current_index_checked, load_instruction_at_checked,
+        },
+        Discriminator,
+    };
+
+    use super::*;
+
+    /// Position of the pool in the accounts of `flash_repay`
+    const FLASH_REPAY_POOL_INDEX: usize = 1;
+
+    pub fn validate_inputs(
+        ctx: &Context<FlashBorrow>,
+        pool: &RefMut<SwapPool>,
+        amount: u64,
+    ) -> Result<AorB> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        require_msg!(
+            amount > 0,
+            SwapError::ZeroTradingTokens,
+            "ZeroTradingTokens: amount=0"
+        );
+        let token_mint = ctx.accounts.token_mint.key();
+        let (a_or_b, token_vault) = if token_mint == pool.token_a_mint {
+            (AorB::A, pool.token_a_vault)
+        } else if token_mint == pool.token_b_mint {
+            (AorB::B, pool.token_b_vault)
+        } else {
+            return err!(SwapError::IncorrectTradingMint);
+        };
+        require_msg!(
+            ctx.accounts.token_vault.key() == token_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_vault.key ({}) != {}",
+                ctx.accounts.token_vault.key(),
+                token_vault
+            )
+        );
+        require_msg!(
+            ctx.accounts.destination_user_ata.key() != token_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: destination_user_ata.key ({}) == token_vault.key ({})",
+                ctx.accounts.destination_user_ata.key(),
+                token_vault
+            )
+        );
+        Ok(a_or_b)
+    }
+
+    /// Check the current instruction is a top-level `flash_borrow` followed by a top-level
+    /// `flash_repay` of the same pool
+    ///
+    /// The repayment itself is enforced by the pool state: `flash_repay` fails unless it repays
+    /// the ongoing loan, failing the whole transaction with it.
+    pub fn require_flash_repay(instructions: &AccountInfo, pool: &Pubkey) -> Result<()> {
+        let current_index = usize::from(load_current_index_checked(instructions)?);
+        let current_ix = load_instruction_at_checked(current_index, instructions)?;
+        require_msg!(
+            current_ix.program_id == crate::ID
+                && current_ix.data.get(..8)
+                    == Some(&crate::instruction::FlashBorrow::DISCRIMINATOR[..]),
+            SwapError::FlashBorrowCpi,
+            &format!(
+                "FlashBorrowCpi: instruction {} is not a flash_borrow of program {}",
+                current_index,
+                crate::ID
+            )
+        );
+
+        for index in current_index + 1.. {
+            let ix = match load_instruction_at_checked(index, instructions) {
+                Ok(ix) => ix,
+                Err(ProgramError::InvalidArgument) => break,
+                Err(e) => return Err(e.into()),
+            };
+            if ix.program_id == crate::ID
+                && ix.data.get(..8) == Some(&crate::instruction::FlashRepay::DISCRIMINATOR[..])
+                && ix
+                    .accounts
+                    .get(FLASH_REPAY_POOL_INDEX)
+                    .map(|account| account.pubkey)
+                    == Some(*pool)
+            {
+                return Ok(());
+            }
+        }
+        msg!(
+            "FlashRepayMissing: no flash_repay of pool {} after instruction {}",
+            pool,
+            current_index
+        );
+        err!(SwapError::FlashRepayMissing)
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve::calculator::AorB,
+    error::SwapError,
+    event,
+    flash_repay::utils::validate_inputs,
+    pool_emitted, require_msg,
+    state::{SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math::TryMath, swap_token},
+};
+
+/// Repay the ongoing flash loan of the pool plus the flash fee, which is left in the vault for
+/// the LPs
+pub fn handler(ctx: Context<FlashRepay>) -> Result<event::FlashLoan> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let a_or_b = validate_inputs(&ctx, &pool)?;
+
+    let amount = pool.flash_loan_amount(a_or_b);
+    require_msg!(
+        amount > 0,
+        SwapError::FlashLoanNotActive,
+        &format!(
+            "FlashLoanNotActive: no flash loan of {} is ongoing",
+            ctx.accounts.token_mint.key()
+        )
+    );
+    let fee = to_u64!(pool.flash_fee(u128::from(amount))?)?;
+    let repay_amount = try_math!(amount.try_add(fee))?;
+    msg!(
+        "Flash repay: mint={}, amount={}, fee={}",
+        ctx.accounts.token_mint.key(),
+        amount,
+        fee
+    );
+
+    let vault_amount = ctx.accounts.token_vault.amount;
+    swap_token::transfer_from_user(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.source_user_ata.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.signer.to_account_info(),
+        swap_token::add_inverse_transfer_fee(
+            &ctx.accounts.token_mint.to_account_info(),
+            repay_amount,
+        )?,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_vault,
+        try_math!(vault_amount.try_add(repay_amount))?,
+    )?;
+
+    *pool.flash_loan_amount_mut(a_or_b) = 0;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::FlashLoan {
+            mint: ctx.accounts.token_mint.key(),
+            amount,
+            fee,
+            reserve_after: ctx.accounts.token_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// Mint of the borrowed token, either of the trading tokens
+    /// CHECK: checked in the handler
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token account repaying the loan
+    #[account(mut,
+        token::mint = token_mint,
+        token::authority = signer,
+        token::token_program = token_program,
+    )]
+    pub source_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the borrowed mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::RefMut;
+
+    use super::*;
+
+    pub fn validate_inputs(ctx: &Context<FlashRepay>, pool: &RefMut<SwapPool>) -> Result<AorB> {
+        let token_mint = ctx.accounts.token_mint.key();
+        let (a_or_b, token_vault) = if token_mint == pool.token_a_mint {
+            (AorB::A, pool.token_a_vault)
+        } else if token_mint == pool.token_b_mint {
+            (AorB::B, pool.token_b_vault)
+        } else {
+            return err!(SwapError::IncorrectTradingMint);
+        };
+        require_msg!(
+            ctx.accounts.token_vault.key() == token_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_vault.key ({}) != {}",
+                ctx.accounts.token_vault.key(),
+                token_vault
+            )
+        );
+        require_msg!(
+            ctx.accounts.source_user_ata.key() != token_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: source_user_ata.key ({}) == token_vault.key ({})",
+                ctx.accounts.source_user_ata.key(),
+                token_vault
+            )
+        );
+        Ok(a_or_b)
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::{accounts::interface_account::InterfaceAccount, prelude::*};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event,
+    instructions::update_pool_config::{utils, VALUE_BYTE_ARRAY_LEN},
+    require_msg,
+    state::{SwapPool, UpdatePoolConfigMode},
+    utils::seeds,
+};
+
+/// Pause the pool on behalf of the admin, restricted to the changes of `update_pool_config`
+/// which only pause: turning `WithdrawalsOnly` on, or adding operations to the `Status`
+///
+/// Unpausing is left to the admin.
+pub fn handler(
+    ctx: Context<GuardianPausePool>,
+    mode: u16,
+    value: &[u8; VALUE_BYTE_ARRAY_LEN],
+) -> Result<event::UpdatePoolConfig> {
+    let pool = &mut ctx.accounts.pool.load_mut()?;
+
+    let (mode, value) = utils::parse_config(mode, value)?;
+    let pauses = match mode {
+        UpdatePoolConfigMode::WithdrawalsOnly => value.to_u64() == 1,
+        UpdatePoolConfigMode::Status => value.to_u64() & pool.status == pool.status,
+        _ => false,
+    };
+    require_msg!(
+        pauses,
+        SwapError::GuardianCannotUnpause,
+        &format!(
+            "GuardianCannotUnpause: {:?} -> {:?} does not only pause the pool",
+            mode, value
+        )
+    );
+
+    let old_value = utils::set_pool_config(pool, mode, &value, ctx.remaining_accounts)?;
+    utils::record_config_change(
+        &ctx.accounts.config_history,
+        mode,
+        old_value,
+        &value,
+        ctx.accounts.guardian.key(),
+    )?;
+
+    emitted!(event::UpdatePoolConfig {
+        mode,
+        value,
+        token_a_reserve: ctx.accounts.token_a_vault.amount,
+        token_b_reserve: ctx.accounts.token_b_vault.amount,
+        pool_token_supply: ctx.accounts.pool_token_mint.supply,
+        slot: Clock::get()?.slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct GuardianPausePool<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(mut,
+        has_one = guardian,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: Config history of the pool, changes are recorded once it is initialized
+    #[account(mut,
+        seeds = [seeds::CONFIG_HISTORY, pool.key().as_ref()],
+        bump,
+    )]
+    pub config_history: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
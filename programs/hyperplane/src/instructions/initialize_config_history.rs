@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{PoolConfigHistory, SwapPool},
+    utils::seeds,
+};
+
+/// Start recording the config changes of a pool, required once for pools created before the history
+pub fn handler(ctx: Context<InitializeConfigHistory>) -> Result<()> {
+    let config_history = &mut ctx.accounts.config_history.load_init()?;
+    config_history.pool = ctx.accounts.pool.key();
+
+    msg!(
+        "Initialized config history: pool={}",
+        ctx.accounts.pool.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfigHistory<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(init,
+        seeds = [seeds::CONFIG_HISTORY, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = PoolConfigHistory::LEN,
+    )]
+    pub config_history: AccountLoader<'info, PoolConfigHistory>,
+
+    pub system_program: Program<'info, System>,
+}
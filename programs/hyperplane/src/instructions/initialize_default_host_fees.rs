@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{DefaultHostFees, SwapPool},
+    utils::seeds,
+};
+
+/// Set the recipient of the host fees of swaps routed without a host fees account
+///
+/// Host fees accrued before are owner fees and can still be withdrawn by the admin, only the ones
+/// of later swaps are held back for the recipient.
+pub fn handler(
+    ctx: Context<InitializeDefaultHostFees>,
+    recipient: Pubkey,
+) -> Result<event::SetDefaultHostFees> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.default_host_fees_a = 0;
+    pool.default_host_fees_b = 0;
+
+    let default_host_fees = &mut ctx.accounts.default_host_fees;
+    default_host_fees.pool = ctx.accounts.pool.key();
+    default_host_fees.recipient = recipient;
+
+    msg!("Initialized default host fees: recipient={}", recipient);
+
+    emitted!(event::SetDefaultHostFees { recipient });
+}
+
+#[derive(Accounts)]
+pub struct InitializeDefaultHostFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, has_one = admin)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(init,
+        seeds = [seeds::DEFAULT_HOST_FEES, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = DefaultHostFees::LEN,
+    )]
+    pub default_host_fees: Box<Account<'info, DefaultHostFees>>,
+
+    pub system_program: Program<'info, System>,
+}
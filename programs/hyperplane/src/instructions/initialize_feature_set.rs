@@ -0,0 +1,39 @@
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
+
+use crate::{error::SwapError, state::FeatureSet, utils::seeds};
+
+pub fn handler(ctx: Context<InitializeFeatureSet>) -> Result<()> {
+    let feature_set = &mut ctx.accounts.feature_set;
+    feature_set.admin = ctx.accounts.admin.key();
+    feature_set.enabled_features = 0;
+
+    msg!("Initialized feature set: admin={}", feature_set.admin);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeatureSet<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program data of this program, the admin must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ SwapError::InvaliPoolAdmin,
+    )]
+    pub program_data: Box<Account<'info, ProgramData>>,
+
+    #[account(init,
+        seeds = [seeds::FEATURE_SET],
+        bump,
+        payer = admin,
+        space = FeatureSet::LEN,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
+
+use crate::{error::SwapError, state::FeeTierRegistry, utils::seeds};
+
+pub fn handler(ctx: Context<InitializeFeeTierRegistry>) -> Result<()> {
+    let fee_tier_registry = &mut ctx.accounts.fee_tier_registry;
+    fee_tier_registry.admin = ctx.accounts.admin.key();
+    fee_tier_registry.tiers_len = 0;
+
+    msg!(
+        "Initialized fee tier registry: admin={}",
+        fee_tier_registry.admin
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeTierRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program data of this program, the admin must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ SwapError::InvaliPoolAdmin,
+    )]
+    pub program_data: Box<Account<'info, ProgramData>>,
+
+    #[account(init,
+        seeds = [seeds::FEE_TIER_REGISTRY],
+        bump,
+        payer = admin,
+        space = FeeTierRegistry::LEN,
+    )]
+    pub fee_tier_registry: Box<Account<'info, FeeTierRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
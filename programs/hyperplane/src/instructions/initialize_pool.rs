@@ -13,19 +13,56 @@ use serde;
 use crate::{
     constraints::SWAP_CONSTRAINTS,
     curve::{base::SwapCurve, fees::Fees},
+    emitted,
     error::SwapError,
+    event, require_msg,
     state::{Curve, SwapPool},
     to_u64,
-    utils::{pool_token, seeds, swap_token},
+    utils::{extension_policy, pool_token, seeds, swap_token},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub enum CurveUserParameters {
     ConstantProduct,
-    ConstantPrice { token_b_price: u64 },
-    Offset { token_b_offset: u64 },
-    Stable { amp: u64 },
+    ConstantPrice {
+        token_b_price: u64,
+        #[cfg_attr(feature = "serde", serde(default))]
+        spread_bps: u64,
+    },
+    Offset {
+        token_b_offset: u64,
+    },
+    Stable {
+        amp: u64,
+    },
+    /// Stable curve around a peg, `token_b_peg` is the price of one token B in tokens A, scaled by
+    /// `PeggedCurve::PEG_SCALE`
+    Pegged {
+        amp: u64,
+        token_b_peg: u64,
+    },
+    /// Constant product within a price range, the prices of one native token A in native tokens B
+    /// are scaled by `ConstantProductWithBoundsCurve::PRICE_SCALE`
+    ConstantProductWithBounds {
+        min_price: u64,
+        max_price: u64,
+    },
+    /// Weighted constant product, the weights are the shares of the pool value held in each
+    /// token and add up to `WeightedCurve::WEIGHTS_DENOMINATOR`
+    Weighted {
+        weight_a: u64,
+        weight_b: u64,
+    },
+    /// Constant price of one token B in tokens A read from the `oracle` price account on every
+    /// swap, see `OraclePeggedCurve`
+    OraclePegged {
+        oracle: Pubkey,
+        oracle_type: u64,
+        scope_price_index: u64,
+        max_age_slots: u64,
+        spread_bps: u64,
+    },
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -40,7 +77,31 @@ pub fn handler(
     curve_parameters: CurveUserParameters,
     fees: Fees,
     initial_supply: InitialSupply,
-) -> Result<()> {
+    burn_owner_fees: bool,
+    strict_token_program: bool,
+) -> Result<event::PoolCreated> {
+    initialize(
+        ctx,
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+        None,
+    )
+}
+
+/// Initialize the pool with `fees`, taken from the fee tier registry when `fee_tier` is set. Fees
+/// of a tier were approved by the program admin and are not checked against the fee constraints.
+pub(crate) fn initialize(
+    ctx: Context<InitializePool>,
+    curve_parameters: CurveUserParameters,
+    fees: Fees,
+    initial_supply: InitialSupply,
+    burn_owner_fees: bool,
+    strict_token_program: bool,
+    fee_tier: Option<u16>,
+) -> Result<event::PoolCreated> {
     let InitialSupply {
         initial_supply_a,
         initial_supply_b,
@@ -66,10 +127,29 @@ pub fn handler(
 
     let swap_constraints = &SWAP_CONSTRAINTS;
 
+    // Mixed spl-token / token-2022 pools are allowed unless the creator or the deployment opts out
+    let strict_token_program = strict_token_program
+        || swap_constraints
+            .as_ref()
+            .map_or(false, |constraints| constraints.strict_token_program);
+    if strict_token_program {
+        require_msg!(
+            ctx.accounts.token_a_token_program.key() == ctx.accounts.token_b_token_program.key(),
+            SwapError::MixedTokenPrograms,
+            &format!(
+                "MixedTokenPrograms: token_a_token_program ({}) != token_b_token_program ({})",
+                ctx.accounts.token_a_token_program.key(),
+                ctx.accounts.token_b_token_program.key()
+            )
+        );
+    }
+
     if let Some(swap_constraints) = swap_constraints {
         // swap_constraints.validate_admin(ctx.accounts.admin.key)?;
         swap_constraints.validate_curve(&swap_curve)?;
-        swap_constraints.validate_fees(&fees)?;
+        if fee_tier.is_none() {
+            swap_constraints.validate_fees(&fees)?;
+        }
         swap_constraints.validate_token_2022_trading_token_extensions(
             &ctx.accounts.token_a_mint.to_account_info(),
         )?;
@@ -77,10 +157,16 @@ pub fn handler(
             &ctx.accounts.token_b_mint.to_account_info(),
         )?;
     }
+    let token_a_behavior = extension_policy::validate_trading_token_mint(
+        &ctx.accounts.token_a_mint.to_account_info(),
+    )?;
+    let token_b_behavior = extension_policy::validate_trading_token_mint(
+        &ctx.accounts.token_b_mint.to_account_info(),
+    )?;
     fees.validate()?;
     swap_curve.calculator.validate()?;
 
-    let initial_amount = swap_curve.calculator.new_pool_supply();
+    let initial_amount = to_u64!(swap_curve.calculator.new_pool_supply())?;
     let pool_authority_bump = *ctx.bumps.get("pool_authority").unwrap();
 
     let pool = &mut ctx.accounts.pool.load_init()?;
@@ -97,6 +183,21 @@ pub fn handler(
     pool.fees = fees;
     pool.curve_type = swap_curve.curve_type.into();
     pool.swap_curve = ctx.accounts.swap_curve.key();
+    pool.allows_deposits = u64::from(swap_curve.calculator.allows_deposits());
+    pool.allows_single_sided_deposits =
+        u64::from(swap_curve.calculator.allows_single_sided_deposits());
+    pool.burn_owner_fees = u64::from(burn_owner_fees);
+    pool.protocol_fee_bps = swap_constraints
+        .as_ref()
+        .map_or(0, |constraints| constraints.protocol_fee_bps);
+    pool.token_a_interest_bearing = u64::from(token_a_behavior.interest_bearing);
+    pool.token_b_interest_bearing = u64::from(token_b_behavior.interest_bearing);
+    pool.swap_curve_bump_seed = u64::from(*ctx.bumps.get("swap_curve").unwrap());
+    pool.token_a_vault_bump_seed = u64::from(*ctx.bumps.get("token_a_vault").unwrap());
+    pool.token_b_vault_bump_seed = u64::from(*ctx.bumps.get("token_b_vault").unwrap());
+    pool.pool_token_mint_bump_seed = u64::from(*ctx.bumps.get("pool_token_mint").unwrap());
+    pool.token_a_fees_vault_bump_seed = u64::from(*ctx.bumps.get("token_a_fees_vault").unwrap());
+    pool.token_b_fees_vault_bump_seed = u64::from(*ctx.bumps.get("token_b_fees_vault").unwrap());
 
     swap_token::transfer_from_user(
         ctx.accounts.token_a_token_program.to_account_info(),
@@ -124,15 +225,36 @@ pub fn handler(
         ctx.accounts.pool_authority.to_account_info(),
         pool_authority_bump,
         ctx.accounts.admin_pool_token_ata.to_account_info(),
-        to_u64!(initial_amount)?,
+        initial_amount,
     )?;
 
     // Serialize the curve with a layout that is specific to the curve type
     swap_curve
         .calculator
         .try_dyn_serialize(ctx.accounts.swap_curve.try_borrow_mut_data()?)?;
-
-    Ok(())
+    Curve::write_checksum(&mut ctx.accounts.swap_curve.try_borrow_mut_data()?);
+    pool.curve_checksum_written = u64::from(true);
+
+    emitted!(event::PoolCreated {
+        pool: ctx.accounts.pool.key(),
+        admin: pool.admin,
+        swap_curve: pool.swap_curve,
+        pool_authority: pool.pool_authority,
+        token_a_mint: pool.token_a_mint,
+        token_b_mint: pool.token_b_mint,
+        token_a_vault: pool.token_a_vault,
+        token_b_vault: pool.token_b_vault,
+        pool_token_mint: pool.pool_token_mint,
+        token_a_fees_vault: pool.token_a_fees_vault,
+        token_b_fees_vault: pool.token_b_fees_vault,
+        curve_type: pool.curve_type,
+        fees,
+        initial_supply_a,
+        initial_supply_b,
+        initial_pool_token_amount: initial_amount,
+        burn_owner_fees,
+        strict_token_program,
+    });
 }
 
 #[derive(Accounts)]
@@ -160,7 +282,6 @@ pub struct InitializePool<'info> {
     pub pool_authority: AccountInfo<'info>,
 
     // todo - elliot - should we block if mint has freeze authority?
-    // todo - elliot - token 2022 - should we block if mint has close authority?
     /// Token A mint
     // note - constraint repeated for clarity
     #[account(
@@ -170,7 +291,6 @@ pub struct InitializePool<'info> {
     pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
 
     // todo - elliot - should we block if mint has freeze authority?
-    // todo - elliot - token 2022 - should we block if mint has close authority?
     /// Token B mint
     // note - constraint repeated for clarity
     #[account(
@@ -179,10 +299,13 @@ pub struct InitializePool<'info> {
     )]
     pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    // note - constraints of init accounts run before the ones of the mints above, check for a
+    // repeated mint before the token B vault init CPI fails on a mint passed with both token programs
     #[account(init,
         seeds = [seeds::TOKEN_A_VAULT, pool.key().as_ref(), token_a_mint.key().as_ref()],
         bump,
         payer = admin,
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
         token::mint = token_a_mint,
         token::authority = pool_authority,
         token::token_program = token_a_token_program,
@@ -276,6 +399,7 @@ pub mod model {
         ConstantProduct,
         ConstantPrice {
             token_b_price: u64,
+            spread_bps: u64,
         },
         Offset {
             token_b_offset: u64,
@@ -285,6 +409,29 @@ pub mod model {
             token_a_decimals: u8,
             token_b_decimals: u8,
         },
+        Pegged {
+            amp: u64,
+            token_b_peg: u64,
+            token_a_decimals: u8,
+            token_b_decimals: u8,
+        },
+        ConstantProductWithBounds {
+            min_price: u64,
+            max_price: u64,
+        },
+        Weighted {
+            weight_a: u64,
+            weight_b: u64,
+        },
+        OraclePegged {
+            oracle: Pubkey,
+            oracle_type: u64,
+            scope_price_index: u64,
+            max_age_slots: u64,
+            spread_bps: u64,
+            token_a_decimals: u8,
+            token_b_decimals: u8,
+        },
     }
 
     impl CurveUserParameters {
@@ -295,11 +442,13 @@ pub mod model {
         ) -> CurveParameters {
             match self {
                 CurveUserParameters::ConstantProduct => CurveParameters::ConstantProduct,
-                CurveUserParameters::ConstantPrice { token_b_price } => {
-                    CurveParameters::ConstantPrice {
-                        token_b_price: *token_b_price,
-                    }
-                }
+                CurveUserParameters::ConstantPrice {
+                    token_b_price,
+                    spread_bps,
+                } => CurveParameters::ConstantPrice {
+                    token_b_price: *token_b_price,
+                    spread_bps: *spread_bps,
+                },
                 CurveUserParameters::Offset { token_b_offset } => CurveParameters::Offset {
                     token_b_offset: *token_b_offset,
                 },
@@ -308,6 +457,38 @@ pub mod model {
                     token_a_decimals,
                     token_b_decimals,
                 },
+                CurveUserParameters::Pegged { amp, token_b_peg } => CurveParameters::Pegged {
+                    amp: *amp,
+                    token_b_peg: *token_b_peg,
+                    token_a_decimals,
+                    token_b_decimals,
+                },
+                CurveUserParameters::ConstantProductWithBounds {
+                    min_price,
+                    max_price,
+                } => CurveParameters::ConstantProductWithBounds {
+                    min_price: *min_price,
+                    max_price: *max_price,
+                },
+                CurveUserParameters::Weighted { weight_a, weight_b } => CurveParameters::Weighted {
+                    weight_a: *weight_a,
+                    weight_b: *weight_b,
+                },
+                CurveUserParameters::OraclePegged {
+                    oracle,
+                    oracle_type,
+                    scope_price_index,
+                    max_age_slots,
+                    spread_bps,
+                } => CurveParameters::OraclePegged {
+                    oracle: *oracle,
+                    oracle_type: *oracle_type,
+                    scope_price_index: *scope_price_index,
+                    max_age_slots: *max_age_slots,
+                    spread_bps: *spread_bps,
+                    token_a_decimals,
+                    token_b_decimals,
+                },
             }
         }
     }
@@ -316,9 +497,13 @@ pub mod model {
         fn from(curve_params: CurveParameters) -> Self {
             match curve_params {
                 CurveParameters::ConstantProduct => CurveUserParameters::ConstantProduct,
-                CurveParameters::ConstantPrice { token_b_price } => {
-                    CurveUserParameters::ConstantPrice { token_b_price }
-                }
+                CurveParameters::ConstantPrice {
+                    token_b_price,
+                    spread_bps,
+                } => CurveUserParameters::ConstantPrice {
+                    token_b_price,
+                    spread_bps,
+                },
                 CurveParameters::Offset { token_b_offset } => {
                     CurveUserParameters::Offset { token_b_offset }
                 }
@@ -327,6 +512,37 @@ pub mod model {
                     token_a_decimals: _,
                     token_b_decimals: _,
                 } => CurveUserParameters::Stable { amp },
+                CurveParameters::Pegged {
+                    amp,
+                    token_b_peg,
+                    token_a_decimals: _,
+                    token_b_decimals: _,
+                } => CurveUserParameters::Pegged { amp, token_b_peg },
+                CurveParameters::ConstantProductWithBounds {
+                    min_price,
+                    max_price,
+                } => CurveUserParameters::ConstantProductWithBounds {
+                    min_price,
+                    max_price,
+                },
+                CurveParameters::Weighted { weight_a, weight_b } => {
+                    CurveUserParameters::Weighted { weight_a, weight_b }
+                }
+                CurveParameters::OraclePegged {
+                    oracle,
+                    oracle_type,
+                    scope_price_index,
+                    max_age_slots,
+                    spread_bps,
+                    token_a_decimals: _,
+                    token_b_decimals: _,
+                } => CurveUserParameters::OraclePegged {
+                    oracle,
+                    oracle_type,
+                    scope_price_index,
+                    max_age_slots,
+                    spread_bps,
+                },
             }
         }
     }
@@ -0,0 +1,216 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    error::SwapError,
+    event, instructions,
+    instructions::{CurveUserParameters, InitialSupply, InitializePool},
+    state::{FeeTierRegistry, SwapPool},
+    utils::seeds,
+};
+
+/// Initialize a pool with the fees of a tier of the fee tier registry instead of caller provided
+/// fees
+///
+/// The tiers are approved by the program admin, so production deployments can open pool creation
+/// to anyone with a set of fees changed without rebuilding the program fee constraints.
+pub fn handler(
+    ctx: Context<InitializePoolWithFeeTier>,
+    curve_parameters: CurveUserParameters,
+    fee_tier: u16,
+    initial_supply: InitialSupply,
+    burn_owner_fees: bool,
+    strict_token_program: bool,
+) -> Result<event::PoolCreated> {
+    let fees = ctx.accounts.fee_tier_registry.tier(fee_tier)?;
+    msg!(
+        "Initialize with fee tier: fee_tier={}, fees={:?}",
+        fee_tier,
+        fees
+    );
+
+    let mut accounts = InitializePool::from(&*ctx.accounts);
+    instructions::initialize_pool::initialize(
+        Context::new(
+            ctx.program_id,
+            &mut accounts,
+            ctx.remaining_accounts,
+            ctx.bumps,
+        ),
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+        Some(fee_tier),
+    )
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolWithFeeTier<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(zero)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: created here at the curve PDA of the pool, owned by the program and zeroed with
+    /// `Curve::LEN` bytes. It is only written by `initialize` with the curve of the parameters
+    #[account(init,
+        seeds = [seeds::SWAP_CURVE, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = Curve::LEN,
+    )]
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owned by the program
+    #[account(mut,
+        seeds = [seeds::POOL_AUTHORITY, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    // todo - elliot - should we block if mint has freeze authority?
+    /// Token A mint
+    // note - constraint repeated for clarity
+    #[account(
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        mint::token_program = token_a_token_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // todo - elliot - should we block if mint has freeze authority?
+    /// Token B mint
+    // note - constraint repeated for clarity
+    #[account(
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        mint::token_program = token_b_token_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // note - constraints of init accounts run before the ones of the mints above, check for a
+    // repeated mint before the token B vault init CPI fails on a mint passed with both token programs
+    #[account(init,
+        seeds = [seeds::TOKEN_A_VAULT, pool.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::TOKEN_B_VAULT, pool.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // todo - elliot - set no close authority, immutable? Should be default?
+    #[account(init,
+        seeds=[seeds::POOL_TOKEN_MINT, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        mint::decimals = 6,
+        mint::authority = pool_authority,
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account to collect trading token a fees into - designated to the pool admin authority
+    #[account(init,
+        seeds=[seeds::TOKEN_A_FEES_VAULT, pool.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account to collect trading token b fees into - designated to the pool admin authority
+    #[account(init,
+        seeds=[seeds::TOKEN_B_FEES_VAULT, pool.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's token A account to deposit initial liquidity from
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::authority = admin,
+        token::token_program = token_a_token_program,
+    )]
+    pub admin_token_a_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's token B account to deposit initial liquidity from
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = admin,
+        token::token_program = token_b_token_program,
+    )]
+    pub admin_token_b_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's pool token account to deposit the initially minted pool tokens into
+    #[account(init,
+        payer = admin,
+        token::mint = pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// The token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// The token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        seeds = [seeds::FEE_TIER_REGISTRY],
+        bump,
+    )]
+    pub fee_tier_registry: Box<Account<'info, FeeTierRegistry>>,
+}
+
+impl<'info> From<&InitializePoolWithFeeTier<'info>> for InitializePool<'info> {
+    fn from(accounts: &InitializePoolWithFeeTier<'info>) -> Self {
+        Self {
+            admin: accounts.admin.clone(),
+            pool: accounts.pool.clone(),
+            swap_curve: accounts.swap_curve.clone(),
+            pool_authority: accounts.pool_authority.clone(),
+            token_a_mint: accounts.token_a_mint.clone(),
+            token_b_mint: accounts.token_b_mint.clone(),
+            token_a_vault: accounts.token_a_vault.clone(),
+            token_b_vault: accounts.token_b_vault.clone(),
+            pool_token_mint: accounts.pool_token_mint.clone(),
+            token_a_fees_vault: accounts.token_a_fees_vault.clone(),
+            token_b_fees_vault: accounts.token_b_fees_vault.clone(),
+            admin_token_a_ata: accounts.admin_token_a_ata.clone(),
+            admin_token_b_ata: accounts.admin_token_b_ata.clone(),
+            admin_pool_token_ata: accounts.admin_pool_token_ata.clone(),
+            system_program: accounts.system_program.clone(),
+            rent: accounts.rent.clone(),
+            pool_token_program: accounts.pool_token_program.clone(),
+            token_a_token_program: accounts.token_a_token_program.clone(),
+            token_b_token_program: accounts.token_b_token_program.clone(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
+
+use crate::{error::SwapError, state::ProtocolFeeConfig, utils::seeds};
+
+/// Set the program-wide authority withdrawing the protocol fees accrued by the pools
+///
+/// The protocol share of the owner trade fees is set by the `protocol_fee_bps` of each pool,
+/// constrained in production builds.
+pub fn handler(ctx: Context<InitializeProtocolFeeConfig>, authority: Pubkey) -> Result<()> {
+    let protocol_fee_config = &mut ctx.accounts.protocol_fee_config;
+    protocol_fee_config.authority = authority;
+
+    msg!("Initialized protocol fee config: authority={}", authority);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program data of this program, the admin must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ SwapError::InvaliPoolAdmin,
+    )]
+    pub program_data: Box<Account<'info, ProgramData>>,
+
+    #[account(init,
+        seeds = [seeds::PROTOCOL_FEE_CONFIG],
+        bump,
+        payer = admin,
+        space = ProtocolFeeConfig::LEN,
+    )]
+    pub protocol_fee_config: Box<Account<'info, ProtocolFeeConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{state::ProtocolFeeConfig, utils::seeds};
+
+/// Create the protocol fee vault of a mint, shared by every pool trading it
+///
+/// The protocol fees of any pool can then be swept into it with `sweep_protocol_fees`, so the
+/// authority withdraws a single vault per mint instead of every fees vault of every pool.
+pub fn handler(ctx: Context<InitializeProtocolFeeVault>) -> Result<()> {
+    msg!(
+        "Initialized protocol fee vault {} of mint {}",
+        ctx.accounts.protocol_fee_vault.key(),
+        ctx.accounts.mint.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::PROTOCOL_FEE_CONFIG],
+        bump,
+        has_one = authority,
+    )]
+    pub protocol_fee_config: Box<Account<'info, ProtocolFeeConfig>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(init,
+        seeds = [seeds::PROTOCOL_FEE_VAULT, mint.key().as_ref()],
+        bump,
+        payer = authority,
+        token::mint = mint,
+        token::authority = protocol_fee_config,
+        token::token_program = token_program,
+    )]
+    pub protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+    solana_program::{hash::hash, program::set_return_data},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve::fees::Fees,
+    error::SwapError,
+    event, instructions,
+    instructions::{CurveUserParameters, InitialSupply, InitializePool},
+    state::{Curve, SwapPool},
+    utils::seeds,
+};
+
+/// Arguments of `initialize_test_pool`, serialized after its discriminator
+///
+/// Anchor generates the dispatch and the IDL entry of every instruction of the program module,
+/// whatever its `#[cfg]`, so this one is dispatched from the program `fallback` to leave it out
+/// of the builds without `dev-mode` and of the IDL.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeTestPoolArgs {
+    pub seed: [u8; 32],
+    pub curve_parameters: CurveUserParameters,
+    pub fees: Fees,
+    pub initial_supply: InitialSupply,
+    pub burn_owner_fees: bool,
+    pub strict_token_program: bool,
+}
+
+impl InitializeTestPoolArgs {
+    /// Discriminator anchor gives the instruction, unchanged for the existing clients
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0; 8];
+        discriminator.copy_from_slice(&hash(b"global:initialize_test_pool").to_bytes()[..8]);
+        discriminator
+    }
+
+    pub fn data(&self) -> Vec<u8> {
+        [&Self::discriminator()[..], &self.try_to_vec().unwrap()].concat()
+    }
+}
+
+/// Run `initialize_test_pool` if `data` is one, like anchor runs the instructions it dispatches
+pub fn dispatch(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> Option<Result<()>> {
+    let ix_data = data.strip_prefix(&InitializeTestPoolArgs::discriminator()[..])?;
+    Some(try_dispatch(program_id, accounts, ix_data))
+}
+
+fn try_dispatch(program_id: &Pubkey, accounts: &[AccountInfo], ix_data: &[u8]) -> Result<()> {
+    msg!("Instruction: InitializeTestPool");
+    let InitializeTestPoolArgs {
+        seed,
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    } = InitializeTestPoolArgs::deserialize(&mut &ix_data[..])
+        .map_err(|_| ErrorCode::InstructionDidNotDeserialize)?;
+
+    let mut bumps = BTreeMap::new();
+    let mut reallocs = BTreeSet::new();
+    let mut remaining_accounts: &[AccountInfo] = accounts;
+    let mut accounts = InitializeTestPool::try_accounts(
+        program_id,
+        &mut remaining_accounts,
+        ix_data,
+        &mut bumps,
+        &mut reallocs,
+    )?;
+
+    let event = handler(
+        Context::new(program_id, &mut accounts, remaining_accounts, bumps),
+        seed,
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    )?;
+    set_return_data(&event.try_to_vec().unwrap());
+
+    accounts.exit(program_id)
+}
+
+/// Initialize a pool at a PDA of the admin and a caller provided seed, for localnet tests which
+/// need the same addresses on every run
+///
+/// Every other pool PDA derives from the pool address, so the whole pool is deterministic for a
+/// given admin, seed and pair of mints. Only built with the `dev-mode` feature.
+pub fn handler(
+    ctx: Context<InitializeTestPool>,
+    seed: [u8; 32],
+    curve_parameters: CurveUserParameters,
+    fees: Fees,
+    initial_supply: InitialSupply,
+    burn_owner_fees: bool,
+    strict_token_program: bool,
+) -> Result<event::PoolCreated> {
+    msg!(
+        "Initialize test pool: admin={}, seed={:?}, pool={}",
+        ctx.accounts.admin.key(),
+        seed,
+        ctx.accounts.pool.key()
+    );
+
+    let mut accounts = InitializePool::from(&*ctx.accounts);
+    instructions::initialize_pool::handler(
+        Context::new(
+            ctx.program_id,
+            &mut accounts,
+            ctx.remaining_accounts,
+            ctx.bumps,
+        ),
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct InitializeTestPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(init,
+        seeds = [seeds::TEST_POOL, admin.key().as_ref(), seed.as_ref()],
+        bump,
+        payer = admin,
+        space = SwapPool::LEN,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: This is checked in the handler
+    #[account(init,
+        seeds = [seeds::SWAP_CURVE, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = Curve::LEN,
+    )]
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owned by the program
+    #[account(mut,
+        seeds = [seeds::POOL_AUTHORITY, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// Token A mint
+    // note - constraint repeated for clarity
+    #[account(
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        mint::token_program = token_a_token_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token B mint
+    // note - constraint repeated for clarity
+    #[account(
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        mint::token_program = token_b_token_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // note - constraints of init accounts run before the ones of the mints above, check for a
+    // repeated mint before the token B vault init CPI fails on a mint passed with both token programs
+    #[account(init,
+        seeds = [seeds::TOKEN_A_VAULT, pool.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        constraint = token_a_mint.key() != token_b_mint.key() @ SwapError::RepeatedMint,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::TOKEN_B_VAULT, pool.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds=[seeds::POOL_TOKEN_MINT, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        mint::decimals = 6,
+        mint::authority = pool_authority,
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account to collect trading token a fees into - designated to the pool admin authority
+    #[account(init,
+        seeds=[seeds::TOKEN_A_FEES_VAULT, pool.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account to collect trading token b fees into - designated to the pool admin authority
+    #[account(init,
+        seeds=[seeds::TOKEN_B_FEES_VAULT, pool.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's token A account to deposit initial liquidity from
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::authority = admin,
+        token::token_program = token_a_token_program,
+    )]
+    pub admin_token_a_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's token B account to deposit initial liquidity from
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = admin,
+        token::token_program = token_b_token_program,
+    )]
+    pub admin_token_b_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin authority's pool token account to deposit the initially minted pool tokens into
+    #[account(init,
+        payer = admin,
+        token::mint = pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// The token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// The token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> From<&InitializeTestPool<'info>> for InitializePool<'info> {
+    fn from(accounts: &InitializeTestPool<'info>) -> Self {
+        Self {
+            admin: accounts.admin.clone(),
+            pool: accounts.pool.clone(),
+            swap_curve: accounts.swap_curve.clone(),
+            pool_authority: accounts.pool_authority.clone(),
+            token_a_mint: accounts.token_a_mint.clone(),
+            token_b_mint: accounts.token_b_mint.clone(),
+            token_a_vault: accounts.token_a_vault.clone(),
+            token_b_vault: accounts.token_b_vault.clone(),
+            pool_token_mint: accounts.pool_token_mint.clone(),
+            token_a_fees_vault: accounts.token_a_fees_vault.clone(),
+            token_b_fees_vault: accounts.token_b_fees_vault.clone(),
+            admin_token_a_ata: accounts.admin_token_a_ata.clone(),
+            admin_token_b_ata: accounts.admin_token_b_ata.clone(),
+            admin_pool_token_ata: accounts.admin_pool_token_ata.clone(),
+            system_program: accounts.system_program.clone(),
+            rent: accounts.rent.clone(),
+            pool_token_program: accounts.pool_token_program.clone(),
+            token_a_token_program: accounts.token_a_token_program.clone(),
+            token_b_token_program: accounts.token_b_token_program.clone(),
+        }
+    }
+}
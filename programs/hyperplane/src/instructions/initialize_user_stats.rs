@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{SwapPool, UserStats},
+    utils::seeds,
+};
+
+/// Opt in to recording the swaps of the owner on a pool, swaps update the stats when they are
+/// passed as first remaining account
+pub fn handler(ctx: Context<InitializeUserStats>) -> Result<()> {
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.pool = ctx.accounts.pool.key();
+    user_stats.owner = ctx.accounts.owner.key();
+
+    msg!(
+        "Initialized user stats: pool={}, owner={}",
+        ctx.accounts.pool.key(),
+        ctx.accounts.owner.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserStats<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(init,
+        seeds = [seeds::USER_STATS, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = UserStats::LEN,
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LockedLpPosition, SwapPool},
+    try_math,
+    utils::{math::TryMath, seeds, swap_token},
+};
+
+/// Lock pool tokens in escrow for `lock_duration_slots`, until only the owner can unlock them
+pub fn handler(
+    ctx: Context<LockLiquidity>,
+    pool_token_amount: u64,
+    lock_duration_slots: u64,
+) -> Result<event::LockLiquidity> {
+    msg!(
+        "Lock liquidity inputs: pool_token_amount={}, lock_duration_slots={}",
+        pool_token_amount,
+        lock_duration_slots,
+    );
+    require_msg!(
+        pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: pool_token_amount=0"
+    );
+    require_msg!(
+        lock_duration_slots > 0,
+        SwapError::InvalidLockDuration,
+        "InvalidLockDuration: lock_duration_slots=0"
+    );
+    let current_slot = Clock::get()?.slot;
+    let unlock_slot = try_math!(current_slot.try_add(lock_duration_slots))?;
+
+    swap_token::transfer_from_user(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        pool_token_amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    position.pool = ctx.accounts.pool.key();
+    position.owner = ctx.accounts.owner.key();
+    position.pool_token_vault = ctx.accounts.pool_token_vault.key();
+    position.pool_token_amount = pool_token_amount;
+    position.locked_slot = current_slot;
+    position.unlock_slot = unlock_slot;
+
+    emitted!(event::LockLiquidity {
+        position: ctx.accounts.position.key(),
+        pool_token_amount,
+        unlock_slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct LockLiquidity<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(init,
+        seeds = [seeds::LOCKED_LP_POSITION, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = LockedLpPosition::LEN,
+    )]
+    pub position: Box<Account<'info, LockedLpPosition>>,
+
+    /// Holds the locked pool tokens until they are unlocked
+    #[account(init,
+        seeds = [seeds::LOCKED_LP_POOL_TOKEN_VAULT, position.key().as_ref()],
+        bump,
+        payer = owner,
+        token::mint = pool_token_mint,
+        token::authority = pool_authority,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's pool token account to lock the pool tokens from
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
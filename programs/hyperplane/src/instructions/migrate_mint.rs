@@ -0,0 +1,439 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed, program_option::COption},
+};
+use anchor_spl::{
+    token_2022::spl_token_2022::{self, extension::StateWithExtensions},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constraints::SWAP_CONSTRAINTS,
+    curve::calculator::AorB,
+    emitted,
+    error::SwapError,
+    event,
+    migrate_mint::utils::{convert, validate_inputs},
+    require_msg,
+    state::{Feature, FeatureSet, SwapPool, SwapState},
+    utils::{extension_policy, seeds, swap_token},
+};
+
+/// Replace token A or B of the pool with a successor mint claimable 1:1, e.g. after a token
+/// contract migration
+///
+/// The balances of the vault and fees vault are converted into new vaults of the successor mint by
+/// the conversion program, invoked once per vault with the pool authority signature, so the pool
+/// does not need to be drained and recreated. The emptied vaults of the old mint are left behind.
+///
+/// The conversion program must be allowed by the program admin in the feature set, and is not
+/// given any other account the pool authority controls. The other vaults and the pool token supply
+/// are checked to be unchanged after the conversion.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateMint<'info>>,
+    a_or_b: AorB,
+    conversion_data: Vec<u8>,
+) -> Result<event::MigrateMint> {
+    ctx.accounts
+        .feature_set
+        .require_enabled(Feature::MintMigration)?;
+    ctx.accounts
+        .feature_set
+        .require_conversion_program_allowed(&ctx.accounts.conversion_program.key())?;
+    let bump_seed = {
+        let pool = ctx.accounts.pool.load()?;
+        validate_inputs(&ctx, &pool, a_or_b)?;
+        pool.bump_seed()
+    };
+    let new_mint_behavior =
+        extension_policy::validate_trading_token_mint(&ctx.accounts.new_mint.to_account_info())?;
+
+    let vault_amount = ctx.accounts.old_vault.amount;
+    let fees_vault_amount = ctx.accounts.old_fees_vault.amount;
+    let other_vault_amount = ctx.accounts.other_vault.amount;
+    let other_fees_vault_amount = ctx.accounts.other_fees_vault.amount;
+    let pool_token_supply = ctx.accounts.pool_token_mint.supply;
+    msg!(
+        "Migrating mint: a_or_b={:?}, old_mint={}, new_mint={}, vault_amount={}, fees_vault_amount={}",
+        a_or_b,
+        ctx.accounts.old_mint.key(),
+        ctx.accounts.new_mint.key(),
+        vault_amount,
+        fees_vault_amount
+    );
+
+    convert(
+        &ctx,
+        &ctx.accounts.old_vault,
+        &ctx.accounts.new_vault,
+        &conversion_data,
+        vault_amount,
+        bump_seed,
+    )?;
+    convert(
+        &ctx,
+        &ctx.accounts.old_fees_vault,
+        &ctx.accounts.new_fees_vault,
+        &conversion_data,
+        fees_vault_amount,
+        bump_seed,
+    )?;
+
+    // 1:1 claim, the old vaults must be emptied into the exact same amounts of the successor mint
+    swap_token::reload_and_require_vault_amount(&mut ctx.accounts.old_vault, 0)?;
+    swap_token::reload_and_require_vault_amount(&mut ctx.accounts.old_fees_vault, 0)?;
+    swap_token::reload_and_require_vault_amount(&mut ctx.accounts.new_vault, vault_amount)?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.new_fees_vault,
+        fees_vault_amount,
+    )?;
+    // and nothing else the pool authority controls may move
+    swap_token::reload_and_require_vault_amount(&mut ctx.accounts.other_vault, other_vault_amount)?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.other_fees_vault,
+        other_fees_vault_amount,
+    )?;
+    ctx.accounts.pool_token_mint.reload()?;
+    require_msg!(
+        ctx.accounts.pool_token_mint.supply == pool_token_supply,
+        SwapError::InvalidConversionProgram,
+        &format!(
+            "InvalidConversionProgram: pool token supply={} != supply before the conversion={}",
+            ctx.accounts.pool_token_mint.supply, pool_token_supply
+        )
+    );
+
+    let pool = &mut ctx.accounts.pool.load_mut()?;
+    let new_mint = ctx.accounts.new_mint.key();
+    let new_vault = ctx.accounts.new_vault.key();
+    let new_fees_vault = ctx.accounts.new_fees_vault.key();
+    let new_vault_bump = u64::from(*ctx.bumps.get("new_vault").unwrap());
+    let new_fees_vault_bump = u64::from(*ctx.bumps.get("new_fees_vault").unwrap());
+    match a_or_b {
+        AorB::A => {
+            pool.token_a_mint = new_mint;
+            pool.token_a_vault = new_vault;
+            pool.token_a_fees_vault = new_fees_vault;
+            pool.token_a_vault_bump_seed = new_vault_bump;
+            pool.token_a_fees_vault_bump_seed = new_fees_vault_bump;
+            pool.token_a_interest_bearing = u64::from(new_mint_behavior.interest_bearing);
+        }
+        AorB::B => {
+            pool.token_b_mint = new_mint;
+            pool.token_b_vault = new_vault;
+            pool.token_b_fees_vault = new_fees_vault;
+            pool.token_b_vault_bump_seed = new_vault_bump;
+            pool.token_b_fees_vault_bump_seed = new_fees_vault_bump;
+            pool.token_b_interest_bearing = u64::from(new_mint_behavior.interest_bearing);
+        }
+    }
+
+    emitted!(event::MigrateMint {
+        old_mint: ctx.accounts.old_mint.key(),
+        new_mint,
+        vault_amount,
+        fees_vault_amount,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(a_or_b: AorB)]
+pub struct MigrateMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: checked in the handler
+    #[account(mut,
+        mint::token_program = old_token_program,
+    )]
+    pub old_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        constraint = new_mint.decimals == old_mint.decimals @ SwapError::InvalidMintMigration,
+        mint::token_program = new_token_program,
+    )]
+    pub new_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = old_token_program,
+    )]
+    pub old_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = old_token_program,
+    )]
+    pub old_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::vault(a_or_b), pool.key().as_ref(), new_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = new_mint,
+        token::authority = pool_authority,
+        token::token_program = new_token_program,
+    )]
+    pub new_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::fees_vault(a_or_b), pool.key().as_ref(), new_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = new_mint,
+        token::authority = pool_authority,
+        token::token_program = new_token_program,
+    )]
+    pub new_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler, the vault of the mint which is not migrated
+    pub other_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler, the fees vault of the mint which is not migrated
+    pub other_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: program converting the old tokens into the successor mint, checked in the handler
+    /// against the conversion programs allowed by the program admin in the feature set
+    #[account(
+        executable,
+        constraint = conversion_program.key() != crate::ID @ SwapError::InvalidMintMigration,
+    )]
+    pub conversion_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [seeds::FEATURE_SET],
+        bump,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+
+    /// Token program of the old mint
+    pub old_token_program: Interface<'info, TokenInterface>,
+    /// Token program of the successor mint
+    pub new_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(
+        ctx: &Context<MigrateMint>,
+        pool: &Ref<SwapPool>,
+        a_or_b: AorB,
+    ) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        let (mint, vault, fees_vault, other_mint, other_vault, other_fees_vault) = match a_or_b {
+            AorB::A => (
+                pool.token_a_mint,
+                pool.token_a_vault,
+                pool.token_a_fees_vault,
+                pool.token_b_mint,
+                pool.token_b_vault,
+                pool.token_b_fees_vault,
+            ),
+            AorB::B => (
+                pool.token_b_mint,
+                pool.token_b_vault,
+                pool.token_b_fees_vault,
+                pool.token_a_mint,
+                pool.token_a_vault,
+                pool.token_a_fees_vault,
+            ),
+        };
+        require_msg!(
+            mint == ctx.accounts.old_mint.key(),
+            SwapError::IncorrectTradingMint,
+            &format!(
+                "IncorrectTradingMint: old_mint.key ({}) != pool mint ({}), a_or_b={:?}",
+                ctx.accounts.old_mint.key(),
+                mint,
+                a_or_b
+            )
+        );
+        require_msg!(
+            vault == ctx.accounts.old_vault.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: old_vault.key ({}) != pool vault ({}), a_or_b={:?}",
+                ctx.accounts.old_vault.key(),
+                vault,
+                a_or_b
+            )
+        );
+        require_msg!(
+            fees_vault == ctx.accounts.old_fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: old_fees_vault.key ({}) != pool fees vault ({}), a_or_b={:?}",
+                ctx.accounts.old_fees_vault.key(),
+                fees_vault,
+                a_or_b
+            )
+        );
+        require_msg!(
+            other_vault == ctx.accounts.other_vault.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: other_vault.key ({}) != pool vault ({}), a_or_b={:?}",
+                ctx.accounts.other_vault.key(),
+                other_vault,
+                a_or_b
+            )
+        );
+        require_msg!(
+            other_fees_vault == ctx.accounts.other_fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: other_fees_vault.key ({}) != pool fees vault ({}), a_or_b={:?}",
+                ctx.accounts.other_fees_vault.key(),
+                other_fees_vault,
+                a_or_b
+            )
+        );
+        require_msg!(
+            ctx.accounts.new_mint.key() != mint && ctx.accounts.new_mint.key() != other_mint,
+            SwapError::RepeatedMint,
+            &format!(
+                "RepeatedMint: new_mint ({}) is already traded by the pool",
+                ctx.accounts.new_mint.key()
+            )
+        );
+        if let Some(swap_constraints) = &SWAP_CONSTRAINTS {
+            swap_constraints.validate_token_2022_trading_token_extensions(
+                &ctx.accounts.new_mint.to_account_info(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Invoke the conversion program to move `amount` out of a vault of the old mint into the
+    /// vault of the successor mint
+    ///
+    /// The conversion program receives the accounts `[source vault, old mint, destination vault,
+    /// new mint, pool authority (signer), old token program, new token program]` followed by the
+    /// remaining accounts, and the instruction data `conversion_data` followed by the amount as a
+    /// little-endian u64. The remaining accounts cannot be the pool or any token account or mint
+    /// the pool authority signature has power over.
+    pub fn convert<'info>(
+        ctx: &Context<'_, '_, '_, 'info, MigrateMint<'info>>,
+        source: &InterfaceAccount<'info, TokenAccount>,
+        destination: &InterfaceAccount<'info, TokenAccount>,
+        conversion_data: &[u8],
+        amount: u64,
+        pool_authority_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let mut accounts = vec![
+            AccountMeta::new(source.key(), false),
+            AccountMeta::new(ctx.accounts.old_mint.key(), false),
+            AccountMeta::new(destination.key(), false),
+            AccountMeta::new(ctx.accounts.new_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.old_token_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.new_token_program.key(), false),
+        ];
+        let mut account_infos = vec![
+            source.to_account_info(),
+            ctx.accounts.old_mint.to_account_info(),
+            destination.to_account_info(),
+            ctx.accounts.new_mint.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            ctx.accounts.old_token_program.to_account_info(),
+            ctx.accounts.new_token_program.to_account_info(),
+        ];
+        for account in ctx.remaining_accounts {
+            require_not_pool_controlled(
+                account,
+                &ctx.accounts.pool.key(),
+                &ctx.accounts.pool_authority.key(),
+            )?;
+            accounts.push(AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            });
+            account_infos.push(account.clone());
+        }
+        account_infos.push(ctx.accounts.conversion_program.to_account_info());
+
+        let mut data = conversion_data.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        let ix = Instruction {
+            program_id: ctx.accounts.conversion_program.key(),
+            accounts,
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &account_infos,
+            &[&[
+                seeds::POOL_AUTHORITY,
+                ctx.accounts.pool.key().as_ref(),
+                &[pool_authority_bump],
+            ]],
+        )?;
+        Ok(())
+    }
+    /// Fail if a conversion account is the pool, or a token account or mint the pool authority
+    /// owns, is a delegate or authority of, which the conversion could move, mint or burn
+    fn require_not_pool_controlled(
+        account: &AccountInfo,
+        pool: &Pubkey,
+        pool_authority: &Pubkey,
+    ) -> Result<()> {
+        let authority = COption::Some(*pool_authority);
+        let controlled = if account.key == pool {
+            true
+        } else if account.owner == &anchor_spl::token::ID
+            || account.owner == &anchor_spl::token_2022::ID
+        {
+            let data = account.try_borrow_data()?;
+            if let Ok(token_account) =
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+            {
+                token_account.base.owner == *pool_authority
+                    || token_account.base.delegate == authority
+                    || token_account.base.close_authority == authority
+            } else if let Ok(mint) =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+            {
+                mint.base.mint_authority == authority || mint.base.freeze_authority == authority
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+        require_msg!(
+            !controlled,
+            SwapError::InvalidConversionProgram,
+            &format!(
+                "InvalidConversionProgram: conversion account {} is controlled by the pool",
+                account.key
+            )
+        );
+        Ok(())
+    }
+}
@@ -1,16 +1,128 @@
+pub mod accept_admin;
+pub mod add_liquidity_provider;
+pub mod add_swap_taker;
+pub mod assert_pool_price;
+pub mod claim_default_host_fees;
+pub mod claim_lp_airdrop;
+pub mod claim_withdraw;
+pub mod close_lp_airdrop;
+pub mod close_pool;
+pub mod create_lp_airdrop;
 pub mod deposit;
+pub mod deposit_both_exact_in;
+pub mod deposit_exact_amounts;
+pub mod deposit_single;
+pub mod donate_to_pool;
+pub mod emit_event;
+pub mod execute_pool_config;
+pub mod extend_pool;
+pub mod flash_borrow;
+pub mod flash_repay;
+pub mod guardian_pause_pool;
+pub mod initialize_config_history;
+pub mod initialize_default_host_fees;
+pub mod initialize_feature_set;
+pub mod initialize_fee_tier_registry;
 pub mod initialize_pool;
+pub mod initialize_pool_with_fee_tier;
+pub mod initialize_protocol_fee_config;
+pub mod initialize_protocol_fee_vault;
+#[cfg(feature = "dev-mode")]
+pub mod initialize_test_pool;
+pub mod initialize_user_stats;
+pub mod lock_liquidity;
+pub mod migrate_mint;
+pub mod propose_admin;
+pub mod propose_pool_config;
+pub mod rebalance_across_pools;
+pub mod refresh_price;
+pub mod register_lp_airdrop;
+pub mod remove_liquidity_provider;
+pub mod remove_swap_taker;
+pub mod request_withdraw;
+pub mod revoke_vault_delegates;
+pub mod set_conversion_program;
+pub mod set_fee_tier;
+pub mod set_guardian;
+pub mod skim_lamports;
 pub mod swap;
+pub mod swap_batch;
+pub mod swap_route_two_hop;
+pub mod sweep_protocol_fees;
+pub mod unlock_liquidity;
+pub mod update_default_host_fees;
+pub mod update_feature_set;
 pub mod update_pool_config;
 pub mod withdraw;
+pub mod withdraw_exact_amounts_out;
 pub mod withdraw_fees;
+pub mod withdraw_protocol_fees;
+pub mod withdraw_shared_protocol_fees;
+pub mod withdraw_single;
+pub mod write_curve_checksum;
 
 #[cfg(test)]
 pub mod test;
 
+pub use accept_admin::*;
+pub use add_liquidity_provider::*;
+pub use add_swap_taker::*;
+pub use assert_pool_price::*;
+pub use claim_default_host_fees::*;
+pub use claim_lp_airdrop::*;
+pub use claim_withdraw::*;
+pub use close_lp_airdrop::*;
+pub use close_pool::*;
+pub use create_lp_airdrop::*;
 pub use deposit::*;
+pub use deposit_both_exact_in::*;
+pub use deposit_exact_amounts::*;
+pub use deposit_single::*;
+pub use donate_to_pool::*;
+pub use emit_event::*;
+pub use execute_pool_config::*;
+pub use extend_pool::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use guardian_pause_pool::*;
+pub use initialize_config_history::*;
+pub use initialize_default_host_fees::*;
+pub use initialize_feature_set::*;
+pub use initialize_fee_tier_registry::*;
 pub use initialize_pool::*;
+pub use initialize_pool_with_fee_tier::*;
+pub use initialize_protocol_fee_config::*;
+pub use initialize_protocol_fee_vault::*;
+#[cfg(feature = "dev-mode")]
+pub use initialize_test_pool::*;
+pub use initialize_user_stats::*;
+pub use lock_liquidity::*;
+pub use migrate_mint::*;
+pub use propose_admin::*;
+pub use propose_pool_config::*;
+pub use rebalance_across_pools::*;
+pub use refresh_price::*;
+pub use register_lp_airdrop::*;
+pub use remove_liquidity_provider::*;
+pub use remove_swap_taker::*;
+pub use request_withdraw::*;
+pub use revoke_vault_delegates::*;
+pub use set_conversion_program::*;
+pub use set_fee_tier::*;
+pub use set_guardian::*;
+pub use skim_lamports::*;
 pub use swap::*;
+pub use swap_batch::*;
+pub use swap_route_two_hop::*;
+pub use sweep_protocol_fees::*;
+pub use unlock_liquidity::*;
+pub use update_default_host_fees::*;
+pub use update_feature_set::*;
 pub use update_pool_config::*;
 pub use withdraw::*;
+pub use withdraw_exact_amounts_out::*;
 pub use withdraw_fees::*;
+pub use withdraw_protocol_fees::*;
+pub use withdraw_shared_protocol_fees::*;
+pub use withdraw_single::*;
+pub use write_curve_checksum::*;
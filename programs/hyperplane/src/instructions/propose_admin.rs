@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{emitted, event, state::SwapPool};
+
+/// Propose a new admin of the pool, which takes over once it signs an `accept_admin`
+///
+/// The current admin keeps its rights until then. Proposing again replaces the pending admin, and
+/// proposing the default pubkey cancels the transfer.
+pub fn handler(ctx: Context<ProposeAdmin>, pending_admin: Pubkey) -> Result<event::ProposeAdmin> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    msg!(
+        "Proposing pool admin: admin={}, pending_admin={} -> {}",
+        pool.admin,
+        pool.pending_admin,
+        pending_admin
+    );
+    pool.pending_admin = pending_admin;
+
+    emitted!(event::ProposeAdmin { pending_admin });
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{ProposedConfigChange, SwapPool},
+    try_math,
+    update_pool_config::{utils::parse_config, VALUE_BYTE_ARRAY_LEN},
+    utils::{math::TryMath, seeds},
+};
+
+/// Propose a pool config change, executable once `approval_window_slots` have passed
+pub fn handler(
+    ctx: Context<ProposePoolConfig>,
+    mode: u16,
+    value: &[u8; VALUE_BYTE_ARRAY_LEN],
+    approval_window_slots: u64,
+) -> Result<event::ProposePoolConfig> {
+    // reject changes that could never be executed
+    let (mode, parsed_value) = parse_config(mode, value)?;
+
+    let proposed_slot = Clock::get()?.slot;
+    let executable_slot = try_math!(proposed_slot.try_add(approval_window_slots))?;
+
+    let proposed_config_change = &mut ctx.accounts.proposed_config_change;
+    proposed_config_change.pool = ctx.accounts.pool.key();
+    proposed_config_change.mode = mode as u16;
+    proposed_config_change.value = *value;
+    proposed_config_change.proposed_slot = proposed_slot;
+    proposed_config_change.executable_slot = executable_slot;
+
+    msg!(
+        "Proposed pool config change: mode={:?}, value={:?}, proposed_slot={}, executable_slot={}",
+        mode,
+        parsed_value,
+        proposed_slot,
+        executable_slot,
+    );
+
+    emitted!(event::ProposePoolConfig {
+        mode,
+        value: parsed_value,
+        executable_slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ProposePoolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(init,
+        seeds = [seeds::PROPOSED_CONFIG_CHANGE, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = ProposedConfigChange::LEN,
+    )]
+    pub proposed_config_change: Box<Account<'info, ProposedConfigChange>>,
+
+    pub system_program: Program<'info, System>,
+}
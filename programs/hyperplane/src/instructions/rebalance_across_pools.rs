@@ -0,0 +1,478 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve,
+    curve::{
+        base::SwapCurve,
+        calculator::{AorB, RoundDirection},
+    },
+    emitted,
+    error::SwapError,
+    event,
+    rebalance_across_pools::utils::validate_inputs,
+    require_msg,
+    state::{PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math::TryMath, pool_token, swap_token},
+    withdraw::utils::sub_withdraw_fee,
+};
+
+/// Moves liquidity from the source pool to the destination pool of the same pair.
+///
+/// The admin's source pool tokens are redeemed straight into the destination pool vaults, any
+/// trading tokens left over after minting `destination_pool_token_amount` are sent to the admin.
+/// The source pool charges its owner withdrawal fee like `withdraw`, and the destination pool
+/// takes the same status checks as `deposit`. The transfer fees of the trading token mints are
+/// paid out of the withdrawn tokens, so the destination vaults receive the full deposit.
+pub fn handler(
+    ctx: Context<RebalanceAcrossPools>,
+    source_pool_token_amount: u64,
+    destination_pool_token_amount: u64,
+) -> Result<event::RebalanceAcrossPools> {
+    let source_pool = ctx.accounts.source_pool.load()?;
+    let destination_pool = ctx.accounts.destination_pool.load()?;
+    validate_inputs(&ctx, &source_pool, &destination_pool)?;
+    msg!(
+        "Rebalance inputs: source_pool_token_amount={}, destination_pool_token_amount={}",
+        source_pool_token_amount,
+        destination_pool_token_amount,
+    );
+
+    let source_curve = curve!(ctx.accounts.source_swap_curve, source_pool);
+    let destination_curve = curve!(ctx.accounts.destination_swap_curve, destination_pool);
+    require!(
+        destination_curve.calculator.allows_deposits(),
+        SwapError::UnsupportedCurveOperation
+    );
+    require_msg!(
+        source_pool_token_amount > 0 && destination_pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: pool token amounts must be greater than 0"
+    );
+
+    let withdrawn = source_curve
+        .calculator
+        .pool_tokens_to_trading_tokens(
+            u128::from(source_pool_token_amount),
+            u128::from(ctx.accounts.source_pool_token_mint.supply),
+            u128::from(ctx.accounts.source_token_a_vault.amount),
+            u128::from(ctx.accounts.source_token_b_vault.amount),
+            RoundDirection::Floor,
+        )
+        .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+    let deposited = destination_curve
+        .calculator
+        .pool_tokens_to_trading_tokens(
+            u128::from(destination_pool_token_amount),
+            u128::from(ctx.accounts.destination_pool_token_mint.supply),
+            u128::from(ctx.accounts.destination_token_a_vault.amount),
+            u128::from(ctx.accounts.destination_token_b_vault.amount),
+            RoundDirection::Ceiling,
+        )
+        .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+    let (token_a_withdrawn, token_a_fees) = sub_withdraw_fee(
+        &source_pool,
+        ctx.accounts.source_token_a_vault.amount,
+        withdrawn.token_a_amount,
+        0,
+        AorB::A,
+    )?;
+    let (token_b_withdrawn, token_b_fees) = sub_withdraw_fee(
+        &source_pool,
+        ctx.accounts.source_token_b_vault.amount,
+        withdrawn.token_b_amount,
+        0,
+        AorB::B,
+    )?;
+    let token_a_deposited = to_u64!(deposited.token_a_amount)?;
+    let token_b_deposited = to_u64!(deposited.token_b_amount)?;
+    let token_a_sent = swap_token::add_inverse_transfer_fee(
+        &ctx.accounts.token_a_mint.to_account_info(),
+        token_a_deposited,
+    )?;
+    let token_b_sent = swap_token::add_inverse_transfer_fee(
+        &ctx.accounts.token_b_mint.to_account_info(),
+        token_b_deposited,
+    )?;
+
+    msg!(
+        "Rebalance outputs: token_a_withdrawn={}, token_b_withdrawn={}, token_a_fees={}, token_b_fees={}, token_a_deposited={}, token_b_deposited={}, token_a_sent={}, token_b_sent={}",
+        token_a_withdrawn,
+        token_b_withdrawn,
+        token_a_fees,
+        token_b_fees,
+        token_a_deposited,
+        token_b_deposited,
+        token_a_sent,
+        token_b_sent,
+    );
+    let token_a_remaining = utils::sub_deposited(token_a_withdrawn, token_a_sent, AorB::A)?;
+    let token_b_remaining = utils::sub_deposited(token_b_withdrawn, token_b_sent, AorB::B)?;
+
+    let source_token_a_vault_amount_after = try_math!(ctx
+        .accounts
+        .source_token_a_vault
+        .amount
+        .try_sub(try_math!(token_a_withdrawn.try_add(token_a_fees))?))?;
+    let source_token_b_vault_amount_after = try_math!(ctx
+        .accounts
+        .source_token_b_vault
+        .amount
+        .try_sub(try_math!(token_b_withdrawn.try_add(token_b_fees))?))?;
+    let destination_token_a_vault_amount_after = try_math!(ctx
+        .accounts
+        .destination_token_a_vault
+        .amount
+        .try_add(token_a_deposited))?;
+    let destination_token_b_vault_amount_after = try_math!(ctx
+        .accounts
+        .destination_token_b_vault
+        .amount
+        .try_add(token_b_deposited))?;
+
+    pool_token::burn(
+        ctx.accounts.source_pool_token_mint.to_account_info(),
+        ctx.accounts.admin_source_pool_token_ata.to_account_info(),
+        ctx.accounts.admin.to_account_info(),
+        ctx.accounts.pool_token_program.to_account_info(),
+        source_pool_token_amount,
+    )?;
+
+    for (vault, mint, destination, token_program, amount) in [
+        (
+            &ctx.accounts.source_token_a_vault,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.destination_token_a_vault.to_account_info(),
+            &ctx.accounts.token_a_token_program,
+            token_a_sent,
+        ),
+        (
+            &ctx.accounts.source_token_b_vault,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.destination_token_b_vault.to_account_info(),
+            &ctx.accounts.token_b_token_program,
+            token_b_sent,
+        ),
+        (
+            &ctx.accounts.source_token_a_vault,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.admin_token_a_ata.to_account_info(),
+            &ctx.accounts.token_a_token_program,
+            token_a_remaining,
+        ),
+        (
+            &ctx.accounts.source_token_b_vault,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.admin_token_b_ata.to_account_info(),
+            &ctx.accounts.token_b_token_program,
+            token_b_remaining,
+        ),
+        (
+            &ctx.accounts.source_token_a_vault,
+            &ctx.accounts.token_a_mint,
+            ctx.accounts.source_token_a_fees_vault.to_account_info(),
+            &ctx.accounts.token_a_token_program,
+            token_a_fees,
+        ),
+        (
+            &ctx.accounts.source_token_b_vault,
+            &ctx.accounts.token_b_mint,
+            ctx.accounts.source_token_b_fees_vault.to_account_info(),
+            &ctx.accounts.token_b_token_program,
+            token_b_fees,
+        ),
+    ] {
+        if amount > 0 {
+            swap_token::transfer_from_vault(
+                token_program.to_account_info(),
+                ctx.accounts.source_pool.to_account_info(),
+                vault.to_account_info(),
+                mint.to_account_info(),
+                destination,
+                ctx.accounts.source_pool_authority.to_account_info(),
+                source_pool.bump_seed(),
+                amount,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    // the destination pool tokens are only minted against what its vaults actually received
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.source_token_a_vault,
+        source_token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.source_token_b_vault,
+        source_token_b_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.destination_token_a_vault,
+        destination_token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.destination_token_b_vault,
+        destination_token_b_vault_amount_after,
+    )?;
+
+    pool_token::mint(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.destination_pool.to_account_info(),
+        ctx.accounts.destination_pool_token_mint.to_account_info(),
+        ctx.accounts.destination_pool_authority.to_account_info(),
+        destination_pool.bump_seed(),
+        ctx.accounts
+            .admin_destination_pool_token_ata
+            .to_account_info(),
+        destination_pool_token_amount,
+    )?;
+
+    emitted!(event::RebalanceAcrossPools {
+        source_pool_token_amount,
+        destination_pool_token_amount,
+        token_a_amount: token_a_deposited,
+        token_b_amount: token_b_deposited,
+        token_a_remaining,
+        token_b_remaining,
+        token_a_fees,
+        token_b_fees,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(source_pool_token_amount: u64)]
+pub struct RebalanceAcrossPools<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+    )]
+    pub source_pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: checked in the handler
+    pub source_swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub source_pool_authority: AccountInfo<'info>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Source pool account collecting the token A withdrawal fee
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Source pool account collecting the token B withdrawal fee
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub source_token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        constraint = destination_pool.key() != source_pool.key() @ SwapError::IncorrectSwapAccount,
+    )]
+    pub destination_pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: checked in the handler
+    pub destination_swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub destination_pool_authority: AccountInfo<'info>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub destination_token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub destination_token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub destination_pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pools
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pools
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Admin's token A account to receive any left over token A
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::authority = admin,
+        token::token_program = token_a_token_program,
+    )]
+    pub admin_token_a_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's token B account to receive any left over token B
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = admin,
+        token::token_program = token_b_token_program,
+    )]
+    pub admin_token_b_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's source pool token account to burn from
+    #[account(mut,
+        constraint = admin_source_pool_token_ata.amount >= source_pool_token_amount @ SwapError::InsufficientPoolTokenFunds,
+        token::mint = source_pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_source_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's destination pool token account to mint to
+    #[account(mut,
+        token::mint = destination_pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_destination_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mints
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(
+        ctx: &Context<RebalanceAcrossPools>,
+        source_pool: &Ref<SwapPool>,
+        destination_pool: &Ref<SwapPool>,
+    ) -> Result<()> {
+        require_msg!(
+            !destination_pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The destination pool is in withdrawals only mode"
+        );
+        destination_pool.require_not_paused(PoolOperation::Deposits)?;
+        require_msg!(
+            !source_pool.flash_loan_active() && !destination_pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the source or destination pool is ongoing"
+        );
+        require_msg!(
+            source_pool.token_a_fees_vault == ctx.accounts.source_token_a_fees_vault.key()
+                && source_pool.token_b_fees_vault == ctx.accounts.source_token_b_fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: source fees vaults ({}, {}) != pool fees vaults ({}, {})",
+                ctx.accounts.source_token_a_fees_vault.key(),
+                ctx.accounts.source_token_b_fees_vault.key(),
+                source_pool.token_a_fees_vault,
+                source_pool.token_b_fees_vault
+            )
+        );
+        for (
+            pool,
+            swap_curve,
+            pool_authority,
+            token_a_vault,
+            token_b_vault,
+            pool_token_mint,
+            side,
+        ) in [
+            (
+                source_pool,
+                ctx.accounts.source_swap_curve.key(),
+                ctx.accounts.source_pool_authority.key(),
+                ctx.accounts.source_token_a_vault.key(),
+                ctx.accounts.source_token_b_vault.key(),
+                ctx.accounts.source_pool_token_mint.key(),
+                "source",
+            ),
+            (
+                destination_pool,
+                ctx.accounts.destination_swap_curve.key(),
+                ctx.accounts.destination_pool_authority.key(),
+                ctx.accounts.destination_token_a_vault.key(),
+                ctx.accounts.destination_token_b_vault.key(),
+                ctx.accounts.destination_pool_token_mint.key(),
+                "destination",
+            ),
+        ] {
+            require_msg!(
+                pool.swap_curve == swap_curve,
+                SwapError::IncorrectSwapAccount,
+                &format!(
+                    "IncorrectSwapAccount: {} swap_curve.key ({}) != pool.swap_curve ({})",
+                    side, swap_curve, pool.swap_curve
+                )
+            );
+            require_msg!(
+                pool.pool_authority == pool_authority,
+                SwapError::InvalidProgramAddress,
+                &format!(
+                    "InvalidProgramAddress: {} pool_authority.key ({}) != pool.pool_authority ({})",
+                    side, pool_authority, pool.pool_authority
+                )
+            );
+            require_msg!(
+                pool.token_a_vault == token_a_vault && pool.token_b_vault == token_b_vault,
+                SwapError::IncorrectSwapAccount,
+                &format!(
+                    "IncorrectSwapAccount: {} vaults ({}, {}) != pool vaults ({}, {})",
+                    side, token_a_vault, token_b_vault, pool.token_a_vault, pool.token_b_vault
+                )
+            );
+            require_msg!(
+                pool.pool_token_mint == pool_token_mint,
+                SwapError::IncorrectPoolMint,
+                &format!(
+                    "IncorrectPoolMint: {} pool_token_mint.key ({}) != pool.pool_token_mint ({})",
+                    side, pool_token_mint, pool.pool_token_mint
+                )
+            );
+        }
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.source_pool_token_mint.to_account_info(),
+            &source_pool.pool_authority,
+        )?;
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.destination_pool_token_mint.to_account_info(),
+            &destination_pool.pool_authority,
+        )?;
+        Ok(())
+    }
+
+    /// Trading tokens withdrawn from the source pool left over after depositing into the destination pool
+    pub fn sub_deposited(withdrawn: u64, deposited: u64, a_or_b: AorB) -> Result<u64> {
+        require_msg!(
+            deposited <= withdrawn,
+            SwapError::ExceededSlippage,
+            &format!(
+                "ExceededSlippage: token={:?} deposited={} > withdrawn={}",
+                a_or_b, deposited, withdrawn
+            )
+        );
+        try_math!(withdrawn.try_sub(deposited))
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    curve::{base::CurveType, oracle_pegged},
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{Curve, OraclePeggedCurve, SwapPool, SwapState},
+    utils::instructions::deserialize_curve,
+};
+
+/// Cache the price of the oracle of an oracle pegged pool in its curve
+///
+/// Anyone can crank the price. The swaps which are not passed the oracle account then swap at the
+/// cached price, and fail with `StaleOracle` once it is older than `max_age_slots` like a price
+/// read from the oracle. The curve checksum, if the pool has one, is rewritten over the new price.
+pub fn handler(ctx: Context<RefreshPrice>) -> Result<event::RefreshPrice> {
+    let pool = ctx.accounts.pool.load()?;
+    require_msg!(
+        pool.curve_type() == CurveType::OraclePegged,
+        SwapError::UnsupportedCurveOperation,
+        &format!(
+            "UnsupportedCurveOperation: {:?} curves have no oracle price to refresh",
+            pool.curve_type()
+        )
+    );
+
+    let mut curve = deserialize_curve::<OraclePeggedCurve>(
+        &ctx.accounts.swap_curve,
+        pool.curve_checksum_written(),
+    )?;
+    let price = oracle_pegged::read_oracle_price(&curve, &ctx.accounts.oracle, Clock::get()?.slot)?;
+    curve.cached_price_value = price.value;
+    curve.cached_price_exponent = price.exponent.into();
+    curve.cached_price_slot = price.slot;
+
+    {
+        let mut data = ctx.accounts.swap_curve.try_borrow_mut_data()?;
+        let dst: &mut [u8] = &mut data;
+        let mut cursor = std::io::Cursor::new(dst);
+        curve.try_serialize(&mut cursor)?;
+        if pool.curve_checksum_written() {
+            Curve::write_checksum(&mut data);
+        }
+    }
+
+    msg!(
+        "Refreshed price: value={}, exponent={}, slot={}",
+        price.value,
+        price.exponent,
+        price.slot
+    );
+
+    emitted!(event::RefreshPrice {
+        value: price.value,
+        exponent: price.exponent,
+        slot: price.slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct RefreshPrice<'info> {
+    #[account(has_one = swap_curve)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool, deserialized as an oracle pegged curve
+    #[account(mut)]
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the oracle of the curve, read with its `oracle_type`
+    pub oracle: UncheckedAccount<'info>,
+}
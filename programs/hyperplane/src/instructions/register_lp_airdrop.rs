@@ -0,0 +1,106 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpAirdrop, LpAirdropPosition, SwapPool},
+    try_math,
+    utils::{math::TryMath, seeds, swap_token},
+};
+
+/// Lock pool tokens in an airdrop until its snapshot slot to receive a share of the reward
+pub fn handler(
+    ctx: Context<RegisterLpAirdrop>,
+    pool_token_amount: u64,
+) -> Result<event::RegisterLpAirdrop> {
+    msg!(
+        "Register LP airdrop inputs: pool_token_amount={}",
+        pool_token_amount,
+    );
+    require_msg!(
+        pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: pool_token_amount=0"
+    );
+    let current_slot = Clock::get()?.slot;
+    let snapshot_slot = ctx.accounts.airdrop.snapshot_slot;
+    require_msg!(
+        current_slot <= snapshot_slot,
+        SwapError::AirdropRegistrationClosed,
+        &format!(
+            "AirdropRegistrationClosed: current_slot={} > snapshot_slot={}",
+            current_slot, snapshot_slot
+        )
+    );
+
+    swap_token::transfer_from_user(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        pool_token_amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    position.airdrop = ctx.accounts.airdrop.key();
+    position.owner = ctx.accounts.owner.key();
+    position.pool_token_amount = pool_token_amount;
+
+    let airdrop = &mut ctx.accounts.airdrop;
+    airdrop.registered_pool_tokens =
+        try_math!(airdrop.registered_pool_tokens.try_add(pool_token_amount))?;
+
+    emitted!(event::RegisterLpAirdrop {
+        pool_token_amount,
+        total_registered_pool_tokens: airdrop.registered_pool_tokens,
+    });
+}
+
+#[derive(Accounts)]
+pub struct RegisterLpAirdrop<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(has_one = pool_token_mint @ SwapError::IncorrectPoolMint)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        has_one = pool,
+        has_one = pool_token_vault,
+    )]
+    pub airdrop: Box<Account<'info, LpAirdrop>>,
+
+    /// CHECK: has_one constraint on the airdrop
+    #[account(mut)]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::LP_AIRDROP_POSITION, airdrop.key().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = LpAirdropPosition::LEN,
+    )]
+    pub position: Box<Account<'info, LpAirdropPosition>>,
+
+    /// Owner's pool token account to register the pool tokens from
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{AllowedLiquidityProvider, SwapPool},
+};
+
+/// Remove a provider from the deposit allow-list, the pool tokens it already holds can still be
+/// withdrawn
+pub fn handler(ctx: Context<RemoveLiquidityProvider>) -> Result<event::RemoveLiquidityProvider> {
+    let provider = ctx.accounts.allowed_liquidity_provider.provider;
+
+    msg!(
+        "Removed allowed liquidity provider: pool={}, provider={}",
+        ctx.accounts.pool.key(),
+        provider,
+    );
+
+    emitted!(event::RemoveLiquidityProvider { provider });
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityProvider<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        has_one = pool,
+        close = admin,
+    )]
+    pub allowed_liquidity_provider: Box<Account<'info, AllowedLiquidityProvider>>,
+}
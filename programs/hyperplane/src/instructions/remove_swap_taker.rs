@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{AllowedSwapTaker, SwapPool},
+};
+
+pub fn handler(ctx: Context<RemoveSwapTaker>) -> Result<event::RemoveSwapTaker> {
+    let taker = ctx.accounts.allowed_swap_taker.taker;
+
+    msg!(
+        "Removed allowed swap taker: pool={}, taker={}",
+        ctx.accounts.pool.key(),
+        taker,
+    );
+
+    emitted!(event::RemoveSwapTaker { taker });
+}
+
+#[derive(Accounts)]
+pub struct RemoveSwapTaker<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        has_one = pool,
+        close = admin,
+    )]
+    pub allowed_swap_taker: Box<Account<'info, AllowedSwapTaker>>,
+}
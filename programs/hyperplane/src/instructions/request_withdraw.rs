@@ -0,0 +1,105 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{SwapPool, WithdrawalTicket},
+    utils::{seeds, swap_token},
+};
+
+/// Queue a withdrawal to be claimed once the pool can pay out the minimum amounts
+///
+/// The pool tokens are moved to a vault owned by the ticket until it is claimed.
+pub fn handler(
+    ctx: Context<RequestWithdraw>,
+    pool_token_amount: u64,
+    minimum_token_a_amount: u64,
+    minimum_token_b_amount: u64,
+) -> Result<event::RequestWithdraw> {
+    msg!(
+        "Request withdraw inputs: pool_token_amount={}, minimum_token_a_amount={}, minimum_token_b_amount={}",
+        pool_token_amount,
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+    );
+    require_msg!(
+        pool_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: pool_token_amount=0"
+    );
+
+    swap_token::transfer_from_user(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        pool_token_amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    let withdrawal_ticket = &mut ctx.accounts.withdrawal_ticket;
+    withdrawal_ticket.pool = ctx.accounts.pool.key();
+    withdrawal_ticket.owner = ctx.accounts.owner.key();
+    withdrawal_ticket.pool_token_amount = pool_token_amount;
+    withdrawal_ticket.minimum_token_a_amount = minimum_token_a_amount;
+    withdrawal_ticket.minimum_token_b_amount = minimum_token_b_amount;
+    withdrawal_ticket.pool_token_vault = ctx.accounts.pool_token_vault.key();
+    withdrawal_ticket.bump = u64::from(*ctx.bumps.get("withdrawal_ticket").unwrap());
+
+    emitted!(event::RequestWithdraw {
+        pool_token_amount,
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(pool_token_amount: u64)]
+pub struct RequestWithdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(has_one = pool_token_mint @ SwapError::IncorrectPoolMint)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Owner's pool token account
+    #[account(mut,
+        constraint = pool_token_user_ata.amount >= pool_token_amount @ SwapError::InsufficientPoolTokenFunds,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [seeds::WITHDRAWAL_TICKET, pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = WithdrawalTicket::LEN,
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+
+    /// Holds the pool tokens of the ticket until it is claimed
+    #[account(init,
+        seeds = [seeds::WITHDRAWAL_TICKET_POOL_TOKEN_VAULT, withdrawal_ticket.key().as_ref()],
+        bump,
+        payer = owner,
+        token::mint = pool_token_mint,
+        token::authority = withdrawal_ticket,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,110 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event,
+    state::{SwapPool, SwapState},
+    utils::swap_token,
+};
+
+/// Clear any delegate or close authority found on the pool vaults
+///
+/// The vaults are owned by the pool authority so neither should ever be set, this is defense in
+/// depth against token program edge cases. Permissionless so anyone noticing one can act on it.
+pub fn handler(ctx: Context<RevokeVaultDelegates>) -> Result<event::RevokeVaultDelegates> {
+    let bump = ctx.accounts.pool.load()?.bump_seed();
+
+    let vaults = [
+        (
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_token_program,
+        ),
+        (
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_token_program,
+        ),
+        (
+            &ctx.accounts.token_a_fees_vault,
+            &ctx.accounts.token_a_token_program,
+        ),
+        (
+            &ctx.accounts.token_b_fees_vault,
+            &ctx.accounts.token_b_token_program,
+        ),
+    ];
+    let mut revoked_vaults = Vec::new();
+    for (vault, token_program) in vaults {
+        if swap_token::revoke_vault_delegates(
+            token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            vault,
+            ctx.accounts.pool_authority.to_account_info(),
+            bump,
+        )? {
+            revoked_vaults.push(vault.key());
+        }
+    }
+
+    if !revoked_vaults.is_empty() {
+        msg!(
+            "ALERT: revoked delegates of {} pool vaults",
+            revoked_vaults.len()
+        );
+    }
+
+    emitted!(event::RevokeVaultDelegates {
+        revoked_vaults,
+        slot: Clock::get()?.slot,
+    });
+}
+
+#[derive(Accounts)]
+pub struct RevokeVaultDelegates<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the trading token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the trading token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{emitted, event, state::FeatureSet, utils::seeds};
+
+/// Allow a conversion program for `migrate_mint` in slot `index` of the feature set, replacing
+/// the program previously allowed there
+///
+/// `migrate_mint` invokes the conversion program with the pool authority signature, so only
+/// programs trusted by the program admin can be set. The default pubkey clears the slot.
+pub fn handler(
+    ctx: Context<SetConversionProgram>,
+    index: u16,
+    program: Pubkey,
+) -> Result<event::SetConversionProgram> {
+    msg!("Setting conversion program {} -> {}", index, program);
+    ctx.accounts
+        .feature_set
+        .set_conversion_program(index, program)?;
+
+    emitted!(event::SetConversionProgram { index, program });
+}
+
+#[derive(Accounts)]
+pub struct SetConversionProgram<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        seeds = [seeds::FEATURE_SET],
+        bump,
+        has_one = admin,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{curve::fees::Fees, emitted, event, state::FeeTierRegistry, utils::seeds};
+
+/// Replace the fees of an existing tier, or append a new tier when `tier` is the next free index
+///
+/// Pools already created from a tier keep the fees they were created with.
+pub fn handler(ctx: Context<SetFeeTier>, tier: u16, fees: Fees) -> Result<event::SetFeeTier> {
+    fees.validate()?;
+
+    msg!("Setting fee tier {} -> {:?}", tier, fees);
+    ctx.accounts.fee_tier_registry.set_tier(tier, fees)?;
+
+    emitted!(event::SetFeeTier { tier, fees });
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTier<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        seeds = [seeds::FEE_TIER_REGISTRY],
+        bump,
+        has_one = admin,
+    )]
+    pub fee_tier_registry: Box<Account<'info, FeeTierRegistry>>,
+}
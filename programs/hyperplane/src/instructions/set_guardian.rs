@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{emitted, event, state::SwapPool};
+
+/// Set the guardian of the pool, which can pause it but not unpause it or change anything else
+///
+/// Setting the default pubkey removes the guardian.
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<event::SetGuardian> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    msg!(
+        "Setting pool guardian: admin={}, guardian={} -> {}",
+        pool.admin,
+        pool.guardian,
+        guardian
+    );
+    pool.guardian = guardian;
+
+    emitted!(event::SetGuardian { guardian });
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+}
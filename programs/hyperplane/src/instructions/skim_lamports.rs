@@ -0,0 +1,120 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    skim_lamports::utils::{excess_lamports, skim_program_owned},
+    state::{SwapPool, SwapState},
+    utils::seeds,
+};
+
+/// Sweep the lamports above rent exemption of the pool, swap curve and pool authority accounts
+///
+/// The vaults are owned by the token program, which has no instruction to withdraw their excess
+/// lamports, so they are left untouched.
+pub fn handler(ctx: Context<SkimLamports>) -> Result<event::SkimLamports> {
+    require_msg!(
+        !ctx.accounts.pool.load()?.flash_loan_active(),
+        SwapError::FlashLoanActive,
+        "A flash loan of the pool is ongoing"
+    );
+    let rent = Rent::get()?;
+    let treasury = ctx.accounts.treasury.to_account_info();
+
+    let pool_lamports = skim_program_owned(&ctx.accounts.pool.to_account_info(), &treasury, &rent)?;
+    let swap_curve_lamports = skim_program_owned(&ctx.accounts.swap_curve, &treasury, &rent)?;
+
+    // the pool authority is a system account, its lamports can only be moved by the system program
+    let pool_authority_lamports = excess_lamports(&ctx.accounts.pool_authority, &rent);
+    if pool_authority_lamports > 0 {
+        let pool_key = ctx.accounts.pool.key();
+        let pool_authority_bump = ctx.accounts.pool.load()?.bump_seed();
+        let inner_seeds = [
+            seeds::POOL_AUTHORITY,
+            pool_key.as_ref(),
+            &[pool_authority_bump],
+        ];
+        let signer_seeds = &[&inner_seeds[..]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.pool_authority.to_account_info(),
+                    to: treasury,
+                },
+                signer_seeds,
+            ),
+            pool_authority_lamports,
+        )?;
+    }
+
+    msg!(
+        "Skimmed lamports: pool={}, swap_curve={}, pool_authority={}",
+        pool_lamports,
+        swap_curve_lamports,
+        pool_authority_lamports
+    );
+
+    emitted!(event::SkimLamports {
+        treasury: ctx.accounts.treasury.key(),
+        pool_lamports,
+        swap_curve_lamports,
+        pool_authority_lamports,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SkimLamports<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// Account chosen by the admin to receive the skimmed lamports
+    /// CHECK: any account can receive lamports
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+mod utils {
+    use super::*;
+    use crate::{try_math, utils::math::TryMath};
+
+    /// Lamports of the account above the minimum balance for rent exemption of its data
+    pub fn excess_lamports(account: &AccountInfo, rent: &Rent) -> u64 {
+        account
+            .lamports()
+            .saturating_sub(rent.minimum_balance(account.data_len()))
+    }
+
+    /// Move the excess lamports of an account owned by this program to the treasury
+    pub fn skim_program_owned(
+        account: &AccountInfo,
+        treasury: &AccountInfo,
+        rent: &Rent,
+    ) -> Result<u64> {
+        let skimmed = excess_lamports(account, rent);
+        if skimmed > 0 {
+            let mut account_lamports = account.try_borrow_mut_lamports()?;
+            **account_lamports = try_math!(account_lamports.try_sub(skimmed))?;
+            let mut treasury_lamports = treasury.try_borrow_mut_lamports()?;
+            **treasury_lamports = try_math!(treasury_lamports.try_add(skimmed))?;
+        }
+        Ok(skimmed)
+    }
+}
@@ -11,37 +11,61 @@ use anchor_spl::{
 
 use crate::{
     curve,
-    curve::{base::SwapCurve, calculator::TradeDirection},
-    emitted,
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator,
+        calculator::{AorB, TradeDirection},
+        oracle_pegged,
+    },
     error::SwapError,
-    event, require_msg,
-    state::{SwapPool, SwapState},
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{AllowedSwapTaker, PoolOperation, SwapPool, SwapState, UserStats},
     swap::utils::validate_inputs,
     to_u64, try_math,
-    utils::{math::TryMath, swap_token},
+    utils::{events, instructions::require_before_deadline, math::TryMath, swap_token},
 };
 
-pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<event::Swap> {
+pub fn handler(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    deadline: Option<i64>,
+) -> Result<event::Swap> {
+    require_before_deadline(deadline)?;
     let pool = ctx.accounts.pool.load()?;
     let trade_direction = validate_inputs(&ctx, &pool)?;
-    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+    let self_trade = utils::is_self_trade(&ctx, &pool)?;
+    let slot = Clock::get()?.slot;
+    let swap_curve = if pool.curve_type() == CurveType::OraclePegged {
+        oracle_pegged::swap_curve_with_oracle(
+            &ctx.accounts.swap_curve,
+            ctx.accounts.oracle.as_ref().map(|oracle| oracle.as_ref()),
+            slot,
+            pool.curve_checksum_written(),
+        )?
+    } else {
+        curve!(ctx.accounts.swap_curve, pool)
+    };
+    let fees = pool.swap_fees(slot)?;
 
     // Take transfer fees into account for actual amount transferred in
     let actual_amount_in = utils::sub_input_transfer_fees(
         &ctx.accounts.source_mint.to_account_info(),
-        &pool.fees,
+        &fees,
         amount_in,
         ctx.accounts.source_token_host_fees_account.is_some(),
     )?;
 
-    msg!(
+    pool_msg!(
+        pool,
         "Swap inputs: trade_direction={:?}, amount_in={}, actual_amount_in={}, minimum_amount_out={}",
         trade_direction,
         amount_in,
         actual_amount_in,
         minimum_amount_out
     );
-    msg!(
+    pool_msg!(
+        pool,
         "Swap pool inputs: swap_type={:?}, source_token_balance={}, destination_token_balance={}",
         swap_curve.curve_type,
         ctx.accounts.source_vault.amount,
@@ -53,34 +77,65 @@ pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> R
             u128::from(ctx.accounts.source_vault.amount),
             u128::from(ctx.accounts.destination_vault.amount),
             trade_direction,
-            pool.fees(),
+            &fees,
         )
-        .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+        .map_err(|e| calculator::map_curve_error(e, SwapError::ZeroTradingTokens))?;
+
+    if swap_curve.curve_type == CurveType::ConstantProduct {
+        utils::validate_price_bounds(
+            &pool,
+            trade_direction,
+            result.new_pool_source_amount,
+            result.new_pool_destination_amount,
+        )?;
+    }
+    utils::validate_trade_size(
+        &pool,
+        trade_direction,
+        u128::from(ctx.accounts.source_vault.amount),
+        u128::from(ctx.accounts.destination_vault.amount),
+        result.new_pool_source_amount,
+        result.new_pool_destination_amount,
+    )?;
 
     // Re-calculate the source amount swapped based on what the curve says
     let source_amount_to_vault = to_u64!(result.source_amount_to_vault)?;
-    let source_amount_to_vault = utils::add_inverse_transfer_fee(
+    let source_amount_to_vault = swap_token::add_inverse_transfer_fee(
         &ctx.accounts.source_mint.to_account_info(),
         source_amount_to_vault,
     )?;
 
     let destination_amount_from_vault = to_u64!(result.destination_amount_swapped)?;
+    // Pools paying the host fee in destination tokens carve it out of the swapped amount
+    let destination_host_fee = if ctx.accounts.destination_token_host_fees_account.is_some() {
+        to_u64!(pool
+            .fees()
+            .destination_host_fee(result.destination_amount_swapped)
+            .map_err(|_| error!(SwapError::FeeCalculationFailure))?)?
+    } else {
+        0
+    };
+    let destination_amount_to_user =
+        try_math!(destination_amount_from_vault.try_sub(destination_host_fee))?;
     let destination_amount_post_transfer_fees = utils::sub_transfer_fee(
         &ctx.accounts.destination_mint.to_account_info(),
-        destination_amount_from_vault,
+        destination_amount_to_user,
     )?;
 
-    msg!(
-        "Swap result: total_source_debit_amount={}, source_amount_swapped={}, trade_fee={}, owner_fee={}, source_amount_to_vault={}, destination_amount_from_vault={}, destination_amount_post_transfer_fees={}",
+    pool_msg!(
+        pool,
+        "Swap result: total_source_debit_amount={}, source_amount_swapped={}, trade_fee={}, owner_fee={}, source_amount_to_vault={}, destination_amount_from_vault={}, destination_host_fee={}, destination_amount_post_transfer_fees={}",
         result.total_source_amount_swapped,
         result.source_amount_swapped,
-        source_amount_to_vault,
         result.trade_fee,
         result.owner_fee,
+        source_amount_to_vault,
         destination_amount_from_vault,
+        destination_host_fee,
         destination_amount_post_transfer_fees
     );
-    require_msg!(
+    require_pool_msg!(
+        pool,
         destination_amount_post_transfer_fees >= minimum_amount_out,
         SwapError::ExceededSlippage,
         &format!(
@@ -89,6 +144,17 @@ pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> R
         )
     );
 
+    let mut source_vault_amount_after = try_math!(ctx
+        .accounts
+        .source_vault
+        .amount
+        .try_add(to_u64!(result.source_amount_to_vault)?))?;
+    let destination_vault_amount_after = try_math!(ctx
+        .accounts
+        .destination_vault
+        .amount
+        .try_sub(destination_amount_from_vault))?;
+
     swap_token::transfer_from_user(
         ctx.accounts.source_token_program.to_account_info(),
         ctx.accounts.source_user_ata.to_account_info(),
@@ -99,19 +165,24 @@ pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> R
         ctx.accounts.source_mint.decimals,
     )?;
 
+    let mut owner_fees_burned = 0;
+    let mut host_fee = 0;
+    let mut default_host_fee = 0;
+    let mut source_host_fee_waived = 0;
+    let mut protocol_fee = 0;
     if result.owner_fee > 0 {
         let mut owner_fee = result.owner_fee;
-        // Allow none to fall through
         if let Some(host_fees_account) = &ctx.accounts.source_token_host_fees_account {
-            let host_fee = pool
+            let host_fee_amount = pool
                 .fees()
                 .host_fee(owner_fee)
                 .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
-            if host_fee > 0 {
-                owner_fee = try_math!(owner_fee.try_sub(host_fee))?;
-                let host_fee = utils::add_inverse_transfer_fee(
+            if host_fee_amount > 0 {
+                owner_fee = try_math!(owner_fee.try_sub(host_fee_amount))?;
+                host_fee = to_u64!(host_fee_amount)?;
+                let host_fee = swap_token::add_inverse_transfer_fee(
                     &ctx.accounts.source_mint.to_account_info(),
-                    to_u64!(host_fee)?,
+                    host_fee,
                 )?;
 
                 swap_token::transfer_from_user(
@@ -124,16 +195,48 @@ pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> R
                     ctx.accounts.source_mint.decimals,
                 )?;
             }
+        } else if ctx.accounts.destination_token_host_fees_account.is_some() {
+            // The host is paid in destination tokens, its share of the owner fee is not charged
+            let host_fee_amount = pool
+                .fees()
+                .host_fee(owner_fee)
+                .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
+            owner_fee = try_math!(owner_fee.try_sub(host_fee_amount))?;
+            source_host_fee_waived = to_u64!(host_fee_amount)?;
+        } else if !pool.burn_owner_fees() {
+            // The host fee is sent to the fees vault along with the owner fee, and set aside there
+            // for the default host fees recipient
+            default_host_fee = to_u64!(pool
+                .fees()
+                .host_fee(owner_fee)
+                .map_err(|_| error!(SwapError::FeeCalculationFailure))?)?;
+            host_fee = default_host_fee;
+        }
+        if !pool.burn_owner_fees() {
+            // The protocol share of what is left of the owner fee is set aside in the fees vault
+            // for the protocol fee authority
+            protocol_fee = to_u64!(pool
+                .protocol_fee(try_math!(owner_fee.try_sub(u128::from(default_host_fee)))?)
+                .map_err(|_| error!(SwapError::FeeCalculationFailure))?)?;
         }
-        let owner_fee = utils::add_inverse_transfer_fee(
+        let owner_fee_to_destination = to_u64!(owner_fee)?;
+        let owner_fee = swap_token::add_inverse_transfer_fee(
             &ctx.accounts.source_mint.to_account_info(),
-            to_u64!(owner_fee)?,
+            owner_fee_to_destination,
         )?;
+        let owner_fee_destination = if pool.burn_owner_fees() {
+            owner_fees_burned = owner_fee;
+            source_vault_amount_after =
+                try_math!(source_vault_amount_after.try_add(owner_fee_to_destination))?;
+            ctx.accounts.source_vault.to_account_info()
+        } else {
+            ctx.accounts.source_token_fees_vault.to_account_info()
+        };
         swap_token::transfer_from_user(
             ctx.accounts.source_token_program.to_account_info(),
             ctx.accounts.source_user_ata.to_account_info(),
             ctx.accounts.source_mint.to_account_info(),
-            ctx.accounts.source_token_fees_vault.to_account_info(),
+            owner_fee_destination,
             ctx.accounts.signer.to_account_info(),
             owner_fee,
             ctx.accounts.source_mint.decimals,
@@ -148,23 +251,122 @@ pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> R
         ctx.accounts.destination_user_ata.to_account_info(),
         ctx.accounts.pool_authority.to_account_info(),
         pool.bump_seed(),
-        destination_amount_from_vault,
+        destination_amount_to_user,
         ctx.accounts.destination_mint.decimals,
     )?;
+    if let Some(host_fees_account) = &ctx.accounts.destination_token_host_fees_account {
+        if destination_host_fee > 0 {
+            host_fee = destination_host_fee;
+            swap_token::transfer_from_vault(
+                ctx.accounts.destination_token_program.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.destination_vault.to_account_info(),
+                ctx.accounts.destination_mint.to_account_info(),
+                host_fees_account.to_account_info(),
+                ctx.accounts.pool_authority.to_account_info(),
+                pool.bump_seed(),
+                destination_host_fee,
+                ctx.accounts.destination_mint.decimals,
+            )?;
+        }
+    }
 
-    let total_fees = to_u64!(result.total_fees)?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.source_vault,
+        source_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.destination_vault,
+        destination_vault_amount_after,
+    )?;
 
-    msg!(
-        "Swap outputs: token_in_amount={}, token_out_amount={}, total_fees={}",
-        source_amount_to_vault,
-        destination_amount_from_vault,
-        total_fees
-    );
-    emitted!(event::Swap {
-        token_in_amount: source_amount_to_vault,
-        token_out_amount: destination_amount_from_vault,
+    let total_fees = try_math!(to_u64!(result.total_fees)?.try_sub(source_host_fee_waived))?;
+    let token_in_amount = try_math!(source_amount_to_vault.try_add(owner_fees_burned))?;
+
+    if let Some(user_stats) = ctx
+        .remaining_accounts
+        .first()
+        .filter(|account| !events::is_event_account(account))
+    {
+        utils::record_user_stats(
+            user_stats,
+            &ctx.accounts.pool.key(),
+            &ctx.accounts.source_user_ata.owner,
+            trade_direction,
+            amount_in,
+            total_fees,
+        )?;
+    }
+
+    pool_msg!(
+        pool,
+        "Swap outputs: token_in_amount={}, token_out_amount={}, total_fees={}, owner_fees_burned={}",
+        token_in_amount,
+        destination_amount_to_user,
         total_fees,
-    });
+        owner_fees_burned
+    );
+
+    let event_mode = pool.event_mode();
+    let dynamic_fees = pool.dynamic_fees_enabled();
+    let outflow_limited = pool.max_slot_outflow_bps != 0;
+    let (source, token_a_amount, token_b_amount) = match trade_direction {
+        TradeDirection::AtoB => (
+            AorB::A,
+            source_vault_amount_after,
+            destination_vault_amount_after,
+        ),
+        TradeDirection::BtoA => (
+            AorB::B,
+            destination_vault_amount_after,
+            source_vault_amount_after,
+        ),
+    };
+    if default_host_fee > 0 || protocol_fee > 0 || dynamic_fees || outflow_limited {
+        drop(pool);
+        let mut pool = ctx.accounts.pool.load_mut()?;
+        let accrued = pool.default_host_fees_mut(source);
+        *accrued = accrued.saturating_add(default_host_fee);
+        let accrued = pool.protocol_fees_mut(source);
+        *accrued = accrued.saturating_add(protocol_fee);
+        if dynamic_fees {
+            pool.update_volatility(u128::from(token_a_amount), u128::from(token_b_amount), slot)?;
+        }
+        if outflow_limited {
+            pool.record_slot_outflow(
+                source,
+                token_in_amount,
+                destination_amount_from_vault,
+                destination_vault_amount_after,
+                slot,
+            )?;
+        }
+    }
+
+    pool_emitted!(
+        event_mode,
+        ctx.remaining_accounts,
+        event::Swap {
+            token_in_amount,
+            token_out_amount: destination_amount_to_user,
+            total_fees,
+            owner_fees_burned,
+            self_trade,
+            host_fee,
+            host_fee_recipient: ctx
+                .accounts
+                .source_token_host_fees_account
+                .as_ref()
+                .or(ctx.accounts.destination_token_host_fees_account.as_ref())
+                .map(|host_fees_account| host_fees_account.key()),
+            trade_fee: to_u64!(result.trade_fee)?,
+            trade_fee_numerator: fees.trade_fee_numerator,
+            protocol_fee,
+            host_fee_in_destination_token: destination_host_fee > 0,
+            token_a_reserve_after: token_a_amount,
+            token_b_reserve_after: token_b_amount,
+        }
+    );
 }
 
 #[derive(Accounts)]
@@ -240,6 +442,22 @@ pub struct Swap<'info> {
     pub source_token_program: Interface<'info, TokenInterface>,
     /// Token program for the destination mint
     pub destination_token_program: Interface<'info, TokenInterface>,
+
+    /// Allow-list entry of the signer - required if the pool only allows permissioned swaps
+    /// CHECK: checked in the handler
+    pub allowed_swap_taker: Option<Box<Account<'info, AllowedSwapTaker>>>,
+
+    /// Price account of the curve - required if the pool uses the oracle pegged curve
+    /// CHECK: checked against the curve in the handler
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// Optional host fees account of the destination mint for front ends, replacing
+    /// `source_token_host_fees_account` on pools paying the host fee in destination tokens
+    #[account(mut,
+        token::mint = destination_mint,
+        token::token_program = destination_token_program,
+    )]
+    pub destination_token_host_fees_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 mod utils {
@@ -248,12 +466,83 @@ mod utils {
     use super::*;
     use crate::curve::fees::Fees;
 
+    /// Add the swap to the stats of the user token accounts owner
+    pub fn record_user_stats(
+        user_stats: &AccountInfo,
+        pool: &Pubkey,
+        owner: &Pubkey,
+        trade_direction: TradeDirection,
+        amount_in: u64,
+        fees: u64,
+    ) -> Result<()> {
+        let mut stats = Account::<UserStats>::try_from(user_stats)?;
+        require_msg!(
+            user_stats.is_writable && stats.pool == *pool && stats.owner == *owner,
+            SwapError::InvalidUserStats,
+            &format!(
+                "InvalidUserStats: user_stats={}, writable={}, pool={}, owner={}",
+                user_stats.key(),
+                user_stats.is_writable,
+                stats.pool,
+                stats.owner
+            )
+        );
+        stats.record_swap(trade_direction, amount_in, fees, Clock::get()?.slot);
+        stats.exit(&crate::ID)
+    }
+
+    /// Whether the pool admin owns either of the user token accounts, rejected if the pool
+    /// prevents self trades
+    pub fn is_self_trade(ctx: &Context<Swap>, pool: &Ref<SwapPool>) -> Result<bool> {
+        let self_trade = ctx.accounts.source_user_ata.owner == pool.admin
+            || ctx.accounts.destination_user_ata.owner == pool.admin;
+        if self_trade {
+            require_msg!(
+                !pool.prevent_self_trades(),
+                SwapError::SelfTradeNotAllowed,
+                &format!(
+                    "SelfTradeNotAllowed: source_user_ata.owner ({}) or destination_user_ata.owner ({}) == pool.admin ({})",
+                    ctx.accounts.source_user_ata.owner,
+                    ctx.accounts.destination_user_ata.owner,
+                    pool.admin
+                )
+            );
+            msg!("Self trade: a user token account is owned by the pool admin");
+        }
+        Ok(self_trade)
+    }
+
     pub fn validate_inputs(ctx: &Context<Swap>, pool: &Ref<SwapPool>) -> Result<TradeDirection> {
         require_msg!(
             !pool.withdrawals_only(),
             SwapError::WithdrawalsOnlyMode,
             "The pool is in withdrawals only mode"
         );
+        pool.require_not_paused(PoolOperation::Swaps)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        if pool.permissioned_swaps() {
+            let allowed_swap_taker = ctx
+                .accounts
+                .allowed_swap_taker
+                .as_ref()
+                .ok_or_else(|| error!(SwapError::SwapTakerNotAllowed))?;
+            require_msg!(
+                allowed_swap_taker.pool == ctx.accounts.pool.key()
+                    && allowed_swap_taker.taker == ctx.accounts.signer.key(),
+                SwapError::SwapTakerNotAllowed,
+                &format!(
+                    "SwapTakerNotAllowed: allowed_swap_taker.pool ({}) != pool.key ({}) or allowed_swap_taker.taker ({}) != signer.key ({})",
+                    allowed_swap_taker.pool,
+                    ctx.accounts.pool.key(),
+                    allowed_swap_taker.taker,
+                    ctx.accounts.signer.key()
+                )
+            );
+        }
         let trade_direction = if ctx.accounts.source_mint.key() == pool.token_a_mint
             && ctx.accounts.destination_mint.key() == pool.token_b_mint
         {
@@ -327,6 +616,34 @@ mod utils {
             }
         };
 
+        if pool.host_fee_in_destination_token() {
+            require_msg!(
+                ctx.accounts.source_token_host_fees_account.is_none(),
+                SwapError::IncorrectSwapAccount,
+                "IncorrectSwapAccount: the pool pays the host fee in destination tokens, source_token_host_fees_account must not be set"
+            );
+        } else {
+            require_msg!(
+                ctx.accounts.destination_token_host_fees_account.is_none(),
+                SwapError::IncorrectSwapAccount,
+                "IncorrectSwapAccount: the pool pays the host fee in source tokens, destination_token_host_fees_account must not be set"
+            );
+        }
+        if let Some(host_fees_account) = &ctx.accounts.destination_token_host_fees_account {
+            require_msg!(
+                host_fees_account.key() != ctx.accounts.destination_vault.key(),
+                SwapError::IncorrectSwapAccount,
+                &format!(
+                    "IncorrectSwapAccount: destination_token_host_fees_account.key ({}) == destination_vault.key ({})",
+                    host_fees_account.key(),
+                    ctx.accounts.destination_vault.key()
+                )
+            );
+        }
+
+        swap_token::require_no_vault_delegates(&ctx.accounts.source_vault)?;
+        swap_token::require_no_vault_delegates(&ctx.accounts.destination_vault)?;
+
         Ok(trade_direction)
     }
 
@@ -422,32 +739,98 @@ mod utils {
         Ok(amount)
     }
 
-    /// Add token mint transfer fees for actual amount sent pre-transfer fees
-    pub fn add_inverse_transfer_fee(
-        mint_acc_info: &AccountInfo,
-        post_fee_amount: u64,
-    ) -> Result<u64> {
-        let mint_data = mint_acc_info.data.borrow();
-        let mint =
-            StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
-                &mint_data,
-            )?;
-        let amount = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-            let transfer_fee = transfer_fee_config
-                .calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
-                .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
-            let amount_add_fee = try_math!(post_fee_amount.try_add(transfer_fee))?;
-            msg!(
-                "Add token transfer fee: fee={}, amount={}, amount_add_fee={}",
-                transfer_fee,
-                post_fee_amount,
-                amount_add_fee
-            );
-            amount_add_fee
-        } else {
-            post_fee_amount
+    /// Reject a swap leaving the spot price (token B per token A) outside of the pool price bounds
+    pub fn validate_price_bounds(
+        pool: &SwapPool,
+        trade_direction: TradeDirection,
+        new_pool_source_amount: u128,
+        new_pool_destination_amount: u128,
+    ) -> Result<()> {
+        if pool.min_price == 0 && pool.max_price == 0 {
+            return Ok(());
+        }
+        let (token_a_amount, token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (new_pool_source_amount, new_pool_destination_amount),
+            TradeDirection::BtoA => (new_pool_destination_amount, new_pool_source_amount),
         };
-        Ok(amount)
+        let price = SwapPool::reserves_price(token_a_amount, token_b_amount)?;
+        require_msg!(
+            price >= u128::from(pool.min_price)
+                && (pool.max_price == 0 || price <= u128::from(pool.max_price)),
+            SwapError::PriceOutOfBounds,
+            &format!(
+                "PriceOutOfBounds: price={} not in [min_price={}, max_price={}]",
+                price, pool.min_price, pool.max_price
+            )
+        );
+        Ok(())
+    }
+
+    /// Reject a swap taking more than `max_trade_size_bps` of the destination reserves, or moving
+    /// the reserves price (token B per token A) by more than `max_price_impact_bps`
+    pub fn validate_trade_size(
+        pool: &SwapPool,
+        trade_direction: TradeDirection,
+        source_reserve: u128,
+        destination_reserve: u128,
+        new_pool_source_amount: u128,
+        new_pool_destination_amount: u128,
+    ) -> Result<()> {
+        if pool.max_trade_size_bps != 0 {
+            let amount_out = destination_reserve.saturating_sub(new_pool_destination_amount);
+            let max_amount_out = try_math!(destination_reserve
+                .try_mul(u128::from(pool.max_trade_size_bps))?
+                .try_div(10_000))?;
+            require_msg!(
+                amount_out <= max_amount_out,
+                SwapError::TradeTooLarge,
+                &format!(
+                    "TradeTooLarge: amount_out={} > max_amount_out={}, max_trade_size_bps={}",
+                    amount_out, max_amount_out, pool.max_trade_size_bps
+                )
+            );
+        }
+
+        if pool.max_price_impact_bps != 0 {
+            let (token_a_before, token_b_before, token_a_after, token_b_after) =
+                match trade_direction {
+                    TradeDirection::AtoB => (
+                        source_reserve,
+                        destination_reserve,
+                        new_pool_source_amount,
+                        new_pool_destination_amount,
+                    ),
+                    TradeDirection::BtoA => (
+                        destination_reserve,
+                        source_reserve,
+                        new_pool_destination_amount,
+                        new_pool_source_amount,
+                    ),
+                };
+            if token_a_before == 0 || token_a_after == 0 {
+                return Ok(());
+            }
+            let price_before = SwapPool::reserves_price(token_a_before, token_b_before)?;
+            let price_after = SwapPool::reserves_price(token_a_after, token_b_after)?;
+            if price_before == 0 {
+                return Ok(());
+            }
+            let price_move = if price_after > price_before {
+                price_after - price_before
+            } else {
+                price_before - price_after
+            };
+            let price_impact_bps = try_math!(price_move.try_mul(10_000)?.try_div(price_before))?;
+            require_msg!(
+                price_impact_bps <= u128::from(pool.max_price_impact_bps),
+                SwapError::TradeTooLarge,
+                &format!(
+                    "TradeTooLarge: price_impact_bps={} > max_price_impact_bps={}",
+                    price_impact_bps, pool.max_price_impact_bps
+                )
+            );
+        }
+        Ok(())
     }
 
     #[cfg(test)]
@@ -463,7 +846,10 @@ mod utils {
         use proptest::{prop_assume, proptest};
 
         use super::*;
-        use crate::instructions::test::runner::syscall_stubs::test_syscall_stubs;
+        use crate::{
+            instructions::test::runner::syscall_stubs::test_syscall_stubs,
+            utils::swap_token::{add_inverse_transfer_fee, gross_up_transfer_fee},
+        };
 
         #[test]
         pub fn test_sub_transfer_fee_when_no_transfer_fees() {
@@ -648,6 +1034,74 @@ mod utils {
             assert_eq!(original, 10_000_000);
         }
 
+        #[test]
+        pub fn test_gross_up_transfer_fee_when_maximum_fee_reached() {
+            let transfer_fee = transfer_fee(100, 50);
+
+            // 1% of 10_000 would be 101, capped at 50
+            let amount = gross_up_transfer_fee(&transfer_fee, 10_000).unwrap();
+
+            assert_eq!(amount, 10_050);
+            assert_eq!(transfer_fee.calculate_post_fee_amount(amount), Some(10_000));
+        }
+
+        #[test]
+        pub fn test_gross_up_transfer_fee_just_below_maximum_fee() {
+            let transfer_fee = transfer_fee(100, 50);
+
+            let amount = gross_up_transfer_fee(&transfer_fee, 4_900).unwrap();
+
+            assert_eq!(amount, 4_950);
+            assert_eq!(transfer_fee.calculate_fee(amount), Some(50));
+            assert_eq!(transfer_fee.calculate_post_fee_amount(amount), Some(4_900));
+        }
+
+        #[test]
+        pub fn test_gross_up_transfer_fee_when_100_percent_fee() {
+            let transfer_fee = transfer_fee(10_000, 1_000);
+
+            let amount = gross_up_transfer_fee(&transfer_fee, 1).unwrap();
+
+            assert_eq!(amount, 1_001);
+            assert_eq!(transfer_fee.calculate_post_fee_amount(amount), Some(1));
+        }
+
+        #[test]
+        pub fn test_gross_up_transfer_fee_when_9999_bps_fee() {
+            let transfer_fee = transfer_fee(9_999, u64::MAX);
+
+            let amount = gross_up_transfer_fee(&transfer_fee, 3).unwrap();
+
+            assert_eq!(amount, 30_000);
+            assert_eq!(transfer_fee.calculate_post_fee_amount(amount), Some(3));
+        }
+
+        #[test]
+        pub fn test_gross_up_zero_amount() {
+            for (bps, maximum_fee) in [(0, 0), (1, u64::MAX), (10_000, u64::MAX)] {
+                assert_eq!(
+                    gross_up_transfer_fee(&transfer_fee(bps, maximum_fee), 0).unwrap(),
+                    0
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_gross_up_transfer_fee_fails_when_it_overflows() {
+            assert_eq!(
+                gross_up_transfer_fee(&transfer_fee(1, u64::MAX), u64::MAX),
+                Err(SwapError::TransferFeeGrossUpImpossible.into())
+            );
+            assert_eq!(
+                gross_up_transfer_fee(&transfer_fee(10_000, 1), u64::MAX),
+                Err(SwapError::TransferFeeGrossUpImpossible.into())
+            );
+            assert_eq!(
+                gross_up_transfer_fee(&transfer_fee(10_000, u64::MAX), 1),
+                Err(SwapError::TransferFeeGrossUpImpossible.into())
+            );
+        }
+
         #[test]
         pub fn test_sub_input_transfer_fee_when_no_transfer_fees_or_protocol_fees() {
             test_syscall_stubs();
@@ -901,6 +1355,25 @@ mod utils {
             }
         }
 
+        proptest! {
+            #[test]
+            fn test_gross_up_transfer_fee_round_trips_exactly(
+                post_fee_amount in 0..u64::MAX / 10_000,
+                transfer_fee_bps in 0..=10_000_u16,
+                maximum_fee in 0..u64::MAX / 2,
+            ) {
+                let transfer_fee = transfer_fee(transfer_fee_bps, maximum_fee);
+
+                let pre_fee_amount = gross_up_transfer_fee(&transfer_fee, post_fee_amount).unwrap();
+
+                assert_eq!(transfer_fee.calculate_post_fee_amount(pre_fee_amount), Some(post_fee_amount));
+                if pre_fee_amount > 0 {
+                    // no smaller amount would do
+                    assert!(transfer_fee.calculate_post_fee_amount(pre_fee_amount - 1).unwrap() < post_fee_amount);
+                }
+            }
+        }
+
         proptest! {
             #[test]
             fn test_sub_input_fees_same_or_less_after_re_adding(
@@ -1043,6 +1516,85 @@ mod utils {
             }
         }
 
+        #[test]
+        pub fn test_validate_price_bounds() {
+            let pool = SwapPool {
+                min_price: 950_000_000,
+                max_price: 1_050_000_000,
+                ..Default::default()
+            };
+
+            // 1000 A : 1040 B -> price 1.04
+            assert!(validate_price_bounds(&pool, TradeDirection::AtoB, 1_000, 1_040).is_ok());
+            assert!(validate_price_bounds(&pool, TradeDirection::BtoA, 1_040, 1_000).is_ok());
+            // 1000 A : 1060 B -> price 1.06
+            assert_eq!(
+                validate_price_bounds(&pool, TradeDirection::BtoA, 1_060, 1_000),
+                Err(SwapError::PriceOutOfBounds.into())
+            );
+            // 1000 A : 940 B -> price 0.94
+            assert_eq!(
+                validate_price_bounds(&pool, TradeDirection::AtoB, 1_000, 940),
+                Err(SwapError::PriceOutOfBounds.into())
+            );
+        }
+
+        #[test]
+        pub fn test_validate_price_bounds_unbounded_side() {
+            let pool = SwapPool {
+                min_price: 950_000_000,
+                ..Default::default()
+            };
+            assert!(validate_price_bounds(&pool, TradeDirection::BtoA, u64::MAX.into(), 1).is_ok());
+
+            let pool = SwapPool::default();
+            assert!(validate_price_bounds(&pool, TradeDirection::AtoB, u64::MAX.into(), 1).is_ok());
+        }
+
+        #[test]
+        pub fn test_validate_trade_size() {
+            let pool = SwapPool {
+                max_trade_size_bps: 500,
+                ..Default::default()
+            };
+            // 50 of 1000 B out is 5% of the reserves
+            assert!(
+                validate_trade_size(&pool, TradeDirection::AtoB, 1_000, 1_000, 1_053, 950).is_ok()
+            );
+            assert_eq!(
+                validate_trade_size(&pool, TradeDirection::AtoB, 1_000, 1_000, 1_054, 949),
+                Err(SwapError::TradeTooLarge.into())
+            );
+
+            let pool = SwapPool {
+                max_price_impact_bps: 1_000,
+                ..Default::default()
+            };
+            // 1000 A : 1000 B -> 1000 A : 900 B, price down 10%
+            assert!(
+                validate_trade_size(&pool, TradeDirection::BtoA, 1_000, 1_000, 900, 1_000).is_ok()
+            );
+            // 1000 A : 1000 B -> 1000 A : 1110 B, price up 11%
+            assert_eq!(
+                validate_trade_size(&pool, TradeDirection::BtoA, 1_000, 1_000, 1_110, 1_000),
+                Err(SwapError::TradeTooLarge.into())
+            );
+
+            let pool = SwapPool::default();
+            assert!(
+                validate_trade_size(&pool, TradeDirection::AtoB, 1_000, 1_000, 1_000_000, 1)
+                    .is_ok()
+            );
+        }
+
+        fn transfer_fee(transfer_fee_bps: u16, maximum_fee: u64) -> TransferFee {
+            TransferFee {
+                epoch: 0.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: transfer_fee_bps.into(),
+            }
+        }
+
         fn mint_with_transfer_fee(mint_data: &mut [u8], transfer_fee_bps: u16) {
             let mut mint =
                 StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::SwapError,
+    event, instructions,
+    instructions::Swap,
+    pool_emitted, require_msg,
+    state::SwapState,
+    try_math,
+    utils::{instructions::require_before_deadline, math::TryMath},
+};
+
+/// Maximum number of fills of a `swap_batch`, about what fits in the compute budget of a
+/// transaction with the most expensive curves
+pub const MAX_SWAP_BATCH_FILLS: usize = 16;
+
+/// One swap of a `swap_batch`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapFill {
+    /// SOURCE amount to transfer, output to DESTINATION is based on the exchange rate
+    pub amount_in: u64,
+    /// Minimum amount of DESTINATION token to output for this fill
+    pub minimum_amount_out: u64,
+}
+
+/// Execute the fills one after the other against the same pool, each exactly like a `swap`
+///
+/// The accounts are deserialized and checked once for the whole batch. Every fill is priced
+/// against the reserves left by the previous one and checked against its own minimum, so a
+/// single fill exceeding its slippage fails the batch.
+pub fn handler(
+    ctx: Context<Swap>,
+    fills: Vec<SwapFill>,
+    deadline: Option<i64>,
+) -> Result<event::SwapBatch> {
+    require_before_deadline(deadline)?;
+    require_msg!(
+        !fills.is_empty() && fills.len() <= MAX_SWAP_BATCH_FILLS,
+        SwapError::InvalidSwapBatch,
+        &format!(
+            "InvalidSwapBatch: {} fills, expected between 1 and {}",
+            fills.len(),
+            MAX_SWAP_BATCH_FILLS
+        )
+    );
+
+    let mut token_in_amount: u64 = 0;
+    let mut token_out_amount: u64 = 0;
+    let mut total_fees: u64 = 0;
+    for fill in fills.iter() {
+        let swap = instructions::swap::handler(
+            Context::new(
+                ctx.program_id,
+                &mut *ctx.accounts,
+                ctx.remaining_accounts,
+                BTreeMap::new(),
+            ),
+            fill.amount_in,
+            fill.minimum_amount_out,
+            None,
+        )?;
+        token_in_amount = try_math!(token_in_amount.try_add(swap.token_in_amount))?;
+        token_out_amount = try_math!(token_out_amount.try_add(swap.token_out_amount))?;
+        total_fees = try_math!(total_fees.try_add(swap.total_fees))?;
+    }
+
+    msg!(
+        "Swap batch outputs: fills={}, token_in_amount={}, token_out_amount={}, total_fees={}",
+        fills.len(),
+        token_in_amount,
+        token_out_amount,
+        total_fees
+    );
+
+    let event_mode = ctx.accounts.pool.load()?.event_mode();
+    pool_emitted!(
+        event_mode,
+        ctx.remaining_accounts,
+        event::SwapBatch {
+            fills: fills.len() as u64,
+            token_in_amount,
+            token_out_amount,
+            total_fees,
+        }
+    );
+}
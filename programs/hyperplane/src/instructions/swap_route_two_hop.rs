@@ -0,0 +1,310 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, instructions,
+    instructions::Swap,
+    require_msg,
+    state::{AllowedSwapTaker, SwapPool},
+    swap_route_two_hop::utils::validate_inputs,
+    try_math,
+    utils::{events, math::TryMath},
+};
+
+/// Swap the source token for the intermediate token in the first pool, then everything received
+/// of the intermediate token for the destination token in the second pool
+///
+/// Each leg is processed exactly like `swap`, the first one without a minimum so that
+/// `minimum_amount_out` only applies to the destination tokens received at the end.
+pub fn handler(
+    ctx: Context<SwapRouteTwoHop>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<event::SwapRouteTwoHop> {
+    validate_inputs(&ctx)?;
+    msg!(
+        "Swap route inputs: first_pool={}, second_pool={}, amount_in={}, minimum_amount_out={}",
+        ctx.accounts.first_pool.key(),
+        ctx.accounts.second_pool.key(),
+        amount_in,
+        minimum_amount_out
+    );
+
+    // User stats are not recorded for routed swaps, only the event accounts are passed on
+    let event_accounts: Vec<AccountInfo> = ctx
+        .remaining_accounts
+        .iter()
+        .filter(|account| events::is_event_account(account))
+        .cloned()
+        .collect();
+
+    let intermediate_amount_before = ctx.accounts.intermediate_user_ata.amount;
+    let destination_amount_before = ctx.accounts.destination_user_ata.amount;
+
+    let first_swap = instructions::swap::handler(
+        Context::new(
+            ctx.program_id,
+            &mut ctx.accounts.first_swap(),
+            &event_accounts,
+            BTreeMap::new(),
+        ),
+        amount_in,
+        0,
+        None,
+    )?;
+
+    ctx.accounts.intermediate_user_ata.reload()?;
+    let intermediate_amount = try_math!(ctx
+        .accounts
+        .intermediate_user_ata
+        .amount
+        .try_sub(intermediate_amount_before))?;
+
+    let second_swap = instructions::swap::handler(
+        Context::new(
+            ctx.program_id,
+            &mut ctx.accounts.second_swap(),
+            &event_accounts,
+            BTreeMap::new(),
+        ),
+        intermediate_amount,
+        minimum_amount_out,
+        None,
+    )?;
+
+    ctx.accounts.destination_user_ata.reload()?;
+    let token_out_amount = try_math!(ctx
+        .accounts
+        .destination_user_ata
+        .amount
+        .try_sub(destination_amount_before))?;
+
+    msg!(
+        "Swap route outputs: token_in_amount={}, intermediate_amount={}, token_out_amount={}",
+        first_swap.token_in_amount,
+        intermediate_amount,
+        token_out_amount
+    );
+
+    emitted!(event::SwapRouteTwoHop {
+        first_pool: ctx.accounts.first_pool.key(),
+        second_pool: ctx.accounts.second_pool.key(),
+        token_in_amount: first_swap.token_in_amount,
+        intermediate_amount,
+        token_out_amount,
+        first_total_fees: first_swap.total_fees,
+        second_total_fees: second_swap.total_fees,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SwapRouteTwoHop<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Pool trading the source token for the intermediate token
+    #[account(mut)]
+    pub first_pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: checked in the handler
+    pub first_swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub first_pool_authority: AccountInfo<'info>,
+
+    /// Pool trading the intermediate token for the destination token
+    #[account(mut,
+        constraint = second_pool.key() != first_pool.key() @ SwapError::IncorrectSwapAccount,
+    )]
+    pub second_pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: checked in the handler
+    pub second_swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub second_pool_authority: AccountInfo<'info>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        constraint = source_mint.key() != intermediate_mint.key() @ SwapError::RepeatedMint,
+        constraint = source_mint.key() != destination_mint.key() @ SwapError::RepeatedMint,
+    )]
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        constraint = intermediate_mint.key() != destination_mint.key() @ SwapError::RepeatedMint,
+    )]
+    pub intermediate_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// First pool vault of the source token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub first_source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// First pool vault of the intermediate token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub first_destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// First pool fees vault of the source token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub first_source_token_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Second pool vault of the intermediate token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub second_source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Second pool vault of the destination token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub second_destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Second pool fees vault of the intermediate token
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub second_source_token_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's source token account
+    // note - authority constraint repeated for clarity
+    #[account(mut,
+        token::mint = source_mint,
+        token::authority = destination_user_ata.owner,
+        token::token_program = source_token_program,
+    )]
+    pub source_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's intermediate token account, receiving the first leg and paying the second
+    #[account(mut,
+        token::mint = intermediate_mint,
+        token::authority = source_user_ata.owner,
+        token::token_program = intermediate_token_program,
+    )]
+    pub intermediate_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's destination token account
+    // note - authority constraint repeated for clarity
+    #[account(mut,
+        token::mint = destination_mint,
+        token::authority = source_user_ata.owner,
+        token::token_program = destination_token_program,
+    )]
+    pub destination_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the source mint
+    pub source_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the intermediate mint
+    pub intermediate_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the destination mint
+    pub destination_token_program: Interface<'info, TokenInterface>,
+
+    /// Allow-list entry of the signer - required if the first pool only allows permissioned swaps
+    /// CHECK: checked in the handler
+    pub first_allowed_swap_taker: Option<Box<Account<'info, AllowedSwapTaker>>>,
+
+    /// Allow-list entry of the signer - required if the second pool only allows permissioned swaps
+    /// CHECK: checked in the handler
+    pub second_allowed_swap_taker: Option<Box<Account<'info, AllowedSwapTaker>>>,
+}
+
+impl<'info> SwapRouteTwoHop<'info> {
+    /// Accounts of the first leg, swapping the source token for the intermediate token
+    fn first_swap(&self) -> Swap<'info> {
+        Swap {
+            signer: self.signer.clone(),
+            pool: self.first_pool.clone(),
+            swap_curve: self.first_swap_curve.clone(),
+            pool_authority: self.first_pool_authority.clone(),
+            source_mint: self.source_mint.clone(),
+            destination_mint: self.intermediate_mint.clone(),
+            source_vault: self.first_source_vault.clone(),
+            destination_vault: self.first_destination_vault.clone(),
+            source_token_fees_vault: self.first_source_token_fees_vault.clone(),
+            source_user_ata: self.source_user_ata.clone(),
+            destination_user_ata: self.intermediate_user_ata.clone(),
+            source_token_host_fees_account: None,
+            source_token_program: self.source_token_program.clone(),
+            destination_token_program: self.intermediate_token_program.clone(),
+            allowed_swap_taker: self.first_allowed_swap_taker.clone(),
+            oracle: None,
+            destination_token_host_fees_account: None,
+        }
+    }
+
+    /// Accounts of the second leg, swapping the intermediate token for the destination token
+    fn second_swap(&self) -> Swap<'info> {
+        Swap {
+            signer: self.signer.clone(),
+            pool: self.second_pool.clone(),
+            swap_curve: self.second_swap_curve.clone(),
+            pool_authority: self.second_pool_authority.clone(),
+            source_mint: self.intermediate_mint.clone(),
+            destination_mint: self.destination_mint.clone(),
+            source_vault: self.second_source_vault.clone(),
+            destination_vault: self.second_destination_vault.clone(),
+            source_token_fees_vault: self.second_source_token_fees_vault.clone(),
+            source_user_ata: self.intermediate_user_ata.clone(),
+            destination_user_ata: self.destination_user_ata.clone(),
+            source_token_host_fees_account: None,
+            source_token_program: self.intermediate_token_program.clone(),
+            destination_token_program: self.destination_token_program.clone(),
+            allowed_swap_taker: self.second_allowed_swap_taker.clone(),
+            oracle: None,
+            destination_token_host_fees_account: None,
+        }
+    }
+}
+
+mod utils {
+    use super::*;
+
+    /// Check the accounts `swap` validates with constraints, the mints and vaults of each leg are
+    /// validated by `swap` itself
+    pub fn validate_inputs(ctx: &Context<SwapRouteTwoHop>) -> Result<()> {
+        for (pool, swap_curve, pool_authority, leg) in [
+            (
+                &ctx.accounts.first_pool,
+                ctx.accounts.first_swap_curve.key(),
+                ctx.accounts.first_pool_authority.key(),
+                "first",
+            ),
+            (
+                &ctx.accounts.second_pool,
+                ctx.accounts.second_swap_curve.key(),
+                ctx.accounts.second_pool_authority.key(),
+                "second",
+            ),
+        ] {
+            let pool = pool.load()?;
+            require_msg!(
+                pool.swap_curve == swap_curve,
+                SwapError::IncorrectSwapAccount,
+                &format!(
+                    "IncorrectSwapAccount: {} swap_curve.key ({}) != pool.swap_curve ({})",
+                    leg, swap_curve, pool.swap_curve
+                )
+            );
+            require_msg!(
+                pool.pool_authority == pool_authority,
+                SwapError::InvalidProgramAddress,
+                &format!(
+                    "InvalidProgramAddress: {} pool_authority.key ({}) != pool.pool_authority ({})",
+                    leg, pool_authority, pool.pool_authority
+                )
+            );
+        }
+        Ok(())
+    }
+}
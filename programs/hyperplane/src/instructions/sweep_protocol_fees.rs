@@ -0,0 +1,133 @@
+use std::cmp;
+
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{PoolOperation, SwapPool, SwapState},
+    sweep_protocol_fees::utils::validate_inputs,
+    utils::{seeds, swap_token},
+};
+
+/// Move the protocol fees accrued in a fees vault of the pool to the shared protocol fee vault of
+/// its mint
+///
+/// Permissionless, the fees can only go to the vault of the protocol fee config. The amount is
+/// added to the swept protocol fees of the pool.
+pub fn handler(ctx: Context<SweepProtocolFees>) -> Result<event::SweepProtocolFees> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let a_or_b = validate_inputs(&ctx, &pool)?;
+
+    let accrued = pool.protocol_fees_mut(a_or_b);
+    let sweep_amount = cmp::min(*accrued, ctx.accounts.fees_vault.amount);
+    require_msg!(
+        sweep_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "No protocol fees to sweep"
+    );
+    *accrued -= sweep_amount;
+    let swept = pool.protocol_fees_swept_mut(a_or_b);
+    *swept = swept.saturating_add(sweep_amount);
+
+    msg!(
+        "Sweeping protocol fees: sweep_amount={}, a_or_b={:?}",
+        sweep_amount,
+        a_or_b
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.fees_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.fees_vault.to_account_info(),
+        ctx.accounts.fees_mint.to_account_info(),
+        ctx.accounts.protocol_fee_vault.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        sweep_amount,
+        ctx.accounts.fees_mint.decimals,
+    )?;
+
+    emitted!(event::SweepProtocolFees {
+        mint: ctx.accounts.fees_mint.key(),
+        sweep_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SweepProtocolFees<'info> {
+    #[account(mut,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        mint::token_program = fees_token_program,
+    )]
+    pub fees_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Fee vault the protocol fees accrued in
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = fees_token_program,
+    )]
+    pub fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Shared protocol fee vault of the fees mint
+    #[account(mut,
+        seeds = [seeds::PROTOCOL_FEE_VAULT, fees_mint.key().as_ref()],
+        bump,
+        token::mint = fees_mint,
+        token::token_program = fees_token_program,
+    )]
+    pub protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the fee token mint
+    pub fees_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::RefMut;
+
+    use super::*;
+    use crate::curve::calculator::AorB;
+
+    pub fn validate_inputs(
+        ctx: &Context<SweepProtocolFees>,
+        pool: &RefMut<SwapPool>,
+    ) -> Result<AorB> {
+        pool.require_not_paused(PoolOperation::FeeWithdrawals)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        let (pool_fees_vault, a_or_b) = if ctx.accounts.fees_mint.key() == pool.token_a_mint {
+            (pool.token_a_fees_vault, AorB::A)
+        } else if ctx.accounts.fees_mint.key() == pool.token_b_mint {
+            (pool.token_b_fees_vault, AorB::B)
+        } else {
+            return err!(SwapError::IncorrectTradingMint);
+        };
+        require_msg!(
+            pool_fees_vault == ctx.accounts.fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: pool_fees_vault.key ({}) != fees_vault.key ({}), a_or_b={:?}",
+                pool_fees_vault,
+                ctx.accounts.fees_vault.key(),
+                a_or_b,
+            )
+        );
+        Ok(a_or_b)
+    }
+}
@@ -249,6 +249,8 @@ impl SwapAccountInfo {
                     fees: self.fees,
                     initial_supply: self.initial_supply.clone(),
                     curve_parameters: self.curve_params.clone().into(),
+                    burn_owner_fees: false,
+                    strict_token_program: false,
                 },
             )
             .unwrap(),
@@ -437,9 +439,13 @@ impl SwapAccountInfo {
                 None,
                 source_token_program_id,
                 destination_token_program_id,
+                None,
+                None,
+                None,
                 ix::Swap {
                     amount_in,
                     minimum_amount_out,
+                    deadline: None,
                 },
             )
             .unwrap(),
@@ -458,6 +464,8 @@ impl SwapAccountInfo {
                 &mut exe.clone(), // Optional front end host fees - passed as the program if not present
                 &mut exe.clone(), // source_token_program
                 &mut exe.clone(), // destination_token_program
+                &mut exe.clone(), // Optional allowed swap taker - passed as the program if not present
+                &mut exe.clone(), // Optional oracle - passed as the program if not present
             ],
         )?;
 
@@ -544,10 +552,13 @@ impl SwapAccountInfo {
                 &pool_token_program_id,
                 &token_a_program_id,
                 &token_b_program_id,
+                None,
+                None,
                 ix::Deposit {
                     pool_token_amount,
                     maximum_token_a_amount,
                     maximum_token_b_amount,
+                    deadline: None,
                 },
             )
             .unwrap(),
@@ -567,6 +578,8 @@ impl SwapAccountInfo {
                 &mut exe.clone(),
                 &mut exe.clone(),
                 &mut exe.clone(),
+                &mut exe.clone(), // Optional referrer - passed as the program if not present
+                &mut exe.clone(), // Optional allowed liquidity provider
             ],
         )
     }
@@ -637,6 +650,8 @@ impl SwapAccountInfo {
                     pool_token_amount,
                     minimum_token_a_amount,
                     minimum_token_b_amount,
+                    burn_dust: false,
+                    deadline: None,
                 },
             )
             .unwrap(),
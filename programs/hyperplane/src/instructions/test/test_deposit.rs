@@ -365,10 +365,13 @@ fn test_deposit(
                     &accounts.pool_token_program_id,
                     &token_a_program_id,
                     &token_b_program_id,
+                    None,
+                    None,
                     ix::Deposit {
                         pool_token_amount: pool_amount.try_into().unwrap(),
                         maximum_token_a_amount: deposit_a,
                         maximum_token_b_amount: deposit_b,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -388,6 +391,8 @@ fn test_deposit(
                     &mut exe.clone(), // pool_token_program
                     &mut exe.clone(), // token_a_token_program
                     &mut exe.clone(), // token_b_token_program
+                    &mut exe.clone(), // Optional referrer - passed as the program if not present
+                    &mut exe.clone(), // Optional allowed liquidity provider
                 ],
             )
         );
@@ -428,10 +433,13 @@ fn test_deposit(
                     &accounts.pool_token_program_id,
                     &wrong_key,
                     &accounts.token_b_program_id,
+                    None,
+                    None,
                     ix::Deposit {
                         pool_token_amount: pool_amount.try_into().unwrap(),
                         maximum_token_a_amount: deposit_a,
                         maximum_token_b_amount: deposit_b,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -451,6 +459,8 @@ fn test_deposit(
                     &mut exe.clone(),
                     &mut exe.clone(),
                     &mut exe.clone(),
+                    &mut exe.clone(), // Optional referrer - passed as the program if not present
+                    &mut exe.clone(), // Optional allowed liquidity provider
                 ],
             )
         );
@@ -491,10 +501,13 @@ fn test_deposit(
                     &accounts.pool_token_program_id,
                     &accounts.token_a_program_id,
                     &wrong_key,
+                    None,
+                    None,
                     ix::Deposit {
                         pool_token_amount: pool_amount.try_into().unwrap(),
                         maximum_token_a_amount: deposit_a,
                         maximum_token_b_amount: deposit_b,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -514,6 +527,8 @@ fn test_deposit(
                     &mut exe.clone(),
                     &mut exe.clone(),
                     &mut exe.clone(),
+                    &mut exe.clone(), // Optional referrer - passed as the program if not present
+                    &mut exe.clone(), // Optional allowed liquidity provider
                 ],
             )
         );
@@ -554,10 +569,13 @@ fn test_deposit(
                     &wrong_key,
                     &accounts.token_a_program_id,
                     &accounts.token_b_program_id,
+                    None,
+                    None,
                     ix::Deposit {
                         pool_token_amount: pool_amount.try_into().unwrap(),
                         maximum_token_a_amount: deposit_a,
                         maximum_token_b_amount: deposit_b,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -577,6 +595,8 @@ fn test_deposit(
                     &mut exe.clone(),
                     &mut exe.clone(),
                     &mut exe.clone(),
+                    &mut exe.clone(), // Optional referrer - passed as the program if not present
+                    &mut exe.clone(), // Optional allowed liquidity provider
                 ],
             )
         );
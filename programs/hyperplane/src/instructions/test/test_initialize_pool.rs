@@ -404,6 +404,8 @@ fn test_initialize(
                         fees: accounts.fees,
                         initial_supply: accounts.initial_supply.clone(),
                         curve_parameters: accounts.curve_params.clone().into(),
+                        burn_owner_fees: false,
+                        strict_token_program: false,
                     },
                 )
                 .unwrap(),
@@ -448,7 +450,10 @@ fn test_initialize(
             host_fee_numerator,
             host_fee_denominator,
         };
-        let curve_params = CurveParameters::ConstantPrice { token_b_price };
+        let curve_params = CurveParameters::ConstantPrice {
+            token_b_price,
+            spread_bps: 0,
+        };
         let mut accounts = SwapAccountInfo::new(
             &user_key,
             fees,
@@ -478,7 +483,10 @@ fn test_initialize(
             host_fee_denominator,
         };
         let token_b_price = 10_000;
-        let curve_params = CurveParameters::ConstantPrice { token_b_price };
+        let curve_params = CurveParameters::ConstantPrice {
+            token_b_price,
+            spread_bps: 0,
+        };
 
         let mut accounts = SwapAccountInfo::new(
             &user_key,
@@ -654,6 +662,7 @@ fn test_initialize(
     //         owner_key,
     //         valid_curve_types,
     //         fees: &fees,
+    //         protocol_fee_bps: 0,
     //     });
     //     let mut accounts = SwapAccountInfo::new(
     //         &user_key,
@@ -734,7 +743,9 @@ fn test_initialize(
     //         owner_key,
     //         valid_curve_types,
     //         fees: &fees,
+    //         protocol_fee_bps: 0,
     //         blocked_trading_token_extensions: &[],
+    //         strict_token_program: false,
     //     });
     //     let mut bad_fees = fees.clone();
     //     bad_fees.trade_fee_numerator = trade_fee_numerator - 1;
@@ -830,7 +841,9 @@ fn test_initialize(
             owner_key,
             valid_curve_types,
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         });
         let mut accounts = SwapAccountInfo::new(
             &user_key,
@@ -868,6 +881,8 @@ fn test_initialize(
                     fees: accounts.fees,
                     initial_supply: accounts.initial_supply.clone(),
                     curve_parameters: accounts.curve_params.clone().into(),
+                    burn_owner_fees: false,
+                    strict_token_program: false,
                 },
             )
             .unwrap(),
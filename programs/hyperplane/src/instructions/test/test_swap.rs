@@ -77,7 +77,10 @@ fn test_valid_swap_curve_all_fees(
     assert::check_valid_swap_curve(
         fees,
         SwapTransferFees::default(),
-        CurveParameters::ConstantPrice { token_b_price },
+        CurveParameters::ConstantPrice {
+            token_b_price,
+            spread_bps: 0,
+        },
         token_a_amount,
         token_b_amount,
         &pool_token_program_id,
@@ -160,7 +163,10 @@ fn test_valid_swap_curve_trade_fee_only(
     assert::check_valid_swap_curve(
         fees,
         SwapTransferFees::default(),
-        CurveParameters::ConstantPrice { token_b_price },
+        CurveParameters::ConstantPrice {
+            token_b_price,
+            spread_bps: 0,
+        },
         token_a_amount,
         token_b_amount / token_b_price,
         &pool_token_program_id,
@@ -240,7 +246,9 @@ fn test_valid_swap_with_fee_constraints(
         owner_key: owner_key_str,
         valid_curve_types,
         fees: &fees,
+        protocol_fee_bps: 0,
         blocked_trading_token_extensions: &[],
+        strict_token_program: false,
     });
     let mut accounts = SwapAccountInfo::new(
         &owner_key,
@@ -281,6 +289,8 @@ fn test_valid_swap_with_fee_constraints(
                 fees: accounts.fees,
                 initial_supply: accounts.initial_supply.clone(),
                 curve_parameters: accounts.curve_params.clone().into(),
+                burn_owner_fees: false,
+                strict_token_program: false,
             },
         )
         .unwrap(),
@@ -359,9 +369,13 @@ fn test_valid_swap_with_fee_constraints(
             Some(&host_fee_token_a_key),
             &token_a_program_id,
             &token_b_program_id,
+            None,
+            None,
+            None,
             ix::Swap {
                 amount_in,
                 minimum_amount_out,
+                deadline: None,
             },
         )
         .unwrap(),
@@ -579,9 +593,13 @@ fn test_invalid_swap(
                     None,
                     &token_a_program_id,
                     &token_b_program_id,
+                    None,
+                    None,
+                    None,
                     ix::Swap {
                         amount_in: initial_a,
                         minimum_amount_out: minimum_token_b_amount,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -685,7 +703,9 @@ fn test_invalid_swap(
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         });
 
         let exe = &mut SolanaAccount::default();
@@ -708,9 +728,13 @@ fn test_invalid_swap(
                 None,
                 &token_a_program_id,
                 &token_b_program_id,
+                None,
+                None,
+                None,
                 ix::Swap {
                     amount_in: initial_a,
                     minimum_amount_out: minimum_token_b_amount,
+                    deadline: None,
                 },
             )
             .unwrap(),
@@ -769,7 +793,9 @@ fn test_invalid_swap(
             owner_key,
             valid_curve_types: &[],
             fees: &fees,
+            protocol_fee_bps: 0,
             blocked_trading_token_extensions: &[],
+            strict_token_program: false,
         });
 
         let exe = &mut SolanaAccount::default();
@@ -796,9 +822,13 @@ fn test_invalid_swap(
                     Some(&bad_token_a_key),
                     &token_a_program_id,
                     &token_b_program_id,
+                    None,
+                    None,
+                    None,
                     ix::Swap {
                         amount_in: initial_a,
                         minimum_amount_out: 0,
+                        deadline: None,
                     },
                 )
                 .unwrap(),
@@ -895,7 +925,7 @@ fn test_overdraw_offset_curve(
     let minimum_token_b_amount = 0;
 
     assert_eq!(
-        Err(SwapError::ZeroTradingTokens.into()),
+        Err(SwapError::EmptyReserves.into()),
         accounts.swap(
             &swapper_key,
             &token_a_key,
@@ -946,7 +976,7 @@ fn test_overdraw_offset_curve(
 
     // try a to b again, fails due to no more liquidity
     assert_eq!(
-        Err(SwapError::ZeroTradingTokens.into()),
+        Err(SwapError::EmptyReserves.into()),
         accounts.swap(
             &swapper_key,
             &token_a_key,
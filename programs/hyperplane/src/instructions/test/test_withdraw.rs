@@ -175,6 +175,8 @@ fn test_withdraw(
                         pool_token_amount: withdraw_amount.try_into().unwrap(),
                         minimum_token_a_amount,
                         minimum_token_b_amount,
+                        burn_dust: false,
+                        deadline: None,
                     }
                 )
                 .unwrap(),
@@ -572,7 +574,10 @@ fn test_withdraw_constant_price_curve(
         host_fee_denominator,
     };
 
-    let curve_params = CurveParameters::ConstantPrice { token_b_price };
+    let curve_params = CurveParameters::ConstantPrice {
+        token_b_price,
+        spread_bps: 0,
+    };
     let swap_curve = SwapCurve::new_from_params(curve_params.clone()).unwrap();
     let total_pool = swap_curve.calculator.new_pool_supply();
     let user_key = Pubkey::new_unique();
@@ -0,0 +1,100 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LockedLpPosition, SwapPool, SwapState},
+    utils::swap_token,
+};
+
+/// Return the locked pool tokens to the owner once the unlock slot is reached, closing the position
+pub fn handler(ctx: Context<UnlockLiquidity>) -> Result<event::UnlockLiquidity> {
+    let pool = ctx.accounts.pool.load()?;
+    let current_slot = Clock::get()?.slot;
+    let unlock_slot = ctx.accounts.position.unlock_slot;
+    require_msg!(
+        current_slot >= unlock_slot,
+        SwapError::LiquidityLocked,
+        &format!(
+            "LiquidityLocked: current_slot={} < unlock_slot={}",
+            current_slot, unlock_slot
+        )
+    );
+
+    // anything sent to the vault on top of the locked amount goes to the owner too, so that the
+    // vault can be closed
+    let pool_token_amount = ctx.accounts.pool_token_vault.amount;
+    msg!(
+        "Unlock liquidity outputs: pool_token_amount={}",
+        pool_token_amount
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        pool_token_amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+    swap_token::close_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+    )?;
+
+    emitted!(event::UnlockLiquidity { pool_token_amount });
+}
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    /// Owner of the position, receives the position and vault rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        has_one = pool,
+        has_one = owner,
+        has_one = pool_token_vault,
+        close = owner,
+    )]
+    pub position: Box<Account<'info, LockedLpPosition>>,
+
+    /// CHECK: has_one constraint on the position
+    #[account(mut)]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's pool token account to return the locked pool tokens to
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
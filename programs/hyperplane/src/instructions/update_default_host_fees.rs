@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{DefaultHostFees, SwapPool},
+    utils::seeds,
+};
+
+/// Change the default host fees recipient, the host fees accrued so far are claimed to the new one
+pub fn handler(
+    ctx: Context<UpdateDefaultHostFees>,
+    recipient: Pubkey,
+) -> Result<event::SetDefaultHostFees> {
+    msg!(
+        "Setting default host fees recipient {} -> {}",
+        ctx.accounts.default_host_fees.recipient,
+        recipient
+    );
+    ctx.accounts.default_host_fees.recipient = recipient;
+
+    emitted!(event::SetDefaultHostFees { recipient });
+}
+
+#[derive(Accounts)]
+pub struct UpdateDefaultHostFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin)]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        seeds = [seeds::DEFAULT_HOST_FEES, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub default_host_fees: Box<Account<'info, DefaultHostFees>>,
+}
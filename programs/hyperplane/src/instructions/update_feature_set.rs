@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    emitted, event,
+    state::{Feature, FeatureSet},
+    utils::seeds,
+};
+
+pub fn handler(
+    ctx: Context<UpdateFeatureSet>,
+    feature: u16,
+    enabled: bool,
+) -> Result<event::UpdateFeatureSet> {
+    let feature =
+        Feature::try_from(feature).map_err(|_| error!(ErrorCode::InstructionDidNotDeserialize))?;
+
+    msg!("Setting feature {:?} -> {}", feature, enabled);
+    ctx.accounts.feature_set.set_enabled(feature, enabled);
+
+    emitted!(event::UpdateFeatureSet { feature, enabled });
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeatureSet<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        seeds = [seeds::FEATURE_SET],
+        bump,
+        has_one = admin,
+    )]
+    pub feature_set: Box<Account<'info, FeatureSet>>,
+}
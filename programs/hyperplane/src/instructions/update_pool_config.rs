@@ -1,8 +1,16 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{accounts::interface_account::InterfaceAccount, prelude::*};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use crate::{
-    emitted, event, set_config,
-    state::{SwapPool, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    constraints::SWAP_CONSTRAINTS,
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{
+        ConfigChange, FeatureSet, PoolConfigHistory, SwapPool, SwapState, UpdatePoolConfigMode,
+        UpdatePoolConfigValue,
+    },
+    utils::{events, seeds},
 };
 
 pub const VALUE_BYTE_ARRAY_LEN: usize = 32;
@@ -14,21 +22,24 @@ pub fn handler(
 ) -> Result<event::UpdatePoolConfig> {
     let pool = &mut ctx.accounts.pool.load_mut()?;
 
-    let mode = UpdatePoolConfigMode::try_from(mode)
-        .map_err(|_| error!(ErrorCode::InstructionDidNotDeserialize))?;
-
-    let value = match mode {
-        UpdatePoolConfigMode::WithdrawalsOnly => {
-            let value = UpdatePoolConfigValue::from_bool_bytes(value)?;
-            let packed_value = value.to_u64();
-            set_config!(pool, withdrawals_only, packed_value);
-            value
-        }
-    };
+    let (mode, value) = utils::parse_config(mode, value)?;
+    utils::require_feature_enabled(ctx.accounts.feature_set.as_deref(), mode, &value)?;
+    let old_value = utils::set_pool_config(pool, mode, &value, ctx.remaining_accounts)?;
+    utils::record_config_change(
+        &ctx.accounts.config_history,
+        mode,
+        old_value,
+        &value,
+        ctx.accounts.admin.key(),
+    )?;
 
     emitted!(event::UpdatePoolConfig {
         mode,
-        value: value.clone()
+        value,
+        token_a_reserve: ctx.accounts.token_a_vault.amount,
+        token_b_reserve: ctx.accounts.token_b_vault.amount,
+        pool_token_supply: ctx.accounts.pool_token_mint.supply,
+        slot: Clock::get()?.slot,
     });
 }
 
@@ -39,11 +50,39 @@ pub struct UpdatePoolConfig<'info> {
 
     #[account(mut,
         has_one = admin,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
     )]
     pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: Config history of the pool, changes are recorded once it is initialized
+    #[account(mut,
+        seeds = [seeds::CONFIG_HISTORY, pool.key().as_ref()],
+        bump,
+    )]
+    pub config_history: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Global feature flags - required to turn on a config gated by a feature, e.g.
+    /// `PermissionedSwaps`
+    #[account(
+        seeds = [seeds::FEATURE_SET],
+        bump,
+    )]
+    pub feature_set: Option<Box<Account<'info, FeatureSet>>>,
 }
 
-mod utils {
+pub(crate) mod utils {
+    use super::*;
 
     #[macro_export]
     macro_rules! set_config {
@@ -64,4 +103,188 @@ mod utils {
             $pool.$config = $value;
         }};
     }
+
+    /// Decode the instruction mode and value, the value encoding depends on the mode
+    pub fn parse_config(
+        mode: u16,
+        value: &[u8; VALUE_BYTE_ARRAY_LEN],
+    ) -> Result<(UpdatePoolConfigMode, UpdatePoolConfigValue)> {
+        let mode = UpdatePoolConfigMode::try_from(mode)
+            .map_err(|_| error!(ErrorCode::InstructionDidNotDeserialize))?;
+
+        let value = UpdatePoolConfigValue::decode(mode, value)?;
+        Ok((mode, value))
+    }
+
+    /// Check that the feature gating the config is enabled before turning it on, see
+    /// [UpdatePoolConfigMode::feature]
+    ///
+    /// Turning a gated config off is always allowed, so pools are not stuck with it once the
+    /// feature is disabled.
+    pub fn require_feature_enabled(
+        feature_set: Option<&FeatureSet>,
+        mode: UpdatePoolConfigMode,
+        value: &UpdatePoolConfigValue,
+    ) -> Result<()> {
+        let feature = match mode.feature() {
+            Some(feature) if value.to_u64() != 0 => feature,
+            _ => return Ok(()),
+        };
+        let feature_set = feature_set.ok_or_else(|| {
+            msg!(
+                "FeatureDisabled: the feature set is required to turn on {:?}",
+                mode
+            );
+            error!(SwapError::FeatureDisabled)
+        })?;
+        feature_set.require_enabled(feature)
+    }
+
+    /// Apply the change to the pool, returns the previous packed value
+    ///
+    /// Status changes are also emitted as a `PoolStatusChanged` through the pool's event mode, so
+    /// indexers can follow the paused operations without decoding every config update.
+    pub fn set_pool_config(
+        pool: &mut SwapPool,
+        mode: UpdatePoolConfigMode,
+        value: &UpdatePoolConfigValue,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<u64> {
+        let packed_value = value.to_u64();
+        let old_value = match mode {
+            UpdatePoolConfigMode::WithdrawalsOnly => pool.withdrawals_only,
+            UpdatePoolConfigMode::PermissionedSwaps => pool.permissioned_swaps,
+            UpdatePoolConfigMode::MinPrice => pool.min_price,
+            UpdatePoolConfigMode::MaxPrice => pool.max_price,
+            UpdatePoolConfigMode::PreventSelfTrades => pool.prevent_self_trades,
+            UpdatePoolConfigMode::QuietLogs => pool.quiet_logs,
+            UpdatePoolConfigMode::EventMode => pool.event_mode,
+            UpdatePoolConfigMode::PermissionedDeposits => pool.permissioned_deposits,
+            UpdatePoolConfigMode::DynamicFeeNumerator => pool.dynamic_fee_numerator,
+            UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator => {
+                pool.max_dynamic_trade_fee_numerator
+            }
+            UpdatePoolConfigMode::Status => pool.status,
+            UpdatePoolConfigMode::BurnOwnerFees => pool.burn_owner_fees,
+            UpdatePoolConfigMode::MaxTradeSizeBps => pool.max_trade_size_bps,
+            UpdatePoolConfigMode::MaxPriceImpactBps => pool.max_price_impact_bps,
+            UpdatePoolConfigMode::MaxSlotOutflowBps => pool.max_slot_outflow_bps,
+            UpdatePoolConfigMode::FlashFeeBps => pool.flash_fee_bps,
+            UpdatePoolConfigMode::ProtocolFeeBps => pool.protocol_fee_bps,
+            UpdatePoolConfigMode::HostFeeInDestinationToken => pool.host_fee_in_destination_token,
+            UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps => {
+                pool.max_single_sided_withdraw_imbalance_bps
+            }
+        };
+        match mode {
+            UpdatePoolConfigMode::WithdrawalsOnly => {
+                set_config!(pool, withdrawals_only, packed_value);
+            }
+            UpdatePoolConfigMode::PermissionedSwaps => {
+                set_config!(pool, permissioned_swaps, packed_value);
+            }
+            UpdatePoolConfigMode::MinPrice => {
+                set_config!(pool, min_price, packed_value);
+            }
+            UpdatePoolConfigMode::MaxPrice => {
+                set_config!(pool, max_price, packed_value);
+            }
+            UpdatePoolConfigMode::PreventSelfTrades => {
+                set_config!(pool, prevent_self_trades, packed_value);
+            }
+            UpdatePoolConfigMode::QuietLogs => {
+                set_config!(pool, quiet_logs, packed_value);
+            }
+            UpdatePoolConfigMode::EventMode => {
+                set_config!(pool, event_mode, packed_value);
+            }
+            UpdatePoolConfigMode::PermissionedDeposits => {
+                set_config!(pool, permissioned_deposits, packed_value);
+            }
+            UpdatePoolConfigMode::DynamicFeeNumerator => {
+                set_config!(pool, dynamic_fee_numerator, packed_value);
+            }
+            UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator => {
+                set_config!(pool, max_dynamic_trade_fee_numerator, packed_value);
+            }
+            UpdatePoolConfigMode::Status => {
+                set_config!(pool, status, packed_value);
+                events::emit(
+                    pool.event_mode(),
+                    remaining_accounts,
+                    &event::PoolStatusChanged {
+                        old_status: old_value,
+                        new_status: packed_value,
+                    },
+                )?;
+            }
+            UpdatePoolConfigMode::BurnOwnerFees => {
+                set_config!(pool, burn_owner_fees, packed_value);
+            }
+            UpdatePoolConfigMode::MaxTradeSizeBps => {
+                set_config!(pool, max_trade_size_bps, packed_value);
+            }
+            UpdatePoolConfigMode::MaxPriceImpactBps => {
+                set_config!(pool, max_price_impact_bps, packed_value);
+            }
+            UpdatePoolConfigMode::MaxSlotOutflowBps => {
+                set_config!(pool, max_slot_outflow_bps, packed_value);
+            }
+            UpdatePoolConfigMode::FlashFeeBps => {
+                set_config!(pool, flash_fee_bps, packed_value);
+            }
+            UpdatePoolConfigMode::ProtocolFeeBps => {
+                if let Some(swap_constraints) = &SWAP_CONSTRAINTS {
+                    swap_constraints.validate_protocol_fee_bps(packed_value)?;
+                }
+                set_config!(pool, protocol_fee_bps, packed_value);
+            }
+            UpdatePoolConfigMode::HostFeeInDestinationToken => {
+                set_config!(pool, host_fee_in_destination_token, packed_value);
+            }
+            UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps => {
+                require_msg!(
+                    packed_value == 0 || pool.curve_type().measures_reserves_imbalance(),
+                    SwapError::UnsupportedCurveOperation,
+                    &format!(
+                        "UnsupportedCurveOperation: {:?} curve has no reserves imbalance to limit",
+                        pool.curve_type()
+                    )
+                );
+                set_config!(pool, max_single_sided_withdraw_imbalance_bps, packed_value);
+            }
+        }
+        pool.validate_fees()?;
+        require_msg!(
+            pool.min_price == 0 || pool.max_price == 0 || pool.min_price <= pool.max_price,
+            SwapError::InvalidPriceBounds,
+            &format!(
+                "InvalidPriceBounds: min_price={} > max_price={}",
+                pool.min_price, pool.max_price
+            )
+        );
+        Ok(old_value)
+    }
+
+    /// Append the change to the pool config history, a no-op until the history is initialized
+    pub fn record_config_change(
+        config_history: &AccountInfo,
+        mode: UpdatePoolConfigMode,
+        old_value: u64,
+        new_value: &UpdatePoolConfigValue,
+        authority: Pubkey,
+    ) -> Result<()> {
+        if config_history.owner != &crate::ID {
+            return Ok(());
+        }
+        let config_history = AccountLoader::<PoolConfigHistory>::try_from(config_history)?;
+        config_history.load_mut()?.push(ConfigChange {
+            mode: mode as u64,
+            old_value,
+            new_value: new_value.to_u64(),
+            slot: Clock::get()?.slot,
+            authority,
+        });
+        Ok(())
+    }
 }
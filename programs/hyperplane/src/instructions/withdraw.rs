@@ -10,35 +10,43 @@ use crate::{
         base::SwapCurve,
         calculator::{AorB, RoundDirection},
     },
-    emitted,
     error::SwapError,
-    event, require_msg,
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
     state::{SwapPool, SwapState},
     to_u64, try_math,
-    utils::{math::TryMath, pool_token, swap_token},
+    utils::{instructions::require_before_deadline, math, math::TryMath, pool_token, swap_token},
     withdraw::utils::validate_inputs,
 };
 
+/// Pool token balance under which what a withdrawal leaves in the user account is dust
+pub const DUST_POOL_TOKEN_AMOUNT: u64 = 1_000;
+
 // todo - elliot token2022 transfer fees
 pub fn handler(
     ctx: Context<Withdraw>,
     pool_token_amount: u64,
     minimum_token_a_amount: u64,
     minimum_token_b_amount: u64,
+    burn_dust: bool,
+    deadline: Option<i64>,
 ) -> Result<event::Withdraw> {
+    require_before_deadline(deadline)?;
     let pool = ctx.accounts.pool.load()?;
     validate_inputs(&ctx, &pool)?;
-    msg!(
-        "Withdraw inputs: minimum_token_a_amount={}, minimum_token_b_amount={}, pool_token_amount={}",
+    pool_msg!(
+        pool,
+        "Withdraw inputs: minimum_token_a_amount={}, minimum_token_b_amount={}, pool_token_amount={}, burn_dust={}",
         minimum_token_a_amount,
         minimum_token_b_amount,
         pool_token_amount,
+        burn_dust,
     );
 
     let swap_curve = curve!(ctx.accounts.swap_curve, pool);
     let calculator = &swap_curve.calculator;
 
-    msg!(
+    pool_msg!(
+        pool,
         "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
         swap_curve.curve_type,
         ctx.accounts.token_a_vault.amount,
@@ -51,6 +59,21 @@ pub fn handler(
         "ZeroTradingTokens: pool_token_amount=0"
     );
 
+    let dust = utils::dust(
+        ctx.accounts.pool_token_user_ata.amount,
+        pool_token_amount,
+        burn_dust,
+    );
+    let pool_token_amount = try_math!(pool_token_amount.try_add(dust))?;
+    if dust > 0 {
+        pool_msg!(
+            pool,
+            "Burning dust: dust={}, pool_token_amount={}",
+            dust,
+            pool_token_amount
+        );
+    }
+
     let results = calculator
         .pool_tokens_to_trading_tokens(
             u128::from(pool_token_amount),
@@ -76,7 +99,8 @@ pub fn handler(
         AorB::B,
     )?;
 
-    msg!(
+    pool_msg!(
+        pool,
         "Withdraw outputs: token_a_to_receive={}, token_b_to_receive={}, pool_tokens_to_burn={}",
         token_a_after_fee,
         token_b_after_fee,
@@ -145,13 +169,46 @@ pub fn handler(
         )?;
     }
 
-    emitted!(event::Withdraw {
-        token_a_amount: token_a_after_fee,
-        token_b_amount: token_b_after_fee,
-        pool_token_amount,
-        token_a_fees,
-        token_b_fees,
-    });
+    let token_a_vault_amount_after =
+        try_math!(
+            try_math!(ctx.accounts.token_a_vault.amount.try_sub(token_a_after_fee))?
+                .try_sub(token_a_fees)
+        )?;
+    let token_b_vault_amount_after =
+        try_math!(
+            try_math!(ctx.accounts.token_b_vault.amount.try_sub(token_b_after_fee))?
+                .try_sub(token_b_fees)
+        )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_a_vault,
+        token_a_vault_amount_after,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_b_vault,
+        token_b_vault_amount_after,
+    )?;
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::Withdraw {
+            token_a_amount: token_a_after_fee,
+            token_b_amount: token_b_after_fee,
+            pool_token_amount,
+            token_a_fees,
+            token_b_fees,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
 }
 
 #[derive(Accounts)]
@@ -159,6 +216,7 @@ pub fn handler(
     pool_token_amount: u64,
     minimum_token_a_amount: u64,
     minimum_token_b_amount: u64,
+    burn_dust: bool,
 )]
 pub struct Withdraw<'info> {
     #[account(mut)]
@@ -243,13 +301,18 @@ pub struct Withdraw<'info> {
     pub token_b_token_program: Interface<'info, TokenInterface>,
 }
 
-mod utils {
+pub(crate) mod utils {
     use std::cell::Ref;
 
     use super::*;
     use crate::curve::calculator::AorB;
 
     pub fn validate_inputs(ctx: &Context<Withdraw>, pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
         require_msg!(
             pool.token_a_vault != ctx.accounts.token_a_user_ata.key(),
             SwapError::IncorrectSwapAccount,
@@ -268,9 +331,23 @@ mod utils {
                 pool.token_b_vault.key()
             )
         );
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
         Ok(())
     }
 
+    /// Pool tokens left to the user by the withdrawal, when they are dust the user asked to burn
+    pub fn dust(user_pool_token_balance: u64, pool_token_amount: u64, burn_dust: bool) -> u64 {
+        let remaining = user_pool_token_balance.saturating_sub(pool_token_amount);
+        if burn_dust && remaining < DUST_POOL_TOKEN_AMOUNT {
+            remaining
+        } else {
+            0
+        }
+    }
+
     pub fn sub_withdraw_fee(
         pool: &Ref<SwapPool>,
         pool_balance: u64,
@@ -289,13 +366,15 @@ mod utils {
         let amount_after_fee = to_u64!(amount_after_fee)?;
         let withdraw_fee = to_u64!(token_withdraw_fee)?;
 
-        msg!(
+        pool_msg!(
+            pool,
             "Token {:?} withdrawal fee: fee={}, amount_after_fee={}",
             a_or_b,
             withdraw_fee,
             amount_after_fee
         );
-        require_msg!(
+        require_pool_msg!(
+            pool,
             amount_after_fee >= minimum_withdraw_amount,
             SwapError::ExceededSlippage,
             &format!(
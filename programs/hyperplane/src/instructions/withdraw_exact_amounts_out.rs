@@ -0,0 +1,456 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve,
+    curve::{
+        base::SwapCurve,
+        calculator::{RoundDirection, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math, math::TryMath, pool_token, swap_token},
+    withdraw_exact_amounts_out::utils::validate_inputs,
+};
+
+/// Withdraw exactly `token_a_amount` and `token_b_amount`, burning at most
+/// `maximum_pool_token_amount` pool tokens
+///
+/// The owner withdrawal fee is charged on top of each amount, as for `withdraw_single`. The
+/// largest balanced part of the amounts and their fees is withdrawn like `withdraw`, and the
+/// remainder of each token like `withdraw_single`, paying the trade and owner fees on the half
+/// implicitly swapped. Amounts off the pool ratio, even by rounding, need single sided withdrawals.
+pub fn handler(
+    ctx: Context<WithdrawExactAmountsOut>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    maximum_pool_token_amount: u64,
+) -> Result<event::WithdrawExactAmountsOut> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&ctx, &pool)?;
+    pool_msg!(
+        pool,
+        "Withdraw exact amounts out inputs: token_a_amount={}, token_b_amount={}, maximum_pool_token_amount={}",
+        token_a_amount,
+        token_b_amount,
+        maximum_pool_token_amount,
+    );
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+
+    require_msg!(
+        token_a_amount > 0 || token_b_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: token_a_amount=0, token_b_amount=0"
+    );
+    require_msg!(
+        ctx.accounts.pool_token_mint.supply > 0,
+        SwapError::EmptySupply,
+        "EmptySupply: pool_token_supply=0"
+    );
+
+    pool_msg!(
+        pool,
+        "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+    );
+
+    let token_a_withdraw_fee = pool
+        .fees()
+        .owner_withdraw_fee(u128::from(token_a_amount))
+        .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
+    let token_b_withdraw_fee = pool
+        .fees()
+        .owner_withdraw_fee(u128::from(token_b_amount))
+        .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
+    let token_a_target = try_math!(u128::from(token_a_amount).try_add(token_a_withdraw_fee))?;
+    let token_b_target = try_math!(u128::from(token_b_amount).try_add(token_b_withdraw_fee))?;
+
+    // the vaults and supply as each part of the withdrawal leaves the pool
+    let mut token_a_vault_amount = u128::from(ctx.accounts.token_a_vault.amount);
+    let mut token_b_vault_amount = u128::from(ctx.accounts.token_b_vault.amount);
+    let mut pool_token_supply = u128::from(ctx.accounts.pool_token_mint.supply);
+
+    // balanced part, proportional to the vaults
+    let balanced_pool_token_amount = utils::pool_tokens_within_amounts(
+        &swap_curve,
+        token_a_target,
+        token_b_target,
+        pool_token_supply,
+        token_a_vault_amount,
+        token_b_vault_amount,
+    )?;
+    let balanced = swap_curve.calculator.pool_tokens_to_trading_tokens(
+        balanced_pool_token_amount,
+        pool_token_supply,
+        token_a_vault_amount,
+        token_b_vault_amount,
+        RoundDirection::Floor,
+    )?;
+    token_a_vault_amount = try_math!(token_a_vault_amount.try_sub(balanced.token_a_amount))?;
+    token_b_vault_amount = try_math!(token_b_vault_amount.try_sub(balanced.token_b_amount))?;
+    pool_token_supply = try_math!(pool_token_supply.try_sub(balanced_pool_token_amount))?;
+
+    // single sided parts, the withdrawal fee is already part of the remainders
+    let single_sided_fees = Fees {
+        owner_withdraw_fee_numerator: 0,
+        ..*pool.fees()
+    };
+    let mut single_sided_pool_token_amount: u128 = 0;
+    let mut token_a_owner_fee = 0;
+    let mut token_b_owner_fee = 0;
+    for trade_direction in [TradeDirection::AtoB, TradeDirection::BtoA] {
+        let remainder = match trade_direction {
+            TradeDirection::AtoB => try_math!(token_a_target.try_sub(balanced.token_a_amount))?,
+            TradeDirection::BtoA => try_math!(token_b_target.try_sub(balanced.token_b_amount))?,
+        };
+        if remainder == 0 {
+            continue;
+        }
+        pool.require_not_paused(PoolOperation::SingleSided)?;
+        pool.require_single_sided_balanced(
+            swap_curve.calculator.as_ref(),
+            token_a_vault_amount,
+            token_b_vault_amount,
+        )?;
+        let result = swap_curve.withdraw_single_token_type_exact_out(
+            remainder,
+            token_a_vault_amount,
+            token_b_vault_amount,
+            pool_token_supply,
+            trade_direction,
+            &single_sided_fees,
+        )?;
+        pool_msg!(
+            pool,
+            "Single sided remainder: trade_direction={:?}, remainder={}, trade_fee={}, owner_fee={}, pool_tokens_to_burn={}",
+            trade_direction,
+            remainder,
+            result.trade_fee,
+            result.owner_fee,
+            result.pool_token_amount,
+        );
+        // the trade fee stays in the vault, as does the owner fee when it is burned
+        let owner_fee = if pool.burn_owner_fees() {
+            0
+        } else {
+            result.owner_fee
+        };
+        let outflow = try_math!(remainder.try_add(owner_fee))?;
+        match trade_direction {
+            TradeDirection::AtoB => {
+                token_a_vault_amount = try_math!(token_a_vault_amount.try_sub(outflow))?;
+                token_a_owner_fee = owner_fee;
+            }
+            TradeDirection::BtoA => {
+                token_b_vault_amount = try_math!(token_b_vault_amount.try_sub(outflow))?;
+                token_b_owner_fee = owner_fee;
+            }
+        }
+        pool_token_supply = try_math!(pool_token_supply.try_sub(result.pool_token_amount))?;
+        single_sided_pool_token_amount =
+            try_math!(single_sided_pool_token_amount.try_add(result.pool_token_amount))?;
+    }
+
+    let pool_token_amount = to_u64!(try_math!(
+        balanced_pool_token_amount.try_add(single_sided_pool_token_amount)
+    )?)?;
+    let single_sided_pool_token_amount = to_u64!(single_sided_pool_token_amount)?;
+    let token_a_fees = to_u64!(try_math!(token_a_withdraw_fee.try_add(token_a_owner_fee))?)?;
+    let token_b_fees = to_u64!(try_math!(token_b_withdraw_fee.try_add(token_b_owner_fee))?)?;
+
+    pool_msg!(
+        pool,
+        "Withdraw exact amounts out outputs: token_a_fees={}, token_b_fees={}, pool_tokens_to_burn={}",
+        token_a_fees,
+        token_b_fees,
+        pool_token_amount,
+    );
+
+    require_pool_msg!(
+        pool,
+        pool_token_amount <= maximum_pool_token_amount,
+        SwapError::ExceededSlippage,
+        &format!(
+            "ExceededSlippage: pool_token_amount={} > maximum_pool_token_amount={}",
+            pool_token_amount, maximum_pool_token_amount
+        )
+    );
+    require_msg!(
+        pool_token_amount <= ctx.accounts.pool_token_user_ata.amount,
+        SwapError::InsufficientPoolTokenFunds,
+        &format!(
+            "InsufficientPoolTokenFunds: pool_token_amount={} > pool_token_user_ata.amount={}",
+            pool_token_amount, ctx.accounts.pool_token_user_ata.amount
+        )
+    );
+
+    pool_token::burn(
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.signer.to_account_info(),
+        ctx.accounts.pool_token_program.to_account_info(),
+        pool_token_amount,
+    )?;
+
+    if token_a_amount > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.token_a_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.token_a_mint.to_account_info(),
+            ctx.accounts.token_a_user_ata.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            token_a_amount,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if token_b_amount > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.token_b_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.token_b_mint.to_account_info(),
+            ctx.accounts.token_b_user_ata.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            token_b_amount,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+    if token_a_fees > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.token_a_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.token_a_mint.to_account_info(),
+            ctx.accounts.token_a_fees_vault.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            token_a_fees,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if token_b_fees > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.token_b_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.token_b_mint.to_account_info(),
+            ctx.accounts.token_b_fees_vault.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            token_b_fees,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_a_vault,
+        to_u64!(token_a_vault_amount)?,
+    )?;
+    swap_token::reload_and_require_vault_amount(
+        &mut ctx.accounts.token_b_vault,
+        to_u64!(token_b_vault_amount)?,
+    )?;
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::WithdrawExactAmountsOut {
+            token_a_amount,
+            token_b_amount,
+            pool_token_amount,
+            single_sided_pool_token_amount,
+            token_a_fees,
+            token_b_fees,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct WithdrawExactAmountsOut<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Account to collect the token A withdrawal and owner fees into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Account to collect the token B withdrawal and owner fees into
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token A token account
+    #[account(mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_token_program,
+    )]
+    pub token_a_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's token B token account
+    #[account(mut,
+        token::mint = token_b_mint,
+        token::authority = token_a_user_ata.owner,
+        token::token_program = token_b_token_program,
+    )]
+    pub token_b_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's pool token account
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = token_b_user_ata.owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(
+        ctx: &Context<WithdrawExactAmountsOut>,
+        pool: &Ref<SwapPool>,
+    ) -> Result<()> {
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        require_msg!(
+            pool.token_a_vault != ctx.accounts.token_a_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_a_user_ata.key ({}) == token_a_vault.key ({})",
+                ctx.accounts.token_a_user_ata.key(),
+                pool.token_a_vault.key()
+            )
+        );
+        require_msg!(
+            pool.token_b_vault != ctx.accounts.token_b_user_ata.key(),
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: token_b_user_ata.key ({}) == token_b_vault.key ({})",
+                ctx.accounts.token_b_user_ata.key(),
+                pool.token_b_vault.key()
+            )
+        );
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
+        Ok(())
+    }
+
+    /// Largest amount of pool tokens whose withdrawal pays out at most the given trading token
+    /// amounts, rounded down as for `withdraw`
+    pub fn pool_tokens_within_amounts(
+        swap_curve: &SwapCurve,
+        token_a_amount: u128,
+        token_b_amount: u128,
+        pool_token_supply: u128,
+        token_a_vault_amount: u128,
+        token_b_vault_amount: u128,
+    ) -> Result<u128> {
+        let pool_value = swap_curve.calculator.pool_tokens_to_trading_tokens(
+            pool_token_supply,
+            pool_token_supply,
+            token_a_vault_amount,
+            token_b_vault_amount,
+            RoundDirection::Floor,
+        )?;
+        if pool_value.token_a_amount == 0 || pool_value.token_b_amount == 0 {
+            // nothing to withdraw in proportion, all of it is single sided
+            return Ok(0);
+        }
+        let from_a = try_math!(token_a_amount
+            .try_mul(pool_token_supply)?
+            .try_div(pool_value.token_a_amount))?;
+        let from_b = try_math!(token_b_amount
+            .try_mul(pool_token_supply)?
+            .try_div(pool_value.token_b_amount))?;
+        let mut pool_tokens = from_a.min(from_b);
+
+        let paid_out = swap_curve.calculator.pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            token_a_vault_amount,
+            token_b_vault_amount,
+            RoundDirection::Floor,
+        )?;
+        if pool_tokens > 0
+            && (paid_out.token_a_amount > token_a_amount
+                || paid_out.token_b_amount > token_b_amount)
+        {
+            pool_tokens -= 1;
+        }
+        Ok(pool_tokens)
+    }
+}
@@ -10,8 +10,8 @@ use crate::{
     emitted,
     error::SwapError,
     event, require_msg,
-    state::{SwapPool, SwapState},
-    utils::swap_token,
+    state::{PoolOperation, SwapPool, SwapState},
+    utils::{seeds, swap_token},
     withdraw_fees::utils::validate_inputs,
 };
 
@@ -20,7 +20,7 @@ pub fn handler(
     requested_withdraw_amount: u64,
 ) -> Result<event::WithdrawFees> {
     let pool = ctx.accounts.pool.load()?;
-    validate_inputs(&ctx, &pool)?;
+    let a_or_b = validate_inputs(&ctx, &pool)?;
 
     require_msg!(
         requested_withdraw_amount > 0,
@@ -28,12 +28,27 @@ pub fn handler(
         "Cannot withdraw zero pool tokens"
     );
 
-    let withdraw_amount = cmp::min(requested_withdraw_amount, ctx.accounts.fees_vault.amount);
+    // The host fees owed to the default host fees recipient are only held back once it is set
+    let default_host_fees = if ctx.accounts.default_host_fees.owner == &crate::ID {
+        pool.default_host_fees(a_or_b)
+    } else {
+        0
+    };
+    let protocol_fees = pool.protocol_fees(a_or_b);
+    let withdrawable_amount = ctx
+        .accounts
+        .fees_vault
+        .amount
+        .saturating_sub(default_host_fees)
+        .saturating_sub(protocol_fees);
+    let withdraw_amount = cmp::min(requested_withdraw_amount, withdrawable_amount);
 
     msg!(
-        "Withdrawing from fees vault: withdraw_amount={}, requested_withdraw_amount={}",
+        "Withdrawing from fees vault: withdraw_amount={}, requested_withdraw_amount={}, default_host_fees={}, protocol_fees={}",
         withdraw_amount,
         requested_withdraw_amount,
+        default_host_fees,
+        protocol_fees,
     );
 
     swap_token::transfer_from_vault(
@@ -48,7 +63,13 @@ pub fn handler(
         ctx.accounts.fees_mint.decimals,
     )?;
 
-    emitted!(event::WithdrawFees { withdraw_amount });
+    emitted!(event::WithdrawFees {
+        withdraw_amount,
+        token_a_reserve: ctx.accounts.token_a_vault.amount,
+        token_b_reserve: ctx.accounts.token_b_vault.amount,
+        pool_token_supply: ctx.accounts.pool_token_mint.supply,
+        slot: Clock::get()?.slot,
+    });
 }
 
 #[derive(Accounts)]
@@ -59,12 +80,24 @@ pub struct WithdrawFees<'info> {
     #[account(mut,
         has_one = admin,
         has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
     )]
     pub pool: AccountLoader<'info, SwapPool>,
 
     /// CHECK: has_one constraint on the pool
     pub pool_authority: AccountInfo<'info>,
 
+    /// CHECK: has_one constraint on the pool
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
     /// CHECK: checked in the handler
     #[account(
         token::token_program = fees_token_program,
@@ -89,6 +122,14 @@ pub struct WithdrawFees<'info> {
 
     /// Token program for the fee token mint
     pub fees_token_program: Interface<'info, TokenInterface>,
+
+    /// Default host fees recipient of the pool, not initialized if the admin has not set one
+    /// CHECK: seeds constraint, ownership checked in the handler
+    #[account(
+        seeds = [seeds::DEFAULT_HOST_FEES, pool.key().as_ref()],
+        bump,
+    )]
+    pub default_host_fees: UncheckedAccount<'info>,
 }
 
 mod utils {
@@ -97,7 +138,13 @@ mod utils {
     use super::*;
     use crate::curve::calculator::AorB;
 
-    pub fn validate_inputs(ctx: &Context<WithdrawFees>, pool: &Ref<SwapPool>) -> Result<()> {
+    pub fn validate_inputs(ctx: &Context<WithdrawFees>, pool: &Ref<SwapPool>) -> Result<AorB> {
+        pool.require_not_paused(PoolOperation::FeeWithdrawals)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
         let (pool_fees_vault, a_or_b) = if ctx.accounts.fees_mint.key() == pool.token_a_mint {
             (pool.token_a_fees_vault.key(), AorB::A)
         } else if ctx.accounts.fees_mint.key() == pool.token_b_mint {
@@ -115,6 +162,6 @@ mod utils {
                 a_or_b,
             )
         );
-        Ok(())
+        Ok(a_or_b)
     }
 }
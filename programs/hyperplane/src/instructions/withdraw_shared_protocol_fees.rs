@@ -0,0 +1,87 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::ProtocolFeeConfig,
+    utils::{seeds, swap_token},
+};
+
+/// Withdraw the whole balance of the shared protocol fee vault of a mint to the protocol fee
+/// authority
+///
+/// Unlike the pool fee withdrawals there is no pool to check for an ongoing flash loan: the shared
+/// vault is only funded by `sweep_protocol_fees`, which is rejected during a loan of its pool, and
+/// is never lent out.
+pub fn handler(ctx: Context<WithdrawSharedProtocolFees>) -> Result<event::WithdrawProtocolFees> {
+    let withdraw_amount = ctx.accounts.protocol_fee_vault.amount;
+    require_msg!(
+        withdraw_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "No protocol fees to withdraw"
+    );
+
+    msg!(
+        "Withdrawing shared protocol fees: withdraw_amount={}, mint={}",
+        withdraw_amount,
+        ctx.accounts.fees_mint.key()
+    );
+
+    swap_token::transfer_from_protocol_fee_vault(
+        ctx.accounts.fees_token_program.to_account_info(),
+        ctx.accounts.protocol_fee_vault.to_account_info(),
+        ctx.accounts.fees_mint.to_account_info(),
+        ctx.accounts.authority_fees_ata.to_account_info(),
+        ctx.accounts.protocol_fee_config.to_account_info(),
+        *ctx.bumps.get("protocol_fee_config").unwrap(),
+        withdraw_amount,
+        ctx.accounts.fees_mint.decimals,
+    )?;
+
+    emitted!(event::WithdrawProtocolFees {
+        mint: ctx.accounts.fees_mint.key(),
+        withdraw_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSharedProtocolFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::PROTOCOL_FEE_CONFIG],
+        bump,
+        has_one = authority,
+    )]
+    pub protocol_fee_config: Box<Account<'info, ProtocolFeeConfig>>,
+
+    #[account(
+        mint::token_program = fees_token_program,
+    )]
+    pub fees_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Shared protocol fee vault of the fees mint
+    #[account(mut,
+        seeds = [seeds::PROTOCOL_FEE_VAULT, fees_mint.key().as_ref()],
+        bump,
+        token::mint = fees_mint,
+        token::token_program = fees_token_program,
+    )]
+    pub protocol_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Authority's token account to withdraw the protocol fees to
+    #[account(mut,
+        token::mint = fees_mint,
+        token::authority = authority,
+        token::token_program = fees_token_program,
+    )]
+    pub authority_fees_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the fee token mint
+    pub fees_token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,306 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    curve,
+    curve::{base::SwapCurve, calculator::TradeDirection},
+    error::SwapError,
+    event, pool_emitted, pool_msg, require_msg, require_pool_msg,
+    state::{PoolOperation, SwapPool, SwapState},
+    to_u64, try_math,
+    utils::{math, math::TryMath, pool_token, swap_token},
+    withdraw_single::utils::validate_inputs,
+};
+
+/// Withdraw exactly `destination_token_amount` of only one of the trading tokens, burning pool
+/// tokens worth the decrease of the pool value
+///
+/// Half of the withdrawal is implicitly swapped from the other token, so the trading fee is left in
+/// the pool and the owner fee is sent to the fees vault, on that half only. The owner withdrawal
+/// fee is charged on the whole amount as for `withdraw`.
+///
+/// Rejected while the reserves are more imbalanced than `max_single_sided_withdraw_imbalance_bps`,
+/// so the first LPs out of a depegged pool cannot drain its scarce token.
+pub fn handler(
+    ctx: Context<WithdrawSingle>,
+    destination_token_amount: u64,
+    maximum_pool_token_amount: u64,
+) -> Result<event::WithdrawSingle> {
+    let pool = ctx.accounts.pool.load()?;
+    let trade_direction = validate_inputs(&ctx, &pool)?;
+    pool_msg!(
+        pool,
+        "Withdraw single inputs: trade_direction={:?}, destination_token_amount={}, maximum_pool_token_amount={}",
+        trade_direction,
+        destination_token_amount,
+        maximum_pool_token_amount,
+    );
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+    pool.require_single_sided_balanced(
+        swap_curve.calculator.as_ref(),
+        u128::from(ctx.accounts.token_a_vault.amount),
+        u128::from(ctx.accounts.token_b_vault.amount),
+    )?;
+
+    require_msg!(
+        destination_token_amount > 0,
+        SwapError::ZeroTradingTokens,
+        "ZeroTradingTokens: destination_token_amount=0"
+    );
+
+    pool_msg!(
+        pool,
+        "Swap pool inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+    );
+
+    let result = swap_curve.withdraw_single_token_type_exact_out(
+        u128::from(destination_token_amount),
+        u128::from(ctx.accounts.token_a_vault.amount),
+        u128::from(ctx.accounts.token_b_vault.amount),
+        u128::from(ctx.accounts.pool_token_mint.supply),
+        trade_direction,
+        pool.fees(),
+    )?;
+
+    let pool_token_amount = to_u64!(result.pool_token_amount)?;
+    let trade_fee = to_u64!(result.trade_fee)?;
+    let owner_fee = to_u64!(result.owner_fee)?;
+    let withdraw_fee = to_u64!(result.withdraw_fee)?;
+
+    pool_msg!(
+        pool,
+        "Withdraw single outputs: trade_fee={}, owner_fee={}, withdraw_fee={}, pool_tokens_to_burn={}",
+        trade_fee,
+        owner_fee,
+        withdraw_fee,
+        pool_token_amount,
+    );
+
+    require_pool_msg!(
+        pool,
+        pool_token_amount <= maximum_pool_token_amount,
+        SwapError::ExceededSlippage,
+        &format!(
+            "ExceededSlippage: pool_token_amount={} > maximum_pool_token_amount={}",
+            pool_token_amount, maximum_pool_token_amount
+        )
+    );
+    require_msg!(
+        pool_token_amount <= ctx.accounts.pool_token_user_ata.amount,
+        SwapError::InsufficientPoolTokenFunds,
+        &format!(
+            "InsufficientPoolTokenFunds: pool_token_amount={} > pool_token_user_ata.amount={}",
+            pool_token_amount, ctx.accounts.pool_token_user_ata.amount
+        )
+    );
+
+    pool_token::burn(
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_token_user_ata.to_account_info(),
+        ctx.accounts.signer.to_account_info(),
+        ctx.accounts.pool_token_program.to_account_info(),
+        pool_token_amount,
+    )?;
+
+    let (destination_vault, destination_vault_amount) = match trade_direction {
+        TradeDirection::AtoB => (
+            ctx.accounts.token_a_vault.to_account_info(),
+            ctx.accounts.token_a_vault.amount,
+        ),
+        TradeDirection::BtoA => (
+            ctx.accounts.token_b_vault.to_account_info(),
+            ctx.accounts.token_b_vault.amount,
+        ),
+    };
+    swap_token::transfer_from_vault(
+        ctx.accounts.destination_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        destination_vault.clone(),
+        ctx.accounts.destination_mint.to_account_info(),
+        ctx.accounts.destination_user_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        destination_token_amount,
+        ctx.accounts.destination_mint.decimals,
+    )?;
+    let owner_fee_to_fees_vault = if pool.burn_owner_fees() { 0 } else { owner_fee };
+    let fees = try_math!(owner_fee_to_fees_vault.try_add(withdraw_fee))?;
+    if fees > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.destination_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            destination_vault,
+            ctx.accounts.destination_mint.to_account_info(),
+            ctx.accounts.destination_token_fees_vault.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            fees,
+            ctx.accounts.destination_mint.decimals,
+        )?;
+    }
+
+    let destination_vault_amount_after = try_math!(try_math!(
+        destination_vault_amount.try_sub(destination_token_amount)
+    )?
+    .try_sub(fees))?;
+    match trade_direction {
+        TradeDirection::AtoB => swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_a_vault,
+            destination_vault_amount_after,
+        )?,
+        TradeDirection::BtoA => swap_token::reload_and_require_vault_amount(
+            &mut ctx.accounts.token_b_vault,
+            destination_vault_amount_after,
+        )?,
+    }
+    ctx.accounts.pool_token_mint.reload()?;
+    ctx.accounts.pool_token_user_ata.reload()?;
+    let user_share_bps_after = math::share_bps(
+        ctx.accounts.pool_token_user_ata.amount,
+        ctx.accounts.pool_token_mint.supply,
+    )?;
+
+    pool_emitted!(
+        pool.event_mode(),
+        ctx.remaining_accounts,
+        event::WithdrawSingle {
+            destination_mint: ctx.accounts.destination_mint.key(),
+            destination_token_amount,
+            pool_token_amount,
+            trade_fee,
+            owner_fee,
+            withdraw_fee,
+            pool_token_supply_after: ctx.accounts.pool_token_mint.supply,
+            user_share_bps_after,
+            token_a_reserve_after: ctx.accounts.token_a_vault.amount,
+            token_b_reserve_after: ctx.accounts.token_b_vault.amount,
+        }
+    );
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSingle<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// Mint of the withdrawn token, either of the trading tokens
+    /// CHECK: checked in the handler
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Account to collect the owner and withdrawal fees into
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub destination_token_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's destination token account
+    #[account(mut,
+        token::mint = destination_mint,
+        token::token_program = destination_token_program,
+    )]
+    pub destination_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer's pool token account
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = destination_user_ata.owner,
+        token::token_program = pool_token_program,
+    )]
+    pub pool_token_user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the destination mint
+    pub destination_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+
+    pub fn validate_inputs(
+        ctx: &Context<WithdrawSingle>,
+        pool: &Ref<SwapPool>,
+    ) -> Result<TradeDirection> {
+        pool.require_not_paused(PoolOperation::SingleSided)?;
+        require_msg!(
+            !pool.flash_loan_active(),
+            SwapError::FlashLoanActive,
+            "A flash loan of the pool is ongoing"
+        );
+        let destination_mint = ctx.accounts.destination_mint.key();
+        let (trade_direction, destination_vault, destination_token_fees_vault) =
+            if destination_mint == pool.token_a_mint {
+                (
+                    TradeDirection::AtoB,
+                    pool.token_a_vault,
+                    pool.token_a_fees_vault,
+                )
+            } else if destination_mint == pool.token_b_mint {
+                (
+                    TradeDirection::BtoA,
+                    pool.token_b_vault,
+                    pool.token_b_fees_vault,
+                )
+            } else {
+                return err!(SwapError::IncorrectTradingMint);
+            };
+        require_msg!(
+            ctx.accounts.destination_token_fees_vault.key() == destination_token_fees_vault,
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: destination_token_fees_vault.key ({}) != {}",
+                ctx.accounts.destination_token_fees_vault.key(),
+                destination_token_fees_vault
+            )
+        );
+        require_msg!(
+            ctx.accounts.destination_user_ata.key() != destination_vault,
+            SwapError::IncorrectSwapAccount,
+            &format!(
+                "IncorrectSwapAccount: destination_user_ata.key ({}) == destination_vault.key ({})",
+                ctx.accounts.destination_user_ata.key(),
+                destination_vault
+            )
+        );
+        pool_token::validate_mint_authorities(
+            &ctx.accounts.pool_token_mint.to_account_info(),
+            &pool.pool_authority,
+        )?;
+        Ok(trade_direction)
+    }
+}
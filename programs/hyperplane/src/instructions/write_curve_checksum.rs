@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    curve,
+    curve::base::SwapCurve,
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{Curve, SwapPool},
+};
+
+/// Write the checksum of the curve of a pool created before curve checksums were introduced
+///
+/// The admin vouches for the current curve parameters. From then on every instruction loading the
+/// curve checks it against the checksum, like for the pools initialized with one.
+pub fn handler(ctx: Context<WriteCurveChecksum>) -> Result<event::WriteCurveChecksum> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require_msg!(
+        !pool.curve_checksum_written(),
+        SwapError::CurveChecksumWritten,
+        "CurveChecksumWritten: the pool curve already has a checksum"
+    );
+
+    // the curve must deserialize as the curve type of the pool before it is sealed
+    let _swap_curve: SwapCurve = curve!(ctx.accounts.swap_curve, pool);
+
+    let mut data = ctx.accounts.swap_curve.try_borrow_mut_data()?;
+    Curve::write_checksum(&mut data);
+    let checksum = Curve::checksum(&data);
+    pool.curve_checksum_written = u64::from(true);
+
+    msg!("Wrote curve checksum: checksum={}", checksum);
+
+    emitted!(event::WriteCurveChecksum { checksum });
+}
+
+#[derive(Accounts)]
+pub struct WriteCurveChecksum<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = swap_curve,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool, deserialized as the curve type of the pool
+    #[account(mut)]
+    pub swap_curve: UncheckedAccount<'info>,
+}
@@ -5,7 +5,11 @@
 use anchor_lang::{
     prelude::{Rent, System},
     solana_program::{
-        instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, sysvar::SysvarId,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey,
+        pubkey::Pubkey,
+        sysvar::SysvarId,
     },
     Id, InstructionData, ToAccountMetas,
 };
@@ -14,9 +18,10 @@ use arbitrary::Arbitrary;
 use derive_more::Constructor;
 
 use crate::{
-    curve::fees::Fees,
-    instructions::CurveUserParameters,
-    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    curve::{base::CurveType, calculator::AorB, fees::Fees},
+    instructions::{CurveUserParameters, SwapFill},
+    state::{Feature, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
     InitialSupply,
 };
 
@@ -30,6 +35,10 @@ pub struct Initialize {
     pub curve_parameters: CurveUserParameters,
     /// initial supply of token A and B
     pub initial_supply: InitialSupply,
+    /// leave owner trade fees in the pool vault instead of the fees vault
+    pub burn_owner_fees: bool,
+    /// require both trading tokens to be owned by the same token program
+    pub strict_token_program: bool,
 }
 
 /// Swap instruction data
@@ -40,6 +49,8 @@ pub struct Swap {
     pub amount_in: u64,
     /// Minimum amount of DESTINATION token to output, prevents excessive slippage
     pub minimum_amount_out: u64,
+    /// Unix timestamp after which the swap is rejected, prevents stale executions
+    pub deadline: Option<i64>,
 }
 
 /// Deposit instruction data
@@ -53,6 +64,42 @@ pub struct Deposit {
     pub maximum_token_a_amount: u64,
     /// Maximum token B amount to deposit, prevents excessive slippage
     pub maximum_token_b_amount: u64,
+    /// Unix timestamp after which the deposit is rejected, prevents stale executions
+    pub deadline: Option<i64>,
+}
+
+/// DepositExactAmounts instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct DepositExactAmounts {
+    /// Token A amount to deposit at most
+    pub token_a_amount: u64,
+    /// Token B amount to deposit at most
+    pub token_b_amount: u64,
+    /// Minimum pool token amount to receive, prevents excessive slippage
+    pub minimum_pool_token_amount: u64,
+}
+
+/// DepositBothExactIn instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct DepositBothExactIn {
+    /// Token A amount to deposit
+    pub token_a_amount: u64,
+    /// Token B amount to deposit
+    pub token_b_amount: u64,
+    /// Minimum pool token amount to receive, prevents excessive slippage
+    pub minimum_pool_token_amount: u64,
+}
+
+/// DepositSingle instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct DepositSingle {
+    /// Amount of the source token to deposit, fees included
+    pub source_token_amount: u64,
+    /// Minimum pool token amount to receive, prevents excessive slippage
+    pub minimum_pool_token_amount: u64,
 }
 
 /// Withdraw instruction data
@@ -66,6 +113,32 @@ pub struct Withdraw {
     pub minimum_token_a_amount: u64,
     /// Minimum amount of token B to receive, prevents excessive slippage
     pub minimum_token_b_amount: u64,
+    /// Also burn the pool tokens left to the user when they are dust, and pay out their share
+    pub burn_dust: bool,
+    /// Unix timestamp after which the withdrawal is rejected, prevents stale executions
+    pub deadline: Option<i64>,
+}
+
+/// WithdrawSingle instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct WithdrawSingle {
+    /// Amount of the destination token to receive
+    pub destination_token_amount: u64,
+    /// Maximum pool token amount to burn, prevents excessive slippage
+    pub maximum_pool_token_amount: u64,
+}
+
+/// WithdrawExactAmountsOut instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct WithdrawExactAmountsOut {
+    /// Token A amount to receive
+    pub token_a_amount: u64,
+    /// Token B amount to receive
+    pub token_b_amount: u64,
+    /// Maximum pool token amount to burn, prevents excessive slippage
+    pub maximum_pool_token_amount: u64,
 }
 
 /// WithdrawFees instruction data
@@ -84,6 +157,114 @@ pub struct UpdatePoolConfig {
     pub value: UpdatePoolConfigValue,
 }
 
+/// UpdateFeatureSet instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct UpdateFeatureSet {
+    /// Feature to toggle
+    pub feature: Feature,
+    /// Whether the feature is enabled
+    pub enabled: bool,
+}
+
+/// SetConversionProgram instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct SetConversionProgram {
+    /// Slot of the feature set conversion programs to set
+    pub index: u16,
+    /// Conversion program allowed for `migrate_mint`, default pubkey to clear the slot
+    pub program: Pubkey,
+}
+
+/// SetFeeTier instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct SetFeeTier {
+    /// Index of the tier to replace, or the number of tiers set to append one
+    pub tier: u16,
+    /// Fees of the tier
+    pub fees: Fees,
+}
+
+/// InitializePoolWithFeeTier instruction data
+#[derive(Debug, PartialEq, Constructor)]
+pub struct InitializeWithFeeTier {
+    /// index of the fee tier registry tier to take the swap fees from
+    pub fee_tier: u16,
+    /// swap curve info for pool, including CurveType and anything
+    /// else that may be required
+    pub curve_parameters: CurveUserParameters,
+    /// initial supply of token A and B
+    pub initial_supply: InitialSupply,
+    /// leave owner trade fees in the pool vault instead of the fees vault
+    pub burn_owner_fees: bool,
+    /// require both trading tokens to be owned by the same token program
+    pub strict_token_program: bool,
+}
+
+/// RebalanceAcrossPools instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct RebalanceAcrossPools {
+    /// Amount of source pool tokens to burn
+    pub source_pool_token_amount: u64,
+    /// Amount of destination pool tokens to mint
+    pub destination_pool_token_amount: u64,
+}
+
+/// RequestWithdraw instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct RequestWithdraw {
+    /// Amount of pool tokens to queue for withdrawal
+    pub pool_token_amount: u64,
+    /// Minimum amount of token A to receive when claimed by anyone but the owner
+    pub minimum_token_a_amount: u64,
+    /// Minimum amount of token B to receive when claimed by anyone but the owner
+    pub minimum_token_b_amount: u64,
+}
+
+/// ProposePoolConfig instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct ProposePoolConfig {
+    /// Update mode
+    pub mode: UpdatePoolConfigMode,
+    /// Value to set
+    pub value: UpdatePoolConfigValue,
+    /// Slots to wait before the change can be executed
+    pub approval_window_slots: u64,
+}
+
+/// SwapRouteTwoHop instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct SwapRouteTwoHop {
+    /// SOURCE amount to transfer to the first pool
+    pub amount_in: u64,
+    /// Minimum amount of DESTINATION token to receive from the second pool
+    pub minimum_amount_out: u64,
+}
+
+/// SwapBatch instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct SwapBatch {
+    /// Swaps executed one after the other against the pool
+    pub fills: Vec<SwapFill>,
+    /// Unix timestamp after which the batch is rejected, prevents stale executions
+    pub deadline: Option<i64>,
+}
+
+/// FlashBorrow instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct FlashBorrow {
+    /// Amount of the trading token to borrow from the pool vault
+    pub amount: u64,
+}
+
+/// AssertPoolPrice instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct AssertPoolPrice {
+    /// Minimum pool price, token A in token B native units scaled by `PRICE_BOUNDS_SCALE`
+    pub min: u64,
+    /// Maximum pool price
+    pub max: u64,
+}
+
 impl From<UpdatePoolConfig> for crate::instruction::UpdatePoolConfig {
     fn from(value: UpdatePoolConfig) -> Self {
         crate::instruction::UpdatePoolConfig {
@@ -121,6 +302,8 @@ pub fn initialize_pool(
                 initial_supply_a,
                 initial_supply_b,
             },
+        burn_owner_fees,
+        strict_token_program,
     }: Initialize,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::InitializePool {
@@ -128,6 +311,8 @@ pub fn initialize_pool(
         initial_supply_b,
         fees,
         curve_parameters,
+        burn_owner_fees,
+        strict_token_program,
     }
     .data();
 
@@ -161,6 +346,81 @@ pub fn initialize_pool(
     })
 }
 
+/// Creates an 'initialize_test_pool' instruction, deriving the pool and all of its PDAs from
+/// the admin and `seed`
+#[cfg(feature = "dev-mode")]
+pub fn initialize_test_pool(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    seed: [u8; 32],
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    admin_token_a_ata: &Pubkey,
+    admin_token_b_ata: &Pubkey,
+    admin_pool_token_ata: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    token_a_program_id: &Pubkey,
+    token_b_program_id: &Pubkey,
+    Initialize {
+        fees,
+        curve_parameters,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    }: Initialize,
+) -> Result<Instruction, ProgramError> {
+    let (pool, _pool_bump) = seeds::pda::test_pool_pda_program_id(program_id, admin, &seed);
+    let seeds::pda::InitPoolPdas {
+        curve,
+        authority,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+    } = seeds::pda::init_pool_pdas_program_id(program_id, &pool, token_a_mint, token_b_mint);
+
+    let data = super::instructions::InitializeTestPoolArgs {
+        seed,
+        curve_parameters,
+        fees,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    }
+    .data();
+
+    // not an anchor instruction of the program, so not in its `accounts` module
+    let accounts = super::__client_accounts_initialize_test_pool::InitializeTestPool {
+        admin: *admin,
+        pool,
+        swap_curve: curve,
+        pool_authority: authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+        admin_token_a_ata: *admin_token_a_ata,
+        admin_token_b_ata: *admin_token_b_ata,
+        admin_pool_token_ata: *admin_pool_token_ata,
+        system_program: System::id(),
+        rent: Rent::id(),
+        pool_token_program: *pool_token_program_id,
+        token_a_token_program: *token_a_program_id,
+        token_b_token_program: *token_b_program_id,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Creates a 'deposit' instruction.
 pub fn deposit(
     program_id: &Pubkey,
@@ -179,16 +439,81 @@ pub fn deposit(
     pool_token_program: &Pubkey,
     token_a_program: &Pubkey,
     token_b_program: &Pubkey,
+    referrer: Option<&Pubkey>,
+    allowed_liquidity_provider: Option<&Pubkey>,
     Deposit {
         pool_token_amount,
         maximum_token_a_amount,
         maximum_token_b_amount,
+        deadline,
     }: Deposit,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::Deposit {
         pool_token_amount,
         maximum_token_a_amount,
         maximum_token_b_amount,
+        deadline,
+    }
+    .data();
+
+    let accounts = super::accounts::Deposit {
+        signer: *user_transfer_authority_pubkey,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        token_a_user_ata: *user_token_a_ata,
+        token_b_user_ata: *user_token_b_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+        referrer: referrer.copied(),
+        allowed_liquidity_provider: allowed_liquidity_provider.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deposit_exact_amounts' instruction.
+pub fn deposit_exact_amounts(
+    program_id: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    user_token_a_ata: &Pubkey,
+    user_token_b_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+    referrer: Option<&Pubkey>,
+    allowed_liquidity_provider: Option<&Pubkey>,
+    DepositExactAmounts {
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
+    }: DepositExactAmounts,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::DepositExactAmounts {
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
     }
     .data();
 
@@ -208,6 +533,71 @@ pub fn deposit(
         pool_token_program: *pool_token_program,
         token_a_token_program: *token_a_program,
         token_b_token_program: *token_b_program,
+        referrer: referrer.copied(),
+        allowed_liquidity_provider: allowed_liquidity_provider.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deposit_both_exact_in' instruction.
+pub fn deposit_both_exact_in(
+    program_id: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    user_token_a_ata: &Pubkey,
+    user_token_b_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+    allowed_liquidity_provider: Option<&Pubkey>,
+    DepositBothExactIn {
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
+    }: DepositBothExactIn,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::DepositBothExactIn {
+        token_a_amount,
+        token_b_amount,
+        minimum_pool_token_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::DepositBothExactIn {
+        signer: *user_transfer_authority_pubkey,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        token_a_user_ata: *user_token_a_ata,
+        token_b_user_ata: *user_token_b_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+        allowed_liquidity_provider: allowed_liquidity_provider.copied(),
     }
     .to_account_metas(None);
 
@@ -242,12 +632,16 @@ pub fn withdraw(
         pool_token_amount,
         minimum_token_a_amount,
         minimum_token_b_amount,
+        burn_dust,
+        deadline,
     }: Withdraw,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::Withdraw {
         pool_token_amount,
         minimum_token_a_amount,
         minimum_token_b_amount,
+        burn_dust,
+        deadline,
     }
     .data();
 
@@ -279,6 +673,67 @@ pub fn withdraw(
     })
 }
 
+/// Creates a 'withdraw_exact_amounts_out' instruction.
+pub fn withdraw_exact_amounts_out(
+    program_id: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    user_token_a_ata: &Pubkey,
+    user_token_b_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+    WithdrawExactAmountsOut {
+        token_a_amount,
+        token_b_amount,
+        maximum_pool_token_amount,
+    }: WithdrawExactAmountsOut,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::WithdrawExactAmountsOut {
+        token_a_amount,
+        token_b_amount,
+        maximum_pool_token_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::WithdrawExactAmountsOut {
+        signer: *user_transfer_authority,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        token_a_user_ata: *user_token_a_ata,
+        token_b_user_ata: *user_token_b_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Creates a 'swap' instruction.
 pub fn swap(
     program_id: &Pubkey,
@@ -296,14 +751,19 @@ pub fn swap(
     source_token_host_fees: Option<&Pubkey>,
     source_token_program_id: &Pubkey,
     destination_token_program_id: &Pubkey,
+    allowed_swap_taker: Option<&Pubkey>,
+    oracle: Option<&Pubkey>,
+    destination_token_host_fees: Option<&Pubkey>,
     Swap {
         amount_in,
         minimum_amount_out,
+        deadline,
     }: Swap,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::Swap {
         amount_in,
         minimum_amount_out,
+        deadline,
     }
     .data();
 
@@ -322,6 +782,9 @@ pub fn swap(
         source_token_host_fees_account: source_token_host_fees.copied(),
         source_token_program: *source_token_program_id,
         destination_token_program: *destination_token_program_id,
+        allowed_swap_taker: allowed_swap_taker.copied(),
+        oracle: oracle.copied(),
+        destination_token_host_fees_account: destination_token_host_fees.copied(),
     }
     .to_account_metas(None);
 
@@ -332,16 +795,72 @@ pub fn swap(
     })
 }
 
-/// Creates a 'withdraw_fees' instruction.
-pub fn withdraw_fees(
+/// Creates a 'swap_batch' instruction, taking the same accounts as a 'swap'.
+pub fn swap_batch(
+    program_id: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    source_mint: &Pubkey,
+    destination_mint: &Pubkey,
+    source_vault: &Pubkey,
+    destination_vault: &Pubkey,
+    source_token_fees_vault: &Pubkey,
+    source_user_ata: &Pubkey,
+    destination_user_ata: &Pubkey,
+    source_token_host_fees: Option<&Pubkey>,
+    source_token_program_id: &Pubkey,
+    destination_token_program_id: &Pubkey,
+    allowed_swap_taker: Option<&Pubkey>,
+    oracle: Option<&Pubkey>,
+    destination_token_host_fees: Option<&Pubkey>,
+    SwapBatch { fills, deadline }: SwapBatch,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SwapBatch { fills, deadline }.data();
+
+    let accounts = super::accounts::Swap {
+        signer: *user_transfer_authority,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        source_mint: *source_mint,
+        destination_mint: *destination_mint,
+        source_vault: *source_vault,
+        destination_vault: *destination_vault,
+        source_token_fees_vault: *source_token_fees_vault,
+        source_user_ata: *source_user_ata,
+        destination_user_ata: *destination_user_ata,
+        source_token_host_fees_account: source_token_host_fees.copied(),
+        source_token_program: *source_token_program_id,
+        destination_token_program: *destination_token_program_id,
+        allowed_swap_taker: allowed_swap_taker.copied(),
+        oracle: oracle.copied(),
+        destination_token_host_fees_account: destination_token_host_fees.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw_fees' instruction.
+pub fn withdraw_fees(
     program_id: &Pubkey,
     admin: &Pubkey,
     pool: &Pubkey,
     pool_authority: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
     fees_mint: &Pubkey,
     fees_vault: &Pubkey,
     admin_fees_ata: &Pubkey,
     fees_token_program: &Pubkey,
+    default_host_fees: &Pubkey,
     WithdrawFees {
         requested_token_amount: requested_pool_token_amount,
     }: WithdrawFees,
@@ -355,10 +874,49 @@ pub fn withdraw_fees(
         admin: *admin,
         pool: *pool,
         pool_authority: *pool_authority,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
         fees_mint: *fees_mint,
         fees_vault: *fees_vault,
         admin_fees_ata: *admin_fees_ata,
         fees_token_program: *fees_token_program,
+        default_host_fees: *default_host_fees,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'revoke_vault_delegates' instruction.
+pub fn revoke_vault_delegates(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    token_a_token_program: &Pubkey,
+    token_b_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RevokeVaultDelegates {}.data();
+
+    let accounts = super::accounts::RevokeVaultDelegates {
+        signer: *signer,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        token_a_token_program: *token_a_token_program,
+        token_b_token_program: *token_b_token_program,
     }
     .to_account_metas(None);
 
@@ -374,6 +932,11 @@ pub fn update_pool_config(
     program_id: &Pubkey,
     admin: &Pubkey,
     pool: &Pubkey,
+    config_history: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    feature_set: Option<&Pubkey>,
     UpdatePoolConfig { mode, value }: UpdatePoolConfig,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::UpdatePoolConfig {
@@ -385,6 +948,231 @@ pub fn update_pool_config(
     let accounts = super::accounts::UpdatePoolConfig {
         admin: *admin,
         pool: *pool,
+        config_history: *config_history,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        feature_set: feature_set.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'add_swap_taker' instruction.
+pub fn add_swap_taker(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    taker: &Pubkey,
+    allowed_swap_taker: &Pubkey,
+    feature_set: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::AddSwapTaker {}.data();
+
+    let accounts = super::accounts::AddSwapTaker {
+        admin: *admin,
+        pool: *pool,
+        taker: *taker,
+        allowed_swap_taker: *allowed_swap_taker,
+        feature_set: *feature_set,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'remove_swap_taker' instruction.
+pub fn remove_swap_taker(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    allowed_swap_taker: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RemoveSwapTaker {}.data();
+
+    let accounts = super::accounts::RemoveSwapTaker {
+        admin: *admin,
+        pool: *pool,
+        allowed_swap_taker: *allowed_swap_taker,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'add_liquidity_provider' instruction.
+pub fn add_liquidity_provider(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    provider: &Pubkey,
+    allowed_liquidity_provider: &Pubkey,
+    feature_set: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::AddLiquidityProvider {}.data();
+
+    let accounts = super::accounts::AddLiquidityProvider {
+        admin: *admin,
+        pool: *pool,
+        provider: *provider,
+        allowed_liquidity_provider: *allowed_liquidity_provider,
+        feature_set: *feature_set,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'remove_liquidity_provider' instruction.
+pub fn remove_liquidity_provider(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    allowed_liquidity_provider: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RemoveLiquidityProvider {}.data();
+
+    let accounts = super::accounts::RemoveLiquidityProvider {
+        admin: *admin,
+        pool: *pool,
+        allowed_liquidity_provider: *allowed_liquidity_provider,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_feature_set' instruction.
+pub fn initialize_feature_set(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    program_data: &Pubkey,
+    feature_set: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeFeatureSet {}.data();
+
+    let accounts = super::accounts::InitializeFeatureSet {
+        admin: *admin,
+        program_data: *program_data,
+        feature_set: *feature_set,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'update_feature_set' instruction.
+pub fn update_feature_set(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    feature_set: &Pubkey,
+    UpdateFeatureSet { feature, enabled }: UpdateFeatureSet,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UpdateFeatureSet {
+        feature: feature as u16,
+        enabled,
+    }
+    .data();
+
+    let accounts = super::accounts::UpdateFeatureSet {
+        admin: *admin,
+        feature_set: *feature_set,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_conversion_program' instruction.
+pub fn set_conversion_program(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    feature_set: &Pubkey,
+    SetConversionProgram { index, program }: SetConversionProgram,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SetConversionProgram { index, program }.data();
+
+    let accounts = super::accounts::SetConversionProgram {
+        admin: *admin,
+        feature_set: *feature_set,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_fee_tier_registry' instruction.
+pub fn initialize_fee_tier_registry(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    program_data: &Pubkey,
+    fee_tier_registry: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeFeeTierRegistry {}.data();
+
+    let accounts = super::accounts::InitializeFeeTierRegistry {
+        admin: *admin,
+        program_data: *program_data,
+        fee_tier_registry: *fee_tier_registry,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_fee_tier' instruction.
+pub fn set_fee_tier(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    fee_tier_registry: &Pubkey,
+    SetFeeTier { tier, fees }: SetFeeTier,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SetFeeTier { tier, fees }.data();
+
+    let accounts = super::accounts::SetFeeTier {
+        admin: *admin,
+        fee_tier_registry: *fee_tier_registry,
     }
     .to_account_metas(None);
 
@@ -394,3 +1182,2097 @@ pub fn update_pool_config(
         data,
     })
 }
+
+/// Creates an 'initialize_pool_with_fee_tier' instruction, deriving the pool PDAs from `pool`
+pub fn initialize_pool_with_fee_tier(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    admin_token_a_ata: &Pubkey,
+    admin_token_b_ata: &Pubkey,
+    admin_pool_token_ata: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    token_a_program_id: &Pubkey,
+    token_b_program_id: &Pubkey,
+    InitializeWithFeeTier {
+        fee_tier,
+        curve_parameters,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    }: InitializeWithFeeTier,
+) -> Result<Instruction, ProgramError> {
+    let seeds::pda::InitPoolPdas {
+        curve,
+        authority,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+    } = seeds::pda::init_pool_pdas_program_id(program_id, pool, token_a_mint, token_b_mint);
+    let (fee_tier_registry, _bump) = seeds::pda::fee_tier_registry_pda_program_id(program_id);
+
+    let data = super::instruction::InitializePoolWithFeeTier {
+        curve_parameters,
+        fee_tier,
+        initial_supply,
+        burn_owner_fees,
+        strict_token_program,
+    }
+    .data();
+
+    let accounts = super::accounts::InitializePoolWithFeeTier {
+        admin: *admin,
+        pool: *pool,
+        swap_curve: curve,
+        pool_authority: authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+        admin_token_a_ata: *admin_token_a_ata,
+        admin_token_b_ata: *admin_token_b_ata,
+        admin_pool_token_ata: *admin_pool_token_ata,
+        system_program: System::id(),
+        rent: Rent::id(),
+        pool_token_program: *pool_token_program_id,
+        token_a_token_program: *token_a_program_id,
+        token_b_token_program: *token_b_program_id,
+        fee_tier_registry,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'rebalance_across_pools' instruction.
+pub fn rebalance_across_pools(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    source_pool: &Pubkey,
+    source_swap_curve: &Pubkey,
+    source_pool_authority: &Pubkey,
+    source_token_a_vault: &Pubkey,
+    source_token_b_vault: &Pubkey,
+    source_pool_token_mint: &Pubkey,
+    source_token_a_fees_vault: &Pubkey,
+    source_token_b_fees_vault: &Pubkey,
+    destination_pool: &Pubkey,
+    destination_swap_curve: &Pubkey,
+    destination_pool_authority: &Pubkey,
+    destination_token_a_vault: &Pubkey,
+    destination_token_b_vault: &Pubkey,
+    destination_pool_token_mint: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    admin_token_a_ata: &Pubkey,
+    admin_token_b_ata: &Pubkey,
+    admin_source_pool_token_ata: &Pubkey,
+    admin_destination_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+    RebalanceAcrossPools {
+        source_pool_token_amount,
+        destination_pool_token_amount,
+    }: RebalanceAcrossPools,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RebalanceAcrossPools {
+        source_pool_token_amount,
+        destination_pool_token_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::RebalanceAcrossPools {
+        admin: *admin,
+        source_pool: *source_pool,
+        source_swap_curve: *source_swap_curve,
+        source_pool_authority: *source_pool_authority,
+        source_token_a_vault: *source_token_a_vault,
+        source_token_b_vault: *source_token_b_vault,
+        source_pool_token_mint: *source_pool_token_mint,
+        source_token_a_fees_vault: *source_token_a_fees_vault,
+        source_token_b_fees_vault: *source_token_b_fees_vault,
+        destination_pool: *destination_pool,
+        destination_swap_curve: *destination_swap_curve,
+        destination_pool_authority: *destination_pool_authority,
+        destination_token_a_vault: *destination_token_a_vault,
+        destination_token_b_vault: *destination_token_b_vault,
+        destination_pool_token_mint: *destination_pool_token_mint,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        admin_token_a_ata: *admin_token_a_ata,
+        admin_token_b_ata: *admin_token_b_ata,
+        admin_source_pool_token_ata: *admin_source_pool_token_ata,
+        admin_destination_pool_token_ata: *admin_destination_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'request_withdraw' instruction.
+pub fn request_withdraw(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_token_mint: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    withdrawal_ticket: &Pubkey,
+    pool_token_vault: &Pubkey,
+    pool_token_program: &Pubkey,
+    RequestWithdraw {
+        pool_token_amount,
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+    }: RequestWithdraw,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RequestWithdraw {
+        pool_token_amount,
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::RequestWithdraw {
+        owner: *owner,
+        pool: *pool,
+        pool_token_mint: *pool_token_mint,
+        pool_token_user_ata: *user_pool_token_ata,
+        withdrawal_ticket: *withdrawal_ticket,
+        pool_token_vault: *pool_token_vault,
+        pool_token_program: *pool_token_program,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'claim_withdraw' instruction.
+pub fn claim_withdraw(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    withdrawal_ticket: &Pubkey,
+    withdrawal_ticket_pool_token_vault: &Pubkey,
+    user_token_a_ata: &Pubkey,
+    user_token_b_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ClaimWithdraw {}.data();
+
+    let accounts = super::accounts::ClaimWithdraw {
+        signer: *signer,
+        owner: *owner,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        withdrawal_ticket: *withdrawal_ticket,
+        pool_token_vault: *withdrawal_ticket_pool_token_vault,
+        token_a_user_ata: *user_token_a_ata,
+        token_b_user_ata: *user_token_b_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'propose_pool_config' instruction.
+pub fn propose_pool_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    proposed_config_change: &Pubkey,
+    ProposePoolConfig {
+        mode,
+        value,
+        approval_window_slots,
+    }: ProposePoolConfig,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ProposePoolConfig {
+        mode: mode as u16,
+        value: value.to_bytes(),
+        approval_window_slots,
+    }
+    .data();
+
+    let accounts = super::accounts::ProposePoolConfig {
+        admin: *admin,
+        pool: *pool,
+        proposed_config_change: *proposed_config_change,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'execute_pool_config' instruction.
+pub fn execute_pool_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    proposed_config_change: &Pubkey,
+    config_history: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    feature_set: Option<&Pubkey>,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ExecutePoolConfig {}.data();
+
+    let accounts = super::accounts::ExecutePoolConfig {
+        admin: *admin,
+        pool: *pool,
+        proposed_config_change: *proposed_config_change,
+        config_history: *config_history,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        feature_set: feature_set.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_config_history' instruction.
+pub fn initialize_config_history(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    config_history: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeConfigHistory {}.data();
+
+    let accounts = super::accounts::InitializeConfigHistory {
+        admin: *admin,
+        pool: *pool,
+        config_history: *config_history,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_user_stats' instruction.
+pub fn initialize_user_stats(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    user_stats: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeUserStats {}.data();
+
+    let accounts = super::accounts::InitializeUserStats {
+        owner: *owner,
+        pool: *pool,
+        user_stats: *user_stats,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Appends the user stats account to a 'swap' instruction so that the swap is recorded
+pub fn with_user_stats(mut instruction: Instruction, user_stats: &Pubkey) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(*user_stats, false));
+    instruction
+}
+
+/// Creates a 'create_lp_airdrop' instruction.
+pub fn create_lp_airdrop(
+    program_id: &Pubkey,
+    sponsor: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    pool_token_mint: &Pubkey,
+    reward_mint: &Pubkey,
+    airdrop: &Pubkey,
+    reward_vault: &Pubkey,
+    pool_token_vault: &Pubkey,
+    sponsor_reward_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    reward_token_program: &Pubkey,
+    reward_amount: u64,
+    snapshot_slot: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::CreateLpAirdrop {
+        reward_amount,
+        snapshot_slot,
+    }
+    .data();
+
+    let accounts = super::accounts::CreateLpAirdrop {
+        sponsor: *sponsor,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        pool_token_mint: *pool_token_mint,
+        reward_mint: *reward_mint,
+        airdrop: *airdrop,
+        reward_vault: *reward_vault,
+        pool_token_vault: *pool_token_vault,
+        sponsor_reward_ata: *sponsor_reward_ata,
+        system_program: System::id(),
+        pool_token_program: *pool_token_program,
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'register_lp_airdrop' instruction.
+pub fn register_lp_airdrop(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_token_mint: &Pubkey,
+    airdrop: &Pubkey,
+    pool_token_vault: &Pubkey,
+    position: &Pubkey,
+    pool_token_user_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    pool_token_amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RegisterLpAirdrop { pool_token_amount }.data();
+
+    let accounts = super::accounts::RegisterLpAirdrop {
+        owner: *owner,
+        pool: *pool,
+        pool_token_mint: *pool_token_mint,
+        airdrop: *airdrop,
+        pool_token_vault: *pool_token_vault,
+        position: *position,
+        pool_token_user_ata: *pool_token_user_ata,
+        system_program: System::id(),
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'claim_lp_airdrop' instruction.
+pub fn claim_lp_airdrop(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    pool_token_mint: &Pubkey,
+    reward_mint: &Pubkey,
+    airdrop: &Pubkey,
+    reward_vault: &Pubkey,
+    pool_token_vault: &Pubkey,
+    position: &Pubkey,
+    reward_user_ata: &Pubkey,
+    pool_token_user_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    reward_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ClaimLpAirdrop {}.data();
+
+    let accounts = super::accounts::ClaimLpAirdrop {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        pool_token_mint: *pool_token_mint,
+        reward_mint: *reward_mint,
+        airdrop: *airdrop,
+        reward_vault: *reward_vault,
+        pool_token_vault: *pool_token_vault,
+        position: *position,
+        reward_user_ata: *reward_user_ata,
+        pool_token_user_ata: *pool_token_user_ata,
+        pool_token_program: *pool_token_program,
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'close_lp_airdrop' instruction.
+pub fn close_lp_airdrop(
+    program_id: &Pubkey,
+    sponsor: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    reward_mint: &Pubkey,
+    airdrop: &Pubkey,
+    reward_vault: &Pubkey,
+    sponsor_reward_ata: &Pubkey,
+    reward_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::CloseLpAirdrop {}.data();
+
+    let accounts = super::accounts::CloseLpAirdrop {
+        sponsor: *sponsor,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        reward_mint: *reward_mint,
+        airdrop: *airdrop,
+        reward_vault: *reward_vault,
+        sponsor_reward_ata: *sponsor_reward_ata,
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'lock_liquidity' instruction.
+pub fn lock_liquidity(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    pool_token_mint: &Pubkey,
+    position: &Pubkey,
+    pool_token_vault: &Pubkey,
+    pool_token_user_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    pool_token_amount: u64,
+    lock_duration_slots: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::LockLiquidity {
+        pool_token_amount,
+        lock_duration_slots,
+    }
+    .data();
+
+    let accounts = super::accounts::LockLiquidity {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        pool_token_mint: *pool_token_mint,
+        position: *position,
+        pool_token_vault: *pool_token_vault,
+        pool_token_user_ata: *pool_token_user_ata,
+        system_program: System::id(),
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'unlock_liquidity' instruction.
+pub fn unlock_liquidity(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    pool_token_mint: &Pubkey,
+    position: &Pubkey,
+    pool_token_vault: &Pubkey,
+    pool_token_user_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UnlockLiquidity {}.data();
+
+    let accounts = super::accounts::UnlockLiquidity {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        pool_token_mint: *pool_token_mint,
+        position: *position,
+        pool_token_vault: *pool_token_vault,
+        pool_token_user_ata: *pool_token_user_ata,
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'donate_to_pool' instruction.
+pub fn donate_to_pool(
+    program_id: &Pubkey,
+    donor: &Pubkey,
+    pool: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    token_a_user_ata: &Pubkey,
+    token_b_user_ata: &Pubkey,
+    token_a_token_program: &Pubkey,
+    token_b_token_program: &Pubkey,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::DonateToPool {
+        token_a_amount,
+        token_b_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::DonateToPool {
+        signer: *donor,
+        pool: *pool,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        token_a_user_ata: *token_a_user_ata,
+        token_b_user_ata: *token_b_user_ata,
+        token_a_token_program: *token_a_token_program,
+        token_b_token_program: *token_b_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'skim_lamports' instruction.
+pub fn skim_lamports(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    treasury: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SkimLamports {}.data();
+
+    let accounts = super::accounts::SkimLamports {
+        admin: *admin,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        treasury: *treasury,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_default_host_fees' instruction.
+pub fn initialize_default_host_fees(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    default_host_fees: &Pubkey,
+    recipient: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeDefaultHostFees {
+        recipient: *recipient,
+    }
+    .data();
+
+    let accounts = super::accounts::InitializeDefaultHostFees {
+        admin: *admin,
+        pool: *pool,
+        default_host_fees: *default_host_fees,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'update_default_host_fees' instruction.
+pub fn update_default_host_fees(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    default_host_fees: &Pubkey,
+    recipient: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UpdateDefaultHostFees {
+        recipient: *recipient,
+    }
+    .data();
+
+    let accounts = super::accounts::UpdateDefaultHostFees {
+        admin: *admin,
+        pool: *pool,
+        default_host_fees: *default_host_fees,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'claim_default_host_fees' instruction.
+pub fn claim_default_host_fees(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    default_host_fees: &Pubkey,
+    fees_mint: &Pubkey,
+    fees_vault: &Pubkey,
+    recipient_fees_ata: &Pubkey,
+    fees_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ClaimDefaultHostFees {}.data();
+
+    let accounts = super::accounts::ClaimDefaultHostFees {
+        signer: *signer,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        default_host_fees: *default_host_fees,
+        fees_mint: *fees_mint,
+        fees_vault: *fees_vault,
+        recipient_fees_ata: *recipient_fees_ata,
+        fees_token_program: *fees_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deposit_single' instruction.
+pub fn deposit_single(
+    program_id: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    source_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    source_token_fees_vault: &Pubkey,
+    user_source_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    source_token_program: &Pubkey,
+    allowed_liquidity_provider: Option<&Pubkey>,
+    DepositSingle {
+        source_token_amount,
+        minimum_pool_token_amount,
+    }: DepositSingle,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::DepositSingle {
+        source_token_amount,
+        minimum_pool_token_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::DepositSingle {
+        signer: *user_transfer_authority,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        source_mint: *source_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        source_token_fees_vault: *source_token_fees_vault,
+        source_user_ata: *user_source_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        source_token_program: *source_token_program,
+        allowed_liquidity_provider: allowed_liquidity_provider.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw_single' instruction.
+pub fn withdraw_single(
+    program_id: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    destination_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    destination_token_fees_vault: &Pubkey,
+    user_destination_ata: &Pubkey,
+    user_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    destination_token_program: &Pubkey,
+    WithdrawSingle {
+        destination_token_amount,
+        maximum_pool_token_amount,
+    }: WithdrawSingle,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::WithdrawSingle {
+        destination_token_amount,
+        maximum_pool_token_amount,
+    }
+    .data();
+
+    let accounts = super::accounts::WithdrawSingle {
+        signer: *user_transfer_authority,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        destination_mint: *destination_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        destination_token_fees_vault: *destination_token_fees_vault,
+        destination_user_ata: *user_destination_ata,
+        pool_token_user_ata: *user_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        destination_token_program: *destination_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap_route_two_hop' instruction.
+pub fn swap_route_two_hop(
+    program_id: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    first_pool: &Pubkey,
+    first_swap_curve: &Pubkey,
+    first_pool_authority: &Pubkey,
+    second_pool: &Pubkey,
+    second_swap_curve: &Pubkey,
+    second_pool_authority: &Pubkey,
+    source_mint: &Pubkey,
+    intermediate_mint: &Pubkey,
+    destination_mint: &Pubkey,
+    first_source_vault: &Pubkey,
+    first_destination_vault: &Pubkey,
+    first_source_token_fees_vault: &Pubkey,
+    second_source_vault: &Pubkey,
+    second_destination_vault: &Pubkey,
+    second_source_token_fees_vault: &Pubkey,
+    source_user_ata: &Pubkey,
+    intermediate_user_ata: &Pubkey,
+    destination_user_ata: &Pubkey,
+    source_token_program_id: &Pubkey,
+    intermediate_token_program_id: &Pubkey,
+    destination_token_program_id: &Pubkey,
+    first_allowed_swap_taker: Option<&Pubkey>,
+    second_allowed_swap_taker: Option<&Pubkey>,
+    SwapRouteTwoHop {
+        amount_in,
+        minimum_amount_out,
+    }: SwapRouteTwoHop,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SwapRouteTwoHop {
+        amount_in,
+        minimum_amount_out,
+    }
+    .data();
+
+    let accounts = super::accounts::SwapRouteTwoHop {
+        signer: *user_transfer_authority,
+        first_pool: *first_pool,
+        first_swap_curve: *first_swap_curve,
+        first_pool_authority: *first_pool_authority,
+        second_pool: *second_pool,
+        second_swap_curve: *second_swap_curve,
+        second_pool_authority: *second_pool_authority,
+        source_mint: *source_mint,
+        intermediate_mint: *intermediate_mint,
+        destination_mint: *destination_mint,
+        first_source_vault: *first_source_vault,
+        first_destination_vault: *first_destination_vault,
+        first_source_token_fees_vault: *first_source_token_fees_vault,
+        second_source_vault: *second_source_vault,
+        second_destination_vault: *second_destination_vault,
+        second_source_token_fees_vault: *second_source_token_fees_vault,
+        source_user_ata: *source_user_ata,
+        intermediate_user_ata: *intermediate_user_ata,
+        destination_user_ata: *destination_user_ata,
+        source_token_program: *source_token_program_id,
+        intermediate_token_program: *intermediate_token_program_id,
+        destination_token_program: *destination_token_program_id,
+        first_allowed_swap_taker: first_allowed_swap_taker.copied(),
+        second_allowed_swap_taker: second_allowed_swap_taker.copied(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'flash_borrow' instruction, to be followed by a 'flash_repay' of the same pool in
+/// the transaction.
+pub fn flash_borrow(
+    program_id: &Pubkey,
+    borrower: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    token_mint: &Pubkey,
+    token_vault: &Pubkey,
+    destination_user_ata: &Pubkey,
+    token_program_id: &Pubkey,
+    FlashBorrow { amount }: FlashBorrow,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::FlashBorrow { amount }.data();
+
+    let accounts = super::accounts::FlashBorrow {
+        signer: *borrower,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        token_mint: *token_mint,
+        token_vault: *token_vault,
+        destination_user_ata: *destination_user_ata,
+        token_program: *token_program_id,
+        instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'flash_repay' instruction.
+pub fn flash_repay(
+    program_id: &Pubkey,
+    borrower: &Pubkey,
+    pool: &Pubkey,
+    token_mint: &Pubkey,
+    token_vault: &Pubkey,
+    source_user_ata: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::FlashRepay {}.data();
+
+    let accounts = super::accounts::FlashRepay {
+        signer: *borrower,
+        pool: *pool,
+        token_mint: *token_mint,
+        token_vault: *token_vault,
+        source_user_ata: *source_user_ata,
+        token_program: *token_program_id,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'assert_pool_price' instruction.
+pub fn assert_pool_price(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    AssertPoolPrice { min, max }: AssertPoolPrice,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::AssertPoolPrice { min, max }.data();
+
+    let accounts = super::accounts::AssertPoolPrice {
+        pool: *pool,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'close_pool' instruction.
+pub fn close_pool(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    admin_token_a_ata: &Pubkey,
+    admin_token_b_ata: &Pubkey,
+    admin_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ClosePool {}.data();
+
+    let accounts = super::accounts::ClosePool {
+        admin: *admin,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        pool_token_mint: *pool_token_mint,
+        admin_token_a_ata: *admin_token_a_ata,
+        admin_token_b_ata: *admin_token_b_ata,
+        admin_pool_token_ata: *admin_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_protocol_fee_config' instruction.
+pub fn initialize_protocol_fee_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    program_data: &Pubkey,
+    protocol_fee_config: &Pubkey,
+    authority: Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeProtocolFeeConfig { authority }.data();
+
+    let accounts = super::accounts::InitializeProtocolFeeConfig {
+        admin: *admin,
+        program_data: *program_data,
+        protocol_fee_config: *protocol_fee_config,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw_protocol_fees' instruction.
+pub fn withdraw_protocol_fees(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    protocol_fee_config: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    fees_mint: &Pubkey,
+    fees_vault: &Pubkey,
+    authority_fees_ata: &Pubkey,
+    fees_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::WithdrawProtocolFees {}.data();
+
+    let accounts = super::accounts::WithdrawProtocolFees {
+        authority: *authority,
+        protocol_fee_config: *protocol_fee_config,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        fees_mint: *fees_mint,
+        fees_vault: *fees_vault,
+        authority_fees_ata: *authority_fees_ata,
+        fees_token_program: *fees_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'propose_admin' instruction.
+pub fn propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    pending_admin: Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ProposeAdmin { pending_admin }.data();
+
+    let accounts = super::accounts::ProposeAdmin {
+        admin: *admin,
+        pool: *pool,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_guardian' instruction.
+pub fn set_guardian(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    guardian: Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SetGuardian { guardian }.data();
+
+    let accounts = super::accounts::SetGuardian {
+        admin: *admin,
+        pool: *pool,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'guardian_pause_pool' instruction, the config change must only pause the pool
+pub fn guardian_pause_pool(
+    program_id: &Pubkey,
+    guardian: &Pubkey,
+    pool: &Pubkey,
+    config_history: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    UpdatePoolConfig { mode, value }: UpdatePoolConfig,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::GuardianPausePool {
+        mode: mode as u16,
+        value: value.to_bytes(),
+    }
+    .data();
+
+    let accounts = super::accounts::GuardianPausePool {
+        guardian: *guardian,
+        pool: *pool,
+        config_history: *config_history,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'accept_admin' instruction.
+pub fn accept_admin(
+    program_id: &Pubkey,
+    pending_admin: &Pubkey,
+    pool: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::AcceptAdmin {}.data();
+
+    let accounts = super::accounts::AcceptAdmin {
+        pending_admin: *pending_admin,
+        pool: *pool,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize_protocol_fee_vault' instruction.
+pub fn initialize_protocol_fee_vault(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    protocol_fee_config: &Pubkey,
+    mint: &Pubkey,
+    protocol_fee_vault: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeProtocolFeeVault {}.data();
+
+    let accounts = super::accounts::InitializeProtocolFeeVault {
+        authority: *authority,
+        protocol_fee_config: *protocol_fee_config,
+        mint: *mint,
+        protocol_fee_vault: *protocol_fee_vault,
+        token_program: *token_program,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'sweep_protocol_fees' instruction.
+pub fn sweep_protocol_fees(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    fees_mint: &Pubkey,
+    fees_vault: &Pubkey,
+    protocol_fee_vault: &Pubkey,
+    fees_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SweepProtocolFees {}.data();
+
+    let accounts = super::accounts::SweepProtocolFees {
+        pool: *pool,
+        pool_authority: *pool_authority,
+        fees_mint: *fees_mint,
+        fees_vault: *fees_vault,
+        protocol_fee_vault: *protocol_fee_vault,
+        fees_token_program: *fees_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw_shared_protocol_fees' instruction.
+pub fn withdraw_shared_protocol_fees(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    protocol_fee_config: &Pubkey,
+    fees_mint: &Pubkey,
+    protocol_fee_vault: &Pubkey,
+    authority_fees_ata: &Pubkey,
+    fees_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::WithdrawSharedProtocolFees {}.data();
+
+    let accounts = super::accounts::WithdrawSharedProtocolFees {
+        authority: *authority,
+        protocol_fee_config: *protocol_fee_config,
+        fees_mint: *fees_mint,
+        protocol_fee_vault: *protocol_fee_vault,
+        authority_fees_ata: *authority_fees_ata,
+        fees_token_program: *fees_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'migrate_mint' instruction.
+///
+/// `conversion_accounts` are passed on to the conversion program after the accounts of each
+/// conversion.
+pub fn migrate_mint(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    old_mint: &Pubkey,
+    new_mint: &Pubkey,
+    old_vault: &Pubkey,
+    old_fees_vault: &Pubkey,
+    new_vault: &Pubkey,
+    new_fees_vault: &Pubkey,
+    other_vault: &Pubkey,
+    other_fees_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    conversion_program: &Pubkey,
+    feature_set: &Pubkey,
+    old_token_program: &Pubkey,
+    new_token_program: &Pubkey,
+    a_or_b: AorB,
+    conversion_data: Vec<u8>,
+    conversion_accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::MigrateMint {
+        a_or_b,
+        conversion_data,
+    }
+    .data();
+
+    let mut accounts = super::accounts::MigrateMint {
+        admin: *admin,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        old_mint: *old_mint,
+        new_mint: *new_mint,
+        old_vault: *old_vault,
+        old_fees_vault: *old_fees_vault,
+        new_vault: *new_vault,
+        new_fees_vault: *new_fees_vault,
+        other_vault: *other_vault,
+        other_fees_vault: *other_fees_vault,
+        pool_token_mint: *pool_token_mint,
+        conversion_program: *conversion_program,
+        feature_set: *feature_set,
+        old_token_program: *old_token_program,
+        new_token_program: *new_token_program,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(conversion_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'extend_pool' instruction.
+pub fn extend_pool(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ExtendPool {}.data();
+
+    let accounts = super::accounts::ExtendPool {
+        payer: *payer,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'write_curve_checksum' instruction.
+pub fn write_curve_checksum(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::WriteCurveChecksum {}.data();
+
+    let accounts = super::accounts::WriteCurveChecksum {
+        admin: *admin,
+        pool: *pool,
+        swap_curve: *swap_curve,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'refresh_price' instruction.
+pub fn refresh_price(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    oracle: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::RefreshPrice {}.data();
+
+    let accounts = super::accounts::RefreshPrice {
+        pool: *pool,
+        swap_curve: *swap_curve,
+        oracle: *oracle,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Appends the event authority and program accounts to a swap, deposit or withdraw instruction
+/// of a pool in `EventMode::Cpi`, after the user stats account if any
+pub fn with_event_cpi_accounts(mut instruction: Instruction, program_id: &Pubkey) -> Instruction {
+    let (event_authority, _) = seeds::pda::event_authority_pda_program_id(program_id);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(event_authority, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*program_id, false));
+    instruction
+}
+
+/// Compute budget program, its instructions are encoded by hand as the program crate does not
+/// depend on the solana sdk
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Recommended compute unit limits per instruction, with headroom over the worst case measured in
+/// the program tests. Use these instead of the 1.4M maximum so transactions schedule better.
+pub mod compute_units {
+    use super::CurveType;
+
+    pub const INITIALIZE_POOL: u32 = 120_000;
+    pub const DEPOSIT: u32 = 60_000;
+    pub const WITHDRAW: u32 = 80_000;
+    pub const WITHDRAW_FEES: u32 = 40_000;
+    pub const UPDATE_POOL_CONFIG: u32 = 20_000;
+    pub const SWAP_CONSTANT_PRODUCT: u32 = 60_000;
+    pub const SWAP_CONSTANT_PRICE: u32 = 60_000;
+    pub const SWAP_OFFSET: u32 = 60_000;
+    /// The stable curve solves the invariant iteratively
+    pub const SWAP_STABLE: u32 = 150_000;
+    pub const SWAP_PEGGED: u32 = 150_000;
+    /// The range bounded curve solves for its virtual reserves with square roots
+    pub const SWAP_CONSTANT_PRODUCT_WITH_BOUNDS: u32 = 100_000;
+    /// The weighted curve raises the reserves ratio to a fractional power with a log and an exp
+    pub const SWAP_WEIGHTED: u32 = 80_000;
+    /// The oracle pegged curve also reads and checks the price account
+    pub const SWAP_ORACLE_PEGGED: u32 = 70_000;
+
+    /// Recommended compute unit limit of a swap for the curve type of the pool
+    pub fn swap(curve_type: CurveType) -> u32 {
+        match curve_type {
+            CurveType::ConstantProduct => SWAP_CONSTANT_PRODUCT,
+            CurveType::ConstantPrice => SWAP_CONSTANT_PRICE,
+            CurveType::Offset => SWAP_OFFSET,
+            CurveType::Stable => SWAP_STABLE,
+            CurveType::Pegged => SWAP_PEGGED,
+            CurveType::ConstantProductWithBounds => SWAP_CONSTANT_PRODUCT_WITH_BOUNDS,
+            CurveType::Weighted => SWAP_WEIGHTED,
+            CurveType::OraclePegged => SWAP_ORACLE_PEGGED,
+        }
+    }
+}
+
+/// Creates a compute budget 'SetComputeUnitLimit' instruction.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Creates a compute budget 'SetComputeUnitPrice' instruction, the price is in micro-lamports.
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Prefixes an instruction with a compute unit limit and price, the price is in micro-lamports
+/// and omitted when zero.
+pub fn with_compute_budget(ix: Instruction, units: u32, micro_lamports: u64) -> Vec<Instruction> {
+    let mut ixs = vec![set_compute_unit_limit(units)];
+    if micro_lamports > 0 {
+        ixs.push(set_compute_unit_price(micro_lamports));
+    }
+    ixs.push(ix);
+    ixs
+}
+
+/// Encoding of the raw `update_pool_config` and `propose_pool_config` values, one helper per mode
+pub mod config_value {
+    use anchor_lang::{prelude::ErrorCode, Result};
+
+    use crate::{
+        state::{EventMode, UpdatePoolConfigMode, UpdatePoolConfigValue},
+        VALUE_BYTE_ARRAY_LEN,
+    };
+
+    pub fn withdrawals_only(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    pub fn permissioned_swaps(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    /// Price of token A in token B native units, scaled by `SwapPool::PRICE_BOUNDS_SCALE`
+    pub fn min_price(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Price of token A in token B native units, scaled by `SwapPool::PRICE_BOUNDS_SCALE`
+    pub fn max_price(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    pub fn prevent_self_trades(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    pub fn quiet_logs(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    pub fn event_mode(value: EventMode) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value as u64).to_bytes()
+    }
+
+    pub fn permissioned_deposits(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    /// Trade fee numerator added per basis point of volatility, zero disables dynamic fees
+    pub fn dynamic_fee_numerator(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    pub fn max_dynamic_trade_fee_numerator(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Bitmask of the paused operations, see `PoolOperation::flag`
+    pub fn status(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Leave the owner trade fees in the pool vaults, compounding them for LPs, instead of the
+    /// fees vaults
+    pub fn burn_owner_fees(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    /// Largest share of the destination reserves a swap can take in basis points, zero if
+    /// unlimited
+    pub fn max_trade_size_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Largest move of the reserves price a swap can cause in basis points, zero if unlimited
+    pub fn max_price_impact_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Largest net outflow of token A or B in a slot, in basis points of its reserves at the start
+    /// of the slot, zero if unlimited
+    pub fn max_slot_outflow_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Flash fee in basis points of the loan, zero if free
+    pub fn flash_fee_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Protocol share of the owner trade fees in basis points, fixed in production builds
+    pub fn protocol_fee_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Take the host fee out of the destination tokens instead of the source tokens
+    pub fn host_fee_in_destination_token(value: bool) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::Bool(value).to_bytes()
+    }
+
+    /// Reserves imbalance in basis points above which single sided withdrawals are rejected,
+    /// zero if unlimited
+    pub fn max_single_sided_withdraw_imbalance_bps(value: u64) -> [u8; VALUE_BYTE_ARRAY_LEN] {
+        UpdatePoolConfigValue::U64(value).to_bytes()
+    }
+
+    /// Decode the raw mode and value of an instruction, e.g. to display a proposed change
+    pub fn decode(
+        mode: u16,
+        value: &[u8; VALUE_BYTE_ARRAY_LEN],
+    ) -> Result<(UpdatePoolConfigMode, UpdatePoolConfigValue)> {
+        let mode = UpdatePoolConfigMode::try_from(mode)
+            .map_err(|_| anchor_lang::error!(ErrorCode::InstructionDidNotDeserialize))?;
+        Ok((mode, UpdatePoolConfigValue::decode(mode, value)?))
+    }
+
+    pub fn decode_bool(value: &[u8; VALUE_BYTE_ARRAY_LEN]) -> Result<bool> {
+        match UpdatePoolConfigValue::from_bool_bytes(value)? {
+            UpdatePoolConfigValue::Bool(value) => Ok(value),
+            UpdatePoolConfigValue::U64(_) => unreachable!(),
+        }
+    }
+
+    pub fn decode_u64(value: &[u8; VALUE_BYTE_ARRAY_LEN]) -> Result<u64> {
+        match UpdatePoolConfigValue::from_u64_bytes(value)? {
+            UpdatePoolConfigValue::U64(value) => Ok(value),
+            UpdatePoolConfigValue::Bool(_) => unreachable!(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::state::PoolOperation;
+
+        #[test]
+        fn test_config_value_roundtrip() {
+            assert!(decode_bool(&withdrawals_only(true)).unwrap());
+            assert!(!decode_bool(&prevent_self_trades(false)).unwrap());
+            assert!(decode_bool(&quiet_logs(true)).unwrap());
+            assert!(decode_bool(&permissioned_deposits(true)).unwrap());
+            assert_eq!(
+                decode(
+                    UpdatePoolConfigMode::EventMode as u16,
+                    &event_mode(EventMode::Cpi)
+                )
+                .unwrap(),
+                (
+                    UpdatePoolConfigMode::EventMode,
+                    UpdatePoolConfigValue::U64(EventMode::Cpi as u64)
+                )
+            );
+            assert_eq!(
+                decode_u64(&max_price(1_050_000_000)).unwrap(),
+                1_050_000_000
+            );
+            assert_eq!(
+                decode(UpdatePoolConfigMode::MinPrice as u16, &min_price(42)).unwrap(),
+                (
+                    UpdatePoolConfigMode::MinPrice,
+                    UpdatePoolConfigValue::U64(42)
+                )
+            );
+            assert_eq!(
+                decode(
+                    UpdatePoolConfigMode::PermissionedSwaps as u16,
+                    &permissioned_swaps(true)
+                )
+                .unwrap(),
+                (
+                    UpdatePoolConfigMode::PermissionedSwaps,
+                    UpdatePoolConfigValue::Bool(true)
+                )
+            );
+            assert_eq!(
+                decode(
+                    UpdatePoolConfigMode::Status as u16,
+                    &status(PoolOperation::Swaps.flag() | PoolOperation::FeeWithdrawals.flag())
+                )
+                .unwrap(),
+                (
+                    UpdatePoolConfigMode::Status,
+                    UpdatePoolConfigValue::U64(0b1001)
+                )
+            );
+        }
+
+        #[test]
+        fn test_config_value_decode_invalid() {
+            let mut value = [0; VALUE_BYTE_ARRAY_LEN];
+            value[0] = 2;
+            assert!(decode_bool(&value).is_err());
+            assert!(decode(UpdatePoolConfigMode::WithdrawalsOnly as u16, &value).is_err());
+            assert!(decode(u16::MAX, &withdrawals_only(true)).is_err());
+            assert!(decode(UpdatePoolConfigMode::EventMode as u16, &min_price(3)).is_err());
+            assert!(decode(UpdatePoolConfigMode::Status as u16, &status(1 << 4)).is_err());
+        }
+    }
+}
+
+/// Conversions between UI amounts, e.g. 1.5 tokens, and native amounts in the smallest unit of the mint
+pub mod amounts {
+    /// Native amount of a UI amount, rounded to the nearest unit
+    ///
+    /// None for negative, non-finite or overflowing amounts.
+    pub fn ui_amount_to_native(ui_amount: f64, decimals: u8) -> Option<u64> {
+        let native = (ui_amount * 10_f64.powi(i32::from(decimals))).round();
+        if !native.is_finite() || native < 0.0 || native >= u64::MAX as f64 {
+            return None;
+        }
+        Some(native as u64)
+    }
+
+    pub fn native_to_ui_amount(amount: u64, decimals: u8) -> f64 {
+        amount as f64 / 10_f64.powi(i32::from(decimals))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_ui_amount_to_native() {
+            assert_eq!(ui_amount_to_native(1.5, 6), Some(1_500_000));
+            assert_eq!(ui_amount_to_native(0.1, 6), Some(100_000));
+            assert_eq!(ui_amount_to_native(0.000001, 6), Some(1));
+            assert_eq!(ui_amount_to_native(123.456789, 9), Some(123_456_789_000));
+            assert_eq!(ui_amount_to_native(42.0, 0), Some(42));
+            assert_eq!(ui_amount_to_native(0.0, 9), Some(0));
+            assert_eq!(ui_amount_to_native(-1.0, 6), None);
+            assert_eq!(ui_amount_to_native(f64::NAN, 6), None);
+            assert_eq!(ui_amount_to_native(f64::INFINITY, 6), None);
+            assert_eq!(ui_amount_to_native(u64::MAX as f64, 6), None);
+        }
+
+        #[test]
+        fn test_native_to_ui_amount() {
+            assert_eq!(native_to_ui_amount(1_500_000, 6), 1.5);
+            assert_eq!(native_to_ui_amount(1, 9), 0.000000001);
+            assert_eq!(native_to_ui_amount(42, 0), 42.0);
+            assert_eq!(
+                ui_amount_to_native(native_to_ui_amount(123_456_789, 6), 6),
+                Some(123_456_789)
+            );
+        }
+    }
+}
+
+/// Ordered account metas of the user instructions of a pool, with the pool side accounts read from
+/// the `SwapPool` so integrators only provide their own accounts
+pub mod account_metas {
+    use anchor_lang::{prelude::AccountMeta, solana_program::pubkey::Pubkey, ToAccountMetas};
+
+    use crate::{curve::calculator::TradeDirection, state::SwapPool};
+
+    /// Accounts of the user calling a pool instruction
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct UserAccounts {
+        /// Owner of the user token accounts, signs the instruction
+        pub signer: Pubkey,
+        pub token_a_ata: Pubkey,
+        pub token_b_ata: Pubkey,
+        pub pool_token_ata: Pubkey,
+        /// Token program of the token A mint
+        pub token_a_program: Pubkey,
+        /// Token program of the token B mint
+        pub token_b_program: Pubkey,
+        /// Token program of the pool token mint
+        pub pool_token_program: Pubkey,
+    }
+
+    /// Pool instruction to build the account metas of, with its optional accounts
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum PoolInstruction {
+        Deposit {
+            referrer: Option<Pubkey>,
+            allowed_liquidity_provider: Option<Pubkey>,
+        },
+        Withdraw,
+        Swap {
+            trade_direction: TradeDirection,
+            source_token_host_fees: Option<Pubkey>,
+            allowed_swap_taker: Option<Pubkey>,
+            /// Price account of an oracle pegged curve
+            oracle: Option<Pubkey>,
+            /// Host fees account of the destination mint, for pools paying the host fee in
+            /// destination tokens
+            destination_token_host_fees: Option<Pubkey>,
+        },
+    }
+
+    impl SwapPool {
+        /// Account metas of `instruction` against the pool at `pool`, in the order the program
+        /// expects them
+        pub fn account_metas(
+            &self,
+            pool: &Pubkey,
+            user: &UserAccounts,
+            instruction: PoolInstruction,
+        ) -> Vec<AccountMeta> {
+            match instruction {
+                PoolInstruction::Deposit {
+                    referrer,
+                    allowed_liquidity_provider,
+                } => crate::accounts::Deposit {
+                    signer: user.signer,
+                    pool: *pool,
+                    swap_curve: self.swap_curve,
+                    pool_authority: self.pool_authority,
+                    token_a_mint: self.token_a_mint,
+                    token_b_mint: self.token_b_mint,
+                    token_a_vault: self.token_a_vault,
+                    token_b_vault: self.token_b_vault,
+                    pool_token_mint: self.pool_token_mint,
+                    token_a_user_ata: user.token_a_ata,
+                    token_b_user_ata: user.token_b_ata,
+                    pool_token_user_ata: user.pool_token_ata,
+                    pool_token_program: user.pool_token_program,
+                    token_a_token_program: user.token_a_program,
+                    token_b_token_program: user.token_b_program,
+                    referrer,
+                    allowed_liquidity_provider,
+                }
+                .to_account_metas(None),
+                PoolInstruction::Withdraw => crate::accounts::Withdraw {
+                    signer: user.signer,
+                    pool: *pool,
+                    swap_curve: self.swap_curve,
+                    pool_authority: self.pool_authority,
+                    token_a_mint: self.token_a_mint,
+                    token_b_mint: self.token_b_mint,
+                    token_a_vault: self.token_a_vault,
+                    token_b_vault: self.token_b_vault,
+                    pool_token_mint: self.pool_token_mint,
+                    token_a_fees_vault: self.token_a_fees_vault,
+                    token_b_fees_vault: self.token_b_fees_vault,
+                    token_a_user_ata: user.token_a_ata,
+                    token_b_user_ata: user.token_b_ata,
+                    pool_token_user_ata: user.pool_token_ata,
+                    pool_token_program: user.pool_token_program,
+                    token_a_token_program: user.token_a_program,
+                    token_b_token_program: user.token_b_program,
+                }
+                .to_account_metas(None),
+                PoolInstruction::Swap {
+                    trade_direction,
+                    source_token_host_fees,
+                    allowed_swap_taker,
+                    oracle,
+                    destination_token_host_fees,
+                } => {
+                    let a = (
+                        self.token_a_mint,
+                        self.token_a_vault,
+                        self.token_a_fees_vault,
+                        user.token_a_ata,
+                        user.token_a_program,
+                    );
+                    let b = (
+                        self.token_b_mint,
+                        self.token_b_vault,
+                        self.token_b_fees_vault,
+                        user.token_b_ata,
+                        user.token_b_program,
+                    );
+                    let (source, destination) = match trade_direction {
+                        TradeDirection::AtoB => (a, b),
+                        TradeDirection::BtoA => (b, a),
+                    };
+                    crate::accounts::Swap {
+                        signer: user.signer,
+                        pool: *pool,
+                        swap_curve: self.swap_curve,
+                        pool_authority: self.pool_authority,
+                        source_mint: source.0,
+                        destination_mint: destination.0,
+                        source_vault: source.1,
+                        destination_vault: destination.1,
+                        source_token_fees_vault: source.2,
+                        source_user_ata: source.3,
+                        destination_user_ata: destination.3,
+                        source_token_host_fees_account: source_token_host_fees,
+                        source_token_program: source.4,
+                        destination_token_program: destination.4,
+                        allowed_swap_taker,
+                        oracle,
+                        destination_token_host_fees_account: destination_token_host_fees,
+                    }
+                    .to_account_metas(None)
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::ix;
+
+        fn pool() -> SwapPool {
+            SwapPool {
+                pool_authority: Pubkey::new_unique(),
+                token_a_vault: Pubkey::new_unique(),
+                token_b_vault: Pubkey::new_unique(),
+                pool_token_mint: Pubkey::new_unique(),
+                token_a_mint: Pubkey::new_unique(),
+                token_b_mint: Pubkey::new_unique(),
+                token_a_fees_vault: Pubkey::new_unique(),
+                token_b_fees_vault: Pubkey::new_unique(),
+                swap_curve: Pubkey::new_unique(),
+                ..Default::default()
+            }
+        }
+
+        fn user() -> UserAccounts {
+            UserAccounts {
+                signer: Pubkey::new_unique(),
+                token_a_ata: Pubkey::new_unique(),
+                token_b_ata: Pubkey::new_unique(),
+                pool_token_ata: Pubkey::new_unique(),
+                token_a_program: Pubkey::new_unique(),
+                token_b_program: Pubkey::new_unique(),
+                pool_token_program: Pubkey::new_unique(),
+            }
+        }
+
+        #[test]
+        fn test_deposit_and_withdraw_account_metas() {
+            let pool_key = Pubkey::new_unique();
+            let pool = pool();
+            let user = user();
+            let referrer = Pubkey::new_unique();
+            let allowed_liquidity_provider = Pubkey::new_unique();
+            let SwapPool {
+                swap_curve,
+                pool_authority,
+                token_a_mint,
+                token_b_mint,
+                token_a_vault,
+                token_b_vault,
+                pool_token_mint,
+                token_a_fees_vault,
+                token_b_fees_vault,
+                ..
+            } = pool;
+
+            let deposit = ix::deposit(
+                &crate::ID,
+                &user.signer,
+                &pool_key,
+                &swap_curve,
+                &pool_authority,
+                &token_a_mint,
+                &token_b_mint,
+                &token_a_vault,
+                &token_b_vault,
+                &pool_token_mint,
+                &user.token_a_ata,
+                &user.token_b_ata,
+                &user.pool_token_ata,
+                &user.pool_token_program,
+                &user.token_a_program,
+                &user.token_b_program,
+                Some(&referrer),
+                Some(&allowed_liquidity_provider),
+                ix::Deposit::new(1, 1, 1, None),
+            )
+            .unwrap();
+            assert_eq!(
+                pool.account_metas(
+                    &pool_key,
+                    &user,
+                    PoolInstruction::Deposit {
+                        referrer: Some(referrer),
+                        allowed_liquidity_provider: Some(allowed_liquidity_provider),
+                    }
+                ),
+                deposit.accounts
+            );
+
+            let withdraw = ix::withdraw(
+                &crate::ID,
+                &user.signer,
+                &pool_key,
+                &swap_curve,
+                &pool_authority,
+                &token_a_mint,
+                &token_b_mint,
+                &token_a_vault,
+                &token_b_vault,
+                &pool_token_mint,
+                &token_a_fees_vault,
+                &token_b_fees_vault,
+                &user.token_a_ata,
+                &user.token_b_ata,
+                &user.pool_token_ata,
+                &user.pool_token_program,
+                &user.token_a_program,
+                &user.token_b_program,
+                ix::Withdraw::new(1, 1, 1, false, None),
+            )
+            .unwrap();
+            assert_eq!(
+                pool.account_metas(&pool_key, &user, PoolInstruction::Withdraw),
+                withdraw.accounts
+            );
+        }
+
+        #[test]
+        fn test_swap_account_metas() {
+            let pool_key = Pubkey::new_unique();
+            let pool = pool();
+            let user = user();
+            let host_fees = Pubkey::new_unique();
+            let SwapPool {
+                swap_curve,
+                pool_authority,
+                token_a_mint,
+                token_b_mint,
+                token_a_vault,
+                token_b_vault,
+                token_b_fees_vault,
+                ..
+            } = pool;
+
+            let swap = ix::swap(
+                &crate::ID,
+                &user.signer,
+                &pool_key,
+                &swap_curve,
+                &pool_authority,
+                &token_b_mint,
+                &token_a_mint,
+                &token_b_vault,
+                &token_a_vault,
+                &token_b_fees_vault,
+                &user.token_b_ata,
+                &user.token_a_ata,
+                Some(&host_fees),
+                &user.token_b_program,
+                &user.token_a_program,
+                None,
+                None,
+                None,
+                ix::Swap::new(1, 1, None),
+            )
+            .unwrap();
+            assert_eq!(
+                pool.account_metas(
+                    &pool_key,
+                    &user,
+                    PoolInstruction::Swap {
+                        trade_direction: TradeDirection::BtoA,
+                        source_token_host_fees: Some(host_fees),
+                        allowed_swap_taker: None,
+                        oracle: None,
+                        destination_token_host_fees: None,
+                    }
+                ),
+                swap.accounts
+            );
+        }
+    }
+}
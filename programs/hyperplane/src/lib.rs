@@ -9,6 +9,7 @@ pub mod curve;
 pub mod error;
 pub mod event;
 pub mod instructions;
+#[cfg(any(test, feature = "client"))]
 pub mod ix;
 pub mod state;
 pub mod utils;
@@ -16,9 +17,12 @@ pub mod utils;
 // Export current sdk types for downstream users building with a different sdk version
 pub use anchor_lang;
 use anchor_lang::prelude::*;
-use curve::fees::Fees;
+use curve::{calculator::AorB, fees::Fees};
 pub use instructions::*;
 
+#[cfg(all(feature = "production", feature = "dev-mode"))]
+compile_error!("the dev-mode feature must not be enabled in production builds");
+
 declare_id!("SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw");
 
 #[program]
@@ -32,12 +36,16 @@ pub mod hyperplane {
         fees: Fees,
         initial_supply_a: u64,
         initial_supply_b: u64,
-    ) -> Result<()> {
+        burn_owner_fees: bool,
+        strict_token_program: bool,
+    ) -> Result<event::PoolCreated> {
         instructions::initialize_pool::handler(
             ctx,
             curve_parameters,
             fees,
             initialize_pool::InitialSupply::new(initial_supply_a, initial_supply_b),
+            burn_owner_fees,
+            strict_token_program,
         )
     }
 
@@ -45,8 +53,17 @@ pub mod hyperplane {
         ctx: Context<Swap>,
         amount_in: u64,
         minimum_amount_out: u64,
+        deadline: Option<i64>,
     ) -> Result<event::Swap> {
-        instructions::swap::handler(ctx, amount_in, minimum_amount_out)
+        instructions::swap::handler(ctx, amount_in, minimum_amount_out, deadline)
+    }
+
+    pub fn swap_batch(
+        ctx: Context<Swap>,
+        fills: Vec<SwapFill>,
+        deadline: Option<i64>,
+    ) -> Result<event::SwapBatch> {
+        instructions::swap_batch::handler(ctx, fills, deadline)
     }
 
     pub fn deposit(
@@ -54,12 +71,14 @@ pub mod hyperplane {
         pool_token_amount: u64,
         maximum_token_a_amount: u64,
         maximum_token_b_amount: u64,
+        deadline: Option<i64>,
     ) -> Result<event::Deposit> {
         instructions::deposit::handler(
             ctx,
             pool_token_amount,
             maximum_token_a_amount,
             maximum_token_b_amount,
+            deadline,
         )
     }
 
@@ -68,12 +87,16 @@ pub mod hyperplane {
         pool_token_amount: u64,
         minimum_token_a_amount: u64,
         minimum_token_b_amount: u64,
+        burn_dust: bool,
+        deadline: Option<i64>,
     ) -> Result<event::Withdraw> {
         instructions::withdraw::handler(
             ctx,
             pool_token_amount,
             minimum_token_a_amount,
             minimum_token_b_amount,
+            burn_dust,
+            deadline,
         )
     }
 
@@ -91,4 +114,375 @@ pub mod hyperplane {
     ) -> Result<event::UpdatePoolConfig> {
         instructions::update_pool_config::handler(ctx, mode, &value)
     }
+
+    pub fn add_swap_taker(ctx: Context<AddSwapTaker>) -> Result<event::AddSwapTaker> {
+        instructions::add_swap_taker::handler(ctx)
+    }
+
+    pub fn remove_swap_taker(ctx: Context<RemoveSwapTaker>) -> Result<event::RemoveSwapTaker> {
+        instructions::remove_swap_taker::handler(ctx)
+    }
+
+    pub fn initialize_feature_set(ctx: Context<InitializeFeatureSet>) -> Result<()> {
+        instructions::initialize_feature_set::handler(ctx)
+    }
+
+    pub fn update_feature_set(
+        ctx: Context<UpdateFeatureSet>,
+        feature: u16,
+        enabled: bool,
+    ) -> Result<event::UpdateFeatureSet> {
+        instructions::update_feature_set::handler(ctx, feature, enabled)
+    }
+
+    pub fn set_conversion_program(
+        ctx: Context<SetConversionProgram>,
+        index: u16,
+        program: Pubkey,
+    ) -> Result<event::SetConversionProgram> {
+        instructions::set_conversion_program::handler(ctx, index, program)
+    }
+
+    pub fn initialize_fee_tier_registry(ctx: Context<InitializeFeeTierRegistry>) -> Result<()> {
+        instructions::initialize_fee_tier_registry::handler(ctx)
+    }
+
+    pub fn set_fee_tier(
+        ctx: Context<SetFeeTier>,
+        tier: u16,
+        fees: Fees,
+    ) -> Result<event::SetFeeTier> {
+        instructions::set_fee_tier::handler(ctx, tier, fees)
+    }
+
+    pub fn initialize_pool_with_fee_tier(
+        ctx: Context<InitializePoolWithFeeTier>,
+        curve_parameters: CurveUserParameters,
+        fee_tier: u16,
+        initial_supply: InitialSupply,
+        burn_owner_fees: bool,
+        strict_token_program: bool,
+    ) -> Result<event::PoolCreated> {
+        instructions::initialize_pool_with_fee_tier::handler(
+            ctx,
+            curve_parameters,
+            fee_tier,
+            initial_supply,
+            burn_owner_fees,
+            strict_token_program,
+        )
+    }
+
+    pub fn rebalance_across_pools(
+        ctx: Context<RebalanceAcrossPools>,
+        source_pool_token_amount: u64,
+        destination_pool_token_amount: u64,
+    ) -> Result<event::RebalanceAcrossPools> {
+        instructions::rebalance_across_pools::handler(
+            ctx,
+            source_pool_token_amount,
+            destination_pool_token_amount,
+        )
+    }
+
+    pub fn request_withdraw(
+        ctx: Context<RequestWithdraw>,
+        pool_token_amount: u64,
+        minimum_token_a_amount: u64,
+        minimum_token_b_amount: u64,
+    ) -> Result<event::RequestWithdraw> {
+        instructions::request_withdraw::handler(
+            ctx,
+            pool_token_amount,
+            minimum_token_a_amount,
+            minimum_token_b_amount,
+        )
+    }
+
+    pub fn claim_withdraw(ctx: Context<ClaimWithdraw>) -> Result<event::Withdraw> {
+        instructions::claim_withdraw::handler(ctx)
+    }
+
+    pub fn propose_pool_config(
+        ctx: Context<ProposePoolConfig>,
+        mode: u16,
+        value: [u8; VALUE_BYTE_ARRAY_LEN],
+        approval_window_slots: u64,
+    ) -> Result<event::ProposePoolConfig> {
+        instructions::propose_pool_config::handler(ctx, mode, &value, approval_window_slots)
+    }
+
+    pub fn execute_pool_config(ctx: Context<ExecutePoolConfig>) -> Result<event::UpdatePoolConfig> {
+        instructions::execute_pool_config::handler(ctx)
+    }
+
+    pub fn initialize_config_history(ctx: Context<InitializeConfigHistory>) -> Result<()> {
+        instructions::initialize_config_history::handler(ctx)
+    }
+
+    pub fn initialize_user_stats(ctx: Context<InitializeUserStats>) -> Result<()> {
+        instructions::initialize_user_stats::handler(ctx)
+    }
+
+    pub fn revoke_vault_delegates(
+        ctx: Context<RevokeVaultDelegates>,
+    ) -> Result<event::RevokeVaultDelegates> {
+        instructions::revoke_vault_delegates::handler(ctx)
+    }
+
+    pub fn deposit_exact_amounts(
+        ctx: Context<Deposit>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        minimum_pool_token_amount: u64,
+    ) -> Result<event::Deposit> {
+        instructions::deposit_exact_amounts::handler(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+            minimum_pool_token_amount,
+        )
+    }
+
+    pub fn create_lp_airdrop(
+        ctx: Context<CreateLpAirdrop>,
+        reward_amount: u64,
+        snapshot_slot: u64,
+    ) -> Result<event::CreateLpAirdrop> {
+        instructions::create_lp_airdrop::handler(ctx, reward_amount, snapshot_slot)
+    }
+
+    pub fn register_lp_airdrop(
+        ctx: Context<RegisterLpAirdrop>,
+        pool_token_amount: u64,
+    ) -> Result<event::RegisterLpAirdrop> {
+        instructions::register_lp_airdrop::handler(ctx, pool_token_amount)
+    }
+
+    pub fn claim_lp_airdrop(ctx: Context<ClaimLpAirdrop>) -> Result<event::ClaimLpAirdrop> {
+        instructions::claim_lp_airdrop::handler(ctx)
+    }
+
+    pub fn close_lp_airdrop(ctx: Context<CloseLpAirdrop>) -> Result<event::CloseLpAirdrop> {
+        instructions::close_lp_airdrop::handler(ctx)
+    }
+
+    pub fn skim_lamports(ctx: Context<SkimLamports>) -> Result<event::SkimLamports> {
+        instructions::skim_lamports::handler(ctx)
+    }
+
+    pub fn initialize_default_host_fees(
+        ctx: Context<InitializeDefaultHostFees>,
+        recipient: Pubkey,
+    ) -> Result<event::SetDefaultHostFees> {
+        instructions::initialize_default_host_fees::handler(ctx, recipient)
+    }
+
+    pub fn update_default_host_fees(
+        ctx: Context<UpdateDefaultHostFees>,
+        recipient: Pubkey,
+    ) -> Result<event::SetDefaultHostFees> {
+        instructions::update_default_host_fees::handler(ctx, recipient)
+    }
+
+    pub fn claim_default_host_fees(
+        ctx: Context<ClaimDefaultHostFees>,
+    ) -> Result<event::ClaimDefaultHostFees> {
+        instructions::claim_default_host_fees::handler(ctx)
+    }
+
+    pub fn deposit_single(
+        ctx: Context<DepositSingle>,
+        source_token_amount: u64,
+        minimum_pool_token_amount: u64,
+    ) -> Result<event::DepositSingle> {
+        instructions::deposit_single::handler(ctx, source_token_amount, minimum_pool_token_amount)
+    }
+
+    pub fn extend_pool(ctx: Context<ExtendPool>) -> Result<event::ExtendPool> {
+        instructions::extend_pool::handler(ctx)
+    }
+
+    pub fn write_curve_checksum(
+        ctx: Context<WriteCurveChecksum>,
+    ) -> Result<event::WriteCurveChecksum> {
+        instructions::write_curve_checksum::handler(ctx)
+    }
+
+    pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<event::RefreshPrice> {
+        instructions::refresh_price::handler(ctx)
+    }
+
+    pub fn emit_event(ctx: Context<EmitEvent>, event: Vec<u8>) -> Result<()> {
+        instructions::emit_event::handler(ctx, event)
+    }
+
+    pub fn withdraw_single(
+        ctx: Context<WithdrawSingle>,
+        destination_token_amount: u64,
+        maximum_pool_token_amount: u64,
+    ) -> Result<event::WithdrawSingle> {
+        instructions::withdraw_single::handler(
+            ctx,
+            destination_token_amount,
+            maximum_pool_token_amount,
+        )
+    }
+
+    pub fn swap_route_two_hop(
+        ctx: Context<SwapRouteTwoHop>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<event::SwapRouteTwoHop> {
+        instructions::swap_route_two_hop::handler(ctx, amount_in, minimum_amount_out)
+    }
+
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, amount: u64) -> Result<()> {
+        instructions::flash_borrow::handler(ctx, amount)
+    }
+
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<event::FlashLoan> {
+        instructions::flash_repay::handler(ctx)
+    }
+
+    pub fn add_liquidity_provider(
+        ctx: Context<AddLiquidityProvider>,
+    ) -> Result<event::AddLiquidityProvider> {
+        instructions::add_liquidity_provider::handler(ctx)
+    }
+
+    pub fn remove_liquidity_provider(
+        ctx: Context<RemoveLiquidityProvider>,
+    ) -> Result<event::RemoveLiquidityProvider> {
+        instructions::remove_liquidity_provider::handler(ctx)
+    }
+
+    pub fn assert_pool_price(ctx: Context<AssertPoolPrice>, min: u64, max: u64) -> Result<()> {
+        instructions::assert_pool_price::handler(ctx, min, max)
+    }
+
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<event::ClosePool> {
+        instructions::close_pool::handler(ctx)
+    }
+
+    pub fn initialize_protocol_fee_config(
+        ctx: Context<InitializeProtocolFeeConfig>,
+        authority: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_protocol_fee_config::handler(ctx, authority)
+    }
+
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+    ) -> Result<event::WithdrawProtocolFees> {
+        instructions::withdraw_protocol_fees::handler(ctx)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        pending_admin: Pubkey,
+    ) -> Result<event::ProposeAdmin> {
+        instructions::propose_admin::handler(ctx, pending_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<event::AcceptAdmin> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<event::SetGuardian> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn guardian_pause_pool(
+        ctx: Context<GuardianPausePool>,
+        mode: u16,
+        value: [u8; VALUE_BYTE_ARRAY_LEN],
+    ) -> Result<event::UpdatePoolConfig> {
+        instructions::guardian_pause_pool::handler(ctx, mode, &value)
+    }
+
+    pub fn initialize_protocol_fee_vault(ctx: Context<InitializeProtocolFeeVault>) -> Result<()> {
+        instructions::initialize_protocol_fee_vault::handler(ctx)
+    }
+
+    pub fn sweep_protocol_fees(
+        ctx: Context<SweepProtocolFees>,
+    ) -> Result<event::SweepProtocolFees> {
+        instructions::sweep_protocol_fees::handler(ctx)
+    }
+
+    pub fn withdraw_shared_protocol_fees(
+        ctx: Context<WithdrawSharedProtocolFees>,
+    ) -> Result<event::WithdrawProtocolFees> {
+        instructions::withdraw_shared_protocol_fees::handler(ctx)
+    }
+
+    pub fn migrate_mint<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateMint<'info>>,
+        a_or_b: AorB,
+        conversion_data: Vec<u8>,
+    ) -> Result<event::MigrateMint> {
+        instructions::migrate_mint::handler(ctx, a_or_b, conversion_data)
+    }
+
+    pub fn deposit_both_exact_in(
+        ctx: Context<DepositBothExactIn>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        minimum_pool_token_amount: u64,
+    ) -> Result<event::DepositBothExactIn> {
+        instructions::deposit_both_exact_in::handler(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+            minimum_pool_token_amount,
+        )
+    }
+
+    pub fn withdraw_exact_amounts_out(
+        ctx: Context<WithdrawExactAmountsOut>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        maximum_pool_token_amount: u64,
+    ) -> Result<event::WithdrawExactAmountsOut> {
+        instructions::withdraw_exact_amounts_out::handler(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+            maximum_pool_token_amount,
+        )
+    }
+
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        pool_token_amount: u64,
+        lock_duration_slots: u64,
+    ) -> Result<event::LockLiquidity> {
+        instructions::lock_liquidity::handler(ctx, pool_token_amount, lock_duration_slots)
+    }
+
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<event::UnlockLiquidity> {
+        instructions::unlock_liquidity::handler(ctx)
+    }
+
+    pub fn donate_to_pool(
+        ctx: Context<DonateToPool>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<event::Donation> {
+        instructions::donate_to_pool::handler(ctx, token_a_amount, token_b_amount)
+    }
+
+    /// Dispatches `initialize_test_pool`, which only exists in programs built with `dev-mode`,
+    /// see `InitializeTestPoolArgs`
+    #[cfg_attr(not(feature = "dev-mode"), allow(unused_variables))]
+    pub fn fallback(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> Result<()> {
+        #[cfg(feature = "dev-mode")]
+        if let Some(result) =
+            instructions::initialize_test_pool::dispatch(program_id, accounts, data)
+        {
+            return result;
+        }
+        Err(ErrorCode::InstructionFallbackNotFound.into())
+    }
 }
@@ -1,8 +1,7 @@
-use std::ops::Deref;
-
 use anchor_lang::{
     account,
     prelude::{borsh, ProgramError, Pubkey},
+    solana_program::hash::hash,
     zero_copy, AnchorDeserialize, AnchorSerialize, Result,
 };
 use enum_dispatch::enum_dispatch;
@@ -10,9 +9,14 @@ use num_enum::TryFromPrimitive;
 use strum::EnumString;
 
 use crate::{
-    curve::{base::CurveType, fees::Fees},
-    try_math,
-    utils::math::decimals_to_factor,
+    curve::{
+        base::CurveType,
+        calculator::{AorB, CurveCalculator, TradeDirection},
+        fees::{self, Fees},
+    },
+    error::SwapError,
+    require_msg, try_math,
+    utils::math::{decimals_to_factor, TryMath},
     VALUE_BYTE_ARRAY_LEN,
 };
 
@@ -43,12 +47,37 @@ pub trait SwapState {
 
     /// The swap curve is in withdraw mode, and will only allow withdrawals
     fn withdrawals_only(&self) -> bool;
+    /// Only allow-listed takers can swap against the pool
+    fn permissioned_swaps(&self) -> bool;
+
+    /// The swap curve accepts deposits after initialization
+    fn allows_deposits(&self) -> bool;
+    /// The swap curve accepts deposits of a single token
+    fn allows_single_sided_deposits(&self) -> bool;
+
+    /// Owner trade fees are left in the pool vault instead of the fees vault
+    fn burn_owner_fees(&self) -> bool;
+
+    /// The host fee is paid in destination tokens, see [Fees::destination_host_fee]
+    fn host_fee_in_destination_token(&self) -> bool;
+
+    /// Swaps to or from token accounts owned by the pool admin are rejected
+    fn prevent_self_trades(&self) -> bool;
+
+    /// Informational logs of swaps, deposits and withdrawals are skipped to save compute
+    fn quiet_logs(&self) -> bool;
+
+    /// Channel the events of swaps, deposits and withdrawals are emitted through
+    fn event_mode(&self) -> EventMode;
+
+    /// Only allow-listed liquidity providers can deposit into the pool
+    fn permissioned_deposits(&self) -> bool;
 }
 
 /// Program states
 
 #[account(zero_copy)]
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, PartialEq)]
 pub struct SwapPool {
     /// Pool admin - account which initialised the pool
     pub admin: Pubkey,
@@ -89,12 +118,407 @@ pub struct SwapPool {
     /// The swap curve is in withdraw mode, and will only allow withdrawals
     pub withdrawals_only: u64,
 
-    pub _padding: [u64; 16],
+    /// Only allow-listed takers can swap against the pool, deposits and withdrawals are unaffected
+    pub permissioned_swaps: u64,
+
+    /// Bump seeds of the remaining pool PDAs, stored so they can be re-derived with `create_program_address`
+    pub swap_curve_bump_seed: u64,
+    pub token_a_vault_bump_seed: u64,
+    pub token_b_vault_bump_seed: u64,
+    pub pool_token_mint_bump_seed: u64,
+    pub token_a_fees_vault_bump_seed: u64,
+    pub token_b_fees_vault_bump_seed: u64,
+
+    /// Lower bound of the post-swap spot price of constant product pools, 0 if unbounded
+    /// Price of token A in token B native units, scaled by `PRICE_BOUNDS_SCALE`
+    pub min_price: u64,
+    /// Upper bound of the post-swap spot price of constant product pools, 0 if unbounded
+    pub max_price: u64,
+
+    /// Capabilities of the swap curve, copied from the curve calculator on initialization
+    /// so clients can tell which actions are supported without knowing the curve type
+    pub allows_deposits: u64,
+    pub allows_single_sided_deposits: u64,
+
+    /// Owner trade fees are left in the pool vault instead of the fees vault, burning the
+    /// protocol's share so the fees accrue entirely to LPs
+    pub burn_owner_fees: u64,
+
+    /// Swaps to or from token accounts owned by the pool admin are rejected, so the reported
+    /// volume cannot be inflated by the admin trading against its own pool
+    pub prevent_self_trades: u64,
+
+    /// Skip the informational logs and failure messages of swaps, deposits and withdrawals, errors
+    /// are still reported by their code. Left unset on devnet pools for the detailed logs.
+    pub quiet_logs: u64,
+
+    /// Host fees of swaps passing no host fees account, kept in the fees vaults and owed to the
+    /// default host fees recipient of the pool. Counted from when the admin sets the recipient.
+    pub default_host_fees_a: u64,
+    pub default_host_fees_b: u64,
+
+    /// Channel the events of swaps, deposits and withdrawals are emitted through, an `EventMode`
+    pub event_mode: u64,
+
+    /// Token A or B lent out by an ongoing flash loan, repaid before the end of the transaction.
+    /// The pool rejects every other action while a loan is ongoing.
+    pub flash_loan_amount_a: u64,
+    pub flash_loan_amount_b: u64,
+
+    /// Only allow-listed liquidity providers can deposit into the pool, swaps and withdrawals are
+    /// unaffected so LPs admitted earlier can always exit
+    pub permissioned_deposits: u64,
+
+    /// Recent price volatility in basis points, raising the trade fee of pools with dynamic fees.
+    /// Grown by the reserves price move of every swap and halved every
+    /// `VOLATILITY_HALF_LIFE_SLOTS`, only tracked while dynamic fees are enabled.
+    pub volatility_bps: u64,
+    /// Reserves price after the last swap, scaled by `PRICE_BOUNDS_SCALE`
+    pub volatility_reference_price: u64,
+    /// Slot of the last volatility update
+    pub volatility_updated_slot: u64,
+
+    /// Protocol share of the owner trade fees, kept in the fees vaults and owed to the protocol
+    /// fee authority
+    pub protocol_fees_a: u64,
+    pub protocol_fees_b: u64,
+
+    /// Admin proposed by the current admin, taking over once it signs an `accept_admin`. Default
+    /// pubkey when no transfer is pending.
+    pub pending_admin: Pubkey,
+
+    /// Protocol fees moved so far from the fees vaults to the shared protocol fee vaults of their
+    /// mint by `sweep_protocol_fees`, so the accrual of each pool can still be reconciled
+    pub protocol_fees_swept_a: u64,
+    pub protocol_fees_swept_b: u64,
+
+    /// Bitmask of the operations paused by the admin, indexed by `PoolOperation`. Independent of
+    /// `withdrawals_only`, which still pauses everything but withdrawals.
+    pub status: u64,
+
+    /// The token A or B mint accrues interest with the Token-2022 interest bearing extension. The
+    /// curves price the raw amounts of the vaults, which the interest does not change.
+    pub token_a_interest_bearing: u64,
+    pub token_b_interest_bearing: u64,
+
+    /// Key allowed to pause the pool on the admin's behalf, e.g. a monitoring bot, without being
+    /// able to unpause it or change anything else. Default pubkey when no guardian is set.
+    pub guardian: Pubkey,
+
+    /// Swaps taking more than this share of the destination reserves are rejected, in basis
+    /// points, 0 if unlimited
+    pub max_trade_size_bps: u64,
+    /// Swaps moving the reserves price by more than this are rejected, in basis points, 0 if
+    /// unlimited
+    pub max_price_impact_bps: u64,
+
+    /// Swaps are rejected once the net outflow of token A or B in a slot exceeds this share of
+    /// its reserves at the start of the slot, in basis points, 0 if unlimited
+    pub max_slot_outflow_bps: u64,
+    /// Slot of the tracked outflows, which restart from zero on the first swap of a new slot.
+    /// Only tracked while `max_slot_outflow_bps` is set.
+    pub outflow_slot: u64,
+    /// Tokens swapped out of the vault minus swapped in during `outflow_slot`, floored at zero
+    pub slot_outflow_a: u64,
+    pub slot_outflow_b: u64,
+
+    /// Fee of flash loans in basis points of the loan, repaid on top of it and left in the vault
+    /// for the LPs, 0 if free. Not a fraction in [Fees], whose growth would shift the layout.
+    pub flash_fee_bps: u64,
+
+    /// Dynamic fees raise the trade fee with the recent price volatility of the pool, disabled
+    /// when zero. Trade fee numerator added per basis point of volatility, over the trade fee
+    /// denominator.
+    pub dynamic_fee_numerator: u64,
+    /// Cap of the trade fee numerator raised by dynamic fees
+    pub max_dynamic_trade_fee_numerator: u64,
+
+    /// Share of the owner trade fees set aside in the fees vault for the program-wide protocol
+    /// fee authority, in basis points, fixed by the fee constraints of production builds
+    pub protocol_fee_bps: u64,
+
+    /// Non-zero when the host fee of swaps passing a host fees account of the destination mint is
+    /// taken out of the destination tokens instead of the owner fee in source tokens
+    pub host_fee_in_destination_token: u64,
+
+    /// Single sided withdrawals and deposits are rejected while the reserves are imbalanced by more
+    /// than this, in basis points, so the LPs of a depegged pool can only leave in kind and no one
+    /// mints pool tokens at the depegged price. 0 if unlimited.
+    pub max_single_sided_withdraw_imbalance_bps: u64,
+
+    /// Non-zero once the checksum of the curve account is written, by `initialize` or by
+    /// `write_curve_checksum` for older pools. The curve is only checked against it when set.
+    pub curve_checksum_written: u64,
+
+    /// Space for new fields, pools created before it was added are grown with `extend_pool`
+    pub _padding: [u64; 29],
+}
+
+impl Default for SwapPool {
+    fn default() -> Self {
+        bytemuck::Zeroable::zeroed()
+    }
 }
 
 impl SwapPool {
     // note: also hardcoded in /js/src/util/const.ts
-    pub const LEN: usize = DISCRIMINATOR_SIZE + 536; // 8 + 536 = 548
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 1048; // 8 + 1048 = 1056
+
+    /// Size of the pools created before the padding was added
+    pub const LEGACY_LEN: usize = DISCRIMINATOR_SIZE + 536;
+
+    /// Scale of `min_price` and `max_price`
+    pub const PRICE_BOUNDS_SCALE: u64 = 1_000_000_000;
+
+    /// Price of token A in token B native units given the pool reserves, scaled by
+    /// `PRICE_BOUNDS_SCALE`. This is the spot price of constant product pools.
+    pub fn reserves_price(token_a_amount: u128, token_b_amount: u128) -> Result<u128> {
+        try_math!(token_b_amount
+            .try_mul(u128::from(Self::PRICE_BOUNDS_SCALE))?
+            .try_div(token_a_amount))
+    }
+
+    /// Number of slots after which the volatility is halved, about a minute
+    pub const VOLATILITY_HALF_LIFE_SLOTS: u64 = 150;
+    /// Cap of the tracked volatility
+    pub const MAX_VOLATILITY_BPS: u64 = 10_000;
+
+    /// Volatility decayed to `slot`
+    pub fn decayed_volatility_bps(&self, slot: u64) -> u64 {
+        let half_lives =
+            slot.saturating_sub(self.volatility_updated_slot) / Self::VOLATILITY_HALF_LIFE_SLOTS;
+        self.volatility_bps
+            .checked_shr(half_lives.try_into().unwrap_or(u32::MAX))
+            .unwrap_or(0)
+    }
+
+    /// The curve account has a checksum to be verified whenever it is loaded
+    pub fn curve_checksum_written(&self) -> bool {
+        self.curve_checksum_written != 0
+    }
+
+    /// Dynamic fees are enabled, the trade fee follows the volatility of the pool
+    pub fn dynamic_fees_enabled(&self) -> bool {
+        self.dynamic_fee_numerator != 0
+    }
+
+    /// Fees charged by a swap at `slot`, with the trade fee raised by the volatility when dynamic
+    /// fees are enabled, see [fees::dynamic_trade_fee_numerator]
+    pub fn swap_fees(&self, slot: u64) -> Result<Fees> {
+        if self.dynamic_fees_enabled() {
+            Ok(Fees {
+                trade_fee_numerator: fees::dynamic_trade_fee_numerator(
+                    self.fees.trade_fee_numerator,
+                    self.dynamic_fee_numerator,
+                    self.max_dynamic_trade_fee_numerator,
+                    self.decayed_volatility_bps(slot),
+                )?,
+                ..self.fees
+            })
+        } else {
+            Ok(self.fees)
+        }
+    }
+
+    /// Validate the fees of the pool along with its dynamic fee parameters
+    pub fn validate_fees(&self) -> Result<()> {
+        self.fees.validate()?;
+        fees::validate_dynamic_fees(
+            &self.fees,
+            self.dynamic_fee_numerator,
+            self.max_dynamic_trade_fee_numerator,
+        )
+    }
+
+    /// Grow the decayed volatility by the move of the reserves price since the last update
+    pub fn update_volatility(
+        &mut self,
+        token_a_amount: u128,
+        token_b_amount: u128,
+        slot: u64,
+    ) -> Result<()> {
+        if token_a_amount == 0 {
+            return Ok(());
+        }
+        let price = Self::reserves_price(token_a_amount, token_b_amount)?;
+        let reference_price = u128::from(self.volatility_reference_price);
+        let price_move_bps = if reference_price == 0 {
+            0
+        } else {
+            let price_move = if price > reference_price {
+                price - reference_price
+            } else {
+                reference_price - price
+            };
+            try_math!(price_move.try_mul(10_000)?.try_div(reference_price))?
+        };
+        let volatility =
+            u128::from(self.decayed_volatility_bps(slot)).saturating_add(price_move_bps);
+
+        self.volatility_bps = volatility.min(u128::from(Self::MAX_VOLATILITY_BPS)) as u64;
+        self.volatility_reference_price = u64::try_from(price).unwrap_or(u64::MAX);
+        self.volatility_updated_slot = slot;
+        Ok(())
+    }
+
+    /// Host fees owed to the default host fees recipient, in token A or B
+    pub fn default_host_fees(&self, a_or_b: AorB) -> u64 {
+        match a_or_b {
+            AorB::A => self.default_host_fees_a,
+            AorB::B => self.default_host_fees_b,
+        }
+    }
+
+    pub fn default_host_fees_mut(&mut self, a_or_b: AorB) -> &mut u64 {
+        match a_or_b {
+            AorB::A => &mut self.default_host_fees_a,
+            AorB::B => &mut self.default_host_fees_b,
+        }
+    }
+
+    /// Protocol fees owed to the protocol fee authority, in token A or B
+    pub fn protocol_fees(&self, a_or_b: AorB) -> u64 {
+        match a_or_b {
+            AorB::A => self.protocol_fees_a,
+            AorB::B => self.protocol_fees_b,
+        }
+    }
+
+    pub fn protocol_fees_mut(&mut self, a_or_b: AorB) -> &mut u64 {
+        match a_or_b {
+            AorB::A => &mut self.protocol_fees_a,
+            AorB::B => &mut self.protocol_fees_b,
+        }
+    }
+
+    pub fn protocol_fees_swept_mut(&mut self, a_or_b: AorB) -> &mut u64 {
+        match a_or_b {
+            AorB::A => &mut self.protocol_fees_swept_a,
+            AorB::B => &mut self.protocol_fees_swept_b,
+        }
+    }
+
+    /// Add a swap to the net outflows of `slot`, failing once the outflow of the destination
+    /// token exceeds `max_slot_outflow_bps` of its reserves at the start of the slot
+    ///
+    /// `destination_reserve` is the destination vault balance after the swap, so the reserves at
+    /// the start of the slot are it plus the net outflow.
+    pub fn record_slot_outflow(
+        &mut self,
+        source: AorB,
+        amount_in: u64,
+        amount_out: u64,
+        destination_reserve: u64,
+        slot: u64,
+    ) -> Result<()> {
+        let (outflow_a, outflow_b) = if self.outflow_slot == slot {
+            (self.slot_outflow_a, self.slot_outflow_b)
+        } else {
+            (0, 0)
+        };
+        let (outflow_a, outflow_b) = match source {
+            AorB::A => (
+                outflow_a.saturating_sub(amount_in),
+                outflow_b.saturating_add(amount_out),
+            ),
+            AorB::B => (
+                outflow_a.saturating_add(amount_out),
+                outflow_b.saturating_sub(amount_in),
+            ),
+        };
+
+        let outflow = u128::from(match source {
+            AorB::A => outflow_b,
+            AorB::B => outflow_a,
+        });
+        let reserve_at_slot_start = try_math!(u128::from(destination_reserve).try_add(outflow))?;
+        let max_outflow = try_math!(reserve_at_slot_start
+            .try_mul(u128::from(self.max_slot_outflow_bps))?
+            .try_div(10_000))?;
+        require_msg!(
+            outflow <= max_outflow,
+            SwapError::SlotOutflowExceeded,
+            &format!(
+                "SlotOutflowExceeded: outflow={} > max_outflow={} in slot {}, max_slot_outflow_bps={}",
+                outflow, max_outflow, slot, self.max_slot_outflow_bps
+            )
+        );
+        self.outflow_slot = slot;
+        self.slot_outflow_a = outflow_a;
+        self.slot_outflow_b = outflow_b;
+        Ok(())
+    }
+
+    /// Amount of token A or B lent out by the ongoing flash loan
+    pub fn flash_loan_amount(&self, a_or_b: AorB) -> u64 {
+        match a_or_b {
+            AorB::A => self.flash_loan_amount_a,
+            AorB::B => self.flash_loan_amount_b,
+        }
+    }
+
+    pub fn flash_loan_amount_mut(&mut self, a_or_b: AorB) -> &mut u64 {
+        match a_or_b {
+            AorB::A => &mut self.flash_loan_amount_a,
+            AorB::B => &mut self.flash_loan_amount_b,
+        }
+    }
+
+    /// Flash fee on the amount of a flash loan, see [fees::flash_fee]
+    pub fn flash_fee(&self, loan_amount: u128) -> Result<u128> {
+        fees::flash_fee(loan_amount, self.flash_fee_bps, 10_000)
+    }
+
+    /// Calculate the protocol share of the owner fee, see [fees::protocol_fee]
+    pub fn protocol_fee(&self, owner_fee: u128) -> Result<u128> {
+        fees::protocol_fee(owner_fee, self.protocol_fee_bps, 10_000)
+    }
+
+    /// Tokens are lent out by a flash loan, so the vault balances are not the pool reserves
+    pub fn flash_loan_active(&self) -> bool {
+        self.flash_loan_amount_a != 0 || self.flash_loan_amount_b != 0
+    }
+
+    pub fn is_paused(&self, operation: PoolOperation) -> bool {
+        self.status & operation.flag() != 0
+    }
+
+    /// Check at the top of a handler that the admin has not paused its operation
+    pub fn require_not_paused(&self, operation: PoolOperation) -> Result<()> {
+        require_msg!(
+            !self.is_paused(operation),
+            SwapError::OperationPaused,
+            &format!("OperationPaused: {:?} are paused on this pool", operation)
+        );
+        Ok(())
+    }
+
+    /// Check before a single sided withdrawal or deposit that the reserves are not imbalanced by
+    /// more than `max_single_sided_withdraw_imbalance_bps`, see
+    /// [CurveCalculator::reserves_imbalance_bps]
+    pub fn require_single_sided_balanced(
+        &self,
+        calculator: &dyn CurveCalculator,
+        token_a_amount: u128,
+        token_b_amount: u128,
+    ) -> Result<()> {
+        if self.max_single_sided_withdraw_imbalance_bps == 0 {
+            return Ok(());
+        }
+        if let Some(imbalance_bps) =
+            calculator.reserves_imbalance_bps(token_a_amount, token_b_amount)?
+        {
+            require_msg!(
+                imbalance_bps <= self.max_single_sided_withdraw_imbalance_bps,
+                SwapError::PoolImbalanced,
+                &format!(
+                    "PoolImbalanced: imbalance_bps={} > max_single_sided_withdraw_imbalance_bps={}",
+                    imbalance_bps, self.max_single_sided_withdraw_imbalance_bps
+                )
+            );
+        }
+        Ok(())
+    }
 }
 
 impl SwapState for SwapPool {
@@ -137,6 +561,42 @@ impl SwapState for SwapPool {
     fn withdrawals_only(&self) -> bool {
         self.withdrawals_only != 0
     }
+
+    fn permissioned_swaps(&self) -> bool {
+        self.permissioned_swaps != 0
+    }
+
+    fn allows_deposits(&self) -> bool {
+        self.allows_deposits != 0
+    }
+
+    fn allows_single_sided_deposits(&self) -> bool {
+        self.allows_single_sided_deposits != 0
+    }
+
+    fn burn_owner_fees(&self) -> bool {
+        self.burn_owner_fees != 0
+    }
+
+    fn host_fee_in_destination_token(&self) -> bool {
+        self.host_fee_in_destination_token != 0
+    }
+
+    fn prevent_self_trades(&self) -> bool {
+        self.prevent_self_trades != 0
+    }
+
+    fn quiet_logs(&self) -> bool {
+        self.quiet_logs != 0
+    }
+
+    fn event_mode(&self) -> EventMode {
+        EventMode::try_from(self.event_mode).unwrap_or(EventMode::Log)
+    }
+
+    fn permissioned_deposits(&self) -> bool {
+        self.permissioned_deposits != 0
+    }
 }
 
 #[derive(
@@ -153,27 +613,106 @@ impl SwapState for SwapPool {
 #[repr(u16)]
 pub enum UpdatePoolConfigMode {
     WithdrawalsOnly = 0,
+    PermissionedSwaps = 1,
+    MinPrice = 2,
+    MaxPrice = 3,
+    PreventSelfTrades = 4,
+    QuietLogs = 5,
+    EventMode = 6,
+    PermissionedDeposits = 7,
+    DynamicFeeNumerator = 8,
+    MaxDynamicTradeFeeNumerator = 9,
+    Status = 10,
+    BurnOwnerFees = 11,
+    MaxTradeSizeBps = 12,
+    MaxPriceImpactBps = 13,
+    MaxSlotOutflowBps = 14,
+    FlashFeeBps = 15,
+    ProtocolFeeBps = 16,
+    HostFeeInDestinationToken = 17,
+    MaxSingleSidedWithdrawImbalanceBps = 18,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
-pub enum UpdatePoolConfigValue {
-    Bool(bool),
+impl UpdatePoolConfigMode {
+    /// Feature of the `FeatureSet` which must be enabled to turn this config on
+    pub fn feature(self) -> Option<Feature> {
+        match self {
+            UpdatePoolConfigMode::PermissionedSwaps => Some(Feature::PermissionedSwaps),
+            UpdatePoolConfigMode::PermissionedDeposits => Some(Feature::PermissionedDeposits),
+            _ => None,
+        }
+    }
+}
+
+/// Channel through which a pool emits its events, picked by the admin for the pool's indexers
+#[derive(
+    Debug,
+    TryFromPrimitive,
+    EnumString,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u64)]
+pub enum EventMode {
+    /// `emit!` style "Program data" log, truncated by RPCs when the logs are too long
+    Log = 0,
+    /// Instruction return data only, which every handler already sets
+    ReturnData = 1,
+    /// Self-invoked `emit_event` instruction carrying the event, never truncated
+    Cpi = 2,
+}
+
+/// Operations the admin can pause independently through the pool `status`
+#[derive(
+    Debug,
+    TryFromPrimitive,
+    EnumString,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u16)]
+pub enum PoolOperation {
+    /// Swaps, including routed swaps
+    Swaps = 0,
+    /// Balanced and single sided deposits, and rebalances into the pool
+    Deposits = 1,
+    /// Single sided deposits and withdrawals
+    SingleSided = 2,
+    /// Owner, protocol and default host fee withdrawals
+    FeeWithdrawals = 3,
 }
 
-impl Deref for UpdatePoolConfigValue {
-    type Target = bool;
+impl PoolOperation {
+    /// Every status bit with an operation, the others must stay unset
+    pub const ALL_FLAGS: u64 = (1 << PoolOperation::Swaps as u64)
+        | (1 << PoolOperation::Deposits as u64)
+        | (1 << PoolOperation::SingleSided as u64)
+        | (1 << PoolOperation::FeeWithdrawals as u64);
 
-    fn deref(&self) -> &Self::Target {
-        match self {
-            UpdatePoolConfigValue::Bool(v) => v,
-        }
+    pub fn flag(self) -> u64 {
+        1 << self as u64
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub enum UpdatePoolConfigValue {
+    Bool(bool),
+    U64(u64),
+}
+
 impl UpdatePoolConfigValue {
     pub fn to_u64(&self) -> u64 {
         match self {
             UpdatePoolConfigValue::Bool(v) => *v as u64,
+            UpdatePoolConfigValue::U64(v) => *v,
         }
     }
 }
@@ -186,6 +725,52 @@ impl UpdatePoolConfigValue {
                 val[0] = *v as u8;
                 val
             }
+            UpdatePoolConfigValue::U64(v) => {
+                val[..8].copy_from_slice(&v.to_le_bytes());
+                val
+            }
+        }
+    }
+
+    /// Decode a raw value, its encoding depends on the mode
+    pub fn decode(mode: UpdatePoolConfigMode, val: &[u8]) -> Result<Self> {
+        match mode {
+            UpdatePoolConfigMode::WithdrawalsOnly
+            | UpdatePoolConfigMode::PermissionedSwaps
+            | UpdatePoolConfigMode::PreventSelfTrades
+            | UpdatePoolConfigMode::QuietLogs
+            | UpdatePoolConfigMode::PermissionedDeposits
+            | UpdatePoolConfigMode::BurnOwnerFees
+            | UpdatePoolConfigMode::HostFeeInDestinationToken => Self::from_bool_bytes(val),
+            UpdatePoolConfigMode::MinPrice
+            | UpdatePoolConfigMode::MaxPrice
+            | UpdatePoolConfigMode::DynamicFeeNumerator
+            | UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator => Self::from_u64_bytes(val),
+            UpdatePoolConfigMode::EventMode => {
+                let value = Self::from_u64_bytes(val)?;
+                EventMode::try_from(value.to_u64())
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(value)
+            }
+            UpdatePoolConfigMode::Status => {
+                let value = Self::from_u64_bytes(val)?;
+                if value.to_u64() & !PoolOperation::ALL_FLAGS != 0 {
+                    return Err(ProgramError::InvalidInstructionData.into());
+                }
+                Ok(value)
+            }
+            UpdatePoolConfigMode::MaxTradeSizeBps
+            | UpdatePoolConfigMode::MaxPriceImpactBps
+            | UpdatePoolConfigMode::MaxSlotOutflowBps
+            | UpdatePoolConfigMode::FlashFeeBps
+            | UpdatePoolConfigMode::ProtocolFeeBps
+            | UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps => {
+                let value = Self::from_u64_bytes(val)?;
+                if value.to_u64() > 10_000 {
+                    return Err(ProgramError::InvalidInstructionData.into());
+                }
+                Ok(value)
+            }
         }
     }
 
@@ -196,11 +781,525 @@ impl UpdatePoolConfigValue {
             _ => Err(ProgramError::InvalidInstructionData.into()),
         }
     }
+
+    pub fn from_u64_bytes(val: &[u8]) -> Result<Self> {
+        let bytes = val
+            .get(..8)
+            .and_then(|b| b.try_into().ok())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(UpdatePoolConfigValue::U64(u64::from_le_bytes(bytes)))
+    }
+}
+
+/// Instructions which can be shipped disabled and enabled per cluster through the `FeatureSet`
+#[derive(
+    Debug,
+    TryFromPrimitive,
+    EnumString,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u16)]
+pub enum Feature {
+    PermissionedSwaps = 0,
+    PermissionedDeposits = 1,
+    MintMigration = 2,
+}
+
+/// Global runtime feature flags, toggled by the program admin
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct FeatureSet {
+    /// Program admin - account which can toggle features
+    pub admin: Pubkey,
+    /// Bitmask of enabled features, indexed by `Feature`
+    pub enabled_features: u64,
+    /// Programs `migrate_mint` may invoke to convert vault balances, default pubkey if unset
+    pub conversion_programs: [Pubkey; FeatureSet::MAX_CONVERSION_PROGRAMS],
+    pub _padding: [u64; 3],
+}
+
+impl FeatureSet {
+    pub const MAX_CONVERSION_PROGRAMS: usize = 3;
+    pub const LEN: usize =
+        DISCRIMINATOR_SIZE + 32 + 8 + (Self::MAX_CONVERSION_PROGRAMS * 32) + (3 * 8);
+
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.enabled_features & (1 << feature as u64) != 0
+    }
+
+    /// Check at the top of a gated handler that its feature has been enabled
+    pub fn require_enabled(&self, feature: Feature) -> Result<()> {
+        require_msg!(
+            self.is_enabled(feature),
+            SwapError::FeatureDisabled,
+            &format!("FeatureDisabled: feature {:?} is not enabled", feature)
+        );
+        Ok(())
+    }
+
+    pub fn set_enabled(&mut self, feature: Feature, enabled: bool) {
+        if enabled {
+            self.enabled_features |= 1 << feature as u64;
+        } else {
+            self.enabled_features &= !(1 << feature as u64);
+        }
+    }
+
+    /// Allow `program` as a conversion program in slot `index`, or clear the slot with the default
+    /// pubkey
+    pub fn set_conversion_program(&mut self, index: u16, program: Pubkey) -> Result<()> {
+        let slot = self
+            .conversion_programs
+            .get_mut(usize::from(index))
+            .ok_or_else(|| {
+                msg!(
+                    "InvalidConversionProgram: index {} >= max {}",
+                    index,
+                    Self::MAX_CONVERSION_PROGRAMS
+                );
+                error!(SwapError::InvalidConversionProgram)
+            })?;
+        *slot = program;
+        Ok(())
+    }
+
+    /// Check that the program admin allowed `program` to convert the vaults of migrated mints
+    pub fn require_conversion_program_allowed(&self, program: &Pubkey) -> Result<()> {
+        require_msg!(
+            *program != Pubkey::default() && self.conversion_programs.contains(program),
+            SwapError::InvalidConversionProgram,
+            &format!(
+                "InvalidConversionProgram: {} is not an allowed conversion program",
+                program
+            )
+        );
+        Ok(())
+    }
+}
+
+/// Fee configurations approved by the program admin, which `initialize_pool_with_fee_tier`
+/// creates pools with by index
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct FeeTierRegistry {
+    /// Program admin - account which can set the fee tiers
+    pub admin: Pubkey,
+    /// Number of tiers set, the first `tiers_len` entries of `tiers`
+    pub tiers_len: u64,
+    pub tiers: [Fees; FeeTierRegistry::MAX_TIERS],
+    pub _padding: [u64; 8],
+}
+
+impl FeeTierRegistry {
+    pub const MAX_TIERS: usize = 8;
+    /// `Fees` are 8 u64
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 8 + (Self::MAX_TIERS * 8 * 8) + (8 * 8);
+
+    /// Fees of the tier at `index`, if it is set
+    pub fn tier(&self, index: u16) -> Result<Fees> {
+        let index = usize::from(index);
+        require_msg!(
+            index < self.tiers_len as usize,
+            SwapError::InvalidFeeTier,
+            &format!(
+                "InvalidFeeTier: tier {} is not set, the registry has {} tiers",
+                index, self.tiers_len
+            )
+        );
+        Ok(self.tiers[index])
+    }
+
+    /// Replace the tier at `index`, or append it when `index` is the number of tiers set
+    pub fn set_tier(&mut self, index: u16, fees: Fees) -> Result<()> {
+        let index = usize::from(index);
+        require_msg!(
+            index <= self.tiers_len as usize && index < Self::MAX_TIERS,
+            SwapError::InvalidFeeTier,
+            &format!(
+                "InvalidFeeTier: tier {} is past the {} tiers set, at most {}",
+                index,
+                self.tiers_len,
+                Self::MAX_TIERS
+            )
+        );
+        self.tiers[index] = fees;
+        if index == self.tiers_len as usize {
+            self.tiers_len += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Program-wide authority collecting the protocol share of the owner trade fees of every pool
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct ProtocolFeeConfig {
+    /// Account which can withdraw the protocol fees accrued by the pools, and from the shared
+    /// protocol fee vaults whose token authority is this config
+    pub authority: Pubkey,
+    pub _padding: [u64; 8],
+}
+
+impl ProtocolFeeConfig {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + (8 * 8);
+}
+
+/// Allow-list entry for a taker of a permissioned swap pool
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct AllowedSwapTaker {
+    /// The pool this entry belongs to
+    pub pool: Pubkey,
+    /// The signer allowed to swap against the pool
+    pub taker: Pubkey,
+    pub _padding: [u64; 4],
+}
+
+impl AllowedSwapTaker {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + (4 * 8);
+}
+
+/// Allow-list entry for a liquidity provider of a permissioned deposit pool
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct AllowedLiquidityProvider {
+    /// The pool this entry belongs to
+    pub pool: Pubkey,
+    /// The signer allowed to deposit into the pool
+    pub provider: Pubkey,
+    pub _padding: [u64; 4],
+}
+
+impl AllowedLiquidityProvider {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + (4 * 8);
+
+    /// Check the depositor of a permissioned deposits pool passed its own allow-list entry
+    pub fn require_allowed(entry: Option<&Self>, pool: &Pubkey, provider: &Pubkey) -> Result<()> {
+        let entry = entry.ok_or(SwapError::LiquidityProviderNotAllowed)?;
+        require_msg!(
+            entry.pool == *pool && entry.provider == *provider,
+            SwapError::LiquidityProviderNotAllowed,
+            &format!(
+                "LiquidityProviderNotAllowed: allowed_liquidity_provider.pool ({}) != pool.key ({}) or allowed_liquidity_provider.provider ({}) != signer.key ({})",
+                entry.pool, pool, entry.provider, provider
+            )
+        );
+        Ok(())
+    }
+}
+
+/// Recipient of the host fees of swaps routed without a host fees account
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct DefaultHostFees {
+    /// The pool this recipient belongs to
+    pub pool: Pubkey,
+    /// Owner of the token accounts the accrued host fees are claimed to
+    pub recipient: Pubkey,
+    pub _padding: [u64; 4],
+}
+
+impl DefaultHostFees {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + (4 * 8);
+}
+
+/// Queued withdrawal of pool tokens, claimable once the pool can pay out the requested minimums
+///
+/// The pool tokens are escrowed in a vault owned by the ticket, out of reach of the pool authority,
+/// and burned when the ticket is claimed.
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct WithdrawalTicket {
+    /// The pool to withdraw from
+    pub pool: Pubkey,
+    /// Owner of the pool tokens, receives the withdrawn trading tokens
+    pub owner: Pubkey,
+    /// Amount of pool tokens to burn
+    pub pool_token_amount: u64,
+    /// Minimum amount of token A to receive before anyone but the owner can claim
+    pub minimum_token_a_amount: u64,
+    /// Minimum amount of token B to receive before anyone but the owner can claim
+    pub minimum_token_b_amount: u64,
+    /// Token account owned by the ticket which escrows the pool tokens
+    pub pool_token_vault: Pubkey,
+    /// Bump seed of the ticket, which signs for its pool token vault
+    pub bump: u64,
+    pub _padding: [u64; 3],
+}
+
+impl WithdrawalTicket {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + 8 + 8 + 8 + 32 + 8 + (3 * 8);
+}
+
+/// Opt-in trading history of a user on a pool, updated by swaps passing it as first remaining account
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct UserStats {
+    /// The pool the swaps are made against
+    pub pool: Pubkey,
+    /// Owner of the user token accounts the swaps are made from
+    pub owner: Pubkey,
+    /// Cumulative amount of token A swapped in, fees included
+    pub volume_token_a: u64,
+    /// Cumulative amount of token B swapped in, fees included
+    pub volume_token_b: u64,
+    /// Cumulative trade and owner fees paid in token A
+    pub fees_paid_token_a: u64,
+    /// Cumulative trade and owner fees paid in token B
+    pub fees_paid_token_b: u64,
+    /// Number of swaps recorded
+    pub trade_count: u64,
+    /// Slot of the last recorded swap
+    pub last_trade_slot: u64,
+    pub _padding: [u64; 8],
+}
+
+impl UserStats {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + (6 * 8) + (8 * 8);
+
+    pub fn record_swap(
+        &mut self,
+        trade_direction: TradeDirection,
+        amount_in: u64,
+        fees: u64,
+        slot: u64,
+    ) {
+        let (volume, fees_paid) = match trade_direction {
+            TradeDirection::AtoB => (&mut self.volume_token_a, &mut self.fees_paid_token_a),
+            TradeDirection::BtoA => (&mut self.volume_token_b, &mut self.fees_paid_token_b),
+        };
+        *volume = volume.saturating_add(amount_in);
+        *fees_paid = fees_paid.saturating_add(fees);
+        self.trade_count = self.trade_count.saturating_add(1);
+        self.last_trade_slot = slot;
+    }
+}
+
+/// Reward tokens shared between the pool token holders which registered before the snapshot slot
+///
+/// A past token balance cannot be read on-chain, so owners lock their pool tokens in the airdrop
+/// vault until the snapshot slot instead, and claim their pro-rata share of the reward afterwards.
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct LpAirdrop {
+    /// The pool whose pool token holders receive the reward
+    pub pool: Pubkey,
+    /// Signer which funded the airdrop, receives the unclaimed rewards when it is closed
+    pub sponsor: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Holds the reward tokens, owned by the pool authority
+    pub reward_vault: Pubkey,
+    /// Holds the registered pool tokens until they are claimed, owned by the pool authority
+    pub pool_token_vault: Pubkey,
+    /// Reward tokens shared between the registered pool tokens
+    pub reward_amount: u64,
+    /// Last slot pool tokens can be registered in, claims are possible from the next slot
+    pub snapshot_slot: u64,
+    /// Pool tokens registered by all owners
+    pub registered_pool_tokens: u64,
+    /// Registered pool tokens which have been claimed and returned to their owner
+    pub claimed_pool_tokens: u64,
+    pub _padding: [u64; 8],
+}
+
+impl LpAirdrop {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + (5 * 32) + (4 * 8) + (8 * 8);
+
+    /// Share of the reward owed to `pool_token_amount` registered pool tokens, rounded down
+    pub fn reward_share(&self, pool_token_amount: u64) -> Option<u64> {
+        if self.registered_pool_tokens == 0 {
+            return Some(0);
+        }
+        let share = u128::from(self.reward_amount)
+            .checked_mul(u128::from(pool_token_amount))?
+            .checked_div(u128::from(self.registered_pool_tokens))?;
+        u64::try_from(share).ok()
+    }
+}
+
+/// Pool tokens registered with an [LpAirdrop] by an owner
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct LpAirdropPosition {
+    pub airdrop: Pubkey,
+    /// Owner of the registered pool tokens, receives them back with the reward share
+    pub owner: Pubkey,
+    pub pool_token_amount: u64,
+    pub _padding: [u64; 4],
+}
+
+impl LpAirdropPosition {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + 8 + (4 * 8);
+}
+
+/// Pool tokens an owner locked in escrow until a slot, e.g. for a team to prove it cannot pull its
+/// liquidity before then
+///
+/// The locked pool tokens keep earning the trade fees left in the pool, they just cannot be
+/// withdrawn or moved until the unlock slot.
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct LockedLpPosition {
+    pub pool: Pubkey,
+    /// Owner of the locked pool tokens, the only signer which can unlock them
+    pub owner: Pubkey,
+    /// Holds the locked pool tokens until they are unlocked, owned by the pool authority
+    pub pool_token_vault: Pubkey,
+    pub pool_token_amount: u64,
+    /// Slot the pool tokens were locked in
+    pub locked_slot: u64,
+    /// First slot the pool tokens can be unlocked in
+    pub unlock_slot: u64,
+    pub _padding: [u64; 8],
+}
+
+impl LockedLpPosition {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + (3 * 32) + (3 * 8) + (8 * 8);
+
+    /// Slots left until the pool tokens can be unlocked, zero once they can
+    pub fn remaining_slots(&self, current_slot: u64) -> u64 {
+        self.unlock_slot.saturating_sub(current_slot)
+    }
+}
+
+/// Pending pool config change, executable by the admin once the approval window has passed
+///
+/// Lets a governance program acting as the pool admin put parameter changes up for review on-chain
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct ProposedConfigChange {
+    /// The pool to update
+    pub pool: Pubkey,
+    /// [UpdatePoolConfigMode] of the change
+    pub mode: u16,
+    /// Value to set, encoded as in `update_pool_config`
+    pub value: [u8; VALUE_BYTE_ARRAY_LEN],
+    /// Slot the change was proposed in
+    pub proposed_slot: u64,
+    /// First slot the change can be executed in
+    pub executable_slot: u64,
+    pub _padding: [u64; 8],
+}
+
+impl ProposedConfigChange {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 2 + VALUE_BYTE_ARRAY_LEN + 8 + 8 + (8 * 8);
+}
+
+/// Number of changes kept in a `PoolConfigHistory`, older changes are overwritten
+pub const CONFIG_HISTORY_LEN: usize = 32;
+
+/// A pool config change, values are packed as in `UpdatePoolConfigValue::to_u64`
+#[zero_copy]
+#[derive(Debug, Default, PartialEq)]
+pub struct ConfigChange {
+    /// [UpdatePoolConfigMode] of the change
+    pub mode: u64,
+    pub old_value: u64,
+    pub new_value: u64,
+    /// Slot the change was applied in
+    pub slot: u64,
+    /// Signer which applied the change
+    pub authority: Pubkey,
+}
+
+/// Bounded history of the config changes of a pool, so LPs can audit the admin
+///
+/// Once initialized, every `update_pool_config` and `execute_pool_config` of the pool is recorded.
+#[account(zero_copy)]
+#[derive(Debug, PartialEq)]
+pub struct PoolConfigHistory {
+    /// The pool the changes belong to
+    pub pool: Pubkey,
+    /// Number of changes recorded since initialization, the next change is written at
+    /// `total_changes % CONFIG_HISTORY_LEN`
+    pub total_changes: u64,
+    pub changes: [ConfigChange; CONFIG_HISTORY_LEN],
+    pub _padding: [u64; 8],
+}
+
+impl Default for PoolConfigHistory {
+    fn default() -> Self {
+        Self {
+            pool: Pubkey::default(),
+            total_changes: 0,
+            changes: [ConfigChange::default(); CONFIG_HISTORY_LEN],
+            _padding: [0; 8],
+        }
+    }
+}
+
+impl PoolConfigHistory {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 8 + (CONFIG_HISTORY_LEN * 64) + (8 * 8);
+
+    pub fn push(&mut self, change: ConfigChange) {
+        let index = (self.total_changes % CONFIG_HISTORY_LEN as u64) as usize;
+        self.changes[index] = change;
+        self.total_changes = self.total_changes.saturating_add(1);
+    }
+
+    /// Recorded changes still in the history, oldest first
+    pub fn recent_changes(&self) -> Vec<ConfigChange> {
+        let len = CONFIG_HISTORY_LEN as u64;
+        let first = self.total_changes.saturating_sub(len);
+        (first..self.total_changes)
+            .map(|i| self.changes[(i % len) as usize])
+            .collect()
+    }
 }
 
 pub struct Curve {}
 impl Curve {
     pub const LEN: usize = DISCRIMINATOR_SIZE + (16 * 8);
+    /// Offset of `checksum`, the last field of every curve
+    pub const CHECKSUM_OFFSET: usize = Self::LEN - 8;
+
+    /// Checksum of the discriminator and parameters of a serialized curve
+    pub fn checksum(data: &[u8]) -> u64 {
+        let hash = hash(&data[..Self::CHECKSUM_OFFSET]);
+        let mut bytes = [0_u8; 8];
+        bytes.copy_from_slice(&hash.as_ref()[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Write the checksum of a serialized curve in its `checksum` field
+    pub fn write_checksum(data: &mut [u8]) {
+        let checksum = Self::checksum(data);
+        data[Self::CHECKSUM_OFFSET..Self::LEN].copy_from_slice(&checksum.to_le_bytes());
+    }
+
+    /// Check a serialized curve against its `checksum` field
+    ///
+    /// Only called for pools with `curve_checksum_written`, the curves created before the checksum
+    /// was introduced have none until `write_curve_checksum` is run.
+    pub fn verify_checksum(data: &[u8]) -> Result<()> {
+        require_msg!(
+            data.len() >= Self::LEN,
+            SwapError::CurveCorrupted,
+            &format!(
+                "CurveCorrupted: curve account is {} bytes, expected {}",
+                data.len(),
+                Self::LEN
+            )
+        );
+        let mut bytes = [0_u8; 8];
+        bytes.copy_from_slice(&data[Self::CHECKSUM_OFFSET..Self::LEN]);
+        let stored = u64::from_le_bytes(bytes);
+        let computed = Self::checksum(data);
+        require_msg!(
+            stored == computed,
+            SwapError::CurveCorrupted,
+            &format!(
+                "CurveCorrupted: stored checksum={} != computed checksum={}",
+                stored, computed
+            )
+        );
+        Ok(())
+    }
 }
 
 #[account]
@@ -208,13 +1307,19 @@ impl Curve {
 pub struct ConstantPriceCurve {
     /// Amount of token A required to get 1 token B
     pub token_b_price: u64,
-    pub _padding: [u64; 15],
+    /// Symmetric bid/ask spread around `token_b_price`, in basis points
+    pub spread_bps: u64,
+    pub _padding: [u64; 13],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
 }
 
 #[account]
 #[derive(Debug, PartialEq, Default)]
 pub struct ConstantProductCurve {
-    pub _padding: [u64; 16],
+    pub _padding: [u64; 15],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
 }
 
 #[account]
@@ -222,7 +1327,9 @@ pub struct ConstantProductCurve {
 pub struct OffsetCurve {
     /// Amount to offset the token B liquidity account
     pub token_b_offset: u64,
-    pub _padding: [u64; 15],
+    pub _padding: [u64; 14],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
 }
 
 #[account]
@@ -234,7 +1341,9 @@ pub struct StableCurve {
     pub token_a_factor: u64,
     /// Amount of token B required to get 1 token A
     pub token_b_factor: u64,
-    pub _padding: [u64; 13],
+    pub _padding: [u64; 12],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
 }
 
 impl StableCurve {
@@ -243,13 +1352,166 @@ impl StableCurve {
             amp,
             token_a_factor: try_math!(decimals_to_factor(token_a_decimals, token_b_decimals))?,
             token_b_factor: try_math!(decimals_to_factor(token_b_decimals, token_a_decimals))?,
-            _padding: [0; 13],
+            _padding: [0; 12],
+            checksum: 0,
+        })
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct PeggedCurve {
+    /// Amplifier constant
+    pub amp: u64,
+    /// Price of one token B in tokens A the liquidity is concentrated around, scaled by `PEG_SCALE`
+    pub token_b_peg: u64,
+    /// Value of one native token A, with the decimals of both mints and the peg applied
+    pub token_a_factor: u64,
+    /// Value of one native token B, with the decimals of both mints and the peg applied
+    pub token_b_factor: u64,
+    pub _padding: [u64; 11],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
+}
+
+impl PeggedCurve {
+    /// Scale of `token_b_peg`
+    pub const PEG_SCALE: u64 = 1_000_000_000;
+
+    pub fn new(
+        amp: u64,
+        token_b_peg: u64,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+    ) -> Result<Self> {
+        let token_a_factor = try_math!(
+            decimals_to_factor(token_a_decimals, token_b_decimals)?.try_mul(Self::PEG_SCALE)
+        )?;
+        let token_b_factor = try_math!(
+            decimals_to_factor(token_b_decimals, token_a_decimals)?.try_mul(token_b_peg)
+        )?;
+        Ok(Self {
+            amp,
+            token_b_peg,
+            token_a_factor,
+            token_b_factor,
+            _padding: [0; 11],
+            checksum: 0,
         })
     }
 }
 
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct ConstantProductWithBoundsCurve {
+    /// Lowest price of token A in token B native units the liquidity is provided at, scaled by
+    /// `PRICE_SCALE`
+    pub min_price: u64,
+    /// Highest price of token A in token B native units the liquidity is provided at, scaled by
+    /// `PRICE_SCALE`
+    pub max_price: u64,
+    pub _padding: [u64; 13],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
+}
+
+impl ConstantProductWithBoundsCurve {
+    /// Scale of `min_price` and `max_price`, the same as the pool price bounds
+    pub const PRICE_SCALE: u64 = SwapPool::PRICE_BOUNDS_SCALE;
+
+    pub fn new(min_price: u64, max_price: u64) -> Self {
+        Self {
+            min_price,
+            max_price,
+            _padding: [0; 13],
+            checksum: 0,
+        }
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct WeightedCurve {
+    /// Share of the pool value held in token A, out of `WEIGHTS_DENOMINATOR`
+    pub weight_a: u64,
+    /// Share of the pool value held in token B, out of `WEIGHTS_DENOMINATOR`
+    pub weight_b: u64,
+    pub _padding: [u64; 13],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
+}
+
+impl WeightedCurve {
+    /// Sum of `weight_a` and `weight_b`, ie. weights are percentages of the pool value
+    pub const WEIGHTS_DENOMINATOR: u64 = 100;
+
+    pub fn new(weight_a: u64, weight_b: u64) -> Self {
+        Self {
+            weight_a,
+            weight_b,
+            _padding: [0; 13],
+            checksum: 0,
+        }
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct OraclePeggedCurve {
+    /// Price account quoting one token B in tokens A, passed to `swap`
+    pub oracle: Pubkey,
+    /// Layout of the price account, see `OracleType`
+    pub oracle_type: u64,
+    /// Index of the price in a Scope prices account, unused with Pyth
+    pub scope_price_index: u64,
+    /// Oldest price accepted by `swap`, in slots behind the current slot
+    pub max_age_slots: u64,
+    /// Symmetric bid/ask spread around the oracle price, in basis points
+    pub spread_bps: u64,
+    pub token_a_decimals: u64,
+    pub token_b_decimals: u64,
+    /// Oracle price cached by `refresh_price`, `value * 10^exponent`, 0 until it is first refreshed
+    /// The swaps which are not passed the oracle account swap at this price
+    pub cached_price_value: u64,
+    pub cached_price_exponent: i64,
+    /// Slot the cached price was published at, its age is checked against `max_age_slots`
+    pub cached_price_slot: u64,
+    pub _padding: [u64; 2],
+    /// Checksum of the curve account written at pool initialization, see `Curve::checksum`
+    pub checksum: u64,
+}
+
+impl OraclePeggedCurve {
+    pub fn new(
+        oracle: Pubkey,
+        oracle_type: u64,
+        scope_price_index: u64,
+        max_age_slots: u64,
+        spread_bps: u64,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+    ) -> Self {
+        Self {
+            oracle,
+            oracle_type,
+            scope_price_index,
+            max_age_slots,
+            spread_bps,
+            token_a_decimals: token_a_decimals.into(),
+            token_b_decimals: token_b_decimals.into(),
+            cached_price_value: 0,
+            cached_price_exponent: 0,
+            cached_price_slot: 0,
+            _padding: [0; 2],
+            checksum: 0,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use anchor_lang::{AccountDeserialize, Discriminator};
+
     use super::*;
 
     #[test]
@@ -257,4 +1519,294 @@ mod tests {
         let x = std::mem::size_of::<SwapPool>();
         assert_eq!(x, SwapPool::LEN - DISCRIMINATOR_SIZE);
     }
+
+    #[test]
+    fn test_feature_set_toggle() {
+        let mut feature_set = FeatureSet::default();
+        assert!(!feature_set.is_enabled(Feature::PermissionedSwaps));
+
+        feature_set.set_enabled(Feature::PermissionedSwaps, true);
+        assert!(feature_set.is_enabled(Feature::PermissionedSwaps));
+        assert_eq!(feature_set.enabled_features, 1);
+
+        feature_set.set_enabled(Feature::PermissionedDeposits, true);
+        assert!(feature_set.is_enabled(Feature::PermissionedDeposits));
+        assert_eq!(feature_set.enabled_features, 3);
+
+        feature_set.set_enabled(Feature::PermissionedSwaps, false);
+        assert!(!feature_set.is_enabled(Feature::PermissionedSwaps));
+        assert!(feature_set.is_enabled(Feature::PermissionedDeposits));
+        assert_eq!(feature_set.enabled_features, 2);
+    }
+
+    #[test]
+    fn test_feature_set_conversion_programs() {
+        let mut feature_set = FeatureSet::default();
+        assert_eq!(
+            feature_set.try_to_vec().unwrap().len(),
+            FeatureSet::LEN - DISCRIMINATOR_SIZE
+        );
+        let program = Pubkey::new_unique();
+        assert_eq!(
+            feature_set.require_conversion_program_allowed(&program),
+            Err(SwapError::InvalidConversionProgram.into())
+        );
+        // unset slots do not allow the default pubkey
+        assert_eq!(
+            feature_set.require_conversion_program_allowed(&Pubkey::default()),
+            Err(SwapError::InvalidConversionProgram.into())
+        );
+
+        feature_set.set_conversion_program(2, program).unwrap();
+        feature_set
+            .require_conversion_program_allowed(&program)
+            .unwrap();
+        assert_eq!(
+            feature_set.set_conversion_program(3, program),
+            Err(SwapError::InvalidConversionProgram.into())
+        );
+
+        feature_set
+            .set_conversion_program(2, Pubkey::default())
+            .unwrap();
+        assert_eq!(
+            feature_set.require_conversion_program_allowed(&program),
+            Err(SwapError::InvalidConversionProgram.into())
+        );
+    }
+
+    #[test]
+    fn test_fee_tier_registry_tiers() {
+        let mut registry = FeeTierRegistry::default();
+        assert_eq!(
+            registry.try_to_vec().unwrap().len(),
+            FeeTierRegistry::LEN - DISCRIMINATOR_SIZE
+        );
+        assert_eq!(
+            registry.tier(0).unwrap_err(),
+            SwapError::InvalidFeeTier.into()
+        );
+
+        let fees = Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            ..Default::default()
+        };
+        registry.set_tier(0, fees).unwrap();
+        assert_eq!(registry.tiers_len, 1);
+        assert_eq!(registry.tier(0).unwrap(), fees);
+
+        // tiers are appended in order and replaced in place
+        assert_eq!(
+            registry.set_tier(2, fees).unwrap_err(),
+            SwapError::InvalidFeeTier.into()
+        );
+        let replaced = Fees {
+            trade_fee_numerator: 30,
+            ..fees
+        };
+        registry.set_tier(0, replaced).unwrap();
+        assert_eq!(registry.tiers_len, 1);
+        assert_eq!(registry.tier(0).unwrap(), replaced);
+
+        for index in 1..FeeTierRegistry::MAX_TIERS as u16 {
+            registry.set_tier(index, fees).unwrap();
+        }
+        assert_eq!(registry.tiers_len, FeeTierRegistry::MAX_TIERS as u64);
+        assert_eq!(
+            registry
+                .set_tier(FeeTierRegistry::MAX_TIERS as u16, fees)
+                .unwrap_err(),
+            SwapError::InvalidFeeTier.into()
+        );
+    }
+
+    #[test]
+    fn test_swap_pool_slot_outflow() {
+        let mut pool = SwapPool {
+            max_slot_outflow_bps: 1_000,
+            ..Default::default()
+        };
+
+        // 60 of 1000 B, then 40 more reaching 10% of the reserves at the start of the slot
+        pool.record_slot_outflow(AorB::A, 60, 60, 940, 1).unwrap();
+        pool.record_slot_outflow(AorB::A, 40, 40, 900, 1).unwrap();
+        assert_eq!(pool.slot_outflow_b, 100);
+        assert_eq!(
+            pool.record_slot_outflow(AorB::A, 1, 1, 899, 1),
+            Err(SwapError::SlotOutflowExceeded.into())
+        );
+
+        // swapping B back in offsets the outflow
+        pool.record_slot_outflow(AorB::B, 50, 50, 950, 1).unwrap();
+        assert_eq!(pool.slot_outflow_b, 50);
+        assert_eq!(pool.slot_outflow_a, 50);
+        pool.record_slot_outflow(AorB::A, 50, 50, 900, 1).unwrap();
+
+        // the next slot starts over
+        pool.record_slot_outflow(AorB::A, 90, 90, 810, 2).unwrap();
+        assert_eq!(pool.outflow_slot, 2);
+        assert_eq!(pool.slot_outflow_a, 0);
+        assert_eq!(pool.slot_outflow_b, 90);
+    }
+
+    #[test]
+    fn test_swap_pool_volatility() {
+        let mut pool = SwapPool {
+            fees: Fees {
+                trade_fee_numerator: 25,
+                trade_fee_denominator: 10_000,
+                ..Default::default()
+            },
+            dynamic_fee_numerator: 1,
+            max_dynamic_trade_fee_numerator: 100,
+            ..Default::default()
+        };
+
+        // the first update only sets the reference price
+        pool.update_volatility(1_000_000, 1_000_000, 10).unwrap();
+        assert_eq!(pool.volatility_bps, 0);
+        assert_eq!(
+            pool.volatility_reference_price,
+            SwapPool::PRICE_BOUNDS_SCALE
+        );
+
+        // 1% move
+        pool.update_volatility(1_000_000, 1_010_000, 10).unwrap();
+        assert_eq!(pool.volatility_bps, 100);
+        assert_eq!(pool.swap_fees(10).unwrap().trade_fee_numerator, 100);
+
+        let half_life = SwapPool::VOLATILITY_HALF_LIFE_SLOTS;
+        assert_eq!(pool.decayed_volatility_bps(10 + half_life - 1), 100);
+        assert_eq!(pool.decayed_volatility_bps(10 + half_life), 50);
+        assert_eq!(pool.decayed_volatility_bps(10 + 2 * half_life), 25);
+        assert_eq!(pool.decayed_volatility_bps(u64::MAX), 0);
+        assert_eq!(
+            pool.swap_fees(10 + half_life).unwrap().trade_fee_numerator,
+            75
+        );
+
+        // the decayed volatility is grown by the next move
+        pool.update_volatility(1_000_000, 1_000_000, 10 + half_life)
+            .unwrap();
+        assert_eq!(pool.volatility_bps, 50 + 99);
+        assert_eq!(pool.volatility_updated_slot, 10 + half_life);
+
+        pool.update_volatility(1, 1_000_000, 10 + half_life)
+            .unwrap();
+        assert_eq!(pool.volatility_bps, SwapPool::MAX_VOLATILITY_BPS);
+
+        // no fee change with dynamic fees disabled
+        pool.dynamic_fee_numerator = 0;
+        assert_eq!(pool.swap_fees(10).unwrap().trade_fee_numerator, 25);
+    }
+
+    #[test]
+    fn test_pool_config_history_size() {
+        let x = std::mem::size_of::<PoolConfigHistory>();
+        assert_eq!(x, PoolConfigHistory::LEN - DISCRIMINATOR_SIZE);
+    }
+
+    #[test]
+    fn test_pool_config_history_wraps_around() {
+        let mut history = PoolConfigHistory::default();
+        for i in 0..(CONFIG_HISTORY_LEN as u64 + 3) {
+            history.push(ConfigChange {
+                new_value: i,
+                ..Default::default()
+            });
+        }
+
+        let changes = history.recent_changes();
+        assert_eq!(changes.len(), CONFIG_HISTORY_LEN);
+        assert_eq!(changes.first().unwrap().new_value, 3);
+        assert_eq!(
+            changes.last().unwrap().new_value,
+            CONFIG_HISTORY_LEN as u64 + 2
+        );
+    }
+
+    #[test]
+    fn test_u64_config_value_roundtrip() {
+        let value = UpdatePoolConfigValue::U64(1_050_000_000);
+        assert_eq!(
+            UpdatePoolConfigValue::from_u64_bytes(&value.to_bytes()).unwrap(),
+            value
+        );
+        assert_eq!(value.to_u64(), 1_050_000_000);
+    }
+
+    #[test]
+    fn test_user_stats_record_swap() {
+        let mut stats = UserStats::default();
+        stats.record_swap(TradeDirection::AtoB, 1_000, 3, 10);
+        stats.record_swap(TradeDirection::BtoA, 500, 2, 12);
+        stats.record_swap(TradeDirection::AtoB, u64::MAX, 1, 15);
+
+        assert_eq!(stats.volume_token_a, u64::MAX);
+        assert_eq!(stats.volume_token_b, 500);
+        assert_eq!(stats.fees_paid_token_a, 4);
+        assert_eq!(stats.fees_paid_token_b, 2);
+        assert_eq!(stats.trade_count, 3);
+        assert_eq!(stats.last_trade_slot, 15);
+    }
+
+    #[test]
+    fn test_lp_airdrop_reward_share() {
+        let airdrop = LpAirdrop {
+            reward_amount: 1_000,
+            registered_pool_tokens: 3,
+            ..Default::default()
+        };
+        assert_eq!(airdrop.reward_share(1), Some(333));
+        assert_eq!(airdrop.reward_share(3), Some(1_000));
+
+        let empty = LpAirdrop {
+            reward_amount: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(empty.reward_share(0), Some(0));
+    }
+
+    #[test]
+    fn test_locked_lp_position_remaining_slots() {
+        let position = LockedLpPosition {
+            locked_slot: 10,
+            unlock_slot: 110,
+            ..Default::default()
+        };
+        assert_eq!(position.remaining_slots(10), 100);
+        assert_eq!(position.remaining_slots(109), 1);
+        assert_eq!(position.remaining_slots(110), 0);
+        assert_eq!(position.remaining_slots(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_curve_checksum() {
+        let curve = StableCurve::new(100, 6, 9).unwrap();
+        let mut data = curve.try_to_vec().unwrap();
+        data.splice(0..0, StableCurve::discriminator());
+        assert_eq!(data.len(), Curve::LEN);
+
+        // a curve without a checksum is corrupted once checked
+        assert_eq!(
+            Curve::verify_checksum(&data),
+            Err(SwapError::CurveCorrupted.into())
+        );
+
+        Curve::write_checksum(&mut data);
+        Curve::verify_checksum(&data).unwrap();
+        let sealed = StableCurve::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(sealed.checksum, Curve::checksum(&data));
+
+        data[DISCRIMINATOR_SIZE] ^= 1;
+        assert_eq!(
+            Curve::verify_checksum(&data),
+            Err(SwapError::CurveCorrupted.into())
+        );
+        assert_eq!(
+            Curve::verify_checksum(&data[..Curve::LEN - 1]),
+            Err(SwapError::CurveCorrupted.into())
+        );
+    }
 }
@@ -0,0 +1,63 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, log::sol_log_data, program::invoke_signed},
+    Event, InstructionData,
+};
+
+use crate::{error::SwapError, require_msg, state::EventMode, utils::seeds};
+
+/// Emit an event through the channel picked for the pool
+///
+/// The event is always returned by the handler as well, so `ReturnData` only skips the log.
+/// `Cpi` expects the event authority and the program among the remaining accounts.
+pub fn emit<E: Event>(
+    event_mode: EventMode,
+    remaining_accounts: &[AccountInfo],
+    event: &E,
+) -> Result<()> {
+    match event_mode {
+        EventMode::Log => sol_log_data(&[&event.data()]),
+        EventMode::ReturnData => {}
+        EventMode::Cpi => emit_cpi(remaining_accounts, event)?,
+    }
+    Ok(())
+}
+
+fn emit_cpi<E: Event>(remaining_accounts: &[AccountInfo], event: &E) -> Result<()> {
+    let (event_authority_key, bump) = seeds::pda::event_authority_pda();
+    let event_authority = find_event_account(remaining_accounts, &event_authority_key)?;
+    let program = find_event_account(remaining_accounts, &crate::ID)?;
+
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: vec![AccountMeta::new_readonly(event_authority_key, true)],
+        data: crate::instruction::EmitEvent {
+            event: event.data(),
+        }
+        .data(),
+    };
+    invoke_signed(
+        &ix,
+        &[event_authority.clone(), program.clone()],
+        &[&[seeds::EVENT_AUTHORITY, &[bump]]],
+    )?;
+    Ok(())
+}
+
+fn find_event_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    let account = remaining_accounts.iter().find(|account| account.key == key);
+    require_msg!(
+        account.is_some(),
+        SwapError::MissingEventAuthority,
+        &format!("MissingEventAuthority: account {} not found", key)
+    );
+    Ok(account.unwrap())
+}
+
+/// Whether the account is one of the accounts passed for `EventMode::Cpi`
+pub fn is_event_account(account: &AccountInfo) -> bool {
+    account.key == &crate::ID || account.key == &seeds::pda::event_authority_pda().0
+}
@@ -0,0 +1,235 @@
+//! Policy of the pool towards the Token-2022 extensions of its trading token mints
+//!
+//! Every mint extension is either supported, because the pool works with it as with a plain
+//! mint, supported with its behavior recorded in the `SwapPool` so clients can account for it,
+//! or rejected at `initialize_pool` because the pool could not honor it.
+
+use anchor_lang::{
+    err, error,
+    prelude::{msg, AccountInfo},
+    Result,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        default_account_state::DefaultAccountState, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{AccountState, Mint},
+};
+
+use crate::{error::SwapError, require_msg};
+
+/// Behavior of a trading token mint to record in the pool
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MintBehavior {
+    /// The mint accrues interest, the UI amounts of the vaults grow while their raw amounts,
+    /// which the curves price, stay unchanged
+    pub interest_bearing: bool,
+}
+
+/// Check the extensions of a trading token mint against the policy and return what the pool
+/// records about it. Mints of the SPL Token program have no extensions.
+pub fn validate_trading_token_mint(mint_info: &AccountInfo) -> Result<MintBehavior> {
+    let mint_data = mint_info.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    // extension types added after the spl-token-2022 version of the program cannot be parsed
+    let extension_types = mint.get_extension_types().map_err(|_| {
+        msg!(
+            "UnsupportedTokenExtension: mint {} has an extension unknown to the program",
+            mint_info.key
+        );
+        error!(SwapError::UnsupportedTokenExtension)
+    })?;
+
+    let mut behavior = MintBehavior::default();
+    for extension_type in extension_types {
+        match extension_type {
+            ExtensionType::InterestBearingConfig => behavior.interest_bearing = true,
+            ExtensionType::DefaultAccountState => {
+                let default_state = mint.get_extension::<DefaultAccountState>()?;
+                // the vaults are created with the default state, a frozen vault cannot trade
+                require_msg!(
+                    default_state.state != AccountState::Frozen as u8,
+                    SwapError::UnsupportedTokenExtension,
+                    &format!(
+                        "UnsupportedTokenExtension: mint {} has the {:?} extension set to frozen",
+                        mint_info.key,
+                        extension_type
+                    )
+                );
+            }
+            // the vaults could not send the tokens they hold
+            ExtensionType::NonTransferable
+            // the delegate could move the tokens out of the vaults
+            | ExtensionType::PermanentDelegate
+            // the vaults only handle public balances
+            | ExtensionType::ConfidentialTransferMint => {
+                msg!(
+                    "UnsupportedTokenExtension: mint {} has the {:?} extension",
+                    mint_info.key,
+                    extension_type
+                );
+                return err!(SwapError::UnsupportedTokenExtension);
+            }
+            // transfer fees are taken into account by every transfer, the mint close authority
+            // cannot close a mint with a supply
+            ExtensionType::TransferFeeConfig | ExtensionType::MintCloseAuthority => {}
+            // token account extensions, not found on mints
+            ExtensionType::Uninitialized
+            | ExtensionType::TransferFeeAmount
+            | ExtensionType::ConfidentialTransferAccount
+            | ExtensionType::ImmutableOwner
+            | ExtensionType::MemoTransfer
+            | ExtensionType::CpiGuard
+            | ExtensionType::NonTransferableAccount => {}
+        }
+    }
+    Ok(behavior)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{clock::Epoch, program_option::COption, program_pack::Pack},
+    };
+    use anchor_spl::token_2022::{
+        spl_token_2022,
+        spl_token_2022::extension::{
+            confidential_transfer::ConfidentialTransferMint,
+            interest_bearing_mint::InterestBearingConfig, non_transferable::NonTransferable,
+            permanent_delegate::PermanentDelegate, StateWithExtensionsMut,
+        },
+    };
+
+    use super::*;
+
+    /// Token-2022 mint with the given extensions, initialized by `init_extensions`
+    fn mint_data(
+        extension_types: &[ExtensionType],
+        init_extensions: impl FnOnce(&mut StateWithExtensionsMut<Mint>),
+    ) -> Vec<u8> {
+        let mut data = vec![0; ExtensionType::get_account_len::<Mint>(extension_types)];
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        init_extensions(&mut mint);
+        mint.base.decimals = 6;
+        mint.base.is_initialized = true;
+        mint.base.mint_authority = COption::Some(Pubkey::new_unique());
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        data
+    }
+
+    fn validate(mint_data: &mut [u8]) -> Result<MintBehavior> {
+        let key = Pubkey::new_unique();
+        let mut lamports = u64::MAX;
+        let token_program = spl_token_2022::id();
+        let mint_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            mint_data,
+            &token_program,
+            false,
+            Epoch::default(),
+        );
+        validate_trading_token_mint(&mint_info)
+    }
+
+    fn default_account_state_mint(state: AccountState) -> Vec<u8> {
+        mint_data(&[ExtensionType::DefaultAccountState], |mint| {
+            mint.init_extension::<DefaultAccountState>(true)
+                .unwrap()
+                .state = state as u8;
+            mint.base.freeze_authority = COption::Some(Pubkey::new_unique());
+        })
+    }
+
+    #[test]
+    fn spl_token_mint_is_supported() {
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                decimals: 6,
+                is_initialized: true,
+                ..Mint::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+        assert_eq!(validate(&mut data).unwrap(), MintBehavior::default());
+    }
+
+    #[test]
+    fn interest_bearing_mint_is_recorded() {
+        let mut data = mint_data(&[ExtensionType::InterestBearingConfig], |mint| {
+            mint.init_extension::<InterestBearingConfig>(true).unwrap();
+        });
+        assert_eq!(
+            validate(&mut data).unwrap(),
+            MintBehavior {
+                interest_bearing: true
+            }
+        );
+    }
+
+    #[test]
+    fn frozen_default_account_state_is_rejected() {
+        let mut data = default_account_state_mint(AccountState::Initialized);
+        validate(&mut data).unwrap();
+
+        let mut data = default_account_state_mint(AccountState::Frozen);
+        assert_eq!(
+            validate(&mut data).unwrap_err(),
+            SwapError::UnsupportedTokenExtension.into()
+        );
+    }
+
+    #[test]
+    fn non_transferable_and_permanent_delegate_mints_are_rejected() {
+        let mut data = mint_data(&[ExtensionType::NonTransferable], |mint| {
+            mint.init_extension::<NonTransferable>(true).unwrap();
+        });
+        assert_eq!(
+            validate(&mut data).unwrap_err(),
+            SwapError::UnsupportedTokenExtension.into()
+        );
+
+        let mut data = mint_data(&[ExtensionType::PermanentDelegate], |mint| {
+            mint.init_extension::<PermanentDelegate>(true)
+                .unwrap()
+                .delegate = Some(Pubkey::new_unique()).try_into().unwrap();
+        });
+        assert_eq!(
+            validate(&mut data).unwrap_err(),
+            SwapError::UnsupportedTokenExtension.into()
+        );
+    }
+
+    #[test]
+    fn confidential_transfer_mint_is_rejected() {
+        let mut data = mint_data(&[ExtensionType::ConfidentialTransferMint], |mint| {
+            mint.init_extension::<ConfidentialTransferMint>(true)
+                .unwrap();
+        });
+        assert_eq!(
+            validate(&mut data).unwrap_err(),
+            SwapError::UnsupportedTokenExtension.into()
+        );
+    }
+
+    #[test]
+    fn unknown_extension_is_rejected() {
+        let mut data = mint_data(&[ExtensionType::InterestBearingConfig], |mint| {
+            mint.init_extension::<InterestBearingConfig>(true).unwrap();
+        });
+        // overwrite the type of the first extension, right after the account type
+        let type_start = spl_token_2022::state::Account::LEN + 1;
+        data[type_start..type_start + 2].copy_from_slice(&1_000_u16.to_le_bytes());
+        assert_eq!(
+            validate(&mut data).unwrap_err(),
+            SwapError::UnsupportedTokenExtension.into()
+        );
+    }
+}
@@ -6,6 +6,8 @@ use anchor_lang::{
     AccountDeserialize, Discriminator, Key,
 };
 
+use crate::{error::SwapError, require_msg, state::Curve};
+
 pub fn deserialize<T: AccountDeserialize + Discriminator>(account: &AccountInfo<'_>) -> Result<T> {
     let data = account.clone().data.borrow().to_owned();
     if account.owner != &crate::ID {
@@ -37,6 +39,20 @@ pub fn deserialize<T: AccountDeserialize + Discriminator>(account: &AccountInfo<
     Ok(user)
 }
 
+/// Deserialize a curve account, failing with `CurveCorrupted` if it does not match its checksum
+///
+/// The checksum is only verified if it was written, see `SwapPool::curve_checksum_written`.
+pub fn deserialize_curve<T: AccountDeserialize + Discriminator>(
+    account: &AccountInfo<'_>,
+    checksum_written: bool,
+) -> Result<T> {
+    let curve = deserialize::<T>(account)?;
+    if checksum_written {
+        Curve::verify_checksum(&account.data.borrow())?;
+    }
+    Ok(curve)
+}
+
 pub fn zero_copy_deserialize<'info, T: bytemuck::AnyBitPattern + Discriminator>(
     account: &'info AccountInfo,
 ) -> Result<Ref<'info, T>> {
@@ -61,3 +77,16 @@ pub fn zero_copy_deserialize<'info, T: bytemuck::AnyBitPattern + Discriminator>(
 
     Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..])))
 }
+
+/// Fail with `DeadlineExceeded` once the cluster unix timestamp is past the `deadline`, if any
+pub fn require_before_deadline(deadline: Option<i64>) -> Result<()> {
+    if let Some(deadline) = deadline {
+        let now = Clock::get()?.unix_timestamp;
+        require_msg!(
+            now <= deadline,
+            SwapError::DeadlineExceeded,
+            &format!("DeadlineExceeded: now ({}) > deadline ({})", now, deadline)
+        );
+    }
+    Ok(())
+}
@@ -4,41 +4,88 @@ macro_rules! curve {
     ($swap_curve_info: expr, $pool: expr) => {
         match $pool.curve_type() {
             $crate::curve::base::CurveType::ConstantProduct => {
-                let calculator = $crate::utils::instructions::deserialize::<
+                let calculator = $crate::utils::instructions::deserialize_curve::<
                     $crate::state::ConstantProductCurve,
-                >(&$swap_curve_info)?;
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
                 SwapCurve {
                     calculator: std::sync::Arc::new(calculator),
                     curve_type: $pool.curve_type(),
                 }
             }
             $crate::curve::base::CurveType::ConstantPrice => {
-                let calculator = $crate::utils::instructions::deserialize::<
+                let calculator = $crate::utils::instructions::deserialize_curve::<
                     $crate::state::ConstantPriceCurve,
-                >(&$swap_curve_info)?;
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
                 SwapCurve {
                     calculator: std::sync::Arc::new(calculator),
                     curve_type: $pool.curve_type(),
                 }
             }
             $crate::curve::base::CurveType::Offset => {
-                let calculator = $crate::utils::instructions::deserialize::<
+                let calculator = $crate::utils::instructions::deserialize_curve::<
                     $crate::state::OffsetCurve,
-                >(&$swap_curve_info)?;
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
                 SwapCurve {
                     calculator: std::sync::Arc::new(calculator),
                     curve_type: $pool.curve_type(),
                 }
             }
             $crate::curve::base::CurveType::Stable => {
-                let calculator = $crate::utils::instructions::deserialize::<
+                let calculator = $crate::utils::instructions::deserialize_curve::<
                     $crate::state::StableCurve,
-                >(&$swap_curve_info)?;
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
                 SwapCurve {
                     calculator: std::sync::Arc::new(calculator),
                     curve_type: $pool.curve_type(),
                 }
             }
+            $crate::curve::base::CurveType::Pegged => {
+                let calculator = $crate::utils::instructions::deserialize_curve::<
+                    $crate::state::PeggedCurve,
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
+                SwapCurve {
+                    calculator: std::sync::Arc::new(calculator),
+                    curve_type: $pool.curve_type(),
+                }
+            }
+            $crate::curve::base::CurveType::ConstantProductWithBounds => {
+                let calculator = $crate::utils::instructions::deserialize_curve::<
+                    $crate::state::ConstantProductWithBoundsCurve,
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
+                SwapCurve {
+                    calculator: std::sync::Arc::new(calculator),
+                    curve_type: $pool.curve_type(),
+                }
+            }
+            $crate::curve::base::CurveType::Weighted => {
+                let calculator = $crate::utils::instructions::deserialize_curve::<
+                    $crate::state::WeightedCurve,
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
+                SwapCurve {
+                    calculator: std::sync::Arc::new(calculator),
+                    curve_type: $pool.curve_type(),
+                }
+            }
+            $crate::curve::base::CurveType::OraclePegged => {
+                // with the price cached by `refresh_price` if it is fresh, only `swap` reads the
+                // oracle account itself
+                let calculator = $crate::utils::instructions::deserialize_curve::<
+                    $crate::state::OraclePeggedCurve,
+                >(&$swap_curve_info, $pool.curve_checksum_written())?;
+                let current_slot = {
+                    use ::anchor_lang::prelude::Sysvar as _;
+                    ::anchor_lang::prelude::Clock::get()?.slot
+                };
+                SwapCurve {
+                    calculator: std::sync::Arc::new(
+                        $crate::curve::oracle_pegged::OraclePeggedCalculator::with_cached_price(
+                            calculator,
+                            current_slot,
+                        ),
+                    ),
+                    curve_type: $pool.curve_type(),
+                }
+            }
         }
     };
 }
@@ -53,6 +100,36 @@ macro_rules! require_msg {
     };
 }
 
+/// `msg!` unless the pool is set to quiet logs, for the informational logs of the hot paths
+#[macro_export]
+macro_rules! pool_msg {
+    ($pool: expr, $($arg:tt)*) => {
+        if !{
+            use $crate::state::SwapState as _;
+            $pool.quiet_logs()
+        } {
+            ::anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
+/// `require_msg!` which only logs the message if the pool is not set to quiet logs, the error
+/// code is always logged by anchor
+#[macro_export]
+macro_rules! require_pool_msg {
+    ($pool: expr, $invariant:expr, $error:expr $(,)?, $message: expr) => {
+        if !($invariant) {
+            if !{
+                use $crate::state::SwapState as _;
+                $pool.quiet_logs()
+            } {
+                ::anchor_lang::prelude::msg!($message);
+            }
+            return Err(anchor_lang::error!($error));
+        }
+    };
+}
+
 /// Print values passed to a function
 #[macro_export]
 macro_rules! dbg_msg {
@@ -96,6 +173,16 @@ macro_rules! emitted {
     };
 }
 
+/// Macro to emit an event through the pool's event mode and return it from the program
+#[macro_export]
+macro_rules! pool_emitted {
+    ($event_mode: expr, $remaining_accounts: expr, $event: expr) => {
+        let event = $event;
+        $crate::utils::events::emit($event_mode, $remaining_accounts, &event)?;
+        return Ok(event);
+    };
+}
+
 /// Macro to convert a value to u64, with useful error message
 #[macro_export]
 macro_rules! to_u64 {
@@ -60,6 +60,7 @@ macro_rules! create_try_math {
 create_try_math!(u8);
 create_try_math!(u64);
 create_try_math!(u128);
+create_try_math!(i128);
 create_try_math!(U256);
 
 pub trait TryMathRef
@@ -191,6 +192,19 @@ impl AbsDiff for U256 {
     }
 }
 
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Share of the pool token supply held, in basis points (rounded down)
+pub fn share_bps(pool_token_amount: u64, pool_token_supply: u64) -> Result<u64> {
+    if pool_token_supply == 0 {
+        return Ok(0);
+    }
+    let share = try_math!(u128::from(pool_token_amount)
+        .try_mul(u128::from(BPS_DENOMINATOR))?
+        .try_div(u128::from(pool_token_supply)))?;
+    to_u64!(share)
+}
+
 pub fn decimals_to_factor(source_decimals: u8, destination_decimals: u8) -> Result<u64> {
     Ok(10_u64.pow((destination_decimals.saturating_sub(source_decimals)) as u32))
 }
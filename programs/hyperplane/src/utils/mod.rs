@@ -1,3 +1,5 @@
+pub mod events;
+pub mod extension_policy;
 pub mod instructions;
 pub mod macros;
 pub mod math;
@@ -1,9 +1,56 @@
 use anchor_lang::{
-    prelude::{AccountInfo, CpiContext},
+    prelude::{AccountInfo, CpiContext, Pubkey},
+    solana_program::program_option::COption,
     Result,
 };
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        mint_close_authority::MintCloseAuthority, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+
+use crate::{error::SwapError, require_msg, utils::seeds};
 
-use crate::utils::seeds;
+/// Check the pool token mint is still controlled by the pool authority alone
+///
+/// None of these can change after pool initialization, the check guards the pool token supply
+/// that all share accounting is based on against any unexpected path.
+pub fn validate_mint_authorities(
+    pool_token_mint: &AccountInfo,
+    pool_authority: &Pubkey,
+) -> Result<()> {
+    let mint_data = pool_token_mint.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    require_msg!(
+        mint.base.mint_authority == COption::Some(*pool_authority),
+        SwapError::InvalidPoolTokenMintAuthority,
+        &format!(
+            "InvalidPoolTokenMintAuthority: mint_authority={:?}, pool_authority={}",
+            mint.base.mint_authority, pool_authority
+        )
+    );
+    require_msg!(
+        mint.base.freeze_authority.is_none(),
+        SwapError::InvalidFreezeAuthority,
+        &format!(
+            "InvalidFreezeAuthority: freeze_authority={:?}",
+            mint.base.freeze_authority
+        )
+    );
+    if let Ok(mint_close_authority) = mint.get_extension::<MintCloseAuthority>() {
+        let close_authority: Option<Pubkey> = mint_close_authority.close_authority.into();
+        require_msg!(
+            close_authority.is_none(),
+            SwapError::InvalidCloseAuthority,
+            &format!(
+                "InvalidCloseAuthority: close_authority={:?}",
+                close_authority
+            )
+        );
+    }
+    Ok(())
+}
 
 /// Issue an spl_token or spl_token_2022 `Mint` instruction.
 pub fn mint<'info>(
@@ -59,3 +106,70 @@ pub fn burn<'info>(
 
     Ok(())
 }
+
+/// Burn pool tokens escrowed in the vault of a withdrawal ticket, signed by the ticket
+#[allow(clippy::too_many_arguments)]
+pub fn burn_from_withdrawal_ticket<'info>(
+    token_program: AccountInfo<'info>,
+    pool_token_mint: AccountInfo<'info>,
+    pool_token_vault: AccountInfo<'info>,
+    withdrawal_ticket: AccountInfo<'info>,
+    pool: &Pubkey,
+    owner: &Pubkey,
+    withdrawal_ticket_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let inner_seeds = [
+        seeds::WITHDRAWAL_TICKET,
+        pool.as_ref(),
+        owner.as_ref(),
+        &[withdrawal_ticket_bump],
+    ];
+    let signer_seeds = &[&inner_seeds[..]];
+
+    anchor_spl::token_2022::burn(
+        CpiContext::new_with_signer(
+            token_program,
+            anchor_spl::token_2022::Burn {
+                mint: pool_token_mint,
+                from: pool_token_vault,
+                authority: withdrawal_ticket,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+/// Close the emptied pool token vault of a withdrawal ticket, signed by the ticket
+pub fn close_withdrawal_ticket_vault<'info>(
+    token_program: AccountInfo<'info>,
+    pool_token_vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    withdrawal_ticket: AccountInfo<'info>,
+    pool: &Pubkey,
+    owner: &Pubkey,
+    withdrawal_ticket_bump: u8,
+) -> Result<()> {
+    let inner_seeds = [
+        seeds::WITHDRAWAL_TICKET,
+        pool.as_ref(),
+        owner.as_ref(),
+        &[withdrawal_ticket_bump],
+    ];
+    let signer_seeds = &[&inner_seeds[..]];
+
+    anchor_spl::token_2022::close_account(CpiContext::new_with_signer(
+        token_program,
+        anchor_spl::token_2022::CloseAccount {
+            account: pool_token_vault,
+            destination,
+            authority: withdrawal_ticket,
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(())
+}
@@ -1,16 +1,86 @@
+use anchor_lang::constant;
+
+use crate::curve::calculator::AorB;
+
+#[constant]
 pub const POOL_AUTHORITY: &[u8] = b"pauthority";
+#[constant]
 pub const POOL_TOKEN_MINT: &[u8] = b"lp";
+#[constant]
 pub const SWAP_CURVE: &[u8] = b"curve";
+#[constant]
 pub const TOKEN_A_VAULT: &[u8] = b"pvault_a";
+#[constant]
 pub const TOKEN_B_VAULT: &[u8] = b"pvault_b";
+#[constant]
 pub const TOKEN_A_FEES_VAULT: &[u8] = b"fvault_a";
+#[constant]
 pub const TOKEN_B_FEES_VAULT: &[u8] = b"fvault_b";
+#[constant]
+pub const ALLOWED_SWAP_TAKER: &[u8] = b"swap_taker";
+#[constant]
+pub const ALLOWED_LIQUIDITY_PROVIDER: &[u8] = b"liquidity_provider";
+#[constant]
+pub const FEATURE_SET: &[u8] = b"feature_set";
+#[constant]
+pub const PROTOCOL_FEE_CONFIG: &[u8] = b"protocol_fee_config";
+#[constant]
+pub const FEE_TIER_REGISTRY: &[u8] = b"fee_tier_registry";
+#[constant]
+pub const PROTOCOL_FEE_VAULT: &[u8] = b"protocol_fee_vault";
+#[constant]
+pub const WITHDRAWAL_TICKET: &[u8] = b"withdrawal_ticket";
+#[constant]
+pub const WITHDRAWAL_TICKET_POOL_TOKEN_VAULT: &[u8] = b"withdrawal_ticket_lp";
+#[constant]
+pub const PROPOSED_CONFIG_CHANGE: &[u8] = b"proposed_config";
+#[constant]
+pub const CONFIG_HISTORY: &[u8] = b"config_history";
+#[constant]
+pub const USER_STATS: &[u8] = b"user_stats";
+#[constant]
+pub const LP_AIRDROP: &[u8] = b"lp_airdrop";
+#[constant]
+pub const LP_AIRDROP_REWARD_VAULT: &[u8] = b"lp_airdrop_reward";
+#[constant]
+pub const LP_AIRDROP_POOL_TOKEN_VAULT: &[u8] = b"lp_airdrop_lp";
+#[constant]
+pub const LP_AIRDROP_POSITION: &[u8] = b"lp_airdrop_position";
+#[constant]
+pub const LOCKED_LP_POSITION: &[u8] = b"locked_lp";
+#[constant]
+pub const LOCKED_LP_POOL_TOKEN_VAULT: &[u8] = b"locked_lp_vault";
+#[constant]
+pub const DEFAULT_HOST_FEES: &[u8] = b"default_host_fees";
+#[constant]
+pub const EVENT_AUTHORITY: &[u8] = b"__event_authority";
+#[constant]
+pub const TEST_POOL: &[u8] = b"test_pool";
+
+/// Seed prefix of the token A or B vault of a pool
+pub fn vault(a_or_b: AorB) -> &'static [u8] {
+    match a_or_b {
+        AorB::A => TOKEN_A_VAULT,
+        AorB::B => TOKEN_B_VAULT,
+    }
+}
+
+/// Seed prefix of the token A or B fees vault of a pool
+pub fn fees_vault(a_or_b: AorB) -> &'static [u8] {
+    match a_or_b {
+        AorB::A => TOKEN_A_FEES_VAULT,
+        AorB::B => TOKEN_B_FEES_VAULT,
+    }
+}
 
 pub mod pda {
-    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{bpf_loader_upgradeable, pubkey::PubkeyError},
+    };
 
     use super::*;
-    use crate::ID;
+    use crate::{state::SwapPool, ID};
 
     pub struct InitPoolPdas {
         pub curve: Pubkey,
@@ -90,6 +160,257 @@ pub mod pda {
         )
     }
 
+    pub fn allowed_swap_taker_pda(pool: &Pubkey, taker: &Pubkey) -> (Pubkey, u8) {
+        allowed_swap_taker_pda_program_id(&ID, pool, taker)
+    }
+
+    pub fn allowed_swap_taker_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        taker: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[ALLOWED_SWAP_TAKER, pool.as_ref(), taker.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn allowed_liquidity_provider_pda(pool: &Pubkey, provider: &Pubkey) -> (Pubkey, u8) {
+        allowed_liquidity_provider_pda_program_id(&ID, pool, provider)
+    }
+
+    pub fn allowed_liquidity_provider_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        provider: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[ALLOWED_LIQUIDITY_PROVIDER, pool.as_ref(), provider.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn feature_set_pda() -> (Pubkey, u8) {
+        feature_set_pda_program_id(&ID)
+    }
+
+    pub fn feature_set_pda_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEATURE_SET], program_id)
+    }
+
+    pub fn fee_tier_registry_pda() -> (Pubkey, u8) {
+        fee_tier_registry_pda_program_id(&ID)
+    }
+
+    pub fn fee_tier_registry_pda_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEE_TIER_REGISTRY], program_id)
+    }
+
+    pub fn protocol_fee_config_pda() -> (Pubkey, u8) {
+        protocol_fee_config_pda_program_id(&ID)
+    }
+
+    pub fn protocol_fee_config_pda_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PROTOCOL_FEE_CONFIG], program_id)
+    }
+
+    pub fn program_data_pda() -> (Pubkey, u8) {
+        program_data_pda_program_id(&ID)
+    }
+
+    pub fn program_data_pda_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+    }
+
+    pub fn protocol_fee_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        protocol_fee_vault_pda_program_id(&ID, mint)
+    }
+
+    pub fn protocol_fee_vault_pda_program_id(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PROTOCOL_FEE_VAULT, mint.as_ref()], program_id)
+    }
+
+    pub fn withdrawal_ticket_pda(pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        withdrawal_ticket_pda_program_id(&ID, pool, owner)
+    }
+
+    pub fn withdrawal_ticket_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        owner: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[WITHDRAWAL_TICKET, pool.as_ref(), owner.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn withdrawal_ticket_pool_token_vault_pda(withdrawal_ticket: &Pubkey) -> (Pubkey, u8) {
+        withdrawal_ticket_pool_token_vault_pda_program_id(&ID, withdrawal_ticket)
+    }
+
+    pub fn withdrawal_ticket_pool_token_vault_pda_program_id(
+        program_id: &Pubkey,
+        withdrawal_ticket: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                WITHDRAWAL_TICKET_POOL_TOKEN_VAULT,
+                withdrawal_ticket.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    pub fn proposed_config_change_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        proposed_config_change_pda_program_id(&ID, pool)
+    }
+
+    pub fn proposed_config_change_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PROPOSED_CONFIG_CHANGE, pool.as_ref()], program_id)
+    }
+
+    pub fn config_history_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        config_history_pda_program_id(&ID, pool)
+    }
+
+    pub fn config_history_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CONFIG_HISTORY, pool.as_ref()], program_id)
+    }
+
+    pub fn user_stats_pda(pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        user_stats_pda_program_id(&ID, pool, owner)
+    }
+
+    pub fn user_stats_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        owner: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[USER_STATS, pool.as_ref(), owner.as_ref()], program_id)
+    }
+
+    pub fn default_host_fees_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        default_host_fees_pda_program_id(&ID, pool)
+    }
+
+    pub fn default_host_fees_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[DEFAULT_HOST_FEES, pool.as_ref()], program_id)
+    }
+
+    pub fn event_authority_pda() -> (Pubkey, u8) {
+        event_authority_pda_program_id(&ID)
+    }
+
+    pub fn event_authority_pda_program_id(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[EVENT_AUTHORITY], program_id)
+    }
+
+    pub fn test_pool_pda(admin: &Pubkey, seed: &[u8; 32]) -> (Pubkey, u8) {
+        test_pool_pda_program_id(&ID, admin, seed)
+    }
+
+    pub fn test_pool_pda_program_id(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        seed: &[u8; 32],
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[TEST_POOL, admin.as_ref(), seed], program_id)
+    }
+
+    pub struct LpAirdropPdas {
+        pub airdrop: Pubkey,
+        pub reward_vault: Pubkey,
+        pub pool_token_vault: Pubkey,
+    }
+
+    pub fn lp_airdrop_pdas(
+        pool: &Pubkey,
+        sponsor: &Pubkey,
+        reward_mint: &Pubkey,
+        snapshot_slot: u64,
+    ) -> LpAirdropPdas {
+        lp_airdrop_pdas_program_id(&ID, pool, sponsor, reward_mint, snapshot_slot)
+    }
+
+    pub fn lp_airdrop_pdas_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        sponsor: &Pubkey,
+        reward_mint: &Pubkey,
+        snapshot_slot: u64,
+    ) -> LpAirdropPdas {
+        let (airdrop, _airdrop_bump) = Pubkey::find_program_address(
+            &[
+                LP_AIRDROP,
+                pool.as_ref(),
+                sponsor.as_ref(),
+                reward_mint.as_ref(),
+                &snapshot_slot.to_le_bytes(),
+            ],
+            program_id,
+        );
+        let (reward_vault, _reward_vault_bump) =
+            Pubkey::find_program_address(&[LP_AIRDROP_REWARD_VAULT, airdrop.as_ref()], program_id);
+        let (pool_token_vault, _pool_token_vault_bump) = Pubkey::find_program_address(
+            &[LP_AIRDROP_POOL_TOKEN_VAULT, airdrop.as_ref()],
+            program_id,
+        );
+
+        LpAirdropPdas {
+            airdrop,
+            reward_vault,
+            pool_token_vault,
+        }
+    }
+
+    pub fn lp_airdrop_position_pda(airdrop: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        lp_airdrop_position_pda_program_id(&ID, airdrop, owner)
+    }
+
+    pub fn lp_airdrop_position_pda_program_id(
+        program_id: &Pubkey,
+        airdrop: &Pubkey,
+        owner: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[LP_AIRDROP_POSITION, airdrop.as_ref(), owner.as_ref()],
+            program_id,
+        )
+    }
+
+    pub struct LockedLpPositionPdas {
+        pub position: Pubkey,
+        pub pool_token_vault: Pubkey,
+    }
+
+    pub fn locked_lp_position_pdas(pool: &Pubkey, owner: &Pubkey) -> LockedLpPositionPdas {
+        locked_lp_position_pdas_program_id(&ID, pool, owner)
+    }
+
+    pub fn locked_lp_position_pdas_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        owner: &Pubkey,
+    ) -> LockedLpPositionPdas {
+        let (position, _position_bump) = Pubkey::find_program_address(
+            &[LOCKED_LP_POSITION, pool.as_ref(), owner.as_ref()],
+            program_id,
+        );
+        let (pool_token_vault, _pool_token_vault_bump) = Pubkey::find_program_address(
+            &[LOCKED_LP_POOL_TOKEN_VAULT, position.as_ref()],
+            program_id,
+        );
+
+        LockedLpPositionPdas {
+            position,
+            pool_token_vault,
+        }
+    }
+
     pub fn init_pool_pdas(
         pool: &Pubkey,
         token_a_mint: &Pubkey,
@@ -132,4 +453,81 @@ pub mod pda {
             token_b_fees_vault,
         }
     }
+
+    /// Re-derive the pool PDAs from the bump seeds stored on the pool
+    ///
+    /// Uses `create_program_address` so no bump search is needed, which is much cheaper on-chain
+    /// than `find_program_address`
+    pub fn pool_pdas_with_bumps(
+        program_id: &Pubkey,
+        pool_key: &Pubkey,
+        pool: &SwapPool,
+    ) -> Result<InitPoolPdas, PubkeyError> {
+        let bump = |bump_seed: u64| [u8::try_from(bump_seed).unwrap()];
+        let token_a_mint = pool.token_a_mint;
+        let token_b_mint = pool.token_b_mint;
+
+        Ok(InitPoolPdas {
+            curve: Pubkey::create_program_address(
+                &[
+                    SWAP_CURVE,
+                    pool_key.as_ref(),
+                    &bump(pool.swap_curve_bump_seed),
+                ],
+                program_id,
+            )?,
+            authority: Pubkey::create_program_address(
+                &[
+                    POOL_AUTHORITY,
+                    pool_key.as_ref(),
+                    &bump(pool.pool_authority_bump_seed),
+                ],
+                program_id,
+            )?,
+            token_a_vault: Pubkey::create_program_address(
+                &[
+                    TOKEN_A_VAULT,
+                    pool_key.as_ref(),
+                    token_a_mint.as_ref(),
+                    &bump(pool.token_a_vault_bump_seed),
+                ],
+                program_id,
+            )?,
+            token_b_vault: Pubkey::create_program_address(
+                &[
+                    TOKEN_B_VAULT,
+                    pool_key.as_ref(),
+                    token_b_mint.as_ref(),
+                    &bump(pool.token_b_vault_bump_seed),
+                ],
+                program_id,
+            )?,
+            pool_token_mint: Pubkey::create_program_address(
+                &[
+                    POOL_TOKEN_MINT,
+                    pool_key.as_ref(),
+                    &bump(pool.pool_token_mint_bump_seed),
+                ],
+                program_id,
+            )?,
+            token_a_fees_vault: Pubkey::create_program_address(
+                &[
+                    TOKEN_A_FEES_VAULT,
+                    pool_key.as_ref(),
+                    token_a_mint.as_ref(),
+                    &bump(pool.token_a_fees_vault_bump_seed),
+                ],
+                program_id,
+            )?,
+            token_b_fees_vault: Pubkey::create_program_address(
+                &[
+                    TOKEN_B_FEES_VAULT,
+                    pool_key.as_ref(),
+                    token_b_mint.as_ref(),
+                    &bump(pool.token_b_fees_vault_bump_seed),
+                ],
+                program_id,
+            )?,
+        })
+    }
 }
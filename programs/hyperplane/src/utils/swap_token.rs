@@ -1,6 +1,19 @@
-use anchor_lang::prelude::{AccountInfo, CpiContext, Result};
+use anchor_lang::{
+    accounts::interface_account::InterfaceAccount,
+    prelude::{msg, AccountInfo, Clock, CpiContext, Key, Result, SolanaSysvar, ToAccountInfo},
+};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{
+            transfer_fee::{TransferFee, TransferFeeConfig, MAX_FEE_BASIS_POINTS},
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+        instruction::AuthorityType,
+    },
+    token_interface::TokenAccount,
+};
 
-use crate::utils::seeds;
+use crate::{error::SwapError, require_msg, utils::seeds};
 
 /// Issue an spl_token or spl_token_2022 `TransferChecked` instruction.
 #[allow(clippy::too_many_arguments)]
@@ -67,3 +80,212 @@ pub fn transfer_from_user<'info>(
 
     Ok(())
 }
+
+/// Issue an spl_token or spl_token_2022 `TransferChecked` instruction out of a shared protocol
+/// fee vault, signed by the protocol fee config
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from_protocol_fee_vault<'info>(
+    token_program: AccountInfo<'info>,
+    source: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    protocol_fee_config: AccountInfo<'info>,
+    protocol_fee_config_bump: u8,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let inner_seeds = [seeds::PROTOCOL_FEE_CONFIG, &[protocol_fee_config_bump]];
+    let signer_seeds = &[&inner_seeds[..]];
+
+    anchor_spl::token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program,
+            anchor_spl::token_2022::TransferChecked {
+                from: source,
+                mint,
+                to: destination,
+                authority: protocol_fee_config,
+            },
+            signer_seeds,
+        ),
+        amount,
+        decimals,
+    )?;
+
+    Ok(())
+}
+
+/// Issue an spl_token or spl_token_2022 `CloseAccount` instruction for an empty pool vault.
+pub fn close_vault<'info>(
+    token_program: AccountInfo<'info>,
+    pool: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    pool_authority_bump: u8,
+) -> Result<()> {
+    let inner_seeds = [
+        seeds::POOL_AUTHORITY,
+        pool.key.as_ref(),
+        &[pool_authority_bump],
+    ];
+    let signer_seeds = &[&inner_seeds[..]];
+
+    anchor_spl::token_2022::close_account(CpiContext::new_with_signer(
+        token_program,
+        anchor_spl::token_2022::CloseAccount {
+            account: vault,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(())
+}
+
+/// Clear the delegate and close authority of a pool vault, returns whether any was set
+pub fn revoke_vault_delegates<'info>(
+    token_program: AccountInfo<'info>,
+    pool: AccountInfo<'info>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    pool_authority_bump: u8,
+) -> Result<bool> {
+    let inner_seeds = [
+        seeds::POOL_AUTHORITY,
+        pool.key.as_ref(),
+        &[pool_authority_bump],
+    ];
+    let signer_seeds = &[&inner_seeds[..]];
+
+    let has_delegate = vault.delegate.is_some();
+    let has_close_authority = vault.close_authority.is_some();
+    if has_delegate {
+        msg!(
+            "Revoking delegate of vault {}: delegate={:?}, delegated_amount={}",
+            vault.key(),
+            vault.delegate,
+            vault.delegated_amount
+        );
+        anchor_spl::token_2022::revoke(CpiContext::new_with_signer(
+            token_program.clone(),
+            anchor_spl::token_2022::Revoke {
+                source: vault.to_account_info(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ))?;
+    }
+    if has_close_authority {
+        msg!(
+            "Removing close authority of vault {}: close_authority={:?}",
+            vault.key(),
+            vault.close_authority
+        );
+        anchor_spl::token_2022::set_authority(
+            CpiContext::new_with_signer(
+                token_program,
+                anchor_spl::token_2022::SetAuthority {
+                    current_authority: authority,
+                    account_or_mint: vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::CloseAccount,
+            None,
+        )?;
+    }
+
+    Ok(has_delegate || has_close_authority)
+}
+
+/// Check a pool vault has neither a delegate nor a close authority
+pub fn require_no_vault_delegates(vault: &InterfaceAccount<TokenAccount>) -> Result<()> {
+    require_msg!(
+        vault.delegate.is_none() && vault.close_authority.is_none(),
+        SwapError::VaultDelegateSet,
+        &format!(
+            "VaultDelegateSet: vault {} has delegate={:?}, close_authority={:?}",
+            vault.key(),
+            vault.delegate,
+            vault.close_authority
+        )
+    );
+    Ok(())
+}
+
+/// Add token mint transfer fees for actual amount sent pre-transfer fees
+pub fn add_inverse_transfer_fee(mint_acc_info: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
+    let mint_data = mint_acc_info.data.borrow();
+    let mint = StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
+        &mint_data,
+    )?;
+    let amount = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        let transfer_fee = transfer_fee_config.get_epoch_fee(Clock::get()?.epoch);
+        let amount_add_fee = gross_up_transfer_fee(transfer_fee, post_fee_amount)?;
+        msg!(
+            "Add token transfer fee: fee={}, amount={}, amount_add_fee={}",
+            amount_add_fee - post_fee_amount,
+            post_fee_amount,
+            amount_add_fee
+        );
+        amount_add_fee
+    } else {
+        post_fee_amount
+    };
+    Ok(amount)
+}
+
+/// Smallest transfer amount which nets exactly `post_fee_amount` after the transfer fee
+///
+/// Fails with `TransferFeeGrossUpImpossible` instead of under-collecting when no such amount fits in a u64
+pub fn gross_up_transfer_fee(transfer_fee: &TransferFee, post_fee_amount: u64) -> Result<u64> {
+    let pre_fee_amount = if post_fee_amount > 0
+        && u16::from(transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS
+    {
+        // spl-token-2022 returns 0 here, everything is taken until the fee is capped
+        post_fee_amount.checked_add(u64::from(transfer_fee.maximum_fee))
+    } else {
+        transfer_fee.calculate_pre_fee_amount(post_fee_amount)
+    };
+    let net_amount =
+        pre_fee_amount.and_then(|amount| transfer_fee.calculate_post_fee_amount(amount));
+    require_msg!(
+        net_amount == Some(post_fee_amount),
+        SwapError::TransferFeeGrossUpImpossible,
+        &format!(
+            "TransferFeeGrossUpImpossible: post_fee_amount={}, pre_fee_amount={:?}, net_amount={:?}, transfer_fee_bps={}, maximum_fee={}",
+            post_fee_amount,
+            pre_fee_amount,
+            net_amount,
+            u16::from(transfer_fee.transfer_fee_basis_points),
+            u64::from(transfer_fee.maximum_fee),
+        )
+    );
+    Ok(pre_fee_amount.unwrap())
+}
+
+/// Reload a pool vault after the transfers of an instruction and check it holds exactly the
+/// amount the instruction computed its result on
+///
+/// Catches any discrepancy between the amounts transferred and the amounts the vault actually
+/// received or sent, such as an unaccounted for transfer fee, instead of leaving the pool with
+/// stale reserves.
+pub fn reload_and_require_vault_amount(
+    vault: &mut InterfaceAccount<TokenAccount>,
+    expected_amount: u64,
+) -> Result<()> {
+    vault.reload()?;
+    require_msg!(
+        vault.amount == expected_amount,
+        SwapError::VaultBalanceMismatch,
+        &format!(
+            "VaultBalanceMismatch: vault {} amount={} != expected_amount={}",
+            vault.key(),
+            vault.amount,
+            expected_amount
+        )
+    );
+    Ok(())
+}
@@ -1,14 +1,33 @@
 #![allow(clippy::too_many_arguments)]
 
+use anchor_lang::InstructionData;
 use hyperplane::{
     curve::calculator::{AorB, TradeDirection},
-    ix::{Deposit, Initialize, Swap, UpdatePoolConfig, Withdraw, WithdrawFees},
-    state::SwapPool,
+    ix,
+    ix::{
+        AssertPoolPrice, Deposit, DepositBothExactIn, DepositExactAmounts, DepositSingle,
+        FlashBorrow, Initialize, InitializeWithFeeTier, ProposePoolConfig, RebalanceAcrossPools,
+        RequestWithdraw, SetConversionProgram, SetFeeTier, Swap, SwapBatch, SwapRouteTwoHop,
+        UpdateFeatureSet, UpdatePoolConfig, Withdraw, WithdrawExactAmountsOut, WithdrawFees,
+        WithdrawSingle,
+    },
+    state::{ProposedConfigChange, SwapPool, UpdatePoolConfigMode},
+    utils::{seeds, seeds::pda::LpAirdropPdas},
+};
+use solana_program_test::{BanksClientError, BanksTransactionResultWithMetadata};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
 };
-use solana_program_test::BanksClientError;
-use solana_sdk::{instruction::Instruction, system_instruction};
 
-use super::types::{PoolUserAccounts, SwapPoolAccounts, TestContext};
+use super::{
+    state,
+    types::{PoolUserAccounts, SwapPoolAccounts, TestContext},
+    utils,
+};
 use crate::send_tx;
 
 pub async fn initialize_pool(
@@ -34,6 +53,46 @@ pub async fn initialize_pool(
     )
 }
 
+/// Initializes a test pool for the admin, mints and admin accounts of `pool`, the pool keypair
+/// and PDAs of `pool` are not used
+#[cfg(feature = "dev-mode")]
+pub async fn initialize_test_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    seed: [u8; 32],
+    initialize: Initialize,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_test_pool(pool, seed, initialize)],
+        pool.admin.admin.as_ref(),
+        pool.admin.pool_token_ata.as_ref()
+    )
+}
+
+pub async fn initialize_pool_with_fee_tier(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    initialize: InitializeWithFeeTier,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [
+            system_instruction::create_account(
+                &ctx.context.payer.pubkey(),
+                &pool.pubkey(),
+                ctx.rent.minimum_balance(SwapPool::LEN),
+                SwapPool::LEN as u64,
+                &hyperplane::id(),
+            ),
+            instructions::initialize_pool_with_fee_tier(pool, initialize)
+        ],
+        pool.pool.as_ref(),
+        pool.admin.admin.as_ref(),
+        pool.admin.pool_token_ata.as_ref()
+    )
+}
+
 pub async fn deposit(
     ctx: &mut TestContext,
     pool: &SwapPoolAccounts,
@@ -42,7 +101,166 @@ pub async fn deposit(
 ) -> Result<(), BanksClientError> {
     send_tx!(
         ctx,
-        [instructions::deposit(pool, user, deposit)],
+        [instructions::deposit(pool, user, None, None, deposit)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_exact_amounts(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    deposit_exact_amounts: DepositExactAmounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::deposit_exact_amounts(
+            pool,
+            user,
+            deposit_exact_amounts
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_both_exact_in(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    deposit_both_exact_in: DepositBothExactIn,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::deposit_both_exact_in(
+            pool,
+            user,
+            deposit_both_exact_in
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_single(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    source: AorB,
+    deposit_single: DepositSingle,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::deposit_single(
+            pool,
+            user,
+            source,
+            None,
+            deposit_single
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_permissioned(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    deposit: Deposit,
+) -> Result<(), BanksClientError> {
+    let (allowed_liquidity_provider, _bump) =
+        seeds::pda::allowed_liquidity_provider_pda(&pool.pubkey(), &user.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::deposit(
+            pool,
+            user,
+            None,
+            Some(&allowed_liquidity_provider),
+            deposit
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_single_permissioned(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    source: AorB,
+    deposit_single: DepositSingle,
+) -> Result<(), BanksClientError> {
+    let (allowed_liquidity_provider, _bump) =
+        seeds::pda::allowed_liquidity_provider_pda(&pool.pubkey(), &user.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::deposit_single(
+            pool,
+            user,
+            source,
+            Some(&allowed_liquidity_provider),
+            deposit_single
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn withdraw_single(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    destination: AorB,
+    withdraw_single: WithdrawSingle,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::withdraw_single(
+            pool,
+            user,
+            destination,
+            withdraw_single
+        )],
+        user.user.as_ref()
+    )
+}
+
+/// Swaps token A for token B in `first_pool`, then token A for token B in `second_pool`, whose
+/// token A is token B of `first_pool`.
+pub async fn swap_route_two_hop(
+    ctx: &mut TestContext,
+    first_pool: &SwapPoolAccounts,
+    second_pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    destination_user_ata: &Pubkey,
+    swap_route_two_hop: SwapRouteTwoHop,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::swap_route_two_hop(
+            first_pool,
+            second_pool,
+            user,
+            destination_user_ata,
+            swap_route_two_hop
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn deposit_with_referrer(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    referrer: &Pubkey,
+    deposit: Deposit,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::deposit(
+            pool,
+            user,
+            Some(referrer),
+            None,
+            deposit
+        )],
         user.user.as_ref()
     )
 }
@@ -61,6 +279,78 @@ pub async fn swap_with_host_fees(
             pool,
             user,
             host_fees,
+            None,
+            trade_direction,
+            swap
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn swap_with_destination_host_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    host_fees: &PoolUserAccounts,
+    trade_direction: TradeDirection,
+    swap: Swap,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::swap_with_accounts(
+            pool,
+            user,
+            None,
+            Some(host_fees),
+            None,
+            None,
+            trade_direction,
+            swap
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn swap_permissioned(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    trade_direction: TradeDirection,
+    swap: Swap,
+) -> Result<(), BanksClientError> {
+    let (allowed_swap_taker, _bump) =
+        seeds::pda::allowed_swap_taker_pda(&pool.pubkey(), &user.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::swap(
+            pool,
+            user,
+            None,
+            Some(&allowed_swap_taker),
+            trade_direction,
+            swap
+        )],
+        user.user.as_ref()
+    )
+}
+
+/// Swaps against an oracle pegged pool, passing the price account of its curve
+pub async fn swap_with_oracle(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    oracle: &Pubkey,
+    trade_direction: TradeDirection,
+    swap: Swap,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::swap_with_oracle(
+            pool,
+            user,
+            None,
+            None,
+            Some(oracle),
             trade_direction,
             swap
         )],
@@ -68,6 +358,45 @@ pub async fn swap_with_host_fees(
     )
 }
 
+pub async fn swap_batch(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    trade_direction: TradeDirection,
+    swap_batch: SwapBatch,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::swap_batch(
+            pool,
+            user,
+            trade_direction,
+            swap_batch
+        )],
+        user.user.as_ref()
+    )
+}
+
+/// Swaps after asserting the pool price in the same transaction, as the first instruction of a
+/// bundle would
+pub async fn swap_with_price_assertion(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    assert_pool_price: AssertPoolPrice,
+    trade_direction: TradeDirection,
+    swap: Swap,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [
+            instructions::assert_pool_price(pool, assert_pool_price),
+            instructions::swap(pool, user, None, None, trade_direction, swap)
+        ],
+        user.user.as_ref()
+    )
+}
+
 pub async fn swap(
     ctx: &mut TestContext,
     pool: &SwapPoolAccounts,
@@ -78,6 +407,24 @@ pub async fn swap(
     swap_with_host_fees(ctx, pool, user, None, trade_direction, swap).await
 }
 
+pub async fn swap_with_user_stats(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    user_stats: &Pubkey,
+    trade_direction: TradeDirection,
+    swap: Swap,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [ix::with_user_stats(
+            instructions::swap(pool, user, None, None, trade_direction, swap),
+            user_stats
+        )],
+        user.user.as_ref()
+    )
+}
+
 pub async fn withdraw(
     ctx: &mut TestContext,
     pool: &SwapPoolAccounts,
@@ -91,6 +438,23 @@ pub async fn withdraw(
     )
 }
 
+pub async fn withdraw_exact_amounts_out(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    withdraw_exact_amounts_out: WithdrawExactAmountsOut,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::withdraw_exact_amounts_out(
+            pool,
+            user,
+            withdraw_exact_amounts_out
+        )],
+        user.user.as_ref()
+    )
+}
+
 pub async fn withdraw_fees(
     ctx: &mut TestContext,
     pool: &SwapPoolAccounts,
@@ -116,11 +480,620 @@ pub async fn update_pool_config(
     )
 }
 
-pub(crate) mod instructions {
-    use hyperplane::{ix, ix::Deposit};
-    use solana_sdk::signer::Signer;
-
-    use super::*;
+pub async fn close_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::close_pool(pool)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_protocol_fee_config(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+    authority: &Pubkey,
+) -> Result<(), BanksClientError> {
+    utils::set_upgrade_authority(ctx, &admin.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::initialize_protocol_fee_config(
+            &admin.pubkey(),
+            authority
+        )],
+        admin
+    )
+}
+
+pub async fn withdraw_protocol_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    authority: &PoolUserAccounts,
+    a_or_b: AorB,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::withdraw_protocol_fees(
+            pool, authority, a_or_b
+        )],
+        authority.user.as_ref()
+    )
+}
+
+pub async fn initialize_protocol_fee_vault(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    authority: &PoolUserAccounts,
+    a_or_b: AorB,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_protocol_fee_vault(
+            pool, authority, a_or_b
+        )],
+        authority.user.as_ref()
+    )
+}
+
+pub async fn sweep_protocol_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    a_or_b: AorB,
+) -> Result<(), BanksClientError> {
+    send_tx!(ctx, [instructions::sweep_protocol_fees(pool, a_or_b)],)
+}
+
+pub async fn withdraw_shared_protocol_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    authority: &PoolUserAccounts,
+    a_or_b: AorB,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::withdraw_shared_protocol_fees(
+            pool, authority, a_or_b
+        )],
+        authority.user.as_ref()
+    )
+}
+
+pub async fn migrate_mint(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    a_or_b: AorB,
+    new_mint: &Pubkey,
+    new_token_program: &Pubkey,
+    conversion_data: Vec<u8>,
+    reserve: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::migrate_mint(
+            pool,
+            a_or_b,
+            new_mint,
+            new_token_program,
+            conversion_data,
+            reserve
+        )],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn propose_admin(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    pending_admin: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::propose_admin(pool, pending_admin)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn set_guardian(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    guardian: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::set_guardian(pool, guardian)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn guardian_pause_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    guardian: &Keypair,
+    update_pool_config: UpdatePoolConfig,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::guardian_pause_pool(
+            pool,
+            &guardian.pubkey(),
+            update_pool_config
+        )],
+        guardian
+    )
+}
+
+pub async fn accept_admin(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    pending_admin: &Keypair,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::accept_admin(pool, &pending_admin.pubkey())],
+        pending_admin
+    )
+}
+
+pub async fn add_swap_taker(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    taker: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::add_swap_taker(pool, taker)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn remove_swap_taker(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    taker: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::remove_swap_taker(pool, taker)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn add_liquidity_provider(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    provider: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::add_liquidity_provider(pool, provider)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn remove_liquidity_provider(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    provider: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::remove_liquidity_provider(pool, provider)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_feature_set(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+) -> Result<(), BanksClientError> {
+    utils::set_upgrade_authority(ctx, &admin.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::initialize_feature_set(&admin.pubkey())],
+        admin
+    )
+}
+
+pub async fn update_feature_set(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+    update_feature_set: UpdateFeatureSet,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::update_feature_set(
+            &admin.pubkey(),
+            update_feature_set
+        )],
+        admin
+    )
+}
+
+pub async fn set_conversion_program(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+    set_conversion_program: SetConversionProgram,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::set_conversion_program(
+            &admin.pubkey(),
+            set_conversion_program
+        )],
+        admin
+    )
+}
+
+pub async fn initialize_fee_tier_registry(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+) -> Result<(), BanksClientError> {
+    utils::set_upgrade_authority(ctx, &admin.pubkey());
+    send_tx!(
+        ctx,
+        [instructions::initialize_fee_tier_registry(&admin.pubkey())],
+        admin
+    )
+}
+
+pub async fn set_fee_tier(
+    ctx: &mut TestContext,
+    admin: &Keypair,
+    set_fee_tier: SetFeeTier,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::set_fee_tier(&admin.pubkey(), set_fee_tier)],
+        admin
+    )
+}
+
+pub async fn rebalance_across_pools(
+    ctx: &mut TestContext,
+    source_pool: &SwapPoolAccounts,
+    destination_pool: &SwapPoolAccounts,
+    rebalance_across_pools: RebalanceAcrossPools,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::rebalance_across_pools(
+            source_pool,
+            destination_pool,
+            rebalance_across_pools
+        )],
+        source_pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn request_withdraw(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    request_withdraw: RequestWithdraw,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::request_withdraw(pool, user, request_withdraw)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn claim_withdraw(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    signer: &Keypair,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::claim_withdraw(pool, user, &signer.pubkey())],
+        signer
+    )
+}
+
+pub async fn propose_pool_config(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    propose_pool_config: ProposePoolConfig,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::propose_pool_config(pool, propose_pool_config)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn execute_pool_config(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    let (proposed_config_change, _bump) = seeds::pda::proposed_config_change_pda(&pool.pubkey());
+    let mode = state::try_get::<ProposedConfigChange>(ctx, proposed_config_change)
+        .await
+        .ok()
+        .and_then(|proposal| UpdatePoolConfigMode::try_from(proposal.mode).ok());
+    send_tx!(
+        ctx,
+        [instructions::execute_pool_config(pool, mode)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_config_history(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_config_history(pool)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_user_stats(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_user_stats(pool, user)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn revoke_vault_delegates(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    signer: &Keypair,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::revoke_vault_delegates(pool, &signer.pubkey())],
+        signer
+    )
+}
+
+/// Airdrops token B of the pool, funded from the token B account of the sponsor
+pub async fn create_lp_airdrop(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    sponsor: &PoolUserAccounts,
+    airdrop: &LpAirdropPdas,
+    reward_amount: u64,
+    snapshot_slot: u64,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::create_lp_airdrop(
+            pool,
+            sponsor,
+            airdrop,
+            reward_amount,
+            snapshot_slot
+        )],
+        sponsor.user.as_ref()
+    )
+}
+
+pub async fn register_lp_airdrop(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    airdrop: &LpAirdropPdas,
+    pool_token_amount: u64,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::register_lp_airdrop(
+            pool,
+            user,
+            airdrop,
+            pool_token_amount
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn claim_lp_airdrop(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    airdrop: &LpAirdropPdas,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::claim_lp_airdrop(pool, user, airdrop)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn close_lp_airdrop(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    sponsor: &PoolUserAccounts,
+    airdrop: &LpAirdropPdas,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::close_lp_airdrop(pool, sponsor, airdrop)],
+        sponsor.user.as_ref()
+    )
+}
+
+pub async fn lock_liquidity(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    pool_token_amount: u64,
+    lock_duration_slots: u64,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::lock_liquidity(
+            pool,
+            user,
+            pool_token_amount,
+            lock_duration_slots
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn unlock_liquidity(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::unlock_liquidity(pool, user)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn donate_to_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::donate_to_pool(
+            pool,
+            user,
+            token_a_amount,
+            token_b_amount
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn skim_lamports(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    treasury: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::skim_lamports(pool, treasury)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_default_host_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    recipient: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_default_host_fees(pool, recipient)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn update_default_host_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    recipient: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::update_default_host_fees(pool, recipient)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn claim_default_host_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    recipient: &PoolUserAccounts,
+    a_or_b: AorB,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::claim_default_host_fees(
+            pool, recipient, a_or_b
+        )],
+        recipient.user.as_ref()
+    )
+}
+
+pub async fn extend_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    let payer = ctx.context.payer.pubkey();
+    send_tx!(ctx, [instructions::extend_pool(&payer, pool)],)
+}
+
+pub async fn write_curve_checksum(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::write_curve_checksum(pool)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn refresh_price(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    oracle: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(ctx, [instructions::refresh_price(pool, oracle)],)
+}
+
+/// Borrow with `flash_borrow`, process `between` and repay with `flash_repay` in one transaction
+pub async fn flash_loan(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    a_or_b: AorB,
+    flash_borrow: FlashBorrow,
+    between: Vec<Instruction>,
+) -> Result<(), BanksClientError> {
+    let mut ixs = vec![instructions::flash_borrow(pool, user, a_or_b, flash_borrow)];
+    ixs.extend(between);
+    ixs.push(instructions::flash_repay(pool, user, a_or_b));
+    send_tx!(ctx, ixs, user.user.as_ref())
+}
+
+/// Send an instruction signed by `signer` and return its result with the logs and return data
+pub async fn process_with_metadata(
+    ctx: &mut TestContext,
+    instruction: Instruction,
+    signer: &Keypair,
+) -> BanksTransactionResultWithMetadata {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&ctx.context.payer.pubkey()),
+        &[&ctx.context.payer, signer],
+        ctx.context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap(),
+    );
+    ctx.context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap()
+}
+
+pub(crate) mod instructions {
+    use hyperplane::{ix, ix::Deposit};
+
+    use super::*;
+    use crate::common::mint_migration;
 
     pub fn initialize_pool(pool: &SwapPoolAccounts, initialize: Initialize) -> Instruction {
         ix::initialize_pool(
@@ -132,198 +1105,1393 @@ pub(crate) mod instructions {
             &pool.token_b_mint,
             &pool.token_a_vault,
             &pool.token_b_vault,
-            &pool.authority,
-            &pool.pool_token_mint,
+            &pool.authority,
+            &pool.pool_token_mint,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &pool.admin.token_a_ata,
+            &pool.admin.token_b_ata,
+            &pool.admin.pool_token_ata.pubkey(),
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            initialize,
+        )
+        .unwrap()
+    }
+
+    pub fn initialize_pool_with_fee_tier(
+        pool: &SwapPoolAccounts,
+        initialize: InitializeWithFeeTier,
+    ) -> Instruction {
+        ix::initialize_pool_with_fee_tier(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.admin.token_a_ata,
+            &pool.admin.token_b_ata,
+            &pool.admin.pool_token_ata.pubkey(),
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            initialize,
+        )
+        .unwrap()
+    }
+
+    #[cfg(feature = "dev-mode")]
+    pub fn initialize_test_pool(
+        pool: &SwapPoolAccounts,
+        seed: [u8; 32],
+        initialize: Initialize,
+    ) -> Instruction {
+        ix::initialize_test_pool(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            seed,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.admin.token_a_ata,
+            &pool.admin.token_b_ata,
+            &pool.admin.pool_token_ata.pubkey(),
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            initialize,
+        )
+        .unwrap()
+    }
+
+    pub fn deposit(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        referrer: Option<&Pubkey>,
+        allowed_liquidity_provider: Option<&Pubkey>,
+        deposit: Deposit,
+    ) -> Instruction {
+        ix::deposit(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            referrer,
+            allowed_liquidity_provider,
+            deposit,
+        )
+        .unwrap()
+    }
+
+    pub fn deposit_exact_amounts(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        deposit_exact_amounts: DepositExactAmounts,
+    ) -> Instruction {
+        ix::deposit_exact_amounts(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            None,
+            None,
+            deposit_exact_amounts,
+        )
+        .unwrap()
+    }
+
+    pub fn deposit_both_exact_in(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        deposit_both_exact_in: DepositBothExactIn,
+    ) -> Instruction {
+        ix::deposit_both_exact_in(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            None,
+            deposit_both_exact_in,
+        )
+        .unwrap()
+    }
+
+    pub fn deposit_single(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        source: AorB,
+        allowed_liquidity_provider: Option<&Pubkey>,
+        deposit_single: DepositSingle,
+    ) -> Instruction {
+        let (source_mint, source_token_program, source_fees_vault, user_source_ata) = match source {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_token_program,
+                &pool.token_a_fees_vault,
+                &user.token_a_ata,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_token_program,
+                &pool.token_b_fees_vault,
+                &user.token_b_ata,
+            ),
+        };
+        ix::deposit_single(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            source_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            source_fees_vault,
+            user_source_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            source_token_program,
+            allowed_liquidity_provider,
+            deposit_single,
+        )
+        .unwrap()
+    }
+
+    pub fn withdraw_single(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        destination: AorB,
+        withdraw_single: WithdrawSingle,
+    ) -> Instruction {
+        let (
+            destination_mint,
+            destination_token_program,
+            destination_fees_vault,
+            user_destination_ata,
+        ) = match destination {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_token_program,
+                &pool.token_a_fees_vault,
+                &user.token_a_ata,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_token_program,
+                &pool.token_b_fees_vault,
+                &user.token_b_ata,
+            ),
+        };
+        ix::withdraw_single(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            destination_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            destination_fees_vault,
+            user_destination_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            destination_token_program,
+            withdraw_single,
+        )
+        .unwrap()
+    }
+
+    pub fn swap_route_two_hop(
+        first_pool: &SwapPoolAccounts,
+        second_pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        destination_user_ata: &Pubkey,
+        swap_route_two_hop: SwapRouteTwoHop,
+    ) -> Instruction {
+        ix::swap_route_two_hop(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &first_pool.pubkey(),
+            &first_pool.curve,
+            &first_pool.authority,
+            &second_pool.pubkey(),
+            &second_pool.curve,
+            &second_pool.authority,
+            &first_pool.token_a_mint,
+            &first_pool.token_b_mint,
+            &second_pool.token_b_mint,
+            &first_pool.token_a_vault,
+            &first_pool.token_b_vault,
+            &first_pool.token_a_fees_vault,
+            &second_pool.token_a_vault,
+            &second_pool.token_b_vault,
+            &second_pool.token_a_fees_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            destination_user_ata,
+            &first_pool.token_a_token_program,
+            &first_pool.token_b_token_program,
+            &second_pool.token_b_token_program,
+            None,
+            None,
+            swap_route_two_hop,
+        )
+        .unwrap()
+    }
+
+    pub fn swap(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        host_fees: Option<&PoolUserAccounts>,
+        allowed_swap_taker: Option<&Pubkey>,
+        trade_direction: TradeDirection,
+        swap: Swap,
+    ) -> Instruction {
+        swap_with_accounts(
+            pool,
+            user,
+            host_fees,
+            None,
+            allowed_swap_taker,
+            None,
+            trade_direction,
+            swap,
+        )
+    }
+
+    pub fn swap_with_oracle(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        host_fees: Option<&PoolUserAccounts>,
+        allowed_swap_taker: Option<&Pubkey>,
+        oracle: Option<&Pubkey>,
+        trade_direction: TradeDirection,
+        swap: Swap,
+    ) -> Instruction {
+        swap_with_accounts(
+            pool,
+            user,
+            host_fees,
+            None,
+            allowed_swap_taker,
+            oracle,
+            trade_direction,
+            swap,
+        )
+    }
+
+    /// Swap with every optional account, `host_fees` pays the host fee in source tokens and
+    /// `destination_host_fees` in destination tokens
+    pub fn swap_with_accounts(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        host_fees: Option<&PoolUserAccounts>,
+        destination_host_fees: Option<&PoolUserAccounts>,
+        allowed_swap_taker: Option<&Pubkey>,
+        oracle: Option<&Pubkey>,
+        trade_direction: TradeDirection,
+        swap: Swap,
+    ) -> Instruction {
+        let host_fees_destination_ata =
+            destination_host_fees.map(|host_fees| match trade_direction {
+                TradeDirection::AtoB => &host_fees.token_b_ata,
+                TradeDirection::BtoA => &host_fees.token_a_ata,
+            });
+        let (
+            (
+                source_mint,
+                source_token_program,
+                source_vault,
+                source_fees_vault,
+                user_source_ata,
+                host_fees_source_ata,
+            ),
+            (destination_mint, destination_token_program, destination_vault, user_destination_ata),
+        ) = match trade_direction {
+            TradeDirection::AtoB => {
+                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_a_ata);
+                (
+                    (
+                        &pool.token_a_mint,
+                        &pool.token_a_token_program,
+                        &pool.token_a_vault,
+                        &pool.token_a_fees_vault,
+                        &user.token_a_ata,
+                        host_fees_source_ata,
+                    ),
+                    (
+                        &pool.token_b_mint,
+                        &pool.token_b_token_program,
+                        &pool.token_b_vault,
+                        &user.token_b_ata,
+                    ),
+                )
+            }
+            TradeDirection::BtoA => {
+                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_b_ata);
+                (
+                    (
+                        &pool.token_b_mint,
+                        &pool.token_b_token_program,
+                        &pool.token_b_vault,
+                        &pool.token_b_fees_vault,
+                        &user.token_b_ata,
+                        host_fees_source_ata,
+                    ),
+                    (
+                        &pool.token_a_mint,
+                        &pool.token_a_token_program,
+                        &pool.token_a_vault,
+                        &user.token_a_ata,
+                    ),
+                )
+            }
+        };
+        ix::swap(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            source_mint,
+            destination_mint,
+            source_vault,
+            destination_vault,
+            source_fees_vault,
+            user_source_ata,
+            user_destination_ata,
+            host_fees_source_ata,
+            source_token_program,
+            destination_token_program,
+            allowed_swap_taker,
+            oracle,
+            host_fees_destination_ata,
+            swap,
+        )
+        .unwrap()
+    }
+
+    /// The batch takes the same accounts as a swap, only the instruction data differs
+    pub fn swap_batch(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        trade_direction: TradeDirection,
+        SwapBatch { fills, deadline }: SwapBatch,
+    ) -> Instruction {
+        let swap = swap(
+            pool,
+            user,
+            None,
+            None,
+            trade_direction,
+            Swap::new(0, 0, None),
+        );
+        Instruction {
+            data: hyperplane::instruction::SwapBatch { fills, deadline }.data(),
+            ..swap
+        }
+    }
+
+    pub fn withdraw(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        withdraw: Withdraw,
+    ) -> Instruction {
+        ix::withdraw(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            withdraw,
+        )
+        .unwrap()
+    }
+
+    pub fn withdraw_exact_amounts_out(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        withdraw_exact_amounts_out: WithdrawExactAmountsOut,
+    ) -> Instruction {
+        ix::withdraw_exact_amounts_out(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            withdraw_exact_amounts_out,
+        )
+        .unwrap()
+    }
+
+    pub fn withdraw_fees(
+        pool: &SwapPoolAccounts,
+        a_or_b: AorB,
+        withdraw_fees: WithdrawFees,
+    ) -> Instruction {
+        let (fees_mint, fees_vault, admin_fees_ata, fees_token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_fees_vault,
+                &pool.admin.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_fees_vault,
+                &pool.admin.token_b_ata,
+                &pool.token_b_token_program,
+            ),
+        };
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&pool.pubkey());
+
+        ix::withdraw_fees(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            fees_mint,
+            fees_vault,
+            admin_fees_ata,
+            fees_token_program,
+            &default_host_fees,
+            withdraw_fees,
+        )
+        .unwrap()
+    }
+
+    pub fn update_pool_config(
+        pool: &SwapPoolAccounts,
+        update_pool_config: UpdatePoolConfig,
+    ) -> Instruction {
+        let (config_history, _bump) = seeds::pda::config_history_pda(&pool.pubkey());
+        let feature_set = feature_set_of(Some(update_pool_config.mode));
+        ix::update_pool_config(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &config_history,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            feature_set.as_ref(),
+            update_pool_config,
+        )
+        .unwrap()
+    }
+
+    pub fn add_swap_taker(pool: &SwapPoolAccounts, taker: &Pubkey) -> Instruction {
+        let (allowed_swap_taker, _bump) = seeds::pda::allowed_swap_taker_pda(&pool.pubkey(), taker);
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::add_swap_taker(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            taker,
+            &allowed_swap_taker,
+            &feature_set,
+        )
+        .unwrap()
+    }
+
+    pub fn remove_swap_taker(pool: &SwapPoolAccounts, taker: &Pubkey) -> Instruction {
+        let (allowed_swap_taker, _bump) = seeds::pda::allowed_swap_taker_pda(&pool.pubkey(), taker);
+        ix::remove_swap_taker(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &allowed_swap_taker,
+        )
+        .unwrap()
+    }
+
+    pub fn add_liquidity_provider(pool: &SwapPoolAccounts, provider: &Pubkey) -> Instruction {
+        let (allowed_liquidity_provider, _bump) =
+            seeds::pda::allowed_liquidity_provider_pda(&pool.pubkey(), provider);
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::add_liquidity_provider(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            provider,
+            &allowed_liquidity_provider,
+            &feature_set,
+        )
+        .unwrap()
+    }
+
+    pub fn remove_liquidity_provider(pool: &SwapPoolAccounts, provider: &Pubkey) -> Instruction {
+        let (allowed_liquidity_provider, _bump) =
+            seeds::pda::allowed_liquidity_provider_pda(&pool.pubkey(), provider);
+        ix::remove_liquidity_provider(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &allowed_liquidity_provider,
+        )
+        .unwrap()
+    }
+
+    pub fn initialize_feature_set(admin: &Pubkey) -> Instruction {
+        let (program_data, _bump) = seeds::pda::program_data_pda();
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::initialize_feature_set(&hyperplane::id(), admin, &program_data, &feature_set).unwrap()
+    }
+
+    pub fn update_feature_set(admin: &Pubkey, update_feature_set: UpdateFeatureSet) -> Instruction {
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::update_feature_set(&hyperplane::id(), admin, &feature_set, update_feature_set).unwrap()
+    }
+
+    pub fn set_conversion_program(
+        admin: &Pubkey,
+        set_conversion_program: SetConversionProgram,
+    ) -> Instruction {
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::set_conversion_program(
+            &hyperplane::id(),
+            admin,
+            &feature_set,
+            set_conversion_program,
+        )
+        .unwrap()
+    }
+
+    pub fn initialize_fee_tier_registry(admin: &Pubkey) -> Instruction {
+        let (program_data, _bump) = seeds::pda::program_data_pda();
+        let (fee_tier_registry, _bump) = seeds::pda::fee_tier_registry_pda();
+        ix::initialize_fee_tier_registry(
+            &hyperplane::id(),
+            admin,
+            &program_data,
+            &fee_tier_registry,
+        )
+        .unwrap()
+    }
+
+    pub fn set_fee_tier(admin: &Pubkey, set_fee_tier: SetFeeTier) -> Instruction {
+        let (fee_tier_registry, _bump) = seeds::pda::fee_tier_registry_pda();
+        ix::set_fee_tier(&hyperplane::id(), admin, &fee_tier_registry, set_fee_tier).unwrap()
+    }
+
+    pub fn rebalance_across_pools(
+        source_pool: &SwapPoolAccounts,
+        destination_pool: &SwapPoolAccounts,
+        rebalance_across_pools: RebalanceAcrossPools,
+    ) -> Instruction {
+        ix::rebalance_across_pools(
+            &hyperplane::id(),
+            &source_pool.admin.pubkey(),
+            &source_pool.pubkey(),
+            &source_pool.curve,
+            &source_pool.authority,
+            &source_pool.token_a_vault,
+            &source_pool.token_b_vault,
+            &source_pool.pool_token_mint,
+            &source_pool.token_a_fees_vault,
+            &source_pool.token_b_fees_vault,
+            &destination_pool.pubkey(),
+            &destination_pool.curve,
+            &destination_pool.authority,
+            &destination_pool.token_a_vault,
+            &destination_pool.token_b_vault,
+            &destination_pool.pool_token_mint,
+            &source_pool.token_a_mint,
+            &source_pool.token_b_mint,
+            &source_pool.admin.token_a_ata,
+            &source_pool.admin.token_b_ata,
+            &source_pool.admin.pool_token_ata.pubkey(),
+            &destination_pool.admin.pool_token_ata.pubkey(),
+            &source_pool.pool_token_program,
+            &source_pool.token_a_token_program,
+            &source_pool.token_b_token_program,
+            rebalance_across_pools,
+        )
+        .unwrap()
+    }
+
+    pub fn request_withdraw(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        request_withdraw: RequestWithdraw,
+    ) -> Instruction {
+        let (withdrawal_ticket, _bump) =
+            seeds::pda::withdrawal_ticket_pda(&pool.pubkey(), &user.pubkey());
+        let (pool_token_vault, _bump) =
+            seeds::pda::withdrawal_ticket_pool_token_vault_pda(&withdrawal_ticket);
+        ix::request_withdraw(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.pool_token_mint,
+            &user.pool_token_ata,
+            &withdrawal_ticket,
+            &pool_token_vault,
+            &pool.pool_token_program,
+            request_withdraw,
+        )
+        .unwrap()
+    }
+
+    pub fn claim_withdraw(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        signer: &Pubkey,
+    ) -> Instruction {
+        let (withdrawal_ticket, _bump) =
+            seeds::pda::withdrawal_ticket_pda(&pool.pubkey(), &user.pubkey());
+        let (pool_token_vault, _bump) =
+            seeds::pda::withdrawal_ticket_pool_token_vault_pda(&withdrawal_ticket);
+        ix::claim_withdraw(
+            &hyperplane::id(),
+            signer,
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &withdrawal_ticket,
+            &pool_token_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn propose_pool_config(
+        pool: &SwapPoolAccounts,
+        propose_pool_config: ProposePoolConfig,
+    ) -> Instruction {
+        let (proposed_config_change, _bump) =
+            seeds::pda::proposed_config_change_pda(&pool.pubkey());
+        ix::propose_pool_config(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &proposed_config_change,
+            propose_pool_config,
+        )
+        .unwrap()
+    }
+
+    pub fn execute_pool_config(
+        pool: &SwapPoolAccounts,
+        mode: Option<UpdatePoolConfigMode>,
+    ) -> Instruction {
+        let (proposed_config_change, _bump) =
+            seeds::pda::proposed_config_change_pda(&pool.pubkey());
+        let (config_history, _bump) = seeds::pda::config_history_pda(&pool.pubkey());
+        let feature_set = feature_set_of(mode);
+        ix::execute_pool_config(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &proposed_config_change,
+            &config_history,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.pool_token_mint,
+            feature_set.as_ref(),
+        )
+        .unwrap()
+    }
+
+    /// The feature set is only passed with the config changes it gates, as it may not exist
+    fn feature_set_of(mode: Option<UpdatePoolConfigMode>) -> Option<Pubkey> {
+        mode.and_then(UpdatePoolConfigMode::feature)
+            .map(|_| seeds::pda::feature_set_pda().0)
+    }
+
+    pub fn initialize_config_history(pool: &SwapPoolAccounts) -> Instruction {
+        let (config_history, _bump) = seeds::pda::config_history_pda(&pool.pubkey());
+        ix::initialize_config_history(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &config_history,
+        )
+        .unwrap()
+    }
+
+    pub fn initialize_user_stats(pool: &SwapPoolAccounts, user: &PoolUserAccounts) -> Instruction {
+        let (user_stats, _bump) = seeds::pda::user_stats_pda(&pool.pubkey(), &user.pubkey());
+        ix::initialize_user_stats(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &user_stats,
+        )
+        .unwrap()
+    }
+
+    pub fn revoke_vault_delegates(pool: &SwapPoolAccounts, signer: &Pubkey) -> Instruction {
+        ix::revoke_vault_delegates(
+            &hyperplane::id(),
+            signer,
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &pool.token_a_fees_vault,
+            &pool.token_b_fees_vault,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn create_lp_airdrop(
+        pool: &SwapPoolAccounts,
+        sponsor: &PoolUserAccounts,
+        airdrop: &LpAirdropPdas,
+        reward_amount: u64,
+        snapshot_slot: u64,
+    ) -> Instruction {
+        ix::create_lp_airdrop(
+            &hyperplane::id(),
+            &sponsor.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.pool_token_mint,
+            &pool.token_b_mint,
+            &airdrop.airdrop,
+            &airdrop.reward_vault,
+            &airdrop.pool_token_vault,
+            &sponsor.token_b_ata,
+            &pool.pool_token_program,
+            &pool.token_b_token_program,
+            reward_amount,
+            snapshot_slot,
+        )
+        .unwrap()
+    }
+
+    pub fn register_lp_airdrop(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        airdrop: &LpAirdropPdas,
+        pool_token_amount: u64,
+    ) -> Instruction {
+        let (position, _bump) =
+            seeds::pda::lp_airdrop_position_pda(&airdrop.airdrop, &user.pubkey());
+        ix::register_lp_airdrop(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.pool_token_mint,
+            &airdrop.airdrop,
+            &airdrop.pool_token_vault,
+            &position,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            pool_token_amount,
+        )
+        .unwrap()
+    }
+
+    pub fn claim_lp_airdrop(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        airdrop: &LpAirdropPdas,
+    ) -> Instruction {
+        let (position, _bump) =
+            seeds::pda::lp_airdrop_position_pda(&airdrop.airdrop, &user.pubkey());
+        ix::claim_lp_airdrop(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.pool_token_mint,
+            &pool.token_b_mint,
+            &airdrop.airdrop,
+            &airdrop.reward_vault,
+            &airdrop.pool_token_vault,
+            &position,
+            &user.token_b_ata,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            &pool.token_b_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn close_lp_airdrop(
+        pool: &SwapPoolAccounts,
+        sponsor: &PoolUserAccounts,
+        airdrop: &LpAirdropPdas,
+    ) -> Instruction {
+        ix::close_lp_airdrop(
+            &hyperplane::id(),
+            &sponsor.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.token_b_mint,
+            &airdrop.airdrop,
+            &airdrop.reward_vault,
+            &sponsor.token_b_ata,
+            &pool.token_b_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn lock_liquidity(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        pool_token_amount: u64,
+        lock_duration_slots: u64,
+    ) -> Instruction {
+        let locked = seeds::pda::locked_lp_position_pdas(&pool.pubkey(), &user.pubkey());
+        ix::lock_liquidity(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.pool_token_mint,
+            &locked.position,
+            &locked.pool_token_vault,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+            pool_token_amount,
+            lock_duration_slots,
+        )
+        .unwrap()
+    }
+
+    pub fn unlock_liquidity(pool: &SwapPoolAccounts, user: &PoolUserAccounts) -> Instruction {
+        let locked = seeds::pda::locked_lp_position_pdas(&pool.pubkey(), &user.pubkey());
+        ix::unlock_liquidity(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &pool.pool_token_mint,
+            &locked.position,
+            &locked.pool_token_vault,
+            &user.pool_token_ata,
+            &pool.pool_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn donate_to_pool(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        ix::donate_to_pool(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
+            &user.token_a_ata,
+            &user.token_b_ata,
+            &pool.token_a_token_program,
+            &pool.token_b_token_program,
+            token_a_amount,
+            token_b_amount,
+        )
+        .unwrap()
+    }
+
+    pub fn skim_lamports(pool: &SwapPoolAccounts, treasury: &Pubkey) -> Instruction {
+        ix::skim_lamports(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            treasury,
+        )
+        .unwrap()
+    }
+
+    pub fn initialize_default_host_fees(
+        pool: &SwapPoolAccounts,
+        recipient: &Pubkey,
+    ) -> Instruction {
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&pool.pubkey());
+        ix::initialize_default_host_fees(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &default_host_fees,
+            recipient,
+        )
+        .unwrap()
+    }
+
+    pub fn update_default_host_fees(pool: &SwapPoolAccounts, recipient: &Pubkey) -> Instruction {
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&pool.pubkey());
+        ix::update_default_host_fees(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &default_host_fees,
+            recipient,
+        )
+        .unwrap()
+    }
+
+    pub fn claim_default_host_fees(
+        pool: &SwapPoolAccounts,
+        recipient: &PoolUserAccounts,
+        a_or_b: AorB,
+    ) -> Instruction {
+        let (default_host_fees, _bump) = seeds::pda::default_host_fees_pda(&pool.pubkey());
+        let (fees_mint, fees_vault, recipient_fees_ata, fees_token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_fees_vault,
+                &recipient.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_fees_vault,
+                &recipient.token_b_ata,
+                &pool.token_b_token_program,
+            ),
+        };
+        ix::claim_default_host_fees(
+            &hyperplane::id(),
+            &recipient.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            &default_host_fees,
+            fees_mint,
+            fees_vault,
+            recipient_fees_ata,
+            fees_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn extend_pool(payer: &Pubkey, pool: &SwapPoolAccounts) -> Instruction {
+        ix::extend_pool(&hyperplane::id(), payer, &pool.pubkey(), &pool.curve).unwrap()
+    }
+
+    pub fn write_curve_checksum(pool: &SwapPoolAccounts) -> Instruction {
+        ix::write_curve_checksum(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+        )
+        .unwrap()
+    }
+
+    pub fn refresh_price(pool: &SwapPoolAccounts, oracle: &Pubkey) -> Instruction {
+        ix::refresh_price(&hyperplane::id(), &pool.pubkey(), &pool.curve, oracle).unwrap()
+    }
+
+    pub fn flash_borrow(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        a_or_b: AorB,
+        flash_borrow: FlashBorrow,
+    ) -> Instruction {
+        let (mint, vault, user_ata, token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_vault,
+                &user.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_vault,
+                &user.token_b_ata,
+                &pool.token_b_token_program,
+            ),
+        };
+        ix::flash_borrow(
+            &hyperplane::id(),
+            &user.pubkey(),
+            &pool.pubkey(),
+            &pool.authority,
+            mint,
+            vault,
+            user_ata,
+            token_program,
+            flash_borrow,
+        )
+        .unwrap()
+    }
+
+    pub fn close_pool(pool: &SwapPoolAccounts) -> Instruction {
+        ix::close_pool(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            &pool.curve,
+            &pool.authority,
+            &pool.token_a_mint,
+            &pool.token_b_mint,
+            &pool.token_a_vault,
+            &pool.token_b_vault,
             &pool.token_a_fees_vault,
             &pool.token_b_fees_vault,
+            &pool.pool_token_mint,
             &pool.admin.token_a_ata,
             &pool.admin.token_b_ata,
             &pool.admin.pool_token_ata.pubkey(),
             &pool.pool_token_program,
             &pool.token_a_token_program,
             &pool.token_b_token_program,
-            initialize,
         )
         .unwrap()
     }
 
-    pub fn deposit(
+    pub fn assert_pool_price(
         pool: &SwapPoolAccounts,
-        user: &PoolUserAccounts,
-        deposit: Deposit,
+        assert_pool_price: AssertPoolPrice,
     ) -> Instruction {
-        ix::deposit(
+        ix::assert_pool_price(
             &hyperplane::id(),
-            &user.pubkey(),
             &pool.pubkey(),
-            &pool.curve,
-            &pool.authority,
-            &pool.token_a_mint,
-            &pool.token_b_mint,
             &pool.token_a_vault,
             &pool.token_b_vault,
-            &pool.pool_token_mint,
-            &user.token_a_ata,
-            &user.token_b_ata,
-            &user.pool_token_ata,
-            &pool.pool_token_program,
-            &pool.token_a_token_program,
-            &pool.token_b_token_program,
-            deposit,
+            assert_pool_price,
         )
         .unwrap()
     }
 
-    pub fn swap(
+    pub fn flash_repay(
         pool: &SwapPoolAccounts,
         user: &PoolUserAccounts,
-        host_fees: Option<&PoolUserAccounts>,
-        trade_direction: TradeDirection,
-        swap: Swap,
+        a_or_b: AorB,
     ) -> Instruction {
-        let (
-            (
-                source_mint,
-                source_token_program,
-                source_vault,
-                source_fees_vault,
-                user_source_ata,
-                host_fees_source_ata,
+        let (mint, vault, user_ata, token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_vault,
+                &user.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_vault,
+                &user.token_b_ata,
+                &pool.token_b_token_program,
             ),
-            (destination_mint, destination_token_program, destination_vault, user_destination_ata),
-        ) = match trade_direction {
-            TradeDirection::AtoB => {
-                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_a_ata);
-                (
-                    (
-                        &pool.token_a_mint,
-                        &pool.token_a_token_program,
-                        &pool.token_a_vault,
-                        &pool.token_a_fees_vault,
-                        &user.token_a_ata,
-                        host_fees_source_ata,
-                    ),
-                    (
-                        &pool.token_b_mint,
-                        &pool.token_b_token_program,
-                        &pool.token_b_vault,
-                        &user.token_b_ata,
-                    ),
-                )
-            }
-            TradeDirection::BtoA => {
-                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_b_ata);
-                (
-                    (
-                        &pool.token_b_mint,
-                        &pool.token_b_token_program,
-                        &pool.token_b_vault,
-                        &pool.token_b_fees_vault,
-                        &user.token_b_ata,
-                        host_fees_source_ata,
-                    ),
-                    (
-                        &pool.token_a_mint,
-                        &pool.token_a_token_program,
-                        &pool.token_a_vault,
-                        &user.token_a_ata,
-                    ),
-                )
-            }
         };
-        ix::swap(
+        ix::flash_repay(
             &hyperplane::id(),
             &user.pubkey(),
             &pool.pubkey(),
-            &pool.curve,
-            &pool.authority,
-            source_mint,
-            destination_mint,
-            source_vault,
-            destination_vault,
-            source_fees_vault,
-            user_source_ata,
-            user_destination_ata,
-            host_fees_source_ata,
-            source_token_program,
-            destination_token_program,
-            swap,
+            mint,
+            vault,
+            user_ata,
+            token_program,
         )
         .unwrap()
     }
 
-    pub fn withdraw(
+    pub fn initialize_protocol_fee_config(admin: &Pubkey, authority: &Pubkey) -> Instruction {
+        let (program_data, _bump) = seeds::pda::program_data_pda();
+        let (protocol_fee_config, _bump) = seeds::pda::protocol_fee_config_pda();
+        ix::initialize_protocol_fee_config(
+            &hyperplane::id(),
+            admin,
+            &program_data,
+            &protocol_fee_config,
+            *authority,
+        )
+        .unwrap()
+    }
+
+    pub fn withdraw_protocol_fees(
         pool: &SwapPoolAccounts,
-        user: &PoolUserAccounts,
-        withdraw: Withdraw,
+        authority: &PoolUserAccounts,
+        a_or_b: AorB,
     ) -> Instruction {
-        ix::withdraw(
+        let (protocol_fee_config, _bump) = seeds::pda::protocol_fee_config_pda();
+        let (fees_mint, fees_vault, authority_fees_ata, fees_token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &pool.token_a_fees_vault,
+                &authority.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &pool.token_b_fees_vault,
+                &authority.token_b_ata,
+                &pool.token_b_token_program,
+            ),
+        };
+        ix::withdraw_protocol_fees(
             &hyperplane::id(),
-            &user.pubkey(),
+            &authority.pubkey(),
+            &protocol_fee_config,
             &pool.pubkey(),
-            &pool.curve,
             &pool.authority,
-            &pool.token_a_mint,
-            &pool.token_b_mint,
+            fees_mint,
+            fees_vault,
+            authority_fees_ata,
+            fees_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn propose_admin(pool: &SwapPoolAccounts, pending_admin: &Pubkey) -> Instruction {
+        ix::propose_admin(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            *pending_admin,
+        )
+        .unwrap()
+    }
+
+    pub fn set_guardian(pool: &SwapPoolAccounts, guardian: &Pubkey) -> Instruction {
+        ix::set_guardian(
+            &hyperplane::id(),
+            &pool.admin.pubkey(),
+            &pool.pubkey(),
+            *guardian,
+        )
+        .unwrap()
+    }
+
+    pub fn guardian_pause_pool(
+        pool: &SwapPoolAccounts,
+        guardian: &Pubkey,
+        update_pool_config: UpdatePoolConfig,
+    ) -> Instruction {
+        let (config_history, _bump) = seeds::pda::config_history_pda(&pool.pubkey());
+        ix::guardian_pause_pool(
+            &hyperplane::id(),
+            guardian,
+            &pool.pubkey(),
+            &config_history,
             &pool.token_a_vault,
             &pool.token_b_vault,
             &pool.pool_token_mint,
-            &pool.token_a_fees_vault,
-            &pool.token_b_fees_vault,
-            &user.token_a_ata,
-            &user.token_b_ata,
-            &user.pool_token_ata,
-            &pool.pool_token_program,
-            &pool.token_a_token_program,
-            &pool.token_b_token_program,
-            withdraw,
+            update_pool_config,
         )
         .unwrap()
     }
 
-    pub fn withdraw_fees(
+    pub fn accept_admin(pool: &SwapPoolAccounts, pending_admin: &Pubkey) -> Instruction {
+        ix::accept_admin(&hyperplane::id(), pending_admin, &pool.pubkey()).unwrap()
+    }
+
+    pub fn initialize_protocol_fee_vault(
         pool: &SwapPoolAccounts,
+        authority: &PoolUserAccounts,
         a_or_b: AorB,
-        withdraw_fees: WithdrawFees,
     ) -> Instruction {
-        let (fees_mint, fees_vault, admin_fees_ata, fees_token_program) = match a_or_b {
+        let (protocol_fee_config, _bump) = seeds::pda::protocol_fee_config_pda();
+        let (mint, token_program) = match a_or_b {
+            AorB::A => (&pool.token_a_mint, &pool.token_a_token_program),
+            AorB::B => (&pool.token_b_mint, &pool.token_b_token_program),
+        };
+        let (protocol_fee_vault, _bump) = seeds::pda::protocol_fee_vault_pda(mint);
+        ix::initialize_protocol_fee_vault(
+            &hyperplane::id(),
+            &authority.pubkey(),
+            &protocol_fee_config,
+            mint,
+            &protocol_fee_vault,
+            token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn sweep_protocol_fees(pool: &SwapPoolAccounts, a_or_b: AorB) -> Instruction {
+        let (fees_mint, fees_vault, fees_token_program) = match a_or_b {
             AorB::A => (
                 &pool.token_a_mint,
                 &pool.token_a_fees_vault,
-                &pool.admin.token_a_ata,
                 &pool.token_a_token_program,
             ),
             AorB::B => (
                 &pool.token_b_mint,
                 &pool.token_b_fees_vault,
-                &pool.admin.token_b_ata,
                 &pool.token_b_token_program,
             ),
         };
-
-        ix::withdraw_fees(
+        let (protocol_fee_vault, _bump) = seeds::pda::protocol_fee_vault_pda(fees_mint);
+        ix::sweep_protocol_fees(
             &hyperplane::id(),
-            &pool.admin.pubkey(),
             &pool.pubkey(),
             &pool.authority,
             fees_mint,
             fees_vault,
-            admin_fees_ata,
+            &protocol_fee_vault,
             fees_token_program,
-            withdraw_fees,
         )
         .unwrap()
     }
 
-    pub fn update_pool_config(
+    pub fn withdraw_shared_protocol_fees(
         pool: &SwapPoolAccounts,
-        update_pool_config: UpdatePoolConfig,
+        authority: &PoolUserAccounts,
+        a_or_b: AorB,
     ) -> Instruction {
-        ix::update_pool_config(
+        let (protocol_fee_config, _bump) = seeds::pda::protocol_fee_config_pda();
+        let (fees_mint, authority_fees_ata, fees_token_program) = match a_or_b {
+            AorB::A => (
+                &pool.token_a_mint,
+                &authority.token_a_ata,
+                &pool.token_a_token_program,
+            ),
+            AorB::B => (
+                &pool.token_b_mint,
+                &authority.token_b_ata,
+                &pool.token_b_token_program,
+            ),
+        };
+        let (protocol_fee_vault, _bump) = seeds::pda::protocol_fee_vault_pda(fees_mint);
+        ix::withdraw_shared_protocol_fees(
+            &hyperplane::id(),
+            &authority.pubkey(),
+            &protocol_fee_config,
+            fees_mint,
+            &protocol_fee_vault,
+            authority_fees_ata,
+            fees_token_program,
+        )
+        .unwrap()
+    }
+
+    pub fn migrate_mint(
+        pool: &SwapPoolAccounts,
+        a_or_b: AorB,
+        new_mint: &Pubkey,
+        new_token_program: &Pubkey,
+        conversion_data: Vec<u8>,
+        reserve: &Pubkey,
+    ) -> Instruction {
+        let (old_mint, old_vault, old_fees_vault, old_token_program, new_vault, new_fees_vault) =
+            match a_or_b {
+                AorB::A => (
+                    &pool.token_a_mint,
+                    &pool.token_a_vault,
+                    &pool.token_a_fees_vault,
+                    &pool.token_a_token_program,
+                    seeds::pda::token_a_vault_pda(&pool.pubkey(), new_mint).0,
+                    seeds::pda::token_a_fees_vault_pda(&pool.pubkey(), new_mint).0,
+                ),
+                AorB::B => (
+                    &pool.token_b_mint,
+                    &pool.token_b_vault,
+                    &pool.token_b_fees_vault,
+                    &pool.token_b_token_program,
+                    seeds::pda::token_b_vault_pda(&pool.pubkey(), new_mint).0,
+                    seeds::pda::token_b_fees_vault_pda(&pool.pubkey(), new_mint).0,
+                ),
+            };
+        let (other_vault, other_fees_vault) = match a_or_b {
+            AorB::A => (&pool.token_b_vault, &pool.token_b_fees_vault),
+            AorB::B => (&pool.token_a_vault, &pool.token_a_fees_vault),
+        };
+        let (feature_set, _bump) = seeds::pda::feature_set_pda();
+        ix::migrate_mint(
             &hyperplane::id(),
             &pool.admin.pubkey(),
             &pool.pubkey(),
-            update_pool_config,
+            &pool.authority,
+            old_mint,
+            new_mint,
+            old_vault,
+            old_fees_vault,
+            &new_vault,
+            &new_fees_vault,
+            other_vault,
+            other_fees_vault,
+            &pool.pool_token_mint,
+            &mint_migration::ID,
+            &feature_set,
+            old_token_program,
+            new_token_program,
+            a_or_b,
+            conversion_data,
+            &mint_migration::conversion_accounts(reserve),
         )
         .unwrap()
     }
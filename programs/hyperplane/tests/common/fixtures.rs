@@ -3,10 +3,13 @@ use solana_sdk::native_token::sol_to_lamports;
 
 use crate::common::{
     client, setup, token_operations,
-    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},
 };
 
-pub enum ProgramDependency {}
+pub enum ProgramDependency {
+    /// Mock conversion program of `migrate_mint`
+    MockMintMigration,
+}
 
 pub async fn new_pool(
     ctx: &mut TestContext,
@@ -34,6 +37,8 @@ pub async fn new_pool(
             fees,
             initial_supply,
             curve_parameters,
+            burn_owner_fees: false,
+            strict_token_program: false,
         },
     )
     .await
@@ -42,6 +47,61 @@ pub async fn new_pool(
     pool
 }
 
+/// Creates a new pool trading the same pair as `pool`, owned by the same admin.
+pub async fn new_sibling_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    fees: Fees,
+    initial_supply: InitialSupply,
+    curve_parameters: CurveUserParameters,
+) -> SwapPoolAccounts {
+    let sibling = setup::new_sibling_pool_accs(ctx, pool, &initial_supply).await;
+
+    client::initialize_pool(
+        ctx,
+        &sibling,
+        Initialize {
+            fees,
+            initial_supply,
+            curve_parameters,
+            burn_owner_fees: false,
+            strict_token_program: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    sibling
+}
+
+/// Creates a new pool trading token B of `pool` against a new token, owned by the same admin.
+pub async fn new_chained_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    fees: Fees,
+    initial_supply: InitialSupply,
+    token_b: TokenSpec,
+    curve_parameters: CurveUserParameters,
+) -> SwapPoolAccounts {
+    let chained = setup::new_chained_pool_accs(ctx, pool, token_b, &initial_supply).await;
+
+    client::initialize_pool(
+        ctx,
+        &chained,
+        Initialize {
+            fees,
+            initial_supply,
+            curve_parameters,
+            burn_owner_fees: false,
+            strict_token_program: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    chained
+}
+
 pub struct Sol;
 impl Sol {
     pub fn one() -> u64 {
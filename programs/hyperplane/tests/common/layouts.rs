@@ -0,0 +1,79 @@
+//! Account layout fixtures - accounts serialized by the previous release, stored under
+//! `tests/layouts`, that the current code must deserialize to the same values
+//!
+//! Refresh them when cutting a release with `UPDATE_LAYOUT_FIXTURES=1 cargo test layout`, never
+//! to make a failing check pass: a failure means accounts already on-chain would be misread.
+//! The `SwapPool` fixture is the first release's layout and is never refreshed, the pools it
+//! created are still live and every later field must keep reading as zero from them.
+//! A fixture shorter than the current account is zero-extended first, as `extend_pool` grows
+//! the pools created before their padding, so fields appended to the padding read as zero.
+
+use std::{env, fmt::Debug, fs, path::PathBuf};
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use bytemuck::Pod;
+use solana_sdk::account::Account;
+
+use crate::common::state;
+
+const UPDATE_FIXTURES_ENV: &str = "UPDATE_LAYOUT_FIXTURES";
+/// Fixtures of accounts created by the first release, never rewritten
+const FROZEN_FIXTURES: &[&str] = &["swap_pool"];
+
+/// Check a borsh account against its fixture
+pub fn assert_layout<T>(name: &str, expected: &T)
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + Debug + PartialEq,
+{
+    let mut current = Vec::new();
+    expected.try_serialize(&mut current).unwrap();
+    check_fixture(name, current, expected);
+}
+
+/// Check a zero-copy account against its fixture
+pub fn assert_zero_copy_layout<T>(name: &str, expected: &T)
+where
+    T: Pod + AccountDeserialize + Discriminator + Debug + PartialEq,
+{
+    let current = [&T::discriminator()[..], bytemuck::bytes_of(expected)].concat();
+    check_fixture(name, current, expected);
+}
+
+fn check_fixture<T>(name: &str, current: Vec<u8>, expected: &T)
+where
+    T: AccountDeserialize + Discriminator + Debug + PartialEq,
+{
+    let path = fixture_path(name);
+    if env::var_os(UPDATE_FIXTURES_ENV).is_some() && !FROZEN_FIXTURES.contains(&name) {
+        fs::write(&path, &current).unwrap();
+    }
+    let mut fixture = fs::read(&path)
+        .unwrap_or_else(|e| panic!("Cannot read layout fixture {}: {}", path.display(), e));
+    if fixture.len() < current.len() {
+        fixture.resize(current.len(), 0);
+    }
+
+    let account = Account {
+        data: fixture.clone(),
+        ..Account::default()
+    };
+    let deserialized = state::deserialize::<T>(&account)
+        .unwrap_or_else(|e| panic!("Cannot deserialize layout fixture {}: {:?}", name, e));
+    assert_eq!(
+        &deserialized, expected,
+        "{} fixture deserializes to different values",
+        name
+    );
+    assert_eq!(
+        fixture, current,
+        "{} serializes differently than its fixture",
+        name
+    );
+}
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("layouts")
+        .join(format!("{}.bin", name))
+}
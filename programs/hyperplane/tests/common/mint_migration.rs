@@ -0,0 +1,98 @@
+//! Mock conversion program of `migrate_mint`, burning the old tokens and paying the same amount
+//! of the successor mint out of a reserve it owns
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use anchor_spl::token_2022::spl_token_2022::{self, extension::StateWithExtensions, state::Mint};
+
+pub const ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+pub const RESERVE_AUTHORITY: &[u8] = b"reserve_authority";
+
+/// First byte of the conversion data, paying one token less than burned to test the 1:1 check
+pub const SHORT_CHANGE: u8 = 1;
+
+pub fn reserve_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RESERVE_AUTHORITY], &ID)
+}
+
+/// Extra accounts of the conversion, appended after the `migrate_mint` accounts
+pub fn conversion_accounts(reserve: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*reserve, false),
+        AccountMeta::new_readonly(reserve_authority().0, false),
+        AccountMeta::new_readonly(ID, false),
+    ]
+}
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (source, old_mint, destination, new_mint, authority) = (
+        &accounts[0],
+        &accounts[1],
+        &accounts[2],
+        &accounts[3],
+        &accounts[4],
+    );
+    let (old_token_program, new_token_program, reserve, reserve_authority_info) =
+        (&accounts[5], &accounts[6], &accounts[7], &accounts[8]);
+    if data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes(data[data.len() - 8..].try_into().unwrap());
+    let paid_amount = if data.len() > 8 && data[0] == SHORT_CHANGE {
+        amount - 1
+    } else {
+        amount
+    };
+    let decimals = StateWithExtensions::<Mint>::unpack(&new_mint.data.borrow())?
+        .base
+        .decimals;
+
+    invoke(
+        &spl_token_2022::instruction::burn(
+            old_token_program.key,
+            source.key,
+            old_mint.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            source.clone(),
+            old_mint.clone(),
+            authority.clone(),
+            old_token_program.clone(),
+        ],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            new_token_program.key,
+            reserve.key,
+            new_mint.key,
+            destination.key,
+            reserve_authority_info.key,
+            &[],
+            paid_amount,
+            decimals,
+        )?,
+        &[
+            reserve.clone(),
+            new_mint.clone(),
+            destination.clone(),
+            reserve_authority_info.clone(),
+            new_token_program.clone(),
+        ],
+        &[&[RESERVE_AUTHORITY, &[reserve_authority().1]]],
+    )?;
+    Ok(())
+}
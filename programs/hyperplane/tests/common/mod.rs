@@ -4,8 +4,11 @@
 
 pub mod client;
 pub mod fixtures;
+pub mod layouts;
 pub mod macros;
+pub mod mint_migration;
 pub mod runner;
+pub mod scenario;
 pub mod setup;
 pub mod state;
 pub mod token_operations;
@@ -1,15 +1,19 @@
 use solana_program_test::{processor, ProgramTest};
 
 use super::types::TestContext;
-use crate::common::fixtures::ProgramDependency;
+use crate::common::{fixtures::ProgramDependency, mint_migration};
 
 pub fn program(dependencies: &[ProgramDependency]) -> ProgramTest {
-    let program_test =
+    let mut program_test =
         ProgramTest::new("hyperplane", hyperplane::ID, processor!(hyperplane::entry));
 
-    dependencies
-        .iter()
-        .for_each(|_dep| unimplemented!("No dependency supported yet."));
+    dependencies.iter().for_each(|dep| match dep {
+        ProgramDependency::MockMintMigration => program_test.add_program(
+            "mock_mint_migration",
+            mint_migration::ID,
+            processor!(mint_migration::process_instruction),
+        ),
+    });
     program_test
 }
 
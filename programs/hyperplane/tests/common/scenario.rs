@@ -0,0 +1,305 @@
+//! Builder of end-to-end scenarios, expanding a few readable lines into program-test executions
+//!
+//! ```ignore
+//! Scenario::new()
+//!     .init(CurveUserParameters::ConstantProduct, fees) // 0.3% trade fee
+//!     .user((1_000, 0))
+//!     .swap(0, TradeDirection::AtoB, 1_000)
+//!     .expect_out(996)
+//!     .expect_quoted()
+//!     .run()
+//!     .await;
+//! ```
+//!
+//! Users are referred to by their index in the order of the `user` calls. An operation failing
+//! the transaction panics unless it is directly followed by `expect_err`, and every expectation
+//! applies to the last operation.
+
+use hyperplane::{
+    curve::{base::SwapCurve, calculator::TradeDirection, fees::Fees},
+    ix::{Deposit, Swap},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::transaction::TransactionError;
+
+use crate::common::{
+    client, fixtures, runner, setup, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+#[derive(Clone, Debug)]
+enum Step {
+    Swap {
+        user: usize,
+        trade_direction: TradeDirection,
+        amount_in: u64,
+    },
+    Deposit {
+        user: usize,
+        pool_token_amount: u64,
+    },
+    ExpectOut(u64),
+    ExpectQuoted,
+    ExpectErr(TransactionError),
+    ExpectReserves(u64, u64),
+}
+
+/// Outcome of the last operation of a scenario
+#[derive(Default)]
+struct Outcome {
+    step: usize,
+    result: Option<Result<(), BanksClientError>>,
+    /// Destination tokens of a swap, or pool tokens of a deposit, received by the user
+    amount_out: u64,
+    /// Amount out of a swap computed with the curve math off-chain, before the swap
+    quoted_amount_out: Option<u64>,
+}
+
+pub struct Scenario {
+    curve_parameters: CurveUserParameters,
+    fees: Fees,
+    initial_supply: InitialSupply,
+    trading_tokens: SwapPairSpec,
+    users: Vec<(u64, u64)>,
+    steps: Vec<Step>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scenario {
+    /// Constant product pool without fees, funded with `setup::default_supply`
+    pub fn new() -> Self {
+        Self {
+            curve_parameters: CurveUserParameters::ConstantProduct,
+            fees: Fees::default(),
+            initial_supply: setup::default_supply(),
+            trading_tokens: SwapPairSpec::default(),
+            users: vec![],
+            steps: vec![],
+        }
+    }
+
+    pub fn init(mut self, curve_parameters: CurveUserParameters, fees: Fees) -> Self {
+        self.curve_parameters = curve_parameters;
+        self.fees = fees;
+        self
+    }
+
+    pub fn initial_supply(mut self, token_a_amount: u64, token_b_amount: u64) -> Self {
+        self.initial_supply = InitialSupply::new(token_a_amount, token_b_amount);
+        self
+    }
+
+    pub fn trading_tokens(mut self, trading_tokens: SwapPairSpec) -> Self {
+        self.trading_tokens = trading_tokens;
+        self
+    }
+
+    /// User funded with token A and B balances
+    pub fn user(mut self, balances: (u64, u64)) -> Self {
+        self.users.push(balances);
+        self
+    }
+
+    pub fn swap(mut self, user: usize, trade_direction: TradeDirection, amount_in: u64) -> Self {
+        self.steps.push(Step::Swap {
+            user,
+            trade_direction,
+            amount_in,
+        });
+        self
+    }
+
+    /// Deposit of `pool_token_amount`, without bounds on the token amounts taken
+    pub fn deposit(mut self, user: usize, pool_token_amount: u64) -> Self {
+        self.steps.push(Step::Deposit {
+            user,
+            pool_token_amount,
+        });
+        self
+    }
+
+    /// The last operation paid out exactly `amount_out`
+    pub fn expect_out(mut self, amount_out: u64) -> Self {
+        self.steps.push(Step::ExpectOut(amount_out));
+        self
+    }
+
+    /// The last swap paid out what the curve math quotes off-chain, e.g. for the client
+    pub fn expect_quoted(mut self) -> Self {
+        self.steps.push(Step::ExpectQuoted);
+        self
+    }
+
+    pub fn expect_err(mut self, err: TransactionError) -> Self {
+        self.steps.push(Step::ExpectErr(err));
+        self
+    }
+
+    pub fn expect_reserves(mut self, token_a_amount: u64, token_b_amount: u64) -> Self {
+        self.steps
+            .push(Step::ExpectReserves(token_a_amount, token_b_amount));
+        self
+    }
+
+    pub async fn run(self) {
+        let program = runner::program(&[]);
+        let mut ctx = runner::start(program).await;
+
+        let pool = fixtures::new_pool(
+            &mut ctx,
+            self.fees,
+            self.initial_supply.clone(),
+            self.trading_tokens,
+            self.curve_parameters.clone(),
+        )
+        .await;
+        let mut users = Vec::with_capacity(self.users.len());
+        for balances in &self.users {
+            users.push(setup::new_pool_user(&mut ctx, &pool, *balances).await);
+        }
+
+        let mut outcome = Outcome::default();
+        for (index, step) in self.steps.iter().enumerate() {
+            let step_index = index + 1;
+            if !matches!(step, Step::ExpectErr(_)) {
+                outcome.require_success();
+            }
+            match step {
+                Step::Swap {
+                    user,
+                    trade_direction,
+                    amount_in,
+                } => {
+                    outcome = Outcome {
+                        step: step_index,
+                        ..self
+                            .swap(&mut ctx, &pool, &users[*user], *trade_direction, *amount_in)
+                            .await
+                    };
+                }
+                Step::Deposit {
+                    user,
+                    pool_token_amount,
+                } => {
+                    let user = &users[*user];
+                    let before = token_operations::balance(&mut ctx, &user.pool_token_ata).await;
+                    let result = client::deposit(
+                        &mut ctx,
+                        &pool,
+                        user,
+                        Deposit::new(*pool_token_amount, u64::MAX, u64::MAX, None),
+                    )
+                    .await;
+                    let after = token_operations::balance(&mut ctx, &user.pool_token_ata).await;
+                    outcome = Outcome {
+                        step: step_index,
+                        result: Some(result),
+                        amount_out: after - before,
+                        quoted_amount_out: None,
+                    };
+                }
+                Step::ExpectOut(amount_out) => {
+                    assert_eq!(
+                        outcome.amount_out, *amount_out,
+                        "step {}: amount out of step {}",
+                        step_index, outcome.step
+                    );
+                }
+                Step::ExpectQuoted => {
+                    let quoted_amount_out = outcome.quoted_amount_out.unwrap_or_else(|| {
+                        panic!("step {}: step {} is not a swap", step_index, outcome.step)
+                    });
+                    assert_eq!(
+                        outcome.amount_out, quoted_amount_out,
+                        "step {}: amount out of step {} differs from the off-chain quote",
+                        step_index, outcome.step
+                    );
+                }
+                Step::ExpectErr(err) => {
+                    let result = outcome.result.take().unwrap_or_else(|| {
+                        panic!("step {}: no operation to expect an error of", step_index)
+                    });
+                    assert_eq!(
+                        result.unwrap_err().unwrap(),
+                        *err,
+                        "step {}: error of step {}",
+                        step_index,
+                        outcome.step
+                    );
+                }
+                Step::ExpectReserves(token_a_amount, token_b_amount) => {
+                    assert_eq!(
+                        (
+                            token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+                            token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+                        ),
+                        (*token_a_amount, *token_b_amount),
+                        "step {}: reserves",
+                        step_index
+                    );
+                }
+            }
+        }
+        outcome.require_success();
+    }
+
+    async fn swap(
+        &self,
+        ctx: &mut TestContext,
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        trade_direction: TradeDirection,
+        amount_in: u64,
+    ) -> Outcome {
+        let (source_vault, destination_vault, destination_user_ata) = match trade_direction {
+            TradeDirection::AtoB => (&pool.token_a_vault, &pool.token_b_vault, &user.token_b_ata),
+            TradeDirection::BtoA => (&pool.token_b_vault, &pool.token_a_vault, &user.token_a_ata),
+        };
+        let swap_curve = SwapCurve::new_from_params(self.curve_parameters.to_curve_params(
+            self.trading_tokens.a.decimals,
+            self.trading_tokens.b.decimals,
+        ))
+        .unwrap();
+        let quoted_amount_out = swap_curve
+            .swap(
+                u128::from(amount_in),
+                u128::from(token_operations::balance(ctx, source_vault).await),
+                u128::from(token_operations::balance(ctx, destination_vault).await),
+                trade_direction,
+                &self.fees,
+            )
+            .ok()
+            .map(|swap_result| u64::try_from(swap_result.destination_amount_swapped).unwrap());
+
+        let before = token_operations::balance(ctx, destination_user_ata).await;
+        let result = client::swap(
+            ctx,
+            pool,
+            user,
+            trade_direction,
+            Swap::new(amount_in, 0, None),
+        )
+        .await;
+        let after = token_operations::balance(ctx, destination_user_ata).await;
+        Outcome {
+            result: Some(result),
+            amount_out: after - before,
+            quoted_amount_out,
+            ..Outcome::default()
+        }
+    }
+}
+
+impl Outcome {
+    fn require_success(&mut self) {
+        if let Some(Err(err)) = self.result.take() {
+            panic!("step {} failed: {:?}", self.step, err);
+        }
+    }
+}
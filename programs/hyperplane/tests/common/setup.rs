@@ -9,7 +9,7 @@ use super::{fixtures::Sol, token_operations, types::TestContext};
 use crate::{
     common::{
         client,
-        types::{PoolAdminAccounts, PoolUserAccounts, SwapPairSpec, SwapPoolAccounts},
+        types::{PoolAdminAccounts, PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TokenSpec},
         utils::calculate_pool_tokens,
     },
     send_tx,
@@ -120,7 +120,7 @@ pub async fn new_lp_user(
             ctx,
             pool,
             &user,
-            Deposit::new(pool_tokens, a_deposit, b_deposit),
+            Deposit::new(pool_tokens, a_deposit, b_deposit, None),
         )
         .await
         .unwrap();
@@ -205,6 +205,136 @@ pub async fn new_pool_accs(
     }
 }
 
+/// Accounts for a new pool trading the same pair as `pool`, owned by the same admin.
+pub async fn new_sibling_pool_accs(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    initial_supply: &InitialSupply,
+) -> SwapPoolAccounts {
+    token_operations::mint_to(
+        ctx,
+        &pool.token_a_token_program,
+        &pool.token_a_mint,
+        &pool.admin.token_a_ata,
+        initial_supply.initial_supply_a,
+    )
+    .await
+    .unwrap();
+    token_operations::mint_to(
+        ctx,
+        &pool.token_b_token_program,
+        &pool.token_b_mint,
+        &pool.admin.token_b_ata,
+        initial_supply.initial_supply_b,
+    )
+    .await
+    .unwrap();
+
+    let sibling = kp();
+
+    let seeds::pda::InitPoolPdas {
+        curve,
+        authority,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+    } = seeds::pda::init_pool_pdas(&sibling.pubkey(), &pool.token_a_mint, &pool.token_b_mint);
+
+    let admin = PoolAdminAccounts::new(
+        pool.admin.admin.clone(),
+        pool.admin.token_a_ata,
+        pool.admin.token_b_ata,
+        kp(),
+    );
+
+    SwapPoolAccounts {
+        admin,
+        pool: sibling,
+        curve,
+        authority,
+        pool_token_mint,
+        token_a_vault,
+        token_b_vault,
+        token_a_fees_vault,
+        token_b_fees_vault,
+        ..pool.clone()
+    }
+}
+
+/// Accounts for a new pool trading token B of `pool` as its token A against a new mint, owned by
+/// the same admin, so that the two pools can be chained.
+pub async fn new_chained_pool_accs(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    token_b: TokenSpec,
+    initial_supply: &InitialSupply,
+) -> SwapPoolAccounts {
+    token_operations::mint_to(
+        ctx,
+        &pool.token_b_token_program,
+        &pool.token_b_mint,
+        &pool.admin.token_b_ata,
+        initial_supply.initial_supply_a,
+    )
+    .await
+    .unwrap();
+
+    let token_b_mint = kp();
+    token_operations::create_mint(ctx, &token_b_mint, token_b)
+        .await
+        .unwrap();
+    let token_b_admin_ata = token_operations::create_and_mint_to_token_account(
+        ctx,
+        &token_b.token_program,
+        &pool.admin.pubkey(),
+        &token_b_mint.pubkey(),
+        initial_supply.initial_supply_b,
+    )
+    .await;
+
+    let chained = kp();
+
+    let seeds::pda::InitPoolPdas {
+        curve,
+        authority,
+        token_a_vault,
+        token_b_vault,
+        pool_token_mint,
+        token_a_fees_vault,
+        token_b_fees_vault,
+    } = seeds::pda::init_pool_pdas(
+        &chained.pubkey(),
+        &pool.token_b_mint,
+        &token_b_mint.pubkey(),
+    );
+
+    let admin = PoolAdminAccounts::new(
+        pool.admin.admin.clone(),
+        pool.admin.token_b_ata,
+        token_b_admin_ata,
+        kp(),
+    );
+
+    SwapPoolAccounts {
+        admin,
+        pool: chained,
+        curve,
+        authority,
+        token_a_mint: pool.token_b_mint,
+        token_b_mint: token_b_mint.pubkey(),
+        pool_token_mint,
+        token_a_vault,
+        token_b_vault,
+        token_a_fees_vault,
+        token_b_fees_vault,
+        pool_token_program: pool.pool_token_program,
+        token_a_token_program: pool.token_b_token_program,
+        token_b_token_program: token_b.token_program,
+    }
+}
+
 pub fn default_supply() -> InitialSupply {
     InitialSupply::new(1_000_000_000000, 1_000_000_000000)
 }
@@ -1,6 +1,16 @@
-use anchor_lang::prelude::Pubkey;
-use hyperplane::curve::{calculator::RoundDirection, math::pool_tokens_to_trading_tokens};
-use solana_sdk::account::AccountSharedData;
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        program_option::COption,
+    },
+};
+use anchor_spl::token_interface::spl_token_2022::state::{Account, Mint};
+use hyperplane::{
+    curve::{calculator::RoundDirection, math::pool_tokens_to_trading_tokens},
+    utils::seeds,
+};
+use solana_sdk::{account::AccountSharedData, program_pack::Pack};
 
 use crate::common::types::TestContext;
 
@@ -45,6 +55,69 @@ pub async fn clone_account_with_new_owner(
         .set_account(new_address, &cloned_account);
 }
 
+/// Write the program data of the program with the given upgrade authority
+///
+/// The test program is loaded as a builtin, which has no program data account of its own
+pub fn set_upgrade_authority(ctx: &mut TestContext, upgrade_authority: &Pubkey) {
+    let (program_data, _bump) = seeds::pda::program_data_pda();
+    let account = AccountSharedData::new_data(
+        1_000_000_000,
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(*upgrade_authority),
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    ctx.context.set_account(&program_data, &account);
+}
+
+/// Overwrite the delegate and close authority of a token account, bypassing the token program
+pub async fn set_token_account_authorities(
+    ctx: &mut TestContext,
+    token_account: &Pubkey,
+    delegate: COption<Pubkey>,
+    delegated_amount: u64,
+    close_authority: COption<Pubkey>,
+) {
+    let mut account = ctx
+        .context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut account_state = Account::unpack_from_slice(&account.data[..Account::LEN]).unwrap();
+    account_state.delegate = delegate;
+    account_state.delegated_amount = delegated_amount;
+    account_state.close_authority = close_authority;
+    account_state.pack_into_slice(&mut account.data[..Account::LEN]);
+    ctx.context
+        .set_account(token_account, &AccountSharedData::from(account));
+}
+
+/// Overwrite the authorities of a mint, bypassing the token program
+pub async fn set_mint_authorities(
+    ctx: &mut TestContext,
+    mint: &Pubkey,
+    mint_authority: COption<Pubkey>,
+    freeze_authority: COption<Pubkey>,
+) {
+    let mut account = ctx
+        .context
+        .banks_client
+        .get_account(*mint)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut mint_state = Mint::unpack_from_slice(&account.data[..Mint::LEN]).unwrap();
+    mint_state.mint_authority = mint_authority;
+    mint_state.freeze_authority = freeze_authority;
+    mint_state.pack_into_slice(&mut account.data[..Mint::LEN]);
+    ctx.context
+        .set_account(mint, &AccountSharedData::from(account));
+}
+
 pub fn calculate_pool_tokens(
     a_amount: u64,
     b_amount: u64,
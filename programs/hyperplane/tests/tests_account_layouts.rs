@@ -0,0 +1,75 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use hyperplane::{
+    curve::fees::Fees,
+    state::{ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve, SwapPool},
+};
+
+use crate::common::layouts;
+
+fn pubkey(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+#[test]
+pub fn test_swap_pool_layout() {
+    let pool = SwapPool {
+        admin: pubkey(1),
+        pool_authority: pubkey(2),
+        pool_authority_bump_seed: 255,
+        token_a_vault: pubkey(3),
+        token_b_vault: pubkey(4),
+        pool_token_mint: pubkey(5),
+        token_a_mint: pubkey(6),
+        token_b_mint: pubkey(7),
+        token_a_fees_vault: pubkey(8),
+        token_b_fees_vault: pubkey(9),
+        fees: Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 2,
+            owner_trade_fee_denominator: 1_000,
+            owner_withdraw_fee_numerator: 3,
+            owner_withdraw_fee_denominator: 10_000,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+        },
+        curve_type: 1,
+        swap_curve: pubkey(10),
+        withdrawals_only: 0,
+        // carved from `_padding` or appended after the first release, the fixture is that
+        // release's layout so they read as zero, like in the pools it created
+        ..Default::default()
+    };
+    layouts::assert_zero_copy_layout("swap_pool", &pool);
+}
+
+#[test]
+pub fn test_curve_layouts() {
+    layouts::assert_layout("constant_product_curve", &ConstantProductCurve::default());
+    layouts::assert_layout(
+        "constant_price_curve",
+        &ConstantPriceCurve {
+            token_b_price: 1_000_000,
+            spread_bps: 25,
+            ..Default::default()
+        },
+    );
+    layouts::assert_layout(
+        "offset_curve",
+        &OffsetCurve {
+            token_b_offset: 500_000,
+            ..Default::default()
+        },
+    );
+    layouts::assert_layout(
+        "stable_curve",
+        &StableCurve {
+            amp: 100,
+            token_a_factor: 1,
+            token_b_factor: 1_000,
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use anchor_lang::prelude::{ErrorCode, Pubkey};
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::UpdatePoolConfig,
+    state::{SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup::{default_supply, new_keypair},
+    state,
+    types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_admin_transfer() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let new_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::propose_admin(&mut ctx, &pool, &new_admin.pubkey())
+        .await
+        .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.admin, pool.admin.pubkey());
+    assert_eq!(pool_state.pending_admin, new_admin.pubkey());
+
+    // the current admin keeps its rights until the transfer is accepted
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::WithdrawalsOnly,
+            UpdatePoolConfigValue::Bool(true),
+        ),
+    )
+    .await
+    .unwrap();
+
+    client::accept_admin(&mut ctx, &pool, &new_admin)
+        .await
+        .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.admin, new_admin.pubkey());
+    assert_eq!(pool_state.pending_admin, Pubkey::default());
+
+    assert_eq!(
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::WithdrawalsOnly,
+                UpdatePoolConfigValue::Bool(false),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        anchor_error!(ErrorCode::ConstraintHasOne)
+    );
+
+    let mut transferred_pool = pool.clone();
+    transferred_pool.admin.admin = new_admin;
+    client::update_pool_config(
+        &mut ctx,
+        &transferred_pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::WithdrawalsOnly,
+            UpdatePoolConfigValue::Bool(false),
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(!state::get_pool(&mut ctx, &pool).await.withdrawals_only());
+}
+
+#[tokio::test]
+pub async fn test_cancel_admin_transfer() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let new_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::propose_admin(&mut ctx, &pool, &new_admin.pubkey())
+        .await
+        .unwrap();
+    client::propose_admin(&mut ctx, &pool, &Pubkey::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client::accept_admin(&mut ctx, &pool, &new_admin)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidPendingAdmin)
+    );
+    assert_eq!(
+        state::get_pool(&mut ctx, &pool).await.admin,
+        pool.admin.pubkey()
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_admin_transfer() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let new_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::propose_admin(&mut ctx, &cloned_pool, &new_admin.pubkey())
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    client::propose_admin(&mut ctx, &pool, &new_admin.pubkey())
+        .await
+        .unwrap();
+
+    // wrong pending admin
+    {
+        let impostor = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::accept_admin(&mut ctx, &pool, &impostor)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::InvalidPendingAdmin)
+        );
+    }
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{AssertPoolPrice, Swap},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_assert_pool_price_guards_the_swap() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (2_000, 0)).await;
+
+    // price is 1.0, scaled by 1e9
+    client::swap_with_price_assertion(
+        &mut ctx,
+        &pool,
+        &user,
+        AssertPoolPrice::new(990_000_000, 1_010_000_000),
+        TradeDirection::AtoB,
+        Swap::new(1_000, 1, None),
+    )
+    .await
+    .unwrap();
+
+    // the pool moves between simulation and landing
+    let other_user = setup::new_pool_user(&mut ctx, &pool, (50_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &other_user,
+        TradeDirection::AtoB,
+        Swap::new(50_000, 1, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::swap_with_price_assertion(
+            &mut ctx,
+            &pool,
+            &user,
+            AssertPoolPrice::new(990_000_000, 1_010_000_000),
+            TradeDirection::AtoB,
+            Swap::new(1_000, 1, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PoolPriceOutOfRange)
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        1_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_assert_pool_price_invalid_bounds() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+
+    assert_eq!(
+        client::swap_with_price_assertion(
+            &mut ctx,
+            &pool,
+            &user,
+            AssertPoolPrice::new(1_010_000_000, 990_000_000),
+            TradeDirection::AtoB,
+            Swap::new(1_000, 1, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidPriceBounds)
+    );
+
+    // swapped vaults
+    let mut cloned_pool = pool.clone();
+    cloned_pool.token_a_vault = pool.token_b_vault;
+    cloned_pool.token_b_vault = pool.token_a_vault;
+    assert_eq!(
+        client::swap_with_price_assertion(
+            &mut ctx,
+            &cloned_pool,
+            &user,
+            AssertPoolPrice::new(990_000_000, 1_010_000_000),
+            TradeDirection::AtoB,
+            Swap::new(1_000, 1, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::IncorrectSwapAccount)
+    );
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    ix::{Initialize, Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_owner_fees_burned_into_pool_vault() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(1_000_000, 1_000_000);
+    let pool = setup::new_pool_accs(&mut ctx, SwapPairSpec::default(), &initial_supply).await;
+    client::initialize_pool(
+        &mut ctx,
+        &pool,
+        Initialize {
+            fees: Fees {
+                trade_fee_numerator: 1,
+                trade_fee_denominator: 100,
+                owner_trade_fee_numerator: 1,
+                owner_trade_fee_denominator: 100,
+                ..Default::default()
+            },
+            initial_supply,
+            curve_parameters: CurveUserParameters::ConstantProduct,
+            burn_owner_fees: true,
+            strict_token_program: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.burn_owner_fees, 1);
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // the owner fee stays with the LPs
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_010_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_burn_owner_fees_toggled_by_admin() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (20_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        100
+    );
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::BurnOwnerFees,
+            UpdatePoolConfigValue::Bool(true),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(state::get_pool(&mut ctx, &pool).await.burn_owner_fees, 1);
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // fees collected before the switch stay withdrawable, the new owner fee compounds for LPs
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        100
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_019_900
+    );
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Deposit, Swap, Withdraw},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{default_supply, new_keypair},
+    token_operations,
+    types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_close_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    let token_a_amount = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
+    let token_b_amount = token_operations::balance(&mut ctx, &pool.token_b_vault).await;
+    let token_a_fees = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    assert!(token_a_fees > 0);
+    let admin_token_a = token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await;
+    let admin_token_b = token_operations::balance(&mut ctx, &pool.admin.token_b_ata).await;
+
+    client::close_pool(&mut ctx, &pool).await.unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await,
+        admin_token_a + token_a_amount + token_a_fees
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_b_ata).await,
+        admin_token_b + token_b_amount
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await,
+        0
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        0
+    );
+    for closed in [
+        pool.pubkey(),
+        pool.curve,
+        pool.token_a_vault,
+        pool.token_b_vault,
+        pool.token_a_fees_vault,
+        pool.token_b_fees_vault,
+    ] {
+        assert!(ctx
+            .context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+pub async fn test_close_pool_with_other_liquidity_providers() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+
+    let lp = setup::new_pool_user(&mut ctx, &pool, (100_000, 100_000)).await;
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &lp,
+        Deposit::new(pool_token_supply / 10, 100_000, 100_000, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::close_pool(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::PoolNotClosable)
+    );
+
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &lp,
+        Withdraw::new(pool_token_supply / 10, 0, 0, false, None),
+    )
+    .await
+    .unwrap();
+    client::close_pool(&mut ctx, &pool).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_security_close_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::close_pool(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // swapped vaults
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.token_a_vault = pool.token_a_fees_vault;
+        cloned_pool.token_a_fees_vault = pool.token_a_vault;
+
+        assert_eq!(
+            client::close_pool(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectSwapAccount)
+        );
+    }
+}
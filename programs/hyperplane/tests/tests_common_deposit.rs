@@ -9,8 +9,11 @@ use hyperplane::{
     CurveUserParameters,
 };
 use solana_program_test::tokio::{self};
+use solana_sdk::pubkey::Pubkey;
 
-use crate::common::{fixtures, setup, setup::default_supply, state, types::SwapPairSpec};
+use crate::common::{
+    fixtures, setup, setup::default_supply, state, token_operations, types::SwapPairSpec,
+};
 
 #[tokio::test]
 pub async fn test_deposit_fails_with_withdrawal_only_mode() {
@@ -49,6 +52,7 @@ pub async fn test_deposit_fails_with_withdrawal_only_mode() {
                 pool_token_amount: 1,
                 maximum_token_a_amount: 1_000,
                 maximum_token_b_amount: 1_000,
+                deadline: None,
             },
         )
         .await
@@ -76,8 +80,45 @@ pub async fn test_deposit_fails_with_withdrawal_only_mode() {
             pool_token_amount: 1,
             maximum_token_a_amount: 1_000,
             maximum_token_b_amount: 1_000,
+            deadline: None,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_deposit_with_referrer() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    client::deposit_with_referrer(
+        &mut ctx,
+        &pool,
+        &user,
+        &Pubkey::new_unique(),
+        Deposit {
+            pool_token_amount: 1,
+            maximum_token_a_amount: 1_000,
+            maximum_token_b_amount: 1_000,
+            deadline: None,
         },
     )
     .await
     .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.pool_token_ata).await,
+        1
+    );
 }
@@ -54,6 +54,7 @@ pub async fn test_swap_fails_with_withdrawal_only_mode() {
             Swap {
                 amount_in: 50,
                 minimum_amount_out: 47,
+                deadline: None,
             },
         )
         .await
@@ -81,6 +82,68 @@ pub async fn test_swap_fails_with_withdrawal_only_mode() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 47,
+            deadline: None,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_swap_with_quiet_logs_still_enforces_slippage() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::QuietLogs,
+            UpdatePoolConfigValue::Bool(true),
+        ),
+    )
+    .await
+    .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.quiet_logs());
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (100, 0)).await;
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap {
+                amount_in: 50,
+                minimum_amount_out: 51,
+                deadline: None,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap {
+            amount_in: 50,
+            minimum_amount_out: 47,
+            deadline: None,
         },
     )
     .await
@@ -121,6 +184,7 @@ pub async fn test_swap_with_host_fees_less_than_one_rounds_down_to_zero() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
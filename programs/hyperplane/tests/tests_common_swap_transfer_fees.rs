@@ -46,6 +46,7 @@ pub async fn test_swap_a_to_b_with_a_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -100,6 +101,7 @@ pub async fn test_swap_b_to_a_with_b_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -154,6 +156,7 @@ pub async fn test_swap_a_to_b_with_b_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -208,6 +211,7 @@ pub async fn test_swap_b_to_a_with_a_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -262,6 +266,7 @@ pub async fn test_swap_a_to_b_with_a_and_b_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -316,6 +321,7 @@ pub async fn test_swap_b_to_a_with_a_and_b_transfer_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -370,6 +376,7 @@ pub async fn test_swap_a_to_b_with_a_and_b_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -424,6 +431,7 @@ pub async fn test_swap_b_to_a_with_a_and_b_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -478,6 +486,7 @@ pub async fn test_swap_a_to_b_with_a_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -532,6 +541,7 @@ pub async fn test_swap_b_to_a_with_b_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -586,6 +596,7 @@ pub async fn test_swap_a_to_b_with_b_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -640,6 +651,7 @@ pub async fn test_swap_b_to_a_with_a_transfer_fees_and_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -698,6 +710,7 @@ pub async fn test_swap_a_to_b_with_a_transfer_fees_and_owner_and_host_fees() {
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -761,6 +774,7 @@ pub async fn test_swap_b_to_a_with_b_transfer_fees_and_owner_and_host_fees() {
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -824,6 +838,7 @@ pub async fn test_swap_a_to_b_with_b_transfer_fees_and_owner_and_host_fees() {
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -887,6 +902,7 @@ pub async fn test_swap_b_to_a_with_a_transfer_fees_and_owner_and_host_fees() {
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -950,6 +966,7 @@ pub async fn test_swap_a_to_b_with_a_and_b_transfer_fees_and_owner_and_host_fees
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -1013,6 +1030,7 @@ pub async fn test_swap_b_to_a_with_a_and_b_transfer_fees_and_owner_and_host_fees
         Swap {
             amount_in: 50_000_000000,
             minimum_amount_out: 43_000_000000,
+            deadline: None,
         },
     )
     .await
@@ -1066,6 +1084,7 @@ pub async fn test_swap_a_to_b_with_a_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1114,6 +1133,7 @@ pub async fn test_swap_b_to_a_with_b_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1162,6 +1182,7 @@ pub async fn test_swap_a_to_b_with_b_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1210,6 +1231,7 @@ pub async fn test_swap_b_to_a_with_a_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1258,6 +1280,7 @@ pub async fn test_swap_a_to_b_with_a_and_b_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1306,6 +1329,7 @@ pub async fn test_swap_b_to_a_with_a_and_b_transfer_fees_and_no_trade_fees() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1358,6 +1382,7 @@ pub async fn test_swap_a_to_b_with_a_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1410,6 +1435,7 @@ pub async fn test_swap_b_to_a_with_b_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1462,6 +1488,7 @@ pub async fn test_swap_a_to_b_with_b_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1514,6 +1541,7 @@ pub async fn test_swap_b_to_a_with_a_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1566,6 +1594,7 @@ pub async fn test_swap_a_to_b_with_a_and_b_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
@@ -1618,6 +1647,7 @@ pub async fn test_swap_b_to_a_with_a_and_b_transfer_fees_and_only_owner_fee() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 44,
+            deadline: None,
         },
     )
     .await
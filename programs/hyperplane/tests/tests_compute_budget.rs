@@ -0,0 +1,91 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    ix,
+    ix::{compute_units, Swap},
+    state::SwapState,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use test_case::test_case;
+
+use crate::{
+    common::{fixtures, setup, setup::default_supply, state, types::SwapPairSpec},
+    send_tx,
+};
+
+#[test]
+pub fn test_compute_budget_instructions() {
+    assert_eq!(
+        ix::set_compute_unit_limit(150_000),
+        ComputeBudgetInstruction::set_compute_unit_limit(150_000)
+    );
+    assert_eq!(
+        ix::set_compute_unit_price(10_000),
+        ComputeBudgetInstruction::set_compute_unit_price(10_000)
+    );
+
+    let swap_ix = ix::set_compute_unit_price(1);
+    assert_eq!(
+        ix::with_compute_budget(swap_ix.clone(), 150_000, 0),
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(150_000),
+            swap_ix.clone()
+        ]
+    );
+    assert_eq!(
+        ix::with_compute_budget(swap_ix.clone(), 150_000, 10_000),
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(150_000),
+            ComputeBudgetInstruction::set_compute_unit_price(10_000),
+            swap_ix
+        ]
+    );
+}
+
+#[test_case(CurveUserParameters::ConstantProduct; "constant_product")]
+#[test_case(CurveUserParameters::ConstantPrice { token_b_price: 1, spread_bps: 0 }; "constant_price")]
+#[test_case(CurveUserParameters::Offset { token_b_offset: 1_000_000 }; "offset")]
+#[test_case(CurveUserParameters::Stable { amp: 100 }; "stable")]
+#[test_case(CurveUserParameters::Pegged { amp: 10, token_b_peg: 1_050_000_000 }; "pegged")]
+#[test_case(CurveUserParameters::ConstantProductWithBounds { min_price: 500_000_000, max_price: 2_000_000_000 }; "constant_product_with_bounds")]
+#[test_case(CurveUserParameters::Weighted { weight_a: 80, weight_b: 20 }; "weighted")]
+#[tokio::test]
+pub async fn test_swap_within_recommended_compute_units(curve_parameters: CurveUserParameters) {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        default_supply(),
+        SwapPairSpec::default(),
+        curve_parameters,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (50_000_000000, 0)).await;
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+
+    let ixs = ix::with_compute_budget(
+        client::instructions::swap(
+            &pool,
+            &user,
+            None,
+            None,
+            TradeDirection::AtoB,
+            Swap::new(50_000_000000, 0, None),
+        ),
+        compute_units::swap(pool_state.curve_type()),
+        0,
+    );
+    send_tx!(ctx, ixs, user.user.as_ref()).unwrap();
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    ix::{ProposePoolConfig, UpdatePoolConfig},
+    state::{PoolConfigHistory, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup::default_supply, state, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_config_changes_recorded_in_history() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    // not recorded, the history does not exist yet
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::MaxPrice,
+            UpdatePoolConfigValue::U64(2_000_000_000),
+        ),
+    )
+    .await
+    .unwrap();
+
+    client::initialize_config_history(&mut ctx, &pool)
+        .await
+        .unwrap();
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::MaxPrice,
+            UpdatePoolConfigValue::U64(1_500_000_000),
+        ),
+    )
+    .await
+    .unwrap();
+    client::propose_pool_config(
+        &mut ctx,
+        &pool,
+        ProposePoolConfig::new(
+            UpdatePoolConfigMode::WithdrawalsOnly,
+            UpdatePoolConfigValue::Bool(true),
+            0,
+        ),
+    )
+    .await
+    .unwrap();
+    client::execute_pool_config(&mut ctx, &pool).await.unwrap();
+
+    let (config_history, _bump) = seeds::pda::config_history_pda(&pool.pubkey());
+    let history = state::get::<PoolConfigHistory>(&mut ctx, config_history).await;
+    assert_eq!(history.pool, pool.pubkey());
+    assert_eq!(history.total_changes, 2);
+
+    let changes = history.recent_changes();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].mode, UpdatePoolConfigMode::MaxPrice as u64);
+    assert_eq!(changes[0].old_value, 2_000_000_000);
+    assert_eq!(changes[0].new_value, 1_500_000_000);
+    assert_eq!(changes[0].authority, pool.admin.pubkey());
+    assert_eq!(
+        changes[1].mode,
+        UpdatePoolConfigMode::WithdrawalsOnly as u64
+    );
+    assert_eq!(changes[1].old_value, 0);
+    assert_eq!(changes[1].new_value, 1);
+    assert!(changes[1].slot >= changes[0].slot);
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{base::CurveType, calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Initialize, Swap},
+    state::ConstantProductWithBoundsCurve,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, token_operations, types::SwapPairSpec};
+
+/// Price range of token A in token B, with 9 decimals
+const MIN_PRICE: u64 = 900_000_000;
+const MAX_PRICE: u64 = 1_100_000_000;
+
+#[tokio::test]
+pub async fn test_success_init_constant_product_with_bounds_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProductWithBounds {
+            min_price: MIN_PRICE,
+            max_price: MAX_PRICE,
+        },
+    )
+    .await;
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(
+        pool_state.curve_type,
+        CurveType::ConstantProductWithBounds as u64
+    );
+    assert_eq!(pool_state.swap_curve, pool.curve);
+
+    let curve = state::get::<ConstantProductWithBoundsCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.min_price, MIN_PRICE);
+    assert_eq!(curve.max_price, MAX_PRICE);
+}
+
+#[tokio::test]
+pub async fn test_init_constant_product_with_bounds_pool_fails_with_inverted_range() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(1_000_000, 1_000_000);
+    let pool = setup::new_pool_accs(&mut ctx, SwapPairSpec::default(), &initial_supply).await;
+
+    assert_eq!(
+        client::initialize_pool(
+            &mut ctx,
+            &pool,
+            Initialize {
+                fees: Fees::default(),
+                initial_supply,
+                curve_parameters: CurveUserParameters::ConstantProductWithBounds {
+                    min_price: MAX_PRICE,
+                    max_price: MIN_PRICE,
+                },
+                burn_owner_fees: false,
+                strict_token_program: false,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidCurve, 1)
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_within_the_range() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProductWithBounds {
+            min_price: MIN_PRICE,
+            max_price: MAX_PRICE,
+        },
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    let received = token_operations::balance(&mut ctx, &user.token_b_ata).await;
+    // less slippage than the 9_900_990 of a constant product pool, above the lower bound
+    assert!(received > 9_900_990);
+    assert!(received < 10_000_000);
+}
+
+#[tokio::test]
+pub async fn test_swap_past_the_range_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProductWithBounds {
+            min_price: MIN_PRICE,
+            max_price: MAX_PRICE,
+        },
+    )
+    .await;
+
+    // 1_200_000_000 token A buy more than the whole token B reserve
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_200_000_000, 0)).await;
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(1_200_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::EmptyReserves)
+    );
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use anchor_lang::{AccountSerialize, Discriminator};
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::Swap,
+    state::{Curve, StableCurve, SwapPool},
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::account::AccountSharedData;
+
+use crate::common::{fixtures, setup, setup::default_supply, state, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_corrupted_curve_rejected() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+
+    let account = ctx
+        .context
+        .banks_client
+        .get_account(pool.curve)
+        .await
+        .unwrap()
+        .unwrap();
+    let curve = state::get_stable_curve(&mut ctx, &pool).await;
+    assert_eq!(curve.checksum, Curve::checksum(&account.data));
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(500, 1, None),
+    )
+    .await
+    .unwrap();
+
+    // bump the amp without updating the checksum
+    let corrupted = StableCurve {
+        amp: curve.amp + 1,
+        ..curve
+    };
+    let mut corrupted_account = AccountSharedData::from(account);
+    let mut data = vec![];
+    corrupted.try_serialize(&mut data).unwrap();
+    corrupted_account.set_data(data);
+    ctx.context.set_account(&pool.curve, &corrupted_account);
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(400, 1, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::CurveCorrupted)
+    );
+}
+
+#[tokio::test]
+pub async fn test_write_curve_checksum_of_legacy_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.curve_checksum_written());
+
+    // a pool created before the checksum, whose curve has none
+    let legacy_pool = SwapPool {
+        curve_checksum_written: 0,
+        ..pool_state
+    };
+    let mut pool_account = AccountSharedData::from(
+        ctx.context
+            .banks_client
+            .get_account(pool.pubkey())
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    pool_account.set_data(
+        [
+            &SwapPool::discriminator()[..],
+            bytemuck::bytes_of(&legacy_pool),
+        ]
+        .concat(),
+    );
+    ctx.context.set_account(&pool.pubkey(), &pool_account);
+
+    let legacy_curve = StableCurve {
+        checksum: 0,
+        ..state::get_stable_curve(&mut ctx, &pool).await
+    };
+    let mut curve_account = AccountSharedData::from(
+        ctx.context
+            .banks_client
+            .get_account(pool.curve)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    let mut data = vec![];
+    legacy_curve.try_serialize(&mut data).unwrap();
+    curve_account.set_data(data);
+    ctx.context.set_account(&pool.curve, &curve_account);
+
+    // the curve is not checked until its checksum is written
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(500, 1, None),
+    )
+    .await
+    .unwrap();
+
+    client::write_curve_checksum(&mut ctx, &pool).await.unwrap();
+
+    assert!(state::get_pool(&mut ctx, &pool)
+        .await
+        .curve_checksum_written());
+    let account = ctx
+        .context
+        .banks_client
+        .get_account(pool.curve)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state::get_stable_curve(&mut ctx, &pool).await,
+        StableCurve {
+            checksum: Curve::checksum(&account.data),
+            ..legacy_curve
+        }
+    );
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(400, 1, None),
+    )
+    .await
+    .unwrap();
+
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 1).unwrap();
+    assert_eq!(
+        client::write_curve_checksum(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::CurveChecksumWritten)
+    );
+}
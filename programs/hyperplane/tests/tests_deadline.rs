@@ -0,0 +1,104 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Deposit, Swap, Withdraw},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::sysvar::clock::Clock;
+
+use crate::common::{
+    fixtures, setup,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
+
+#[tokio::test]
+pub async fn test_deadline() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000_000, 10_000_000)).await;
+    let now = ctx
+        .context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, Some(now - 1)),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::DeadlineExceeded)
+    );
+    assert_eq!(
+        client::deposit(
+            &mut ctx,
+            &pool,
+            &user,
+            Deposit::new(1_000, 2_000, 2_000, Some(now - 1))
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::DeadlineExceeded)
+    );
+    assert_eq!(
+        client::withdraw(
+            &mut ctx,
+            &pool,
+            &admin,
+            Withdraw::new(1_000, 0, 0, false, Some(now - 1))
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::DeadlineExceeded)
+    );
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, Some(now + 60)),
+    )
+    .await
+    .unwrap();
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &user,
+        Deposit::new(1_000, 2_000, 2_000, Some(now + 60)),
+    )
+    .await
+    .unwrap();
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &admin,
+        Withdraw::new(1_000, 0, 0, false, Some(now + 60)),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Swap, WithdrawFees},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 1,
+        trade_fee_denominator: 100,
+        owner_trade_fee_numerator: 1,
+        owner_trade_fee_denominator: 100,
+        owner_withdraw_fee_numerator: 0,
+        owner_withdraw_fee_denominator: 0,
+        host_fee_numerator: 20,
+        host_fee_denominator: 100,
+    }
+}
+
+async fn swap_a_to_b(ctx: &mut TestContext, pool: &SwapPoolAccounts) {
+    let user = setup::new_pool_user(ctx, pool, (1_000_000, 0)).await;
+    client::swap(
+        ctx,
+        pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_host_fees_of_swaps_without_host_accrue_to_default_recipient() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        fees(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let recipient = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_default_host_fees(&mut ctx, &pool, &recipient.pubkey())
+        .await
+        .unwrap();
+
+    swap_a_to_b(&mut ctx, &pool).await;
+
+    // owner fee of 1% on the 1_000_000 swapped, a fifth of it is the host fee
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        10_000
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.default_host_fees_a, 2_000);
+    assert_eq!(pool_state.default_host_fees_b, 0);
+
+    // the admin can only withdraw the owner's share
+    let initial_admin_balance = token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await;
+    client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(u64::MAX))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await,
+        initial_admin_balance + 8_000
+    );
+
+    client::claim_default_host_fees(&mut ctx, &pool, &recipient, AorB::A)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &recipient.token_a_ata).await,
+        2_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.default_host_fees_a, 0);
+
+    assert_eq!(
+        client::claim_default_host_fees(&mut ctx, &pool, &recipient, AorB::A)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
+
+#[tokio::test]
+pub async fn test_host_fees_before_default_recipient_is_set_stay_with_owner() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        fees(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    swap_a_to_b(&mut ctx, &pool).await;
+
+    let recipient = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_default_host_fees(&mut ctx, &pool, &recipient.pubkey())
+        .await
+        .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.default_host_fees_a, 0);
+
+    client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(u64::MAX))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+
+    // host fees accrued after a change of recipient are claimed to the new one
+    let new_recipient = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::update_default_host_fees(&mut ctx, &pool, &new_recipient.pubkey())
+        .await
+        .unwrap();
+    swap_a_to_b(&mut ctx, &pool).await;
+
+    assert!(
+        client::claim_default_host_fees(&mut ctx, &pool, &recipient, AorB::A)
+            .await
+            .is_err()
+    );
+    client::claim_default_host_fees(&mut ctx, &pool, &new_recipient, AorB::A)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &new_recipient.token_a_ata).await,
+        2_000
+    );
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{DepositBothExactIn, UpdatePoolConfig},
+    state::{PoolOperation, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_deposit_both_exact_in_rebalances_the_excess() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 3_000_000)).await;
+    // 1_000_000 of each token are balanced, the 2_000_000 of token B left mint the rest
+    let balanced_pool_tokens = 1_000_000 * pool_token_supply / 1_000_000_000;
+
+    assert_eq!(
+        client::deposit_both_exact_in(
+            &mut ctx,
+            &pool,
+            &user,
+            DepositBothExactIn::new(1_000_000, 3_000_000, 3 * balanced_pool_tokens),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::deposit_both_exact_in(
+        &mut ctx,
+        &pool,
+        &user,
+        DepositBothExactIn::new(1_000_000, 3_000_000, balanced_pool_tokens),
+    )
+    .await
+    .unwrap();
+
+    // the user gives away exactly the amounts
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        0
+    );
+    // 1% trade and owner fees on the 1_000_000 of token B implicitly swapped
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        10_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_001_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_002_990_000
+    );
+    // the remainder is worth a bit less than 1% of the pool after the balanced part
+    let pool_tokens = token_operations::balance(&mut ctx, &user.pool_token_ata).await;
+    assert!(pool_tokens > balanced_pool_tokens + balanced_pool_tokens * 98 / 100);
+    assert!(pool_tokens < 2 * balanced_pool_tokens);
+}
+
+#[tokio::test]
+pub async fn test_deposit_both_exact_in_balanced_amounts_pay_no_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 2_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 2_000_000)).await;
+
+    client::deposit_both_exact_in(
+        &mut ctx,
+        &pool,
+        &user,
+        DepositBothExactIn::new(1_000_000, 2_000_000, 0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.pool_token_ata).await,
+        1_000_000 * pool_token_supply / 1_000_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_deposit_both_exact_in_remainder_is_single_sided() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (3_000_000, 1_000_000)).await;
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::Status,
+            UpdatePoolConfigValue::U64(PoolOperation::SingleSided.flag()),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::deposit_both_exact_in(
+            &mut ctx,
+            &pool,
+            &user,
+            DepositBothExactIn::new(3_000_000, 1_000_000, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+
+    // balanced amounts do not need the single sided deposit
+    client::deposit_both_exact_in(
+        &mut ctx,
+        &pool,
+        &user,
+        DepositBothExactIn::new(1_000_000, 1_000_000, 0),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        2_000_000
+    );
+}
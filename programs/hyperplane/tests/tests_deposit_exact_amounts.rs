@@ -0,0 +1,67 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees, error::SwapError, ix::DepositExactAmounts, CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, setup::default_supply, token_operations, types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_deposit_exact_amounts_limited_by_scarcer_token() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+    let token_a_vault_balance = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 3_000_000)).await;
+    // the vaults are balanced, only 1_000_000 of token B is needed
+    let expected_pool_tokens = 1_000_000 * pool_token_supply / token_a_vault_balance;
+
+    assert_eq!(
+        client::deposit_exact_amounts(
+            &mut ctx,
+            &pool,
+            &user,
+            DepositExactAmounts::new(1_000_000, 3_000_000, expected_pool_tokens + 1),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::deposit_exact_amounts(
+        &mut ctx,
+        &pool,
+        &user,
+        DepositExactAmounts::new(1_000_000, 3_000_000, expected_pool_tokens),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.pool_token_ata).await,
+        expected_pool_tokens
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        2_000_000
+    );
+}
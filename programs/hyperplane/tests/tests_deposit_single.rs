@@ -0,0 +1,146 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::AorB, fees::Fees},
+    error::SwapError,
+    ix::DepositSingle,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_deposit_single_token_a() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000_000, 0)).await;
+
+    // supply * (sqrt(1.01) - 1) = 4_987_562.1
+    assert_eq!(
+        client::deposit_single(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            DepositSingle::new(10_000_000, 4_987_563),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::deposit_single(
+        &mut ctx,
+        &pool,
+        &user,
+        AorB::A,
+        DepositSingle::new(10_000_000, 4_987_000),
+    )
+    .await
+    .unwrap();
+
+    let pool_tokens = token_operations::balance(&mut ctx, &user.pool_token_ata).await;
+    assert!((4_987_000..=4_987_562).contains(&pool_tokens));
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_010_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_000_000_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_deposit_single_token_b_pays_fees_on_half() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 10_000_000)).await;
+
+    client::deposit_single(
+        &mut ctx,
+        &pool,
+        &user,
+        AorB::B,
+        DepositSingle::new(10_000_000, 0),
+    )
+    .await
+    .unwrap();
+
+    // 1% trade and owner fees on the 5_000_000 implicitly swapped, the trade fee stays in the
+    // vault without minting pool tokens for it
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        50_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_009_950_000
+    );
+    // supply * (sqrt(1.0099) - 1) = 4_937_809.0
+    let pool_tokens = token_operations::balance(&mut ctx, &user.pool_token_ata).await;
+    assert!((4_937_000..=4_937_809).contains(&pool_tokens));
+}
+
+#[tokio::test]
+pub async fn test_deposit_single_fails_on_offset_curve() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::Offset {
+            token_b_offset: 1_000_000,
+        },
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+
+    assert_eq!(
+        client::deposit_single(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            DepositSingle::new(1_000, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::UnsupportedCurveOperation)
+    );
+}
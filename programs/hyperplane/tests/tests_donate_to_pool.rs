@@ -0,0 +1,125 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::AorB, fees::Fees},
+    error::SwapError,
+    ix::{Deposit, FlashBorrow},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{client::instructions, fixtures, setup, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_donation_raises_the_pool_token_value() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+
+    let donor = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    client::donate_to_pool(&mut ctx, &pool, &donor, 1_000, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &donor.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_001_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_000_000
+    );
+    // no pool tokens were minted for the donation
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        pool_token_supply
+    );
+
+    // the next depositor pays for its share of the donation
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_100, 100_000)).await;
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &user,
+        Deposit {
+            pool_token_amount: pool_token_supply / 10,
+            maximum_token_a_amount: 100_100,
+            maximum_token_b_amount: 100_000,
+            deadline: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_donation_of_nothing_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let donor = setup::new_pool_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    assert_eq!(
+        client::donate_to_pool(&mut ctx, &pool, &donor, 0, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
+
+#[tokio::test]
+pub async fn test_pool_rejects_donations_during_flash_loan() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 1_000)).await;
+    assert_eq!(
+        client::flash_loan(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            FlashBorrow::new(100_000),
+            vec![instructions::donate_to_pool(&pool, &user, 0, 1_000)],
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::FlashLoanActive, 1)
+    );
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    event,
+    ix::{Swap, UpdatePoolConfig},
+    state::{SwapPool, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn swap(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    amount_in: u64,
+) -> event::Swap {
+    let processed = client::process_with_metadata(
+        ctx,
+        client::instructions::swap(
+            pool,
+            user,
+            None,
+            None,
+            TradeDirection::AtoB,
+            Swap::new(amount_in, 0, None),
+        ),
+        &user.user,
+    )
+    .await;
+    processed.result.unwrap();
+    let return_data = processed.metadata.unwrap().return_data.unwrap();
+    event::Swap::deserialize(&mut &return_data.data[..]).unwrap()
+}
+
+async fn set_fee_config(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    mode: UpdatePoolConfigMode,
+    value: u64,
+) -> Result<(), solana_program_test::BanksClientError> {
+    client::update_pool_config(
+        ctx,
+        pool,
+        UpdatePoolConfig::new(mode, UpdatePoolConfigValue::U64(value)),
+    )
+    .await
+}
+
+#[tokio::test]
+pub async fn test_dynamic_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            ..Default::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 0)).await;
+
+    // the cap must be raised above the trade fee first
+    assert_eq!(
+        set_fee_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfigMode::DynamicFeeNumerator,
+            1
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidFee)
+    );
+    set_fee_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfigMode::MaxDynamicTradeFeeNumerator,
+        100,
+    )
+    .await
+    .unwrap();
+    set_fee_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfigMode::DynamicFeeNumerator,
+        1,
+    )
+    .await
+    .unwrap();
+
+    // the first swap sets the reference price, the second one moves it
+    let first = swap(&mut ctx, &pool, &user, 10_000).await;
+    assert_eq!(first.trade_fee_numerator, 25);
+    assert_eq!(first.trade_fee, 25);
+    assert_eq!(state::get_pool(&mut ctx, &pool).await.volatility_bps, 0);
+
+    let second = swap(&mut ctx, &pool, &user, 10_001).await;
+    assert_eq!(second.trade_fee_numerator, 25);
+    let volatility_bps = state::get_pool(&mut ctx, &pool).await.volatility_bps;
+    assert!(volatility_bps > 75);
+
+    // raised up to the cap
+    let third = swap(&mut ctx, &pool, &user, 20_000).await;
+    assert_eq!(third.trade_fee_numerator, 100);
+    assert_eq!(third.trade_fee, 200);
+
+    // back to the base fee once the volatility decayed
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context
+        .warp_to_slot(slot + 16 * SwapPool::VOLATILITY_HALF_LIFE_SLOTS)
+        .unwrap();
+    let fourth = swap(&mut ctx, &pool, &user, 10_003).await;
+    assert_eq!(fourth.trade_fee_numerator, 25);
+
+    // disabled again
+    set_fee_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfigMode::DynamicFeeNumerator,
+        0,
+    )
+    .await
+    .unwrap();
+    let fifth = swap(&mut ctx, &pool, &user, 10_004).await;
+    assert_eq!(fifth.trade_fee_numerator, 25);
+}
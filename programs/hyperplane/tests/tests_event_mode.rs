@@ -0,0 +1,294 @@
+mod common;
+
+use anchor_lang::{AnchorDeserialize, Discriminator, __private::base64};
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    event, ix,
+    ix::{Swap, UpdatePoolConfig},
+    state::{
+        EventMode, PoolOperation, SwapPool, SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue,
+    },
+    CurveUserParameters,
+};
+use solana_program_test::{tokio, BanksTransactionResultWithMetadata};
+use solana_sdk::{
+    account::AccountSharedData, instruction::InstructionError, transaction::TransactionError,
+};
+
+use crate::common::{
+    fixtures, setup,
+    setup::default_supply,
+    state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+const PROGRAM_DATA_LOG: &str = "Program data: ";
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await
+}
+
+async fn set_event_mode(ctx: &mut TestContext, pool: &SwapPoolAccounts, event_mode: EventMode) {
+    client::update_pool_config(
+        ctx,
+        pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::EventMode,
+            UpdatePoolConfigValue::U64(event_mode as u64),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(state::get_pool(ctx, pool).await.event_mode(), event_mode);
+}
+
+fn swap_logs(processed: &BanksTransactionResultWithMetadata) -> &[String] {
+    &processed.metadata.as_ref().unwrap().log_messages
+}
+
+fn return_data_event(processed: &BanksTransactionResultWithMetadata) -> event::Swap {
+    let return_data = processed
+        .metadata
+        .as_ref()
+        .unwrap()
+        .return_data
+        .as_ref()
+        .unwrap();
+    assert_eq!(return_data.program_id, hyperplane::id());
+    event::Swap::deserialize(&mut &return_data.data[..]).unwrap()
+}
+
+#[tokio::test]
+pub async fn test_log_event_mode_by_default() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    assert_eq!(
+        state::get_pool(&mut ctx, &pool).await.event_mode(),
+        EventMode::Log
+    );
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+
+    let processed = client::process_with_metadata(
+        &mut ctx,
+        client::instructions::swap(
+            &pool,
+            &user,
+            None,
+            None,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        ),
+        &user.user,
+    )
+    .await;
+    processed.result.unwrap();
+
+    let returned = return_data_event(&processed);
+    assert_eq!(returned.token_in_amount, 1_000);
+    assert_eq!(
+        returned.token_a_reserve_after,
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await
+    );
+    assert_eq!(
+        returned.token_b_reserve_after,
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await
+    );
+    let logged: Vec<event::Swap> = swap_logs(&processed)
+        .iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_LOG))
+        .map(|data| base64::decode(data).unwrap())
+        .filter_map(|data| {
+            data.strip_prefix(&event::Swap::discriminator())
+                .map(|data| event::Swap::deserialize(&mut &data[..]).unwrap())
+        })
+        .collect();
+    assert_eq!(logged, vec![returned]);
+}
+
+#[tokio::test]
+pub async fn test_return_data_event_mode_skips_the_log() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    set_event_mode(&mut ctx, &pool, EventMode::ReturnData).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+
+    let processed = client::process_with_metadata(
+        &mut ctx,
+        client::instructions::swap(
+            &pool,
+            &user,
+            None,
+            None,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        ),
+        &user.user,
+    )
+    .await;
+    processed.result.unwrap();
+
+    assert_eq!(return_data_event(&processed).token_in_amount, 1_000);
+    assert!(!swap_logs(&processed)
+        .iter()
+        .any(|log| log.starts_with(PROGRAM_DATA_LOG)));
+}
+
+#[tokio::test]
+pub async fn test_cpi_event_mode_invokes_emit_event() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    set_event_mode(&mut ctx, &pool, EventMode::Cpi).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (2_000, 0)).await;
+
+    let swap_ix = client::instructions::swap(
+        &pool,
+        &user,
+        None,
+        None,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    );
+    assert_eq!(
+        client::process_with_metadata(&mut ctx, swap_ix.clone(), &user.user)
+            .await
+            .result
+            .unwrap_err(),
+        hyperplane_error!(SwapError::MissingEventAuthority)
+    );
+
+    let processed = client::process_with_metadata(
+        &mut ctx,
+        ix::with_event_cpi_accounts(swap_ix, &hyperplane::id()),
+        &user.user,
+    )
+    .await;
+    processed.result.unwrap();
+
+    assert_eq!(return_data_event(&processed).token_in_amount, 1_000);
+    let logs = swap_logs(&processed);
+    assert!(logs.contains(&format!("Program {} invoke [2]", hyperplane::id())));
+    assert!(!logs.iter().any(|log| log.starts_with(PROGRAM_DATA_LOG)));
+}
+
+#[tokio::test]
+pub async fn test_cpi_event_mode_pool_status_changed() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    set_event_mode(&mut ctx, &pool, EventMode::Cpi).await;
+
+    let pause_ix = client::instructions::update_pool_config(
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::Status,
+            UpdatePoolConfigValue::U64(PoolOperation::Swaps.flag()),
+        ),
+    );
+    assert_eq!(
+        client::process_with_metadata(&mut ctx, pause_ix.clone(), &pool.admin.admin)
+            .await
+            .result
+            .unwrap_err(),
+        hyperplane_error!(SwapError::MissingEventAuthority)
+    );
+
+    let processed = client::process_with_metadata(
+        &mut ctx,
+        ix::with_event_cpi_accounts(pause_ix, &hyperplane::id()),
+        &pool.admin.admin,
+    )
+    .await;
+    processed.result.unwrap();
+
+    assert_eq!(
+        state::get_pool(&mut ctx, &pool).await.status,
+        PoolOperation::Swaps.flag()
+    );
+    let logs = swap_logs(&processed);
+    assert!(logs.contains(&format!("Program {} invoke [2]", hyperplane::id())));
+    assert!(!logs
+        .iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_LOG))
+        .map(|data| base64::decode(data).unwrap())
+        .any(|data| data.starts_with(&event::PoolStatusChanged::discriminator())));
+}
+
+#[tokio::test]
+pub async fn test_update_event_mode_rejects_unknown_mode() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+
+    assert_eq!(
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::EventMode,
+                UpdatePoolConfigValue::U64(3),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+pub async fn test_extend_legacy_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+
+    // truncate the pool to its size before the padding was added, the curve capabilities were
+    // carved from the padding after that so they read as zero
+    assert!(pool_state.allows_deposits() && pool_state.allows_single_sided_deposits());
+    let legacy_pool = SwapPool {
+        allows_deposits: 0,
+        allows_single_sided_deposits: 0,
+        ..pool_state
+    };
+    let legacy_data = [
+        &SwapPool::discriminator()[..],
+        bytemuck::bytes_of(&legacy_pool),
+    ]
+    .concat();
+    let mut legacy_account = AccountSharedData::new(
+        ctx.rent.minimum_balance(SwapPool::LEGACY_LEN),
+        SwapPool::LEGACY_LEN,
+        &hyperplane::id(),
+    );
+    legacy_account.set_data(legacy_data[..SwapPool::LEGACY_LEN].to_vec());
+    ctx.context.set_account(&pool.pubkey(), &legacy_account);
+
+    client::extend_pool(&mut ctx, &pool).await.unwrap();
+
+    let account = ctx
+        .context
+        .banks_client
+        .get_account(pool.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), SwapPool::LEN);
+    assert_eq!(account.lamports, ctx.rent.minimum_balance(SwapPool::LEN));
+    assert_eq!(state::get_pool(&mut ctx, &pool).await, pool_state);
+
+    // extending a pool at the current size is a no-op
+    client::extend_pool(&mut ctx, &pool).await.unwrap();
+    assert_eq!(state::get_pool(&mut ctx, &pool).await, pool_state);
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{InitializeWithFeeTier, SetFeeTier},
+    state::{FeeTierRegistry, SwapPool},
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::{
+    tokio::{self},
+    BanksClientError,
+};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures::Sol,
+    setup,
+    setup::new_keypair,
+    state,
+    types::{SwapPairSpec, TestContext},
+};
+
+fn tier_fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 30,
+        trade_fee_denominator: 10_000,
+        owner_trade_fee_numerator: 5,
+        owner_trade_fee_denominator: 10_000,
+        ..Default::default()
+    }
+}
+
+fn initialize(fee_tier: u16) -> InitializeWithFeeTier {
+    InitializeWithFeeTier {
+        fee_tier,
+        curve_parameters: CurveUserParameters::ConstantProduct,
+        initial_supply: InitialSupply::new(1_000_000, 1_000_000),
+        burn_owner_fees: false,
+        strict_token_program: false,
+    }
+}
+
+async fn new_pool_with_fee_tier(
+    ctx: &mut TestContext,
+    fee_tier: u16,
+) -> Result<SwapPool, BanksClientError> {
+    let pool = setup::new_pool_accs(
+        ctx,
+        SwapPairSpec::default(),
+        &InitialSupply::new(1_000_000, 1_000_000),
+    )
+    .await;
+    client::initialize_pool_with_fee_tier(ctx, &pool, initialize(fee_tier)).await?;
+    Ok(state::get_pool(ctx, &pool).await)
+}
+
+#[tokio::test]
+pub async fn test_initialize_pool_with_fee_tier() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let admin = new_keypair(&mut ctx, Sol::one()).await;
+    client::initialize_fee_tier_registry(&mut ctx, &admin)
+        .await
+        .unwrap();
+    client::set_fee_tier(&mut ctx, &admin, SetFeeTier::new(0, tier_fees()))
+        .await
+        .unwrap();
+
+    let (registry, _bump) = seeds::pda::fee_tier_registry_pda();
+    let registry = state::get::<FeeTierRegistry>(&mut ctx, registry).await;
+    assert_eq!(registry.admin, admin.pubkey());
+    assert_eq!(registry.tiers_len, 1);
+
+    let pool = new_pool_with_fee_tier(&mut ctx, 0).await.unwrap();
+    assert_eq!(pool.fees, tier_fees());
+
+    // pools keep the fees they were created with when the tier changes
+    client::set_fee_tier(
+        &mut ctx,
+        &admin,
+        SetFeeTier::new(
+            0,
+            Fees {
+                trade_fee_numerator: 100,
+                ..tier_fees()
+            },
+        ),
+    )
+    .await
+    .unwrap();
+    let pool_after = new_pool_with_fee_tier(&mut ctx, 0).await.unwrap();
+    assert_eq!(pool_after.fees.trade_fee_numerator, 100);
+    assert_eq!(pool.fees, tier_fees());
+}
+
+#[tokio::test]
+pub async fn test_initialize_pool_with_unset_fee_tier() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let admin = new_keypair(&mut ctx, Sol::one()).await;
+    client::initialize_fee_tier_registry(&mut ctx, &admin)
+        .await
+        .unwrap();
+    client::set_fee_tier(&mut ctx, &admin, SetFeeTier::new(0, tier_fees()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        new_pool_with_fee_tier(&mut ctx, 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidFeeTier)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_set_fee_tier() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let admin = new_keypair(&mut ctx, Sol::one()).await;
+    client::initialize_fee_tier_registry(&mut ctx, &admin)
+        .await
+        .unwrap();
+
+    // wrong admin
+    {
+        let wrong_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::set_fee_tier(&mut ctx, &wrong_admin, SetFeeTier::new(0, tier_fees()))
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // invalid fees
+    assert_eq!(
+        client::set_fee_tier(
+            &mut ctx,
+            &admin,
+            SetFeeTier::new(
+                0,
+                Fees {
+                    trade_fee_numerator: 2,
+                    trade_fee_denominator: 1,
+                    ..Default::default()
+                },
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidFee)
+    );
+
+    // tiers are appended in order
+    assert_eq!(
+        client::set_fee_tier(&mut ctx, &admin, SetFeeTier::new(1, tier_fees()))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidFeeTier)
+    );
+
+    // already initialized
+    {
+        let other_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert!(client::initialize_fee_tier_registry(&mut ctx, &other_admin)
+            .await
+            .is_err());
+    }
+}
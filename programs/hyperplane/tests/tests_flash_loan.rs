@@ -0,0 +1,178 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{FlashBorrow, Swap, UpdatePoolConfig, WithdrawFees},
+    state::{SwapPool, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::{
+    common::{
+        client::instructions,
+        fixtures, setup, state, token_operations,
+        types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+    },
+    send_tx,
+};
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    client::update_pool_config(
+        ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::FlashFeeBps,
+            UpdatePoolConfigValue::U64(9),
+        ),
+    )
+    .await
+    .unwrap();
+    pool
+}
+
+#[tokio::test]
+pub async fn test_flash_loan_repays_the_fee_to_the_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+
+    // the user only holds the fee, the loan itself is repaid with the borrowed tokens
+    let user = setup::new_pool_user(&mut ctx, &pool, (90, 0)).await;
+    client::flash_loan(
+        &mut ctx,
+        &pool,
+        &user,
+        AorB::A,
+        FlashBorrow::new(100_000),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_000_090
+    );
+    let pool_state = state::get::<SwapPool>(&mut ctx, pool.pubkey()).await;
+    assert!(!pool_state.flash_loan_active());
+}
+
+#[tokio::test]
+pub async fn test_flash_borrow_fails_without_repay() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    assert_eq!(
+        send_tx!(
+            ctx,
+            [instructions::flash_borrow(
+                &pool,
+                &user,
+                AorB::A,
+                FlashBorrow::new(100_000)
+            )],
+            user.user.as_ref()
+        )
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::FlashRepayMissing)
+    );
+
+    // repaying the other token does not repay the loan
+    assert_eq!(
+        send_tx!(
+            ctx,
+            [
+                instructions::flash_borrow(&pool, &user, AorB::A, FlashBorrow::new(100_000)),
+                instructions::flash_repay(&pool, &user, AorB::B)
+            ],
+            user.user.as_ref()
+        )
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::FlashLoanNotActive, 1)
+    );
+}
+
+#[tokio::test]
+pub async fn test_pool_rejects_swaps_during_flash_loan() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    assert_eq!(
+        client::flash_loan(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            FlashBorrow::new(900_000),
+            vec![instructions::swap(
+                &pool,
+                &user,
+                None,
+                None,
+                TradeDirection::BtoA,
+                Swap::new(1_000, 0, None),
+            )],
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::FlashLoanActive, 1)
+    );
+}
+
+#[tokio::test]
+pub async fn test_pool_rejects_fee_withdrawals_during_flash_loan() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+
+    // the admin borrows so that it can sign the admin instructions of the same transaction
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    for between in [
+        instructions::withdraw_fees(&pool, AorB::A, WithdrawFees::new(1)),
+        instructions::skim_lamports(&pool, &admin.pubkey()),
+    ] {
+        assert_eq!(
+            client::flash_loan(
+                &mut ctx,
+                &pool,
+                &admin,
+                AorB::A,
+                FlashBorrow::new(900_000),
+                vec![between],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::FlashLoanActive, 1)
+        );
+    }
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use anchor_lang::prelude::{ErrorCode, Pubkey};
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{PoolOperation, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{default_supply, new_keypair},
+    state,
+    types::SwapPairSpec,
+};
+
+fn status(paused: &[PoolOperation]) -> UpdatePoolConfig {
+    let status = paused
+        .iter()
+        .fold(0, |status, operation| status | operation.flag());
+    UpdatePoolConfig::new(
+        UpdatePoolConfigMode::Status,
+        UpdatePoolConfigValue::U64(status),
+    )
+}
+
+fn withdrawals_only(enabled: bool) -> UpdatePoolConfig {
+    UpdatePoolConfig::new(
+        UpdatePoolConfigMode::WithdrawalsOnly,
+        UpdatePoolConfigValue::Bool(enabled),
+    )
+}
+
+#[tokio::test]
+pub async fn test_guardian_pauses_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let guardian = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::set_guardian(&mut ctx, &pool, &guardian.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        state::get_pool(&mut ctx, &pool).await.guardian,
+        guardian.pubkey()
+    );
+
+    client::guardian_pause_pool(&mut ctx, &pool, &guardian, status(&[PoolOperation::Swaps]))
+        .await
+        .unwrap();
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+
+    // more operations can be paused, none resumed
+    client::guardian_pause_pool(
+        &mut ctx,
+        &pool,
+        &guardian,
+        status(&[PoolOperation::Swaps, PoolOperation::Deposits]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        client::guardian_pause_pool(
+            &mut ctx,
+            &pool,
+            &guardian,
+            status(&[PoolOperation::Deposits]),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::GuardianCannotUnpause)
+    );
+
+    client::guardian_pause_pool(&mut ctx, &pool, &guardian, withdrawals_only(true))
+        .await
+        .unwrap();
+    assert_eq!(
+        client::guardian_pause_pool(&mut ctx, &pool, &guardian, withdrawals_only(false))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::GuardianCannotUnpause)
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.withdrawals_only, 1);
+    assert!(pool_state.is_paused(PoolOperation::Swaps));
+    assert!(pool_state.is_paused(PoolOperation::Deposits));
+
+    // only the admin resumes the pool
+    client::update_pool_config(&mut ctx, &pool, withdrawals_only(false))
+        .await
+        .unwrap();
+    client::update_pool_config(&mut ctx, &pool, status(&[]))
+        .await
+        .unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_001, 0, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_security_guardian() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let guardian = new_keypair(&mut ctx, Sol::one()).await;
+
+    // no guardian set
+    assert_eq!(
+        client::guardian_pause_pool(&mut ctx, &pool, &guardian, withdrawals_only(true))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        anchor_error!(ErrorCode::ConstraintHasOne)
+    );
+
+    client::set_guardian(&mut ctx, &pool, &guardian.pubkey())
+        .await
+        .unwrap();
+
+    // wrong guardian
+    {
+        let wrong_guardian = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::guardian_pause_pool(&mut ctx, &pool, &wrong_guardian, withdrawals_only(true))
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // config changes which do not pause the pool
+    assert_eq!(
+        client::guardian_pause_pool(
+            &mut ctx,
+            &pool,
+            &guardian,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::PermissionedSwaps,
+                UpdatePoolConfigValue::Bool(true),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::GuardianCannotUnpause)
+    );
+
+    // removed by the admin
+    client::set_guardian(&mut ctx, &pool, &Pubkey::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        client::guardian_pause_pool(&mut ctx, &pool, &guardian, withdrawals_only(true))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        anchor_error!(ErrorCode::ConstraintHasOne)
+    );
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 1,
+        trade_fee_denominator: 100,
+        owner_trade_fee_numerator: 1,
+        owner_trade_fee_denominator: 100,
+        host_fee_numerator: 20,
+        host_fee_denominator: 100,
+        ..Default::default()
+    }
+}
+
+async fn new_pool(ctx: &mut TestContext, host_fee_in_destination_token: bool) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        fees(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    client::update_pool_config(
+        ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::HostFeeInDestinationToken,
+            UpdatePoolConfigValue::Bool(host_fee_in_destination_token),
+        ),
+    )
+    .await
+    .unwrap();
+    pool
+}
+
+#[tokio::test]
+pub async fn test_host_fee_carved_out_of_destination_tokens() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, true).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+
+    client::swap_with_destination_host_fees(
+        &mut ctx,
+        &pool,
+        &user,
+        &host,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // the host share of the 1% owner fee is not charged in token a
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        2_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        8_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &host.token_a_ata).await,
+        0
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.default_host_fees_a, 0);
+
+    // instead the host gets a fifth of 1% of the token b swapped out of the vault
+    let user_amount_out = token_operations::balance(&mut ctx, &user.token_b_ata).await;
+    let host_fee = token_operations::balance(&mut ctx, &host.token_b_ata).await;
+    let amount_out = user_amount_out + host_fee;
+    assert!(host_fee > 0);
+    assert_eq!(host_fee, amount_out / 100 * 20 / 100);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        10_000_000 - amount_out
+    );
+}
+
+#[tokio::test]
+pub async fn test_host_fee_in_destination_token_slippage_is_net_of_host_fee() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, true).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::swap_with_destination_host_fees(
+        &mut ctx,
+        &pool,
+        &user,
+        &host,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+    let user_amount_out = token_operations::balance(&mut ctx, &user.token_b_ata).await;
+    let amount_out = user_amount_out + token_operations::balance(&mut ctx, &host.token_b_ata).await;
+
+    // the same swap against an identical pool, the minimum applies to what the user receives
+    let pool = new_pool(&mut ctx, true).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    assert_eq!(
+        client::swap_with_destination_host_fees(
+            &mut ctx,
+            &pool,
+            &user,
+            &host,
+            TradeDirection::AtoB,
+            Swap::new(1_000_000, amount_out, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+    client::swap_with_destination_host_fees(
+        &mut ctx,
+        &pool,
+        &user,
+        &host,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, user_amount_out, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        user_amount_out
+    );
+}
+
+#[tokio::test]
+pub async fn test_host_fees_account_must_match_the_host_fee_token() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, true).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    assert_eq!(
+        client::swap_with_host_fees(
+            &mut ctx,
+            &pool,
+            &user,
+            Some(&host),
+            TradeDirection::AtoB,
+            Swap::new(1_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::IncorrectSwapAccount)
+    );
+
+    let pool = new_pool(&mut ctx, false).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    assert_eq!(
+        client::swap_with_destination_host_fees(
+            &mut ctx,
+            &pool,
+            &user,
+            &host,
+            TradeDirection::AtoB,
+            Swap::new(1_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::IncorrectSwapAccount)
+    );
+}
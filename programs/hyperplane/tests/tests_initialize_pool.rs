@@ -1,16 +1,21 @@
 mod common;
 
+use anchor_spl::token_2022::spl_token_2022;
 use common::{client, runner};
 use hyperplane::{
     curve::{base::CurveType, calculator::INITIAL_SWAP_POOL_AMOUNT, fees::Fees},
     error::SwapError,
+    state::SwapState,
     utils::seeds,
     CurveUserParameters, InitialSupply,
 };
 use solana_program_test::tokio::{self};
 use solana_sdk::signer::Signer;
 
-use crate::common::{fixtures, setup, state, token_operations, types::SwapPairSpec};
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{SwapPairSpec, TokenSpec},
+};
 
 #[tokio::test]
 pub async fn test_success_init_swap_pool() {
@@ -56,6 +61,18 @@ pub async fn test_success_init_swap_pool() {
     assert_eq!(pool_state.fees, fees);
     assert_eq!(pool_state.curve_type, CurveType::ConstantProduct as u64);
     assert_eq!(pool_state.swap_curve, pool.curve);
+    assert!(pool_state.allows_deposits());
+    assert!(pool_state.allows_single_sided_deposits());
+
+    let pdas =
+        seeds::pda::pool_pdas_with_bumps(&hyperplane::ID, &pool.pubkey(), &pool_state).unwrap();
+    assert_eq!(pdas.curve, pool.curve);
+    assert_eq!(pdas.authority, pool.authority);
+    assert_eq!(pdas.token_a_vault, pool.token_a_vault);
+    assert_eq!(pdas.token_b_vault, pool.token_b_vault);
+    assert_eq!(pdas.pool_token_mint, pool.pool_token_mint);
+    assert_eq!(pdas.token_a_fees_vault, pool.token_a_fees_vault);
+    assert_eq!(pdas.token_b_fees_vault, pool.token_b_fees_vault);
 
     let _curve = state::get_constant_product_curve(&mut ctx, &pool).await;
 
@@ -69,6 +86,27 @@ pub async fn test_success_init_swap_pool() {
     assert_eq!(admin_pool_token_balance, INITIAL_SWAP_POOL_AMOUNT as u64);
 }
 
+#[tokio::test]
+pub async fn test_init_offset_pool_disallows_deposits() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(100, 100),
+        SwapPairSpec::default(),
+        CurveUserParameters::Offset {
+            token_b_offset: 1_000,
+        },
+    )
+    .await;
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(!pool_state.allows_deposits());
+    assert!(!pool_state.allows_single_sided_deposits());
+}
+
 #[tokio::test]
 pub async fn test_initialize_pool_with_same_token_a_and_b() {
     let program = runner::program(&[]);
@@ -95,6 +133,8 @@ pub async fn test_initialize_pool_with_same_token_a_and_b() {
                 fees: Fees::default(),
                 initial_supply,
                 curve_parameters: CurveUserParameters::Stable { amp: 100 },
+                burn_owner_fees: false,
+                strict_token_program: false,
             },
         )
         .await
@@ -130,6 +170,46 @@ pub async fn test_initialize_pool_with_same_token_b_and_a() {
                 fees: Fees::default(),
                 initial_supply,
                 curve_parameters: CurveUserParameters::Stable { amp: 100 },
+                burn_owner_fees: false,
+                strict_token_program: false,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::RepeatedMint, 1)
+    )
+}
+
+#[tokio::test]
+pub async fn test_initialize_pool_with_same_mint_and_different_token_programs() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(100, 100);
+    let mut pool =
+        setup::new_pool_accs(&mut ctx, SwapPairSpec::spl_tokens(9, 9), &initial_supply).await;
+
+    pool.token_b_mint = pool.token_a_mint;
+    let (token_b_vault, _token_b_vault_bump_seed) =
+        seeds::pda::token_b_vault_pda(&pool.pubkey(), &pool.token_a_mint);
+    pool.token_b_vault = token_b_vault;
+    let (token_b_fees_vault, _token_b_fees_vault_bump_seed) =
+        seeds::pda::token_b_fees_vault_pda(&pool.pubkey(), &pool.token_a_mint);
+    pool.token_b_fees_vault = token_b_fees_vault;
+    pool.admin.token_b_ata = pool.admin.token_a_ata;
+    // the token B vault init would fail in the token-2022 CPI, the mint belongs to spl-token
+    pool.token_b_token_program = spl_token_2022::id();
+    assert_eq!(
+        client::initialize_pool(
+            &mut ctx,
+            &pool,
+            hyperplane::ix::Initialize {
+                fees: Fees::default(),
+                initial_supply,
+                curve_parameters: CurveUserParameters::Stable { amp: 100 },
+                burn_owner_fees: false,
+                strict_token_program: false,
             },
         )
         .await
@@ -138,3 +218,35 @@ pub async fn test_initialize_pool_with_same_token_b_and_a() {
         hyperplane_error!(SwapError::RepeatedMint, 1)
     )
 }
+
+#[tokio::test]
+pub async fn test_initialize_strict_token_program_pool_with_mixed_token_programs_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(100, 100);
+    let pair = SwapPairSpec::new(
+        TokenSpec::spl_token(6),
+        TokenSpec::new(6, 0, spl_token_2022::id()),
+    );
+    let pool = setup::new_pool_accs(&mut ctx, pair, &initial_supply).await;
+    let initialize = |strict_token_program| hyperplane::ix::Initialize {
+        fees: Fees::default(),
+        initial_supply: initial_supply.clone(),
+        curve_parameters: CurveUserParameters::ConstantProduct,
+        burn_owner_fees: false,
+        strict_token_program,
+    };
+
+    assert_eq!(
+        client::initialize_pool(&mut ctx, &pool, initialize(true))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::MixedTokenPrograms, 1)
+    );
+
+    client::initialize_pool(&mut ctx, &pool, initialize(false))
+        .await
+        .unwrap();
+}
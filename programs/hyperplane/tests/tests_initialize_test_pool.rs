@@ -0,0 +1,82 @@
+mod common;
+
+use common::runner;
+use solana_program_test::tokio::{self};
+
+#[cfg(feature = "dev-mode")]
+const SEED: [u8; 32] = *b"sol-usdc-localnet-snapshot-tests";
+
+#[cfg(feature = "dev-mode")]
+fn initialize() -> hyperplane::ix::Initialize {
+    hyperplane::ix::Initialize {
+        fees: hyperplane::curve::fees::Fees::default(),
+        curve_parameters: hyperplane::CurveUserParameters::ConstantProduct,
+        initial_supply: hyperplane::InitialSupply::new(1_000_000, 1_000_000),
+        burn_owner_fees: false,
+        strict_token_program: false,
+    }
+}
+
+#[cfg(feature = "dev-mode")]
+#[tokio::test]
+pub async fn test_initialize_test_pool_at_deterministic_addresses() {
+    use common::client;
+    use hyperplane::{state::SwapPool, utils::seeds, InitialSupply};
+
+    use crate::common::{setup, state, token_operations, types::SwapPairSpec};
+
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let accs = setup::new_pool_accs(
+        &mut ctx,
+        SwapPairSpec::default(),
+        &InitialSupply::new(1_000_000, 1_000_000),
+    )
+    .await;
+
+    client::initialize_test_pool(&mut ctx, &accs, SEED, initialize())
+        .await
+        .unwrap();
+
+    let (pool, _bump) = seeds::pda::test_pool_pda(&accs.admin.pubkey(), &SEED);
+    let pdas = seeds::pda::init_pool_pdas(&pool, &accs.token_a_mint, &accs.token_b_mint);
+    let pool_state = state::get::<SwapPool>(&mut ctx, pool).await;
+    assert_eq!(pool_state.admin, accs.admin.pubkey());
+    assert_eq!(pool_state.swap_curve, pdas.curve);
+    assert_eq!(pool_state.pool_authority, pdas.authority);
+    assert_eq!(pool_state.token_a_vault, pdas.token_a_vault);
+    assert_eq!(pool_state.token_b_vault, pdas.token_b_vault);
+    assert_eq!(pool_state.pool_token_mint, pdas.pool_token_mint);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pdas.token_a_vault).await,
+        1_000_000
+    );
+
+    // the seed can only be used once per admin
+    assert!(
+        client::initialize_test_pool(&mut ctx, &accs, SEED, initialize())
+            .await
+            .is_err()
+    );
+}
+
+#[cfg(not(feature = "dev-mode"))]
+#[tokio::test]
+pub async fn test_initialize_test_pool_requires_dev_mode() {
+    use anchor_lang::{prelude::ErrorCode, solana_program::hash::hash};
+    use solana_sdk::instruction::Instruction;
+
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    // the instruction is left out of the program, whatever its accounts and arguments
+    let initialize_test_pool = Instruction {
+        program_id: hyperplane::id(),
+        accounts: vec![],
+        data: hash(b"global:initialize_test_pool").to_bytes()[..8].to_vec(),
+    };
+    assert_eq!(
+        send_tx!(ctx, [initialize_test_pool],).unwrap_err().unwrap(),
+        anchor_error!(ErrorCode::InstructionFallbackNotFound)
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees, error::SwapError, state::LockedLpPosition, utils::seeds, CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, setup::default_supply, state, token_operations, types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_lock_liquidity_until_unlock_slot() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp = setup::new_lp_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    let pool_tokens = token_operations::balance(&mut ctx, &lp.pool_token_ata).await;
+    let locked = seeds::pda::locked_lp_position_pdas(&pool.pubkey(), &lp.pubkey());
+
+    client::lock_liquidity(&mut ctx, &pool, &lp, pool_tokens, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &locked.pool_token_vault).await,
+        pool_tokens
+    );
+    let position = state::get::<LockedLpPosition>(&mut ctx, locked.position).await;
+    assert_eq!(position.pool, pool.pubkey());
+    assert_eq!(position.owner, lp.pubkey());
+    assert_eq!(position.pool_token_vault, locked.pool_token_vault);
+    assert_eq!(position.pool_token_amount, pool_tokens);
+    assert_eq!(position.unlock_slot, position.locked_slot + 100);
+
+    // a second lock needs the first one to be unlocked
+    assert!(client::lock_liquidity(&mut ctx, &pool, &lp, 1, 100)
+        .await
+        .is_err());
+
+    ctx.context.warp_to_slot(position.unlock_slot - 1).unwrap();
+    assert_eq!(
+        client::unlock_liquidity(&mut ctx, &pool, &lp)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::LiquidityLocked)
+    );
+
+    ctx.context.warp_to_slot(position.unlock_slot).unwrap();
+    client::unlock_liquidity(&mut ctx, &pool, &lp)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        pool_tokens
+    );
+    assert!(
+        state::try_get::<LockedLpPosition>(&mut ctx, locked.position)
+            .await
+            .is_err()
+    );
+    assert!(ctx
+        .context
+        .banks_client
+        .get_account(locked.pool_token_vault)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+pub async fn test_lock_liquidity_fails_without_duration() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp = setup::new_lp_user(&mut ctx, &pool, (1_000, 1_000)).await;
+
+    assert_eq!(
+        client::lock_liquidity(&mut ctx, &pool, &lp, 1, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidLockDuration)
+    );
+    assert_eq!(
+        client::lock_liquidity(&mut ctx, &pool, &lp, 0, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    state::{LpAirdrop, LpAirdropPosition},
+    utils::seeds,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, setup::default_supply, state, token_operations, types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_lp_airdrop_shared_pro_rata_between_registered_pool_tokens() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let sponsor = setup::new_pool_user(&mut ctx, &pool, (0, 1_000)).await;
+    let lp_1 = setup::new_lp_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    let lp_2 = setup::new_lp_user(&mut ctx, &pool, (2_000, 2_000)).await;
+    let late_lp = setup::new_lp_user(&mut ctx, &pool, (1_000, 1_000)).await;
+    let lp_1_pool_tokens = token_operations::balance(&mut ctx, &lp_1.pool_token_ata).await;
+    let lp_2_pool_tokens = token_operations::balance(&mut ctx, &lp_2.pool_token_ata).await;
+    let lp_1_token_b = token_operations::balance(&mut ctx, &lp_1.token_b_ata).await;
+    let lp_2_token_b = token_operations::balance(&mut ctx, &lp_2.token_b_ata).await;
+
+    let snapshot_slot = 100;
+    let airdrop = seeds::pda::lp_airdrop_pdas(
+        &pool.pubkey(),
+        &sponsor.pubkey(),
+        &pool.token_b_mint,
+        snapshot_slot,
+    );
+    client::create_lp_airdrop(&mut ctx, &pool, &sponsor, &airdrop, 1_000, snapshot_slot)
+        .await
+        .unwrap();
+
+    client::register_lp_airdrop(&mut ctx, &pool, &lp_1, &airdrop, lp_1_pool_tokens)
+        .await
+        .unwrap();
+    client::register_lp_airdrop(&mut ctx, &pool, &lp_2, &airdrop, lp_2_pool_tokens)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &airdrop.pool_token_vault).await,
+        lp_1_pool_tokens + lp_2_pool_tokens
+    );
+
+    assert_eq!(
+        client::claim_lp_airdrop(&mut ctx, &pool, &lp_1, &airdrop)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::AirdropNotClaimable)
+    );
+
+    ctx.context.warp_to_slot(snapshot_slot + 1).unwrap();
+
+    assert_eq!(
+        client::register_lp_airdrop(&mut ctx, &pool, &late_lp, &airdrop, 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::AirdropRegistrationClosed)
+    );
+
+    client::claim_lp_airdrop(&mut ctx, &pool, &lp_1, &airdrop)
+        .await
+        .unwrap();
+
+    // the sponsor cannot take the reward back while a position is unclaimed
+    assert_eq!(
+        client::close_lp_airdrop(&mut ctx, &pool, &sponsor, &airdrop)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::AirdropNotClaimable)
+    );
+
+    client::claim_lp_airdrop(&mut ctx, &pool, &lp_2, &airdrop)
+        .await
+        .unwrap();
+
+    let total_pool_tokens = lp_1_pool_tokens + lp_2_pool_tokens;
+    let lp_1_reward = 1_000 * lp_1_pool_tokens / total_pool_tokens;
+    let lp_2_reward = 1_000 * lp_2_pool_tokens / total_pool_tokens;
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp_1.token_b_ata).await,
+        lp_1_token_b + lp_1_reward
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp_2.token_b_ata).await,
+        lp_2_token_b + lp_2_reward
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp_1.pool_token_ata).await,
+        lp_1_pool_tokens
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp_2.pool_token_ata).await,
+        lp_2_pool_tokens
+    );
+
+    let airdrop_state = state::get::<LpAirdrop>(&mut ctx, airdrop.airdrop).await;
+    assert_eq!(airdrop_state.registered_pool_tokens, total_pool_tokens);
+    assert_eq!(airdrop_state.claimed_pool_tokens, total_pool_tokens);
+    let (position, _bump) = seeds::pda::lp_airdrop_position_pda(&airdrop.airdrop, &lp_1.pubkey());
+    assert!(state::try_get::<LpAirdropPosition>(&mut ctx, position)
+        .await
+        .is_err());
+
+    // rounding remainder goes back to the sponsor
+    client::close_lp_airdrop(&mut ctx, &pool, &sponsor, &airdrop)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &sponsor.token_b_ata).await,
+        1_000 - lp_1_reward - lp_2_reward
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &airdrop.reward_vault).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_create_lp_airdrop_fails_with_past_snapshot_slot() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let sponsor = setup::new_pool_user(&mut ctx, &pool, (0, 1_000)).await;
+    ctx.context.warp_to_slot(100).unwrap();
+
+    let airdrop =
+        seeds::pda::lp_airdrop_pdas(&pool.pubkey(), &sponsor.pubkey(), &pool.token_b_mint, 100);
+    assert_eq!(
+        client::create_lp_airdrop(&mut ctx, &pool, &sponsor, &airdrop, 1_000, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidAirdropSnapshotSlot)
+    );
+}
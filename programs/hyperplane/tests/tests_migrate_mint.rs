@@ -0,0 +1,360 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{SetConversionProgram, Swap, UpdateFeatureSet, WithdrawFees},
+    state::Feature,
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer};
+
+use crate::common::{
+    client::instructions,
+    fixtures,
+    fixtures::{ProgramDependency, Sol},
+    mint_migration,
+    setup::{self, kp, new_keypair},
+    state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},
+};
+
+/// Pool with owner fees accrued in token A, and the feature set allowing mint migrations
+async fn setup_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        Fees {
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(ctx, &pool, (100_000, 0)).await;
+    client::swap(
+        ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(100_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    client::initialize_feature_set(ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    pool
+}
+
+/// Successor mint and the reserve the mock conversion program pays it out of
+async fn new_successor_mint(ctx: &mut TestContext, token_spec: TokenSpec) -> (Pubkey, Pubkey) {
+    let new_mint = kp();
+    token_operations::create_mint(ctx, &new_mint, token_spec)
+        .await
+        .unwrap();
+    let (reserve_authority, _bump) = mint_migration::reserve_authority();
+    let reserve = token_operations::create_token_account(
+        ctx,
+        &token_spec.token_program,
+        &new_mint.pubkey(),
+        &reserve_authority,
+    )
+    .await
+    .unwrap();
+    token_operations::mint_to(
+        ctx,
+        &token_spec.token_program,
+        &new_mint.pubkey(),
+        &reserve,
+        10_000_000,
+    )
+    .await
+    .unwrap();
+    (new_mint.pubkey(), reserve)
+}
+
+async fn enable_mint_migration(ctx: &mut TestContext, pool: &SwapPoolAccounts) {
+    client::update_feature_set(
+        ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::MintMigration, true),
+    )
+    .await
+    .unwrap();
+}
+
+async fn allow_mock_conversion_program(ctx: &mut TestContext, pool: &SwapPoolAccounts) {
+    client::set_conversion_program(
+        ctx,
+        &pool.admin.admin,
+        SetConversionProgram::new(0, mint_migration::ID),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_migrate_mint() {
+    let program = runner::program(&[ProgramDependency::MockMintMigration]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = setup_pool(&mut ctx).await;
+    enable_mint_migration(&mut ctx, &pool).await;
+    allow_mock_conversion_program(&mut ctx, &pool).await;
+    let (new_mint, reserve) = new_successor_mint(&mut ctx, TokenSpec::default()).await;
+    let token_b_vault_amount = token_operations::balance(&mut ctx, &pool.token_b_vault).await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+    let vault_amount = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
+    let fees_vault_amount = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let old_supply = token_operations::supply(&mut ctx, &pool.token_a_mint).await;
+    assert_eq!(fees_vault_amount, 1_000);
+
+    client::migrate_mint(
+        &mut ctx,
+        &pool,
+        AorB::A,
+        &new_mint,
+        &pool.token_a_token_program,
+        vec![0],
+        &reserve,
+    )
+    .await
+    .unwrap();
+
+    let (new_vault, _bump) = seeds::pda::token_a_vault_pda(&pool.pubkey(), &new_mint);
+    let (new_fees_vault, _bump) = seeds::pda::token_a_fees_vault_pda(&pool.pubkey(), &new_mint);
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.token_a_mint, new_mint);
+    assert_eq!(pool_state.token_a_vault, new_vault);
+    assert_eq!(pool_state.token_a_fees_vault, new_fees_vault);
+    assert_eq!(pool_state.token_b_mint, pool.token_b_mint);
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &new_vault).await,
+        vault_amount
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &new_fees_vault).await,
+        fees_vault_amount
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.token_a_mint).await,
+        old_supply - vault_amount - fees_vault_amount
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        token_b_vault_amount
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        pool_token_supply
+    );
+
+    // the pool keeps trading with the successor mint
+    let mut migrated_pool = pool.clone();
+    migrated_pool.token_a_mint = new_mint;
+    migrated_pool.token_a_vault = new_vault;
+    migrated_pool.token_a_fees_vault = new_fees_vault;
+    migrated_pool.admin.token_a_ata = token_operations::create_token_account(
+        &mut ctx,
+        &pool.token_a_token_program,
+        &new_mint,
+        &pool.admin.pubkey(),
+    )
+    .await
+    .unwrap();
+    let user = setup::new_pool_user(&mut ctx, &migrated_pool, (100_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &migrated_pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(100_000, 0, None),
+    )
+    .await
+    .unwrap();
+    client::withdraw_fees(&mut ctx, &migrated_pool, AorB::A, WithdrawFees::new(2_000))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &migrated_pool.admin.token_a_ata).await,
+        2_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_migrate_mint() {
+    let program = runner::program(&[ProgramDependency::MockMintMigration]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = setup_pool(&mut ctx).await;
+    let (new_mint, reserve) = new_successor_mint(&mut ctx, TokenSpec::default()).await;
+
+    // feature disabled on the cluster
+    {
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &new_mint,
+                &pool.token_a_token_program,
+                vec![0],
+                &reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::FeatureDisabled)
+        );
+    }
+
+    enable_mint_migration(&mut ctx, &pool).await;
+
+    // conversion program not allowed by the program admin
+    {
+        // same transaction as with the feature disabled
+        let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+        ctx.context.warp_to_slot(slot + 1).unwrap();
+
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &new_mint,
+                &pool.token_a_token_program,
+                vec![0],
+                &reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::InvalidConversionProgram)
+        );
+    }
+
+    allow_mock_conversion_program(&mut ctx, &pool).await;
+
+    // conversion given an account the pool authority controls
+    {
+        let mut ix = instructions::migrate_mint(
+            &pool,
+            AorB::A,
+            &new_mint,
+            &pool.token_a_token_program,
+            vec![0],
+            &reserve,
+        );
+        ix.accounts
+            .push(AccountMeta::new(pool.token_b_vault, false));
+
+        assert_eq!(
+            send_tx!(ctx, [ix], pool.admin.admin.as_ref())
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::InvalidConversionProgram)
+        );
+    }
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &cloned_pool,
+                AorB::A,
+                &new_mint,
+                &pool.token_a_token_program,
+                vec![0],
+                &reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // successor mint with different decimals
+    {
+        let (other_mint, other_reserve) =
+            new_successor_mint(&mut ctx, TokenSpec::spl_token(9)).await;
+
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &other_mint,
+                &pool.token_a_token_program,
+                vec![0],
+                &other_reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::InvalidMintMigration)
+        );
+    }
+
+    // successor mint already traded by the pool
+    {
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &pool.token_b_mint,
+                &pool.token_b_token_program,
+                vec![0],
+                &reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::RepeatedMint)
+        );
+    }
+
+    // conversion paying less than 1:1
+    {
+        assert_eq!(
+            client::migrate_mint(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &new_mint,
+                &pool.token_a_token_program,
+                vec![mint_migration::SHORT_CHANGE],
+                &reserve,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::VaultBalanceMismatch)
+        );
+    }
+}
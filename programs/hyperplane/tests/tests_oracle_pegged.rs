@@ -0,0 +1,285 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{base::CurveType, calculator::TradeDirection, fees::Fees, oracle_pegged::OracleType},
+    error::SwapError,
+    ix::Swap,
+    state::OraclePeggedCurve,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::{account::AccountSharedData, pubkey::Pubkey};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+const MAX_AGE_SLOTS: u64 = 10;
+const SLOT: u64 = 100;
+
+/// Write a Pyth v2 price account publishing `value * 10^exponent` at `slot`
+fn set_pyth_price(ctx: &mut TestContext, oracle: &Pubkey, value: i64, exponent: i32, slot: u64) {
+    let mut data = vec![0; 240];
+    data[0..4].copy_from_slice(&0xa1b2c3d4_u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2_u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3_u32.to_le_bytes());
+    data[20..24].copy_from_slice(&exponent.to_le_bytes());
+    data[208..216].copy_from_slice(&value.to_le_bytes());
+    // trading
+    data[224..228].copy_from_slice(&1_u32.to_le_bytes());
+    data[232..240].copy_from_slice(&slot.to_le_bytes());
+
+    let mut account = AccountSharedData::new(1_000_000_000, data.len(), &Pubkey::new_unique());
+    account.set_data(data);
+    ctx.context.set_account(oracle, &account);
+}
+
+/// USDC / SOL pool with SOL priced 150 USDC by its oracle
+async fn new_pool(ctx: &mut TestContext, oracle: &Pubkey) -> SwapPoolAccounts {
+    ctx.context.warp_to_slot(SLOT).unwrap();
+    set_pyth_price(ctx, oracle, 15_000_000_000, -8, SLOT);
+    fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        InitialSupply::new(1_500_000_000, 10_000_000_000),
+        SwapPairSpec::spl_tokens(6, 9),
+        CurveUserParameters::OraclePegged {
+            oracle: *oracle,
+            oracle_type: OracleType::Pyth.into(),
+            scope_price_index: 0,
+            max_age_slots: MAX_AGE_SLOTS,
+            spread_bps: 0,
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+pub async fn test_success_init_oracle_pegged_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+
+    let pool = new_pool(&mut ctx, &oracle).await;
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.curve_type, CurveType::OraclePegged as u64);
+    assert!(!pool_state.allows_single_sided_deposits());
+
+    let curve = state::get::<OraclePeggedCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.oracle, oracle);
+    assert_eq!(curve.max_age_slots, MAX_AGE_SLOTS);
+    assert_eq!(curve.token_a_decimals, 6);
+    assert_eq!(curve.token_b_decimals, 9);
+}
+
+#[tokio::test]
+pub async fn test_swap_at_the_oracle_price() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+    let pool = new_pool(&mut ctx, &oracle).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 1_000_000_000)).await;
+    client::swap_with_oracle(
+        &mut ctx,
+        &pool,
+        &user,
+        &oracle,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000_000, 150_000_000, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        150_000_000
+    );
+
+    // the price moves to 160 USDC
+    set_pyth_price(&mut ctx, &oracle, 16_000_000_000, -8, SLOT);
+    client::swap_with_oracle(
+        &mut ctx,
+        &pool,
+        &user,
+        &oracle,
+        TradeDirection::AtoB,
+        Swap::new(80_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        500_000_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_with_stale_price_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+    let pool = new_pool(&mut ctx, &oracle).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 1_000_000_000)).await;
+
+    ctx.context.warp_to_slot(SLOT + MAX_AGE_SLOTS + 1).unwrap();
+    assert_eq!(
+        client::swap_with_oracle(
+            &mut ctx,
+            &pool,
+            &user,
+            &oracle,
+            TradeDirection::BtoA,
+            Swap::new(1_000_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::StaleOracle)
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_without_the_curve_oracle_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+    let pool = new_pool(&mut ctx, &oracle).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 1_000_000_000)).await;
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::BtoA,
+            Swap::new(1_000_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidOracle)
+    );
+
+    // a price account publishing a higher price, but not the one of the curve
+    let other_oracle = Pubkey::new_unique();
+    set_pyth_price(&mut ctx, &other_oracle, 30_000_000_000, -8, SLOT);
+    assert_eq!(
+        client::swap_with_oracle(
+            &mut ctx,
+            &pool,
+            &user,
+            &other_oracle,
+            TradeDirection::BtoA,
+            Swap::new(1_000_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidOracle)
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_at_the_refreshed_price() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+    let pool = new_pool(&mut ctx, &oracle).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 2_000_000_000)).await;
+
+    client::refresh_price(&mut ctx, &pool, &oracle)
+        .await
+        .unwrap();
+    let curve = state::get::<OraclePeggedCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.cached_price_value, 15_000_000_000);
+    assert_eq!(curve.cached_price_exponent, -8);
+    assert_eq!(curve.cached_price_slot, SLOT);
+
+    // swaps without the oracle account at the cached price, the rewritten curve checksum holds
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000_000, 150_000_000, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        150_000_000
+    );
+
+    // the cached price goes stale with the price it was read from
+    ctx.context.warp_to_slot(SLOT + MAX_AGE_SLOTS + 1).unwrap();
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::BtoA,
+            Swap::new(1_000_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::StaleOracle)
+    );
+
+    // until the price moves to 160 USDC and is refreshed
+    set_pyth_price(
+        &mut ctx,
+        &oracle,
+        16_000_000_000,
+        -8,
+        SLOT + MAX_AGE_SLOTS + 1,
+    );
+    client::refresh_price(&mut ctx, &pool, &oracle)
+        .await
+        .unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000_000, 160_000_000, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        310_000_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_refresh_price_fails_with_a_stale_or_foreign_oracle() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let oracle = Pubkey::new_unique();
+    let pool = new_pool(&mut ctx, &oracle).await;
+
+    let other_oracle = Pubkey::new_unique();
+    set_pyth_price(&mut ctx, &other_oracle, 30_000_000_000, -8, SLOT);
+    assert_eq!(
+        client::refresh_price(&mut ctx, &pool, &other_oracle)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidOracle)
+    );
+
+    ctx.context.warp_to_slot(SLOT + MAX_AGE_SLOTS + 1).unwrap();
+    assert_eq!(
+        client::refresh_price(&mut ctx, &pool, &oracle)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::StaleOracle)
+    );
+    let curve = state::get::<OraclePeggedCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.cached_price_value, 0);
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{base::CurveType, calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Initialize, Swap},
+    state::PeggedCurve,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, token_operations, types::SwapPairSpec};
+
+/// Price of token B in token A, with 9 decimals
+const PEG: u64 = 1_050_000_000;
+
+#[tokio::test]
+pub async fn test_success_init_pegged_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_050_000, 1_000_000_000),
+        SwapPairSpec::spl_tokens(6, 9),
+        CurveUserParameters::Pegged {
+            amp: 10,
+            token_b_peg: PEG,
+        },
+    )
+    .await;
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.curve_type, CurveType::Pegged as u64);
+    assert_eq!(pool_state.swap_curve, pool.curve);
+
+    let curve = state::get::<PeggedCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.amp, 10);
+    assert_eq!(curve.token_b_peg, PEG);
+    assert_eq!(curve.token_a_factor, 1_000 * PeggedCurve::PEG_SCALE);
+    assert_eq!(curve.token_b_factor, PEG);
+}
+
+#[tokio::test]
+pub async fn test_init_pegged_pool_fails_with_zero_peg() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(1_000_000, 1_000_000);
+    let pool = setup::new_pool_accs(&mut ctx, SwapPairSpec::default(), &initial_supply).await;
+
+    assert_eq!(
+        client::initialize_pool(
+            &mut ctx,
+            &pool,
+            Initialize {
+                fees: Fees::default(),
+                initial_supply,
+                curve_parameters: CurveUserParameters::Pegged {
+                    amp: 10,
+                    token_b_peg: 0,
+                },
+                burn_owner_fees: false,
+                strict_token_program: false,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidCurve, 1)
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_near_the_peg() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    // reserves of equal value at the peg
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_050_000_000, 1_000_000_000),
+        SwapPairSpec::spl_tokens(6, 6),
+        CurveUserParameters::Pegged {
+            amp: 10,
+            token_b_peg: PEG,
+        },
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 10_000_000)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(10_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    let received = token_operations::balance(&mut ctx, &user.token_a_ata).await;
+    // less slippage than the 10_396_039 of a constant product pool, below the peg price
+    assert!(received > 10_396_039);
+    assert!(received < 10_500_000);
+}
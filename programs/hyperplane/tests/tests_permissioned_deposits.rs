@@ -0,0 +1,215 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Deposit, DepositSingle, Swap, UpdateFeatureSet, UpdatePoolConfig, Withdraw},
+    state::{
+        AllowedLiquidityProvider, Feature, SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue,
+    },
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{default_supply, new_keypair},
+    state, token_operations,
+    types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_permissioned_deposits() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let permissioned_deposits = UpdatePoolConfig::new(
+        UpdatePoolConfigMode::PermissionedDeposits,
+        UpdatePoolConfigValue::Bool(true),
+    );
+    assert_eq!(
+        client::update_pool_config(&mut ctx, &pool, permissioned_deposits.clone())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+
+    let provider = setup::new_pool_user(&mut ctx, &pool, (200_000, 100_000)).await;
+    client::initialize_feature_set(&mut ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    assert_eq!(
+        client::add_liquidity_provider(&mut ctx, &pool, &provider.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+
+    client::update_feature_set(
+        &mut ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::PermissionedDeposits, true),
+    )
+    .await
+    .unwrap();
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 1).unwrap();
+    client::update_pool_config(&mut ctx, &pool, permissioned_deposits)
+        .await
+        .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.permissioned_deposits());
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+
+    assert_eq!(
+        client::deposit(
+            &mut ctx,
+            &pool,
+            &provider,
+            Deposit::new(pool_token_supply / 10, 100_000, 100_000, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::LiquidityProviderNotAllowed)
+    );
+    assert_eq!(
+        client::deposit_single(
+            &mut ctx,
+            &pool,
+            &provider,
+            AorB::A,
+            DepositSingle::new(10_000, 1),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::LiquidityProviderNotAllowed)
+    );
+
+    client::add_liquidity_provider(&mut ctx, &pool, &provider.pubkey())
+        .await
+        .unwrap();
+    let (allowed_liquidity_provider, _bump) =
+        seeds::pda::allowed_liquidity_provider_pda(&pool.pubkey(), &provider.pubkey());
+    let allowed_liquidity_provider =
+        state::get::<AllowedLiquidityProvider>(&mut ctx, allowed_liquidity_provider).await;
+    assert_eq!(allowed_liquidity_provider.pool, pool.pubkey());
+    assert_eq!(allowed_liquidity_provider.provider, provider.pubkey());
+
+    client::deposit_permissioned(
+        &mut ctx,
+        &pool,
+        &provider,
+        Deposit::new(pool_token_supply / 10, 100_000, 100_000, None),
+    )
+    .await
+    .unwrap();
+    client::deposit_single_permissioned(
+        &mut ctx,
+        &pool,
+        &provider,
+        AorB::A,
+        DepositSingle::new(10_000, 1),
+    )
+    .await
+    .unwrap();
+    let pool_tokens = token_operations::balance(&mut ctx, &provider.pool_token_ata).await;
+    assert!(pool_tokens > pool_token_supply / 10);
+
+    client::remove_liquidity_provider(&mut ctx, &pool, &provider.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        client::deposit_permissioned(
+            &mut ctx,
+            &pool,
+            &provider,
+            Deposit::new(pool_token_supply / 100, 10_000, 10_000, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        anchor_error!(ErrorCode::AccountNotInitialized)
+    );
+
+    // withdrawals and swaps are still permissionless
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &provider,
+        Withdraw::new(pool_tokens, 0, 0, false, None),
+    )
+    .await
+    .unwrap();
+    let taker = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &taker,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 1, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_security_add_liquidity_provider() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let provider = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_feature_set(&mut ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    client::update_feature_set(
+        &mut ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::PermissionedDeposits, true),
+    )
+    .await
+    .unwrap();
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::add_liquidity_provider(&mut ctx, &cloned_pool, &provider.pubkey())
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdateFeatureSet, UpdatePoolConfig},
+    state::{AllowedSwapTaker, Feature, SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{default_supply, new_keypair},
+    state,
+    types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_permissioned_swaps() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+
+    let permissioned_swaps = UpdatePoolConfig::new(
+        UpdatePoolConfigMode::PermissionedSwaps,
+        UpdatePoolConfigValue::Bool(true),
+    );
+    assert_eq!(
+        client::update_pool_config(&mut ctx, &pool, permissioned_swaps.clone())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (100, 0)).await;
+    client::initialize_feature_set(&mut ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 1).unwrap();
+    assert_eq!(
+        client::update_pool_config(&mut ctx, &pool, permissioned_swaps.clone())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+    assert_eq!(
+        client::add_swap_taker(&mut ctx, &pool, &user.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+
+    client::update_feature_set(
+        &mut ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::PermissionedSwaps, true),
+    )
+    .await
+    .unwrap();
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 1).unwrap();
+    client::update_pool_config(&mut ctx, &pool, permissioned_swaps)
+        .await
+        .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.permissioned_swaps());
+
+    // deposits are still permissionless
+    setup::new_lp_user(&mut ctx, &pool, (100, 100)).await;
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap {
+                amount_in: 50,
+                minimum_amount_out: 47,
+                deadline: None,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::SwapTakerNotAllowed)
+    );
+
+    client::add_swap_taker(&mut ctx, &pool, &user.pubkey())
+        .await
+        .unwrap();
+    let (allowed_swap_taker, _bump) =
+        seeds::pda::allowed_swap_taker_pda(&pool.pubkey(), &user.pubkey());
+    let allowed_swap_taker = state::get::<AllowedSwapTaker>(&mut ctx, allowed_swap_taker).await;
+    assert_eq!(allowed_swap_taker.pool, pool.pubkey());
+    assert_eq!(allowed_swap_taker.taker, user.pubkey());
+
+    client::swap_permissioned(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap {
+            amount_in: 50,
+            minimum_amount_out: 47,
+            deadline: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    client::remove_swap_taker(&mut ctx, &pool, &user.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        client::swap_permissioned(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap {
+                amount_in: 50,
+                minimum_amount_out: 47,
+                deadline: None,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        anchor_error!(ErrorCode::AccountNotInitialized)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_add_swap_taker() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_feature_set(&mut ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    client::update_feature_set(
+        &mut ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::PermissionedSwaps, true),
+    )
+    .await
+    .unwrap();
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::add_swap_taker(&mut ctx, &cloned_pool, &user.pubkey())
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Deposit, DepositSingle, Swap, UpdatePoolConfig, Withdraw, WithdrawFees, WithdrawSingle},
+    state::{PoolOperation, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+use crate::common::{
+    fixtures, setup, state,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn set_status(ctx: &mut TestContext, pool: &SwapPoolAccounts, paused: &[PoolOperation]) {
+    let status = paused
+        .iter()
+        .fold(0, |status, operation| status | operation.flag());
+    client::update_pool_config(
+        ctx,
+        pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::Status,
+            UpdatePoolConfigValue::U64(status),
+        ),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_pool_status() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000_000, 10_000_000)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    set_status(
+        &mut ctx,
+        &pool,
+        &[PoolOperation::Swaps, PoolOperation::SingleSided],
+    )
+    .await;
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.is_paused(PoolOperation::Swaps));
+    assert!(!pool_state.is_paused(PoolOperation::Deposits));
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(1_000_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+    assert_eq!(
+        client::deposit_single(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            DepositSingle::new(1_000, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+    assert_eq!(
+        client::withdraw_single(
+            &mut ctx,
+            &pool,
+            &admin,
+            AorB::A,
+            WithdrawSingle::new(1_000, 10_000),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+
+    // the operations left running are unaffected
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &user,
+        Deposit::new(1_000, 2_000, 2_000, None),
+    )
+    .await
+    .unwrap();
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &admin,
+        Withdraw::new(1_000, 0, 0, false, None),
+    )
+    .await
+    .unwrap();
+    client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(1_000))
+        .await
+        .unwrap();
+
+    set_status(
+        &mut ctx,
+        &pool,
+        &[PoolOperation::Deposits, PoolOperation::FeeWithdrawals],
+    )
+    .await;
+
+    assert_eq!(
+        client::deposit(
+            &mut ctx,
+            &pool,
+            &user,
+            Deposit::new(1_000, 2_000, 2_000, None)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+    assert_eq!(
+        client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(1_000))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    set_status(&mut ctx, &pool, &[]).await;
+    assert_eq!(state::get_pool(&mut ctx, &pool).await.status, 0);
+    client::deposit_single(
+        &mut ctx,
+        &pool,
+        &user,
+        AorB::A,
+        DepositSingle::new(1_000, 0),
+    )
+    .await
+    .unwrap();
+    client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(1_000))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_pool_status_unknown_flag() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    assert_eq!(
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::Status,
+                UpdatePoolConfigValue::U64(PoolOperation::ALL_FLAGS + 1),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_swap_rejected_outside_price_bounds() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    for (mode, price) in [
+        (UpdatePoolConfigMode::MinPrice, 950_000_000),
+        (UpdatePoolConfigMode::MaxPrice, 1_050_000_000),
+    ] {
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(mode, UpdatePoolConfigValue::U64(price)),
+        )
+        .await
+        .unwrap();
+    }
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.min_price, 950_000_000);
+    assert_eq!(pool_state.max_price, 1_050_000_000);
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 100_000)).await;
+
+    // 1_010_000 A : 990_100 B - price ~0.98
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // 1_050_000 A : 952_381 B - price ~0.907
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(40_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PriceOutOfBounds)
+    );
+
+    // 961_539 A : 1_040_000 B - price ~1.08
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::BtoA,
+            Swap::new(49_900, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PriceOutOfBounds)
+    );
+
+    // back to 1:1
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(9_900, 0, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_min_price_above_max_price_rejected() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::MaxPrice,
+            UpdatePoolConfigValue::U64(1_050_000_000),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MinPrice,
+                UpdatePoolConfigValue::U64(1_100_000_000),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidPriceBounds)
+    );
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{config_value, ProposePoolConfig, UpdateFeatureSet},
+    state::{Feature, ProposedConfigChange, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup::{default_supply, new_keypair},
+    state,
+    types::SwapPairSpec,
+};
+
+#[tokio::test]
+pub async fn test_execute_proposed_pool_config_after_approval_window() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    client::propose_pool_config(
+        &mut ctx,
+        &pool,
+        ProposePoolConfig::new(
+            UpdatePoolConfigMode::WithdrawalsOnly,
+            UpdatePoolConfigValue::Bool(true),
+            100,
+        ),
+    )
+    .await
+    .unwrap();
+
+    let (proposed_config_change, _bump) = seeds::pda::proposed_config_change_pda(&pool.pubkey());
+    let proposal = state::get::<ProposedConfigChange>(&mut ctx, proposed_config_change).await;
+    assert_eq!(proposal.pool, pool.pubkey());
+    assert_eq!(proposal.mode, UpdatePoolConfigMode::WithdrawalsOnly as u16);
+    assert!(config_value::decode_bool(&proposal.value).unwrap());
+    assert_eq!(proposal.executable_slot, proposal.proposed_slot + 100);
+
+    assert_eq!(
+        client::execute_pool_config(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ProposalNotExecutable)
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.withdrawals_only, 0);
+
+    ctx.context.warp_to_slot(proposal.executable_slot).unwrap();
+    client::execute_pool_config(&mut ctx, &pool).await.unwrap();
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.withdrawals_only, 1);
+    assert!(ctx
+        .context
+        .banks_client
+        .get_account(proposed_config_change)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+pub async fn test_security_propose_pool_config() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let proposal = ProposePoolConfig::new(
+        UpdatePoolConfigMode::PermissionedSwaps,
+        UpdatePoolConfigValue::Bool(true),
+        0,
+    );
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::propose_pool_config(&mut ctx, &cloned_pool, proposal.clone())
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    client::propose_pool_config(&mut ctx, &pool, proposal)
+        .await
+        .unwrap();
+
+    // the feature gating the change is checked on execution
+    client::initialize_feature_set(&mut ctx, &pool.admin.admin)
+        .await
+        .unwrap();
+    assert_eq!(
+        client::execute_pool_config(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::FeatureDisabled)
+    );
+    client::update_feature_set(
+        &mut ctx,
+        &pool.admin.admin,
+        UpdateFeatureSet::new(Feature::PermissionedSwaps, true),
+    )
+    .await
+    .unwrap();
+
+    // wrong admin executing
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::execute_pool_config(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 1).unwrap();
+    client::execute_pool_config(&mut ctx, &pool).await.unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.permissioned_swaps, 1);
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig, WithdrawFees},
+    state::{ProtocolFeeConfig, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 1,
+        trade_fee_denominator: 100,
+        owner_trade_fee_numerator: 1,
+        owner_trade_fee_denominator: 100,
+        host_fee_numerator: 20,
+        host_fee_denominator: 100,
+        ..Default::default()
+    }
+}
+
+/// Pool giving a tenth of its owner fees to the protocol
+async fn new_pool(ctx: &mut TestContext, fees: Fees) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        fees,
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    client::update_pool_config(
+        ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::ProtocolFeeBps,
+            UpdatePoolConfigValue::U64(1_000),
+        ),
+    )
+    .await
+    .unwrap();
+    pool
+}
+
+#[tokio::test]
+pub async fn test_protocol_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, fees()).await;
+    let authority = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_protocol_fee_config(&mut ctx, &pool.admin.admin, &authority.pubkey())
+        .await
+        .unwrap();
+    let (protocol_fee_config, _bump) = seeds::pda::protocol_fee_config_pda();
+    let protocol_fee_config = state::get::<ProtocolFeeConfig>(&mut ctx, protocol_fee_config).await;
+    assert_eq!(protocol_fee_config.authority, authority.pubkey());
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // owner fee of 1% on the 1_000_000 swapped, the protocol takes a tenth of what is left after
+    // the fifth of host fee
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        10_000
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.protocol_fees_a, 800);
+    assert_eq!(pool_state.protocol_fees_b, 0);
+
+    // the admin cannot withdraw the protocol's share
+    let initial_admin_balance = token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await;
+    client::withdraw_fees(&mut ctx, &pool, AorB::A, WithdrawFees::new(u64::MAX))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await,
+        initial_admin_balance + 9_200
+    );
+
+    client::withdraw_protocol_fees(&mut ctx, &pool, &authority, AorB::A)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &authority.token_a_ata).await,
+        800
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        0
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.protocol_fees_a, 0);
+
+    assert_eq!(
+        client::withdraw_protocol_fees(&mut ctx, &pool, &authority, AorB::A)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
+
+#[tokio::test]
+pub async fn test_protocol_fees_with_host_fees_account() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, fees()).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 1_000_000)).await;
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::swap_with_host_fees(
+        &mut ctx,
+        &pool,
+        &user,
+        Some(&host),
+        TradeDirection::BtoA,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &host.token_b_ata).await,
+        2_000
+    );
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.protocol_fees_a, 0);
+    assert_eq!(pool_state.protocol_fees_b, 800);
+}
+
+#[tokio::test]
+pub async fn test_close_pool_with_protocol_fees_owed() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    // no host fee, which would accrue to the default host fees recipient
+    let pool = new_pool(
+        &mut ctx,
+        Fees {
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+            ..fees()
+        },
+    )
+    .await;
+    let authority = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_protocol_fee_config(&mut ctx, &pool.admin.admin, &authority.pubkey())
+        .await
+        .unwrap();
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::close_pool(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::PoolNotClosable)
+    );
+
+    client::withdraw_protocol_fees(&mut ctx, &pool, &authority, AorB::A)
+        .await
+        .unwrap();
+    client::close_pool(&mut ctx, &pool).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn test_security_withdraw_protocol_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx, fees()).await;
+    let authority = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_protocol_fee_config(&mut ctx, &pool.admin.admin, &authority.pubkey())
+        .await
+        .unwrap();
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // wrong authority
+    {
+        let imposter = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+        assert_eq!(
+            client::withdraw_protocol_fees(&mut ctx, &pool, &imposter, AorB::A)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // pool vault instead of the fees vault
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.token_a_fees_vault = pool.token_a_vault;
+
+        assert_eq!(
+            client::withdraw_protocol_fees(&mut ctx, &cloned_pool, &authority, AorB::A)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeAccount)
+        );
+    }
+}
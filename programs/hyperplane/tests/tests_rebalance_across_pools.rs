@@ -0,0 +1,251 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::INITIAL_SWAP_POOL_AMOUNT, fees::Fees},
+    error::SwapError,
+    ix::RebalanceAcrossPools,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup::new_keypair,
+    token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},
+};
+
+const INITIAL_POOL_TOKENS: u64 = INITIAL_SWAP_POOL_AMOUNT as u64;
+
+async fn new_pools(ctx: &mut TestContext) -> (SwapPoolAccounts, SwapPoolAccounts) {
+    new_pools_with(ctx, Fees::default(), SwapPairSpec::default()).await
+}
+
+async fn new_pools_with(
+    ctx: &mut TestContext,
+    source_fees: Fees,
+    trading_tokens: SwapPairSpec,
+) -> (SwapPoolAccounts, SwapPoolAccounts) {
+    let source_pool = fixtures::new_pool(
+        ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            ..source_fees
+        },
+        InitialSupply::new(1000, 1000),
+        trading_tokens,
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let destination_pool = fixtures::new_sibling_pool(
+        ctx,
+        &source_pool,
+        Fees {
+            trade_fee_numerator: 5,
+            trade_fee_denominator: 10000,
+            ..Default::default()
+        },
+        InitialSupply::new(100, 100),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    (source_pool, destination_pool)
+}
+
+#[tokio::test]
+pub async fn test_rebalance_across_pools() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (source_pool, destination_pool) = new_pools(&mut ctx).await;
+
+    client::rebalance_across_pools(
+        &mut ctx,
+        &source_pool,
+        &destination_pool,
+        RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 10),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::supply(&mut ctx, &source_pool.pool_token_mint).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &source_pool.admin.pool_token_ata.pubkey()).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &destination_pool.pool_token_mint).await,
+        INITIAL_POOL_TOKENS + INITIAL_POOL_TOKENS / 10
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &destination_pool.admin.pool_token_ata.pubkey()).await,
+        INITIAL_POOL_TOKENS + INITIAL_POOL_TOKENS / 10
+    );
+
+    for (vault, expected) in [
+        (&source_pool.token_a_vault, 500),
+        (&source_pool.token_b_vault, 500),
+        (&destination_pool.token_a_vault, 110),
+        (&destination_pool.token_b_vault, 110),
+        (&source_pool.admin.token_a_ata, 490),
+        (&source_pool.admin.token_b_ata, 490),
+    ] {
+        assert_eq!(token_operations::balance(&mut ctx, vault).await, expected);
+    }
+}
+
+#[tokio::test]
+pub async fn test_rebalance_across_pools_with_withdraw_and_transfer_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (source_pool, destination_pool) = new_pools_with(
+        &mut ctx,
+        Fees {
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+            ..Default::default()
+        },
+        SwapPairSpec::new(TokenSpec::transfer_fees(100), TokenSpec::default()),
+    )
+    .await;
+
+    client::rebalance_across_pools(
+        &mut ctx,
+        &source_pool,
+        &destination_pool,
+        RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 10),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::supply(&mut ctx, &destination_pool.pool_token_mint).await,
+        INITIAL_POOL_TOKENS + INITIAL_POOL_TOKENS / 10
+    );
+    // 500 withdrawn minus a 5 withdrawal fee, 11 sent so that the destination (seeded with 99
+    // after the transfer fee) nets 10, and the 484 left over lose a 5 transfer fee to the admin
+    for (vault, expected) in [
+        (&source_pool.token_a_vault, 500),
+        (&source_pool.token_b_vault, 500),
+        (&source_pool.token_a_fees_vault, 4),
+        (&source_pool.token_b_fees_vault, 5),
+        (&destination_pool.token_a_vault, 109),
+        (&destination_pool.token_b_vault, 110),
+        (&source_pool.admin.token_a_ata, 479),
+        (&source_pool.admin.token_b_ata, 485),
+    ] {
+        assert_eq!(token_operations::balance(&mut ctx, vault).await, expected);
+    }
+}
+
+#[tokio::test]
+pub async fn test_rebalance_across_pools_exceeds_withdrawn_amounts() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (source_pool, destination_pool) = new_pools(&mut ctx).await;
+
+    // 1/10 of the source pool is worth 100 of each token, 2x the destination pool needs 200
+    assert_eq!(
+        client::rebalance_across_pools(
+            &mut ctx,
+            &source_pool,
+            &destination_pool,
+            RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 10, INITIAL_POOL_TOKENS * 2),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_rebalance_across_pools() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (source_pool, destination_pool) = new_pools(&mut ctx).await;
+
+    // same pool
+    {
+        assert_eq!(
+            client::rebalance_across_pools(
+                &mut ctx,
+                &source_pool,
+                &source_pool,
+                RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 2),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::IncorrectSwapAccount)
+        );
+    }
+
+    // wrong admin
+    {
+        let mut cloned_pool = source_pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::rebalance_across_pools(
+                &mut ctx,
+                &cloned_pool,
+                &destination_pool,
+                RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 10),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // fees vault of another pool
+    {
+        let mut cloned_pool = source_pool.clone();
+        cloned_pool.token_a_fees_vault = destination_pool.token_a_fees_vault;
+
+        assert_eq!(
+            client::rebalance_across_pools(
+                &mut ctx,
+                &cloned_pool,
+                &destination_pool,
+                RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 10),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeAccount)
+        );
+    }
+
+    // destination pool vault of another pool
+    {
+        let mut cloned_pool = destination_pool.clone();
+        cloned_pool.token_a_vault = source_pool.token_a_vault;
+
+        assert_eq!(
+            client::rebalance_across_pools(
+                &mut ctx,
+                &source_pool,
+                &cloned_pool,
+                RebalanceAcrossPools::new(INITIAL_POOL_TOKENS / 2, INITIAL_POOL_TOKENS / 10),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::IncorrectSwapAccount)
+        );
+    }
+}
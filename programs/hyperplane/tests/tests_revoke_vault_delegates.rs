@@ -0,0 +1,106 @@
+mod common;
+
+use anchor_lang::{prelude::Pubkey, solana_program::program_option::COption};
+use anchor_spl::token_interface::spl_token_2022::state::Account as TokenAccount;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::Swap,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::program_pack::Pack;
+
+use crate::common::{
+    fixtures, setup,
+    setup::{default_supply, new_keypair},
+    types::{SwapPairSpec, TestContext},
+    utils,
+};
+
+#[tokio::test]
+pub async fn test_revoke_vault_delegates() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let attacker = Pubkey::new_unique();
+    utils::set_token_account_authorities(
+        &mut ctx,
+        &pool.token_a_vault,
+        COption::Some(attacker),
+        u64::MAX,
+        COption::None,
+    )
+    .await;
+    utils::set_token_account_authorities(
+        &mut ctx,
+        &pool.token_b_fees_vault,
+        COption::None,
+        0,
+        COption::Some(attacker),
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::VaultDelegateSet)
+    );
+
+    // anyone can revoke
+    let signer = new_keypair(&mut ctx, fixtures::Sol::one()).await;
+    client::revoke_vault_delegates(&mut ctx, &pool, signer.as_ref())
+        .await
+        .unwrap();
+
+    let token_a_vault = get_token_account(&mut ctx, &pool.token_a_vault).await;
+    assert_eq!(token_a_vault.delegate, COption::None);
+    assert_eq!(token_a_vault.delegated_amount, 0);
+    let token_b_fees_vault = get_token_account(&mut ctx, &pool.token_b_fees_vault).await;
+    assert_eq!(token_b_fees_vault.close_authority, COption::None);
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // a no-op once the vaults are clean
+    client::revoke_vault_delegates(&mut ctx, &pool, signer.as_ref())
+        .await
+        .unwrap();
+}
+
+async fn get_token_account(ctx: &mut TestContext, address: &Pubkey) -> TokenAccount {
+    let account = ctx
+        .context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    TokenAccount::unpack_from_slice(&account.data[..TokenAccount::LEN]).unwrap()
+}
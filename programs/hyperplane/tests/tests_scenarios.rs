@@ -0,0 +1,65 @@
+mod common;
+
+use common::scenario::Scenario;
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+fn trade_fee_bps(bps: u64) -> Fees {
+    Fees {
+        trade_fee_numerator: bps,
+        trade_fee_denominator: 10_000,
+        ..Fees::default()
+    }
+}
+
+#[tokio::test]
+pub async fn test_scenario_constant_product_swaps() {
+    Scenario::new()
+        .init(CurveUserParameters::ConstantProduct, trade_fee_bps(30))
+        .user((1_000_000, 1_000_000))
+        .swap(0, TradeDirection::AtoB, 1_000)
+        .expect_out(996)
+        .expect_quoted()
+        .swap(0, TradeDirection::BtoA, 1_000_000)
+        .expect_quoted()
+        .run()
+        .await;
+}
+
+#[tokio::test]
+pub async fn test_scenario_stable_swaps_match_the_off_chain_quote() {
+    Scenario::new()
+        .init(CurveUserParameters::Stable { amp: 100 }, trade_fee_bps(4))
+        .user((100_000_000000, 0))
+        .swap(0, TradeDirection::AtoB, 10_000_000000)
+        .expect_quoted()
+        .swap(0, TradeDirection::AtoB, 90_000_000000)
+        .expect_quoted()
+        .run()
+        .await;
+}
+
+#[tokio::test]
+pub async fn test_scenario_offset_curve_rejects_deposits() {
+    Scenario::new()
+        .init(
+            CurveUserParameters::Offset {
+                token_b_offset: 1_000_000,
+            },
+            Fees::default(),
+        )
+        .initial_supply(1_000_000, 1_000_000)
+        .user((1_000, 1_000))
+        .deposit(0, 1_000)
+        .expect_err(hyperplane_error!(SwapError::UnsupportedCurveOperation))
+        .swap(0, TradeDirection::BtoA, 1_000)
+        .expect_quoted()
+        .expect_out(499)
+        .expect_reserves(1_000_000 - 499, 1_001_000)
+        .run()
+        .await;
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{error::SwapError, ix::UpdateFeatureSet, state::Feature};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{client::instructions, fixtures::Sol, setup::new_keypair, utils};
+
+#[tokio::test]
+pub async fn test_security_initialize_global_accounts_requires_upgrade_authority() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let upgrade_authority = new_keypair(&mut ctx, Sol::one()).await;
+    utils::set_upgrade_authority(&mut ctx, &upgrade_authority.pubkey());
+    let wrong_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+    assert_eq!(
+        send_tx!(
+            ctx,
+            [instructions::initialize_feature_set(&wrong_admin.pubkey())],
+            wrong_admin.as_ref()
+        )
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvaliPoolAdmin)
+    );
+    assert_eq!(
+        send_tx!(
+            ctx,
+            [instructions::initialize_fee_tier_registry(
+                &wrong_admin.pubkey()
+            )],
+            wrong_admin.as_ref()
+        )
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvaliPoolAdmin)
+    );
+    assert_eq!(
+        send_tx!(
+            ctx,
+            [instructions::initialize_protocol_fee_config(
+                &wrong_admin.pubkey(),
+                &wrong_admin.pubkey()
+            )],
+            wrong_admin.as_ref()
+        )
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvaliPoolAdmin)
+    );
+
+    send_tx!(
+        ctx,
+        [instructions::initialize_feature_set(
+            &upgrade_authority.pubkey()
+        )],
+        upgrade_authority.as_ref()
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_security_update_feature_set() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let admin = new_keypair(&mut ctx, Sol::one()).await;
+    client::initialize_feature_set(&mut ctx, &admin)
+        .await
+        .unwrap();
+
+    // wrong admin
+    {
+        let wrong_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::update_feature_set(
+                &mut ctx,
+                &wrong_admin,
+                UpdateFeatureSet::new(Feature::PermissionedSwaps, true),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // already initialized
+    {
+        let other_admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert!(client::initialize_feature_set(&mut ctx, &other_admin)
+            .await
+            .is_err());
+    }
+}
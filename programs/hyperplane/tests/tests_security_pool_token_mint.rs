@@ -0,0 +1,109 @@
+mod common;
+
+use anchor_lang::solana_program::program_option::COption;
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{Deposit, Withdraw},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signature::Signer;
+
+use crate::common::{
+    fixtures, setup,
+    setup::kp,
+    token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+    utils,
+};
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        InitialSupply::new(100, 100),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await
+}
+
+#[tokio::test]
+pub async fn test_security_pool_token_mint_authority_changed() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+    let lp = setup::new_lp_user(&mut ctx, &pool, (50, 50)).await;
+    let lp_pool_tokens = token_operations::balance(&mut ctx, &lp.pool_token_ata).await;
+    let depositor = setup::new_pool_user(&mut ctx, &pool, (50, 50)).await;
+
+    utils::set_mint_authorities(
+        &mut ctx,
+        &pool.pool_token_mint,
+        COption::Some(kp().pubkey()),
+        COption::None,
+    )
+    .await;
+
+    assert_eq!(
+        client::deposit(&mut ctx, &pool, &depositor, Deposit::new(10, 50, 50, None))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidPoolTokenMintAuthority)
+    );
+    assert_eq!(
+        client::withdraw(
+            &mut ctx,
+            &pool,
+            &lp,
+            Withdraw::new(lp_pool_tokens, 0, 0, false, None)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidPoolTokenMintAuthority)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_pool_token_mint_freeze_authority_set() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+    let lp = setup::new_lp_user(&mut ctx, &pool, (50, 50)).await;
+    let lp_pool_tokens = token_operations::balance(&mut ctx, &lp.pool_token_ata).await;
+    let depositor = setup::new_pool_user(&mut ctx, &pool, (50, 50)).await;
+
+    utils::set_mint_authorities(
+        &mut ctx,
+        &pool.pool_token_mint,
+        COption::Some(pool.authority),
+        COption::Some(kp().pubkey()),
+    )
+    .await;
+
+    assert_eq!(
+        client::deposit(&mut ctx, &pool, &depositor, Deposit::new(10, 50, 50, None))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidFreezeAuthority)
+    );
+    assert_eq!(
+        client::withdraw(
+            &mut ctx,
+            &pool,
+            &lp,
+            Withdraw::new(lp_pool_tokens, 0, 0, false, None)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidFreezeAuthority)
+    );
+}
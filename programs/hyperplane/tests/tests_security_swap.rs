@@ -46,7 +46,7 @@ pub async fn test_security_swap() {
     .await;
 
     let user = setup::new_pool_user(&mut ctx, &pool, (51, 0)).await;
-    let swap = Swap::new(50, 47);
+    let swap = Swap::new(50, 47, None);
 
     // wrong signer
     {
@@ -55,7 +55,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -76,7 +76,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -95,7 +95,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -116,7 +116,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -137,7 +137,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -158,7 +158,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -179,7 +179,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -205,7 +205,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -231,7 +231,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -257,7 +257,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -285,7 +285,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -314,7 +314,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -341,7 +341,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -368,7 +368,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -397,7 +397,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -424,7 +424,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &pool,
                 &cloned_lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -443,7 +443,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -462,7 +462,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
@@ -481,7 +481,7 @@ pub async fn test_security_withdraw() {
                 &mut ctx,
                 &cloned_pool,
                 &lp,
-                Withdraw::new(lp_pool_tokens, 1, 1)
+                Withdraw::new(lp_pool_tokens, 1, 1, false, None)
             )
             .await
             .unwrap_err()
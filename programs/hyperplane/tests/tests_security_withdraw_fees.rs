@@ -54,7 +54,7 @@ pub async fn test_security_withdraw_fees() {
         &pool,
         &user,
         TradeDirection::AtoB,
-        Swap::new(50, 47),
+        Swap::new(50, 47, None),
     )
     .await
     .unwrap();
@@ -63,7 +63,7 @@ pub async fn test_security_withdraw_fees() {
         &pool,
         &user,
         TradeDirection::BtoA,
-        Swap::new(46, 1),
+        Swap::new(46, 1, None),
     )
     .await
     .unwrap();
@@ -0,0 +1,98 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup,
+    setup::default_supply,
+    state, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
+
+#[tokio::test]
+pub async fn test_prevent_self_trades() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let admin = PoolUserAccounts::new(
+        pool.admin.admin.clone(),
+        pool.admin.token_a_ata,
+        pool.admin.token_b_ata,
+        pool.admin.pool_token_ata.pubkey(),
+    );
+    token_operations::mint_to(
+        &mut ctx,
+        &pool.token_a_token_program,
+        &pool.token_a_mint,
+        &admin.token_a_ata,
+        2_000,
+    )
+    .await
+    .unwrap();
+
+    // self trades are allowed by default
+    client::swap(
+        &mut ctx,
+        &pool,
+        &admin,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::PreventSelfTrades,
+            UpdatePoolConfigValue::Bool(true),
+        ),
+    )
+    .await
+    .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.prevent_self_trades());
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &admin,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::SelfTradeNotAllowed)
+    );
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    )
+    .await
+    .unwrap();
+}
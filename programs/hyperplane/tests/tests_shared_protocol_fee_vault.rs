@@ -0,0 +1,203 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 1,
+        trade_fee_denominator: 100,
+        owner_trade_fee_numerator: 1,
+        owner_trade_fee_denominator: 100,
+        ..Default::default()
+    }
+}
+
+/// Two pools of the same pair with a protocol fee, and the protocol fee authority
+async fn setup_pools(
+    ctx: &mut TestContext,
+) -> (SwapPoolAccounts, SwapPoolAccounts, PoolUserAccounts) {
+    let pool = fixtures::new_pool(
+        ctx,
+        fees(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let sibling = fixtures::new_sibling_pool(
+        ctx,
+        &pool,
+        fees(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    for pool in [&pool, &sibling] {
+        client::update_pool_config(
+            ctx,
+            pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::ProtocolFeeBps,
+                UpdatePoolConfigValue::U64(1_000),
+            ),
+        )
+        .await
+        .unwrap();
+    }
+    let authority = setup::new_pool_user(ctx, &pool, (0, 0)).await;
+    client::initialize_protocol_fee_config(ctx, &pool.admin.admin, &authority.pubkey())
+        .await
+        .unwrap();
+    (pool, sibling, authority)
+}
+
+async fn swap_a_to_b(ctx: &mut TestContext, pool: &SwapPoolAccounts, amount_in: u64) {
+    let user = setup::new_pool_user(ctx, pool, (amount_in, 0)).await;
+    client::swap(
+        ctx,
+        pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(amount_in, 0, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_shared_protocol_fee_vault() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (pool, sibling, authority) = setup_pools(&mut ctx).await;
+    client::initialize_protocol_fee_vault(&mut ctx, &pool, &authority, AorB::A)
+        .await
+        .unwrap();
+    let (protocol_fee_vault, _bump) = seeds::pda::protocol_fee_vault_pda(&pool.token_a_mint);
+
+    // owner fee of 1% on the 1_000_000 swapped, the protocol takes a tenth of it
+    swap_a_to_b(&mut ctx, &pool, 1_000_000).await;
+    swap_a_to_b(&mut ctx, &sibling, 2_000_000).await;
+
+    client::sweep_protocol_fees(&mut ctx, &pool, AorB::A)
+        .await
+        .unwrap();
+    client::sweep_protocol_fees(&mut ctx, &sibling, AorB::A)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &protocol_fee_vault).await,
+        3_000
+    );
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.protocol_fees_a, 0);
+    assert_eq!(pool_state.protocol_fees_swept_a, 1_000);
+    let sibling_state = state::get_pool(&mut ctx, &sibling).await;
+    assert_eq!(sibling_state.protocol_fees_a, 0);
+    assert_eq!(sibling_state.protocol_fees_swept_a, 2_000);
+
+    // the swept fees are no longer owed by the pools
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        9_000
+    );
+    assert_eq!(
+        client::sweep_protocol_fees(&mut ctx, &pool, AorB::A)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+
+    client::withdraw_shared_protocol_fees(&mut ctx, &pool, &authority, AorB::A)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &authority.token_a_ata).await,
+        3_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &protocol_fee_vault).await,
+        0
+    );
+    assert_eq!(
+        client::withdraw_shared_protocol_fees(&mut ctx, &pool, &authority, AorB::A)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_shared_protocol_fee_vault() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let (pool, _sibling, authority) = setup_pools(&mut ctx).await;
+    let imposter = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+
+    // wrong authority creating the vault
+    {
+        assert_eq!(
+            client::initialize_protocol_fee_vault(&mut ctx, &pool, &imposter, AorB::A)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    client::initialize_protocol_fee_vault(&mut ctx, &pool, &authority, AorB::A)
+        .await
+        .unwrap();
+    swap_a_to_b(&mut ctx, &pool, 1_000_000).await;
+
+    // pool vault instead of the fees vault
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.token_a_fees_vault = pool.token_a_vault;
+
+        assert_eq!(
+            client::sweep_protocol_fees(&mut ctx, &cloned_pool, AorB::A)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeAccount)
+        );
+    }
+
+    client::sweep_protocol_fees(&mut ctx, &pool, AorB::A)
+        .await
+        .unwrap();
+
+    // wrong authority withdrawing
+    {
+        assert_eq!(
+            client::withdraw_shared_protocol_fees(&mut ctx, &pool, &imposter, AorB::A)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+}
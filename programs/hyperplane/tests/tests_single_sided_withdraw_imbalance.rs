@@ -0,0 +1,163 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::AorB, fees::Fees},
+    error::SwapError,
+    ix::{
+        DepositBothExactIn, DepositSingle, UpdatePoolConfig, Withdraw, WithdrawExactAmountsOut,
+        WithdrawSingle,
+    },
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn set_max_imbalance(ctx: &mut TestContext, pool: &SwapPoolAccounts, max_bps: u64) {
+    client::update_pool_config(
+        ctx,
+        pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
+            UpdatePoolConfigValue::U64(max_bps),
+        ),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_single_sided_withdrawals_off_above_imbalance() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    // 3 A for 1 B, an imbalance of 5_000 bps
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_500_000_000, 500_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    set_max_imbalance(&mut ctx, &pool, 2_000).await;
+
+    assert_eq!(
+        client::withdraw_single(
+            &mut ctx,
+            &pool,
+            &admin,
+            AorB::B,
+            WithdrawSingle::new(1_000_000, u64::MAX),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PoolImbalanced)
+    );
+    // only the remainder of B off the pool ratio is single sided
+    assert_eq!(
+        client::withdraw_exact_amounts_out(
+            &mut ctx,
+            &pool,
+            &admin,
+            WithdrawExactAmountsOut::new(1_000_000, 1_000_000, u64::MAX),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PoolImbalanced)
+    );
+
+    // LPs can still leave in kind
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &admin,
+        Withdraw::new(1_000_000, 0, 0, false, None),
+    )
+    .await
+    .unwrap();
+
+    // no pool tokens are minted against a single token at the depegged price either
+    assert_eq!(
+        client::deposit_single(
+            &mut ctx,
+            &pool,
+            &admin,
+            AorB::A,
+            DepositSingle::new(100_000, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PoolImbalanced)
+    );
+    assert_eq!(
+        client::deposit_both_exact_in(
+            &mut ctx,
+            &pool,
+            &admin,
+            DepositBothExactIn::new(100_000, 0, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::PoolImbalanced)
+    );
+
+    set_max_imbalance(&mut ctx, &pool, 6_000).await;
+    let token_b_before = token_operations::balance(&mut ctx, &admin.token_b_ata).await;
+    client::withdraw_single(
+        &mut ctx,
+        &pool,
+        &admin,
+        AorB::B,
+        WithdrawSingle::new(1_000_000, u64::MAX),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_b_ata).await,
+        token_b_before + 1_000_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_max_imbalance_rejected_on_unpegged_curves() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_500_000_000, 500_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    // a constant product pool is balanced at any price, there is no imbalance to limit
+    assert_eq!(
+        client::update_pool_config(
+            &mut ctx,
+            &pool,
+            UpdatePoolConfig::new(
+                UpdatePoolConfigMode::MaxSingleSidedWithdrawImbalanceBps,
+                UpdatePoolConfigValue::U64(2_000),
+            ),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::UnsupportedCurveOperation)
+    );
+    // turning the limit off is always allowed
+    set_max_imbalance(&mut ctx, &pool, 0).await;
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{curve::fees::Fees, CurveUserParameters};
+use solana_program_test::tokio::{self};
+use solana_sdk::{pubkey::Pubkey, signer::Signer, system_instruction};
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup::{default_supply, new_keypair},
+    types::{SwapPairSpec, TestContext},
+};
+
+async fn lamports(ctx: &mut TestContext, address: &Pubkey) -> u64 {
+    ctx.context
+        .banks_client
+        .get_balance(*address)
+        .await
+        .unwrap()
+}
+
+async fn data_len(ctx: &mut TestContext, address: &Pubkey) -> usize {
+    ctx.context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .map_or(0, |account| account.data.len())
+}
+
+#[tokio::test]
+pub async fn test_skim_lamports_above_rent_exemption_to_treasury() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let treasury = new_keypair(&mut ctx, Sol::one()).await;
+
+    let excess = 1_000_000;
+    let pool_authority_rent = ctx.rent.minimum_balance(0);
+    let pool_authority_top_up =
+        pool_authority_rent.saturating_sub(lamports(&mut ctx, &pool.authority).await) + excess;
+    send_tx!(
+        ctx,
+        [
+            system_instruction::transfer(&ctx.context.payer.pubkey(), &pool.pubkey(), excess),
+            system_instruction::transfer(&ctx.context.payer.pubkey(), &pool.curve, excess),
+            system_instruction::transfer(
+                &ctx.context.payer.pubkey(),
+                &pool.authority,
+                pool_authority_top_up
+            )
+        ],
+    )
+    .unwrap();
+
+    client::skim_lamports(&mut ctx, &pool, &treasury.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        lamports(&mut ctx, &treasury.pubkey()).await,
+        Sol::one() + 3 * excess
+    );
+    for address in [pool.pubkey(), pool.curve, pool.authority] {
+        let rent = ctx.rent.minimum_balance(data_len(&mut ctx, &address).await);
+        assert_eq!(lamports(&mut ctx, &address).await, rent);
+    }
+
+    // nothing left to skim
+    client::skim_lamports(&mut ctx, &pool, &treasury.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        lamports(&mut ctx, &treasury.pubkey()).await,
+        Sol::one() + 3 * excess
+    );
+}
+
+#[tokio::test]
+pub async fn test_skim_lamports_fails_with_non_admin_signer() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let mut pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+    assert_eq!(
+        client::skim_lamports(&mut ctx, &pool, &Pubkey::new_unique())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        anchor_error!(ErrorCode::ConstraintHasOne)
+    );
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_slot_outflow_circuit_breaker() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 0)).await;
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::MaxSlotOutflowBps,
+            UpdatePoolConfigValue::U64(200),
+        ),
+    )
+    .await
+    .unwrap();
+
+    // ~19_600 B out of the 20_000 allowed in the slot
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_001, 0, None),
+    )
+    .await
+    .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.slot_outflow_b > 19_000);
+    assert_eq!(pool_state.slot_outflow_a, 0);
+
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(5_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::SlotOutflowExceeded)
+    );
+
+    // B sent back into the pool in the same slot nets off the B outflow
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(5_000, 0, None),
+    )
+    .await
+    .unwrap();
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert!(pool_state.slot_outflow_b < 15_000);
+    assert!(pool_state.slot_outflow_a > 4_000);
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(5_002, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // the limit applies again from the reserves at the start of the next slot
+    let slot = ctx.context.banks_client.get_root_slot().await.unwrap();
+    ctx.context.warp_to_slot(slot + 10).unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(5_001, 0, None),
+    )
+    .await
+    .unwrap();
+}
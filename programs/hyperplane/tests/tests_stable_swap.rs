@@ -109,6 +109,7 @@ pub async fn test_swap_a_to_b() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 47,
+            deadline: None,
         },
     )
     .await
@@ -166,6 +167,7 @@ pub async fn test_swap_b_to_a() {
         Swap {
             amount_in: 50,
             minimum_amount_out: 47,
+            deadline: None,
         },
     )
     .await
@@ -205,6 +207,7 @@ async fn test_swap_does_not_lose_value_from_rounding() {
             let swap = Swap {
                 amount_in,
                 minimum_amount_out: 0,
+                deadline: None,
             };
             [
                 (TradeDirection::AtoB, swap.clone()),
@@ -266,3 +269,48 @@ async fn test_swap_does_not_lose_value_from_rounding() {
         initial_balance
     );
 }
+
+#[tokio::test]
+pub async fn test_swap_between_different_decimals() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    // 1_000 tokens of 6 decimals A and 9 decimals B
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000_000),
+        SwapPairSpec::spl_tokens(6, 9),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 1_000_000_000)).await;
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+    // 1 token A is worth about 1 token B
+    let user_b_balance = token_operations::balance(&mut ctx, &user.token_b_ata).await;
+    assert!(user_b_balance > 1_999_000_000);
+    assert!(user_b_balance < 2_000_000_000);
+
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+    let user_a_balance = token_operations::balance(&mut ctx, &user.token_a_ata).await;
+    assert!(user_a_balance > 999_000);
+    assert!(user_a_balance <= 1_000_000);
+}
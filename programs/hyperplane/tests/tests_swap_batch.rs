@@ -0,0 +1,135 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, SwapBatch},
+    CurveUserParameters, InitialSupply, SwapFill,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    fixtures::new_pool(
+        ctx,
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await
+}
+
+fn fill(amount_in: u64, minimum_amount_out: u64) -> SwapFill {
+    SwapFill {
+        amount_in,
+        minimum_amount_out,
+    }
+}
+
+#[tokio::test]
+pub async fn test_swap_batch_matches_sequential_swaps() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let batch_pool = new_pool(&mut ctx).await;
+    let batch_user = setup::new_pool_user(&mut ctx, &batch_pool, (30_000, 0)).await;
+    client::swap_batch(
+        &mut ctx,
+        &batch_pool,
+        &batch_user,
+        TradeDirection::AtoB,
+        SwapBatch::new(vec![fill(10_000, 0), fill(5_000, 0), fill(15_000, 0)], None),
+    )
+    .await
+    .unwrap();
+
+    let pool = new_pool(&mut ctx).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (30_000, 0)).await;
+    for amount_in in [10_000, 5_000, 15_000] {
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(amount_in, 0, None),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &batch_user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &batch_user.token_b_ata).await,
+        token_operations::balance(&mut ctx, &user.token_b_ata).await
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &batch_pool.token_a_vault).await,
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &batch_pool.token_b_vault).await,
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_batch_fails_if_one_fill_exceeds_its_slippage() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (20_000, 0)).await;
+    // the second fill gets less than the first at the same size
+    assert_eq!(
+        client::swap_batch(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            SwapBatch::new(vec![fill(10_000, 9_800), fill(10_000, 9_800)], None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        20_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_batch_without_fills_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = new_pool(&mut ctx).await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (20_000, 0)).await;
+    assert_eq!(
+        client::swap_batch(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            SwapBatch::new(vec![], None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidSwapBatch)
+    );
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees, error::SwapError, ix::SwapRouteTwoHop, CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{SwapPairSpec, TokenSpec},
+};
+
+const CURVE: CurveUserParameters = CurveUserParameters::ConstantPrice {
+    token_b_price: 1,
+    spread_bps: 0,
+};
+
+#[tokio::test]
+pub async fn test_swap_route_two_hop() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let first_pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CURVE,
+    )
+    .await;
+    let second_pool = fixtures::new_chained_pool(
+        &mut ctx,
+        &first_pool,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        TokenSpec::default(),
+        CURVE,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &first_pool, (1_000, 0)).await;
+    let destination_user_ata = token_operations::create_token_account(
+        &mut ctx,
+        &second_pool.token_b_token_program,
+        &second_pool.token_b_mint,
+        &user.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // the 1% trade fee of the second pool leaves 990 out of the 1_000 intermediate tokens
+    assert_eq!(
+        client::swap_route_two_hop(
+            &mut ctx,
+            &first_pool,
+            &second_pool,
+            &user,
+            &destination_user_ata,
+            SwapRouteTwoHop::new(1_000, 991),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::swap_route_two_hop(
+        &mut ctx,
+        &first_pool,
+        &second_pool,
+        &user,
+        &destination_user_ata,
+        SwapRouteTwoHop::new(1_000, 990),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &destination_user_ata).await,
+        990
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &first_pool.token_b_vault).await,
+        999_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &second_pool.token_a_vault).await,
+        1_001_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &second_pool.token_b_vault).await,
+        999_010
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_route_two_hop_fails_through_the_same_pool_twice() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CURVE,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000, 0)).await;
+    assert_eq!(
+        client::swap_route_two_hop(
+            &mut ctx,
+            &pool,
+            &pool,
+            &user,
+            &user.token_b_ata,
+            SwapRouteTwoHop::new(1_000, 0),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::IncorrectSwapAccount)
+    );
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn set_limit(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    mode: UpdatePoolConfigMode,
+    bps: u64,
+) {
+    client::update_pool_config(
+        ctx,
+        pool,
+        UpdatePoolConfig::new(mode, UpdatePoolConfigValue::U64(bps)),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_max_trade_size() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 0)).await;
+    set_limit(&mut ctx, &pool, UpdatePoolConfigMode::MaxTradeSizeBps, 100).await;
+
+    // 11_000 A buys ~10_880 B, more than 1% of the B reserves
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::AtoB,
+            Swap::new(11_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::TradeTooLarge)
+    );
+
+    // 10_000 A buys 9_900 B
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(10_000, 0, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        9_900
+    );
+
+    // lifting the limit allows the larger swap
+    set_limit(&mut ctx, &pool, UpdatePoolConfigMode::MaxTradeSizeBps, 0).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(50_000, 0, None),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_max_price_impact() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 100_000)).await;
+    set_limit(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfigMode::MaxPriceImpactBps,
+        200,
+    )
+    .await;
+
+    // 1_000_000 A : 1_020_000 B moves the price by ~4%
+    assert_eq!(
+        client::swap(
+            &mut ctx,
+            &pool,
+            &user,
+            TradeDirection::BtoA,
+            Swap::new(20_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::TradeTooLarge)
+    );
+
+    // 1_000_000 A : 1_005_000 B moves the price by ~1%
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(5_000, 0, None),
+    )
+    .await
+    .unwrap();
+}
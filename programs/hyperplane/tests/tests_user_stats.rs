@@ -0,0 +1,121 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::Swap,
+    state::UserStats,
+    utils::seeds,
+    CurveUserParameters,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, setup::default_supply, state, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_swaps_recorded_in_user_stats() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Default::default()
+        },
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000, 10_000)).await;
+    client::initialize_user_stats(&mut ctx, &pool, &user)
+        .await
+        .unwrap();
+    let (user_stats, _bump) = seeds::pda::user_stats_pda(&pool.pubkey(), &user.pubkey());
+
+    // not recorded, the stats are opt-in per swap
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    client::swap_with_user_stats(
+        &mut ctx,
+        &pool,
+        &user,
+        &user_stats,
+        TradeDirection::AtoB,
+        Swap::new(1_000, 0, None),
+    )
+    .await
+    .unwrap();
+    client::swap_with_user_stats(
+        &mut ctx,
+        &pool,
+        &user,
+        &user_stats,
+        TradeDirection::BtoA,
+        Swap::new(2_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    let stats = state::get::<UserStats>(&mut ctx, user_stats).await;
+    assert_eq!(stats.pool, pool.pubkey());
+    assert_eq!(stats.owner, user.pubkey());
+    assert_eq!(stats.trade_count, 2);
+    assert_eq!(stats.volume_token_a, 1_000);
+    assert_eq!(stats.volume_token_b, 2_000);
+    assert_eq!(stats.fees_paid_token_a, 20);
+    assert_eq!(stats.fees_paid_token_b, 40);
+    assert!(stats.last_trade_slot > 0);
+}
+
+#[tokio::test]
+pub async fn test_swap_with_user_stats_of_another_owner_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        default_supply(),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_000, 0)).await;
+    let other_user = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::initialize_user_stats(&mut ctx, &pool, &other_user)
+        .await
+        .unwrap();
+    let (other_user_stats, _bump) =
+        seeds::pda::user_stats_pda(&pool.pubkey(), &other_user.pubkey());
+
+    assert_eq!(
+        client::swap_with_user_stats(
+            &mut ctx,
+            &pool,
+            &user,
+            &other_user_stats,
+            TradeDirection::AtoB,
+            Swap::new(1_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidUserStats)
+    );
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{Deposit, DepositSingle, Swap},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, TokenSpec},
+};
+
+#[tokio::test]
+pub async fn test_deposit_with_transfer_fees_fills_the_vault_exactly() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::new(TokenSpec::transfer_fees(10), TokenSpec::default()),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    // 10 bps transfer fee taken on the initial supply
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        999_000
+    );
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 100_000)).await;
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &user,
+        Deposit {
+            pool_token_amount: pool_token_supply / 10,
+            maximum_token_a_amount: 99_900,
+            maximum_token_b_amount: 100_000,
+            deadline: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // the user pays the transfer fee on top of the 99_900 the pool tokens are worth
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_098_900
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_100_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.pool_token_ata).await,
+        pool_token_supply / 10
+    );
+}
+
+#[tokio::test]
+pub async fn test_deposit_single_with_transfer_fees_fills_the_vault_exactly() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::new(TokenSpec::transfer_fees(10), TokenSpec::default()),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (10_011, 0)).await;
+    client::deposit_single(
+        &mut ctx,
+        &pool,
+        &user,
+        AorB::A,
+        DepositSingle::new(10_000, 1),
+    )
+    .await
+    .unwrap();
+
+    // 10_011 nets exactly 10_000 after the 10 bps transfer fee
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        1_009_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_fails_when_the_source_vault_receives_more_than_computed() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            host_fee_numerator: 10,
+            host_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (100_000, 0)).await;
+    // the host fee lands in the source vault on top of the amount the swap was computed on
+    let source_vault_as_host = PoolUserAccounts {
+        user: user.user.clone(),
+        token_a_ata: pool.token_a_vault,
+        token_b_ata: pool.token_b_vault,
+        pool_token_ata: user.pool_token_ata,
+    };
+
+    assert_eq!(
+        client::swap_with_host_fees(
+            &mut ctx,
+            &pool,
+            &user,
+            Some(&source_vault_as_host),
+            TradeDirection::AtoB,
+            Swap::new(100_000, 0, None),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::VaultBalanceMismatch)
+    );
+
+    let host = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    client::swap_with_host_fees(
+        &mut ctx,
+        &pool,
+        &user,
+        Some(&host),
+        TradeDirection::AtoB,
+        Swap::new(100_000, 0, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &host.token_a_ata).await,
+        100
+    );
+}
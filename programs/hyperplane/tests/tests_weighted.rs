@@ -0,0 +1,103 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{base::CurveType, calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Initialize, Swap},
+    state::WeightedCurve,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{fixtures, setup, state, token_operations, types::SwapPairSpec};
+
+#[tokio::test]
+pub async fn test_success_init_weighted_pool() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::Weighted {
+            weight_a: 80,
+            weight_b: 20,
+        },
+    )
+    .await;
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.curve_type, CurveType::Weighted as u64);
+    assert_eq!(pool_state.swap_curve, pool.curve);
+
+    let curve = state::get::<WeightedCurve>(&mut ctx, pool.curve).await;
+    assert_eq!(curve.weight_a, 80);
+    assert_eq!(curve.weight_b, 20);
+}
+
+#[tokio::test]
+pub async fn test_init_weighted_pool_fails_with_weights_not_adding_up() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(1_000_000, 1_000_000);
+    let pool = setup::new_pool_accs(&mut ctx, SwapPairSpec::default(), &initial_supply).await;
+
+    assert_eq!(
+        client::initialize_pool(
+            &mut ctx,
+            &pool,
+            Initialize {
+                fees: Fees::default(),
+                initial_supply,
+                curve_parameters: CurveUserParameters::Weighted {
+                    weight_a: 80,
+                    weight_b: 80,
+                },
+                burn_owner_fees: false,
+                strict_token_program: false,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidCurve, 1)
+    );
+}
+
+#[tokio::test]
+pub async fn test_swap_prices_token_a_by_its_weight() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000_000, 1_000_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::Weighted {
+            weight_a: 80,
+            weight_b: 20,
+        },
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 0, None),
+    )
+    .await
+    .unwrap();
+
+    // an 80/20 pool holding equal amounts prices token A at 4 tokens B
+    let received = token_operations::balance(&mut ctx, &user.token_b_ata).await;
+    assert!(received < 4_000_000);
+    assert!(received > 3_999_000);
+}
@@ -4,13 +4,17 @@ use common::{client, runner};
 use hyperplane::{
     curve::{calculator::INITIAL_SWAP_POOL_AMOUNT, fees::Fees},
     error::SwapError,
+    instructions::withdraw::DUST_POOL_TOKEN_AMOUNT,
     ix::Withdraw,
     CurveUserParameters, InitialSupply,
 };
 use solana_program_test::tokio::{self};
 use solana_sdk::signer::Signer;
 
-use crate::common::{fixtures, setup, token_operations, types::SwapPairSpec};
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
 
 #[tokio::test]
 pub async fn test_successful_withdraw_full_initial_balance_with_fees() {
@@ -39,7 +43,7 @@ pub async fn test_successful_withdraw_full_initial_balance_with_fees() {
         &mut ctx,
         &pool,
         &pool.admin.clone().into(),
-        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64, 99, 99),
+        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64, 99, 99, false, None),
     )
     .await
     .unwrap();
@@ -96,7 +100,7 @@ pub async fn test_successful_withdraw_lp_user_full_balance_with_fees() {
         &mut ctx,
         &pool,
         &lp,
-        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 2, 49, 49),
+        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 2, 49, 49, false, None),
     )
     .await
     .unwrap();
@@ -153,7 +157,13 @@ pub async fn test_successful_withdraw_partial_balance() {
         &mut ctx,
         &pool,
         &lp,
-        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 4, 2_475_000, 2_475_000),
+        Withdraw::new(
+            INITIAL_SWAP_POOL_AMOUNT as u64 / 4,
+            2_475_000,
+            2_475_000,
+            false,
+            None,
+        ),
     )
     .await
     .unwrap();
@@ -213,7 +223,13 @@ pub async fn test_withdraw_more_than_owned_fails() {
             &mut ctx,
             &pool,
             &lp,
-            Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64, 2_475_000, 2_475_000)
+            Withdraw::new(
+                INITIAL_SWAP_POOL_AMOUNT as u64,
+                2_475_000,
+                2_475_000,
+                false,
+                None
+            )
         )
         .await
         .unwrap_err()
@@ -250,16 +266,74 @@ pub async fn test_withdraw_0_fails() {
         &mut ctx,
         &pool,
         &lp,
-        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 4, 0, 0),
+        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 4, 0, 0, false, None),
     )
     .await
     .unwrap();
 
     assert_eq!(
-        client::withdraw(&mut ctx, &pool, &lp, Withdraw::new(0, 1, 1),)
+        client::withdraw(&mut ctx, &pool, &lp, Withdraw::new(0, 1, 1, false, None),)
             .await
             .unwrap_err()
             .unwrap(),
         hyperplane_error!(SwapError::ZeroTradingTokens)
     );
 }
+
+#[tokio::test]
+pub async fn test_withdraw_burns_dust_left_to_the_user_when_asked() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+
+    // not dust, kept
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &admin,
+        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 2, 0, 0, true, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await,
+        INITIAL_SWAP_POOL_AMOUNT as u64 / 2
+    );
+
+    let dust = DUST_POOL_TOKEN_AMOUNT - 1;
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &admin,
+        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64 / 2 - dust, 0, 0, true, None),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await,
+        0
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        0
+    );
+    // the dust share is paid out with the rest
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await,
+        1_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_b_ata).await,
+        1_000_000
+    );
+}
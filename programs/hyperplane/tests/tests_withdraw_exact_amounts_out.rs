@@ -0,0 +1,208 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{UpdatePoolConfig, WithdrawExactAmountsOut},
+    state::{PoolOperation, UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
+
+#[tokio::test]
+pub async fn test_withdraw_exact_amounts_out_balanced() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let pool_tokens_before =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+
+    // the 1% withdrawal fee is paid on top of the amounts, with pool tokens
+    assert_eq!(
+        client::withdraw_exact_amounts_out(
+            &mut ctx,
+            &pool,
+            &admin,
+            WithdrawExactAmountsOut::new(1_000_000, 1_000_000, 1_009_999),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::withdraw_exact_amounts_out(
+        &mut ctx,
+        &pool,
+        &admin,
+        WithdrawExactAmountsOut::new(1_000_000, 1_000_000, 1_010_000),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await,
+        pool_tokens_before - 1_010_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_a_ata).await,
+        1_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_b_ata).await,
+        1_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        10_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        10_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        998_990_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        998_990_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_withdraw_exact_amounts_out_imbalanced() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let pool_tokens_before =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+
+    client::withdraw_exact_amounts_out(
+        &mut ctx,
+        &pool,
+        &admin,
+        WithdrawExactAmountsOut::new(3_000_000, 1_000_000, u64::MAX),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_a_ata).await,
+        3_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_b_ata).await,
+        1_000_000
+    );
+    // 1% trade and owner fees on the 1_000_000 of token A implicitly swapped
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await,
+        10_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        996_990_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        999_000_000
+    );
+    // 1_000_000 for the balanced part, then
+    // 999_000_000 * (1 - sqrt(996_980_000 / 999_000_000)) = 1_010_511.1 for the remainder
+    let pool_tokens_burned =
+        pool_tokens_before - token_operations::balance(&mut ctx, &admin.pool_token_ata).await;
+    assert!((2_010_512..=2_010_600).contains(&pool_tokens_burned));
+}
+
+#[tokio::test]
+pub async fn test_withdraw_exact_amounts_out_remainder_is_single_sided() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::Status,
+            UpdatePoolConfigValue::U64(PoolOperation::SingleSided.flag()),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::withdraw_exact_amounts_out(
+            &mut ctx,
+            &pool,
+            &admin,
+            WithdrawExactAmountsOut::new(3_000_000, 1_000_000, u64::MAX),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::OperationPaused)
+    );
+
+    // balanced amounts do not need the single sided withdrawal
+    client::withdraw_exact_amounts_out(
+        &mut ctx,
+        &pool,
+        &admin,
+        WithdrawExactAmountsOut::new(1_000_000, 1_000_000, 1_000_000),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.token_a_ata).await,
+        1_000_000
+    );
+}
@@ -45,7 +45,7 @@ pub async fn test_successful_withdraw_full_balance() {
         &pool,
         &user,
         TradeDirection::AtoB,
-        Swap::new(50, 47),
+        Swap::new(50, 47, None),
     )
     .await
     .unwrap();
@@ -92,7 +92,7 @@ pub async fn test_successful_withdraw_full_balance_token_b() {
         &pool,
         &user,
         TradeDirection::BtoA,
-        Swap::new(50, 47),
+        Swap::new(50, 47, None),
     )
     .await
     .unwrap();
@@ -139,7 +139,7 @@ pub async fn test_successful_withdraw_full_balance_request_u64_max() {
         &pool,
         &user,
         TradeDirection::AtoB,
-        Swap::new(50, 47),
+        Swap::new(50, 47, None),
     )
     .await
     .unwrap();
@@ -185,7 +185,7 @@ pub async fn test_successful_withdraw_partial_balance() {
         &pool,
         &user,
         TradeDirection::AtoB,
-        Swap::new(1_000_000, 970_000),
+        Swap::new(1_000_000, 970_000, None),
     )
     .await
     .unwrap();
@@ -239,7 +239,7 @@ pub async fn test_withdraw_0_fails() {
         &pool,
         &user,
         TradeDirection::AtoB,
-        Swap::new(50, 47),
+        Swap::new(50, 47, None),
     )
     .await
     .unwrap();
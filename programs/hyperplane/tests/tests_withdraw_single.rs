@@ -0,0 +1,167 @@
+mod common;
+
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::AorB, fees::Fees},
+    error::SwapError,
+    ix::WithdrawSingle,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures, setup, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
+
+#[tokio::test]
+pub async fn test_withdraw_single_token_a() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let pool_tokens_before =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+
+    // supply * (1 - sqrt(0.99)) = 5_012_562.8
+    assert_eq!(
+        client::withdraw_single(
+            &mut ctx,
+            &pool,
+            &admin,
+            AorB::A,
+            WithdrawSingle::new(10_000_000, 5_012_000),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    client::withdraw_single(
+        &mut ctx,
+        &pool,
+        &admin,
+        AorB::A,
+        WithdrawSingle::new(10_000_000, 5_013_000),
+    )
+    .await
+    .unwrap();
+
+    let pool_tokens_burned = pool_tokens_before
+        - token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+    assert!((5_012_563..=5_013_000).contains(&pool_tokens_burned));
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await,
+        10_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        990_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        1_000_000_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_withdraw_single_token_b_pays_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+            ..Fees::default()
+        },
+        InitialSupply::new(1_000_000_000, 1_000_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    client::withdraw_single(
+        &mut ctx,
+        &pool,
+        &pool.admin.clone().into(),
+        AorB::B,
+        WithdrawSingle::new(10_000_000, u64::MAX),
+    )
+    .await
+    .unwrap();
+
+    // 1% trade and owner fees on the 5_000_000 implicitly swapped and 1% withdrawal fee on the
+    // whole amount, the trade fee stays in the vault
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.token_b_ata).await,
+        10_000_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await,
+        150_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        989_850_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_withdraw_single_fails_beyond_reserves_or_balance() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1_000_000, 1_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    assert_eq!(
+        client::withdraw_single(
+            &mut ctx,
+            &pool,
+            &pool.admin.clone().into(),
+            AorB::A,
+            WithdrawSingle::new(1_000_000, u64::MAX),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::EmptyReserves)
+    );
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+    assert_eq!(
+        client::withdraw_single(
+            &mut ctx,
+            &pool,
+            &user,
+            AorB::A,
+            WithdrawSingle::new(1_000, u64::MAX),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InsufficientPoolTokenFunds)
+    );
+}
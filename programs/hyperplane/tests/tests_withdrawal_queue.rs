@@ -0,0 +1,298 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{TradeDirection, INITIAL_SWAP_POOL_AMOUNT},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{RequestWithdraw, Swap},
+    state::WithdrawalTicket,
+    utils::seeds,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signer::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::new_keypair,
+    state, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec},
+};
+
+const INITIAL_POOL_TOKENS: u64 = INITIAL_SWAP_POOL_AMOUNT as u64;
+
+#[tokio::test]
+pub async fn test_withdrawal_queue_claimed_when_liquidity_returns() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1000, 1000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp: PoolUserAccounts = pool.admin.clone().into();
+    let cranker = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::request_withdraw(
+        &mut ctx,
+        &pool,
+        &lp,
+        RequestWithdraw::new(INITIAL_POOL_TOKENS / 2, 400, 400),
+    )
+    .await
+    .unwrap();
+    let (withdrawal_ticket, _bump) =
+        seeds::pda::withdrawal_ticket_pda(&pool.pubkey(), &lp.pubkey());
+    let ticket = state::get::<WithdrawalTicket>(&mut ctx, withdrawal_ticket).await;
+    assert_eq!(ticket.pool, pool.pubkey());
+    assert_eq!(ticket.owner, lp.pubkey());
+    assert_eq!(ticket.pool_token_amount, INITIAL_POOL_TOKENS / 2);
+    let (pool_token_vault, _bump) =
+        seeds::pda::withdrawal_ticket_pool_token_vault_pda(&withdrawal_ticket);
+    assert_eq!(ticket.pool_token_vault, pool_token_vault);
+    // escrowed by the ticket until it is claimed
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool_token_vault).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+
+    // drain token B, the ticket is only worth 250 of it
+    let swapper = setup::new_pool_user(&mut ctx, &pool, (1000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &swapper,
+        TradeDirection::AtoB,
+        Swap::new(1000, 500, None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        client::claim_withdraw(&mut ctx, &pool, &lp, &cranker)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ExceededSlippage)
+    );
+
+    // token B liquidity returns
+    client::swap(
+        &mut ctx,
+        &pool,
+        &swapper,
+        TradeDirection::BtoA,
+        Swap::new(500, 1000, None),
+    )
+    .await
+    .unwrap();
+    client::claim_withdraw(&mut ctx, &pool, &lp, &cranker)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_a_ata).await,
+        500
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_b_ata).await,
+        500
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        INITIAL_POOL_TOKENS / 2
+    );
+    for closed in [withdrawal_ticket, pool_token_vault] {
+        assert!(ctx
+            .context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+pub async fn test_withdrawal_queue_owner_claims_below_minimums() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1000, 1000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp: PoolUserAccounts = pool.admin.clone().into();
+
+    client::request_withdraw(
+        &mut ctx,
+        &pool,
+        &lp,
+        RequestWithdraw::new(INITIAL_POOL_TOKENS / 2, 10_000, 10_000),
+    )
+    .await
+    .unwrap();
+    client::claim_withdraw(&mut ctx, &pool, &lp, &lp.user)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_a_ata).await,
+        500
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_b_ata).await,
+        500
+    );
+}
+
+#[tokio::test]
+pub async fn test_withdrawal_queue_claims_pool_tokens_sent_to_the_ticket_vault() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1000, 1000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp: PoolUserAccounts = pool.admin.clone().into();
+    let cranker = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::request_withdraw(
+        &mut ctx,
+        &pool,
+        &lp,
+        RequestWithdraw::new(INITIAL_POOL_TOKENS / 4, 0, 0),
+    )
+    .await
+    .unwrap();
+    let (withdrawal_ticket, _bump) =
+        seeds::pda::withdrawal_ticket_pda(&pool.pubkey(), &lp.pubkey());
+    let (pool_token_vault, _bump) =
+        seeds::pda::withdrawal_ticket_pool_token_vault_pda(&withdrawal_ticket);
+
+    // a non-empty vault could not be closed
+    send_tx!(
+        ctx,
+        [spl_token_2022::instruction::transfer_checked(
+            &pool.pool_token_program,
+            &lp.pool_token_ata,
+            &pool.pool_token_mint,
+            &pool_token_vault,
+            &lp.pubkey(),
+            &[],
+            INITIAL_POOL_TOKENS / 4,
+            6,
+        )
+        .unwrap()],
+        lp.user.as_ref()
+    )
+    .unwrap();
+    client::claim_withdraw(&mut ctx, &pool, &lp, &cranker)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_a_ata).await,
+        500
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.token_b_ata).await,
+        500
+    );
+    assert!(ctx
+        .context
+        .banks_client
+        .get_account(pool_token_vault)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+pub async fn test_security_claim_withdraw() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(1000, 1000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let lp: PoolUserAccounts = pool.admin.clone().into();
+    let cranker = new_keypair(&mut ctx, Sol::one()).await;
+
+    client::request_withdraw(
+        &mut ctx,
+        &pool,
+        &lp,
+        RequestWithdraw::new(INITIAL_POOL_TOKENS / 2, 0, 0),
+    )
+    .await
+    .unwrap();
+
+    // trading tokens paid to someone else
+    {
+        let other = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+        let mut cloned_lp = lp.clone();
+        cloned_lp.token_a_ata = other.token_a_ata;
+
+        assert_eq!(
+            client::claim_withdraw(&mut ctx, &pool, &cloned_lp, &cranker)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintTokenOwner)
+        );
+    }
+
+    // pool tokens burned out of another account than the ticket vault
+    {
+        let (withdrawal_ticket, _bump) =
+            seeds::pda::withdrawal_ticket_pda(&pool.pubkey(), &lp.pubkey());
+        let (pool_token_vault, _bump) =
+            seeds::pda::withdrawal_ticket_pool_token_vault_pda(&withdrawal_ticket);
+        let mut claim_ix = client::instructions::claim_withdraw(&pool, &lp, &cranker.pubkey());
+        claim_ix
+            .accounts
+            .iter_mut()
+            .filter(|account| account.pubkey == pool_token_vault)
+            .for_each(|account| account.pubkey = lp.pool_token_ata);
+
+        assert_eq!(
+            send_tx!(ctx, [claim_ix], cranker.as_ref())
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+}